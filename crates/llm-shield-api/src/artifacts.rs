@@ -0,0 +1,167 @@
+//! Artifacts fetched from cloud storage
+//!
+//! The model registry, the scanner pipeline and the pipeline's pattern packs
+//! can be referenced as `storage://<key>[#sha256=<hex>]`. They are fetched
+//! from the configured cloud storage into `cloud.artifacts.cache_dir`, so a
+//! fleet of API servers picks up new artifacts published to one bucket.
+
+use crate::config::{AppConfig, ConfigError, Result, ScannersConfig};
+use llm_shield_cloud::{ArtifactCache, ArtifactRef, CloudStorage};
+use llm_shield_models::ModelRegistry;
+use llm_shield_scanners::{parse_params_pack, PipelineConfig};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Create the artifact cache for a storage provider
+pub fn artifact_cache(config: &AppConfig, storage: Arc<dyn CloudStorage>) -> ArtifactCache {
+    ArtifactCache::new(storage, &config.cloud.artifacts.cache_dir)
+}
+
+/// Load the configured model registry from a local file or cloud storage
+pub async fn load_model_registry(
+    config: &AppConfig,
+    artifacts: Option<&ArtifactCache>,
+) -> Result<ModelRegistry> {
+    let path = &config.models.registry_path;
+    match ArtifactRef::parse(path) {
+        Some(reference) => {
+            let artifacts = require_storage(artifacts, path)?;
+            ModelRegistry::from_storage(artifacts, &reference).await
+        }
+        None => ModelRegistry::from_file(path),
+    }
+    .map_err(|e| ConfigError::LoadError(format!("{}: {}", path, e)))
+}
+
+/// Load and validate the configured pipeline, if any
+///
+/// The pipeline file and its `storage://` pattern packs are fetched from
+/// cloud storage; local pipeline files may reference packs in either place.
+pub async fn load_pipeline(
+    config: &ScannersConfig,
+    artifacts: Option<&ArtifactCache>,
+) -> Result<Option<PipelineConfig>> {
+    let Some(path) = &config.pipeline_file else {
+        return Ok(None);
+    };
+    let load_error = |e: String| ConfigError::LoadError(format!("{}: {}", path, e));
+
+    let mut pipeline = match ArtifactRef::parse(path) {
+        Some(reference) => {
+            let text = read_text(require_storage(artifacts, path)?, &reference).await?;
+            PipelineConfig::from_named_str(&reference.key, &text)
+                .map_err(|e| load_error(e.to_string()))?
+        }
+        None => PipelineConfig::from_path(path).map_err(|e| load_error(e.to_string()))?,
+    };
+
+    resolve_params_packs(&mut pipeline, artifacts)
+        .await
+        .map_err(|e| load_error(e.to_string()))?;
+    pipeline.validate().map_err(|e| load_error(e.to_string()))?;
+
+    Ok(Some(pipeline))
+}
+
+/// Merge the `storage://` pattern packs of a pipeline into its scanners
+pub async fn resolve_params_packs(
+    pipeline: &mut PipelineConfig,
+    artifacts: Option<&ArtifactCache>,
+) -> Result<()> {
+    let references: Vec<String> = pipeline
+        .pending_params_from()
+        .into_iter()
+        .filter(|reference| ArtifactRef::is_storage_uri(reference))
+        .map(str::to_string)
+        .collect();
+    if references.is_empty() {
+        return Ok(());
+    }
+
+    let mut packs = HashMap::new();
+    for uri in references {
+        let reference = ArtifactRef::parse(&uri).expect("filtered storage URI");
+        let text = read_text(require_storage(artifacts, &uri)?, &reference).await?;
+        let pack = parse_params_pack(&reference.key, &text)
+            .map_err(|e| ConfigError::LoadError(e.to_string()))?;
+        packs.insert(uri, pack);
+    }
+
+    pipeline
+        .resolve_params_from(|reference| Ok(packs.get(reference).cloned()))
+        .map_err(|e| ConfigError::LoadError(e.to_string()))
+}
+
+async fn read_text(artifacts: &ArtifactCache, reference: &ArtifactRef) -> Result<String> {
+    let bytes = artifacts
+        .read_ref(reference)
+        .await
+        .map_err(|e| ConfigError::LoadError(e.to_string()))?;
+    String::from_utf8(bytes)
+        .map_err(|_| ConfigError::LoadError(format!("{}: not valid UTF-8", reference)))
+}
+
+fn require_storage<'a>(
+    artifacts: Option<&'a ArtifactCache>,
+    uri: &str,
+) -> Result<&'a ArtifactCache> {
+    artifacts.ok_or_else(|| {
+        ConfigError::ValidationError(format!("{} requires a cloud storage provider", uri))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use llm_shield_cloud::LocalStorage;
+
+    #[tokio::test]
+    async fn test_load_pipeline_from_storage() {
+        let dir = std::env::temp_dir().join(format!("llm-shield-artifacts-{}", std::process::id()));
+        let storage = Arc::new(LocalStorage::new(dir.join("bucket")).await.unwrap());
+        let mut config = AppConfig::default();
+        config.cloud.artifacts.cache_dir = dir.join("cache").display().to_string();
+        config.scanners.pipeline_file = Some("storage://pipelines/api.yaml".to_string());
+        let artifacts = artifact_cache(&config, storage.clone());
+
+        storage
+            .put_object(
+                "pipelines/api.yaml",
+                b"input:\n  - type: ban_competitors\n    params_from: [\"storage://packs/competitors.json\"]\n",
+            )
+            .await
+            .unwrap();
+
+        // Missing pack
+        let err = load_pipeline(&config.scanners, Some(&artifacts))
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("packs/competitors.json"),
+            "{}",
+            err
+        );
+
+        storage
+            .put_object("packs/competitors.json", br#"{"competitors": ["Acme"]}"#)
+            .await
+            .unwrap();
+        let pipeline = load_pipeline(&config.scanners, Some(&artifacts))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            pipeline.input[0].params["competitors"],
+            serde_json::json!(["Acme"])
+        );
+        assert!(dir.join("cache/packs/competitors.json").exists());
+
+        // Storage references need a storage provider
+        assert!(matches!(
+            load_pipeline(&config.scanners, None).await,
+            Err(ConfigError::ValidationError(_))
+        ));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
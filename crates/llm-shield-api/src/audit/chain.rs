@@ -0,0 +1,257 @@
+//! Audit entries, hashing and checkpoint signatures
+
+use crate::models::ScannerResult;
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// `prev_hash` of the first entry of a log
+pub const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// Outcome of a scan
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Verdict {
+    /// All scanners passed
+    Allow,
+    /// Text was delivered with detections redacted
+    Redact,
+    /// At least one scanner failed
+    Block,
+}
+
+impl Verdict {
+    /// Verdict of a set of scanner results
+    pub fn of(results: &[ScannerResult]) -> Self {
+        if results.iter().all(|r| r.is_valid) {
+            Verdict::Allow
+        } else {
+            Verdict::Block
+        }
+    }
+}
+
+/// A single scan decision
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditDecision {
+    /// `x-request-id` of the request, or a generated ID
+    pub request_id: String,
+    /// `x-execution-id` of the request
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub execution_id: Option<String>,
+    /// Authenticated API key or gateway caller
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub caller_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tenant_id: Option<String>,
+    /// Scan operation, e.g. `scan_prompt`
+    pub operation: String,
+    /// Scanners that ran
+    pub scanners: Vec<String>,
+    /// Scanners that failed, when known
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub triggered: Vec<String>,
+    pub verdict: Verdict,
+    pub risk_score: f32,
+    /// HMAC-SHA256 of the scanned text under the audit key
+    pub input_hash: String,
+}
+
+impl AuditDecision {
+    /// Summarize scanner results; the verdict is derived from the results
+    pub fn from_results(
+        request_id: impl Into<String>,
+        operation: impl Into<String>,
+        results: &[ScannerResult],
+        input_hash: String,
+    ) -> Self {
+        Self {
+            request_id: request_id.into(),
+            execution_id: None,
+            caller_id: None,
+            tenant_id: None,
+            operation: operation.into(),
+            scanners: results.iter().map(|r| r.scanner.clone()).collect(),
+            triggered: results
+                .iter()
+                .filter(|r| !r.is_valid)
+                .map(|r| r.scanner.clone())
+                .collect(),
+            verdict: Verdict::of(results),
+            risk_score: results.iter().map(|r| r.risk_score).fold(0.0f32, f32::max),
+            input_hash,
+        }
+    }
+}
+
+/// Signed attestation of the chain head
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Checkpoint {
+    /// Decisions recorded since the start of the log
+    pub records: u64,
+    pub key_id: String,
+    /// HMAC-SHA256 over the checkpoint's sequence number and `prev_hash`
+    pub signature: String,
+}
+
+/// Payload of an entry
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AuditEvent {
+    Decision(AuditDecision),
+    Checkpoint(Checkpoint),
+}
+
+/// An entry of the chain
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub seq: u64,
+    pub timestamp: DateTime<Utc>,
+    /// Hash of the previous entry, [`GENESIS_HASH`] for the first one
+    pub prev_hash: String,
+    #[serde(flatten)]
+    pub event: AuditEvent,
+}
+
+/// Stored form of an entry: the entry's JSON and its hash
+#[derive(Debug, Deserialize)]
+pub(crate) struct RawLine<'a> {
+    #[serde(borrow)]
+    pub entry: &'a serde_json::value::RawValue,
+    pub hash: String,
+}
+
+impl AuditEntry {
+    /// Serialize to a log line (without trailing newline), returning the
+    /// line and the entry hash
+    pub fn to_line(&self) -> serde_json::Result<(String, String)> {
+        let entry = serde_json::to_string(self)?;
+        let hash = entry_hash(&entry);
+        Ok((format!(r#"{{"entry":{},"hash":"{}"}}"#, entry, hash), hash))
+    }
+}
+
+/// SHA-256 of an entry's JSON, hex encoded
+pub fn entry_hash(entry_json: &str) -> String {
+    hex::encode(Sha256::digest(entry_json.as_bytes()))
+}
+
+/// Key signing checkpoints and hashing scanned text
+#[derive(Clone)]
+pub struct AuditKey {
+    id: String,
+    secret: Vec<u8>,
+}
+
+impl AuditKey {
+    pub fn new(id: impl Into<String>, secret: impl Into<Vec<u8>>) -> Self {
+        Self {
+            id: id.into(),
+            secret: secret.into(),
+        }
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Keyed hash of scanned text
+    ///
+    /// Keyed so that short inputs cannot be recovered by hashing guesses.
+    pub fn input_hash(&self, text: &str) -> String {
+        self.mac(&[b"input", text.as_bytes()])
+    }
+
+    /// Signature of a checkpoint at `seq` over the chain head `prev_hash`
+    pub fn sign_checkpoint(&self, seq: u64, prev_hash: &str) -> String {
+        self.mac(&[
+            b"checkpoint",
+            seq.to_string().as_bytes(),
+            prev_hash.as_bytes(),
+        ])
+    }
+
+    /// Check a checkpoint signature in constant time
+    pub fn verify_checkpoint(&self, seq: u64, prev_hash: &str, signature: &str) -> bool {
+        let Ok(signature) = hex::decode(signature) else {
+            return false;
+        };
+        self.hmac(&[
+            b"checkpoint",
+            seq.to_string().as_bytes(),
+            prev_hash.as_bytes(),
+        ])
+        .verify_slice(&signature)
+        .is_ok()
+    }
+
+    fn mac(&self, parts: &[&[u8]]) -> String {
+        hex::encode(self.hmac(parts).finalize().into_bytes())
+    }
+
+    fn hmac(&self, parts: &[&[u8]]) -> HmacSha256 {
+        let mut mac =
+            HmacSha256::new_from_slice(&self.secret).expect("HMAC accepts keys of any length");
+        for part in parts {
+            // Length-prefixed so part boundaries are unambiguous
+            mac.update(&(part.len() as u64).to_be_bytes());
+            mac.update(part);
+        }
+        mac
+    }
+}
+
+impl std::fmt::Debug for AuditKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AuditKey")
+            .field("id", &self.id)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entry_line_roundtrip() {
+        let entry = AuditEntry {
+            seq: 1,
+            timestamp: Utc::now(),
+            prev_hash: GENESIS_HASH.to_string(),
+            event: AuditEvent::Decision(AuditDecision::from_results(
+                "req-1",
+                "scan_prompt",
+                &[],
+                "abc".to_string(),
+            )),
+        };
+        let (line, hash) = entry.to_line().unwrap();
+
+        let raw: RawLine = serde_json::from_str(&line).unwrap();
+        assert_eq!(raw.hash, hash);
+        assert_eq!(entry_hash(raw.entry.get()), hash);
+        let parsed: AuditEntry = serde_json::from_str(raw.entry.get()).unwrap();
+        assert_eq!(parsed, entry);
+        assert!(line.contains(r#""type":"decision""#));
+    }
+
+    #[test]
+    fn test_checkpoint_signature() {
+        let key = AuditKey::new("k1", "0123456789abcdef0123456789abcdef");
+        let signature = key.sign_checkpoint(10, "head");
+        assert!(key.verify_checkpoint(10, "head", &signature));
+        assert!(!key.verify_checkpoint(11, "head", &signature));
+        assert!(!key.verify_checkpoint(10, "other", &signature));
+        assert!(!AuditKey::new("k1", "another key").verify_checkpoint(10, "head", &signature));
+
+        assert_eq!(key.input_hash("hello"), key.input_hash("hello"));
+        assert_ne!(
+            key.input_hash("hello"),
+            hex::encode(Sha256::digest(b"hello"))
+        );
+    }
+}
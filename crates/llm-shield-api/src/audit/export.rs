@@ -0,0 +1,85 @@
+//! Export of closed audit segments
+
+use super::log::Segment;
+use super::Result;
+use async_trait::async_trait;
+use std::path::PathBuf;
+
+#[cfg(feature = "cloud")]
+use super::AuditError;
+#[cfg(feature = "cloud")]
+use llm_shield_cloud::CloudStorage;
+#[cfg(feature = "cloud")]
+use std::sync::Arc;
+
+/// Destination for segments closed by a checkpoint
+#[async_trait]
+pub trait AuditExporter: Send + Sync {
+    /// Store a segment; failed segments are retried at the next checkpoint
+    async fn export(&self, segment: &Segment) -> Result<()>;
+
+    /// Name used in logs
+    fn name(&self) -> &str;
+}
+
+/// Writes segments as files into a local directory
+#[derive(Debug, Clone)]
+pub struct DirExporter {
+    dir: PathBuf,
+}
+
+impl DirExporter {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+}
+
+#[async_trait]
+impl AuditExporter for DirExporter {
+    async fn export(&self, segment: &Segment) -> Result<()> {
+        tokio::fs::create_dir_all(&self.dir).await?;
+        // Written under a temporary name so readers never see partial segments
+        let path = self.dir.join(segment.name());
+        let tmp = self.dir.join(format!(".{}.tmp", segment.name()));
+        tokio::fs::write(&tmp, &segment.data).await?;
+        tokio::fs::rename(&tmp, &path).await?;
+        Ok(())
+    }
+
+    fn name(&self) -> &str {
+        "directory"
+    }
+}
+
+/// Uploads segments to cloud storage under a key prefix
+#[cfg(feature = "cloud")]
+pub struct StorageExporter {
+    storage: Arc<dyn CloudStorage>,
+    prefix: String,
+}
+
+#[cfg(feature = "cloud")]
+impl StorageExporter {
+    pub fn new(storage: Arc<dyn CloudStorage>, prefix: impl Into<String>) -> Self {
+        Self {
+            storage,
+            prefix: prefix.into(),
+        }
+    }
+}
+
+#[cfg(feature = "cloud")]
+#[async_trait]
+impl AuditExporter for StorageExporter {
+    async fn export(&self, segment: &Segment) -> Result<()> {
+        let key = format!("{}{}", self.prefix, segment.name());
+        self.storage
+            .put_object(&key, &segment.data)
+            .await
+            .map_err(|e| AuditError::Export(format!("{}: {}", key, e)))
+    }
+
+    fn name(&self) -> &str {
+        "cloud storage"
+    }
+}
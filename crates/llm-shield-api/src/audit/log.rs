@@ -0,0 +1,315 @@
+//! Append-only audit log file
+
+use super::chain::{AuditDecision, AuditEntry, AuditEvent, AuditKey, Checkpoint, GENESIS_HASH};
+use super::verify::Verifier;
+use super::{AuditError, Result};
+use chrono::Utc;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// File name of the log inside the audit directory
+pub const LOG_FILE_NAME: &str = "audit.jsonl";
+
+/// Entries closed by a checkpoint, ready for export
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Segment {
+    pub first_seq: u64,
+    /// Sequence number of the closing checkpoint
+    pub last_seq: u64,
+    /// Log lines, newline terminated
+    pub data: Vec<u8>,
+}
+
+impl Segment {
+    /// Export file name; names sort in sequence order
+    pub fn name(&self) -> String {
+        format!("{:020}-{:020}.jsonl", self.first_seq, self.last_seq)
+    }
+}
+
+/// Hash-chained log of scan decisions
+///
+/// The log is a single JSON Lines file. Opening an existing log verifies its
+/// chain and continues it; a log that fails verification is not appended to.
+#[derive(Debug)]
+pub struct AuditLog {
+    path: PathBuf,
+    file: File,
+    key: AuditKey,
+    next_seq: u64,
+    head: String,
+    /// Decisions since the start of the log
+    records: u64,
+    /// Lines since the last checkpoint
+    pending: Vec<u8>,
+    pending_first_seq: u64,
+    pending_decisions: u64,
+}
+
+impl AuditLog {
+    /// Open or create the log in `dir`
+    pub fn open(dir: impl AsRef<Path>, key: AuditKey) -> Result<Self> {
+        let dir = dir.as_ref();
+        std::fs::create_dir_all(dir)?;
+        let path = dir.join(LOG_FILE_NAME);
+
+        let mut log = Self {
+            file: OpenOptions::new().create(true).append(true).open(&path)?,
+            path,
+            key,
+            next_seq: 1,
+            head: GENESIS_HASH.to_string(),
+            records: 0,
+            pending: Vec::new(),
+            pending_first_seq: 1,
+            pending_decisions: 0,
+        };
+        log.recover()?;
+        Ok(log)
+    }
+
+    /// Path of the log file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn key(&self) -> &AuditKey {
+        &self.key
+    }
+
+    /// Sign checkpoints from now on with `key`
+    pub fn set_key(&mut self, key: AuditKey) {
+        self.key = key;
+    }
+
+    /// Sequence number of the last entry, 0 for an empty log
+    pub fn last_seq(&self) -> u64 {
+        self.next_seq - 1
+    }
+
+    /// Decisions not yet covered by a checkpoint
+    pub fn pending_decisions(&self) -> u64 {
+        self.pending_decisions
+    }
+
+    /// Append a decision, returning its sequence number
+    pub fn append(&mut self, decision: AuditDecision) -> Result<u64> {
+        let seq = self.write(AuditEvent::Decision(decision))?;
+        self.records += 1;
+        self.pending_decisions += 1;
+        Ok(seq)
+    }
+
+    /// Append a signed checkpoint, returning the segment it closes
+    ///
+    /// Returns `None` without writing when no decision was appended since
+    /// the last checkpoint.
+    pub fn checkpoint(&mut self) -> Result<Option<Segment>> {
+        if self.pending_decisions == 0 {
+            return Ok(None);
+        }
+
+        let seq = self.next_seq;
+        let checkpoint = Checkpoint {
+            records: self.records,
+            key_id: self.key.id().to_string(),
+            signature: self.key.sign_checkpoint(seq, &self.head),
+        };
+        self.write(AuditEvent::Checkpoint(checkpoint))?;
+        self.file.sync_data()?;
+
+        let segment = Segment {
+            first_seq: self.pending_first_seq,
+            last_seq: seq,
+            data: std::mem::take(&mut self.pending),
+        };
+        self.pending_first_seq = seq + 1;
+        self.pending_decisions = 0;
+        Ok(Some(segment))
+    }
+
+    fn write(&mut self, event: AuditEvent) -> Result<u64> {
+        let seq = self.next_seq;
+        let entry = AuditEntry {
+            seq,
+            timestamp: Utc::now(),
+            prev_hash: self.head.clone(),
+            event,
+        };
+        let (mut line, hash) = entry.to_line()?;
+        line.push('\n');
+
+        // One write per line, so a crash leaves at most a truncated last line
+        self.file.write_all(line.as_bytes())?;
+
+        self.pending.extend_from_slice(line.as_bytes());
+        self.next_seq += 1;
+        self.head = hash;
+        Ok(seq)
+    }
+
+    /// Verify the existing log and continue its chain
+    fn recover(&mut self) -> Result<()> {
+        let text = std::fs::read_to_string(&self.path)?;
+        if text.is_empty() {
+            return Ok(());
+        }
+        if !text.ends_with('\n') {
+            return Err(AuditError::Corrupted(format!(
+                "{}: last entry is incomplete",
+                self.path.display()
+            )));
+        }
+
+        // Signatures are left to the verification tool: checkpoints signed
+        // before a key rotation must not prevent appending
+        let lines: Vec<&str> = text.lines().collect();
+        let mut verifier = Verifier::new(None);
+        for (i, line) in lines.iter().enumerate() {
+            verifier.push_line(format!("{}:{}", self.path.display(), i + 1), line);
+        }
+        let report = verifier.finish();
+        if let Some(violation) = report.violations.first() {
+            return Err(AuditError::Corrupted(violation.to_string()));
+        }
+        if report.first_seq != Some(1) {
+            return Err(AuditError::Corrupted(format!(
+                "{}: log does not start at the first entry",
+                self.path.display()
+            )));
+        }
+
+        let last_seq = report.last_seq.unwrap_or(0);
+        self.next_seq = last_seq + 1;
+        self.head = report.head.unwrap_or_else(|| GENESIS_HASH.to_string());
+        self.records = report.decisions;
+        self.pending_decisions = report.unsigned_tail;
+        // Decisions after the last checkpoint form the pending segment
+        for line in &lines[lines.len() - report.unsigned_tail as usize..] {
+            self.pending.extend_from_slice(line.as_bytes());
+            self.pending.push(b'\n');
+        }
+        self.pending_first_seq = last_seq + 1 - report.unsigned_tail;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audit::verify::{verify_paths, ViolationKind};
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("llm-shield-audit-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn key() -> AuditKey {
+        AuditKey::new("test", "0123456789abcdef0123456789abcdef")
+    }
+
+    fn decision(request_id: &str) -> AuditDecision {
+        AuditDecision::from_results(request_id, "scan_prompt", &[], key().input_hash("text"))
+    }
+
+    #[test]
+    fn test_append_checkpoint_and_reopen() {
+        let dir = temp_dir("reopen");
+        let mut log = AuditLog::open(&dir, key()).unwrap();
+        assert!(log.checkpoint().unwrap().is_none());
+
+        assert_eq!(log.append(decision("r1")).unwrap(), 1);
+        assert_eq!(log.append(decision("r2")).unwrap(), 2);
+        let segment = log.checkpoint().unwrap().unwrap();
+        assert_eq!((segment.first_seq, segment.last_seq), (1, 3));
+        assert_eq!(
+            segment.name(),
+            "00000000000000000001-00000000000000000003.jsonl"
+        );
+        assert_eq!(segment.data.iter().filter(|b| **b == b'\n').count(), 3);
+        log.append(decision("r3")).unwrap();
+        drop(log);
+
+        // Reopening continues the chain and the pending segment
+        let mut log = AuditLog::open(&dir, key()).unwrap();
+        assert_eq!(log.last_seq(), 4);
+        assert_eq!(log.pending_decisions(), 1);
+        log.append(decision("r4")).unwrap();
+        let segment = log.checkpoint().unwrap().unwrap();
+        assert_eq!((segment.first_seq, segment.last_seq), (4, 6));
+
+        let report = verify_paths(&[log.path().to_path_buf()], Some(&key())).unwrap();
+        assert!(report.is_valid(), "{:?}", report.violations);
+        assert_eq!(report.decisions, 4);
+        assert_eq!(report.verified_checkpoints, 2);
+        assert_eq!(report.unsigned_tail, 0);
+
+        // Segments verify as a sequence too
+        let exported = dir.join("exported");
+        std::fs::create_dir_all(&exported).unwrap();
+        std::fs::write(exported.join(segment.name()), &segment.data).unwrap();
+        let report = verify_paths(&[exported], Some(&key())).unwrap();
+        assert!(report.is_valid(), "{:?}", report.violations);
+        assert_eq!(report.first_seq, Some(4));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_verify_detects_tampering() {
+        let dir = temp_dir("tamper");
+        let mut log = AuditLog::open(&dir, key()).unwrap();
+        for i in 0..4 {
+            log.append(decision(&format!("r{}", i))).unwrap();
+        }
+        log.checkpoint().unwrap();
+        let path = log.path().to_path_buf();
+        drop(log);
+        let original = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = original.lines().collect();
+        let check = |text: String| {
+            std::fs::write(&path, text).unwrap();
+            verify_paths(std::slice::from_ref(&path), Some(&key())).unwrap()
+        };
+
+        // Edited decision
+        let edited = original.replacen(r#""verdict":"allow""#, r#""verdict":"block""#, 1);
+        let report = check(edited);
+        assert_eq!(report.violations[0].kind, ViolationKind::HashMismatch);
+        assert_eq!(report.violations[0].seq, Some(1));
+
+        // Removed decision
+        let mut removed = lines.clone();
+        removed.remove(1);
+        let report = check(removed.join("\n"));
+        assert!(matches!(
+            report.violations[0].kind,
+            ViolationKind::Gap {
+                expected: 2,
+                found: 3
+            }
+        ));
+
+        // Wrong key
+        check(original.clone());
+        let report = verify_paths(
+            std::slice::from_ref(&path),
+            Some(&AuditKey::new("test", "other")),
+        )
+        .unwrap();
+        assert_eq!(report.violations[0].kind, ViolationKind::InvalidSignature);
+
+        // A tampered log is not appended to
+        let edited = original.replacen("r2", "r9", 1);
+        check(edited);
+        assert!(matches!(
+            AuditLog::open(&dir, key()),
+            Err(AuditError::Corrupted(_))
+        ));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,62 @@
+//! Tamper-evident audit log of scan decisions
+//!
+//! Every allow/block decision made by the scan endpoints is appended to a
+//! hash-chained JSON Lines log:
+//!
+//! ```text
+//! {"entry":{"seq":1,"timestamp":"...","prev_hash":"000...","type":"decision",...},"hash":"9f2c..."}
+//! {"entry":{"seq":2,"timestamp":"...","prev_hash":"9f2c...","type":"decision",...},"hash":"41ab..."}
+//! {"entry":{"seq":3,"timestamp":"...","prev_hash":"41ab...","type":"checkpoint",...},"hash":"c07d..."}
+//! ```
+//!
+//! - Each line stores the SHA-256 of its `entry` and each entry the hash of
+//!   the entry before it, so editing, removing or reordering a line breaks
+//!   the chain.
+//! - Checkpoints are appended periodically and carry an HMAC-SHA256
+//!   signature over the chain head, so the chain cannot be rewritten from
+//!   scratch without the signing key.
+//! - Records never contain scanned text, only a keyed hash of it
+//!   ([`AuditKey::input_hash`]).
+//!
+//! Each checkpoint closes a segment, which is handed to the configured
+//! [`AuditExporter`]s (a local directory or cloud storage). The
+//! `llm-shield-audit` binary verifies a log or a directory of exported
+//! segments and reports gaps, edits and invalid signatures.
+
+pub mod chain;
+pub mod export;
+pub mod log;
+pub mod recorder;
+pub mod verify;
+
+pub use chain::{AuditDecision, AuditEntry, AuditEvent, AuditKey, Checkpoint, Verdict};
+#[cfg(feature = "cloud")]
+pub use export::StorageExporter;
+pub use export::{AuditExporter, DirExporter};
+pub use log::{AuditLog, Segment};
+pub use recorder::AuditRecorder;
+pub use verify::{verify_paths, Verifier, VerifyReport, Violation, ViolationKind};
+
+use thiserror::Error;
+
+/// Audit log errors
+#[derive(Debug, Error)]
+pub enum AuditError {
+    #[error("Audit log I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Audit log serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
+
+    #[error("Audit log failed verification: {0}")]
+    Corrupted(String),
+
+    #[error("Audit export failed: {0}")]
+    Export(String),
+
+    #[error("Audit recorder has stopped")]
+    Stopped,
+}
+
+/// Result type for audit operations
+pub type Result<T> = std::result::Result<T, AuditError>;
@@ -0,0 +1,263 @@
+//! Background recording of scan decisions
+
+use super::chain::{AuditDecision, AuditKey};
+use super::export::{AuditExporter, DirExporter};
+use super::log::{AuditLog, Segment};
+use super::{AuditError, Result};
+use crate::config::AuditConfig;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use tokio::sync::{mpsc, oneshot};
+
+/// Segments kept per exporter while it is failing; older ones are dropped
+/// (they remain in the local log)
+const MAX_QUEUED_SEGMENTS: usize = 1024;
+
+enum Command {
+    Record(AuditDecision),
+    Checkpoint(oneshot::Sender<Result<()>>),
+    RotateKey(AuditKey),
+}
+
+/// Handle to the audit log, shared by the request handlers
+///
+/// Decisions are appended by a background task, so recording never waits on
+/// disk. A checkpoint is written every `checkpoint_every` decisions and every
+/// `checkpoint_interval`, and when the last handle is dropped.
+#[derive(Clone)]
+pub struct AuditRecorder {
+    tx: mpsc::UnboundedSender<Command>,
+    key: Arc<RwLock<AuditKey>>,
+}
+
+impl AuditRecorder {
+    /// Start recording into `log`, exporting closed segments to `exporters`
+    pub fn spawn(
+        log: AuditLog,
+        exporters: Vec<Arc<dyn AuditExporter>>,
+        checkpoint_every: u64,
+        checkpoint_interval: Duration,
+    ) -> Self {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let key = Arc::new(RwLock::new(log.key().clone()));
+        let worker = Worker {
+            log,
+            exporters: exporters.into_iter().map(|e| (e, Vec::new())).collect(),
+            checkpoint_every: checkpoint_every.max(1),
+        };
+        tokio::spawn(worker.run(rx, checkpoint_interval));
+        Self { tx, key }
+    }
+
+    /// Open the configured log and start recording
+    ///
+    /// `exporters` are used in addition to `audit.export_dir`. Cloud storage
+    /// export is set up by [`crate::state::AppStateBuilder`].
+    pub fn from_config(
+        config: &AuditConfig,
+        mut exporters: Vec<Arc<dyn AuditExporter>>,
+    ) -> Result<Self> {
+        let secret = config.signing_key.as_deref().unwrap_or_default();
+        let log = AuditLog::open(&config.dir, AuditKey::new(&config.key_id, secret))?;
+        if let Some(dir) = &config.export_dir {
+            exporters.push(Arc::new(DirExporter::new(dir)));
+        }
+        Ok(Self::spawn(
+            log,
+            exporters,
+            config.checkpoint_every,
+            config.checkpoint_interval(),
+        ))
+    }
+
+    /// Keyed hash of scanned text for [`AuditDecision::input_hash`]
+    pub fn input_hash(&self, text: &str) -> String {
+        self.key().input_hash(text)
+    }
+
+    /// Queue a decision; never blocks
+    pub fn record(&self, decision: AuditDecision) {
+        if self.tx.send(Command::Record(decision)).is_err() {
+            tracing::error!("Audit recorder has stopped, scan decision not recorded");
+        }
+    }
+
+    /// Write a checkpoint after all queued decisions and export pending segments
+    pub async fn checkpoint(&self) -> Result<()> {
+        let (reply, done) = oneshot::channel();
+        self.tx
+            .send(Command::Checkpoint(reply))
+            .map_err(|_| AuditError::Stopped)?;
+        done.await.map_err(|_| AuditError::Stopped)?
+    }
+
+    /// Sign with a rotated `secret` under the same key id
+    ///
+    /// Decisions queued before the rotation are checkpointed with the
+    /// previous key, so segments verify with the key that was current when
+    /// they were closed.
+    pub fn rotate_key(&self, secret: &str) -> Result<()> {
+        let key = AuditKey::new(self.key().id(), secret);
+        self.tx
+            .send(Command::RotateKey(key.clone()))
+            .map_err(|_| AuditError::Stopped)?;
+        *self.key.write().unwrap_or_else(|e| e.into_inner()) = key;
+        Ok(())
+    }
+
+    fn key(&self) -> AuditKey {
+        self.key.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+}
+
+impl std::fmt::Debug for AuditRecorder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AuditRecorder")
+            .field("key", &self.key())
+            .finish_non_exhaustive()
+    }
+}
+
+struct Worker {
+    log: AuditLog,
+    /// Exporters with the segments they have not stored yet
+    exporters: Vec<(Arc<dyn AuditExporter>, Vec<Segment>)>,
+    checkpoint_every: u64,
+}
+
+impl Worker {
+    async fn run(mut self, mut rx: mpsc::UnboundedReceiver<Command>, interval: Duration) {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        ticker.tick().await;
+
+        loop {
+            tokio::select! {
+                command = rx.recv() => match command {
+                    Some(Command::Record(decision)) => {
+                        if let Err(e) = self.log.append(decision) {
+                            tracing::error!(error = %e, "Failed to append scan decision to audit log");
+                        } else if self.log.pending_decisions() >= self.checkpoint_every {
+                            self.checkpoint_logged().await;
+                        }
+                    }
+                    Some(Command::Checkpoint(reply)) => {
+                        let _ = reply.send(self.checkpoint().await);
+                    }
+                    Some(Command::RotateKey(key)) => {
+                        self.checkpoint_logged().await;
+                        self.log.set_key(key);
+                    }
+                    None => {
+                        self.checkpoint_logged().await;
+                        return;
+                    }
+                },
+                _ = ticker.tick() => self.checkpoint_logged().await,
+            }
+        }
+    }
+
+    async fn checkpoint_logged(&mut self) {
+        if let Err(e) = self.checkpoint().await {
+            tracing::error!(error = %e, "Audit checkpoint failed");
+        }
+    }
+
+    async fn checkpoint(&mut self) -> Result<()> {
+        if let Some(segment) = self.log.checkpoint()? {
+            for (_, queue) in &mut self.exporters {
+                queue.push(segment.clone());
+            }
+        }
+
+        let mut result = Ok(());
+        for (exporter, queue) in &mut self.exporters {
+            while let Some(segment) = queue.first() {
+                if let Err(e) = exporter.export(segment).await {
+                    tracing::warn!(exporter = exporter.name(), segment = %segment.name(), error = %e, "Audit segment export failed, will retry");
+                    result = Err(e);
+                    break;
+                }
+                queue.remove(0);
+            }
+            if queue.len() > MAX_QUEUED_SEGMENTS {
+                let dropped = queue.len() - MAX_QUEUED_SEGMENTS;
+                queue.drain(..dropped);
+                tracing::error!(
+                    exporter = exporter.name(),
+                    dropped,
+                    "Dropped unexported audit segments"
+                );
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audit::verify::verify_paths;
+
+    #[tokio::test]
+    async fn test_recorder_exports_segments() {
+        let dir =
+            std::env::temp_dir().join(format!("llm-shield-audit-recorder-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let key = AuditKey::new("test", "0123456789abcdef0123456789abcdef");
+        let log = AuditLog::open(dir.join("log"), key.clone()).unwrap();
+        let exporter: Arc<dyn AuditExporter> = Arc::new(DirExporter::new(dir.join("export")));
+        let recorder = AuditRecorder::spawn(log, vec![exporter], 2, Duration::from_secs(3600));
+
+        for i in 0..3 {
+            let decision = AuditDecision::from_results(
+                format!("req-{}", i),
+                "scan_prompt",
+                &[],
+                recorder.input_hash("text"),
+            );
+            recorder.record(decision);
+        }
+        recorder.checkpoint().await.unwrap();
+
+        // One segment closed after two decisions, one by the explicit checkpoint
+        let segments = std::fs::read_dir(dir.join("export")).unwrap().count();
+        assert_eq!(segments, 2);
+        let report = verify_paths(&[dir.join("export")], Some(&key)).unwrap();
+        assert!(report.is_valid(), "{:?}", report.violations);
+        assert_eq!(report.decisions, 3);
+        assert_eq!(report.verified_checkpoints, 2);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_rotated_key_signs_later_checkpoints() {
+        let dir =
+            std::env::temp_dir().join(format!("llm-shield-audit-rotate-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let old = AuditKey::new("test", "0123456789abcdef0123456789abcdef");
+        let new = AuditKey::new("test", "fedcba9876543210fedcba9876543210");
+        let log = AuditLog::open(&dir, old.clone()).unwrap();
+        let recorder = AuditRecorder::spawn(log, Vec::new(), 100, Duration::from_secs(3600));
+        let decision =
+            |id: &str| AuditDecision::from_results(id.to_string(), "scan_prompt", &[], String::new());
+
+        let hash = recorder.input_hash("text");
+        recorder.record(decision("req-1"));
+        recorder.rotate_key("fedcba9876543210fedcba9876543210").unwrap();
+        assert_ne!(recorder.input_hash("text"), hash);
+        recorder.record(decision("req-2"));
+        recorder.checkpoint().await.unwrap();
+
+        // The decision before the rotation was checkpointed with the old key
+        for key in [&old, &new] {
+            let report = verify_paths(std::slice::from_ref(&dir), Some(key)).unwrap();
+            assert_eq!(report.verified_checkpoints, 1);
+            assert_eq!(report.violations.len(), 1, "{:?}", report.violations);
+        }
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
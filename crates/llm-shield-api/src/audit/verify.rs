@@ -0,0 +1,266 @@
+//! Audit log verification
+
+use super::chain::{entry_hash, AuditEntry, AuditEvent, AuditKey, RawLine, GENESIS_HASH};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// What is wrong with an entry
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ViolationKind {
+    /// The line is not a valid entry
+    Malformed(String),
+    /// The entry does not match its hash: it was edited
+    HashMismatch,
+    /// Sequence numbers skip or repeat: entries were removed or inserted
+    Gap { expected: u64, found: u64 },
+    /// `prev_hash` is not the hash of the previous entry
+    BrokenLink,
+    /// The first entry of a complete log does not start the chain
+    BadGenesis,
+    /// The checkpoint signature does not match the chain head
+    InvalidSignature,
+    /// The checkpoint was signed with a different key
+    UnknownKey(String),
+    /// The checkpoint's decision count disagrees with the entries seen
+    CountMismatch { expected: u64, found: u64 },
+}
+
+/// A problem found at a location of the log
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    /// `file:line`
+    pub location: String,
+    pub seq: Option<u64>,
+    pub kind: ViolationKind,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.location)?;
+        if let Some(seq) = self.seq {
+            write!(f, " (seq {})", seq)?;
+        }
+        match &self.kind {
+            ViolationKind::Malformed(e) => write!(f, ": malformed entry: {}", e),
+            ViolationKind::HashMismatch => write!(f, ": entry does not match its hash (edited)"),
+            ViolationKind::Gap { expected, found } => {
+                write!(f, ": expected seq {}, found {} (gap)", expected, found)
+            }
+            ViolationKind::BrokenLink => {
+                write!(f, ": prev_hash does not match the previous entry")
+            }
+            ViolationKind::BadGenesis => write!(f, ": first entry does not start the chain"),
+            ViolationKind::InvalidSignature => write!(f, ": invalid checkpoint signature"),
+            ViolationKind::UnknownKey(id) => {
+                write!(f, ": checkpoint signed with unknown key '{}'", id)
+            }
+            ViolationKind::CountMismatch { expected, found } => write!(
+                f,
+                ": checkpoint counts {} decisions, log has {}",
+                found, expected
+            ),
+        }
+    }
+}
+
+/// Result of verifying a log
+#[derive(Debug, Clone, Default)]
+pub struct VerifyReport {
+    pub first_seq: Option<u64>,
+    pub last_seq: Option<u64>,
+    /// Hash of the last entry
+    pub head: Option<String>,
+    pub decisions: u64,
+    pub checkpoints: u64,
+    /// Checkpoints whose signature was checked
+    pub verified_checkpoints: u64,
+    /// Decisions after the last checkpoint, not yet covered by a signature
+    pub unsigned_tail: u64,
+    pub violations: Vec<Violation>,
+}
+
+impl VerifyReport {
+    /// Whether no violations were found
+    pub fn is_valid(&self) -> bool {
+        self.violations.is_empty()
+    }
+}
+
+/// Incremental verifier fed one line at a time
+///
+/// Without a key, the chain is verified but checkpoint signatures are not.
+#[derive(Debug)]
+pub struct Verifier<'a> {
+    key: Option<&'a AuditKey>,
+    /// Decisions since the start of the log, once known
+    records: Option<u64>,
+    report: VerifyReport,
+}
+
+impl<'a> Verifier<'a> {
+    pub fn new(key: Option<&'a AuditKey>) -> Self {
+        Self {
+            key,
+            records: None,
+            report: VerifyReport::default(),
+        }
+    }
+
+    /// Verify the next line; `location` identifies it in violations
+    pub fn push_line(&mut self, location: impl Into<String>, line: &str) {
+        let location = location.into();
+        let ParsedLine {
+            entry,
+            hash,
+            hash_matches,
+        } = match parse_line(line) {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                self.violation(location, None, ViolationKind::Malformed(e));
+                return;
+            }
+        };
+        let seq = Some(entry.seq);
+
+        if !hash_matches {
+            self.violation(location.clone(), seq, ViolationKind::HashMismatch);
+        }
+
+        match (self.report.last_seq, self.report.head.as_deref()) {
+            (Some(last), Some(head)) => {
+                if entry.seq != last + 1 {
+                    self.violation(
+                        location.clone(),
+                        seq,
+                        ViolationKind::Gap {
+                            expected: last + 1,
+                            found: entry.seq,
+                        },
+                    );
+                    // Counts are unknown across a gap
+                    self.records = None;
+                } else if entry.prev_hash != head {
+                    self.violation(location.clone(), seq, ViolationKind::BrokenLink);
+                }
+            }
+            _ => {
+                self.report.first_seq = Some(entry.seq);
+                if entry.seq == 1 {
+                    if entry.prev_hash != GENESIS_HASH {
+                        self.violation(location.clone(), seq, ViolationKind::BadGenesis);
+                    }
+                    self.records = Some(0);
+                }
+            }
+        }
+
+        match &entry.event {
+            AuditEvent::Decision(_) => {
+                self.report.decisions += 1;
+                self.report.unsigned_tail += 1;
+                self.records = self.records.map(|n| n + 1);
+            }
+            AuditEvent::Checkpoint(checkpoint) => {
+                self.report.checkpoints += 1;
+                self.report.unsigned_tail = 0;
+                match self.records {
+                    Some(expected) if expected != checkpoint.records => self.violation(
+                        location.clone(),
+                        seq,
+                        ViolationKind::CountMismatch {
+                            expected,
+                            found: checkpoint.records,
+                        },
+                    ),
+                    _ => {}
+                }
+                // Later checkpoints are checked against this one
+                self.records = Some(checkpoint.records);
+
+                if let Some(key) = self.key {
+                    if checkpoint.key_id != key.id() {
+                        self.violation(
+                            location.clone(),
+                            seq,
+                            ViolationKind::UnknownKey(checkpoint.key_id.clone()),
+                        );
+                    } else if !key.verify_checkpoint(
+                        entry.seq,
+                        &entry.prev_hash,
+                        &checkpoint.signature,
+                    ) {
+                        self.violation(location.clone(), seq, ViolationKind::InvalidSignature);
+                    } else {
+                        self.report.verified_checkpoints += 1;
+                    }
+                }
+            }
+        }
+
+        self.report.last_seq = Some(entry.seq);
+        self.report.head = Some(hash);
+    }
+
+    /// Verify every line of a log file
+    pub fn push_file(&mut self, path: &Path) -> std::io::Result<()> {
+        let text = std::fs::read_to_string(path)?;
+        for (i, line) in text.lines().enumerate() {
+            if !line.trim().is_empty() {
+                self.push_line(format!("{}:{}", path.display(), i + 1), line);
+            }
+        }
+        Ok(())
+    }
+
+    pub fn finish(self) -> VerifyReport {
+        self.report
+    }
+
+    fn violation(&mut self, location: String, seq: Option<u64>, kind: ViolationKind) {
+        self.report.violations.push(Violation {
+            location,
+            seq,
+            kind,
+        });
+    }
+}
+
+struct ParsedLine {
+    entry: AuditEntry,
+    /// Hash recorded on the line
+    hash: String,
+    hash_matches: bool,
+}
+
+fn parse_line(line: &str) -> Result<ParsedLine, String> {
+    let raw: RawLine = serde_json::from_str(line).map_err(|e| e.to_string())?;
+    let entry = serde_json::from_str(raw.entry.get()).map_err(|e| e.to_string())?;
+    Ok(ParsedLine {
+        entry,
+        hash_matches: entry_hash(raw.entry.get()) == raw.hash,
+        hash: raw.hash,
+    })
+}
+
+/// Verify log files and directories of exported segments, in order
+///
+/// Segments in a directory are verified in file name order, which is
+/// sequence order for exported segments.
+pub fn verify_paths(paths: &[PathBuf], key: Option<&AuditKey>) -> std::io::Result<VerifyReport> {
+    let mut verifier = Verifier::new(key);
+    for path in paths {
+        if path.is_dir() {
+            let mut files: Vec<_> = std::fs::read_dir(path)?
+                .filter_map(|entry| entry.ok().map(|e| e.path()))
+                .filter(|p| p.extension().is_some_and(|ext| ext == "jsonl"))
+                .collect();
+            files.sort();
+            for file in files {
+                verifier.push_file(&file)?;
+            }
+        } else {
+            verifier.push_file(path)?;
+        }
+    }
+    Ok(verifier.finish())
+}
@@ -0,0 +1,104 @@
+//! Verify LLM Shield audit logs
+//!
+//! ```text
+//! llm-shield-audit verify [--key-env VAR] [--key-id ID] <log file or segment dir>...
+//! ```
+//!
+//! Checks the hash chain of the given log files and directories of exported
+//! segments (in order) and, when a key is available, the checkpoint
+//! signatures. Exits with status 1 if any violation is found.
+
+use llm_shield_api::audit::{verify_paths, AuditKey};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+/// Environment variable holding the signing key by default, as read by the API
+const DEFAULT_KEY_ENV: &str = "LLM_SHIELD_API__AUDIT__SIGNING_KEY";
+
+const USAGE: &str = "Usage: llm-shield-audit verify [--key-env VAR] [--key-id ID] <path>...
+
+Verifies audit log files and directories of exported segments.
+
+Options:
+  --key-env VAR   Environment variable holding the signing key
+                  (default: LLM_SHIELD_API__AUDIT__SIGNING_KEY)
+  --key-id ID     Identifier of the signing key (default: default)";
+
+fn main() -> ExitCode {
+    let mut args = std::env::args().skip(1);
+    if args.next().as_deref() != Some("verify") {
+        eprintln!("{}", USAGE);
+        return ExitCode::from(2);
+    }
+
+    let mut key_env = DEFAULT_KEY_ENV.to_string();
+    let mut key_id = "default".to_string();
+    let mut paths = Vec::new();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--key-env" | "--key-id" => {
+                let Some(value) = args.next() else {
+                    eprintln!("{} requires a value\n\n{}", arg, USAGE);
+                    return ExitCode::from(2);
+                };
+                if arg == "--key-env" {
+                    key_env = value;
+                } else {
+                    key_id = value;
+                }
+            }
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return ExitCode::SUCCESS;
+            }
+            _ => paths.push(PathBuf::from(arg)),
+        }
+    }
+    if paths.is_empty() {
+        eprintln!("{}", USAGE);
+        return ExitCode::from(2);
+    }
+
+    let key = std::env::var(&key_env)
+        .ok()
+        .filter(|secret| !secret.is_empty())
+        .map(|secret| AuditKey::new(key_id, secret));
+    if key.is_none() {
+        eprintln!(
+            "warning: {} is not set, checkpoint signatures are not verified",
+            key_env
+        );
+    }
+
+    let report = match verify_paths(&paths, key.as_ref()) {
+        Ok(report) => report,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return ExitCode::from(2);
+        }
+    };
+
+    for violation in &report.violations {
+        println!("{}", violation);
+    }
+    println!(
+        "entries {}..{}: {} decisions, {} checkpoints ({} signatures verified), {} decisions after the last checkpoint",
+        report.first_seq.unwrap_or(0),
+        report.last_seq.unwrap_or(0),
+        report.decisions,
+        report.checkpoints,
+        report.verified_checkpoints,
+        report.unsigned_tail,
+    );
+    if report.first_seq.is_some_and(|seq| seq != 1) {
+        println!("note: the log does not start at the first entry");
+    }
+
+    if report.is_valid() {
+        println!("OK");
+        ExitCode::SUCCESS
+    } else {
+        println!("FAILED: {} violations", report.violations.len());
+        ExitCode::from(1)
+    }
+}
@@ -3,7 +3,7 @@
 #[cfg(feature = "cloud")]
 use crate::config::{AppConfig, CloudProvider};
 #[cfg(feature = "cloud")]
-use llm_shield_cloud::{CloudLogger, CloudMetrics, CloudSecretManager, CloudStorage};
+use llm_shield_cloud::{CloudLogger, CloudMetrics, CloudSecretManager, CloudStorage, CloudTracer};
 #[cfg(feature = "cloud")]
 use std::sync::Arc;
 #[cfg(feature = "cloud")]
@@ -35,6 +35,7 @@ pub struct CloudProviders {
     pub storage: Option<Arc<dyn CloudStorage>>,
     pub metrics: Option<Arc<dyn CloudMetrics>>,
     pub logger: Option<Arc<dyn CloudLogger>>,
+    pub tracer: Option<Arc<dyn CloudTracer>>,
 }
 
 /// Initialize cloud providers based on configuration
@@ -46,13 +47,12 @@ pub async fn initialize_cloud_providers(config: &AppConfig) -> Result<CloudProvi
 
     match config.cloud.provider {
         CloudProvider::None => Err(CloudInitError::NotEnabled),
-        #[cfg(feature = "cloud-aws")]
-        CloudProvider::Aws => initialize_aws(config).await,
-        #[cfg(feature = "cloud-gcp")]
-        CloudProvider::Gcp => initialize_gcp(config).await,
-        #[cfg(feature = "cloud-azure")]
-        CloudProvider::Azure => initialize_azure(config).await,
-        #[cfg(not(any(feature = "cloud-aws", feature = "cloud-gcp", feature = "cloud-azure")))]
+        #[cfg(feature = "cloud-otlp")]
+        CloudProvider::Otlp => initialize_otlp(config),
+        CloudProvider::Local => initialize_local(config).await,
+        #[cfg(feature = "cloud-vault")]
+        CloudProvider::Vault => initialize_vault(config).await,
+        // AWS, GCP and Azure provider SDK features are disabled (see Cargo.toml)
         _ => Err(CloudInitError::UnsupportedProvider(format!(
             "{:?}",
             config.cloud.provider
@@ -60,329 +60,172 @@ pub async fn initialize_cloud_providers(config: &AppConfig) -> Result<CloudProvi
     }
 }
 
-/// Initialize AWS cloud providers
-#[cfg(all(feature = "cloud", feature = "cloud-aws"))]
-async fn initialize_aws(config: &AppConfig) -> Result<CloudProviders> {
-    use llm_shield_cloud_aws::{AwsCloudWatchLogs, AwsCloudWatchMetrics, AwsS3Storage, AwsSecretsManager};
-
-    let aws_config = aws_config::from_env();
-    let aws_config = if let Some(region) = &config.cloud.aws.region {
-        aws_config
-            .region(aws_config::Region::new(region.clone()))
-            .load()
-            .await
-    } else {
-        aws_config.load().await
-    };
-
-    let mut secret_manager = None;
-    let mut storage = None;
-    let mut metrics = None;
-    let mut logger = None;
-
-    // Initialize Secrets Manager
-    if config.cloud.aws.secrets.enabled {
-        secret_manager = Some(
-            Arc::new(
-                AwsSecretsManager::new(aws_config.clone())
-                    .map_err(|e| CloudInitError::InitializationError(e.to_string()))?,
-            ) as Arc<dyn CloudSecretManager>
-        );
-    }
+/// Initialize OpenTelemetry (OTLP) exporters
+#[cfg(feature = "cloud-otlp")]
+fn initialize_otlp(config: &AppConfig) -> Result<CloudProviders> {
+    use crate::config::OtlpProtocol;
+    use llm_shield_cloud_otlp::{OtlpLogger, OtlpMetrics, OtlpTracer};
 
-    // Initialize S3 Storage
-    if config.cloud.aws.storage.enabled {
-        let bucket = config
-            .cloud
-            .aws
-            .storage
-            .bucket
-            .as_ref()
-            .ok_or_else(|| CloudInitError::MissingConfiguration("AWS S3 bucket".to_string()))?;
-
-        storage = Some(
-            Arc::new(
-                AwsS3Storage::new(aws_config.clone(), bucket)
-                    .await
-                    .map_err(|e| CloudInitError::InitializationError(e.to_string()))?,
-            ) as Arc<dyn CloudStorage>
-        );
+    let otlp = &config.cloud.otlp;
+    let endpoint = otlp
+        .endpoint
+        .as_ref()
+        .ok_or_else(|| CloudInitError::MissingConfiguration("OTLP endpoint".to_string()))?;
+
+    let mut exporter_config = llm_shield_cloud::OtlpConfig {
+        endpoint: endpoint.clone(),
+        protocol: match otlp.protocol {
+            OtlpProtocol::Grpc => llm_shield_cloud::OtlpProtocol::Grpc,
+            OtlpProtocol::HttpProtobuf => llm_shield_cloud::OtlpProtocol::HttpProtobuf,
+        },
+        headers: otlp.headers.clone(),
+        ..Default::default()
+    };
+    if let Some(service_name) = &otlp.service_name {
+        exporter_config.service_name = service_name.clone();
     }
 
-    // Initialize CloudWatch Metrics
-    if config.cloud.aws.observability.metrics_enabled {
-        let namespace = config
-            .cloud
-            .aws
-            .observability
-            .namespace
-            .as_ref()
-            .ok_or_else(|| {
-                CloudInitError::MissingConfiguration("AWS CloudWatch namespace".to_string())
-            })?;
-
-        metrics = Some(
-            Arc::new(
-                AwsCloudWatchMetrics::new(aws_config.clone(), namespace)
-                    .await
-                    .map_err(|e| CloudInitError::InitializationError(e.to_string()))?,
-            ) as Arc<dyn CloudMetrics>
-        );
-    }
+    let init_error = |e: llm_shield_cloud::CloudError| CloudInitError::InitializationError(e.to_string());
 
-    // Initialize CloudWatch Logs
-    if config.cloud.aws.observability.logs_enabled {
-        let log_group = config
-            .cloud
-            .aws
-            .observability
-            .log_group
-            .as_ref()
-            .ok_or_else(|| {
-                CloudInitError::MissingConfiguration("AWS CloudWatch log group".to_string())
-            })?;
+    let tracer = if otlp.traces_enabled {
+        Some(Arc::new(OtlpTracer::new(&exporter_config).map_err(init_error)?) as Arc<dyn CloudTracer>)
+    } else {
+        None
+    };
 
-        let log_stream = config
-            .cloud
-            .aws
-            .observability
-            .log_stream
-            .as_ref()
-            .ok_or_else(|| {
-                CloudInitError::MissingConfiguration("AWS CloudWatch log stream".to_string())
-            })?;
+    let metrics = if otlp.metrics_enabled {
+        Some(Arc::new(OtlpMetrics::new(&exporter_config).map_err(init_error)?) as Arc<dyn CloudMetrics>)
+    } else {
+        None
+    };
 
-        logger = Some(
-            Arc::new(
-                AwsCloudWatchLogs::new(aws_config, log_group, log_stream)
-                    .await
-                    .map_err(|e| CloudInitError::InitializationError(e.to_string()))?,
-            ) as Arc<dyn CloudLogger>
-        );
-    }
+    let logger = if otlp.logs_enabled {
+        Some(Arc::new(OtlpLogger::new(&exporter_config).map_err(init_error)?) as Arc<dyn CloudLogger>)
+    } else {
+        None
+    };
 
     Ok(CloudProviders {
-        secret_manager,
-        storage,
+        secret_manager: None,
+        storage: None,
         metrics,
         logger,
+        tracer,
     })
 }
 
-/// Initialize GCP cloud providers
-#[cfg(all(feature = "cloud", feature = "cloud-gcp"))]
-async fn initialize_gcp(config: &AppConfig) -> Result<CloudProviders> {
-    use llm_shield_cloud_gcp::{
-        GcpCloudLogging, GcpCloudMonitoring, GcpCloudStorage, GcpSecretManager,
-    };
+/// Initialize local filesystem providers
+#[cfg(feature = "cloud")]
+async fn initialize_local(config: &AppConfig) -> Result<CloudProviders> {
+    use llm_shield_cloud::{LocalSecretManager, LocalStorage};
 
-    let project_id = config
-        .cloud
-        .gcp
-        .project_id
-        .as_ref()
-        .ok_or_else(|| CloudInitError::MissingConfiguration("GCP project ID".to_string()))?;
+    let local = &config.cloud.local;
+    let init_error = |e: llm_shield_cloud::CloudError| CloudInitError::InitializationError(e.to_string());
 
     let mut secret_manager = None;
     let mut storage = None;
-    let mut metrics = None;
-    let mut logger = None;
-
-    // Initialize Secret Manager
-    if config.cloud.gcp.secrets.enabled {
-        secret_manager = Some(
-            Arc::new(
-                GcpSecretManager::new(project_id)
-                    .await
-                    .map_err(|e| CloudInitError::InitializationError(e.to_string()))?,
-            ) as Arc<dyn CloudSecretManager>
-        );
-    }
 
-    // Initialize Cloud Storage
-    if config.cloud.gcp.storage.enabled {
-        let bucket = config
-            .cloud
-            .gcp
+    // Initialize local storage
+    if local.storage.enabled {
+        let path = local
             .storage
-            .bucket
+            .path
             .as_ref()
-            .ok_or_else(|| CloudInitError::MissingConfiguration("GCP bucket".to_string()))?;
+            .ok_or_else(|| CloudInitError::MissingConfiguration("Local storage path".to_string()))?;
 
         storage = Some(
-            Arc::new(
-                GcpCloudStorage::new(bucket)
-                    .await
-                    .map_err(|e| CloudInitError::InitializationError(e.to_string()))?,
-            ) as Arc<dyn CloudStorage>
+            Arc::new(LocalStorage::new(path).await.map_err(init_error)?) as Arc<dyn CloudStorage>
         );
     }
 
-    // Initialize Cloud Monitoring
-    if config.cloud.gcp.observability.metrics_enabled {
-        metrics = Some(
-            Arc::new(
-                GcpCloudMonitoring::new(project_id)
-                    .await
-                    .map_err(|e| CloudInitError::InitializationError(e.to_string()))?,
-            ) as Arc<dyn CloudMetrics>
-        );
-    }
+    // Initialize local secrets
+    if local.secrets.enabled {
+        let mut secrets = match (&local.secrets.path, &local.secrets.env_prefix) {
+            (Some(path), env_prefix) => {
+                let secrets = LocalSecretManager::new(path).await.map_err(init_error)?;
+                match env_prefix {
+                    Some(prefix) => secrets.with_env_prefix(prefix),
+                    None => secrets,
+                }
+            }
+            (None, Some(prefix)) => LocalSecretManager::from_env(prefix),
+            (None, None) => {
+                return Err(CloudInitError::MissingConfiguration(
+                    "Local secrets path or env prefix".to_string(),
+                ))
+            }
+        };
+
+        if let Some(key_env) = &local.secrets.encryption_key_env {
+            let key = std::env::var(key_env).map_err(|_| {
+                CloudInitError::MissingConfiguration(format!(
+                    "Local secrets encryption key (environment variable {})",
+                    key_env
+                ))
+            })?;
+            secrets = secrets.with_encryption_key_hex(&key).map_err(init_error)?;
+        }
 
-    // Initialize Cloud Logging
-    if config.cloud.gcp.observability.logs_enabled {
-        let log_name = config
-            .cloud
-            .gcp
-            .observability
-            .log_name
-            .as_ref()
-            .ok_or_else(|| CloudInitError::MissingConfiguration("GCP log name".to_string()))?;
-
-        logger = Some(
-            Arc::new(
-                GcpCloudLogging::new(project_id, log_name)
-                    .await
-                    .map_err(|e| CloudInitError::InitializationError(e.to_string()))?,
-            ) as Arc<dyn CloudLogger>
-        );
+        secret_manager = Some(Arc::new(secrets) as Arc<dyn CloudSecretManager>);
     }
 
     Ok(CloudProviders {
         secret_manager,
         storage,
-        metrics,
-        logger,
+        metrics: None,
+        logger: None,
+        tracer: None,
     })
 }
 
-/// Initialize Azure cloud providers
-#[cfg(all(feature = "cloud", feature = "cloud-azure"))]
-async fn initialize_azure(config: &AppConfig) -> Result<CloudProviders> {
-    use llm_shield_cloud_azure::{
-        AzureBlobStorage, AzureKeyVault, AzureMonitorLogs, AzureMonitorMetrics,
-    };
-
-    let mut secret_manager = None;
-    let mut storage = None;
-    let mut metrics = None;
-    let mut logger = None;
-
-    // Initialize Key Vault
-    if config.cloud.azure.secrets.enabled {
-        let vault_url = config
-            .cloud
-            .azure
-            .secrets
-            .vault_url
-            .as_ref()
-            .ok_or_else(|| CloudInitError::MissingConfiguration("Azure vault URL".to_string()))?;
-
-        secret_manager = Some(
-            Arc::new(
-                AzureKeyVault::new(vault_url)
-                    .await
-                    .map_err(|e| CloudInitError::InitializationError(e.to_string()))?,
-            ) as Arc<dyn CloudSecretManager>
-        );
-    }
-
-    // Initialize Blob Storage
-    if config.cloud.azure.storage.enabled {
-        let account_name = config
-            .cloud
-            .azure
-            .storage
-            .account_name
-            .as_ref()
-            .ok_or_else(|| {
-                CloudInitError::MissingConfiguration("Azure storage account".to_string())
-            })?;
+/// Initialize HashiCorp Vault secret manager
+#[cfg(feature = "cloud-vault")]
+async fn initialize_vault(config: &AppConfig) -> Result<CloudProviders> {
+    use crate::config::VaultAuthMethod;
+    use llm_shield_cloud::VaultAuthConfig;
+    use llm_shield_cloud_vault::VaultSecretManager;
 
-        let container_name = config
-            .cloud
-            .azure
-            .storage
-            .container_name
-            .as_ref()
-            .ok_or_else(|| CloudInitError::MissingConfiguration("Azure container".to_string()))?;
-
-        storage = Some(
-            Arc::new(
-                AzureBlobStorage::new(account_name, container_name)
-                    .await
-                    .map_err(|e| CloudInitError::InitializationError(e.to_string()))?,
-            ) as Arc<dyn CloudStorage>
-        );
-    }
-
-    // Initialize Azure Monitor Metrics
-    if config.cloud.azure.observability.metrics_enabled {
-        let resource_id = config
-            .cloud
-            .azure
-            .observability
-            .resource_id
-            .as_ref()
-            .ok_or_else(|| CloudInitError::MissingConfiguration("Azure resource ID".to_string()))?;
+    let vault = &config.cloud.vault;
+    let address = vault
+        .address
+        .as_ref()
+        .ok_or_else(|| CloudInitError::MissingConfiguration("Vault address".to_string()))?;
+
+    let auth = match vault.auth_method {
+        VaultAuthMethod::Token => VaultAuthConfig::Token {
+            token: vault.token.clone(),
+        },
+        VaultAuthMethod::AppRole => VaultAuthConfig::AppRole {
+            mount: vault.approle_mount.clone().unwrap_or_else(|| "approle".to_string()),
+            role_id: vault
+                .role_id
+                .clone()
+                .ok_or_else(|| CloudInitError::MissingConfiguration("Vault role ID".to_string()))?,
+            secret_id: vault.secret_id.clone(),
+        },
+    };
 
-        let region = config
-            .cloud
-            .azure
-            .observability
-            .region
-            .as_ref()
-            .ok_or_else(|| CloudInitError::MissingConfiguration("Azure region".to_string()))?;
-
-        metrics = Some(
-            Arc::new(
-                AzureMonitorMetrics::new(resource_id, region)
-                    .await
-                    .map_err(|e| CloudInitError::InitializationError(e.to_string()))?,
-            ) as Arc<dyn CloudMetrics>
-        );
+    let mut vault_config = llm_shield_cloud::VaultConfig {
+        address: address.clone(),
+        namespace: vault.namespace.clone(),
+        auth,
+        cache_ttl_seconds: vault.cache_ttl_seconds,
+        ..Default::default()
+    };
+    if let Some(mount) = &vault.mount {
+        vault_config.mount = mount.clone();
     }
 
-    // Initialize Azure Monitor Logs
-    if config.cloud.azure.observability.logs_enabled {
-        let workspace_id = config
-            .cloud
-            .azure
-            .observability
-            .workspace_id
-            .as_ref()
-            .ok_or_else(|| CloudInitError::MissingConfiguration("Azure workspace ID".to_string()))?;
-
-        let shared_key = config
-            .cloud
-            .azure
-            .observability
-            .shared_key
-            .as_ref()
-            .ok_or_else(|| CloudInitError::MissingConfiguration("Azure shared key".to_string()))?;
-
-        let log_type = config
-            .cloud
-            .azure
-            .observability
-            .log_type
-            .as_ref()
-            .ok_or_else(|| CloudInitError::MissingConfiguration("Azure log type".to_string()))?;
-
-        logger = Some(
-            Arc::new(
-                AzureMonitorLogs::new(workspace_id, shared_key, log_type)
-                    .await
-                    .map_err(|e| CloudInitError::InitializationError(e.to_string()))?,
-            ) as Arc<dyn CloudLogger>
-        );
-    }
+    let secrets = VaultSecretManager::new(&vault_config)
+        .await
+        .map_err(|e| CloudInitError::InitializationError(e.to_string()))?;
 
     Ok(CloudProviders {
-        secret_manager,
-        storage,
-        metrics,
-        logger,
+        secret_manager: Some(Arc::new(secrets) as Arc<dyn CloudSecretManager>),
+        storage: None,
+        metrics: None,
+        logger: None,
+        tracer: None,
     })
 }
 
@@ -390,7 +233,6 @@ async fn initialize_azure(config: &AppConfig) -> Result<CloudProviders> {
 #[cfg(feature = "cloud")]
 mod tests {
     use super::*;
-    use crate::config::CloudConfig;
 
     #[tokio::test]
     async fn test_cloud_not_enabled() {
@@ -408,4 +250,55 @@ mod tests {
         let result = initialize_cloud_providers(&config).await;
         assert!(matches!(result, Err(CloudInitError::NotEnabled)));
     }
+
+    #[cfg(feature = "cloud-otlp")]
+    #[tokio::test]
+    async fn test_initialize_otlp() {
+        let mut config = AppConfig::default();
+        config.cloud.enabled = true;
+        config.cloud.provider = CloudProvider::Otlp;
+        config.cloud.otlp.traces_enabled = true;
+
+        let result = initialize_cloud_providers(&config).await;
+        assert!(matches!(result, Err(CloudInitError::MissingConfiguration(_))));
+
+        config.cloud.otlp.endpoint = Some("http://localhost:4317".to_string());
+        let providers = initialize_cloud_providers(&config).await.unwrap();
+        assert!(providers.tracer.is_some());
+        assert!(providers.metrics.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_initialize_local() {
+        let dir = std::env::temp_dir().join(format!("llm-shield-local-{}", std::process::id()));
+        let mut config = AppConfig::default();
+        config.cloud.enabled = true;
+        config.cloud.provider = CloudProvider::Local;
+        config.cloud.local.storage.enabled = true;
+        config.cloud.local.storage.path = Some(dir.join("storage").display().to_string());
+        config.cloud.local.secrets.enabled = true;
+        config.cloud.local.secrets.path = Some(dir.join("secrets").display().to_string());
+        config.cloud.local.secrets.encryption_key_env =
+            Some("LLM_SHIELD_TEST_INIT_LOCAL_KEY".to_string());
+
+        // Encryption key variable not set
+        let result = initialize_cloud_providers(&config).await;
+        assert!(matches!(result, Err(CloudInitError::MissingConfiguration(_))));
+
+        std::env::set_var("LLM_SHIELD_TEST_INIT_LOCAL_KEY", "ab".repeat(32));
+        let providers = initialize_cloud_providers(&config).await.unwrap();
+        std::env::remove_var("LLM_SHIELD_TEST_INIT_LOCAL_KEY");
+
+        let storage = providers.storage.unwrap();
+        storage.put_object("models/a.onnx", b"model").await.unwrap();
+        assert_eq!(storage.get_object("models/a.onnx").await.unwrap(), b"model");
+
+        let secrets = providers.secret_manager.unwrap();
+        let value = llm_shield_cloud::SecretValue::from_string("sk-test".to_string());
+        secrets.create_secret("api-key", &value).await.unwrap();
+        assert_eq!(secrets.get_secret("api-key").await.unwrap().as_string(), "sk-test");
+        assert!(providers.metrics.is_none());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
 }
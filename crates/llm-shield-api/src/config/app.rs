@@ -1,6 +1,10 @@
 //! Main application configuration
 
-use super::{AuthConfig, CloudConfig, ConfigError, ObservabilityConfig, RateLimitConfig, Result};
+use super::{
+    parse_secret_uri, AuditConfig, AuthConfig, CloudConfig, ConfigError, ObservabilityConfig,
+    RateLimitConfig, Result, ScannersConfig, SiemConfig, StreamingConfig, TenantsConfig,
+    SECRET_URI_SCHEME, STORAGE_URI_SCHEME,
+};
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
@@ -28,9 +32,55 @@ pub struct AppConfig {
     /// Cloud integration configuration
     #[serde(default)]
     pub cloud: CloudConfig,
+
+    /// Streaming proxy configuration
+    #[serde(default)]
+    pub streaming: StreamingConfig,
+
+    /// Scanner pipeline configuration
+    #[serde(default)]
+    pub scanners: ScannersConfig,
+    /// Audit log of scan decisions
+    #[serde(default)]
+    pub audit: AuditConfig,
+
+    /// Security event emission to a SIEM
+    #[serde(default)]
+    pub siem: SiemConfig,
+
+    /// Per-tenant scanner profiles
+    #[serde(default)]
+    pub tenants: TenantsConfig,
 }
 
 impl AppConfig {
+    /// Values that may be given as `secret://` references, by config path
+    ///
+    /// Scanner parameters of the pipeline may be references too; see
+    /// [`pipeline_secret_fields_mut`](super::pipeline_secret_fields_mut).
+    pub fn secret_fields_mut(&mut self) -> [(&'static str, &mut Option<String>); 4] {
+        [
+            ("auth.gateway_secret", &mut self.auth.gateway_secret),
+            ("streaming.upstream_api_key", &mut self.streaming.upstream_api_key),
+            ("audit.signing_key", &mut self.audit.signing_key),
+            ("siem.webhook.token", &mut self.siem.webhook.token),
+        ]
+    }
+
+    /// `secret://` references among the secret values, by config path
+    pub fn secret_refs(&self) -> Vec<(&'static str, &str)> {
+        [
+            ("auth.gateway_secret", &self.auth.gateway_secret),
+            ("streaming.upstream_api_key", &self.streaming.upstream_api_key),
+            ("audit.signing_key", &self.audit.signing_key),
+            ("siem.webhook.token", &self.siem.webhook.token),
+        ]
+        .into_iter()
+        .filter_map(|(path, value)| Some((path, value.as_deref()?)))
+        .filter(|(_, value)| value.starts_with(SECRET_URI_SCHEME))
+        .collect()
+    }
+
     /// Validate configuration
     pub fn validate(&self) -> Result<()> {
         self.server.validate()?;
@@ -40,6 +90,56 @@ impl AppConfig {
         self.cache.validate()?;
         self.models.validate()?;
         self.cloud.validate()?;
+        self.streaming.validate()?;
+        self.scanners.validate()?;
+        self.audit.validate()?;
+        self.siem.validate()?;
+        self.tenants.validate()?;
+
+        if self.audit.enabled && self.audit.export_to_storage && !self.cloud.enabled {
+            return Err(ConfigError::ValidationError(
+                "Audit export to cloud storage requires cloud integrations".to_string(),
+            ));
+        }
+
+        // secret:// values are resolved through the cloud secret manager
+        for (path, uri) in self.secret_refs() {
+            let Some((provider, _)) = parse_secret_uri(uri) else {
+                return Err(ConfigError::ValidationError(format!(
+                    "{}: expected {}<provider>/<name>",
+                    path, SECRET_URI_SCHEME
+                )));
+            };
+            if !self.cloud.enabled {
+                return Err(ConfigError::ValidationError(format!(
+                    "{} requires a cloud secret manager, but cloud integrations are disabled",
+                    path
+                )));
+            }
+            if provider != self.cloud.provider.as_str() {
+                return Err(ConfigError::ValidationError(format!(
+                    "{} references provider {}, but the configured provider is {}",
+                    path,
+                    provider,
+                    self.cloud.provider.as_str()
+                )));
+            }
+        }
+
+        // storage:// artifacts are fetched through the cloud provider
+        if !self.cloud.enabled {
+            let uris = [Some(&self.models.registry_path), self.scanners.pipeline_file.as_ref()];
+            if let Some(uri) = uris
+                .into_iter()
+                .flatten()
+                .find(|uri| uri.starts_with(STORAGE_URI_SCHEME))
+            {
+                return Err(ConfigError::ValidationError(format!(
+                    "{} requires cloud storage, but cloud integrations are disabled",
+                    uri
+                )));
+            }
+        }
         Ok(())
     }
 }
@@ -54,6 +154,11 @@ impl Default for AppConfig {
             cache: CacheConfig::default(),
             models: ModelsConfig::default(),
             cloud: CloudConfig::default(),
+            streaming: StreamingConfig::default(),
+            scanners: ScannersConfig::default(),
+            audit: AuditConfig::default(),
+            siem: SiemConfig::default(),
+            tenants: TenantsConfig::default(),
         }
     }
 }
@@ -190,7 +295,8 @@ impl Default for CacheConfig {
 /// Models configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ModelsConfig {
-    /// Path to model registry file
+    /// Path to model registry file, or `storage://<key>[#sha256=<hex>]`
+    /// to fetch it from cloud storage
     #[serde(default = "default_model_registry")]
     pub registry_path: String,
 
@@ -346,4 +452,44 @@ mod tests {
         let config = AppConfig::default();
         assert!(config.validate().is_ok());
     }
+
+    #[test]
+    fn test_app_config_storage_requires_cloud() {
+        let mut config = AppConfig::default();
+        config.models.registry_path = "storage://models/registry.json".to_string();
+        assert!(config.validate().is_err());
+
+        config.models.registry_path = "models/registry.json".to_string();
+        config.scanners.pipeline_file = Some("storage://pipelines/api.yaml".to_string());
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_app_config_secret_refs() {
+        let mut config = AppConfig::default();
+        config.auth.gateway_secret = Some("secret://local/gateway".to_string());
+        config.streaming.upstream_api_key = Some("sk-plain".to_string());
+        assert_eq!(
+            config.secret_refs(),
+            vec![("auth.gateway_secret", "secret://local/gateway")]
+        );
+        // Cloud integrations disabled
+        assert!(config.validate().is_err());
+
+        config.cloud.enabled = true;
+        config.cloud.provider = crate::config::CloudProvider::Local;
+        config.cloud.local.secrets.enabled = true;
+        config.cloud.local.secrets.env_prefix = Some("LLM_SHIELD_SECRET_".to_string());
+        assert!(config.validate().is_ok());
+
+        // Audit key length is checked once resolved
+        config.audit.enabled = true;
+        config.audit.signing_key = Some("secret://local/audit-key".to_string());
+        assert!(config.validate().is_ok());
+
+        config.auth.gateway_secret = Some("secret://aws/prod/gateway".to_string());
+        assert!(config.validate().is_err());
+        config.auth.gateway_secret = Some("secret://local".to_string());
+        assert!(config.validate().is_err());
+    }
 }
@@ -0,0 +1,153 @@
+//! Audit log configuration
+
+use super::{ConfigError, Result, SECRET_URI_SCHEME};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Minimum length of the checkpoint signing key in bytes
+const MIN_SIGNING_KEY_LEN: usize = 32;
+
+/// Configuration for the audit log of scan decisions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditConfig {
+    /// Record scan decisions
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Directory holding the log
+    #[serde(default = "default_dir")]
+    pub dir: String,
+
+    /// Secret signing checkpoints and hashing scanned text
+    #[serde(default, skip_serializing)]
+    pub signing_key: Option<String>,
+
+    /// Identifier of the signing key, recorded in checkpoints
+    #[serde(default = "default_key_id")]
+    pub key_id: String,
+
+    /// Write a checkpoint after this many decisions
+    #[serde(default = "default_checkpoint_every")]
+    pub checkpoint_every: u64,
+
+    /// Write a checkpoint at least this often, in seconds
+    #[serde(default = "default_checkpoint_interval_secs")]
+    pub checkpoint_interval_secs: u64,
+
+    /// Directory receiving a copy of each closed segment
+    #[serde(default)]
+    pub export_dir: Option<String>,
+
+    /// Upload closed segments to the configured cloud storage
+    #[serde(default)]
+    pub export_to_storage: bool,
+
+    /// Key prefix of segments uploaded to cloud storage
+    #[serde(default = "default_export_prefix")]
+    pub export_prefix: String,
+}
+
+impl AuditConfig {
+    /// Get checkpoint interval
+    pub fn checkpoint_interval(&self) -> Duration {
+        Duration::from_secs(self.checkpoint_interval_secs)
+    }
+
+    /// Validate audit configuration
+    pub fn validate(&self) -> Result<()> {
+        if !self.enabled {
+            return Ok(());
+        }
+
+        match &self.signing_key {
+            // Checked again once the reference is resolved
+            Some(key) if key.starts_with(SECRET_URI_SCHEME) => {}
+            Some(key) if key.len() >= MIN_SIGNING_KEY_LEN => {}
+            _ => {
+                return Err(ConfigError::ValidationError(format!(
+                    "Audit signing key must be at least {} bytes",
+                    MIN_SIGNING_KEY_LEN
+                )))
+            }
+        }
+
+        if self.dir.is_empty() {
+            return Err(ConfigError::ValidationError(
+                "Audit log directory cannot be empty".to_string(),
+            ));
+        }
+
+        if self.key_id.is_empty() {
+            return Err(ConfigError::ValidationError(
+                "Audit key ID cannot be empty".to_string(),
+            ));
+        }
+
+        if self.checkpoint_every == 0 || self.checkpoint_interval_secs == 0 {
+            return Err(ConfigError::ValidationError(
+                "Audit checkpoint frequency must be greater than 0".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for AuditConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            dir: default_dir(),
+            signing_key: None,
+            key_id: default_key_id(),
+            checkpoint_every: default_checkpoint_every(),
+            checkpoint_interval_secs: default_checkpoint_interval_secs(),
+            export_dir: None,
+            export_to_storage: false,
+            export_prefix: default_export_prefix(),
+        }
+    }
+}
+
+fn default_dir() -> String {
+    "./data/audit".to_string()
+}
+
+fn default_key_id() -> String {
+    "default".to_string()
+}
+
+fn default_checkpoint_every() -> u64 {
+    1000
+}
+
+fn default_checkpoint_interval_secs() -> u64 {
+    300 // 5 minutes
+}
+
+fn default_export_prefix() -> String {
+    "audit/".to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_audit_config_validation() {
+        let mut config = AuditConfig::default();
+        assert!(config.validate().is_ok());
+
+        config.enabled = true;
+        assert!(config.validate().is_err());
+
+        config.signing_key = Some("too-short".to_string());
+        assert!(config.validate().is_err());
+
+        config.signing_key = Some("0123456789abcdef0123456789abcdef".to_string());
+        assert!(config.validate().is_ok());
+
+        config.checkpoint_every = 0;
+        assert!(config.validate().is_err());
+    }
+}
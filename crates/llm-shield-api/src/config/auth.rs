@@ -17,6 +17,11 @@ pub struct AuthConfig {
     /// Path to API keys file (for file backend)
     #[serde(default = "default_keys_file")]
     pub keys_file: String,
+
+    /// Shared secret validating LLM-Security-Core caller tokens (default:
+    /// `GATEWAY_SHARED_SECRET` environment variable)
+    #[serde(default, skip_serializing)]
+    pub gateway_secret: Option<String>,
 }
 
 impl AuthConfig {
@@ -45,6 +50,7 @@ impl Default for AuthConfig {
             enabled: default_auth_enabled(),
             storage_backend: default_storage_backend(),
             keys_file: default_keys_file(),
+            gateway_secret: None,
         }
     }
 }
@@ -95,6 +101,7 @@ mod tests {
             enabled: true,
             storage_backend: StorageBackend::File,
             keys_file: String::new(),
+            gateway_secret: None,
         };
         assert!(config.validate().is_err());
 
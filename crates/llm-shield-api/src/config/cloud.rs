@@ -2,6 +2,7 @@
 
 use super::{ConfigError, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 /// Cloud provider configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -10,7 +11,7 @@ pub struct CloudConfig {
     #[serde(default)]
     pub enabled: bool,
 
-    /// Cloud provider (aws, gcp, azure)
+    /// Cloud provider (aws, gcp, azure, otlp, local, vault)
     #[serde(default)]
     pub provider: CloudProvider,
 
@@ -25,6 +26,26 @@ pub struct CloudConfig {
     /// Azure configuration
     #[serde(default)]
     pub azure: AzureConfig,
+
+    /// OpenTelemetry (OTLP) configuration
+    #[serde(default)]
+    pub otlp: OtlpConfig,
+
+    /// Local filesystem configuration
+    #[serde(default)]
+    pub local: LocalConfig,
+
+    /// HashiCorp Vault configuration
+    #[serde(default)]
+    pub vault: VaultConfig,
+
+    /// Cache for artifacts fetched from cloud storage
+    #[serde(default)]
+    pub artifacts: ArtifactsConfig,
+
+    /// Resolution of `secret://` configuration values
+    #[serde(default)]
+    pub secret_refs: SecretRefsConfig,
 }
 
 impl CloudConfig {
@@ -34,10 +55,16 @@ impl CloudConfig {
             return Ok(());
         }
 
+        self.artifacts.validate()?;
+        self.secret_refs.validate()?;
+
         match self.provider {
             CloudProvider::Aws => self.aws.validate()?,
             CloudProvider::Gcp => self.gcp.validate()?,
             CloudProvider::Azure => self.azure.validate()?,
+            CloudProvider::Otlp => self.otlp.validate()?,
+            CloudProvider::Local => self.local.validate()?,
+            CloudProvider::Vault => self.vault.validate()?,
             CloudProvider::None => {
                 return Err(ConfigError::ValidationError(
                     "Cloud enabled but no provider specified".to_string(),
@@ -57,6 +84,11 @@ impl Default for CloudConfig {
             aws: AwsConfig::default(),
             gcp: GcpConfig::default(),
             azure: AzureConfig::default(),
+            otlp: OtlpConfig::default(),
+            local: LocalConfig::default(),
+            vault: VaultConfig::default(),
+            artifacts: ArtifactsConfig::default(),
+            secret_refs: SecretRefsConfig::default(),
         }
     }
 }
@@ -70,6 +102,24 @@ pub enum CloudProvider {
     Aws,
     Gcp,
     Azure,
+    Otlp,
+    Local,
+    Vault,
+}
+
+impl CloudProvider {
+    /// Configuration name, as used in `secret://<provider>/...` references
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CloudProvider::None => "none",
+            CloudProvider::Aws => "aws",
+            CloudProvider::Gcp => "gcp",
+            CloudProvider::Azure => "azure",
+            CloudProvider::Otlp => "otlp",
+            CloudProvider::Local => "local",
+            CloudProvider::Vault => "vault",
+        }
+    }
 }
 
 /// AWS configuration
@@ -292,7 +342,7 @@ impl GcpStorageConfig {
     }
 }
 
-/// GCP Cloud Monitoring/Logging configuration
+/// GCP Cloud Monitoring/Logging/Trace configuration
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct GcpObservabilityConfig {
     /// Enable Cloud Monitoring metrics
@@ -303,6 +353,10 @@ pub struct GcpObservabilityConfig {
     #[serde(default)]
     pub logs_enabled: bool,
 
+    /// Enable Cloud Trace
+    #[serde(default)]
+    pub traces_enabled: bool,
+
     /// Log name for Cloud Logging
     pub log_name: Option<String>,
 }
@@ -429,20 +483,27 @@ pub struct AzureObservabilityConfig {
     #[serde(default)]
     pub logs_enabled: bool,
 
+    /// Enable Application Insights traces
+    #[serde(default)]
+    pub traces_enabled: bool,
+
     /// Azure Monitor resource ID
     pub resource_id: Option<String>,
 
     /// Azure region
     pub region: Option<String>,
 
-    /// Log Analytics workspace ID
-    pub workspace_id: Option<String>,
+    /// Logs ingestion URL of the data collection endpoint
+    pub logs_endpoint: Option<String>,
 
-    /// Log Analytics shared key
-    pub shared_key: Option<String>,
+    /// Immutable ID of the data collection rule
+    pub logs_rule_id: Option<String>,
 
-    /// Log type for Log Analytics
-    pub log_type: Option<String>,
+    /// Data collection rule stream receiving the logs
+    pub logs_stream: Option<String>,
+
+    /// Application Insights connection string
+    pub connection_string: Option<String>,
 }
 
 impl AzureObservabilityConfig {
@@ -462,29 +523,342 @@ impl AzureObservabilityConfig {
         }
 
         if self.logs_enabled {
-            if self.workspace_id.is_none() {
+            if self.logs_endpoint.is_none() {
                 return Err(ConfigError::ValidationError(
-                    "Azure Log Analytics workspace ID must be specified when logs enabled"
+                    "Azure data collection endpoint must be specified when logs enabled"
                         .to_string(),
                 ));
             }
-            if self.shared_key.is_none() {
+            if self.logs_rule_id.is_none() {
                 return Err(ConfigError::ValidationError(
-                    "Azure Log Analytics shared key must be specified when logs enabled"
+                    "Azure data collection rule ID must be specified when logs enabled"
                         .to_string(),
                 ));
             }
-            if self.log_type.is_none() {
+            if self.logs_stream.is_none() {
                 return Err(ConfigError::ValidationError(
-                    "Azure log type must be specified when logs enabled".to_string(),
+                    "Azure data collection rule stream must be specified when logs enabled"
+                        .to_string(),
                 ));
             }
         }
 
+        if self.traces_enabled && self.connection_string.is_none() {
+            return Err(ConfigError::ValidationError(
+                "Application Insights connection string must be specified when traces enabled"
+                    .to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// OTLP transport protocol
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub enum OtlpProtocol {
+    /// Protobuf over gRPC
+    #[default]
+    #[serde(rename = "grpc")]
+    Grpc,
+    /// Protobuf over HTTP
+    #[serde(rename = "http/protobuf")]
+    HttpProtobuf,
+}
+
+/// OpenTelemetry (OTLP) exporter configuration
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct OtlpConfig {
+    /// Collector endpoint (e.g., http://otel-collector:4317)
+    pub endpoint: Option<String>,
+
+    /// Transport protocol (grpc, http/protobuf)
+    #[serde(default)]
+    pub protocol: OtlpProtocol,
+
+    /// Headers sent with every export (e.g., collector authentication)
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+
+    /// Service name reported to the collector (default: llm-shield)
+    pub service_name: Option<String>,
+
+    /// Export execution spans as traces
+    #[serde(default)]
+    pub traces_enabled: bool,
+
+    /// Export metrics
+    #[serde(default)]
+    pub metrics_enabled: bool,
+
+    /// Export logs
+    #[serde(default)]
+    pub logs_enabled: bool,
+}
+
+impl OtlpConfig {
+    fn validate(&self) -> Result<()> {
+        if !(self.traces_enabled || self.metrics_enabled || self.logs_enabled) {
+            return Ok(());
+        }
+
+        match &self.endpoint {
+            None => Err(ConfigError::ValidationError(
+                "OTLP endpoint must be specified when traces, metrics or logs enabled".to_string(),
+            )),
+            Some(endpoint)
+                if !(endpoint.starts_with("http://") || endpoint.starts_with("https://")) =>
+            {
+                Err(ConfigError::ValidationError(format!(
+                    "OTLP endpoint must be an http(s) URL: {}",
+                    endpoint
+                )))
+            }
+            Some(_) => Ok(()),
+        }
+    }
+}
+
+/// Local filesystem configuration (on-premises deployments, tests)
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct LocalConfig {
+    /// Directory-backed storage configuration
+    #[serde(default)]
+    pub storage: LocalStorageConfig,
+
+    /// File/environment-backed secrets configuration
+    #[serde(default)]
+    pub secrets: LocalSecretsConfig,
+}
+
+impl LocalConfig {
+    fn validate(&self) -> Result<()> {
+        self.storage.validate()?;
+        self.secrets.validate()?;
+        Ok(())
+    }
+}
+
+/// Local storage configuration
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct LocalStorageConfig {
+    /// Enable local storage
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Root directory for stored objects
+    pub path: Option<String>,
+}
+
+impl LocalStorageConfig {
+    fn validate(&self) -> Result<()> {
+        if self.enabled && self.path.is_none() {
+            return Err(ConfigError::ValidationError(
+                "Local storage path must be specified when enabled".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Local secrets configuration
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct LocalSecretsConfig {
+    /// Enable local secrets
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Directory holding one file per secret
+    pub path: Option<String>,
+
+    /// Prefix of environment variables providing secrets (e.g., LLM_SHIELD_SECRET_)
+    pub env_prefix: Option<String>,
+
+    /// Environment variable holding the hex-encoded 256-bit key encrypting secret files
+    pub encryption_key_env: Option<String>,
+}
+
+impl LocalSecretsConfig {
+    fn validate(&self) -> Result<()> {
+        if !self.enabled {
+            return Ok(());
+        }
+        if self.path.is_none() && self.env_prefix.is_none() {
+            return Err(ConfigError::ValidationError(
+                "Local secrets path or env prefix must be specified when enabled".to_string(),
+            ));
+        }
+        if self.encryption_key_env.is_some() && self.path.is_none() {
+            return Err(ConfigError::ValidationError(
+                "Local secrets encryption requires a secrets path".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Vault authentication method
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum VaultAuthMethod {
+    /// Static token (falls back to VAULT_TOKEN)
+    #[default]
+    Token,
+    /// AppRole role ID and secret ID
+    AppRole,
+}
+
+/// HashiCorp Vault configuration (KV version 2 secrets)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VaultConfig {
+    /// Vault server address (e.g., https://vault.internal:8200)
+    pub address: Option<String>,
+
+    /// Mount path of the KV v2 secrets engine (default: secret)
+    pub mount: Option<String>,
+
+    /// Vault Enterprise namespace
+    pub namespace: Option<String>,
+
+    /// Authentication method (token, approle)
+    #[serde(default)]
+    pub auth_method: VaultAuthMethod,
+
+    /// Token for token auth (default: VAULT_TOKEN environment variable)
+    pub token: Option<String>,
+
+    /// AppRole role ID
+    pub role_id: Option<String>,
+
+    /// AppRole secret ID (default: VAULT_SECRET_ID environment variable)
+    pub secret_id: Option<String>,
+
+    /// Mount path of the AppRole auth method (default: approle)
+    pub approle_mount: Option<String>,
+
+    /// Secret cache TTL in seconds
+    #[serde(default = "default_cache_ttl")]
+    pub cache_ttl_seconds: u64,
+}
+
+impl Default for VaultConfig {
+    fn default() -> Self {
+        Self {
+            address: None,
+            mount: None,
+            namespace: None,
+            auth_method: VaultAuthMethod::Token,
+            token: None,
+            role_id: None,
+            secret_id: None,
+            approle_mount: None,
+            cache_ttl_seconds: default_cache_ttl(),
+        }
+    }
+}
+
+impl VaultConfig {
+    fn validate(&self) -> Result<()> {
+        match &self.address {
+            None => {
+                return Err(ConfigError::ValidationError(
+                    "Vault address must be specified".to_string(),
+                ))
+            }
+            Some(address)
+                if !(address.starts_with("http://") || address.starts_with("https://")) =>
+            {
+                return Err(ConfigError::ValidationError(format!(
+                    "Vault address must be an http(s) URL: {}",
+                    address
+                )))
+            }
+            Some(_) => {}
+        }
+
+        if self.auth_method == VaultAuthMethod::AppRole && self.role_id.is_none() {
+            return Err(ConfigError::ValidationError(
+                "Vault role ID must be specified for AppRole auth".to_string(),
+            ));
+        }
+
+        if self.cache_ttl_seconds == 0 {
+            return Err(ConfigError::ValidationError(
+                "Vault secrets cache TTL must be greater than 0".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// Cache for artifacts (model registry, scanner pipeline, pattern packs)
+/// referenced as `storage://<key>`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArtifactsConfig {
+    /// Local directory holding fetched artifacts
+    #[serde(default = "default_artifacts_cache_dir")]
+    pub cache_dir: String,
+}
+
+impl ArtifactsConfig {
+    fn validate(&self) -> Result<()> {
+        if self.cache_dir.is_empty() {
+            return Err(ConfigError::ValidationError(
+                "Artifact cache directory cannot be empty".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl Default for ArtifactsConfig {
+    fn default() -> Self {
+        Self {
+            cache_dir: default_artifacts_cache_dir(),
+        }
+    }
+}
+
+/// Resolution of `secret://` configuration values
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SecretRefsConfig {
+    /// How often references are re-read and rotations applied, in seconds
+    ///
+    /// Values are cached by the secret manager (e.g. `cloud.vault.cache_ttl_seconds`),
+    /// not in addition to it.
+    #[serde(default = "default_cache_ttl")]
+    pub ttl_secs: u64,
+}
+
+impl SecretRefsConfig {
+    /// Get refresh interval
+    pub fn ttl(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.ttl_secs)
+    }
+
+    fn validate(&self) -> Result<()> {
+        if self.ttl_secs == 0 {
+            return Err(ConfigError::ValidationError(
+                "Secret reference TTL must be greater than 0".to_string(),
+            ));
+        }
         Ok(())
     }
 }
 
+impl Default for SecretRefsConfig {
+    fn default() -> Self {
+        Self {
+            ttl_secs: default_cache_ttl(),
+        }
+    }
+}
+
+fn default_artifacts_cache_dir() -> String {
+    "./data/artifacts".to_string()
+}
+
 fn default_cache_ttl() -> u64 {
     300 // 5 minutes
 }
@@ -575,4 +949,105 @@ mod tests {
         config.storage.container_name = Some("models".to_string());
         assert!(config.validate().is_ok());
     }
+
+    #[test]
+    fn test_azure_logs_need_collection_rule() {
+        let mut config = AzureObservabilityConfig {
+            logs_enabled: true,
+            logs_endpoint: Some("https://dce.eastus-1.ingest.monitor.azure.com".to_string()),
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+
+        config.logs_rule_id = Some("dcr-00000000000000000000000000000000".to_string());
+        assert!(config.validate().is_err());
+
+        config.logs_stream = Some("Custom-LLMShieldLog_CL".to_string());
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_azure_traces_need_connection_string() {
+        let mut config = AzureObservabilityConfig {
+            traces_enabled: true,
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+
+        config.connection_string =
+            Some("InstrumentationKey=00000000-0000-0000-0000-000000000000".to_string());
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_otlp_config_validation() {
+        let mut config: CloudConfig = serde_json::from_value(serde_json::json!({
+            "enabled": true,
+            "provider": "otlp",
+            "otlp": { "protocol": "http/protobuf", "traces_enabled": true }
+        }))
+        .unwrap();
+        assert_eq!(config.provider, CloudProvider::Otlp);
+        assert_eq!(config.otlp.protocol, OtlpProtocol::HttpProtobuf);
+
+        // Traces enabled but no endpoint
+        assert!(config.validate().is_err());
+
+        config.otlp.endpoint = Some("otel-collector:4318".to_string());
+        assert!(config.validate().is_err());
+
+        config.otlp.endpoint = Some("http://otel-collector:4318".to_string());
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_local_config_validation() {
+        let mut config: CloudConfig = serde_json::from_value(serde_json::json!({
+            "enabled": true,
+            "provider": "local",
+            "local": {
+                "storage": { "enabled": true },
+                "secrets": { "enabled": true, "encryption_key_env": "LLM_SHIELD_SECRETS_KEY" }
+            }
+        }))
+        .unwrap();
+        assert_eq!(config.provider, CloudProvider::Local);
+
+        // Storage enabled but no path
+        assert!(config.validate().is_err());
+        config.local.storage.path = Some("/var/lib/llm-shield/storage".to_string());
+
+        // Encryption needs a secrets directory, env vars alone are not enough
+        config.local.secrets.env_prefix = Some("LLM_SHIELD_SECRET_".to_string());
+        assert!(config.validate().is_err());
+
+        config.local.secrets.path = Some("/etc/llm-shield/secrets".to_string());
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_vault_config_validation() {
+        let mut config: CloudConfig = serde_json::from_value(serde_json::json!({
+            "enabled": true,
+            "provider": "vault",
+            "vault": { "auth_method": "approle" }
+        }))
+        .unwrap();
+        assert_eq!(config.provider, CloudProvider::Vault);
+        assert_eq!(config.vault.auth_method, VaultAuthMethod::AppRole);
+        assert_eq!(config.vault.cache_ttl_seconds, 300);
+
+        // No address
+        assert!(config.validate().is_err());
+
+        config.vault.address = Some("vault.internal:8200".to_string());
+        assert!(config.validate().is_err());
+        config.vault.address = Some("https://vault.internal:8200".to_string());
+
+        // AppRole without role ID
+        assert!(config.validate().is_err());
+
+        config.vault.role_id = Some("llm-shield".to_string());
+        assert!(config.validate().is_ok());
+    }
 }
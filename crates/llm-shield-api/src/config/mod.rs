@@ -1,20 +1,47 @@
 //! Configuration management for the API server
 
 pub mod app;
+pub mod audit;
 pub mod auth;
 pub mod cloud;
 pub mod observability;
 pub mod rate_limit;
+pub mod scanners;
+pub mod siem;
+pub mod streaming;
+pub mod tenants;
 
 pub use app::AppConfig;
+pub use audit::AuditConfig;
 pub use auth::AuthConfig;
-pub use cloud::CloudConfig;
+pub use cloud::{CloudConfig, CloudProvider, OtlpProtocol, VaultAuthMethod};
 pub use observability::ObservabilityConfig;
 pub use rate_limit::{RateLimitConfig, RateLimitTier};
+pub use scanners::{pipeline_secret_fields_mut, ScannersConfig};
+pub use siem::{SiemConfig, SiemFormat, SiemTransportKind, SyslogProtocol};
+pub use streaming::StreamingConfig;
+pub use tenants::TenantsConfig;
 
 use std::path::Path;
 use thiserror::Error;
 
+/// Scheme of artifacts (model registry, pipeline, pattern packs) fetched from
+/// the configured cloud storage
+pub const STORAGE_URI_SCHEME: &str = "storage://";
+
+/// Scheme of configuration values resolved through the configured cloud
+/// secret manager: `secret://<provider>/<name>`
+pub const SECRET_URI_SCHEME: &str = "secret://";
+
+/// Split a `secret://<provider>/<name>` reference into provider and secret
+/// name; `None` for values that are not well-formed references
+pub fn parse_secret_uri(value: &str) -> Option<(&str, &str)> {
+    value
+        .strip_prefix(SECRET_URI_SCHEME)?
+        .split_once('/')
+        .filter(|(provider, name)| !provider.is_empty() && !name.is_empty())
+}
+
 /// Configuration errors
 #[derive(Debug, Error)]
 pub enum ConfigError {
@@ -0,0 +1,123 @@
+//! Scanner pipeline configuration
+
+use super::{ConfigError, Result, STORAGE_URI_SCHEME};
+use llm_shield_scanners::PipelineConfig;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Configuration for the scanners served by the API
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScannersConfig {
+    /// Declarative pipeline file (YAML, TOML or JSON) listing the scanners,
+    /// or `storage://<key>[#sha256=<hex>]` to fetch it from cloud storage
+    #[serde(default)]
+    pub pipeline_file: Option<String>,
+
+    /// Reload the scanners when the pipeline file (or, for pipelines in
+    /// cloud storage, the pipeline or one of its pattern packs) changes
+    #[serde(default)]
+    pub watch: bool,
+
+    /// How often the pipeline file is checked for changes, in seconds
+    #[serde(default = "default_poll_interval_secs")]
+    pub poll_interval_secs: u64,
+}
+
+impl ScannersConfig {
+    /// Get pipeline file poll interval
+    pub fn poll_interval(&self) -> Duration {
+        Duration::from_secs(self.poll_interval_secs)
+    }
+
+    /// Whether the pipeline file is fetched from cloud storage
+    pub fn is_storage_pipeline(&self) -> bool {
+        self.pipeline_file
+            .as_deref()
+            .is_some_and(|path| path.starts_with(STORAGE_URI_SCHEME))
+    }
+
+    /// Load and validate the pipeline file, if one is configured
+    ///
+    /// Pipelines and pattern packs in cloud storage need the asynchronous
+    /// loader in `crate::artifacts` (`cloud` feature).
+    pub fn load_pipeline(&self) -> Result<Option<PipelineConfig>> {
+        let Some(path) = &self.pipeline_file else {
+            return Ok(None);
+        };
+        if self.is_storage_pipeline() {
+            return Err(ConfigError::LoadError(format!(
+                "{}: pipelines in cloud storage must be loaded asynchronously",
+                path
+            )));
+        }
+
+        let pipeline = PipelineConfig::from_path(path)
+            .and_then(|pipeline| pipeline.validate().map(|_| pipeline))
+            .map_err(|e| ConfigError::LoadError(format!("{}: {}", path, e)))?;
+        Ok(Some(pipeline))
+    }
+
+    /// Validate scanner configuration
+    pub fn validate(&self) -> Result<()> {
+        if let Some(path) = &self.pipeline_file {
+            if path.trim().is_empty() {
+                return Err(ConfigError::ValidationError(
+                    "Scanner pipeline file cannot be empty".to_string(),
+                ));
+            }
+        }
+
+        if self.poll_interval_secs == 0 {
+            return Err(ConfigError::ValidationError(
+                "Scanner pipeline poll interval must be greater than 0".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// Scanner parameters that may be given as `secret://` references, by config
+/// path such as `scanners.input[0].params.api_key`
+pub fn pipeline_secret_fields_mut(pipeline: &mut PipelineConfig) -> Vec<(String, &mut String)> {
+    pipeline
+        .string_params_mut()
+        .into_iter()
+        .map(|(path, value)| (format!("scanners.{}", path), value))
+        .collect()
+}
+
+impl Default for ScannersConfig {
+    fn default() -> Self {
+        Self {
+            pipeline_file: None,
+            watch: false,
+            poll_interval_secs: default_poll_interval_secs(),
+        }
+    }
+}
+
+fn default_poll_interval_secs() -> u64 {
+    5
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scanners_config_load_pipeline() {
+        assert!(ScannersConfig::default().load_pipeline().unwrap().is_none());
+
+        let path = std::env::temp_dir().join(format!("api-pipeline-{}.yaml", std::process::id()));
+        std::fs::write(&path, "input:\n  - type: secrets\n  - type: nope\n").unwrap();
+        let config = ScannersConfig {
+            pipeline_file: Some(path.display().to_string()),
+            ..Default::default()
+        };
+
+        let err = config.load_pipeline().unwrap_err().to_string();
+        std::fs::remove_file(&path).unwrap();
+        assert!(err.contains("input[1].type"));
+    }
+}
@@ -0,0 +1,394 @@
+//! SIEM event emission configuration
+
+use super::{ConfigError, Result};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Format of emitted security events
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SiemFormat {
+    /// ArcSight Common Event Format
+    Cef,
+    /// OCSF Detection Finding JSON
+    #[default]
+    Ocsf,
+    /// Elastic Common Schema JSON
+    Ecs,
+}
+
+/// Destination of emitted security events
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SiemTransportKind {
+    /// RFC 5424 syslog
+    #[default]
+    Syslog,
+    /// Append to a local file, one event per line
+    File,
+    /// POST batches to an HTTP endpoint
+    Webhook,
+}
+
+/// Syslog transport protocol
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SyslogProtocol {
+    /// One datagram per event
+    #[default]
+    Udp,
+    /// Octet-counted framing (RFC 6587)
+    Tcp,
+    /// Octet-counted framing over TLS (RFC 5425)
+    Tls,
+}
+
+/// Configuration for emitting security events to a SIEM
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SiemConfig {
+    /// Emit security events
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Event format (cef, ocsf, ecs)
+    #[serde(default)]
+    pub format: SiemFormat,
+
+    /// Emit allowed scans at or above this risk score; blocked scans are
+    /// always emitted
+    #[serde(default = "default_min_risk_score")]
+    pub min_risk_score: f32,
+
+    /// Destination (syslog, file, webhook)
+    #[serde(default)]
+    pub transport: SiemTransportKind,
+
+    /// Syslog destination
+    #[serde(default)]
+    pub syslog: SyslogConfig,
+
+    /// File destination
+    #[serde(default)]
+    pub file: SiemFileConfig,
+
+    /// Webhook destination
+    #[serde(default)]
+    pub webhook: SiemWebhookConfig,
+
+    /// Maximum events per delivery
+    #[serde(default = "default_batch_size")]
+    pub batch_size: usize,
+
+    /// Deliver queued events at least this often, in milliseconds
+    #[serde(default = "default_flush_interval_ms")]
+    pub flush_interval_ms: u64,
+
+    /// Retries of a failed delivery before its events are dropped
+    #[serde(default = "default_max_retries")]
+    pub max_retries: u32,
+
+    /// Delay before the first retry, doubled on each further retry
+    #[serde(default = "default_retry_backoff_ms")]
+    pub retry_backoff_ms: u64,
+
+    /// Events buffered while deliveries are slow; further events are dropped
+    #[serde(default = "default_queue_capacity")]
+    pub queue_capacity: usize,
+}
+
+impl SiemConfig {
+    /// Get flush interval
+    pub fn flush_interval(&self) -> Duration {
+        Duration::from_millis(self.flush_interval_ms)
+    }
+
+    /// Get initial retry backoff
+    pub fn retry_backoff(&self) -> Duration {
+        Duration::from_millis(self.retry_backoff_ms)
+    }
+
+    /// Validate SIEM configuration
+    pub fn validate(&self) -> Result<()> {
+        if !self.enabled {
+            return Ok(());
+        }
+
+        if !(0.0..=1.0).contains(&self.min_risk_score) {
+            return Err(ConfigError::ValidationError(
+                "SIEM minimum risk score must be between 0.0 and 1.0".to_string(),
+            ));
+        }
+
+        if self.batch_size == 0 || self.flush_interval_ms == 0 || self.queue_capacity == 0 {
+            return Err(ConfigError::ValidationError(
+                "SIEM batch size, flush interval and queue capacity must be greater than 0"
+                    .to_string(),
+            ));
+        }
+
+        match self.transport {
+            SiemTransportKind::Syslog => self.syslog.validate(),
+            SiemTransportKind::File => {
+                if self.file.path.is_empty() {
+                    return Err(ConfigError::ValidationError(
+                        "SIEM file path cannot be empty".to_string(),
+                    ));
+                }
+                Ok(())
+            }
+            SiemTransportKind::Webhook => self.webhook.validate(),
+        }
+    }
+}
+
+impl Default for SiemConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            format: SiemFormat::default(),
+            min_risk_score: default_min_risk_score(),
+            transport: SiemTransportKind::default(),
+            syslog: SyslogConfig::default(),
+            file: SiemFileConfig::default(),
+            webhook: SiemWebhookConfig::default(),
+            batch_size: default_batch_size(),
+            flush_interval_ms: default_flush_interval_ms(),
+            max_retries: default_max_retries(),
+            retry_backoff_ms: default_retry_backoff_ms(),
+            queue_capacity: default_queue_capacity(),
+        }
+    }
+}
+
+/// Syslog destination (RFC 5424)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyslogConfig {
+    /// Collector address (host:port)
+    #[serde(default = "default_syslog_address")]
+    pub address: String,
+
+    /// Transport protocol (udp, tcp, tls)
+    #[serde(default)]
+    pub protocol: SyslogProtocol,
+
+    /// Facility code (default: 13, log audit)
+    #[serde(default = "default_facility")]
+    pub facility: u8,
+
+    /// APP-NAME of messages
+    #[serde(default = "default_app_name")]
+    pub app_name: String,
+
+    /// HOSTNAME of messages (default: HOSTNAME environment variable)
+    pub hostname: Option<String>,
+
+    /// PEM file of the CA certificate trusted for TLS, in addition to the
+    /// system roots
+    pub ca_cert: Option<String>,
+
+    /// TLS server name (default: host of `address`)
+    pub server_name: Option<String>,
+
+    /// Skip TLS certificate verification (testing only)
+    #[serde(default)]
+    pub accept_invalid_certs: bool,
+
+    /// Time limit in seconds for connecting and delivering a batch
+    #[serde(default = "default_syslog_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+impl SyslogConfig {
+    /// Get delivery timeout
+    pub fn timeout(&self) -> Duration {
+        Duration::from_secs(self.timeout_secs)
+    }
+
+    fn validate(&self) -> Result<()> {
+        if self.address.is_empty() {
+            return Err(ConfigError::ValidationError(
+                "Syslog address cannot be empty".to_string(),
+            ));
+        }
+        if self.facility > 23 {
+            return Err(ConfigError::ValidationError(format!(
+                "Syslog facility must be between 0 and 23: {}",
+                self.facility
+            )));
+        }
+        if self.app_name.is_empty() || self.app_name.chars().any(|c| !c.is_ascii_graphic()) {
+            return Err(ConfigError::ValidationError(format!(
+                "Syslog app name must be non-empty printable ASCII: {:?}",
+                self.app_name
+            )));
+        }
+        if self.timeout_secs == 0 {
+            return Err(ConfigError::ValidationError(
+                "Syslog timeout must be greater than 0".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl Default for SyslogConfig {
+    fn default() -> Self {
+        Self {
+            address: default_syslog_address(),
+            protocol: SyslogProtocol::default(),
+            facility: default_facility(),
+            app_name: default_app_name(),
+            hostname: None,
+            ca_cert: None,
+            server_name: None,
+            accept_invalid_certs: false,
+            timeout_secs: default_syslog_timeout_secs(),
+        }
+    }
+}
+
+/// File destination
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SiemFileConfig {
+    /// File events are appended to
+    #[serde(default = "default_file_path")]
+    pub path: String,
+}
+
+impl Default for SiemFileConfig {
+    fn default() -> Self {
+        Self {
+            path: default_file_path(),
+        }
+    }
+}
+
+/// Webhook destination
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SiemWebhookConfig {
+    /// Endpoint receiving batches of events
+    pub url: Option<String>,
+
+    /// Bearer token sent with every batch
+    #[serde(default, skip_serializing)]
+    pub token: Option<String>,
+
+    /// Request timeout in seconds
+    #[serde(default = "default_webhook_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+impl SiemWebhookConfig {
+    /// Get request timeout
+    pub fn timeout(&self) -> Duration {
+        Duration::from_secs(self.timeout_secs)
+    }
+
+    fn validate(&self) -> Result<()> {
+        match &self.url {
+            None => Err(ConfigError::ValidationError(
+                "SIEM webhook URL must be specified".to_string(),
+            )),
+            Some(url) if !(url.starts_with("http://") || url.starts_with("https://")) => {
+                Err(ConfigError::ValidationError(format!(
+                    "SIEM webhook URL must be an http(s) URL: {}",
+                    url
+                )))
+            }
+            Some(_) if self.timeout_secs == 0 => Err(ConfigError::ValidationError(
+                "SIEM webhook timeout must be greater than 0".to_string(),
+            )),
+            Some(_) => Ok(()),
+        }
+    }
+}
+
+impl Default for SiemWebhookConfig {
+    fn default() -> Self {
+        Self {
+            url: None,
+            token: None,
+            timeout_secs: default_webhook_timeout_secs(),
+        }
+    }
+}
+
+fn default_min_risk_score() -> f32 {
+    0.7
+}
+
+fn default_batch_size() -> usize {
+    100
+}
+
+fn default_flush_interval_ms() -> u64 {
+    1000
+}
+
+fn default_max_retries() -> u32 {
+    3
+}
+
+fn default_retry_backoff_ms() -> u64 {
+    500
+}
+
+fn default_queue_capacity() -> usize {
+    10_000
+}
+
+fn default_syslog_address() -> String {
+    "127.0.0.1:514".to_string()
+}
+
+fn default_facility() -> u8 {
+    13 // log audit
+}
+
+fn default_app_name() -> String {
+    "llm-shield".to_string()
+}
+
+fn default_syslog_timeout_secs() -> u64 {
+    10
+}
+
+fn default_file_path() -> String {
+    "./data/siem/events.log".to_string()
+}
+
+fn default_webhook_timeout_secs() -> u64 {
+    10
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_siem_config_validation() {
+        let mut config = SiemConfig::default();
+        assert!(config.validate().is_ok());
+
+        config.enabled = true;
+        assert!(config.validate().is_ok());
+
+        config.syslog.facility = 24;
+        assert!(config.validate().is_err());
+        config.syslog.facility = 13;
+
+        config.syslog.timeout_secs = 0;
+        assert!(config.validate().is_err());
+        config.syslog.timeout_secs = 10;
+
+        config.transport = SiemTransportKind::Webhook;
+        assert!(config.validate().is_err());
+        config.webhook.url = Some("siem.internal/events".to_string());
+        assert!(config.validate().is_err());
+        config.webhook.url = Some("https://siem.internal/events".to_string());
+        assert!(config.validate().is_ok());
+
+        config.min_risk_score = 1.5;
+        assert!(config.validate().is_err());
+    }
+}
@@ -0,0 +1,81 @@
+//! Streaming proxy configuration
+
+use super::{ConfigError, Result};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Configuration for the `/v1/scan/stream` upstream proxy
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StreamingConfig {
+    /// Upstream LLM endpoint returning a server-sent event stream
+    #[serde(default)]
+    pub upstream_url: Option<String>,
+
+    /// Bearer token sent to the upstream endpoint
+    #[serde(default, skip_serializing)]
+    pub upstream_api_key: Option<String>,
+
+    /// Upstream request timeout in seconds, covering the whole stream
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+impl StreamingConfig {
+    /// Get upstream timeout
+    pub fn timeout(&self) -> Duration {
+        Duration::from_secs(self.timeout_secs)
+    }
+
+    /// Validate streaming configuration
+    pub fn validate(&self) -> Result<()> {
+        if let Some(url) = &self.upstream_url {
+            if !(url.starts_with("http://") || url.starts_with("https://")) {
+                return Err(ConfigError::ValidationError(
+                    "Streaming upstream URL must be http or https".to_string(),
+                ));
+            }
+        }
+
+        if self.timeout_secs == 0 {
+            return Err(ConfigError::ValidationError(
+                "Streaming timeout must be greater than 0".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for StreamingConfig {
+    fn default() -> Self {
+        Self {
+            upstream_url: None,
+            upstream_api_key: None,
+            timeout_secs: default_timeout_secs(),
+        }
+    }
+}
+
+fn default_timeout_secs() -> u64 {
+    300
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_streaming_config_validation() {
+        let mut config = StreamingConfig::default();
+        assert!(config.validate().is_ok());
+
+        config.upstream_url = Some("ftp://example.com".to_string());
+        assert!(config.validate().is_err());
+
+        config.upstream_url = Some("https://api.example.com/v1/chat/completions".to_string());
+        assert!(config.validate().is_ok());
+
+        config.timeout_secs = 0;
+        assert!(config.validate().is_err());
+    }
+}
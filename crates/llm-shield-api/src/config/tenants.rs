@@ -0,0 +1,98 @@
+//! Tenant profile configuration
+
+use super::{ConfigError, Result};
+use serde::{Deserialize, Serialize};
+
+/// Configuration for per-tenant scanner profiles
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TenantsConfig {
+    /// Resolve a tenant profile for each scan request
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// JSON file holding the profiles; profiles are kept in memory when unset
+    #[serde(default)]
+    pub profiles_file: Option<String>,
+
+    /// Profile for callers not mapped to one; the shared scanners are used
+    /// when unset
+    #[serde(default)]
+    pub default_profile: Option<String>,
+
+    /// Take the tenant from the `x-tenant-id` header
+    ///
+    /// When set, the header attributes scan events and audit records and
+    /// selects profiles for callers not mapped to one. Otherwise the tenant
+    /// is the authenticated API key ID or gateway caller ID. The header is
+    /// not authenticated, so only enable this behind a gateway that sets it.
+    #[serde(default)]
+    pub trust_tenant_header: bool,
+
+    /// Maximum number of built tenant pipelines kept in memory
+    #[serde(default = "default_max_cached_pipelines")]
+    pub max_cached_pipelines: usize,
+}
+
+impl TenantsConfig {
+    /// Validate tenant configuration
+    pub fn validate(&self) -> Result<()> {
+        if let Some(path) = &self.profiles_file {
+            if path.trim().is_empty() {
+                return Err(ConfigError::ValidationError(
+                    "Tenant profiles file cannot be empty".to_string(),
+                ));
+            }
+        }
+
+        if let Some(profile) = &self.default_profile {
+            if profile.trim().is_empty() {
+                return Err(ConfigError::ValidationError(
+                    "Default tenant profile cannot be empty".to_string(),
+                ));
+            }
+        }
+
+        if self.max_cached_pipelines == 0 {
+            return Err(ConfigError::ValidationError(
+                "Tenant pipeline cache size must be greater than 0".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for TenantsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            profiles_file: None,
+            default_profile: None,
+            trust_tenant_header: false,
+            max_cached_pipelines: default_max_cached_pipelines(),
+        }
+    }
+}
+
+fn default_max_cached_pipelines() -> usize {
+    256
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tenants_config_validation() {
+        let mut config = TenantsConfig::default();
+        assert!(!config.enabled);
+        assert!(config.validate().is_ok());
+
+        config.profiles_file = Some(" ".to_string());
+        assert!(config.validate().is_err());
+
+        config.profiles_file = Some("config/tenants.json".to_string());
+        config.max_cached_pipelines = 0;
+        assert!(config.validate().is_err());
+    }
+}
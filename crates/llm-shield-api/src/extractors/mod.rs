@@ -1,3 +1,128 @@
 //! Custom Axum extractors
 
-// TODO: Implement extractors in Phase 10A Week 5-6
+use crate::middleware::gateway::GatewayCaller;
+use crate::middleware::AuthenticatedUser;
+use crate::services::event_sink::tenant_from_headers;
+use crate::state::AppState;
+use axum::{
+    async_trait,
+    extract::{FromRef, FromRequestParts},
+    http::request::Parts,
+    http::HeaderMap,
+};
+use std::convert::Infallible;
+
+/// Header carrying the client's request ID
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Who made a scan request, for attribution in events and the audit log
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ScanContext {
+    /// `x-request-id` header, or a generated UUID
+    pub request_id: String,
+    /// Authenticated API key ID or gateway caller ID
+    pub caller_id: Option<String>,
+    /// Tenant the scan is attributed to: the `x-tenant-id` header when
+    /// `tenants.trust_tenant_header` is set, otherwise the caller
+    pub tenant_id: Option<String>,
+}
+
+impl ScanContext {
+    /// Context of an unauthenticated request with the given headers
+    ///
+    /// The `x-tenant-id` header is only read when `trust_tenant_header` is
+    /// set, as any client can send it.
+    pub fn from_headers(headers: &HeaderMap, trust_tenant_header: bool) -> Self {
+        let request_id = headers
+            .get(REQUEST_ID_HEADER)
+            .and_then(|v| v.to_str().ok())
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .map(str::to_string)
+            .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+
+        Self {
+            request_id,
+            caller_id: None,
+            tenant_id: tenant_from_headers(headers).filter(|_| trust_tenant_header),
+        }
+    }
+
+    /// Set the authenticated caller, which is also the tenant unless a
+    /// trusted header named one
+    pub fn with_caller(mut self, caller_id: Option<String>) -> Self {
+        if self.tenant_id.is_none() {
+            self.tenant_id = caller_id.clone();
+        }
+        self.caller_id = caller_id;
+        self
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for ScanContext
+where
+    S: Send + Sync,
+    AppState: FromRef<S>,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let trust_tenant_header = AppState::from_ref(state).config.tenants.trust_tenant_header;
+        let caller_id = parts
+            .extensions
+            .get::<AuthenticatedUser>()
+            .map(|user| user.key_id.clone())
+            .or_else(|| {
+                parts
+                    .extensions
+                    .get::<GatewayCaller>()
+                    .map(|caller| caller.caller_id.clone())
+            });
+        Ok(Self::from_headers(&parts.headers, trust_tenant_header).with_caller(caller_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::Request;
+
+    fn request_parts() -> Parts {
+        let request = Request::builder()
+            .header(REQUEST_ID_HEADER, "req-1")
+            .header("x-tenant-id", "tenant-a")
+            .extension(GatewayCaller {
+                caller_id: "gateway-caller".to_string(),
+            })
+            .body(())
+            .unwrap();
+        request.into_parts().0
+    }
+
+    #[tokio::test]
+    async fn test_scan_context_from_request() {
+        let state = AppState::new(crate::config::AppConfig::default());
+        let context = ScanContext::from_request_parts(&mut request_parts(), &state)
+            .await
+            .unwrap();
+        assert_eq!(context.request_id, "req-1");
+        assert_eq!(context.caller_id.as_deref(), Some("gateway-caller"));
+        // The header is not trusted by default; the caller is the tenant
+        assert_eq!(context.tenant_id.as_deref(), Some("gateway-caller"));
+
+        let mut config = crate::config::AppConfig::default();
+        config.tenants.trust_tenant_header = true;
+        let state = AppState::new(config);
+        let context = ScanContext::from_request_parts(&mut request_parts(), &state)
+            .await
+            .unwrap();
+        assert_eq!(context.tenant_id.as_deref(), Some("tenant-a"));
+
+        // Request IDs are generated when missing
+        let context = ScanContext::from_headers(&HeaderMap::new(), true);
+        assert!(!context.request_id.is_empty());
+        assert_eq!(context.caller_id, None);
+        assert_eq!(context.tenant_id, None);
+    }
+}
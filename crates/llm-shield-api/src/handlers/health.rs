@@ -1,6 +1,8 @@
 //! Health check endpoints
 
-use axum::{http::StatusCode, response::IntoResponse, Json};
+use crate::state::AppState;
+use axum::{extract::State, http::StatusCode, response::IntoResponse, Json};
+use llm_shield_core::ReloadStatus;
 use serde::{Deserialize, Serialize};
 
 /// Health check response
@@ -73,19 +75,46 @@ pub async fn live() -> impl IntoResponse {
     (StatusCode::OK, Json(response))
 }
 
+/// Version information response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VersionInfo {
+    /// Service version
+    pub version: String,
+
+    /// Build timestamp
+    pub build_time: String,
+
+    /// Git commit hash
+    pub git_commit: String,
+
+    /// Scanner set version and reload history
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scanners: Option<ReloadStatus>,
+}
+
+impl VersionInfo {
+    fn current() -> Self {
+        Self {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            build_time: "unknown".to_string(), // TODO: Add build time
+            git_commit: "unknown".to_string(), // TODO: Add git commit
+            scanners: None,
+        }
+    }
+}
+
 /// Version information endpoint
 pub async fn version() -> impl IntoResponse {
-    #[derive(Serialize)]
-    struct VersionInfo {
-        version: String,
-        build_time: String,
-        git_commit: String,
-    }
+    (StatusCode::OK, Json(VersionInfo::current()))
+}
 
+/// Version information endpoint, including the scanner set version
+///
+/// The scanner version starts at 1 and increases with every hot reload.
+pub async fn version_info(State(state): State<AppState>) -> impl IntoResponse {
     let info = VersionInfo {
-        version: env!("CARGO_PKG_VERSION").to_string(),
-        build_time: "unknown".to_string(), // TODO: Add build time
-        git_commit: "unknown".to_string(), // TODO: Add git commit
+        scanners: Some(state.reload_status()),
+        ..VersionInfo::current()
     };
 
     (StatusCode::OK, Json(info))
@@ -118,4 +147,22 @@ mod tests {
         let response = version().await.into_response();
         assert_eq!(response.status(), StatusCode::OK);
     }
+
+    #[tokio::test]
+    async fn test_version_info_reports_reloads() {
+        use llm_shield_scanners::PipelineConfig;
+
+        let state = AppState::new(crate::config::AppConfig::default());
+        let pipeline = PipelineConfig::from_yaml_str("input:\n  - type: secrets\n").unwrap();
+        state.reload_pipeline(&pipeline).unwrap();
+
+        let response = version_info(State(state)).await.into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let info: VersionInfo = serde_json::from_slice(&body).unwrap();
+        let scanners = info.scanners.unwrap();
+        assert_eq!(scanners.version, 2);
+        assert_eq!(scanners.reloads, 1);
+    }
 }
@@ -5,7 +5,7 @@ pub mod ingest;
 pub mod scan;
 pub mod scanners;
 
-pub use health::{health, live, ready, version};
+pub use health::{health, live, ready, version, version_info};
 pub use ingest::ingest_scan;
-pub use scan::{scan_batch, scan_output, scan_prompt};
+pub use scan::{scan_batch, scan_conversation, scan_output, scan_prompt, scan_stream};
 pub use scanners::list_scanners;
@@ -25,6 +25,7 @@ use axum::{extract::State, http::StatusCode, response::IntoResponse, Json};
 pub async fn list_scanners(State(state): State<AppState>) -> impl IntoResponse {
     let scanners: Vec<ScannerMetadataResponse> = state
         .scanners
+        .load()
         .iter()
         .map(|(name, scanner)| ScannerMetadataResponse {
             name: name.clone(),
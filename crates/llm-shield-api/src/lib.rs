@@ -3,6 +3,9 @@
 //! Production-grade REST API exposing LLM Shield scanners and anonymization
 //! capabilities via HTTP with enterprise-grade security, observability, and performance.
 
+#[cfg(feature = "cloud")]
+pub mod artifacts;
+pub mod audit;
 pub mod auth;
 #[cfg(feature = "cloud")]
 pub mod cloud_init;
@@ -14,9 +17,12 @@ pub mod models;
 pub mod observability;
 pub mod rate_limiting;
 pub mod router;
+pub mod secrets;
 pub mod server;
 pub mod services;
+pub mod siem;
 pub mod state;
+pub mod tenants;
 
 // Re-exports
 pub use config::AppConfig;
@@ -1,8 +1,18 @@
 //! LLM Shield REST API Server
+//!
+//! Reads its configuration from the file named by `LLM_SHIELD_API_CONFIG`
+//! (optional) and `LLM_SHIELD_API__*` environment overrides, starts the
+//! configured cloud providers, tenant profiles, audit log and SIEM emitter,
+//! then serves the configured scanner pipeline, reloading it when
+//! `scanners.watch` is set.
 
-use llm_shield_api::router::create_router;
+use llm_shield_api::config::{load_config, AppConfig};
+use llm_shield_api::router::create_router_with_state;
+use llm_shield_api::state::{AppState, AppStateBuilder};
+use std::error::Error;
+use std::path::PathBuf;
 use tokio::signal;
-use tracing::info;
+use tracing::{info, warn};
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -12,8 +22,18 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .compact()
         .init();
 
+    // Load configuration and start the configured components
+    let config_path = std::env::var_os("LLM_SHIELD_API_CONFIG").map(PathBuf::from);
+    let config = load_config(config_path.as_deref())?;
+    let state = build_state(config).await?;
+
+    // Keep the watchers alive for the lifetime of the server
+    let _watch = state.watch_pipeline();
+    #[cfg(feature = "cloud")]
+    let _secrets = state.watch_secrets();
+
     // Create router
-    let app = create_router();
+    let app = create_router_with_state(state.clone());
 
     // Bind server (respect PORT env for Cloud Run, default to 8080)
     let port = std::env::var("PORT").unwrap_or_else(|_| "8080".to_string());
@@ -29,11 +49,59 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .with_graceful_shutdown(shutdown_signal())
         .await?;
 
+    flush(&state).await;
     info!("Server shutdown complete");
 
     Ok(())
 }
 
+/// Build the application state from `config`
+///
+/// Cloud providers are initialized first, so that `secret://` values and
+/// `storage://` pipelines resolve before the components using them start.
+async fn build_state(config: AppConfig) -> Result<AppState, Box<dyn Error>> {
+    #[cfg(feature = "cloud")]
+    let builder = {
+        let providers = if config.cloud.enabled {
+            Some(llm_shield_api::cloud_init::initialize_cloud_providers(&config).await?)
+        } else {
+            None
+        };
+        let mut builder = AppStateBuilder::new(config);
+        if let Some(providers) = providers {
+            builder = builder.with_cloud_providers(providers);
+        }
+        builder
+            .resolve_config_secrets()
+            .await?
+            .load_configured_pipeline()
+            .await?
+    };
+    #[cfg(not(feature = "cloud"))]
+    let builder = AppStateBuilder::new(config).register_configured_pipeline()?;
+
+    Ok(builder
+        .start_tenants()
+        .await?
+        .start_audit()?
+        .start_siem()?
+        .build())
+}
+
+/// Deliver queued audit decisions and security events before exiting
+async fn flush(state: &AppState) {
+    if let Some(audit) = &state.audit {
+        if let Err(e) = audit.checkpoint().await {
+            warn!("Failed to checkpoint audit log: {}", e);
+        }
+    }
+    if let Some(siem) = &state.siem {
+        if let Err(e) = siem.flush().await {
+            warn!("Failed to flush security events: {}", e);
+        }
+    }
+}
+
 /// Wait for shutdown signal (Ctrl+C or SIGTERM)
 async fn shutdown_signal() {
     let ctrl_c = async {
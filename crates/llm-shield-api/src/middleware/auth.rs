@@ -6,6 +6,7 @@
 
 use crate::auth::AuthService;
 use crate::middleware::rate_limit::ClientTier;
+use crate::observability::prometheus::{record_auth_failure, AuthFailureReason};
 use axum::{
     body::Body,
     extract::Request,
@@ -74,10 +75,12 @@ pub async fn auth_middleware(
             if let Some(key) = header.strip_prefix("Bearer ") {
                 key
             } else {
+                record_auth_failure(AuthFailureReason::Malformed);
                 return create_unauthorized_response("Invalid authorization header format");
             }
         }
         None => {
+            record_auth_failure(AuthFailureReason::Missing);
             return create_unauthorized_response("Missing authorization header");
         }
     };
@@ -87,6 +90,7 @@ pub async fn auth_middleware(
         Ok(key) => key,
         Err(e) => {
             tracing::warn!("API key validation failed: {}", e);
+            record_auth_failure(AuthFailureReason::Invalid);
             return create_unauthorized_response("Invalid or expired API key");
         }
     };
@@ -2,12 +2,17 @@
 //!
 //! Validates caller tokens on scan routes to ensure LLM-Shield is ONLY
 //! invoked via LLM-Security-Core. This middleware is enabled when the
-//! `GATEWAY_SHARED_SECRET` environment variable is set.
+//! `GATEWAY_SHARED_SECRET` environment variable or `auth.gateway_secret` is
+//! set.
 //!
 //! When the secret is not configured, this middleware is a no-op for
 //! backward compatibility.
 
+use crate::config::AuthConfig;
+use crate::observability::prometheus::{record_auth_failure, AuthFailureReason};
+use crate::state::AppState;
 use axum::{
+    extract::State,
     http::{Request, StatusCode},
     middleware::Next,
     response::{IntoResponse, Response},
@@ -16,17 +21,17 @@ use axum::{
 use hmac::{Hmac, Mac};
 use sha2::Sha256;
 use serde_json::json;
-use std::sync::OnceLock;
 
 type HmacSha256 = Hmac<Sha256>;
 
-/// Cached gateway shared secret (loaded once from env).
-static GATEWAY_SECRET: OnceLock<Option<String>> = OnceLock::new();
-
-fn get_gateway_secret() -> &'static Option<String> {
-    GATEWAY_SECRET.get_or_init(|| {
-        std::env::var("GATEWAY_SHARED_SECRET").ok().filter(|s| !s.is_empty())
-    })
+/// Configured gateway shared secret: `auth.gateway_secret`, or the
+/// `GATEWAY_SHARED_SECRET` environment variable when it is not set
+pub fn configured_secret(config: &AuthConfig) -> Option<String> {
+    config
+        .gateway_secret
+        .clone()
+        .or_else(|| std::env::var("GATEWAY_SHARED_SECRET").ok())
+        .filter(|s| !s.is_empty())
 }
 
 /// Authenticated caller identity, added to request extensions after validation.
@@ -37,20 +42,29 @@ pub struct GatewayCaller {
 
 /// Gateway middleware that validates caller tokens.
 ///
-/// When `GATEWAY_SHARED_SECRET` is configured:
+/// When a secret is configured ([`AppState::gateway_secret`]):
 /// - Extracts `x-caller-id`, `x-caller-signature`, `x-caller-issued-at` headers
 /// - Validates HMAC-SHA256 signature against the secret
 /// - Checks token expiry (5 minute TTL, 30s clock skew tolerance)
 /// - Adds `GatewayCaller` to request extensions
 /// - Returns 401 if validation fails
 ///
-/// When `GATEWAY_SHARED_SECRET` is NOT configured:
+/// When no secret is configured:
 /// - Passes through all requests (backward compatible)
 pub async fn gateway_middleware(
+    State(state): State<AppState>,
     mut request: Request<axum::body::Body>,
     next: Next,
 ) -> Response {
-    let secret = match get_gateway_secret() {
+    let secret = match state.gateway_secret.get() {
+        // An unresolved secret:// reference is not a secret; refuse to use it
+        Some(secret) if secret.starts_with(crate::config::SECRET_URI_SCHEME) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "error": "Gateway configuration error" })),
+            )
+                .into_response();
+        }
         Some(secret) => secret,
         None => {
             // No secret configured - skip validation (backward compatible)
@@ -82,6 +96,7 @@ pub async fn gateway_middleware(
 
     // Check required fields
     if caller_id.is_empty() || signature.is_empty() || issued_at.is_empty() {
+        record_auth_failure(AuthFailureReason::Missing);
         return (
             StatusCode::UNAUTHORIZED,
             Json(json!({
@@ -110,6 +125,7 @@ pub async fn gateway_middleware(
     let sig_bytes = match hex::decode(&signature) {
         Ok(bytes) => bytes,
         Err(_) => {
+            record_auth_failure(AuthFailureReason::Invalid);
             return (
                 StatusCode::UNAUTHORIZED,
                 Json(json!({
@@ -123,6 +139,7 @@ pub async fn gateway_middleware(
     };
 
     if mac.verify_slice(&sig_bytes).is_err() {
+        record_auth_failure(AuthFailureReason::Invalid);
         return (
             StatusCode::UNAUTHORIZED,
             Json(json!({
@@ -140,6 +157,7 @@ pub async fn gateway_middleware(
         let age = now.signed_duration_since(issued_time);
 
         if age.num_seconds() > 300 {
+            record_auth_failure(AuthFailureReason::Invalid);
             return (
                 StatusCode::UNAUTHORIZED,
                 Json(json!({
@@ -152,6 +170,7 @@ pub async fn gateway_middleware(
         }
 
         if age.num_seconds() < -30 {
+            record_auth_failure(AuthFailureReason::Invalid);
             return (
                 StatusCode::UNAUTHORIZED,
                 Json(json!({
@@ -163,6 +182,7 @@ pub async fn gateway_middleware(
                 .into_response();
         }
     } else {
+        record_auth_failure(AuthFailureReason::Invalid);
         return (
             StatusCode::UNAUTHORIZED,
             Json(json!({
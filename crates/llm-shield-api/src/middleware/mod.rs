@@ -5,7 +5,7 @@
 //! - `auth`: API key authentication
 //! - `rate_limit`: Rate limiting and concurrent request limiting
 //! - `execution_context`: Agentics execution context validation and repo span creation
-//! - `gateway`: LLM-Security-Core caller token validation (optional, env-var gated)
+//! - `gateway`: LLM-Security-Core caller token validation (optional, shared-secret gated)
 
 pub mod auth;
 pub mod execution_context;
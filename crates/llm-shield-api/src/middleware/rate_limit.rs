@@ -5,6 +5,7 @@
 //! Axum middleware that enforces rate limits before processing requests.
 
 use crate::config::rate_limit::RateLimitTier;
+use crate::observability::prometheus::{record_rate_limit_rejection, RateLimitReason};
 use crate::rate_limiting::{ConcurrentLimiter, MultiTierRateLimiter, RateLimiter};
 use axum::{
     body::Body,
@@ -69,6 +70,7 @@ pub async fn rate_limit_middleware(
 
     if !decision.allowed {
         // Rate limit exceeded - return 429
+        record_rate_limit_rejection(RateLimitReason::Quota, tier);
         return create_rate_limit_response(decision);
     }
 
@@ -83,6 +85,7 @@ pub async fn rate_limit_middleware(
 
     if permit.is_none() {
         // Too many concurrent requests
+        record_rate_limit_rejection(RateLimitReason::Concurrency, tier);
         return create_concurrent_limit_response(decision);
     }
 
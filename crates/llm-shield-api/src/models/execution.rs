@@ -222,6 +222,15 @@ pub struct EnvelopedBatchScanResponse {
     pub execution: ExecutionOutput,
 }
 
+/// Enveloped conversation scan response.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnvelopedConversationScanResponse {
+    /// The conversation scan result.
+    pub result: super::response::ConversationScanResponse,
+    /// Execution span tree from the Agentics framework.
+    pub execution: ExecutionOutput,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
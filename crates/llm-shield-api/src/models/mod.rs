@@ -7,15 +7,17 @@ pub mod response;
 
 pub use error::{ApiError, ErrorResponse};
 pub use request::{
-    AnonymizeRequest, BatchScanRequest, DeanonymizeRequest, ScanOutputRequest, ScanPromptRequest,
+    AnonymizeRequest, BatchScanRequest, ConversationMessage, DeanonymizeRequest,
+    ScanConversationRequest, ScanOutputRequest, ScanPromptRequest, ScanStreamRequest,
 };
 pub use response::{
-    AnonymizeResponse, AnonymizedEntityDto, BatchScanResponse, DeanonymizeResponse, EntityDto,
-    ListScannersResponse, RiskFactorDto, ScanResponse, ScannerMetadataResponse, ScannerResult,
+    AnonymizeResponse, AnonymizedEntityDto, BatchScanResponse, ConversationScanResponse,
+    DeanonymizeResponse, EntityDto, ListScannersResponse, MessageScanDto, RiskFactorDto,
+    ScanDetailDto, ScanResponse, ScannerMetadataResponse, ScannerResult, StreamChunkEvent,
 };
 pub use execution::{
-    EnvelopedBatchScanResponse, EnvelopedScanResponse, ExecutionOutput, ExecutionSpan, SpanArtifact,
-    SpanStatus, SpanType,
+    EnvelopedBatchScanResponse, EnvelopedConversationScanResponse, EnvelopedScanResponse,
+    ExecutionOutput, ExecutionSpan, SpanArtifact, SpanStatus, SpanType,
 };
 
 /// Generic API response wrapper
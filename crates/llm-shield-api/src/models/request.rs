@@ -1,5 +1,6 @@
 //! Request DTOs
 
+use llm_shield_core::{Message, Role};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 use validator::Validate;
@@ -39,6 +40,11 @@ pub struct ScanOutputRequest {
     #[serde(default)]
     pub scanners: Vec<String>,
 
+    /// Reference documents for grounding checks (e.g. RAG context)
+    #[validate(length(max = 50))]
+    #[serde(default)]
+    pub references: Vec<String>,
+
     /// Enable result caching
     #[serde(default = "default_cache_enabled")]
     pub cache_enabled: bool,
@@ -58,6 +64,66 @@ pub struct BatchScanRequest {
     pub max_concurrent: usize,
 }
 
+/// Scan conversation request
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct ScanConversationRequest {
+    /// Messages in conversation order
+    #[validate(length(min = 1, max = 200, message = "Conversation must have between 1 and 200 messages"))]
+    #[validate(nested)]
+    pub messages: Vec<ConversationMessage>,
+
+    /// Scanners to run (empty = all scanners, routed by message role)
+    #[validate(length(max = 20))]
+    #[serde(default)]
+    pub scanners: Vec<String>,
+
+    /// Client conversation id; when set, results for earlier turns are
+    /// reused and only new messages are scanned
+    #[validate(length(min = 1, max = 100))]
+    #[serde(default)]
+    pub conversation_id: Option<String>,
+}
+
+/// A role-tagged conversation message
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct ConversationMessage {
+    /// Message author: system, user, assistant or tool
+    pub role: Role,
+
+    /// Message text
+    #[validate(length(min = 1, max = 100000))]
+    pub content: String,
+
+    /// Optional author name (e.g. the tool name)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+impl From<ConversationMessage> for Message {
+    fn from(message: ConversationMessage) -> Self {
+        Message {
+            role: message.role,
+            content: message.content,
+            name: message.name,
+        }
+    }
+}
+
+/// Streaming output scan request
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct ScanStreamRequest {
+    /// Scanners to run (empty = all output scanners that support streaming)
+    #[validate(length(max = 20))]
+    #[serde(default)]
+    pub scanners: Vec<String>,
+
+    /// Request body forwarded to the upstream LLM endpoint
+    pub request: serde_json::Value,
+}
+
 /// Anonymization request
 #[derive(Debug, Clone, Serialize, Deserialize, Validate)]
 #[serde(rename_all = "camelCase")]
@@ -146,6 +212,7 @@ mod tests {
             prompt: "What is AI?".to_string(),
             output: "AI is artificial intelligence".to_string(),
             scanners: vec![],
+            references: vec![],
             cache_enabled: true,
         };
 
@@ -267,4 +334,44 @@ mod tests {
         assert!(req.scanners.is_empty());
         assert!(req.cache_enabled);
     }
+
+    #[test]
+    fn test_scan_conversation_request_deserialize() {
+        let req: ScanConversationRequest = serde_json::from_str(
+            r#"{
+                "messages": [
+                    {"role": "system", "content": "Be helpful"},
+                    {"role": "user", "content": "Hi"},
+                    {"role": "tool", "content": "result", "name": "search"}
+                ],
+                "conversationId": "conv-1"
+            }"#,
+        )
+        .unwrap();
+
+        assert!(req.validate().is_ok());
+        assert_eq!(req.messages[2].role, Role::Tool);
+        assert_eq!(req.conversation_id.as_deref(), Some("conv-1"));
+    }
+
+    #[test]
+    fn test_scan_conversation_request_validates_messages() {
+        let empty = ScanConversationRequest {
+            messages: vec![],
+            scanners: vec![],
+            conversation_id: None,
+        };
+        assert!(empty.validate().is_err());
+
+        let blank_message = ScanConversationRequest {
+            messages: vec![ConversationMessage {
+                role: Role::User,
+                content: "".to_string(),
+                name: None,
+            }],
+            scanners: vec![],
+            conversation_id: None,
+        };
+        assert!(blank_message.validate().is_err());
+    }
 }
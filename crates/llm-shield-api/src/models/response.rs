@@ -1,5 +1,6 @@
 //! Response DTOs
 
+use llm_shield_core::Role;
 use serde::{Deserialize, Serialize};
 
 /// Scan result response
@@ -108,6 +109,101 @@ pub struct BatchScanResponse {
     pub failure_count: usize,
 }
 
+/// Conversation scan response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConversationScanResponse {
+    /// Whether every message and the cross-turn scan passed
+    pub is_valid: bool,
+
+    /// Highest risk score across all scans (0.0-1.0)
+    pub risk_score: f32,
+
+    /// Per-message results, in conversation order
+    pub messages: Vec<MessageScanDto>,
+
+    /// Result of scanning recent user and tool messages together
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cross_turn: Option<ScanDetailDto>,
+
+    /// Indices of the messages that triggered a detection
+    pub triggered_messages: Vec<usize>,
+
+    /// Indices of the messages scanned by this request (others were reused)
+    pub scanned_messages: Vec<usize>,
+
+    /// Processing time in milliseconds
+    pub scan_time_ms: u64,
+}
+
+/// Scan result for one conversation message
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MessageScanDto {
+    /// Position of the message in the conversation
+    pub index: usize,
+
+    /// Message author
+    pub role: Role,
+
+    /// Scan outcome
+    #[serde(flatten)]
+    pub detail: ScanDetailDto,
+
+    /// Whether the result was reused from an earlier request
+    pub cached: bool,
+}
+
+/// Aggregated outcome of the scanners run on one text
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScanDetailDto {
+    /// Whether all scanners passed
+    pub is_valid: bool,
+
+    /// Highest risk score (0.0-1.0)
+    pub risk_score: f32,
+
+    /// Sanitized text
+    pub sanitized_text: String,
+
+    /// Risk factors detected
+    #[serde(default)]
+    pub risk_factors: Vec<RiskFactorDto>,
+
+    /// Entities detected
+    #[serde(default)]
+    pub entities: Vec<EntityDto>,
+}
+
+/// One server-sent event of a streaming output scan
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StreamChunkEvent {
+    /// Scanned text released to the client (may be empty)
+    pub text: String,
+
+    /// Whether the released text passed all scanners
+    pub is_valid: bool,
+
+    /// Highest risk score in this chunk (0.0-1.0)
+    pub risk_score: f32,
+
+    /// Risk factors detected in this chunk
+    #[serde(default)]
+    pub risk_factors: Vec<RiskFactorDto>,
+
+    /// Entities detected, positioned in the original stream
+    #[serde(default)]
+    pub entities: Vec<EntityDto>,
+
+    /// Whether the stream was cut off by a detection
+    pub blocked: bool,
+
+    /// Whether this is the last event
+    pub done: bool,
+}
+
 /// Anonymization response
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
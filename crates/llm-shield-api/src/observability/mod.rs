@@ -1,3 +1,10 @@
 //! Observability (metrics, logging, tracing)
 
-// TODO: Implement observability in Phase 10A Week 7-8
+#[cfg(feature = "cloud")]
+pub mod otel;
+pub mod prometheus;
+
+pub use prometheus::{
+    install as install_metrics, metrics_handler, metrics_middleware, AuthFailureReason,
+    RateLimitReason,
+};
@@ -0,0 +1,128 @@
+//! Execution span export
+//!
+//! Converts the repo/agent `ExecutionSpan` tree of a finished request into
+//! cloud tracer spans, so any `CloudTracer` (e.g. the OTLP exporter) can
+//! export it as a real trace.
+//!
+//! ## Mapping
+//! - The `execution_id` becomes the trace ID, so all spans of an execution
+//!   share one trace.
+//! - The caller's `parent_span_id` becomes the parent of the repo span.
+//! - Verdicts of `detection_signal` artifacts become `shield.*` attributes;
+//!   failures set the error status and `error.message`.
+
+use crate::models::{ExecutionOutput, ExecutionSpan, SpanStatus, SpanType};
+use llm_shield_cloud::Span;
+use std::time::{Duration, SystemTime};
+
+/// Flatten an execution tree into spans, parents first
+pub fn execution_spans(execution: &ExecutionOutput) -> Vec<Span> {
+    let mut spans = Vec::new();
+    collect(&execution.repo_span, &mut spans);
+    spans
+}
+
+fn collect(span: &ExecutionSpan, spans: &mut Vec<Span>) {
+    spans.push(to_span(span));
+    for child in &span.children {
+        collect(child, spans);
+    }
+}
+
+fn to_span(span: &ExecutionSpan) -> Span {
+    let mut attributes = span.attributes.clone();
+    let span_type = match span.span_type {
+        SpanType::Core => "core",
+        SpanType::Repo => "repo",
+        SpanType::Agent => "agent",
+    };
+    attributes.insert("shield.span_type".to_string(), span_type.to_string());
+    attributes.insert("shield.execution_id".to_string(), span.execution_id.clone());
+
+    for artifact in &span.artifacts {
+        match artifact.artifact_type.as_str() {
+            "detection_signal" => {
+                if let Some(valid) = artifact.data.get("isValid") {
+                    attributes.insert("shield.valid".to_string(), valid.to_string());
+                }
+                // Risk scores are f32; print them without f64 widening noise
+                if let Some(risk) = artifact.data.get("riskScore").and_then(|r| r.as_f64()) {
+                    attributes.insert("shield.risk_score".to_string(), (risk as f32).to_string());
+                }
+            }
+            "error" => {
+                if let Some(reason) = artifact.data.get("error_reason").and_then(|r| r.as_str()) {
+                    attributes.insert("error.message".to_string(), reason.to_string());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let start_time = parse_time(&span.start_time).unwrap_or_else(SystemTime::now);
+    let end_time = span.end_time.as_deref().and_then(parse_time).or_else(|| {
+        span.duration_ms
+            .map(|ms| start_time + Duration::from_millis(ms))
+    });
+
+    Span {
+        name: span.name.clone(),
+        span_id: span.span_id.clone(),
+        trace_id: span.execution_id.clone(),
+        parent_span_id: (!span.parent_span_id.is_empty()).then(|| span.parent_span_id.clone()),
+        start_time,
+        end_time,
+        attributes,
+        status: match span.status {
+            SpanStatus::Completed => Some("OK".to_string()),
+            SpanStatus::Error => Some("ERROR".to_string()),
+            SpanStatus::Running => None,
+        },
+    }
+}
+
+fn parse_time(timestamp: &str) -> Option<SystemTime> {
+    chrono::DateTime::parse_from_rfc3339(timestamp)
+        .ok()
+        .map(SystemTime::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_execution_spans() {
+        let mut repo = ExecutionSpan::new_repo("exec-1", "core-span");
+
+        let mut ok = ExecutionSpan::new_agent(&repo, "toxicity");
+        ok.attach_artifact(
+            "detection_signal",
+            serde_json::json!({ "scanner": "toxicity", "isValid": false, "riskScore": 0.9 }),
+        );
+        ok.complete();
+        repo.children.push(ok);
+
+        let mut failed = ExecutionSpan::new_agent(&repo, "secrets");
+        failed.fail("timed out");
+        repo.children.push(failed);
+
+        let spans = execution_spans(&repo.finalize().unwrap());
+        assert_eq!(spans.len(), 3);
+
+        let (root, agents) = (&spans[0], &spans[1..]);
+        assert_eq!(root.parent_span_id.as_deref(), Some("core-span"));
+        assert_eq!(root.status.as_deref(), Some("OK"));
+        assert!(root.end_time.is_some());
+
+        for agent in agents {
+            assert_eq!(agent.trace_id, "exec-1");
+            assert_eq!(agent.parent_span_id.as_ref(), Some(&root.span_id));
+            assert_eq!(agent.attributes["shield.span_type"], "agent");
+        }
+        assert_eq!(agents[0].attributes["shield.valid"], "false");
+        assert_eq!(agents[0].attributes["shield.risk_score"], "0.9");
+        assert_eq!(agents[1].status.as_deref(), Some("ERROR"));
+        assert_eq!(agents[1].attributes["error.message"], "timed out");
+    }
+}
@@ -0,0 +1,355 @@
+//! Prometheus metrics
+//!
+//! Metrics are recorded through the [`metrics`] facade and rendered by a
+//! Prometheus recorder installed once per process by [`install`]. Until a
+//! recorder is installed, recording is a no-op.
+//!
+//! ## Metrics
+//!
+//! | Name | Type | Labels |
+//! |------|------|--------|
+//! | `llm_shield_http_requests_total` | counter | `route`, `status` |
+//! | `llm_shield_http_request_duration_seconds` | histogram | `route` |
+//! | `llm_shield_http_requests_in_flight` | gauge | `route` |
+//! | `llm_shield_scanner_duration_seconds` | histogram | `scanner` |
+//! | `llm_shield_scanner_errors_total` | counter | `scanner` |
+//! | `llm_shield_scanner_verdicts_total` | counter | `scanner`, `verdict` |
+//! | `llm_shield_scans_total` | counter | `scan_type`, `verdict` |
+//! | `llm_shield_risk_score` | histogram | `scan_type` |
+//! | `llm_shield_cache_hits_total` / `_misses_total` | counter | |
+//! | `llm_shield_cache_hit_ratio` | gauge | |
+//! | `llm_shield_rate_limit_rejections_total` | counter | `reason` |
+//! | `llm_shield_auth_failures_total` | counter | `reason` |
+//! | `llm_shield_scanner_set_version` | gauge | |
+//!
+//! ## Label Cardinality
+//!
+//! By default labels are bounded by the route table and the scanner set,
+//! and `status` is a class such as `2xx`. With
+//! [`detailed_labels`](crate::config::observability::MetricsConfig::detailed_labels)
+//! enabled, HTTP metrics also carry `method` and the exact `status`, scan
+//! metrics carry the `tenant` of the request and rate-limit rejections
+//! carry the client `tier`. Tenants are unbounded, so enable it only when the
+//! tenant set is known to be small.
+
+use crate::config::observability::MetricsConfig;
+use crate::config::rate_limit::RateLimitTier;
+use crate::models::ScannerResult;
+use crate::services::ScanKind;
+use crate::state::AppState;
+use axum::{
+    extract::{MatchedPath, Request, State},
+    http::{header, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use llm_shield_models::cache::CacheStats;
+use metrics::Label;
+use metrics_exporter_prometheus::{Matcher, PrometheusBuilder, PrometheusHandle};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+pub const HTTP_REQUESTS: &str = "llm_shield_http_requests_total";
+pub const HTTP_DURATION: &str = "llm_shield_http_request_duration_seconds";
+pub const HTTP_IN_FLIGHT: &str = "llm_shield_http_requests_in_flight";
+pub const SCANNER_DURATION: &str = "llm_shield_scanner_duration_seconds";
+pub const SCANNER_ERRORS: &str = "llm_shield_scanner_errors_total";
+pub const SCANNER_VERDICTS: &str = "llm_shield_scanner_verdicts_total";
+pub const SCANS: &str = "llm_shield_scans_total";
+pub const RISK_SCORE: &str = "llm_shield_risk_score";
+pub const CACHE_HITS: &str = "llm_shield_cache_hits_total";
+pub const CACHE_MISSES: &str = "llm_shield_cache_misses_total";
+pub const CACHE_HIT_RATIO: &str = "llm_shield_cache_hit_ratio";
+pub const RATE_LIMIT_REJECTIONS: &str = "llm_shield_rate_limit_rejections_total";
+pub const AUTH_FAILURES: &str = "llm_shield_auth_failures_total";
+pub const SCANNER_SET_VERSION: &str = "llm_shield_scanner_set_version";
+
+/// Latency buckets in seconds, from sub-millisecond pattern scanners to
+/// multi-second networked ones
+const LATENCY_BUCKETS: &[f64] = &[
+    0.0005, 0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+const RISK_BUCKETS: &[f64] = &[0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 0.7, 0.8, 0.9, 1.0];
+
+static HANDLE: OnceLock<PrometheusHandle> = OnceLock::new();
+static DETAILED_LABELS: AtomicBool = AtomicBool::new(false);
+
+/// Why a rate-limited request was rejected
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RateLimitReason {
+    /// Per-window request quota exhausted
+    Quota,
+    /// Too many concurrent requests
+    Concurrency,
+}
+
+impl RateLimitReason {
+    fn as_str(&self) -> &'static str {
+        match self {
+            RateLimitReason::Quota => "quota",
+            RateLimitReason::Concurrency => "concurrency",
+        }
+    }
+}
+
+/// Why a request failed authentication
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuthFailureReason {
+    /// No credentials were sent
+    Missing,
+    /// Credentials were not in the expected format
+    Malformed,
+    /// Credentials were rejected
+    Invalid,
+}
+
+impl AuthFailureReason {
+    fn as_str(&self) -> &'static str {
+        match self {
+            AuthFailureReason::Missing => "missing",
+            AuthFailureReason::Malformed => "malformed",
+            AuthFailureReason::Invalid => "invalid",
+        }
+    }
+}
+
+/// Install the Prometheus recorder and return its handle
+///
+/// Returns `None` when metrics are disabled. Calling this again returns the
+/// same handle; `detailed_labels` always follows the latest configuration.
+pub fn install(config: &MetricsConfig) -> Option<PrometheusHandle> {
+    if !config.enabled {
+        return None;
+    }
+    DETAILED_LABELS.store(config.detailed_labels, Ordering::Relaxed);
+
+    let handle = HANDLE.get_or_init(|| {
+        let recorder = recorder_builder().build_recorder();
+        let handle = recorder.handle();
+        if metrics::set_global_recorder(recorder).is_err() {
+            tracing::warn!("A metrics recorder is already installed; Prometheus metrics will be empty");
+        }
+        handle
+    });
+    Some(handle.clone())
+}
+
+/// The installed recorder's handle, if any
+pub fn handle() -> Option<PrometheusHandle> {
+    HANDLE.get().cloned()
+}
+
+fn recorder_builder() -> PrometheusBuilder {
+    PrometheusBuilder::new()
+        .set_buckets_for_metric(Matcher::Suffix("_seconds".to_string()), LATENCY_BUCKETS)
+        .and_then(|builder| builder.set_buckets_for_metric(Matcher::Full(RISK_SCORE.to_string()), RISK_BUCKETS))
+        .expect("bucket lists are non-empty")
+}
+
+fn detailed() -> bool {
+    DETAILED_LABELS.load(Ordering::Relaxed)
+}
+
+/// Record one scanner execution
+pub fn record_scanner_execution(scanner: &str, elapsed: Duration, failed: bool) {
+    let labels = [Label::new("scanner", scanner.to_string())];
+    metrics::histogram!(SCANNER_DURATION, labels.iter()).record(elapsed.as_secs_f64());
+    if failed {
+        metrics::counter!(SCANNER_ERRORS, labels.iter()).increment(1);
+    }
+}
+
+/// Record the verdicts and risk of a completed scan
+pub fn record_scan(kind: ScanKind, tenant_id: Option<&str>, results: &[ScannerResult]) {
+    let mut scan_labels = vec![Label::new("scan_type", kind.as_str())];
+    if detailed() {
+        scan_labels.push(Label::new("tenant", tenant_id.unwrap_or("none").to_string()));
+    }
+
+    let mut is_valid = true;
+    let mut risk_score = 0.0f32;
+    for result in results {
+        is_valid &= result.is_valid;
+        risk_score = risk_score.max(result.risk_score);
+
+        let mut labels = vec![
+            Label::new("scanner", result.scanner.clone()),
+            Label::new("verdict", verdict(result.is_valid)),
+        ];
+        labels.extend(scan_labels.iter().skip(1).cloned());
+        metrics::counter!(SCANNER_VERDICTS, labels.iter()).increment(1);
+    }
+
+    metrics::histogram!(RISK_SCORE, scan_labels.iter()).record(f64::from(risk_score));
+    scan_labels.push(Label::new("verdict", verdict(is_valid)));
+    metrics::counter!(SCANS, scan_labels.iter()).increment(1);
+}
+
+fn verdict(is_valid: bool) -> &'static str {
+    if is_valid {
+        "pass"
+    } else {
+        "fail"
+    }
+}
+
+/// Publish result cache statistics
+pub fn record_cache_stats(stats: &CacheStats) {
+    metrics::counter!(CACHE_HITS).absolute(stats.hits);
+    metrics::counter!(CACHE_MISSES).absolute(stats.misses);
+    metrics::gauge!(CACHE_HIT_RATIO).set(stats.hit_rate());
+}
+
+/// Record a request rejected by rate limiting
+pub fn record_rate_limit_rejection(reason: RateLimitReason, tier: RateLimitTier) {
+    let mut labels = vec![Label::new("reason", reason.as_str())];
+    if detailed() {
+        labels.push(Label::new("tier", format!("{:?}", tier).to_lowercase()));
+    }
+    metrics::counter!(RATE_LIMIT_REJECTIONS, labels.iter()).increment(1);
+}
+
+/// Record a request that failed authentication
+pub fn record_auth_failure(reason: AuthFailureReason) {
+    metrics::counter!(AUTH_FAILURES, "reason" => reason.as_str()).increment(1);
+}
+
+/// Middleware recording request counts, latency and in-flight requests
+///
+/// Requests are labelled with their matched route template, never the raw
+/// path, so path parameters cannot inflate cardinality.
+pub async fn metrics_middleware(request: Request, next: Next) -> Response {
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| "unmatched".to_string());
+    let method = request.method().to_string();
+
+    let in_flight = InFlightGuard::new(route.clone());
+    let start = Instant::now();
+
+    let response = next.run(request).await;
+
+    drop(in_flight);
+    let status = response.status();
+    let mut labels = vec![Label::new("route", route)];
+    metrics::histogram!(HTTP_DURATION, labels.iter()).record(start.elapsed().as_secs_f64());
+
+    if detailed() {
+        labels.push(Label::new("method", method));
+        labels.push(Label::new("status", status.as_u16().to_string()));
+    } else {
+        labels.push(Label::new("status", format!("{}xx", status.as_u16() / 100)));
+    }
+    metrics::counter!(HTTP_REQUESTS, labels.iter()).increment(1);
+
+    response
+}
+
+/// Counts a request as in flight until dropped
+///
+/// Decrementing on drop keeps the gauge accurate when the client disconnects
+/// and the request future is cancelled before the handler returns.
+struct InFlightGuard(metrics::Gauge);
+
+impl InFlightGuard {
+    fn new(route: String) -> Self {
+        let gauge = metrics::gauge!(HTTP_IN_FLIGHT, "route" => route);
+        gauge.increment(1.0);
+        Self(gauge)
+    }
+}
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        self.0.decrement(1.0);
+    }
+}
+
+/// GET metrics endpoint in the Prometheus text format
+pub async fn metrics_handler(State(state): State<AppState>) -> Response {
+    let Some(handle) = handle() else {
+        return (StatusCode::NOT_FOUND, "Metrics are disabled").into_response();
+    };
+
+    record_cache_stats(&state.cache.stats());
+    metrics::gauge!(SCANNER_SET_VERSION).set(state.reload_status().version as f64);
+
+    (
+        StatusCode::OK,
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        handle.render(),
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(scanner: &str, is_valid: bool, risk_score: f32) -> ScannerResult {
+        ScannerResult {
+            scanner: scanner.to_string(),
+            is_valid,
+            risk_score,
+            risk_factors: Vec::new(),
+            entities: Vec::new(),
+            execution_time_ms: Some(1),
+        }
+    }
+
+    #[test]
+    fn test_scan_metrics_rendered() {
+        let recorder = recorder_builder().build_recorder();
+        let handle = recorder.handle();
+
+        metrics::with_local_recorder(&recorder, || {
+            record_scanner_execution("Secrets", Duration::from_millis(3), false);
+            record_scanner_execution("Toxicity", Duration::from_millis(40), true);
+            record_scan(
+                ScanKind::Prompt,
+                Some("tenant-a"),
+                &[result("Secrets", false, 0.9), result("Toxicity", true, 0.1)],
+            );
+            record_rate_limit_rejection(RateLimitReason::Quota, RateLimitTier::Free);
+            record_auth_failure(AuthFailureReason::Missing);
+            record_cache_stats(&CacheStats { hits: 3, misses: 1 });
+        });
+
+        let output = handle.render();
+        assert!(output.contains(r#"llm_shield_scanner_duration_seconds_bucket{scanner="Secrets",le="0.005"} 1"#));
+        assert!(output.contains(r#"llm_shield_scanner_errors_total{scanner="Toxicity"} 1"#));
+        assert!(output.contains(r#"llm_shield_scanner_verdicts_total{scanner="Secrets",verdict="fail"} 1"#));
+        assert!(output.contains(r#"llm_shield_scans_total{scan_type="prompt",verdict="fail"} 1"#));
+        assert!(output.contains(r#"llm_shield_risk_score_bucket{scan_type="prompt",le="0.9"} 1"#));
+        assert!(output.contains(r#"llm_shield_rate_limit_rejections_total{reason="quota"} 1"#));
+        assert!(output.contains(r#"llm_shield_auth_failures_total{reason="missing"} 1"#));
+        assert!(output.contains("llm_shield_cache_hit_ratio 0.75"));
+        // Tenants are only recorded with detailed labels
+        assert!(!output.contains("tenant-a"));
+    }
+
+    #[test]
+    fn test_in_flight_released_on_drop() {
+        let recorder = recorder_builder().build_recorder();
+        let handle = recorder.handle();
+
+        metrics::with_local_recorder(&recorder, || {
+            let first = InFlightGuard::new("/v1/scan/prompt".to_string());
+            let second = InFlightGuard::new("/v1/scan/prompt".to_string());
+            drop(first);
+            assert!(handle
+                .render()
+                .contains(r#"llm_shield_http_requests_in_flight{route="/v1/scan/prompt"} 1"#));
+            // A cancelled request drops its guard without reaching the end of the middleware
+            drop(second);
+        });
+
+        assert!(handle
+            .render()
+            .contains(r#"llm_shield_http_requests_in_flight{route="/v1/scan/prompt"} 0"#));
+    }
+}
@@ -4,6 +4,7 @@ use axum::{middleware, routing::{get, post}, Router};
 
 use crate::handlers;
 use crate::middleware::{execution_context_middleware, gateway_middleware};
+use crate::observability;
 use crate::state::AppState;
 
 /// Create the application router
@@ -28,30 +29,45 @@ pub fn create_router() -> Router {
 ///
 /// Scan routes are guarded by two middleware layers:
 /// 1. **Gateway middleware**: Validates caller tokens (LLM-Security-Core enforcement).
-///    Only active when `GATEWAY_SHARED_SECRET` env var is set. No-op otherwise.
+///    Only active when `auth.gateway_secret` or the `GATEWAY_SHARED_SECRET` env var
+///    is set. No-op otherwise.
 /// 2. **Execution context middleware**: Validates `x-execution-id` and `x-parent-span-id`.
 ///    Rejects with 400 if either is missing. Creates a repo-level ExecutionSpan.
 ///
 /// Health/version/scanner-list probes are NOT guarded (infrastructure routes).
+///
+/// When metrics are enabled, the Prometheus endpoint is mounted at
+/// `observability.metrics.path` and every route is instrumented.
 pub fn create_router_with_state(state: AppState) -> Router {
     // Scan routes: require gateway token + execution context
     let scan_routes = Router::new()
         .route("/v1/scan/prompt", post(handlers::scan_prompt))
         .route("/v1/scan/output", post(handlers::scan_output))
         .route("/v1/scan/batch", post(handlers::scan_batch))
+        .route("/v1/scan/conversation", post(handlers::scan_conversation))
+        .route("/v1/scan/stream", post(handlers::scan_stream))
         .layer(middleware::from_fn(execution_context_middleware))
-        .layer(middleware::from_fn(gateway_middleware));
+        .layer(middleware::from_fn_with_state(state.clone(), gateway_middleware));
 
     // Infrastructure routes: no execution context required
-    Router::new()
+    let mut router = Router::new()
         .route("/health", get(handlers::health))
         .route("/health/ready", get(handlers::ready))
         .route("/health/live", get(handlers::live))
-        .route("/version", get(handlers::version))
+        .route("/version", get(handlers::version_info))
         .route("/v1/scanners", get(handlers::list_scanners))
         .route("/api/v1/scan", post(handlers::ingest_scan))
-        .merge(scan_routes)
-        .with_state(state)
+        .merge(scan_routes);
+
+    // Prometheus metrics: served at the configured path, recorded for every route
+    let metrics_config = &state.config.observability.metrics;
+    if observability::install_metrics(metrics_config).is_some() {
+        router = router
+            .route(&metrics_config.path, get(observability::metrics_handler))
+            .route_layer(middleware::from_fn(observability::metrics_middleware));
+    }
+
+    router.with_state(state)
 }
 
 #[cfg(test)]
@@ -140,4 +156,29 @@ mod tests {
 
         assert_eq!(response.status(), StatusCode::NOT_FOUND);
     }
+
+    #[tokio::test]
+    async fn test_metrics_route() {
+        use crate::config::AppConfig;
+
+        let app = create_router_with_state(AppState::new(AppConfig::default()));
+
+        let response = app
+            .clone()
+            .oneshot(Request::builder().uri("/v1/scanners").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = app
+            .oneshot(Request::builder().uri("/metrics").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = String::from_utf8(body.to_vec()).unwrap();
+        assert!(body.contains(r#"llm_shield_http_requests_total{route="/v1/scanners",status="2xx"}"#));
+        assert!(body.contains("llm_shield_scanner_set_version 1"));
+    }
 }
@@ -0,0 +1,324 @@
+//! `secret://` references in configuration
+//!
+//! Secret configuration values ([`AppConfig::secret_fields_mut`] and the
+//! string parameters of pipeline scanners) may name a secret of the
+//! configured cloud secret manager instead of holding it:
+//! `secret://<provider>/<name>`, e.g. `secret://aws/prod/jwt` or
+//! `secret://local/gateway`. `<provider>` must be the configured
+//! `cloud.provider`; `<name>` is passed to the secret manager as is.
+//!
+//! References are resolved when the application state is built and re-read
+//! every `cloud.secret_refs.ttl_secs` by
+//! [`AppState::watch_secrets`](crate::state::AppState::watch_secrets), which
+//! hands rotated values to the components using them. Values are cached by
+//! the secret manager only, so a rotation is picked up at the first refresh
+//! after the manager's own cache expires.
+
+use std::sync::{Arc, RwLock};
+
+#[cfg(feature = "cloud")]
+use crate::config::{parse_secret_uri, pipeline_secret_fields_mut, AppConfig, ConfigError, Result};
+#[cfg(feature = "cloud")]
+use llm_shield_cloud::CloudSecretManager;
+#[cfg(feature = "cloud")]
+use llm_shield_scanners::PipelineConfig;
+#[cfg(feature = "cloud")]
+use std::time::Duration;
+
+/// Prefix of the config paths of pipeline scanner parameters
+#[cfg(feature = "cloud")]
+const SCANNERS_PATH: &str = "scanners.";
+
+/// A secret shared by the request handlers that can be replaced at runtime
+///
+/// Clones see replacements.
+#[derive(Clone, Default)]
+pub struct SharedSecret(Arc<RwLock<Option<String>>>);
+
+impl SharedSecret {
+    /// Secret holding `value`; `None` or an empty value means no secret
+    pub fn new(value: Option<String>) -> Self {
+        let secret = Self::default();
+        secret.set(value);
+        secret
+    }
+
+    /// Current value
+    pub fn get(&self) -> Option<String> {
+        self.0.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Replace the value; `None` or an empty value clears it
+    pub fn set(&self, value: Option<String>) {
+        *self.0.write().unwrap_or_else(|e| e.into_inner()) = value.filter(|v| !v.is_empty());
+    }
+}
+
+impl std::fmt::Debug for SharedSecret {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let value = if self.get().is_some() { "[REDACTED]" } else { "None" };
+        f.debug_tuple("SharedSecret").field(&value).finish()
+    }
+}
+
+/// A config value resolved from a reference
+#[cfg(feature = "cloud")]
+struct SecretField {
+    path: String,
+    uri: String,
+    value: String,
+}
+
+/// Resolves `secret://` references and detects rotated values
+#[cfg(feature = "cloud")]
+pub struct ConfigSecrets {
+    manager: Arc<dyn CloudSecretManager>,
+    interval: Duration,
+    fields: RwLock<Vec<SecretField>>,
+}
+
+#[cfg(feature = "cloud")]
+impl ConfigSecrets {
+    /// Resolve references through `manager`, re-reading them every `interval`
+    pub fn new(manager: Arc<dyn CloudSecretManager>, interval: Duration) -> Self {
+        Self {
+            manager,
+            interval,
+            fields: RwLock::new(Vec::new()),
+        }
+    }
+
+    /// How often references are re-read
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Value of a `secret://` reference
+    pub async fn resolve(&self, uri: &str) -> Result<String> {
+        let (_, name) = parse_secret_uri(uri).ok_or_else(|| {
+            ConfigError::ValidationError(format!("invalid secret reference {}", uri))
+        })?;
+        let value = self
+            .manager
+            .get_secret(name)
+            .await
+            .map_err(|e| ConfigError::LoadError(format!("{}: {}", uri, e)))?;
+        String::from_utf8(value.as_bytes().to_vec())
+            .map_err(|_| ConfigError::LoadError(format!("{}: secret is not valid UTF-8", uri)))
+    }
+
+    /// Replace the references in `config` with their values and validate it
+    pub async fn resolve_config(&self, config: &mut AppConfig) -> Result<()> {
+        config.validate()?;
+
+        let mut fields = Vec::new();
+        for (path, value) in config.secret_fields_mut() {
+            let Some(uri) = value.clone().filter(|v| parse_secret_uri(v).is_some()) else {
+                continue;
+            };
+            let resolved = self.resolve(&uri).await?;
+            *value = Some(resolved.clone());
+            fields.push(SecretField {
+                path: path.to_string(),
+                uri,
+                value: resolved,
+            });
+        }
+
+        // Checks that depend on the values, e.g. the audit key length
+        config.validate()?;
+        self.replace_fields(|path| !path.starts_with(SCANNERS_PATH), fields);
+        Ok(())
+    }
+
+    /// Replace the references among the scanner parameters of `pipeline`
+    /// with their values
+    ///
+    /// They replace the references of the previously resolved pipeline.
+    pub async fn resolve_pipeline(&self, pipeline: &mut PipelineConfig) -> Result<()> {
+        let mut fields = Vec::new();
+        for (path, value) in pipeline_secret_fields_mut(pipeline) {
+            if parse_secret_uri(value).is_none() {
+                continue;
+            }
+            let uri = std::mem::take(value);
+            *value = self.resolve(&uri).await?;
+            fields.push(SecretField {
+                path,
+                uri,
+                value: value.clone(),
+            });
+        }
+
+        self.replace_fields(|path| path.starts_with(SCANNERS_PATH), fields);
+        Ok(())
+    }
+
+    /// Config paths resolved from references
+    pub fn paths(&self) -> Vec<String> {
+        self.fields
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .map(|field| field.path.clone())
+            .collect()
+    }
+
+    /// Re-read every reference, returning the config paths whose value
+    /// changed with their new value
+    pub async fn refresh(&self) -> Vec<(String, String)> {
+        let references: Vec<(String, String)> = self
+            .fields
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .map(|field| (field.path.clone(), field.uri.clone()))
+            .collect();
+
+        let mut values = Vec::new();
+        for (path, uri) in references {
+            match self.resolve(&uri).await {
+                Ok(value) => values.push((path, value)),
+                Err(e) => {
+                    tracing::warn!(path = %path, error = %e, "Secret refresh failed, keeping current value")
+                }
+            }
+        }
+
+        let mut fields = self.fields.write().unwrap_or_else(|e| e.into_inner());
+        values.retain(|(path, value)| {
+            // Fields replaced in the meantime are left to the next refresh
+            let Some(field) = fields.iter_mut().find(|field| field.path == *path) else {
+                return false;
+            };
+            if field.value == *value {
+                return false;
+            }
+            field.value = value.clone();
+            tracing::info!(path = %path, "Secret rotated");
+            true
+        });
+        values
+    }
+
+    fn replace_fields<F>(&self, replaced: F, new: Vec<SecretField>)
+    where
+        F: Fn(&str) -> bool,
+    {
+        let mut fields = self.fields.write().unwrap_or_else(|e| e.into_inner());
+        fields.retain(|field| !replaced(&field.path));
+        fields.extend(new);
+    }
+}
+
+#[cfg(feature = "cloud")]
+impl std::fmt::Debug for ConfigSecrets {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ConfigSecrets")
+            .field("paths", &self.paths())
+            .field("interval", &self.interval)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shared_secret_replaced_for_clones() {
+        let secret = SharedSecret::new(Some("one".to_string()));
+        let clone = secret.clone();
+        clone.set(Some("two".to_string()));
+        assert_eq!(secret.get().as_deref(), Some("two"));
+        assert!(!format!("{:?}", secret).contains("two"));
+
+        secret.set(Some(String::new()));
+        assert_eq!(clone.get(), None);
+        assert_eq!(SharedSecret::new(Some(String::new())).get(), None);
+    }
+
+    #[cfg(feature = "cloud")]
+    #[tokio::test]
+    async fn test_resolve_and_rotate_config_secrets() {
+        use crate::config::CloudProvider;
+        use llm_shield_cloud::{LocalSecretManager, SecretValue};
+
+        let dir =
+            std::env::temp_dir().join(format!("llm-shield-secret-refs-{}", std::process::id()));
+        let manager = Arc::new(LocalSecretManager::new(&dir).await.unwrap());
+        let secret = |value: &str| SecretValue::from_string(value.to_string());
+        for (name, value) in [("gateway", "gw-1"), ("audit-key", "too-short"), ("scanner", "s-1")] {
+            manager.create_secret(name, &secret(value)).await.unwrap();
+        }
+
+        let mut config = AppConfig::default();
+        config.cloud.enabled = true;
+        config.cloud.provider = CloudProvider::Local;
+        config.cloud.local.secrets.enabled = true;
+        config.cloud.local.secrets.path = Some(dir.display().to_string());
+        config.auth.gateway_secret = Some("secret://local/gateway".to_string());
+        config.streaming.upstream_api_key = Some("sk-plain".to_string());
+
+        let secrets = ConfigSecrets::new(manager.clone(), Duration::from_secs(60));
+        secrets.resolve_config(&mut config).await.unwrap();
+        assert_eq!(config.auth.gateway_secret.as_deref(), Some("gw-1"));
+        assert_eq!(
+            config.streaming.upstream_api_key.as_deref(),
+            Some("sk-plain")
+        );
+        assert_eq!(secrets.paths(), vec!["auth.gateway_secret"]);
+
+        // Scanner parameters
+        let mut pipeline = PipelineConfig::from_yaml_str(
+            "input:\n  - type: ban_substrings\n    params:\n      substrings: [plain, \"secret://local/scanner\"]\n",
+        )
+        .unwrap();
+        secrets.resolve_pipeline(&mut pipeline).await.unwrap();
+        assert_eq!(
+            pipeline.input[0].params["substrings"],
+            serde_json::json!(["plain", "s-1"])
+        );
+        assert_eq!(
+            secrets.paths(),
+            vec!["auth.gateway_secret", "scanners.input[0].params.substrings[1]"]
+        );
+
+        // The secret manager is read on every refresh
+        assert!(secrets.refresh().await.is_empty());
+        manager
+            .update_secret("gateway", &secret("gw-2"))
+            .await
+            .unwrap();
+        manager
+            .update_secret("scanner", &secret("s-2"))
+            .await
+            .unwrap();
+        assert_eq!(
+            secrets.refresh().await,
+            vec![
+                ("auth.gateway_secret".to_string(), "gw-2".to_string()),
+                (
+                    "scanners.input[0].params.substrings[1]".to_string(),
+                    "s-2".to_string()
+                ),
+            ]
+        );
+        assert!(secrets.refresh().await.is_empty());
+
+        // Resolved values are validated
+        config.audit.enabled = true;
+        config.audit.signing_key = Some("secret://local/audit-key".to_string());
+        assert!(secrets.resolve_config(&mut config).await.is_err());
+
+        config.audit.signing_key = Some("secret://local/missing".to_string());
+        let err = secrets.resolve_config(&mut config).await.unwrap_err();
+        assert!(
+            err.to_string().contains("secret://local/missing"),
+            "{}",
+            err
+        );
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,242 @@
+//! Conversation scanning service
+//!
+//! Routes conversation messages to input or output scanners by role and
+//! keeps per-conversation scan state between requests, so a client that
+//! sends the full history with a `conversationId` only pays for new turns.
+
+use crate::models::{ApiError, ScannerResult};
+use crate::services::{ScanKind, ScannerService};
+use llm_shield_core::{
+    async_trait, ConversationState, MessageScanner, Role, ScanResult, Scanner, Vault,
+};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Scans conversation messages with the scanners for their role
+pub struct ConversationScanService {
+    service: ScannerService,
+    input: Vec<Arc<dyn Scanner>>,
+    output: Vec<Arc<dyn Scanner>>,
+    executed: Mutex<Vec<(ScanKind, ScannerResult)>>,
+}
+
+impl ConversationScanService {
+    /// Create a service scanning against `vault`
+    pub fn new(
+        vault: Vault,
+        input: Vec<Arc<dyn Scanner>>,
+        output: Vec<Arc<dyn Scanner>>,
+    ) -> Self {
+        Self {
+            service: ScannerService::with_vault(vault),
+            input,
+            output,
+            executed: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Per-scanner results of every scan run so far, with the scan kind
+    pub fn take_executed(&self) -> Vec<(ScanKind, ScannerResult)> {
+        self.executed
+            .lock()
+            .map(|mut executed| std::mem::take(&mut *executed))
+            .unwrap_or_default()
+    }
+
+    /// Underlying scanner service
+    pub fn scanner_service(&self) -> &ScannerService {
+        &self.service
+    }
+}
+
+#[async_trait]
+impl MessageScanner for ConversationScanService {
+    type Error = ApiError;
+
+    async fn scan_message(
+        &self,
+        role: Role,
+        text: &str,
+        _vault: &Vault,
+    ) -> Result<ScanResult, ApiError> {
+        // The scanner service shares storage with the conversation vault
+        let (kind, scanners) = if role.is_output() {
+            (ScanKind::Output, &self.output)
+        } else {
+            (ScanKind::Prompt, &self.input)
+        };
+
+        let mut results = Vec::with_capacity(scanners.len());
+        for scanner in scanners {
+            let (result, dto) = self
+                .service
+                .execute_scanner_with_result(Arc::clone(scanner), text)
+                .await
+                .map_err(ApiError::ScannerError)?;
+            results.push(result);
+            if let Ok(mut executed) = self.executed.lock() {
+                executed.push((kind, dto));
+            }
+        }
+
+        if results.is_empty() {
+            return Ok(ScanResult::pass(text.to_string()));
+        }
+        Ok(ScanResult::combine(results))
+    }
+}
+
+/// Scan state kept between requests for one conversation
+pub struct StoredConversation {
+    pub state: ConversationState,
+    pub vault: Vault,
+}
+
+/// TTL store of conversation scan state, keyed by tenant and conversation id
+pub struct ConversationStore {
+    ttl: Duration,
+    capacity: usize,
+    entries: Mutex<HashMap<String, (Instant, StoredConversation)>>,
+}
+
+impl ConversationStore {
+    /// Create a store whose entries expire `ttl` after their last update
+    pub fn new(ttl: Duration, capacity: usize) -> Self {
+        Self {
+            ttl,
+            capacity: capacity.max(1),
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Remove and return the state for `key`, if present and fresh
+    ///
+    /// The caller puts it back after scanning. Concurrent requests for the
+    /// same conversation each scan from whatever state they found.
+    pub fn take(&self, key: &str) -> Option<StoredConversation> {
+        let mut entries = self.entries.lock().ok()?;
+        match entries.remove(key) {
+            Some((updated_at, stored)) if updated_at.elapsed() < self.ttl => Some(stored),
+            _ => None,
+        }
+    }
+
+    /// Store the state for `key`
+    pub fn put(&self, key: String, stored: StoredConversation) {
+        let Ok(mut entries) = self.entries.lock() else {
+            return;
+        };
+
+        if entries.len() >= self.capacity {
+            entries.retain(|_, (updated_at, _)| updated_at.elapsed() < self.ttl);
+        }
+        if entries.len() >= self.capacity {
+            if let Some(oldest) = entries
+                .iter()
+                .min_by_key(|(_, (updated_at, _))| *updated_at)
+                .map(|(key, _)| key.clone())
+            {
+                entries.remove(&oldest);
+            }
+        }
+
+        entries.insert(key, (Instant::now(), stored));
+    }
+
+    /// Number of stored conversations
+    pub fn len(&self) -> usize {
+        self.entries.lock().map(|e| e.len()).unwrap_or(0)
+    }
+
+    /// Whether no conversations are stored
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use llm_shield_core::{Conversation, Message, ScannerType};
+
+    struct FlagScanner {
+        name: &'static str,
+        scanner_type: ScannerType,
+    }
+
+    #[async_trait]
+    impl Scanner for FlagScanner {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        async fn scan(&self, input: &str, _vault: &Vault) -> llm_shield_core::Result<ScanResult> {
+            if input.contains("bad") {
+                Ok(ScanResult::fail(input.to_string(), 0.9))
+            } else {
+                Ok(ScanResult::pass(input.to_string()))
+            }
+        }
+
+        fn scanner_type(&self) -> ScannerType {
+            self.scanner_type
+        }
+    }
+
+    #[tokio::test]
+    async fn test_routes_messages_by_role() {
+        let vault = Vault::new();
+        let service = ConversationScanService::new(
+            vault.clone(),
+            vec![Arc::new(FlagScanner {
+                name: "input",
+                scanner_type: ScannerType::Input,
+            })],
+            vec![Arc::new(FlagScanner {
+                name: "output",
+                scanner_type: ScannerType::Output,
+            })],
+        );
+
+        let conversation = Conversation::new()
+            .with_message(Message::user("hello"))
+            .with_message(Message::assistant("bad answer"));
+        let result = ConversationState::new()
+            .with_window(1)
+            .scan(&conversation, &service, &vault)
+            .await
+            .unwrap();
+
+        assert_eq!(result.triggered, vec![1]);
+        let executed = service.take_executed();
+        assert_eq!(executed.len(), 2);
+        assert_eq!(executed[0].0, ScanKind::Prompt);
+        assert_eq!(executed[1].0, ScanKind::Output);
+        assert_eq!(executed[1].1.scanner, "output");
+    }
+
+    #[test]
+    fn test_store_take_and_expiry() {
+        let store = ConversationStore::new(Duration::from_secs(60), 10);
+        store.put(
+            "a".to_string(),
+            StoredConversation {
+                state: ConversationState::new(),
+                vault: Vault::new(),
+            },
+        );
+        assert!(store.take("a").is_some());
+        assert!(store.take("a").is_none());
+
+        let expired = ConversationStore::new(Duration::ZERO, 10);
+        expired.put(
+            "a".to_string(),
+            StoredConversation {
+                state: ConversationState::new(),
+                vault: Vault::new(),
+            },
+        );
+        assert!(expired.take("a").is_none());
+    }
+}
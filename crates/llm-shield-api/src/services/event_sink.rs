@@ -0,0 +1,148 @@
+//! Scan event sink
+//!
+//! After each scan the handlers emit one [`ScanEvent`] per scanner to the
+//! configured [`ScanEventSink`], so that downstream consumers (such as the
+//! dashboard's Postgres writer) can reflect live traffic.
+//!
+//! Sinks are called on the request path and must not block: implementations
+//! are expected to buffer events and flush them in the background.
+
+use crate::models::ScannerResult;
+use axum::http::HeaderMap;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Header carrying the tenant a scan is attributed to
+pub const TENANT_HEADER: &str = "x-tenant-id";
+
+/// Which endpoint produced the scan
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ScanKind {
+    Prompt,
+    Output,
+}
+
+impl ScanKind {
+    /// Label value for metrics
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ScanKind::Prompt => "prompt",
+            ScanKind::Output => "output",
+        }
+    }
+}
+
+/// Outcome of a single scanner run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanEvent {
+    pub timestamp: DateTime<Utc>,
+    /// Tenant from the `x-tenant-id` header, if present
+    pub tenant_id: Option<String>,
+    pub kind: ScanKind,
+    pub scanner: String,
+    pub latency_ms: u64,
+    pub risk_score: f32,
+    pub is_valid: bool,
+    /// Risk factor descriptions reported by the scanner
+    #[serde(default)]
+    pub risk_factors: Vec<String>,
+}
+
+impl ScanEvent {
+    /// Build one event per scanner result
+    pub fn from_results(
+        tenant_id: Option<&str>,
+        kind: ScanKind,
+        results: &[ScannerResult],
+    ) -> Vec<ScanEvent> {
+        let timestamp = Utc::now();
+        results
+            .iter()
+            .map(|result| ScanEvent {
+                timestamp,
+                tenant_id: tenant_id.map(str::to_string),
+                kind,
+                scanner: result.scanner.clone(),
+                latency_ms: result.execution_time_ms.unwrap_or(0),
+                risk_score: result.risk_score,
+                is_valid: result.is_valid,
+                risk_factors: result
+                    .risk_factors
+                    .iter()
+                    .map(|rf| rf.description.clone())
+                    .collect(),
+            })
+            .collect()
+    }
+}
+
+/// Destination for scan outcomes
+pub trait ScanEventSink: Send + Sync {
+    /// Record events; must return without waiting on I/O
+    fn record(&self, events: Vec<ScanEvent>);
+}
+
+/// Extract the tenant from request headers
+pub fn tenant_from_headers(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get(TENANT_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::RiskFactorDto;
+
+    #[test]
+    fn test_events_from_results() {
+        let results = vec![
+            ScannerResult {
+                scanner: "toxicity".to_string(),
+                is_valid: false,
+                risk_score: 0.9,
+                risk_factors: vec![RiskFactorDto {
+                    description: "Toxic language".to_string(),
+                    severity: "High".to_string(),
+                    score: 0.9,
+                    metadata: None,
+                }],
+                entities: vec![],
+                execution_time_ms: Some(12),
+            },
+            ScannerResult {
+                scanner: "secrets".to_string(),
+                is_valid: true,
+                risk_score: 0.0,
+                risk_factors: vec![],
+                entities: vec![],
+                execution_time_ms: None,
+            },
+        ];
+
+        let events = ScanEvent::from_results(Some("tenant-a"), ScanKind::Prompt, &results);
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].tenant_id.as_deref(), Some("tenant-a"));
+        assert_eq!(events[0].latency_ms, 12);
+        assert!(!events[0].is_valid);
+        assert_eq!(events[0].risk_factors, vec!["Toxic language"]);
+        assert_eq!(events[1].latency_ms, 0);
+        assert_eq!(events[0].timestamp, events[1].timestamp);
+    }
+
+    #[test]
+    fn test_tenant_from_headers() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(tenant_from_headers(&headers), None);
+
+        headers.insert(TENANT_HEADER, " ".parse().unwrap());
+        assert_eq!(tenant_from_headers(&headers), None);
+
+        headers.insert(TENANT_HEADER, "tenant-a".parse().unwrap());
+        assert_eq!(tenant_from_headers(&headers).as_deref(), Some("tenant-a"));
+    }
+}
@@ -1,5 +1,11 @@
 //! Business logic services
 
+pub mod conversation_service;
+pub mod event_sink;
 pub mod scanner_service;
+pub mod stream_proxy;
 
+pub use conversation_service::{ConversationScanService, ConversationStore, StoredConversation};
+pub use event_sink::{ScanEvent, ScanEventSink, ScanKind};
 pub use scanner_service::ScannerService;
+pub use stream_proxy::{extract_text, SseParser};
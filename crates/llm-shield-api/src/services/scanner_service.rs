@@ -1,7 +1,13 @@
 //! Scanner service for executing scans
 
-use crate::models::{EntityDto, RiskFactorDto, ScanResponse, ScannerResult};
-use llm_shield_core::{Scanner, ScanResult, Vault};
+use crate::models::{
+    ConversationScanResponse, EntityDto, MessageScanDto, RiskFactorDto, ScanDetailDto,
+    ScanResponse, ScannerResult, StreamChunkEvent,
+};
+use crate::observability::prometheus;
+use llm_shield_core::{
+    ConversationScanResult, Entity, RiskFactor, Scanner, ScanResult, StreamChunk, Vault,
+};
 use std::sync::Arc;
 use std::time::Instant;
 
@@ -18,22 +24,42 @@ impl ScannerService {
         }
     }
 
+    /// Create scanner service with a pre-populated vault
+    pub fn with_vault(vault: Vault) -> Self {
+        Self {
+            vault: Arc::new(vault),
+        }
+    }
+
     /// Execute a single scanner
     pub async fn execute_scanner(
         &self,
         scanner: Arc<dyn Scanner>,
         input: &str,
     ) -> Result<ScannerResult, String> {
+        self.execute_scanner_with_result(scanner, input)
+            .await
+            .map(|(_, result)| result)
+    }
+
+    /// Execute a single scanner, returning the raw result alongside the DTO
+    pub async fn execute_scanner_with_result(
+        &self,
+        scanner: Arc<dyn Scanner>,
+        input: &str,
+    ) -> Result<(ScanResult, ScannerResult), String> {
         let start = Instant::now();
 
-        let scan_result = scanner
-            .scan(input, &self.vault)
-            .await
-            .map_err(|e| format!("Scanner execution failed: {}", e))?;
+        let scan_result = scanner.scan(input, &self.vault).await;
+        let elapsed = start.elapsed();
+        prometheus::record_scanner_execution(scanner.name(), elapsed, scan_result.is_err());
 
-        let execution_time_ms = start.elapsed().as_millis() as u64;
+        let scan_result =
+            scan_result.map_err(|e| format!("Scanner execution failed: {}", e))?;
+        let execution_time_ms = elapsed.as_millis() as u64;
 
-        Ok(self.convert_scan_result(scanner.name(), scan_result, Some(execution_time_ms)))
+        let dto = self.convert_scan_result(scanner.name(), scan_result.clone(), Some(execution_time_ms));
+        Ok((scan_result, dto))
     }
 
     /// Execute multiple scanners in sequence
@@ -84,6 +110,49 @@ impl ScannerService {
         }
     }
 
+    /// Create conversation scan response from a conversation result
+    pub fn create_conversation_response(
+        &self,
+        result: ConversationScanResult,
+        scan_time_ms: u64,
+    ) -> ConversationScanResponse {
+        let scanned_messages = result.scanned();
+
+        let messages = result
+            .messages
+            .iter()
+            .map(|m| MessageScanDto {
+                index: m.index,
+                role: m.role,
+                detail: self.convert_detail(&m.result),
+                cached: m.cached,
+            })
+            .collect();
+
+        ConversationScanResponse {
+            is_valid: result.is_valid,
+            risk_score: result.risk_score,
+            messages,
+            cross_turn: result.cross_turn.as_ref().map(|r| self.convert_detail(r)),
+            triggered_messages: result.triggered,
+            scanned_messages,
+            scan_time_ms,
+        }
+    }
+
+    /// Create a stream event from a streaming scanner chunk
+    pub fn create_stream_event(&self, chunk: StreamChunk) -> StreamChunkEvent {
+        StreamChunkEvent {
+            risk_factors: convert_risk_factors(&chunk.risk_factors),
+            entities: convert_entities(&chunk.entities),
+            text: chunk.text,
+            is_valid: chunk.is_valid,
+            risk_score: chunk.risk_score,
+            blocked: chunk.blocked,
+            done: chunk.done,
+        }
+    }
+
     /// Convert ScanResult to ScannerResult DTO
     fn convert_scan_result(
         &self,
@@ -91,40 +160,51 @@ impl ScannerService {
         scan_result: ScanResult,
         execution_time_ms: Option<u64>,
     ) -> ScannerResult {
-        // Convert risk factors
-        let risk_factors = scan_result
-            .risk_factors
-            .iter()
-            .map(|rf| RiskFactorDto {
-                description: rf.description.clone(),
-                severity: format!("{:?}", rf.severity),
-                score: rf.score_contribution,
-                metadata: None, // RiskFactor doesn't have metadata field
-            })
-            .collect();
-
-        // Convert entities
-        let entities = scan_result
-            .entities
-            .iter()
-            .map(|e| EntityDto {
-                entity_type: e.entity_type.clone(),
-                text: e.text.clone(),
-                start: e.start,
-                end: e.end,
-                confidence: Some(e.confidence),
-            })
-            .collect();
-
         ScannerResult {
             scanner: scanner_name.to_string(),
             is_valid: scan_result.is_valid,
             risk_score: scan_result.risk_score,
-            risk_factors,
-            entities,
+            risk_factors: convert_risk_factors(&scan_result.risk_factors),
+            entities: convert_entities(&scan_result.entities),
             execution_time_ms,
         }
     }
+
+    /// Convert an aggregated ScanResult to ScanDetailDto
+    fn convert_detail(&self, scan_result: &ScanResult) -> ScanDetailDto {
+        ScanDetailDto {
+            is_valid: scan_result.is_valid,
+            risk_score: scan_result.risk_score,
+            sanitized_text: scan_result.sanitized_text.clone(),
+            risk_factors: convert_risk_factors(&scan_result.risk_factors),
+            entities: convert_entities(&scan_result.entities),
+        }
+    }
+}
+
+fn convert_risk_factors(risk_factors: &[RiskFactor]) -> Vec<RiskFactorDto> {
+    risk_factors
+        .iter()
+        .map(|rf| RiskFactorDto {
+            description: rf.description.clone(),
+            severity: format!("{:?}", rf.severity),
+            score: rf.score_contribution,
+            metadata: None, // RiskFactor doesn't have metadata field
+        })
+        .collect()
+}
+
+fn convert_entities(entities: &[Entity]) -> Vec<EntityDto> {
+    entities
+        .iter()
+        .map(|e| EntityDto {
+            entity_type: e.entity_type.clone(),
+            text: e.text.clone(),
+            start: e.start,
+            end: e.end,
+            confidence: Some(e.confidence),
+        })
+        .collect()
 }
 
 impl Default for ScannerService {
@@ -0,0 +1,168 @@
+//! Upstream token stream proxy
+//!
+//! Parses the server-sent event stream of an upstream LLM and extracts the
+//! generated text of each event, so it can be fed through a
+//! [`StreamingScanner`](llm_shield_core::StreamingScanner) before it reaches
+//! the client.
+
+use crate::config::StreamingConfig;
+use serde_json::Value;
+
+/// Terminal `data:` payload sent by OpenAI-compatible upstreams
+const DONE_MARKER: &str = "[DONE]";
+
+/// Incremental parser for a `text/event-stream` body
+///
+/// Bytes may arrive split anywhere, including inside a UTF-8 sequence or a
+/// line, so incomplete input is kept until the event is terminated.
+#[derive(Debug, Default)]
+pub struct SseParser {
+    buffer: Vec<u8>,
+    data: Vec<String>,
+    done: bool,
+}
+
+impl SseParser {
+    /// Create an empty parser
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether the upstream sent its terminal marker
+    pub fn is_done(&self) -> bool {
+        self.done
+    }
+
+    /// Feed raw bytes, returning the `data` payload of each completed event
+    pub fn push(&mut self, bytes: &[u8]) -> Vec<String> {
+        self.buffer.extend_from_slice(bytes);
+
+        let mut events = Vec::new();
+        while let Some(newline) = self.buffer.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = self.buffer.drain(..=newline).collect();
+            let line = String::from_utf8_lossy(&line);
+            let line = line.trim_end_matches(['\n', '\r']);
+
+            if line.is_empty() {
+                if let Some(event) = self.dispatch() {
+                    events.push(event);
+                }
+            } else if let Some(value) = line.strip_prefix("data:") {
+                self.data
+                    .push(value.strip_prefix(' ').unwrap_or(value).to_string());
+            }
+            // Comments, `event:`, `id:` and `retry:` fields carry no text
+        }
+        events
+    }
+
+    /// Flush a final event not terminated by a blank line
+    pub fn finish(&mut self) -> Option<String> {
+        let rest = std::mem::take(&mut self.buffer);
+        let rest = String::from_utf8_lossy(&rest);
+        if let Some(value) = rest.trim_end().strip_prefix("data:") {
+            self.data
+                .push(value.strip_prefix(' ').unwrap_or(value).to_string());
+        }
+        self.dispatch()
+    }
+
+    fn dispatch(&mut self) -> Option<String> {
+        if self.data.is_empty() || self.done {
+            self.data.clear();
+            return None;
+        }
+        let data = self.data.join("\n");
+        self.data.clear();
+        if data == DONE_MARKER {
+            self.done = true;
+            return None;
+        }
+        Some(data)
+    }
+}
+
+/// Extract generated text from one upstream event payload
+///
+/// Understands OpenAI chat (`choices[0].delta.content`) and completion
+/// (`choices[0].text`) chunks and Anthropic `content_block_delta` events.
+/// Other JSON payloads carry no text; non-JSON payloads are text themselves.
+pub fn extract_text(data: &str) -> Option<String> {
+    let Ok(value) = serde_json::from_str::<Value>(data) else {
+        return Some(data.to_string());
+    };
+
+    let text = value
+        .pointer("/choices/0/delta/content")
+        .or_else(|| value.pointer("/choices/0/text"))
+        .or_else(|| value.pointer("/delta/text"))
+        .and_then(Value::as_str)?;
+
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+/// Open the upstream stream for `body`
+///
+/// `api_key` is the current `streaming.upstream_api_key`, which may have
+/// been rotated since the configuration was loaded.
+pub async fn open_upstream(
+    config: &StreamingConfig,
+    api_key: Option<&str>,
+    url: &str,
+    body: &Value,
+) -> Result<reqwest::Response, reqwest::Error> {
+    let client = reqwest::Client::builder()
+        .timeout(config.timeout())
+        .build()?;
+
+    let mut request = client
+        .post(url)
+        .header(reqwest::header::ACCEPT, "text/event-stream")
+        .json(body);
+    if let Some(key) = api_key {
+        request = request.bearer_auth(key);
+    }
+
+    request.send().await?.error_for_status()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parser_handles_split_events() {
+        let mut parser = SseParser::new();
+        assert!(parser.push(b"data: {\"choices\":[{\"delta\"").is_empty());
+
+        let events = parser.push(b":{\"content\":\"Hi\"}}]}\n\n: ping\n\ndata: second\r\n\r\n");
+        assert_eq!(events.len(), 2);
+        assert_eq!(extract_text(&events[0]).as_deref(), Some("Hi"));
+        assert_eq!(events[1], "second");
+
+        assert!(parser.push(b"data: [DONE]\n\n").is_empty());
+        assert!(parser.is_done());
+    }
+
+    #[test]
+    fn test_parser_flushes_unterminated_event() {
+        let mut parser = SseParser::new();
+        assert!(parser.push(b"data: tail").is_empty());
+        assert_eq!(parser.finish().as_deref(), Some("tail"));
+    }
+
+    #[test]
+    fn test_extract_text_formats() {
+        assert_eq!(
+            extract_text(r#"{"choices":[{"text":"done"}]}"#).as_deref(),
+            Some("done")
+        );
+        assert_eq!(
+            extract_text(r#"{"type":"content_block_delta","delta":{"type":"text_delta","text":"yo"}}"#)
+                .as_deref(),
+            Some("yo")
+        );
+        assert_eq!(extract_text(r#"{"type":"message_start"}"#), None);
+        assert_eq!(extract_text("plain").as_deref(), Some("plain"));
+    }
+}
@@ -0,0 +1,303 @@
+//! Batched background delivery of security events
+
+use super::format::{self, EncodedEvent};
+use super::transport::{FileTransport, SiemTransport, SyslogTransport, WebhookTransport};
+use super::{Result, SecurityEvent, SiemError};
+use crate::audit::{AuditDecision, Verdict};
+use crate::config::{SiemConfig, SiemFormat, SiemTransportKind};
+use std::time::Duration;
+use tokio::sync::{mpsc, oneshot};
+
+/// Batching and retry settings of an emitter
+#[derive(Debug, Clone)]
+pub struct BatchPolicy {
+    /// Maximum events per delivery
+    pub batch_size: usize,
+    /// Deliver queued events at least this often
+    pub flush_interval: Duration,
+    /// Retries of a failed delivery before its events are dropped
+    pub max_retries: u32,
+    /// Delay before the first retry, doubled on each further retry
+    pub retry_backoff: Duration,
+    /// Events buffered while deliveries are slow
+    pub queue_capacity: usize,
+}
+
+impl BatchPolicy {
+    pub fn from_config(config: &SiemConfig) -> Self {
+        Self {
+            batch_size: config.batch_size,
+            flush_interval: config.flush_interval(),
+            max_retries: config.max_retries,
+            retry_backoff: config.retry_backoff(),
+            queue_capacity: config.queue_capacity,
+        }
+    }
+}
+
+impl Default for BatchPolicy {
+    fn default() -> Self {
+        Self::from_config(&SiemConfig::default())
+    }
+}
+
+enum Command {
+    Emit(SecurityEvent),
+    Flush(oneshot::Sender<Result<()>>),
+    SetToken(String),
+}
+
+/// Handle to the SIEM emitter, shared by the request handlers
+///
+/// Events are queued without blocking and delivered by a background task
+/// once `batch_size` events are queued or every `flush_interval`. A failed
+/// delivery is retried with exponential backoff, so a batch may be delivered
+/// more than once. Events are dropped when the queue is full or retries are
+/// exhausted.
+#[derive(Clone)]
+pub struct SiemEmitter {
+    tx: mpsc::Sender<Command>,
+    min_risk_score: f32,
+}
+
+impl SiemEmitter {
+    /// Start delivering events encoded as `format` through `transport`
+    pub fn spawn(
+        transport: Box<dyn SiemTransport>,
+        format: SiemFormat,
+        min_risk_score: f32,
+        policy: BatchPolicy,
+    ) -> Self {
+        let (tx, rx) = mpsc::channel(policy.queue_capacity.max(1));
+        let worker = Worker {
+            transport,
+            format,
+            batch: Vec::new(),
+            policy,
+        };
+        tokio::spawn(worker.run(rx));
+        Self { tx, min_risk_score }
+    }
+
+    /// Start the configured transport
+    pub fn from_config(config: &SiemConfig) -> Result<Self> {
+        let transport: Box<dyn SiemTransport> = match config.transport {
+            SiemTransportKind::Syslog => Box::new(SyslogTransport::from_config(&config.syslog)?),
+            SiemTransportKind::File => Box::new(FileTransport::new(&config.file.path)),
+            SiemTransportKind::Webhook => Box::new(WebhookTransport::from_config(
+                &config.webhook,
+                config.format,
+            )?),
+        };
+        Ok(Self::spawn(
+            transport,
+            config.format,
+            config.min_risk_score,
+            BatchPolicy::from_config(config),
+        ))
+    }
+
+    /// Whether a scan outcome is reported: anything not allowed, and allowed
+    /// scans at or above the minimum risk score
+    pub fn is_reportable(&self, verdict: Verdict, risk_score: f32) -> bool {
+        verdict != Verdict::Allow || risk_score >= self.min_risk_score
+    }
+
+    /// Queue an event if it is reportable; never blocks
+    pub fn emit(&self, event: SecurityEvent) {
+        if !self.is_reportable(event.verdict, event.risk_score) {
+            return;
+        }
+        match self.tx.try_send(Command::Emit(event)) {
+            Ok(()) => {}
+            Err(mpsc::error::TrySendError::Full(_)) => {
+                tracing::warn!("SIEM event queue is full, security event dropped")
+            }
+            Err(mpsc::error::TrySendError::Closed(_)) => {
+                tracing::error!("SIEM emitter has stopped, security event dropped")
+            }
+        }
+    }
+
+    /// Queue the event of a scan decision if it is reportable
+    pub fn emit_decision(&self, decision: &AuditDecision) {
+        if self.is_reportable(decision.verdict, decision.risk_score) {
+            self.emit(SecurityEvent::from_decision(decision));
+        }
+    }
+
+    /// Deliver all queued events
+    pub async fn flush(&self) -> Result<()> {
+        let (reply, done) = oneshot::channel();
+        self.tx
+            .send(Command::Flush(reply))
+            .await
+            .map_err(|_| SiemError::Stopped)?;
+        done.await.map_err(|_| SiemError::Stopped)?
+    }
+
+    /// Authenticate later webhook deliveries with a rotated token
+    pub async fn set_webhook_token(&self, token: String) -> Result<()> {
+        self.tx
+            .send(Command::SetToken(token))
+            .await
+            .map_err(|_| SiemError::Stopped)
+    }
+}
+
+impl std::fmt::Debug for SiemEmitter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SiemEmitter")
+            .field("min_risk_score", &self.min_risk_score)
+            .finish_non_exhaustive()
+    }
+}
+
+struct Worker {
+    transport: Box<dyn SiemTransport>,
+    format: SiemFormat,
+    batch: Vec<EncodedEvent>,
+    policy: BatchPolicy,
+}
+
+impl Worker {
+    async fn run(mut self, mut rx: mpsc::Receiver<Command>) {
+        let mut ticker = tokio::time::interval(self.policy.flush_interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        ticker.tick().await;
+
+        loop {
+            tokio::select! {
+                command = rx.recv() => match command {
+                    Some(Command::Emit(event)) => {
+                        match format::encode(self.format, &event) {
+                            Ok(encoded) => self.batch.push(encoded),
+                            Err(e) => tracing::error!(error = %e, "Failed to encode security event"),
+                        }
+                        if self.batch.len() >= self.policy.batch_size {
+                            let _ = self.deliver().await;
+                        }
+                    }
+                    Some(Command::Flush(reply)) => {
+                        let _ = reply.send(self.deliver().await);
+                    }
+                    Some(Command::SetToken(token)) => self.transport.set_token(token),
+                    None => {
+                        let _ = self.deliver().await;
+                        return;
+                    }
+                },
+                _ = ticker.tick() => {
+                    let _ = self.deliver().await;
+                }
+            }
+        }
+    }
+
+    /// Send the queued batch, retrying with backoff; the batch is dropped
+    /// once retries are exhausted
+    async fn deliver(&mut self) -> Result<()> {
+        if self.batch.is_empty() {
+            return Ok(());
+        }
+
+        let mut backoff = self.policy.retry_backoff;
+        let mut attempt = 0;
+        loop {
+            match self.transport.send(&self.batch).await {
+                Ok(()) => {
+                    self.batch.clear();
+                    return Ok(());
+                }
+                Err(e) if attempt < self.policy.max_retries => {
+                    attempt += 1;
+                    tracing::warn!(transport = self.transport.name(), attempt, error = %e, "SIEM delivery failed, retrying");
+                    tokio::time::sleep(backoff).await;
+                    backoff *= 2;
+                }
+                Err(e) => {
+                    tracing::error!(
+                        transport = self.transport.name(),
+                        dropped = self.batch.len(),
+                        error = %e,
+                        "SIEM delivery failed, security events dropped"
+                    );
+                    self.batch.clear();
+                    return Err(e);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use std::sync::{Arc, Mutex};
+
+    /// Fails the first `failures` deliveries, then records batches
+    struct FlakyTransport {
+        failures: Arc<Mutex<u32>>,
+        batches: Arc<Mutex<Vec<usize>>>,
+    }
+
+    #[async_trait]
+    impl SiemTransport for FlakyTransport {
+        async fn send(&mut self, events: &[EncodedEvent]) -> Result<()> {
+            let mut failures = self.failures.lock().unwrap();
+            if *failures > 0 {
+                *failures -= 1;
+                return Err(SiemError::Webhook("unavailable".to_string()));
+            }
+            self.batches.lock().unwrap().push(events.len());
+            Ok(())
+        }
+
+        fn name(&self) -> &str {
+            "flaky"
+        }
+    }
+
+    fn decision(verdict: Verdict, risk_score: f32) -> AuditDecision {
+        let mut decision = AuditDecision::from_results("req-1", "scan_prompt", &[], String::new());
+        decision.verdict = verdict;
+        decision.risk_score = risk_score;
+        decision
+    }
+
+    #[tokio::test]
+    async fn test_emitter_batches_and_retries() {
+        let failures = Arc::new(Mutex::new(1));
+        let batches = Arc::new(Mutex::new(Vec::new()));
+        let transport = FlakyTransport {
+            failures: failures.clone(),
+            batches: batches.clone(),
+        };
+        let policy = BatchPolicy {
+            batch_size: 2,
+            flush_interval: Duration::from_secs(3600),
+            max_retries: 1,
+            retry_backoff: Duration::from_millis(1),
+            queue_capacity: 16,
+        };
+        let emitter = SiemEmitter::spawn(Box::new(transport), SiemFormat::Cef, 0.7, policy);
+
+        emitter.emit_decision(&decision(Verdict::Block, 0.2));
+        // Low-risk allowed scans are not reported
+        emitter.emit_decision(&decision(Verdict::Allow, 0.1));
+        emitter.emit_decision(&decision(Verdict::Allow, 0.8));
+        emitter.emit_decision(&decision(Verdict::Redact, 0.5));
+        emitter.flush().await.unwrap();
+
+        // The first batch succeeded on retry
+        assert_eq!(*batches.lock().unwrap(), vec![2, 1]);
+
+        // Retries exhausted: the batch is dropped and the flush fails
+        *failures.lock().unwrap() = 2;
+        emitter.emit_decision(&decision(Verdict::Block, 0.9));
+        assert!(emitter.flush().await.is_err());
+        emitter.flush().await.unwrap();
+        assert_eq!(batches.lock().unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,319 @@
+//! CEF, OCSF and ECS encodings of security events
+
+use super::{Result, SecurityEvent, Severity};
+use crate::audit::Verdict;
+use crate::config::SiemFormat;
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde_json::{json, Value};
+
+const VENDOR: &str = "LLM-Dev-Ops";
+const PRODUCT: &str = "LLM Shield";
+const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// OCSF schema version of emitted findings
+const OCSF_VERSION: &str = "1.3.0";
+/// ECS version of emitted documents
+const ECS_VERSION: &str = "8.11.0";
+
+/// An event encoded for delivery
+#[derive(Debug, Clone, PartialEq)]
+pub struct EncodedEvent {
+    pub timestamp: DateTime<Utc>,
+    pub severity: Severity,
+    /// Single-line CEF record or JSON document
+    pub payload: String,
+}
+
+/// Encode an event in the given format
+pub fn encode(format: SiemFormat, event: &SecurityEvent) -> Result<EncodedEvent> {
+    let payload = match format {
+        SiemFormat::Cef => cef(event),
+        SiemFormat::Ocsf => serde_json::to_string(&ocsf(event))?,
+        SiemFormat::Ecs => serde_json::to_string(&ecs(event))?,
+    };
+    Ok(EncodedEvent {
+        timestamp: event.timestamp,
+        severity: event.severity(),
+        payload,
+    })
+}
+
+/// CEF record:
+/// `CEF:0|LLM-Dev-Ops|LLM Shield|<version>|<operation>:<verdict>|<title>|<severity>|<extension>`
+pub fn cef(event: &SecurityEvent) -> String {
+    let severity = match event.severity() {
+        Severity::Low => 3,
+        Severity::Medium => 5,
+        Severity::High => 8,
+        Severity::Critical => 10,
+    };
+
+    let mut extension = vec![
+        ("rt", event.timestamp.timestamp_millis().to_string()),
+        ("act", verdict_name(event.verdict).to_string()),
+        ("externalId", event.request_id.clone()),
+    ];
+    if let Some(caller) = &event.caller_id {
+        extension.push(("suser", caller.clone()));
+    }
+    extension.push(("cfp1Label", "riskScore".to_string()));
+    extension.push(("cfp1", format!("{:.2}", event.risk_score)));
+    extension.push(("cs1Label", "scanners".to_string()));
+    extension.push(("cs1", event.scanners.join(",")));
+    if !event.triggered.is_empty() {
+        extension.push(("cs2Label", "triggered".to_string()));
+        extension.push(("cs2", event.triggered.join(",")));
+    }
+    if let Some(tenant) = &event.tenant_id {
+        extension.push(("cs3Label", "tenantId".to_string()));
+        extension.push(("cs3", tenant.clone()));
+    }
+    if let Some(execution) = &event.execution_id {
+        extension.push(("cs4Label", "executionId".to_string()));
+        extension.push(("cs4", execution.clone()));
+    }
+
+    let extension: Vec<String> = extension
+        .into_iter()
+        .map(|(key, value)| format!("{}={}", key, cef_extension_value(&value)))
+        .collect();
+    format!(
+        "CEF:0|{}|{}|{}|{}|{}|{}|{}",
+        cef_header(VENDOR),
+        cef_header(PRODUCT),
+        cef_header(VERSION),
+        cef_header(&format!(
+            "{}:{}",
+            event.operation,
+            verdict_name(event.verdict)
+        )),
+        cef_header(&event.title()),
+        severity,
+        extension.join(" ")
+    )
+}
+
+/// OCSF Detection Finding (class 2004)
+pub fn ocsf(event: &SecurityEvent) -> Value {
+    let severity_id = match event.severity() {
+        Severity::Low => 2,
+        Severity::Medium => 3,
+        Severity::High => 4,
+        Severity::Critical => 5,
+    };
+    let (action_id, action, disposition_id, disposition) = match event.verdict {
+        Verdict::Allow => (1, "Allowed", 15, "Detected"),
+        Verdict::Block => (2, "Denied", 2, "Blocked"),
+        Verdict::Redact => (4, "Modified", 11, "Corrected"),
+    };
+
+    let mut metadata = json!({
+        "version": OCSF_VERSION,
+        "product": {
+            "name": PRODUCT,
+            "vendor_name": VENDOR,
+            "version": VERSION,
+        },
+    });
+    insert_some(&mut metadata, "correlation_uid", &event.execution_id);
+    insert_some(&mut metadata, "tenant_uid", &event.tenant_id);
+
+    let mut finding = json!({
+        "class_uid": 2004,
+        "class_name": "Detection Finding",
+        "category_uid": 2,
+        "category_name": "Findings",
+        "activity_id": 1,
+        "activity_name": "Create",
+        "type_uid": 200401,
+        "type_name": "Detection Finding: Create",
+        "time": event.timestamp.timestamp_millis(),
+        "severity_id": severity_id,
+        "severity": event.severity().as_str(),
+        "status_id": 1,
+        "status": "New",
+        "action_id": action_id,
+        "action": action,
+        "disposition_id": disposition_id,
+        "disposition": disposition,
+        "message": event.title(),
+        "metadata": metadata,
+        "finding_info": {
+            "uid": event.request_id,
+            "title": event.title(),
+            "types": [event.operation],
+            "related_analytics": event
+                .triggered
+                .iter()
+                .map(|scanner| json!({ "name": scanner, "type_id": 1, "type": "Rule" }))
+                .collect::<Vec<_>>(),
+        },
+        "risk_score": risk_percent(event.risk_score),
+        "unmapped": {
+            "verdict": verdict_name(event.verdict),
+            "scanners": event.scanners,
+        },
+    });
+    if let Some(caller) = &event.caller_id {
+        finding["actor"] = json!({ "user": { "uid": caller } });
+    }
+    finding
+}
+
+/// Elastic Common Schema document
+pub fn ecs(event: &SecurityEvent) -> Value {
+    // Elastic's conventional severity values for low to critical
+    let severity = match event.severity() {
+        Severity::Low => 21,
+        Severity::Medium => 47,
+        Severity::High => 73,
+        Severity::Critical => 99,
+    };
+    let event_type = match event.verdict {
+        Verdict::Allow => "allowed",
+        Verdict::Block => "denied",
+        Verdict::Redact => "change",
+    };
+
+    let mut document = json!({
+        "@timestamp": event.timestamp.to_rfc3339_opts(SecondsFormat::Millis, true),
+        "ecs": { "version": ECS_VERSION },
+        "message": event.title(),
+        "event": {
+            "kind": "alert",
+            "category": ["intrusion_detection"],
+            "type": [event_type],
+            "action": event.operation,
+            "outcome": "success",
+            "severity": severity,
+            "risk_score": risk_percent(event.risk_score),
+            "id": event.request_id,
+            "provider": "llm-shield-api",
+            "dataset": "llm_shield.security",
+        },
+        "observer": {
+            "vendor": VENDOR,
+            "product": PRODUCT,
+            "version": VERSION,
+        },
+        "llm_shield": {
+            "operation": event.operation,
+            "verdict": verdict_name(event.verdict),
+            "risk_score": event.risk_score,
+            "scanners": event.scanners,
+            "triggered": event.triggered,
+        },
+    });
+    if let Some(caller) = &event.caller_id {
+        document["user"] = json!({ "id": caller });
+    }
+    if let Some(tenant) = &event.tenant_id {
+        document["organization"] = json!({ "id": tenant });
+    }
+    if let Some(execution) = &event.execution_id {
+        document["trace"] = json!({ "id": execution });
+    }
+    document
+}
+
+fn verdict_name(verdict: Verdict) -> &'static str {
+    match verdict {
+        Verdict::Allow => "allow",
+        Verdict::Redact => "redact",
+        Verdict::Block => "block",
+    }
+}
+
+fn risk_percent(risk_score: f32) -> u32 {
+    (risk_score.clamp(0.0, 1.0) * 100.0).round() as u32
+}
+
+fn insert_some(object: &mut Value, key: &str, value: &Option<String>) {
+    if let (Some(object), Some(value)) = (object.as_object_mut(), value) {
+        object.insert(key.to_string(), Value::String(value.clone()));
+    }
+}
+
+/// Escape a CEF header field
+fn cef_header(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('|', "\\|")
+        .replace(['\r', '\n'], " ")
+}
+
+/// Escape a CEF extension value
+fn cef_extension_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('=', "\\=")
+        .replace('\r', "\\r")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event() -> SecurityEvent {
+        SecurityEvent {
+            timestamp: Utc::now(),
+            request_id: "req=1".to_string(),
+            execution_id: Some("exec-1".to_string()),
+            caller_id: Some("key|abc".to_string()),
+            tenant_id: Some("tenant-a".to_string()),
+            operation: "scan_prompt".to_string(),
+            scanners: vec!["Secrets".to_string(), "Toxicity".to_string()],
+            triggered: vec!["Secrets".to_string()],
+            verdict: Verdict::Block,
+            risk_score: 0.95,
+        }
+    }
+
+    #[test]
+    fn test_cef_encoding() {
+        let record = cef(&event());
+        assert!(record.starts_with(&format!(
+            "CEF:0|LLM-Dev-Ops|LLM Shield|{}|scan_prompt:block|Prompt blocked|10|",
+            VERSION
+        )));
+        assert!(record.contains("act=block"));
+        assert!(record.contains(r"externalId=req\=1"));
+        // Pipes only need escaping in the header
+        assert!(record.contains("suser=key|abc"));
+        assert!(record.contains("cs2Label=triggered cs2=Secrets"));
+        assert!(record.contains("cs3=tenant-a"));
+        assert!(!record.contains('\n'));
+
+        assert_eq!(cef_header(r"a|b\c"), r"a\|b\\c");
+        assert_eq!(cef_extension_value("a=b\nc"), r"a\=b\nc");
+    }
+
+    #[test]
+    fn test_json_encodings() {
+        let finding = ocsf(&event());
+        assert_eq!(finding["class_uid"], 2004);
+        assert_eq!(finding["type_uid"], 200401);
+        assert_eq!(finding["severity_id"], 5);
+        assert_eq!(finding["disposition"], "Blocked");
+        assert_eq!(finding["risk_score"], 95);
+        assert_eq!(finding["actor"]["user"]["uid"], "key|abc");
+        assert_eq!(finding["metadata"]["tenant_uid"], "tenant-a");
+        assert_eq!(
+            finding["finding_info"]["related_analytics"][0]["name"],
+            "Secrets"
+        );
+
+        let document = ecs(&event());
+        assert_eq!(document["event"]["kind"], "alert");
+        assert_eq!(document["event"]["type"][0], "denied");
+        assert_eq!(document["event"]["severity"], 99);
+        assert_eq!(document["organization"]["id"], "tenant-a");
+        assert_eq!(document["trace"]["id"], "exec-1");
+        assert_eq!(document["llm_shield"]["triggered"][0], "Secrets");
+
+        let encoded = encode(SiemFormat::Ecs, &event()).unwrap();
+        assert_eq!(encoded.severity, Severity::Critical);
+        assert!(!encoded.payload.contains('\n'));
+    }
+}
@@ -0,0 +1,162 @@
+//! Security events for SIEM ingestion
+//!
+//! Blocked scans, and allowed scans at or above `siem.min_risk_score`, are
+//! turned into [`SecurityEvent`]s, encoded as CEF, OCSF or ECS
+//! ([`crate::config::SiemFormat`]) and delivered by a [`SiemTransport`]:
+//!
+//! - syslog (RFC 5424) over UDP, TCP or TLS
+//! - a local file, one event per line
+//! - a webhook receiving newline-delimited batches
+//!
+//! The [`SiemEmitter`] queues events without blocking the request, delivers
+//! them in batches from a background task and retries failed deliveries with
+//! exponential backoff. Like the audit log, events never contain scanned
+//! text.
+
+pub mod emitter;
+pub mod format;
+pub mod transport;
+
+pub use emitter::{BatchPolicy, SiemEmitter};
+pub use format::EncodedEvent;
+pub use transport::{FileTransport, SiemTransport, SyslogTransport, WebhookTransport};
+
+use crate::audit::{AuditDecision, Verdict};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use thiserror::Error;
+
+/// SIEM emission errors
+#[derive(Debug, Error)]
+pub enum SiemError {
+    #[error("SIEM I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("SIEM serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
+
+    #[error("SIEM TLS error: {0}")]
+    Tls(String),
+
+    #[error("SIEM webhook error: {0}")]
+    Webhook(String),
+
+    #[error("SIEM emitter has stopped")]
+    Stopped,
+}
+
+/// Result type for SIEM operations
+pub type Result<T> = std::result::Result<T, SiemError>;
+
+/// Severity of a security event
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+impl Severity {
+    /// Severity of a scan outcome; blocked scans are at least medium
+    pub fn of(verdict: Verdict, risk_score: f32) -> Self {
+        let severity = if risk_score >= 0.9 {
+            Severity::Critical
+        } else if risk_score >= 0.7 {
+            Severity::High
+        } else if risk_score >= 0.4 {
+            Severity::Medium
+        } else {
+            Severity::Low
+        };
+        if verdict == Verdict::Block {
+            severity.max(Severity::Medium)
+        } else {
+            severity
+        }
+    }
+
+    /// Display name
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Severity::Low => "Low",
+            Severity::Medium => "Medium",
+            Severity::High => "High",
+            Severity::Critical => "Critical",
+        }
+    }
+}
+
+/// A blocked or high-risk scan
+#[derive(Debug, Clone, PartialEq)]
+pub struct SecurityEvent {
+    pub timestamp: DateTime<Utc>,
+    /// `x-request-id` of the request, or a generated ID
+    pub request_id: String,
+    /// `x-execution-id` of the request
+    pub execution_id: Option<String>,
+    /// Authenticated API key or gateway caller
+    pub caller_id: Option<String>,
+    pub tenant_id: Option<String>,
+    /// Scan operation, e.g. `scan_prompt`
+    pub operation: String,
+    /// Scanners that ran
+    pub scanners: Vec<String>,
+    /// Scanners that failed, when known
+    pub triggered: Vec<String>,
+    pub verdict: Verdict,
+    pub risk_score: f32,
+}
+
+impl SecurityEvent {
+    /// Event of a recorded scan decision
+    pub fn from_decision(decision: &AuditDecision) -> Self {
+        Self {
+            timestamp: Utc::now(),
+            request_id: decision.request_id.clone(),
+            execution_id: decision.execution_id.clone(),
+            caller_id: decision.caller_id.clone(),
+            tenant_id: decision.tenant_id.clone(),
+            operation: decision.operation.clone(),
+            scanners: decision.scanners.clone(),
+            triggered: decision.triggered.clone(),
+            verdict: decision.verdict,
+            risk_score: decision.risk_score,
+        }
+    }
+
+    pub fn severity(&self) -> Severity {
+        Severity::of(self.verdict, self.risk_score)
+    }
+
+    /// Short description, e.g. `Prompt blocked`
+    pub fn title(&self) -> String {
+        let subject = match self.operation.as_str() {
+            "scan_prompt" | "scan_batch" => "Prompt",
+            "scan_output" => "Output",
+            "scan_conversation" => "Conversation",
+            "scan_stream" => "Stream",
+            _ => "Scan",
+        };
+        let outcome = match self.verdict {
+            Verdict::Block => "blocked",
+            Verdict::Redact => "redacted",
+            Verdict::Allow => "flagged as high risk",
+        };
+        format!("{} {}", subject, outcome)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_severity() {
+        assert_eq!(Severity::of(Verdict::Allow, 0.1), Severity::Low);
+        assert_eq!(Severity::of(Verdict::Block, 0.1), Severity::Medium);
+        assert_eq!(Severity::of(Verdict::Allow, 0.75), Severity::High);
+        assert_eq!(Severity::of(Verdict::Block, 0.95), Severity::Critical);
+    }
+}
@@ -0,0 +1,536 @@
+//! Delivery of encoded events
+
+use super::format::EncodedEvent;
+use super::{Result, Severity, SiemError};
+use crate::config::siem::{SiemWebhookConfig, SyslogConfig};
+use crate::config::{SiemFormat, SyslogProtocol};
+use async_trait::async_trait;
+use chrono::SecondsFormat;
+use std::path::PathBuf;
+use std::time::Duration;
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+use tokio::net::{TcpStream, UdpSocket};
+
+/// Maximum HOSTNAME length in RFC 5424
+const MAX_HOSTNAME_LEN: usize = 255;
+/// Maximum APP-NAME length in RFC 5424
+const MAX_APP_NAME_LEN: usize = 48;
+/// MSGID of emitted syslog messages
+const SYSLOG_MSGID: &str = "security";
+
+/// Destination of security events
+#[async_trait]
+pub trait SiemTransport: Send {
+    /// Deliver a batch; a failed batch is retried as a whole
+    async fn send(&mut self, events: &[EncodedEvent]) -> Result<()>;
+
+    /// Transport name for logs
+    fn name(&self) -> &str;
+
+    /// Authenticate later deliveries with a rotated token; transports
+    /// without a token ignore it
+    fn set_token(&mut self, _token: String) {}
+}
+
+enum Connection {
+    Udp(UdpSocket),
+    Stream(Box<dyn AsyncWrite + Unpin + Send>),
+}
+
+/// RFC 5424 syslog over UDP, TCP or TLS
+///
+/// UDP sends one message per datagram; TCP and TLS use octet-counted
+/// framing. Connections are opened on first use and reopened after a
+/// failed delivery. Connecting and writing a batch are bounded by the
+/// configured timeout, so a stalled collector fails the batch instead of
+/// blocking the emitter.
+pub struct SyslogTransport {
+    address: String,
+    protocol: SyslogProtocol,
+    facility: u8,
+    hostname: String,
+    app_name: String,
+    tls: Option<(tokio_native_tls::TlsConnector, String)>,
+    timeout: Duration,
+    connection: Option<Connection>,
+}
+
+impl SyslogTransport {
+    pub fn from_config(config: &SyslogConfig) -> Result<Self> {
+        let tls = if config.protocol == SyslogProtocol::Tls {
+            let mut builder = native_tls::TlsConnector::builder();
+            if let Some(path) = &config.ca_cert {
+                let pem = std::fs::read(path)?;
+                let cert = native_tls::Certificate::from_pem(&pem).map_err(|e| {
+                    SiemError::Tls(format!("invalid CA certificate {}: {}", path, e))
+                })?;
+                builder.add_root_certificate(cert);
+            }
+            builder.danger_accept_invalid_certs(config.accept_invalid_certs);
+            let connector = builder.build().map_err(|e| SiemError::Tls(e.to_string()))?;
+            let server_name = config
+                .server_name
+                .clone()
+                .unwrap_or_else(|| host_of(&config.address).to_string());
+            Some((connector.into(), server_name))
+        } else {
+            None
+        };
+
+        let hostname = config
+            .hostname
+            .clone()
+            .or_else(|| std::env::var("HOSTNAME").ok())
+            .unwrap_or_default();
+
+        Ok(Self {
+            address: config.address.clone(),
+            protocol: config.protocol,
+            facility: config.facility,
+            hostname: header_field(&hostname, MAX_HOSTNAME_LEN),
+            app_name: header_field(&config.app_name, MAX_APP_NAME_LEN),
+            tls,
+            timeout: config.timeout(),
+            connection: None,
+        })
+    }
+
+    /// RFC 5424 message of an event
+    pub fn message(&self, event: &EncodedEvent) -> String {
+        let severity = match event.severity {
+            Severity::Critical => 2,
+            Severity::High => 3,
+            Severity::Medium => 4,
+            Severity::Low => 5,
+        };
+        format!(
+            "<{}>1 {} {} {} {} {} - {}",
+            self.facility as u16 * 8 + severity,
+            event.timestamp.to_rfc3339_opts(SecondsFormat::Micros, true),
+            self.hostname,
+            self.app_name,
+            std::process::id(),
+            SYSLOG_MSGID,
+            event.payload
+        )
+    }
+
+    async fn write(&mut self, events: &[EncodedEvent]) -> Result<()> {
+        if self.connection.is_none() {
+            self.connection = Some(connect(&self.address, self.protocol, self.tls.as_ref()).await?);
+        }
+        let messages: Vec<String> = events.iter().map(|event| self.message(event)).collect();
+
+        match self.connection.as_mut() {
+            Some(Connection::Udp(socket)) => {
+                for message in &messages {
+                    socket.send(message.as_bytes()).await?;
+                }
+            }
+            Some(Connection::Stream(stream)) => {
+                let mut frames = String::new();
+                for message in &messages {
+                    frames.push_str(&format!("{} {}", message.len(), message));
+                }
+                stream.write_all(frames.as_bytes()).await?;
+                stream.flush().await?;
+            }
+            None => unreachable!("connection opened above"),
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl SiemTransport for SyslogTransport {
+    async fn send(&mut self, events: &[EncodedEvent]) -> Result<()> {
+        let result = match tokio::time::timeout(self.timeout, self.write(events)).await {
+            Ok(result) => result,
+            Err(_) => Err(std::io::Error::new(
+                std::io::ErrorKind::TimedOut,
+                format!("syslog delivery to {} timed out", self.address),
+            )
+            .into()),
+        };
+        if result.is_err() {
+            self.connection = None;
+        }
+        result
+    }
+
+    fn name(&self) -> &str {
+        "syslog"
+    }
+}
+
+async fn connect(
+    address: &str,
+    protocol: SyslogProtocol,
+    tls: Option<&(tokio_native_tls::TlsConnector, String)>,
+) -> Result<Connection> {
+    match protocol {
+        SyslogProtocol::Udp => {
+            let addr = tokio::net::lookup_host(address)
+                .await?
+                .next()
+                .ok_or_else(|| {
+                    std::io::Error::new(
+                        std::io::ErrorKind::NotFound,
+                        format!("could not resolve {}", address),
+                    )
+                })?;
+            let local = if addr.is_ipv4() {
+                "0.0.0.0:0"
+            } else {
+                "[::]:0"
+            };
+            let socket = UdpSocket::bind(local).await?;
+            socket.connect(addr).await?;
+            Ok(Connection::Udp(socket))
+        }
+        SyslogProtocol::Tcp => Ok(Connection::Stream(Box::new(
+            TcpStream::connect(address).await?,
+        ))),
+        SyslogProtocol::Tls => {
+            let (connector, server_name) =
+                tls.ok_or_else(|| SiemError::Tls("TLS connector not configured".to_string()))?;
+            let stream = TcpStream::connect(address).await?;
+            let stream = connector
+                .connect(server_name, stream)
+                .await
+                .map_err(|e| SiemError::Tls(e.to_string()))?;
+            Ok(Connection::Stream(Box::new(stream)))
+        }
+    }
+}
+
+/// Appends events to a file, one per line
+pub struct FileTransport {
+    path: PathBuf,
+}
+
+impl FileTransport {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+#[async_trait]
+impl SiemTransport for FileTransport {
+    async fn send(&mut self, events: &[EncodedEvent]) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        // Reopened per batch so external log rotation is picked up
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .await?;
+        let mut lines = String::new();
+        for event in events {
+            lines.push_str(&event.payload);
+            lines.push('\n');
+        }
+        file.write_all(lines.as_bytes()).await?;
+        file.flush().await?;
+        Ok(())
+    }
+
+    fn name(&self) -> &str {
+        "file"
+    }
+}
+
+/// POSTs batches as newline-delimited events
+pub struct WebhookTransport {
+    client: reqwest::Client,
+    url: String,
+    token: Option<String>,
+    content_type: &'static str,
+}
+
+impl WebhookTransport {
+    pub fn new(
+        url: impl Into<String>,
+        token: Option<String>,
+        timeout: Duration,
+        format: SiemFormat,
+    ) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(timeout)
+            .build()
+            .map_err(|e| SiemError::Webhook(e.to_string()))?;
+        let content_type = match format {
+            SiemFormat::Cef => "text/plain",
+            SiemFormat::Ocsf | SiemFormat::Ecs => "application/x-ndjson",
+        };
+        Ok(Self {
+            client,
+            url: url.into(),
+            token,
+            content_type,
+        })
+    }
+
+    pub fn from_config(config: &SiemWebhookConfig, format: SiemFormat) -> Result<Self> {
+        let url = config
+            .url
+            .clone()
+            .ok_or_else(|| SiemError::Webhook("no webhook URL configured".to_string()))?;
+        Self::new(url, config.token.clone(), config.timeout(), format)
+    }
+}
+
+#[async_trait]
+impl SiemTransport for WebhookTransport {
+    async fn send(&mut self, events: &[EncodedEvent]) -> Result<()> {
+        let mut body = String::new();
+        for event in events {
+            body.push_str(&event.payload);
+            body.push('\n');
+        }
+
+        let mut request = self
+            .client
+            .post(&self.url)
+            .header(reqwest::header::CONTENT_TYPE, self.content_type)
+            .body(body);
+        if let Some(token) = &self.token {
+            request = request.bearer_auth(token);
+        }
+        let response = request
+            .send()
+            .await
+            .map_err(|e| SiemError::Webhook(e.to_string()))?;
+        if !response.status().is_success() {
+            return Err(SiemError::Webhook(format!(
+                "{} returned {}",
+                self.url,
+                response.status()
+            )));
+        }
+        Ok(())
+    }
+
+    fn name(&self) -> &str {
+        "webhook"
+    }
+
+    fn set_token(&mut self, token: String) {
+        self.token = Some(token).filter(|token| !token.is_empty());
+    }
+}
+
+/// Host part of `host:port`, without IPv6 brackets
+fn host_of(address: &str) -> &str {
+    let host = address.rsplit_once(':').map_or(address, |(host, _)| host);
+    host.trim_start_matches('[').trim_end_matches(']')
+}
+
+/// Syslog header field: printable ASCII, `-` when empty
+fn header_field(value: &str, max_len: usize) -> String {
+    let field: String = value
+        .chars()
+        .filter(|c| c.is_ascii_graphic())
+        .take(max_len)
+        .collect();
+    if field.is_empty() {
+        "-".to_string()
+    } else {
+        field
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use tokio::io::AsyncReadExt;
+    use tokio::net::TcpListener;
+
+    fn event(payload: &str) -> EncodedEvent {
+        EncodedEvent {
+            timestamp: Utc::now(),
+            severity: Severity::High,
+            payload: payload.to_string(),
+        }
+    }
+
+    fn syslog_config(address: String, protocol: SyslogProtocol) -> SyslogConfig {
+        SyslogConfig {
+            address,
+            protocol,
+            hostname: Some("shield host".to_string()),
+            ..SyslogConfig::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_syslog_to_local_listener() {
+        // UDP: one datagram per event
+        let listener = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let config = syslog_config(
+            listener.local_addr().unwrap().to_string(),
+            SyslogProtocol::Udp,
+        );
+        let mut transport = SyslogTransport::from_config(&config).unwrap();
+        transport
+            .send(&[event("CEF:0|first"), event("CEF:0|second")])
+            .await
+            .unwrap();
+
+        let mut buf = [0u8; 2048];
+        let len = listener.recv(&mut buf).await.unwrap();
+        let message = std::str::from_utf8(&buf[..len]).unwrap();
+        // Facility 13 (log audit) * 8 + severity 3 (error)
+        assert!(message.starts_with("<107>1 "), "{}", message);
+        let fields: Vec<&str> = message.splitn(8, ' ').collect();
+        assert_eq!(fields[2], "shieldhost");
+        assert_eq!(fields[3], "llm-shield");
+        assert_eq!(fields[5], SYSLOG_MSGID);
+        assert_eq!(fields[6], "-");
+        assert_eq!(fields[7], "CEF:0|first");
+        let len = listener.recv(&mut buf).await.unwrap();
+        assert!(std::str::from_utf8(&buf[..len])
+            .unwrap()
+            .ends_with(" CEF:0|second"));
+
+        // TCP: octet-counted frames
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let config = syslog_config(
+            listener.local_addr().unwrap().to_string(),
+            SyslogProtocol::Tcp,
+        );
+        let received = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut data = String::new();
+            stream.read_to_string(&mut data).await.unwrap();
+            data
+        });
+        let mut transport = SyslogTransport::from_config(&config).unwrap();
+        transport
+            .send(&[event("{\"a\":1}"), event("{\"b\":2}")])
+            .await
+            .unwrap();
+        drop(transport);
+
+        let data = received.await.unwrap();
+        let mut rest = data.as_str();
+        let mut messages = Vec::new();
+        while !rest.is_empty() {
+            let (len, tail) = rest.split_once(' ').unwrap();
+            let len: usize = len.parse().unwrap();
+            messages.push(&tail[..len]);
+            rest = &tail[len..];
+        }
+        assert_eq!(messages.len(), 2);
+        assert!(messages[0].starts_with("<107>1 ") && messages[0].ends_with(" {\"a\":1}"));
+        assert!(messages[1].ends_with(" {\"b\":2}"));
+
+        assert_eq!(host_of("siem.internal:6514"), "siem.internal");
+        assert_eq!(host_of("[::1]:6514"), "::1");
+        assert_eq!(header_field("", 10), "-");
+    }
+
+    #[tokio::test]
+    async fn test_syslog_stalled_collector_times_out() {
+        // Accepts the connection but never answers the TLS handshake
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let mut config = syslog_config(
+            listener.local_addr().unwrap().to_string(),
+            SyslogProtocol::Tls,
+        );
+        config.timeout_secs = 1;
+        let accepted = tokio::spawn(async move { listener.accept().await.unwrap() });
+
+        let mut transport = SyslogTransport::from_config(&config).unwrap();
+        let result = tokio::time::timeout(
+            Duration::from_secs(5),
+            transport.send(&[event("CEF:0|stalled")]),
+        )
+        .await
+        .expect("delivery should give up on its own");
+
+        assert!(
+            matches!(&result, Err(SiemError::Io(e)) if e.kind() == std::io::ErrorKind::TimedOut),
+            "{:?}",
+            result
+        );
+        assert!(transport.connection.is_none());
+        drop(accepted);
+    }
+
+    #[tokio::test]
+    async fn test_file_and_webhook_transports() {
+        let path = std::env::temp_dir()
+            .join(format!("llm-shield-siem-{}", std::process::id()))
+            .join("events.log");
+        let mut transport = FileTransport::new(&path);
+        transport.send(&[event("one")]).await.unwrap();
+        transport
+            .send(&[event("two"), event("three")])
+            .await
+            .unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "one\ntwo\nthree\n");
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+
+        use axum::{http::HeaderMap, routing::post, Router};
+        use std::sync::{Arc, Mutex};
+
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let sink = received.clone();
+        let app = Router::new().route(
+            "/events",
+            post(move |headers: HeaderMap, body: String| async move {
+                let auth = headers
+                    .get("authorization")
+                    .and_then(|v| v.to_str().ok())
+                    .unwrap_or_default()
+                    .to_string();
+                sink.lock().unwrap().push((auth, body));
+                axum::http::StatusCode::ACCEPTED
+            }),
+        );
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let mut transport = WebhookTransport::new(
+            format!("http://{}/events", addr),
+            Some("secret-token".to_string()),
+            Duration::from_secs(5),
+            SiemFormat::Ecs,
+        )
+        .unwrap();
+        transport
+            .send(&[event("{\"a\":1}"), event("{\"b\":2}")])
+            .await
+            .unwrap();
+        transport.set_token("rotated-token".to_string());
+        transport.send(&[event("{\"c\":3}")]).await.unwrap();
+        let received = received.lock().unwrap().clone();
+        assert_eq!(
+            received,
+            vec![
+                (
+                    "Bearer secret-token".to_string(),
+                    "{\"a\":1}\n{\"b\":2}\n".to_string()
+                ),
+                (
+                    "Bearer rotated-token".to_string(),
+                    "{\"c\":3}\n".to_string()
+                ),
+            ]
+        );
+
+        let mut missing = WebhookTransport::new(
+            format!("http://{}/missing", addr),
+            None,
+            Duration::from_secs(5),
+            SiemFormat::Cef,
+        )
+        .unwrap();
+        assert!(missing.send(&[event("x")]).await.is_err());
+    }
+}
@@ -1,13 +1,26 @@
 //! Shared application state
 
+use crate::audit::{AuditDecision, AuditExporter, AuditRecorder};
 use crate::config::AppConfig;
-use llm_shield_core::Scanner;
+use crate::extractors::ScanContext;
+use crate::middleware::gateway;
+use crate::models::{ExecutionOutput, ScannerResult};
+use crate::observability::prometheus;
+use crate::secrets::SharedSecret;
+use crate::services::{ConversationStore, ScanEvent, ScanEventSink, ScanKind};
+use crate::siem::SiemEmitter;
+use crate::tenants::{ProfileStore, TenantPipelines, TenantScanners};
+use llm_shield_core::{watch_file, ReloadStatus, Reloadable, Scanner, WatchHandle};
 use llm_shield_models::cache::{CacheConfig, ResultCache};
+use llm_shield_scanners::{PipelineConfig, PipelineResources};
 use std::collections::HashMap;
 use std::sync::Arc;
 
 #[cfg(feature = "cloud")]
-use llm_shield_cloud::{CloudLogger, CloudMetrics, CloudSecretManager, CloudStorage};
+use llm_shield_cloud::{CloudLogger, CloudMetrics, CloudSecretManager, CloudStorage, CloudTracer};
+
+/// Scanner name -> Scanner instance
+pub type ScannerRegistry = HashMap<String, Arc<dyn Scanner>>;
 
 /// Shared application state
 ///
@@ -21,12 +34,36 @@ pub struct AppState {
     /// Application configuration
     pub config: Arc<AppConfig>,
 
-    /// Scanner registry (scanner name -> Scanner instance)
-    pub scanners: Arc<HashMap<String, Arc<dyn Scanner>>>,
+    /// Scanner registry, replaced as a whole on reload
+    pub scanners: Arc<Reloadable<ScannerRegistry>>,
 
     /// Result cache
     pub cache: Arc<ResultCache>,
 
+    /// Per-conversation scan state for incremental conversation scans
+    pub conversations: Arc<ConversationStore>,
+
+    /// Scan event sink (optional)
+    pub event_sink: Option<Arc<dyn ScanEventSink>>,
+
+    /// Audit log of scan decisions (optional)
+    pub audit: Option<AuditRecorder>,
+
+    /// Security event emitter for blocked and high-risk scans (optional)
+    pub siem: Option<SiemEmitter>,
+
+    /// Per-tenant scanner pipelines (optional)
+    pub tenants: Option<Arc<TenantPipelines>>,
+
+    /// Models for model-backed pipeline scanners such as `factual_consistency`
+    pub pipeline_resources: PipelineResources,
+
+    /// Shared secret validating caller tokens, replaced when it rotates
+    pub gateway_secret: SharedSecret,
+
+    /// API key of the streaming upstream, replaced when it rotates
+    pub upstream_api_key: SharedSecret,
+
     /// Cloud secret manager (optional)
     #[cfg(feature = "cloud")]
     pub secret_manager: Option<Arc<dyn CloudSecretManager>>,
@@ -42,6 +79,14 @@ pub struct AppState {
     /// Cloud logger (optional)
     #[cfg(feature = "cloud")]
     pub cloud_logger: Option<Arc<dyn CloudLogger>>,
+
+    /// Cloud tracer for execution spans (optional)
+    #[cfg(feature = "cloud")]
+    pub cloud_tracer: Option<Arc<dyn CloudTracer>>,
+
+    /// Config values resolved from `secret://` references (optional)
+    #[cfg(feature = "cloud")]
+    pub secrets: Option<Arc<crate::secrets::ConfigSecrets>>,
 }
 
 impl AppState {
@@ -53,11 +98,22 @@ impl AppState {
             ttl: config.cache.ttl(),
         };
         let cache = ResultCache::new(cache_config);
+        let conversations = ConversationStore::new(config.cache.ttl(), config.cache.max_size);
+        let gateway_secret = SharedSecret::new(gateway::configured_secret(&config.auth));
+        let upstream_api_key = SharedSecret::new(config.streaming.upstream_api_key.clone());
 
         Self {
             config: Arc::new(config),
-            scanners: Arc::new(HashMap::new()),
+            scanners: Arc::new(Reloadable::new(HashMap::new())),
             cache: Arc::new(cache),
+            conversations: Arc::new(conversations),
+            event_sink: None,
+            audit: None,
+            siem: None,
+            tenants: None,
+            pipeline_resources: PipelineResources::default(),
+            gateway_secret,
+            upstream_api_key,
             #[cfg(feature = "cloud")]
             secret_manager: None,
             #[cfg(feature = "cloud")]
@@ -66,29 +122,396 @@ impl AppState {
             cloud_metrics: None,
             #[cfg(feature = "cloud")]
             cloud_logger: None,
+            #[cfg(feature = "cloud")]
+            cloud_tracer: None,
+            #[cfg(feature = "cloud")]
+            secrets: None,
         }
     }
 
     /// Register a scanner
     pub fn with_scanner(mut self, scanner: Arc<dyn Scanner>) -> Self {
-        let scanners = Arc::make_mut(&mut self.scanners);
+        let mut scanners = ScannerRegistry::clone(&self.scanners.load());
         scanners.insert(scanner.name().to_string(), scanner);
+        self.scanners = Arc::new(Reloadable::new(scanners));
         self
     }
 
     /// Get scanner by name
     pub fn get_scanner(&self, name: &str) -> Option<Arc<dyn Scanner>> {
-        self.scanners.get(name).cloned()
+        self.scanners.load().get(name).cloned()
     }
 
     /// List all registered scanners
     pub fn list_scanners(&self) -> Vec<String> {
-        self.scanners.keys().cloned().collect()
+        self.scanners.load().keys().cloned().collect()
     }
 
     /// Get scanner count
     pub fn scanner_count(&self) -> usize {
-        self.scanners.len()
+        self.scanners.load().len()
+    }
+
+    /// Replace all registered scanners with the scanners of a pipeline
+    ///
+    /// Every scanner is built before the swap, so an invalid pipeline leaves
+    /// the current scanners in place. Returns the new scanner version.
+    pub fn reload_pipeline(&self, pipeline: &PipelineConfig) -> llm_shield_core::Result<u64> {
+        let version = self.swap_scanners(|| {
+            let built = pipeline.build_with(&self.pipeline_resources)?;
+            Ok(built.input.into_iter().chain(built.output).collect())
+        })?;
+
+        // Tenant pipelines are derived from the new pipeline
+        if let Some(tenants) = &self.tenants {
+            tenants.set_base(pipeline.clone(), self.pipeline_resources.clone());
+        }
+        Ok(version)
+    }
+
+    /// Replace all registered scanners
+    pub fn reload_scanners(&self, scanners: Vec<Arc<dyn Scanner>>) -> llm_shield_core::Result<u64> {
+        self.swap_scanners(|| Ok(scanners))
+    }
+
+    /// Restore the scanners replaced by the last reload
+    pub fn rollback_scanners(&self) -> llm_shield_core::Result<u64> {
+        let version = self.scanners.rollback()?;
+        self.cache.clear();
+        Ok(version)
+    }
+
+    /// Scanner reload history
+    pub fn reload_status(&self) -> ReloadStatus {
+        self.scanners.status()
+    }
+
+    /// Watch the configured pipeline file and reload scanners when it changes
+    ///
+    /// Returns `None` unless `scanners.pipeline_file` is set and
+    /// `scanners.watch` is enabled. Watching stops when the handle is dropped.
+    /// Pipelines in cloud storage, and pipelines of a configuration with
+    /// `secret://` references, are polled at the same interval instead.
+    pub fn watch_pipeline(&self) -> Option<WatchHandle> {
+        let config = &self.config.scanners;
+        let path = config.pipeline_file.clone().filter(|_| config.watch)?;
+
+        #[cfg(feature = "cloud")]
+        if config.is_storage_pipeline() || self.secrets.is_some() {
+            return Some(self.poll_pipeline(path));
+        }
+
+        let state = self.clone();
+        Some(watch_file(path, config.poll_interval(), move |path| {
+            let result = PipelineConfig::from_path(path).and_then(|pipeline| {
+                // Parse failures count as failed reloads too
+                state.reload_pipeline(&pipeline)
+            });
+            match result {
+                Ok(version) => {
+                    tracing::info!(path = %path.display(), version, "Reloaded scanner pipeline")
+                }
+                Err(e) => {
+                    tracing::warn!(path = %path.display(), error = %e, "Scanner pipeline reload failed, keeping current scanners")
+                }
+            }
+        }))
+    }
+
+    #[cfg(feature = "cloud")]
+    fn poll_pipeline(&self, path: String) -> WatchHandle {
+        let state = self.clone();
+        WatchHandle::spawn(async move {
+            let artifacts = state.artifact_cache();
+            let mut ticker = tokio::time::interval(state.config.scanners.poll_interval());
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            ticker.tick().await;
+
+            let mut last = None;
+            loop {
+                ticker.tick().await;
+                let pipeline = match state.load_resolved_pipeline(artifacts.as_ref()).await {
+                    Ok(Some(pipeline)) => pipeline,
+                    Ok(None) => continue,
+                    Err(e) => {
+                        tracing::warn!(path = %path, error = %e, "Scanner pipeline reload failed, keeping current scanners");
+                        continue;
+                    }
+                };
+                if last.as_ref() == Some(&pipeline) {
+                    continue;
+                }
+                // The first poll only records the pipeline loaded at startup
+                if last.is_some() {
+                    match state.reload_pipeline(&pipeline) {
+                        Ok(version) => {
+                            tracing::info!(path = %path, version, "Reloaded scanner pipeline")
+                        }
+                        Err(e) => {
+                            tracing::warn!(path = %path, error = %e, "Scanner pipeline reload failed, keeping current scanners")
+                        }
+                    }
+                }
+                last = Some(pipeline);
+            }
+        })
+    }
+
+    /// Re-read `secret://` config values periodically and apply rotations
+    ///
+    /// Returns `None` unless the configuration references secrets. Rotated
+    /// values replace the gateway secret, the streaming upstream API key, the
+    /// audit signing key and the SIEM webhook token; a rotated scanner
+    /// parameter reloads the pipeline. Watching stops when the handle is
+    /// dropped.
+    #[cfg(feature = "cloud")]
+    pub fn watch_secrets(&self) -> Option<WatchHandle> {
+        let secrets = self.secrets.clone()?;
+        let state = self.clone();
+        Some(WatchHandle::spawn(async move {
+            let mut ticker = tokio::time::interval(secrets.interval());
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            ticker.tick().await;
+            loop {
+                ticker.tick().await;
+                let rotated = secrets.refresh().await;
+                state.apply_rotated_secrets(rotated).await;
+            }
+        }))
+    }
+
+    /// Hand rotated config values, by config path, to the components using them
+    #[cfg(feature = "cloud")]
+    pub async fn apply_rotated_secrets(&self, rotated: Vec<(String, String)>) {
+        let mut reload_pipeline = false;
+        for (path, value) in rotated {
+            let result = match path.as_str() {
+                "auth.gateway_secret" => {
+                    self.gateway_secret.set(Some(value));
+                    Ok(())
+                }
+                "streaming.upstream_api_key" => {
+                    self.upstream_api_key.set(Some(value));
+                    Ok(())
+                }
+                "audit.signing_key" => self.rotate_audit_key(value),
+                "siem.webhook.token" => match &self.siem {
+                    Some(siem) => siem.set_webhook_token(value).await.map_err(|e| e.to_string()),
+                    None => Ok(()),
+                },
+                path => {
+                    reload_pipeline |= path.starts_with("scanners.");
+                    Ok(())
+                }
+            };
+            if let Err(e) = result {
+                tracing::error!(path = %path, error = %e, "Failed to apply rotated secret");
+            }
+        }
+
+        if reload_pipeline {
+            let artifacts = self.artifact_cache();
+            let result = match self.load_resolved_pipeline(artifacts.as_ref()).await {
+                Ok(Some(pipeline)) => self.reload_pipeline(&pipeline).map_err(|e| e.to_string()),
+                Ok(None) => return,
+                Err(e) => Err(e.to_string()),
+            };
+            match result {
+                Ok(version) => {
+                    tracing::info!(version, "Reloaded scanner pipeline with rotated secrets")
+                }
+                Err(e) => {
+                    tracing::warn!(error = %e, "Scanner pipeline reload failed, keeping current scanners")
+                }
+            }
+        }
+    }
+
+    #[cfg(feature = "cloud")]
+    fn rotate_audit_key(&self, secret: String) -> std::result::Result<(), String> {
+        let Some(audit) = &self.audit else {
+            return Ok(());
+        };
+        let mut config = self.config.audit.clone();
+        config.signing_key = Some(secret);
+        config.validate().map_err(|e| e.to_string())?;
+        audit
+            .rotate_key(config.signing_key.as_deref().unwrap_or_default())
+            .map_err(|e| e.to_string())
+    }
+
+    /// Load the configured pipeline from a local file or cloud storage and
+    /// resolve its `secret://` parameters
+    #[cfg(feature = "cloud")]
+    async fn load_resolved_pipeline(
+        &self,
+        artifacts: Option<&llm_shield_cloud::ArtifactCache>,
+    ) -> crate::config::Result<Option<PipelineConfig>> {
+        let mut pipeline = crate::artifacts::load_pipeline(&self.config.scanners, artifacts).await?;
+        if let (Some(pipeline), Some(secrets)) = (&mut pipeline, &self.secrets) {
+            secrets.resolve_pipeline(pipeline).await?;
+        }
+        Ok(pipeline)
+    }
+
+    /// Artifact cache over the configured cloud storage, if any
+    #[cfg(feature = "cloud")]
+    pub fn artifact_cache(&self) -> Option<llm_shield_cloud::ArtifactCache> {
+        let storage = self.cloud_storage.clone()?;
+        Some(crate::artifacts::artifact_cache(&self.config, storage))
+    }
+
+    /// Load the configured model registry from a local file or cloud storage
+    #[cfg(feature = "cloud")]
+    pub async fn load_model_registry(
+        &self,
+    ) -> crate::config::Result<llm_shield_models::ModelRegistry> {
+        let artifacts = self.artifact_cache();
+        crate::artifacts::load_model_registry(&self.config, artifacts.as_ref()).await
+    }
+
+    fn swap_scanners<F>(&self, build: F) -> llm_shield_core::Result<u64>
+    where
+        F: FnOnce() -> llm_shield_core::Result<Vec<Arc<dyn Scanner>>>,
+    {
+        let version = self.scanners.reload(|_| {
+            let scanners: ScannerRegistry = build()?
+                .into_iter()
+                .map(|scanner| (scanner.name().to_string(), scanner))
+                .collect();
+            if scanners.is_empty() {
+                return Err(llm_shield_core::Error::config(
+                    "Reloaded scanner set contains no scanners",
+                ));
+            }
+            Ok(scanners)
+        })?;
+
+        // Cached results came from the previous scanners
+        self.cache.clear();
+        Ok(version)
+    }
+
+    /// Set scan event sink
+    pub fn with_event_sink(mut self, sink: Arc<dyn ScanEventSink>) -> Self {
+        self.event_sink = Some(sink);
+        self
+    }
+
+    /// Set per-tenant scanner pipelines
+    pub fn with_tenants(mut self, tenants: Arc<TenantPipelines>) -> Self {
+        self.tenants = Some(tenants);
+        self
+    }
+
+    /// Scanners serving a request: its tenant profile's pipeline, or the
+    /// shared scanners when no profile applies
+    pub async fn resolve_scanners(
+        &self,
+        context: &ScanContext,
+    ) -> llm_shield_core::Result<TenantScanners> {
+        if let Some(tenants) = &self.tenants {
+            if let Some(resolved) = tenants.resolve(context).await? {
+                return Ok(resolved);
+            }
+        }
+        Ok(TenantScanners {
+            profile_id: None,
+            scanners: self.scanners.load(),
+        })
+    }
+
+    /// Record scan metrics and forward scanner results to the event sink
+    pub fn record_scan(&self, tenant_id: Option<&str>, kind: ScanKind, results: &[ScannerResult]) {
+        prometheus::record_scan(kind, tenant_id, results);
+        if let Some(sink) = &self.event_sink {
+            sink.record(ScanEvent::from_results(tenant_id, kind, results));
+        }
+    }
+
+    /// Set audit recorder
+    pub fn with_audit(mut self, audit: AuditRecorder) -> Self {
+        self.audit = Some(audit);
+        self
+    }
+
+    /// Set SIEM emitter
+    pub fn with_siem(mut self, siem: SiemEmitter) -> Self {
+        self.siem = Some(siem);
+        self
+    }
+
+    /// Whether scan decisions are recorded in the audit log or emitted to
+    /// the SIEM
+    pub fn records_decisions(&self) -> bool {
+        self.audit.is_some() || self.siem.is_some()
+    }
+
+    /// Keyed hash of scanned text for [`AuditDecision::input_hash`], empty
+    /// without an audit log
+    pub fn input_hash(&self, input: &str) -> String {
+        self.audit
+            .as_ref()
+            .map(|audit| audit.input_hash(input))
+            .unwrap_or_default()
+    }
+
+    /// Record the decision of a scan over `input` in the audit log and emit
+    /// it to the SIEM, if enabled
+    pub fn audit_scan(
+        &self,
+        context: &ScanContext,
+        execution_id: &str,
+        operation: &str,
+        input: &str,
+        results: &[ScannerResult],
+    ) {
+        if self.records_decisions() {
+            let decision = AuditDecision::from_results(
+                &context.request_id,
+                operation,
+                results,
+                self.input_hash(input),
+            );
+            self.record_decision(context, execution_id, decision);
+        }
+    }
+
+    /// Record a decision in the audit log and emit it to the SIEM,
+    /// attributed to the request
+    pub fn record_decision(
+        &self,
+        context: &ScanContext,
+        execution_id: &str,
+        mut decision: AuditDecision,
+    ) {
+        decision.execution_id = Some(execution_id.to_string());
+        decision.caller_id = context.caller_id.clone();
+        decision.tenant_id = context.tenant_id.clone();
+        if let Some(siem) = &self.siem {
+            siem.emit_decision(&decision);
+        }
+        if let Some(audit) = &self.audit {
+            audit.record(decision);
+        }
+    }
+
+    /// Export a finalized execution tree to the cloud tracer, if any
+    ///
+    /// The export runs in the background and never fails the request.
+    pub fn export_execution(&self, execution: &ExecutionOutput) {
+        #[cfg(feature = "cloud")]
+        if let Some(tracer) = self.cloud_tracer.clone() {
+            let spans = crate::observability::otel::execution_spans(execution);
+            tokio::spawn(async move {
+                if let Err(e) = tracer.export_spans(&spans).await {
+                    tracing::warn!(error = %e, "Failed to export execution spans");
+                }
+            });
+        }
+
+        #[cfg(not(feature = "cloud"))]
+        let _ = execution;
     }
 
     /// Set cloud secret manager
@@ -118,12 +541,25 @@ impl AppState {
         self.cloud_logger = Some(logger);
         self
     }
+
+    /// Set cloud tracer
+    #[cfg(feature = "cloud")]
+    pub fn with_cloud_tracer(mut self, tracer: Arc<dyn CloudTracer>) -> Self {
+        self.cloud_tracer = Some(tracer);
+        self
+    }
 }
 
 /// Builder for AppState with fluent API
 pub struct AppStateBuilder {
     config: AppConfig,
     scanners: HashMap<String, Arc<dyn Scanner>>,
+    pipeline: Option<PipelineConfig>,
+    event_sink: Option<Arc<dyn ScanEventSink>>,
+    audit: Option<AuditRecorder>,
+    siem: Option<SiemEmitter>,
+    tenants: Option<Arc<TenantPipelines>>,
+    pipeline_resources: PipelineResources,
     #[cfg(feature = "cloud")]
     secret_manager: Option<Arc<dyn CloudSecretManager>>,
     #[cfg(feature = "cloud")]
@@ -132,6 +568,10 @@ pub struct AppStateBuilder {
     cloud_metrics: Option<Arc<dyn CloudMetrics>>,
     #[cfg(feature = "cloud")]
     cloud_logger: Option<Arc<dyn CloudLogger>>,
+    #[cfg(feature = "cloud")]
+    cloud_tracer: Option<Arc<dyn CloudTracer>>,
+    #[cfg(feature = "cloud")]
+    secrets: Option<Arc<crate::secrets::ConfigSecrets>>,
 }
 
 impl AppStateBuilder {
@@ -140,6 +580,12 @@ impl AppStateBuilder {
         Self {
             config,
             scanners: HashMap::new(),
+            pipeline: None,
+            event_sink: None,
+            audit: None,
+            siem: None,
+            tenants: None,
+            pipeline_resources: PipelineResources::default(),
             #[cfg(feature = "cloud")]
             secret_manager: None,
             #[cfg(feature = "cloud")]
@@ -148,6 +594,10 @@ impl AppStateBuilder {
             cloud_metrics: None,
             #[cfg(feature = "cloud")]
             cloud_logger: None,
+            #[cfg(feature = "cloud")]
+            cloud_tracer: None,
+            #[cfg(feature = "cloud")]
+            secrets: None,
         }
     }
 
@@ -165,6 +615,177 @@ impl AppStateBuilder {
         self
     }
 
+    /// Set the models used by model-backed pipeline scanners
+    ///
+    /// Set before registering a pipeline that lists such scanners, e.g.
+    /// `factual_consistency` needs an NLI backend.
+    pub fn with_pipeline_resources(mut self, resources: PipelineResources) -> Self {
+        self.pipeline_resources = resources;
+        self
+    }
+
+    /// Register the enabled scanners of a declarative pipeline
+    ///
+    /// Fails without registering anything if any scanner is invalid.
+    pub fn register_pipeline(mut self, pipeline: &PipelineConfig) -> llm_shield_core::Result<Self> {
+        let built = pipeline.build_with(&self.pipeline_resources)?;
+        self.pipeline = Some(pipeline.clone());
+        Ok(self
+            .register_scanners(built.input)
+            .register_scanners(built.output))
+    }
+
+    /// Register the scanners of the configured `scanners.pipeline_file`, if any
+    pub fn register_configured_pipeline(self) -> crate::config::Result<Self> {
+        match self.config.scanners.load_pipeline()? {
+            Some(pipeline) => self
+                .register_pipeline(&pipeline)
+                .map_err(|e| crate::config::ConfigError::ValidationError(e.to_string())),
+            None => Ok(self),
+        }
+    }
+
+    /// Register the scanners of the configured pipeline, fetching it and its
+    /// pattern packs from cloud storage as needed
+    ///
+    /// Set the cloud storage provider first when the pipeline or its packs
+    /// use `storage://` references, and resolve the config secrets first
+    /// when scanner parameters use `secret://` references.
+    #[cfg(feature = "cloud")]
+    pub async fn load_configured_pipeline(self) -> crate::config::Result<Self> {
+        let artifacts = self
+            .cloud_storage
+            .clone()
+            .map(|storage| crate::artifacts::artifact_cache(&self.config, storage));
+        let mut pipeline =
+            crate::artifacts::load_pipeline(&self.config.scanners, artifacts.as_ref()).await?;
+        if let (Some(pipeline), Some(secrets)) = (&mut pipeline, &self.secrets) {
+            secrets.resolve_pipeline(pipeline).await?;
+        }
+        match pipeline {
+            Some(pipeline) => self
+                .register_pipeline(&pipeline)
+                .map_err(|e| crate::config::ConfigError::ValidationError(e.to_string())),
+            None => Ok(self),
+        }
+    }
+
+    /// Set scan event sink
+    pub fn with_event_sink(mut self, sink: Arc<dyn ScanEventSink>) -> Self {
+        self.event_sink = Some(sink);
+        self
+    }
+
+    /// Resolve the `secret://` values of the configuration through the cloud
+    /// secret manager
+    ///
+    /// Set the cloud secret manager first, and resolve before starting
+    /// components that use the values, such as the audit log. With a secret
+    /// manager, the scanner parameters of the pipeline are resolved when it
+    /// is loaded by [`load_configured_pipeline`](Self::load_configured_pipeline).
+    #[cfg(feature = "cloud")]
+    pub async fn resolve_config_secrets(mut self) -> crate::config::Result<Self> {
+        let Some(manager) = self.secret_manager.clone() else {
+            if self.config.secret_refs().is_empty() {
+                return Ok(self);
+            }
+            return Err(crate::config::ConfigError::ValidationError(
+                "secret:// configuration values require a cloud secret manager".to_string(),
+            ));
+        };
+
+        let secrets =
+            crate::secrets::ConfigSecrets::new(manager, self.config.cloud.secret_refs.ttl());
+        secrets.resolve_config(&mut self.config).await?;
+        self.secrets = Some(Arc::new(secrets));
+        Ok(self)
+    }
+
+    /// Set audit recorder
+    pub fn with_audit(mut self, audit: AuditRecorder) -> Self {
+        self.audit = Some(audit);
+        self
+    }
+
+    /// Start the audit log configured in `audit`, if enabled
+    ///
+    /// Set the cloud storage provider first when segments are exported to
+    /// cloud storage.
+    pub fn start_audit(mut self) -> crate::audit::Result<Self> {
+        let config = &self.config.audit;
+        if !config.enabled {
+            return Ok(self);
+        }
+
+        #[allow(unused_mut)]
+        let mut exporters: Vec<Arc<dyn AuditExporter>> = Vec::new();
+        if config.export_to_storage {
+            #[cfg(feature = "cloud")]
+            {
+                let storage = self.cloud_storage.clone().ok_or_else(|| {
+                    crate::audit::AuditError::Export(
+                        "no cloud storage provider is configured".to_string(),
+                    )
+                })?;
+                exporters.push(Arc::new(crate::audit::StorageExporter::new(
+                    storage,
+                    config.export_prefix.clone(),
+                )));
+            }
+            #[cfg(not(feature = "cloud"))]
+            return Err(crate::audit::AuditError::Export(
+                "export to cloud storage requires the `cloud` feature".to_string(),
+            ));
+        }
+
+        self.audit = Some(AuditRecorder::from_config(config, exporters)?);
+        Ok(self)
+    }
+
+    /// Set SIEM emitter
+    pub fn with_siem(mut self, siem: SiemEmitter) -> Self {
+        self.siem = Some(siem);
+        self
+    }
+
+    /// Start the SIEM emitter configured in `siem`, if enabled
+    pub fn start_siem(mut self) -> crate::siem::Result<Self> {
+        if self.config.siem.enabled {
+            self.siem = Some(SiemEmitter::from_config(&self.config.siem)?);
+        }
+        Ok(self)
+    }
+
+    /// Set per-tenant scanner pipelines
+    ///
+    /// Profiles without their own pipeline are derived from the last
+    /// registered pipeline.
+    pub fn with_tenants(mut self, tenants: Arc<TenantPipelines>) -> Self {
+        self.tenants = Some(tenants);
+        self
+    }
+
+    /// Resolve tenant profiles from `store`, configured by `tenants`
+    pub fn with_profile_store(self, store: Arc<dyn ProfileStore>) -> Self {
+        let tenants = TenantPipelines::from_config(&self.config.tenants, store);
+        self.with_tenants(Arc::new(tenants))
+    }
+
+    /// Resolve tenant profiles from the configured `tenants.profiles_file`,
+    /// or an empty in-memory store, if enabled
+    pub async fn start_tenants(self) -> crate::tenants::Result<Self> {
+        let config = &self.config.tenants;
+        if !config.enabled {
+            return Ok(self);
+        }
+
+        let store: Arc<dyn ProfileStore> = match &config.profiles_file {
+            Some(path) => Arc::new(crate::tenants::FileProfileStore::new(path).await?),
+            None => Arc::new(crate::tenants::MemoryProfileStore::new()),
+        };
+        Ok(self.with_profile_store(store))
+    }
+
     /// Set cloud secret manager
     #[cfg(feature = "cloud")]
     pub fn with_secret_manager(mut self, manager: Arc<dyn CloudSecretManager>) -> Self {
@@ -193,18 +814,52 @@ impl AppStateBuilder {
         self
     }
 
+    /// Set cloud tracer
+    #[cfg(feature = "cloud")]
+    pub fn with_cloud_tracer(mut self, tracer: Arc<dyn CloudTracer>) -> Self {
+        self.cloud_tracer = Some(tracer);
+        self
+    }
+
+    /// Set all initialized cloud providers
+    #[cfg(feature = "cloud")]
+    pub fn with_cloud_providers(mut self, providers: crate::cloud_init::CloudProviders) -> Self {
+        self.secret_manager = providers.secret_manager;
+        self.cloud_storage = providers.storage;
+        self.cloud_metrics = providers.metrics;
+        self.cloud_logger = providers.logger;
+        self.cloud_tracer = providers.tracer;
+        self
+    }
+
     /// Build the AppState
     pub fn build(self) -> AppState {
+        if let (Some(tenants), Some(pipeline)) = (&self.tenants, self.pipeline) {
+            tenants.set_base(pipeline, self.pipeline_resources.clone());
+        }
+
         let cache_config = CacheConfig {
             max_size: self.config.cache.max_size,
             ttl: self.config.cache.ttl(),
         };
         let cache = ResultCache::new(cache_config);
+        let conversations =
+            ConversationStore::new(self.config.cache.ttl(), self.config.cache.max_size);
+        let gateway_secret = SharedSecret::new(gateway::configured_secret(&self.config.auth));
+        let upstream_api_key = SharedSecret::new(self.config.streaming.upstream_api_key.clone());
 
         AppState {
             config: Arc::new(self.config),
-            scanners: Arc::new(self.scanners),
+            scanners: Arc::new(Reloadable::new(self.scanners)),
             cache: Arc::new(cache),
+            conversations: Arc::new(conversations),
+            event_sink: self.event_sink,
+            audit: self.audit,
+            siem: self.siem,
+            tenants: self.tenants,
+            pipeline_resources: self.pipeline_resources,
+            gateway_secret,
+            upstream_api_key,
             #[cfg(feature = "cloud")]
             secret_manager: self.secret_manager,
             #[cfg(feature = "cloud")]
@@ -213,6 +868,10 @@ impl AppStateBuilder {
             cloud_metrics: self.cloud_metrics,
             #[cfg(feature = "cloud")]
             cloud_logger: self.cloud_logger,
+            #[cfg(feature = "cloud")]
+            cloud_tracer: self.cloud_tracer,
+            #[cfg(feature = "cloud")]
+            secrets: self.secrets,
         }
     }
 }
@@ -325,6 +984,44 @@ mod tests {
         assert_eq!(state.scanner_count(), 3);
     }
 
+    #[test]
+    fn test_app_state_builder_pipeline() {
+        let pipeline = PipelineConfig::from_yaml_str(
+            "input:\n  - type: secrets\n  - type: invisible_text\n    enabled: false\noutput:\n  - type: sensitive\n",
+        )
+        .unwrap();
+
+        let state = AppStateBuilder::new(AppConfig::default())
+            .register_pipeline(&pipeline)
+            .unwrap()
+            .build();
+
+        assert_eq!(state.scanner_count(), 2);
+        assert!(state.get_scanner("Secrets").is_some());
+    }
+
+    #[test]
+    fn test_app_state_reload_pipeline() {
+        let state = AppState::new(AppConfig::default()).with_scanner(Arc::new(MockScanner {
+            name: "scanner1".to_string(),
+        }));
+        let handler_view = state.clone();
+
+        let pipeline = PipelineConfig::from_yaml_str("input:\n  - type: secrets\n").unwrap();
+        assert_eq!(state.reload_pipeline(&pipeline).unwrap(), 2);
+        // Clones share the registry
+        assert_eq!(handler_view.list_scanners(), vec!["Secrets".to_string()]);
+
+        let invalid = PipelineConfig::from_yaml_str("input:\n  - type: token_limit\n    params:\n      limit: 0\n").unwrap();
+        assert!(state.reload_pipeline(&invalid).is_err());
+        assert!(state.get_scanner("Secrets").is_some());
+        assert_eq!(state.reload_status().failures, 1);
+
+        state.rollback_scanners().unwrap();
+        assert!(handler_view.get_scanner("scanner1").is_some());
+        assert_eq!(state.reload_status().version, 3);
+    }
+
     #[test]
     fn test_app_state_clone() {
         let config = AppConfig::default();
@@ -350,4 +1047,60 @@ mod tests {
         // Cache should be initialized with config values
         assert!(state.cache.get("nonexistent").is_none());
     }
+
+    #[derive(Default)]
+    struct CollectingSink {
+        events: std::sync::Mutex<Vec<ScanEvent>>,
+    }
+
+    impl ScanEventSink for CollectingSink {
+        fn record(&self, events: Vec<ScanEvent>) {
+            self.events.lock().unwrap().extend(events);
+        }
+    }
+
+    #[test]
+    fn test_record_scan_forwards_to_sink() {
+        let sink = Arc::new(CollectingSink::default());
+        let state = AppStateBuilder::new(AppConfig::default())
+            .with_event_sink(sink.clone())
+            .build();
+
+        let results = vec![ScannerResult {
+            scanner: "toxicity".to_string(),
+            is_valid: true,
+            risk_score: 0.1,
+            risk_factors: vec![],
+            entities: vec![],
+            execution_time_ms: Some(5),
+        }];
+        state.record_scan(Some("tenant-a"), ScanKind::Output, &results);
+
+        let events = sink.events.lock().unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].kind, ScanKind::Output);
+
+        // Without a sink, recording is a no-op
+        AppState::new(AppConfig::default()).record_scan(None, ScanKind::Prompt, &results);
+    }
+
+    #[cfg(feature = "cloud")]
+    #[tokio::test]
+    async fn test_rotated_secrets_reach_clones() {
+        let mut config = AppConfig::default();
+        config.auth.gateway_secret = Some("gw-1".to_string());
+        let state = AppState::new(config);
+        let handler_state = state.clone();
+        assert_eq!(handler_state.gateway_secret.get().as_deref(), Some("gw-1"));
+        assert_eq!(handler_state.upstream_api_key.get(), None);
+
+        state
+            .apply_rotated_secrets(vec![
+                ("auth.gateway_secret".to_string(), "gw-2".to_string()),
+                ("streaming.upstream_api_key".to_string(), "sk-2".to_string()),
+            ])
+            .await;
+        assert_eq!(handler_state.gateway_secret.get().as_deref(), Some("gw-2"));
+        assert_eq!(handler_state.upstream_api_key.get().as_deref(), Some("sk-2"));
+    }
 }
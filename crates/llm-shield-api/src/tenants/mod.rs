@@ -0,0 +1,26 @@
+//! Per-tenant scanner configuration
+//!
+//! A [`TenantProfile`] selects the scanners a tenant runs and adjusts their
+//! thresholds, banned lists and redaction. Profiles live in a
+//! [`ProfileStore`] and are mapped to callers by API key ID or gateway
+//! caller ID.
+//!
+//! ```text
+//! /v1/scan/* → ScanContext → TenantPipelines → ProfileStore
+//!                                  ↓
+//!                     profile.apply(base pipeline) → build → cache
+//! ```
+//!
+//! Requests without a profile keep using the shared scanners in
+//! [`crate::state::AppState::scanners`].
+
+pub mod pipelines;
+pub mod profile;
+pub mod store;
+
+pub use pipelines::{TenantPipelines, TenantScanners};
+pub use profile::{BannedLists, TenantProfile};
+pub use store::{FileProfileStore, MemoryProfileStore, ProfileStore};
+
+/// Result type for tenant operations
+pub type Result<T> = std::result::Result<T, llm_shield_core::Error>;
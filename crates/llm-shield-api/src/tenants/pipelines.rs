@@ -0,0 +1,307 @@
+//! Per-tenant scanner pipelines
+
+use super::{ProfileStore, Result, TenantProfile};
+use crate::config::TenantsConfig;
+use crate::extractors::ScanContext;
+use crate::state::ScannerRegistry;
+use llm_shield_core::{Error, Scanner};
+use llm_shield_scanners::{PipelineConfig, PipelineResources};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, RwLock};
+
+/// Scanners serving one request
+#[derive(Clone)]
+pub struct TenantScanners {
+    /// Profile the scanners were built for; `None` for the shared scanners
+    pub profile_id: Option<String>,
+
+    /// Scanners by name
+    pub scanners: Arc<ScannerRegistry>,
+}
+
+impl TenantScanners {
+    /// Get scanner by name
+    pub fn get(&self, name: &str) -> Option<Arc<dyn Scanner>> {
+        self.scanners.get(name).cloned()
+    }
+}
+
+/// A built pipeline and the profile it was built from
+struct CachedPipeline {
+    profile: TenantProfile,
+    scanners: Arc<ScannerRegistry>,
+    last_used: u64,
+}
+
+#[derive(Default)]
+struct PipelineCache {
+    entries: HashMap<String, CachedPipeline>,
+    /// Bumped whenever the base pipeline changes
+    generation: u64,
+    clock: u64,
+}
+
+/// Resolves tenant profiles and builds their pipelines
+///
+/// The profile is looked up by the caller (API key ID or gateway caller ID),
+/// then by the `x-tenant-id` header when it is trusted, then the default
+/// profile. Requests without a profile use the shared scanners.
+///
+/// Built pipelines are cached per profile and rebuilt when the stored
+/// profile or the base pipeline changes; the least recently used pipeline
+/// is evicted when the cache is full.
+pub struct TenantPipelines {
+    store: Arc<dyn ProfileStore>,
+    base: RwLock<(PipelineConfig, PipelineResources)>,
+    cache: Mutex<PipelineCache>,
+    max_cached: usize,
+    default_profile: Option<String>,
+    trust_tenant_header: bool,
+}
+
+impl TenantPipelines {
+    /// Create a resolver over a profile store
+    pub fn new(store: Arc<dyn ProfileStore>, max_cached: usize) -> Self {
+        Self {
+            store,
+            base: RwLock::new(Default::default()),
+            cache: Mutex::new(PipelineCache::default()),
+            max_cached: max_cached.max(1),
+            default_profile: None,
+            trust_tenant_header: false,
+        }
+    }
+
+    /// Create a resolver configured by `tenants`
+    pub fn from_config(config: &TenantsConfig, store: Arc<dyn ProfileStore>) -> Self {
+        let mut pipelines = Self::new(store, config.max_cached_pipelines);
+        pipelines.default_profile = config.default_profile.clone();
+        pipelines.trust_tenant_header = config.trust_tenant_header;
+        pipelines
+    }
+
+    /// Set the profile for callers not mapped to one
+    pub fn with_default_profile(mut self, id: impl Into<String>) -> Self {
+        self.default_profile = Some(id.into());
+        self
+    }
+
+    /// Select profiles by the `x-tenant-id` header
+    pub fn with_tenant_header(mut self, trusted: bool) -> Self {
+        self.trust_tenant_header = trusted;
+        self
+    }
+
+    /// Profile store
+    pub fn store(&self) -> &Arc<dyn ProfileStore> {
+        &self.store
+    }
+
+    /// Replace the pipeline that profiles without their own are derived from,
+    /// and the models their model-backed scanners are built with
+    pub fn set_base(&self, pipeline: PipelineConfig, resources: PipelineResources) {
+        *self.base.write().unwrap_or_else(|e| e.into_inner()) = (pipeline, resources);
+        self.clear();
+    }
+
+    /// Drop all built pipelines
+    pub fn clear(&self) {
+        let mut cache = self.lock_cache();
+        cache.entries.clear();
+        cache.generation += 1;
+    }
+
+    /// Number of built pipelines in the cache
+    pub fn cached_count(&self) -> usize {
+        self.lock_cache().entries.len()
+    }
+
+    /// Profile serving a request, if any
+    pub async fn profile_for(&self, context: &ScanContext) -> Result<Option<TenantProfile>> {
+        if let Some(caller_id) = &context.caller_id {
+            if let Some(profile) = self.store.get_by_caller(caller_id).await? {
+                return Ok(Some(profile));
+            }
+        }
+
+        if self.trust_tenant_header {
+            if let Some(tenant_id) = &context.tenant_id {
+                if let Some(profile) = self.store.get(tenant_id).await? {
+                    return Ok(Some(profile));
+                }
+            }
+        }
+
+        match &self.default_profile {
+            Some(id) => self.store.get(id).await?.map(Some).ok_or_else(|| {
+                Error::config(format!("Default tenant profile '{}' does not exist", id))
+            }),
+            None => Ok(None),
+        }
+    }
+
+    /// Scanners of the request's profile; `None` when no profile applies
+    pub async fn resolve(&self, context: &ScanContext) -> Result<Option<TenantScanners>> {
+        let Some(profile) = self.profile_for(context).await? else {
+            return Ok(None);
+        };
+        let scanners = self.pipeline_for(&profile)?;
+        Ok(Some(TenantScanners {
+            profile_id: Some(profile.id),
+            scanners,
+        }))
+    }
+
+    /// Built scanners of a profile, from the cache when it is current
+    pub fn pipeline_for(&self, profile: &TenantProfile) -> Result<Arc<ScannerRegistry>> {
+        let generation = {
+            let mut cache = self.lock_cache();
+            cache.clock += 1;
+            let now = cache.clock;
+            if let Some(entry) = cache.entries.get_mut(&profile.id) {
+                if entry.profile == *profile {
+                    entry.last_used = now;
+                    return Ok(entry.scanners.clone());
+                }
+            }
+            cache.generation
+        };
+
+        // Built outside the lock; scanners may be slow to construct
+        let (pipeline, resources) = {
+            let base = self.base.read().unwrap_or_else(|e| e.into_inner());
+            (profile.apply(&base.0), base.1.clone())
+        };
+        let built = pipeline
+            .build_with(&resources)
+            .map_err(|e| Error::config(format!("Tenant profile '{}': {}", profile.id, e)))?;
+        let scanners: Arc<ScannerRegistry> = Arc::new(
+            built
+                .input
+                .into_iter()
+                .chain(built.output)
+                .map(|scanner| (scanner.name().to_string(), scanner))
+                .collect(),
+        );
+
+        let mut cache = self.lock_cache();
+        // A base pipeline swapped in meanwhile makes this build stale
+        if cache.generation == generation {
+            if !cache.entries.contains_key(&profile.id) && cache.entries.len() >= self.max_cached {
+                let oldest = cache
+                    .entries
+                    .iter()
+                    .min_by_key(|(_, entry)| entry.last_used)
+                    .map(|(id, _)| id.clone());
+                if let Some(oldest) = oldest {
+                    cache.entries.remove(&oldest);
+                }
+            }
+            let last_used = cache.clock;
+            cache.entries.insert(
+                profile.id.clone(),
+                CachedPipeline {
+                    profile: profile.clone(),
+                    scanners: scanners.clone(),
+                    last_used,
+                },
+            );
+        }
+
+        Ok(scanners)
+    }
+
+    fn lock_cache(&self) -> std::sync::MutexGuard<'_, PipelineCache> {
+        self.cache.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tenants::MemoryProfileStore;
+
+    fn context(caller_id: Option<&str>, tenant_id: Option<&str>) -> ScanContext {
+        ScanContext {
+            request_id: "req-1".to_string(),
+            caller_id: caller_id.map(str::to_string),
+            tenant_id: tenant_id.map(str::to_string),
+        }
+    }
+
+    async fn pipelines() -> TenantPipelines {
+        let store = Arc::new(MemoryProfileStore::new());
+        let mut acme = TenantProfile::new("acme");
+        acme.callers = vec!["key-1".to_string()];
+        acme.scanners = vec!["secrets".to_string()];
+        store.store(&acme).await.unwrap();
+        let mut globex = TenantProfile::new("globex");
+        globex.scanners = vec!["toxicity".to_string()];
+        store.store(&globex).await.unwrap();
+
+        let pipelines = TenantPipelines::new(store, 1);
+        pipelines.set_base(
+            PipelineConfig::from_yaml_str("input:\n  - type: secrets\n  - type: toxicity\n")
+                .unwrap(),
+            PipelineResources::default(),
+        );
+        pipelines
+    }
+
+    #[tokio::test]
+    async fn test_resolve_profile_by_caller() {
+        let pipelines = pipelines().await;
+
+        let resolved = pipelines
+            .resolve(&context(Some("key-1"), None))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(resolved.profile_id.as_deref(), Some("acme"));
+        assert_eq!(resolved.scanners.len(), 1);
+        assert!(resolved.get("Secrets").is_some());
+
+        // The tenant header is ignored unless trusted
+        assert!(pipelines
+            .resolve(&context(None, Some("globex")))
+            .await
+            .unwrap()
+            .is_none());
+        let pipelines = pipelines.with_tenant_header(true);
+        let resolved = pipelines
+            .resolve(&context(None, Some("globex")))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(resolved.profile_id.as_deref(), Some("globex"));
+
+        let pipelines = pipelines.with_default_profile("missing");
+        assert!(pipelines.resolve(&context(None, None)).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_pipeline_cache() {
+        let pipelines = pipelines().await;
+        let mut acme = pipelines.store().get("acme").await.unwrap().unwrap();
+
+        let first = pipelines.pipeline_for(&acme).unwrap();
+        assert!(Arc::ptr_eq(&first, &pipelines.pipeline_for(&acme).unwrap()));
+
+        // Changed profiles are rebuilt
+        acme.scanners.push("toxicity".to_string());
+        let second = pipelines.pipeline_for(&acme).unwrap();
+        assert_eq!(second.len(), 2);
+
+        // The least recently used pipeline is evicted
+        let globex = pipelines.store().get("globex").await.unwrap().unwrap();
+        pipelines.pipeline_for(&globex).unwrap();
+        assert_eq!(pipelines.cached_count(), 1);
+        assert!(!Arc::ptr_eq(
+            &second,
+            &pipelines.pipeline_for(&acme).unwrap()
+        ));
+
+        pipelines.set_base(PipelineConfig::default(), PipelineResources::default());
+        assert_eq!(pipelines.cached_count(), 0);
+    }
+}
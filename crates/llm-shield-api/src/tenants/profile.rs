@@ -0,0 +1,303 @@
+//! Tenant scanner profiles
+
+use super::Result;
+use llm_shield_core::Error;
+use llm_shield_scanners::pipeline::{INPUT_SCANNER_TYPES, OUTPUT_SCANNER_TYPES};
+use llm_shield_scanners::{PipelineConfig, ScannerSpec};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+
+/// Scanner types whose configuration has a `threshold`
+const THRESHOLD_SCANNER_TYPES: &[&str] = &[
+    "ban_code",
+    "ban_topics",
+    "bias",
+    "gibberish",
+    "invisible_text",
+    "malicious_urls",
+    "no_refusal",
+    "prompt_injection",
+    "relevance",
+    "sentiment",
+    "toxicity",
+];
+
+/// Redaction switch of each scanner type that can redact
+const REDACT_PARAMS: &[(&str, &str)] = &[
+    ("ban_code", "redact"),
+    ("ban_competitors", "redact"),
+    ("ban_substrings", "redact"),
+    ("regex", "redact"),
+    ("secrets", "redact"),
+    ("sensitive", "redact_mode"),
+];
+
+/// Scanner configuration for one tenant
+///
+/// A profile starts from its own `pipeline`, or the server's pipeline when
+/// unset, and narrows and adjusts it. Scanners are named by pipeline type
+/// (`secrets`, `ban_topics`, ...).
+///
+/// ```json
+/// {
+///   "id": "acme",
+///   "callers": ["key-id-1", "acme-gateway"],
+///   "scanners": ["secrets", "toxicity", "ban_substrings"],
+///   "thresholds": {"toxicity": 0.8},
+///   "banned": {"substrings": ["project falcon"]},
+///   "redact": true
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TenantProfile {
+    /// Profile ID
+    pub id: String,
+
+    /// API key IDs and gateway caller IDs served by this profile
+    #[serde(default)]
+    pub callers: Vec<String>,
+
+    /// Pipeline used instead of the server's pipeline
+    #[serde(default)]
+    pub pipeline: Option<PipelineConfig>,
+
+    /// Scanner types to run; empty = every scanner of the pipeline
+    ///
+    /// Types missing from the pipeline are added with default parameters.
+    #[serde(default)]
+    pub scanners: Vec<String>,
+
+    /// Threshold by scanner type
+    #[serde(default)]
+    pub thresholds: BTreeMap<String, f32>,
+
+    /// Banned lists added to the pipeline's
+    #[serde(default)]
+    pub banned: BannedLists,
+
+    /// Redact findings instead of only flagging them, for every scanner that
+    /// can; unset keeps the pipeline's behaviour
+    #[serde(default)]
+    pub redact: Option<bool>,
+
+    /// Further parameters by scanner type, applied last
+    #[serde(default)]
+    pub params: BTreeMap<String, Map<String, Value>>,
+}
+
+/// Tenant-specific banned lists
+///
+/// A non-empty list adds the matching input scanner when the pipeline does
+/// not have it.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BannedLists {
+    /// Substrings for `ban_substrings`
+    pub substrings: Vec<String>,
+
+    /// Competitor names for `ban_competitors`
+    pub competitors: Vec<String>,
+}
+
+impl TenantProfile {
+    /// Create an empty profile, which runs every scanner of the pipeline
+    pub fn new(id: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Check the profile without building its scanners
+    pub fn validate(&self) -> Result<()> {
+        if self.id.trim().is_empty() {
+            return Err(Error::config("Tenant profile ID cannot be empty"));
+        }
+
+        for scanner_type in &self.scanners {
+            if !is_known_type(scanner_type) {
+                return Err(Error::config(format!(
+                    "Tenant profile '{}': unknown scanner type '{}'",
+                    self.id, scanner_type
+                )));
+            }
+        }
+
+        for (scanner_type, threshold) in &self.thresholds {
+            if !THRESHOLD_SCANNER_TYPES.contains(&scanner_type.as_str()) {
+                return Err(Error::config(format!(
+                    "Tenant profile '{}': scanner type '{}' has no threshold",
+                    self.id, scanner_type
+                )));
+            }
+            if !(0.0..=1.0).contains(threshold) {
+                return Err(Error::config(format!(
+                    "Tenant profile '{}': threshold for '{}' must be between 0.0 and 1.0",
+                    self.id, scanner_type
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The tenant's pipeline, derived from `base` unless the profile has its own
+    pub fn apply(&self, base: &PipelineConfig) -> PipelineConfig {
+        let mut pipeline = self.pipeline.clone().unwrap_or_else(|| base.clone());
+
+        if !self.scanners.is_empty() {
+            let selected = |spec: &ScannerSpec| self.scanners.contains(&spec.scanner_type);
+            pipeline.input.retain(selected);
+            pipeline.output.retain(selected);
+
+            for scanner_type in &self.scanners {
+                let present = pipeline
+                    .input
+                    .iter()
+                    .chain(&pipeline.output)
+                    .any(|spec| &spec.scanner_type == scanner_type);
+                if present {
+                    continue;
+                }
+                // Unknown types are left to the pipeline build to report
+                if OUTPUT_SCANNER_TYPES.contains(&scanner_type.as_str())
+                    && !INPUT_SCANNER_TYPES.contains(&scanner_type.as_str())
+                {
+                    pipeline.output.push(ScannerSpec::new(scanner_type));
+                } else {
+                    pipeline.input.push(ScannerSpec::new(scanner_type));
+                }
+            }
+        }
+
+        for (scanner_type, key, values) in [
+            ("ban_substrings", "substrings", &self.banned.substrings),
+            ("ban_competitors", "competitors", &self.banned.competitors),
+        ] {
+            if values.is_empty() {
+                continue;
+            }
+            if !pipeline
+                .input
+                .iter()
+                .any(|spec| spec.scanner_type == scanner_type)
+            {
+                pipeline.input.push(ScannerSpec::new(scanner_type));
+            }
+            for spec in pipeline
+                .input
+                .iter_mut()
+                .filter(|spec| spec.scanner_type == scanner_type)
+            {
+                let list = spec
+                    .params
+                    .entry(key)
+                    .or_insert_with(|| Value::Array(Vec::new()));
+                if let Value::Array(list) = list {
+                    list.extend(values.iter().cloned().map(Value::from));
+                }
+            }
+        }
+
+        for spec in pipeline.input.iter_mut().chain(pipeline.output.iter_mut()) {
+            if let Some(threshold) = self.thresholds.get(&spec.scanner_type) {
+                spec.params
+                    .insert("threshold".to_string(), Value::from(*threshold));
+            }
+            if let Some(redact) = self.redact {
+                if let Some((_, key)) = REDACT_PARAMS
+                    .iter()
+                    .find(|(scanner_type, _)| *scanner_type == spec.scanner_type)
+                {
+                    spec.params.insert(key.to_string(), Value::from(redact));
+                }
+            }
+            if let Some(params) = self.params.get(&spec.scanner_type) {
+                spec.params.extend(params.clone());
+            }
+        }
+
+        pipeline
+    }
+}
+
+fn is_known_type(scanner_type: &str) -> bool {
+    INPUT_SCANNER_TYPES.contains(&scanner_type) || OUTPUT_SCANNER_TYPES.contains(&scanner_type)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn base() -> PipelineConfig {
+        PipelineConfig::from_yaml_str(
+            "input:\n  - type: secrets\n  - type: toxicity\n  - type: ban_substrings\n    params:\n      substrings: [\"shared\"]\noutput:\n  - type: sensitive\n",
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_profile_selects_and_adjusts_scanners() {
+        let profile: TenantProfile = serde_json::from_value(serde_json::json!({
+            "id": "acme",
+            "scanners": ["toxicity", "ban_substrings", "sensitive", "ban_topics"],
+            "thresholds": {"toxicity": 0.8},
+            "banned": {"substrings": ["falcon"], "competitors": ["Globex"]},
+            "redact": true
+        }))
+        .unwrap();
+        profile.validate().unwrap();
+
+        let pipeline = profile.apply(&base());
+        let types: Vec<_> = pipeline
+            .input
+            .iter()
+            .map(|s| s.scanner_type.as_str())
+            .collect();
+        assert_eq!(types, ["toxicity", "ban_substrings", "ban_competitors"]);
+        let types: Vec<_> = pipeline
+            .output
+            .iter()
+            .map(|s| s.scanner_type.as_str())
+            .collect();
+        assert_eq!(types, ["sensitive", "ban_topics"]);
+
+        assert_eq!(
+            pipeline.input[0].params["threshold"],
+            serde_json::json!(0.8f32)
+        );
+        assert_eq!(
+            pipeline.input[1].params["substrings"],
+            serde_json::json!(["shared", "falcon"])
+        );
+        assert_eq!(pipeline.input[1].params["redact"], serde_json::json!(true));
+        assert_eq!(
+            pipeline.input[2].params["competitors"],
+            serde_json::json!(["Globex"])
+        );
+        assert_eq!(
+            pipeline.output[0].params["redact_mode"],
+            serde_json::json!(true)
+        );
+        assert!(pipeline.build().is_ok());
+    }
+
+    #[test]
+    fn test_profile_validation() {
+        assert!(TenantProfile::new("").validate().is_err());
+        assert!(TenantProfile::new("acme").validate().is_ok());
+
+        let mut profile = TenantProfile::new("acme");
+        profile.scanners = vec!["nope".to_string()];
+        assert!(profile.validate().is_err());
+
+        let mut profile = TenantProfile::new("acme");
+        profile.thresholds.insert("secrets".to_string(), 0.5);
+        assert!(profile.validate().is_err());
+
+        profile.thresholds = BTreeMap::from([("toxicity".to_string(), 1.5)]);
+        assert!(profile.validate().is_err());
+    }
+}
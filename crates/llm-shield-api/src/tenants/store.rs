@@ -0,0 +1,303 @@
+//! Tenant profile storage backends
+
+use super::{Result, TenantProfile};
+use async_trait::async_trait;
+use llm_shield_core::Error;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// Tenant profile storage trait
+///
+/// ## Implementations
+///
+/// - `MemoryProfileStore`: In-memory HashMap (testing/development)
+/// - `FileProfileStore`: JSON file persistence
+#[async_trait]
+pub trait ProfileStore: Send + Sync {
+    /// Store or replace a profile
+    async fn store(&self, profile: &TenantProfile) -> Result<()>;
+
+    /// Retrieve a profile by its ID
+    async fn get(&self, id: &str) -> Result<Option<TenantProfile>>;
+
+    /// Retrieve the profile serving an API key ID or gateway caller ID
+    async fn get_by_caller(&self, caller_id: &str) -> Result<Option<TenantProfile>>;
+
+    /// Delete a profile by ID
+    async fn delete(&self, id: &str) -> Result<()>;
+
+    /// List all profiles
+    async fn list(&self) -> Result<Vec<TenantProfile>>;
+}
+
+/// Reject a profile that is invalid or claims a caller of another profile
+fn check_profile(profiles: &HashMap<String, TenantProfile>, profile: &TenantProfile) -> Result<()> {
+    profile.validate()?;
+    let taken = profiles
+        .values()
+        .filter(|other| other.id != profile.id)
+        .flat_map(|other| other.callers.iter().map(move |caller| (caller, &other.id)))
+        .find(|(caller, _)| profile.callers.contains(caller));
+    if let Some((caller, other)) = taken {
+        return Err(Error::config(format!(
+            "Caller '{}' is already served by tenant profile '{}'",
+            caller, other
+        )));
+    }
+    Ok(())
+}
+
+fn find_by_caller(
+    profiles: &HashMap<String, TenantProfile>,
+    caller_id: &str,
+) -> Option<TenantProfile> {
+    profiles
+        .values()
+        .find(|profile| profile.callers.iter().any(|caller| caller == caller_id))
+        .cloned()
+}
+
+/// In-memory profile storage (for testing/development)
+pub struct MemoryProfileStore {
+    profiles: Arc<RwLock<HashMap<String, TenantProfile>>>,
+}
+
+impl MemoryProfileStore {
+    /// Create a new in-memory store
+    pub fn new() -> Self {
+        Self {
+            profiles: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+}
+
+impl Default for MemoryProfileStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl ProfileStore for MemoryProfileStore {
+    async fn store(&self, profile: &TenantProfile) -> Result<()> {
+        let mut profiles = self.profiles.write().await;
+        check_profile(&profiles, profile)?;
+        profiles.insert(profile.id.clone(), profile.clone());
+        Ok(())
+    }
+
+    async fn get(&self, id: &str) -> Result<Option<TenantProfile>> {
+        let profiles = self.profiles.read().await;
+        Ok(profiles.get(id).cloned())
+    }
+
+    async fn get_by_caller(&self, caller_id: &str) -> Result<Option<TenantProfile>> {
+        let profiles = self.profiles.read().await;
+        Ok(find_by_caller(&profiles, caller_id))
+    }
+
+    async fn delete(&self, id: &str) -> Result<()> {
+        let mut profiles = self.profiles.write().await;
+        profiles.remove(id);
+        Ok(())
+    }
+
+    async fn list(&self) -> Result<Vec<TenantProfile>> {
+        let profiles = self.profiles.read().await;
+        Ok(profiles.values().cloned().collect())
+    }
+}
+
+/// File-based profile storage (JSON persistence)
+///
+/// Profiles are loaded once and written back atomically (temp file +
+/// rename) on every change.
+///
+/// ## File Format
+///
+/// ```json
+/// {
+///   "profiles": [
+///     {
+///       "id": "acme",
+///       "callers": ["..."],
+///       "scanners": ["secrets", "toxicity"],
+///       ...
+///     }
+///   ]
+/// }
+/// ```
+pub struct FileProfileStore {
+    file_path: PathBuf,
+    profiles: Arc<RwLock<HashMap<String, TenantProfile>>>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+struct ProfileFile {
+    profiles: Vec<TenantProfile>,
+}
+
+impl FileProfileStore {
+    /// Open a profile file, starting empty if it does not exist
+    pub async fn new<P: Into<PathBuf>>(file_path: P) -> Result<Self> {
+        let file_path = file_path.into();
+
+        if let Some(parent) = file_path.parent() {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .map_err(|e| Error::config(format!("Failed to create profile directory: {}", e)))?;
+        }
+
+        let storage = Self {
+            file_path,
+            profiles: Arc::new(RwLock::new(HashMap::new())),
+        };
+        storage.load().await?;
+
+        Ok(storage)
+    }
+
+    /// Load profiles from file
+    async fn load(&self) -> Result<()> {
+        if !tokio::fs::try_exists(&self.file_path)
+            .await
+            .unwrap_or(false)
+        {
+            return Ok(());
+        }
+
+        let contents = tokio::fs::read_to_string(&self.file_path)
+            .await
+            .map_err(|e| Error::config(format!("Failed to read profiles file: {}", e)))?;
+
+        let profile_file: ProfileFile = serde_json::from_str(&contents)
+            .map_err(|e| Error::config(format!("Failed to parse profiles file: {}", e)))?;
+
+        let mut profiles = self.profiles.write().await;
+        profiles.clear();
+        for profile in profile_file.profiles {
+            check_profile(&profiles, &profile)?;
+            profiles.insert(profile.id.clone(), profile);
+        }
+
+        Ok(())
+    }
+
+    /// Save profiles to file
+    async fn save(&self, profiles: &HashMap<String, TenantProfile>) -> Result<()> {
+        let mut list: Vec<_> = profiles.values().cloned().collect();
+        list.sort_by(|a, b| a.id.cmp(&b.id));
+        let contents = serde_json::to_string_pretty(&ProfileFile { profiles: list })
+            .map_err(|e| Error::config(format!("Failed to serialize profiles: {}", e)))?;
+
+        let temp_path = self.file_path.with_extension("tmp");
+        tokio::fs::write(&temp_path, contents)
+            .await
+            .map_err(|e| Error::config(format!("Failed to write temp file: {}", e)))?;
+
+        tokio::fs::rename(&temp_path, &self.file_path)
+            .await
+            .map_err(|e| Error::config(format!("Failed to rename temp file: {}", e)))?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ProfileStore for FileProfileStore {
+    async fn store(&self, profile: &TenantProfile) -> Result<()> {
+        let mut profiles = self.profiles.write().await;
+        check_profile(&profiles, profile)?;
+        let previous = profiles.insert(profile.id.clone(), profile.clone());
+
+        // Keep memory and file in step when the write fails
+        if let Err(e) = self.save(&profiles).await {
+            match previous {
+                Some(previous) => profiles.insert(profile.id.clone(), previous),
+                None => profiles.remove(&profile.id),
+            };
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    async fn get(&self, id: &str) -> Result<Option<TenantProfile>> {
+        let profiles = self.profiles.read().await;
+        Ok(profiles.get(id).cloned())
+    }
+
+    async fn get_by_caller(&self, caller_id: &str) -> Result<Option<TenantProfile>> {
+        let profiles = self.profiles.read().await;
+        Ok(find_by_caller(&profiles, caller_id))
+    }
+
+    async fn delete(&self, id: &str) -> Result<()> {
+        let mut profiles = self.profiles.write().await;
+        if let Some(previous) = profiles.remove(id) {
+            if let Err(e) = self.save(&profiles).await {
+                profiles.insert(previous.id.clone(), previous);
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+
+    async fn list(&self) -> Result<Vec<TenantProfile>> {
+        let profiles = self.profiles.read().await;
+        Ok(profiles.values().cloned().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile(id: &str, callers: &[&str]) -> TenantProfile {
+        TenantProfile {
+            callers: callers.iter().map(|c| c.to_string()).collect(),
+            ..TenantProfile::new(id)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_memory_profile_store() {
+        let store = MemoryProfileStore::new();
+        store.store(&profile("acme", &["key-1"])).await.unwrap();
+
+        assert_eq!(store.get("acme").await.unwrap().unwrap().id, "acme");
+        assert_eq!(
+            store.get_by_caller("key-1").await.unwrap().unwrap().id,
+            "acme"
+        );
+        assert!(store.get_by_caller("key-2").await.unwrap().is_none());
+
+        // A caller belongs to one profile
+        assert!(store.store(&profile("globex", &["key-1"])).await.is_err());
+        store.store(&profile("acme", &["key-2"])).await.unwrap();
+        store.store(&profile("globex", &["key-1"])).await.unwrap();
+        assert_eq!(store.list().await.unwrap().len(), 2);
+
+        store.delete("acme").await.unwrap();
+        assert!(store.get("acme").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_file_profile_store_persists() {
+        let path =
+            std::env::temp_dir().join(format!("tenant-profiles-{}.json", uuid::Uuid::new_v4()));
+
+        let store = FileProfileStore::new(&path).await.unwrap();
+        let mut acme = profile("acme", &["key-1"]);
+        acme.thresholds.insert("toxicity".to_string(), 0.8);
+        store.store(&acme).await.unwrap();
+        store.store(&profile("globex", &[])).await.unwrap();
+        store.delete("globex").await.unwrap();
+
+        let reopened = FileProfileStore::new(&path).await.unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(reopened.get_by_caller("key-1").await.unwrap(), Some(acme));
+        assert_eq!(reopened.list().await.unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,324 @@
+//! Microsoft Entra ID access tokens for the Azure REST APIs.
+//!
+//! [`AzureCredential::from_env`] resolves credentials the way
+//! `DefaultAzureCredential` does for the common cases:
+//!
+//! 1. Service principal: `AZURE_TENANT_ID`, `AZURE_CLIENT_ID`, `AZURE_CLIENT_SECRET`
+//!    (and optionally `AZURE_AUTHORITY_HOST`)
+//! 2. App Service / Container Apps managed identity: `IDENTITY_ENDPOINT`, `IDENTITY_HEADER`
+//! 3. VM managed identity through IMDS (user-assigned with `AZURE_CLIENT_ID`)
+
+use llm_shield_cloud::{CloudError, Result};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::sync::Mutex;
+
+const DEFAULT_AUTHORITY_HOST: &str = "https://login.microsoftonline.com";
+const IMDS_ENDPOINT: &str = "http://169.254.169.254/metadata/identity/oauth2/token";
+
+/// Tokens are refreshed this long before they expire.
+const REFRESH_MARGIN: Duration = Duration::from_secs(300);
+
+/// Lifetime assumed when a token response carries no expiry.
+const DEFAULT_LIFETIME: Duration = Duration::from_secs(3600);
+
+enum Source {
+    AccessToken(String),
+    ClientSecret {
+        authority_host: String,
+        tenant_id: String,
+        client_id: String,
+        client_secret: String,
+    },
+    ManagedIdentity {
+        endpoint: String,
+        /// `X-IDENTITY-HEADER` of App Service; IMDS when absent
+        identity_header: Option<String>,
+        client_id: Option<String>,
+    },
+}
+
+struct CachedToken {
+    token: String,
+    expires_at: SystemTime,
+}
+
+/// Source of bearer tokens for Azure resources, with per-resource caching.
+pub struct AzureCredential {
+    source: Source,
+    client: reqwest::Client,
+    tokens: Mutex<HashMap<String, CachedToken>>,
+}
+
+impl AzureCredential {
+    fn with_source(source: Source) -> Self {
+        Self {
+            source,
+            client: reqwest::Client::new(),
+            tokens: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Resolves credentials from the environment (see the module docs).
+    pub fn from_env() -> Self {
+        let var = |name: &str| std::env::var(name).ok().filter(|v| !v.is_empty());
+
+        if let (Some(tenant_id), Some(client_id), Some(client_secret)) = (
+            var("AZURE_TENANT_ID"),
+            var("AZURE_CLIENT_ID"),
+            var("AZURE_CLIENT_SECRET"),
+        ) {
+            let credential = Self::client_secret(tenant_id, client_id, client_secret);
+            return match var("AZURE_AUTHORITY_HOST") {
+                Some(host) => credential.with_authority_host(host),
+                None => credential,
+            };
+        }
+
+        if let (Some(endpoint), Some(header)) = (var("IDENTITY_ENDPOINT"), var("IDENTITY_HEADER")) {
+            return Self::with_source(Source::ManagedIdentity {
+                endpoint,
+                identity_header: Some(header),
+                client_id: var("AZURE_CLIENT_ID"),
+            });
+        }
+
+        Self::managed_identity(var("AZURE_CLIENT_ID"))
+    }
+
+    /// Uses a fixed bearer token, e.g. from `az account get-access-token`.
+    pub fn access_token(token: impl Into<String>) -> Self {
+        Self::with_source(Source::AccessToken(token.into()))
+    }
+
+    /// Uses a service principal's client secret.
+    pub fn client_secret(
+        tenant_id: impl Into<String>,
+        client_id: impl Into<String>,
+        client_secret: impl Into<String>,
+    ) -> Self {
+        Self::with_source(Source::ClientSecret {
+            authority_host: DEFAULT_AUTHORITY_HOST.to_string(),
+            tenant_id: tenant_id.into(),
+            client_id: client_id.into(),
+            client_secret: client_secret.into(),
+        })
+    }
+
+    /// Uses the VM's managed identity through IMDS; `client_id` selects a
+    /// user-assigned identity.
+    pub fn managed_identity(client_id: Option<String>) -> Self {
+        Self::with_source(Source::ManagedIdentity {
+            endpoint: IMDS_ENDPOINT.to_string(),
+            identity_header: None,
+            client_id,
+        })
+    }
+
+    /// Sets the Entra ID authority of a service principal, e.g. for
+    /// sovereign clouds.
+    pub fn with_authority_host(mut self, host: impl Into<String>) -> Self {
+        if let Source::ClientSecret { authority_host, .. } = &mut self.source {
+            *authority_host = host.into().trim_end_matches('/').to_string();
+        }
+        self
+    }
+
+    /// Bearer token for `resource`, e.g. `https://monitoring.azure.com/`.
+    ///
+    /// Tokens are cached until shortly before they expire.
+    pub async fn token(&self, resource: &str) -> Result<String> {
+        if let Source::AccessToken(token) = &self.source {
+            return Ok(token.clone());
+        }
+
+        let mut tokens = self.tokens.lock().await;
+        if let Some(cached) = tokens.get(resource) {
+            if SystemTime::now() + REFRESH_MARGIN < cached.expires_at {
+                return Ok(cached.token.clone());
+            }
+        }
+
+        let fetched = self.fetch(resource).await?;
+        let token = fetched.token.clone();
+        tokens.insert(resource.to_string(), fetched);
+        Ok(token)
+    }
+
+    async fn fetch(&self, resource: &str) -> Result<CachedToken> {
+        let request = match &self.source {
+            Source::AccessToken(_) => unreachable!("fixed tokens are not fetched"),
+            Source::ClientSecret {
+                authority_host,
+                tenant_id,
+                client_id,
+                client_secret,
+            } => {
+                let scope = format!("{}/.default", resource.trim_end_matches('/'));
+                self.client
+                    .post(format!("{authority_host}/{tenant_id}/oauth2/v2.0/token"))
+                    .form(&[
+                        ("grant_type", "client_credentials"),
+                        ("client_id", client_id.as_str()),
+                        ("client_secret", client_secret.as_str()),
+                        ("scope", scope.as_str()),
+                    ])
+            }
+            Source::ManagedIdentity {
+                endpoint,
+                identity_header,
+                client_id,
+            } => {
+                let mut query = vec![("resource", resource)];
+                if let Some(client_id) = client_id {
+                    query.push(("client_id", client_id.as_str()));
+                }
+                match identity_header {
+                    Some(header) => {
+                        query.push(("api-version", "2019-08-01"));
+                        self.client
+                            .get(endpoint)
+                            .header("X-IDENTITY-HEADER", header)
+                            .query(&query)
+                    }
+                    None => {
+                        query.push(("api-version", "2018-02-01"));
+                        self.client
+                            .get(endpoint)
+                            .header("Metadata", "true")
+                            .query(&query)
+                    }
+                }
+            }
+        };
+
+        let response = request
+            .timeout(Duration::from_secs(30))
+            .send()
+            .await
+            .map_err(|e| CloudError::AuthFailed(format!("Azure token request failed: {e}")))?;
+        let status = response.status();
+        let body: Value = response.json().await.unwrap_or_default();
+        if !status.is_success() {
+            let reason = body
+                .get("error_description")
+                .or_else(|| body.get("error"))
+                .map_or_else(|| status.to_string(), Value::to_string);
+            return Err(CloudError::AuthFailed(format!(
+                "Azure token request for {resource} rejected: {reason}"
+            )));
+        }
+
+        let token = body
+            .get("access_token")
+            .and_then(Value::as_str)
+            .ok_or_else(|| {
+                CloudError::AuthFailed("Azure token response has no access_token".to_string())
+            })?
+            .to_string();
+        Ok(CachedToken {
+            token,
+            expires_at: expiry(&body),
+        })
+    }
+}
+
+/// Expiry of a token response: `expires_in` seconds (Entra ID, IMDS) or the
+/// `expires_on` Unix time (App Service), as a number or a string.
+fn expiry(body: &Value) -> SystemTime {
+    let seconds = |key: &str| {
+        body.get(key).and_then(|v| match v {
+            Value::Number(n) => n.as_u64(),
+            Value::String(s) => s.parse().ok(),
+            _ => None,
+        })
+    };
+
+    if let Some(expires_in) = seconds("expires_in") {
+        SystemTime::now() + Duration::from_secs(expires_in)
+    } else if let Some(expires_on) = seconds("expires_on") {
+        UNIX_EPOCH + Duration::from_secs(expires_on)
+    } else {
+        SystemTime::now() + DEFAULT_LIFETIME
+    }
+}
+
+impl std::fmt::Debug for AzureCredential {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let source = match &self.source {
+            Source::AccessToken(_) => "access_token",
+            Source::ClientSecret { .. } => "client_secret",
+            Source::ManagedIdentity { .. } => "managed_identity",
+        };
+        f.debug_struct("AzureCredential")
+            .field("source", &source)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::extract::{Path, State};
+    use axum::Form;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_expiry_formats() {
+        let in_an_hour = SystemTime::now() + Duration::from_secs(3599);
+        let expires_in = expiry(&serde_json::json!({ "expires_in": "3600" }));
+        assert!(expires_in >= in_an_hour);
+
+        let expires_on = expiry(&serde_json::json!({ "expires_on": 1_700_000_000 }));
+        assert_eq!(expires_on, UNIX_EPOCH + Duration::from_secs(1_700_000_000));
+    }
+
+    #[tokio::test]
+    async fn test_client_secret_tokens_are_cached_per_resource() {
+        async fn token(
+            State(requests): State<Arc<AtomicUsize>>,
+            Path(tenant): Path<String>,
+            Form(form): Form<HashMap<String, String>>,
+        ) -> axum::Json<Value> {
+            requests.fetch_add(1, Ordering::SeqCst);
+            assert_eq!(tenant, "tenant-1");
+            assert_eq!(form["grant_type"], "client_credentials");
+            assert_eq!(form["client_secret"], "secret");
+            axum::Json(serde_json::json!({
+                "token_type": "Bearer",
+                "expires_in": 3599,
+                "access_token": format!("token-for-{}", form["scope"]),
+            }))
+        }
+
+        let requests = Arc::new(AtomicUsize::new(0));
+        let app = axum::Router::new()
+            .route("/:tenant/oauth2/v2.0/token", axum::routing::post(token))
+            .with_state(requests.clone());
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let credential = AzureCredential::client_secret("tenant-1", "client-1", "secret")
+            .with_authority_host(format!("http://{addr}/"));
+
+        let monitoring = credential
+            .token("https://monitoring.azure.com/")
+            .await
+            .unwrap();
+        assert_eq!(
+            monitoring,
+            "token-for-https://monitoring.azure.com/.default"
+        );
+        credential
+            .token("https://monitoring.azure.com/")
+            .await
+            .unwrap();
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+
+        credential.token("https://vault.azure.net").await.unwrap();
+        assert_eq!(requests.load(Ordering::SeqCst), 2);
+    }
+}
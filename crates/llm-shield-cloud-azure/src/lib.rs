@@ -6,7 +6,11 @@
 //! - **Secret Management**: Azure Key Vault via `AzureKeyVault`
 //! - **Object Storage**: Azure Blob Storage via `AzureBlobStorage`
 //! - **Metrics**: Azure Monitor Metrics via `AzureMonitorMetrics`
-//! - **Logging**: Azure Monitor Logs via `AzureMonitorLogs`
+//! - **Logging**: Azure Monitor Logs via `AzureMonitorLogs`, Application Insights via `AzureAppInsights`
+//! - **Tracing**: Application Insights via `AzureTracer`
+//!
+//! Observability talks to the Azure REST APIs directly; Entra ID tokens come
+//! from [`AzureCredential`].
 //!
 //! # Features
 //!
@@ -148,9 +152,9 @@
 //! #[tokio::main]
 //! async fn main() -> Result<(), Box<dyn std::error::Error>> {
 //!     let logger = AzureMonitorLogs::new(
-//!         "workspace-id",
-//!         "shared-key",
-//!         "LLMShieldLog"
+//!         "https://shield-dce-abcd.eastus-1.ingest.monitor.azure.com",
+//!         "dcr-00000000000000000000000000000000",
+//!         "Custom-LLMShieldLog_CL"
 //!     ).await?;
 //!
 //!     // Simple logging
@@ -167,21 +171,27 @@
 //!         labels,
 //!         trace_id: Some("trace-789".to_string()),
 //!         span_id: Some("span-012".to_string()),
+//!         source: None,
 //!     };
 //!
 //!     logger.log_structured(&entry).await?;
 //!
+//!     // Entries are exported in the background; wait for delivery
+//!     logger.flush().await?;
+//!
 //!     Ok(())
 //! }
 //! ```
 //!
 //! # Azure Credentials
 //!
-//! This crate uses DefaultAzureCredential which tries:
+//! [`AzureCredential::from_env`] tries:
 //!
 //! 1. **Environment variables**: AZURE_TENANT_ID, AZURE_CLIENT_ID, AZURE_CLIENT_SECRET
-//! 2. **Azure CLI**: `az login` credentials
-//! 3. **Managed Identity**: For Azure VMs, App Service, Container Apps, etc.
+//! 2. **Managed Identity**: App Service and Container Apps (IDENTITY_ENDPOINT)
+//! 3. **Managed Identity**: Azure VMs through IMDS
+//!
+//! Application Insights authenticates with its connection string instead.
 //!
 //! # RBAC Permissions
 //!
@@ -206,15 +216,16 @@
 //!       account_name: mystorageaccount
 //!       container_name: models
 //!     monitor:
-//!       workspace_id: workspace-id
-//!       log_type: LLMShieldLog
+//!       endpoint: https://shield-dce-abcd.eastus-1.ingest.monitor.azure.com
+//!       rule_id: dcr-00000000000000000000000000000000
+//!       stream_name: Custom-LLMShieldLog_CL
 //! ```
 //!
 //! # Performance
 //!
 //! - **Secret caching**: >90% cache hit rate reduces API calls
 //! - **Block blob uploads**: Automatically used for objects >4MB
-//! - **Batch export**: Metrics (20/batch) and logs (100/batch)
+//! - **Batch export**: Background batching with retry of throttled requests
 //! - **Async operations**: All I/O is fully asynchronous with tokio
 //!
 //! # Testing
@@ -229,28 +240,32 @@
 //!
 //! MIT OR Apache-2.0
 
+pub mod credential;
+pub mod observability;
+
 // Stub implementations due to SDK breaking changes
 // TODO: Update to latest Azure SDK APIs
-pub mod observability_stub;
 pub mod secrets_stub;
 pub mod storage_stub;
 
 // Re-export main types
-pub use observability_stub::{AzureMonitor, AzureAppInsights};
+pub use credential::AzureCredential;
+pub use observability::{
+    AzureAppInsights, AzureMonitorLogs, AzureMonitorMetrics, AzureTracer, ExportOptions,
+};
 pub use secrets_stub::AzureKeyVault;
 pub use storage_stub::AzureBlobStorage;
 
 // Keep original modules but don't compile them
 // #[cfg(feature = "azure-full-impl")]
-// pub mod observability;
-// #[cfg(feature = "azure-full-impl")]
 // pub mod secrets;
 // #[cfg(feature = "azure-full-impl")]
 // pub mod storage;
 
 // Re-export cloud abstractions for convenience
 pub use llm_shield_cloud::{
-    CloudError, CloudLogger, CloudMetrics, CloudSecretManager, CloudStorage, GetObjectOptions,
+    CloudError, CloudLogger, CloudMetrics, CloudSecretManager, CloudStorage, CloudTracer,
+    GetObjectOptions,
     LogEntry, LogLevel, Metric, ObjectMetadata, PutObjectOptions, Result, SecretMetadata,
     SecretValue,
 };
@@ -1,260 +1,364 @@
-//! Azure Monitor observability integration.
+//! Azure Monitor and Application Insights observability integration.
 //!
-//! Provides implementations of `CloudMetrics` and `CloudLogger` traits for Azure Monitor.
-
+//! Provides implementations of `CloudMetrics`, `CloudLogger` and `CloudTracer`
+//! on top of the Azure REST APIs:
+//!
+//! - [`AzureMonitorMetrics`] with the Azure Monitor custom metrics API
+//! - [`AzureMonitorLogs`] with the Azure Monitor Logs Ingestion API
+//! - [`AzureAppInsights`] and [`AzureTracer`] with the Application Insights ingestion API
+//!
+//! Each exporter queues items and sends them in batches from a background
+//! task (see [`ExportOptions`]), so recording never waits on the network.
+//! Throttled and unavailable requests are retried with exponential backoff.
+//! Use `flush` to wait for delivery, e.g. before shutdown.
+
+use crate::credential::AzureCredential;
+use chrono::{DateTime, SecondsFormat, Utc};
+use llm_shield_cloud::export::{self, AttemptError, BatchExport, BatchQueue};
 use llm_shield_cloud::{
-    async_trait, CloudError, CloudLogger, CloudMetrics, LogEntry, LogLevel, Metric, Result,
+    async_trait, BatchConfig, CloudError, CloudLogger, CloudMetrics, CloudTracer, LogEntry,
+    LogLevel, Metric, Result, RetryConfig, Span,
 };
-use reqwest::Client;
-use serde_json::json;
-use std::collections::HashMap;
+use serde_json::{json, Map, Value};
+use std::collections::BTreeMap;
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use std::time::{Duration, SystemTime};
+
+/// Token audience of the Azure Monitor custom metrics API.
+const MONITORING_RESOURCE: &str = "https://monitoring.azure.com/";
+
+/// Token audience of the Azure Monitor Logs Ingestion API.
+const LOGS_INGESTION_RESOURCE: &str = "https://monitor.azure.com/";
+
+/// Version of the Logs Ingestion API.
+const LOGS_INGESTION_API_VERSION: &str = "2023-01-01";
+
+/// Namespace of exported custom metrics.
+const METRIC_NAMESPACE: &str = "LLMShield";
+
+/// Application Insights ingestion endpoint used when the connection string
+/// names none.
+const APP_INSIGHTS_ENDPOINT: &str = "https://dc.services.visualstudio.com";
+
+/// Cloud role name reported to Application Insights.
+const CLOUD_ROLE: &str = "llm-shield";
+
+/// Endpoint, batching and retry settings of an exporter.
+#[derive(Debug, Clone)]
+pub struct ExportOptions {
+    /// Base URL replacing the service endpoint, e.g. for a proxy.
+    pub endpoint: Option<String>,
+
+    /// Batching of exported items.
+    pub batch: BatchConfig,
+
+    /// Retry of throttled or failed requests.
+    pub retry: RetryConfig,
+
+    /// Timeout of a single request in seconds.
+    pub timeout_seconds: u64,
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        Self {
+            endpoint: None,
+            batch: BatchConfig::default(),
+            retry: RetryConfig::default(),
+            timeout_seconds: 10,
+        }
+    }
+}
+
+impl ExportOptions {
+    fn endpoint_or(&self, default: String) -> String {
+        self.endpoint
+            .clone()
+            .unwrap_or(default)
+            .trim_end_matches('/')
+            .to_string()
+    }
+}
+
+/// Sends requests, retrying throttled and failed ones.
+struct HttpSender {
+    client: reqwest::Client,
+    retry: RetryConfig,
+}
+
+impl HttpSender {
+    fn new(options: &ExportOptions) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(options.timeout_seconds.max(1)))
+            .build()
+            .map_err(|e| CloudError::ClientInit(e.to_string()))?;
+        Ok(Self {
+            client,
+            retry: options.retry.clone(),
+        })
+    }
+
+    /// Sends the request built by `build` until it succeeds, returning the
+    /// response status and body.
+    async fn send<F>(&self, build: F) -> std::result::Result<(u16, String), String>
+    where
+        F: Fn(&reqwest::Client) -> reqwest::RequestBuilder,
+    {
+        export::retry(&self.retry, || async {
+            let response = build(&self.client).send().await.map_err(|e| {
+                if e.is_connect() || e.is_timeout() {
+                    AttemptError::retryable(e.to_string())
+                } else {
+                    AttemptError::permanent(e.to_string())
+                }
+            })?;
+
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            if status.is_success() {
+                Ok((status.as_u16(), body))
+            } else {
+                Err(AttemptError::http_status(
+                    status.as_u16(),
+                    format!("{status}: {body}"),
+                ))
+            }
+        })
+        .await
+    }
+}
 
 /// Azure Monitor Metrics implementation of `CloudMetrics`.
 ///
-/// This implementation provides:
-/// - Batched metric export for efficiency
-/// - Support for custom metrics
-/// - Automatic namespace configuration
-/// - Standard metric dimensions
+/// Metrics are sent to the custom metrics API of the resource as
+/// `LLMShield` namespace metrics. Values of the same metric, minute and
+/// dimension values in a batch are pre-aggregated into min, max, sum and
+/// count, as the API expects. Requires the *Monitoring Metrics Publisher*
+/// role on the resource.
 ///
 /// # Example
 ///
 /// ```no_run
 /// use llm_shield_cloud_azure::AzureMonitorMetrics;
 /// use llm_shield_cloud::{CloudMetrics, Metric};
-/// use std::collections::HashMap;
 ///
 /// #[tokio::main]
 /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
 ///     let metrics = AzureMonitorMetrics::new(
-///         "my-resource-id",
-///         "my-region"
-///     ).await?;
-///
-///     let metric = Metric {
-///         name: "RequestCount".to_string(),
-///         value: 1.0,
-///         timestamp: std::time::SystemTime::now()
-///             .duration_since(std::time::UNIX_EPOCH)?
-///             .as_secs(),
-///         dimensions: HashMap::new(),
-///         unit: Some("Count".to_string()),
-///     };
+///         "/subscriptions/sub-id/resourceGroups/rg/providers/Microsoft.App/containerApps/shield",
+///         "eastus",
+///     )
+///     .await?;
 ///
-///     metrics.export_metric(&metric).await?;
+///     metrics
+///         .export_metric(&Metric::new("RequestCount", 1.0).with_dimension("scanner", "toxicity"))
+///         .await?;
+///     metrics.flush().await?;
 ///     Ok(())
 /// }
 /// ```
 pub struct AzureMonitorMetrics {
-    client: Client,
     resource_id: String,
     region: String,
-    batch_buffer: Arc<RwLock<Vec<Metric>>>,
-    batch_size: usize,
+    queue: BatchQueue<Metric>,
+}
+
+struct MetricsExporter {
+    sender: HttpSender,
+    credential: Arc<AzureCredential>,
+    url: String,
+}
+
+#[async_trait]
+impl BatchExport<Metric> for MetricsExporter {
+    async fn export(&self, batch: Vec<Metric>) -> Result<()> {
+        let token = self
+            .credential
+            .token(MONITORING_RESOURCE)
+            .await
+            .map_err(|e| CloudError::MetricsExport(e.to_string()))?;
+
+        // Send every request, reporting the first failure
+        let mut result = Ok(());
+        for body in metric_requests(&batch) {
+            let sent = self
+                .sender
+                .send(|client| client.post(&self.url).bearer_auth(&token).json(&body))
+                .await;
+            if let (Err(message), Ok(())) = (sent, &result) {
+                result = Err(CloudError::MetricsExport(message));
+            }
+        }
+        result
+    }
 }
 
 impl AzureMonitorMetrics {
-    /// Creates a new Azure Monitor Metrics client with default configuration.
+    /// Creates a metrics exporter for an Azure resource.
     ///
     /// # Arguments
     ///
     /// * `resource_id` - Azure resource ID (e.g., "/subscriptions/.../resourceGroups/.../...")
-    /// * `region` - Azure region (e.g., "eastus", "westeurope")
+    /// * `region` - Azure region of the resource (e.g., "eastus", "westeurope")
+    ///
+    /// Credentials are resolved with [`AzureCredential::from_env`].
     ///
     /// # Errors
     ///
-    /// Returns error if configuration is invalid.
+    /// Returns error if the HTTP client cannot be created.
     pub async fn new(resource_id: impl Into<String>, region: impl Into<String>) -> Result<Self> {
-        let resource_id = resource_id.into();
-        let region = region.into();
-
-        let client = Client::new();
-
-        tracing::info!(
-            "Initialized Azure Monitor Metrics client for resource: {} in region: {}",
-            resource_id,
-            region
-        );
-
-        Ok(Self {
-            client,
+        Self::with_options(
             resource_id,
             region,
-            batch_buffer: Arc::new(RwLock::new(Vec::new())),
-            batch_size: 20,
-        })
+            AzureCredential::from_env(),
+            ExportOptions::default(),
+        )
     }
 
-    /// Creates a new Azure Monitor Metrics client with custom batch size.
-    ///
-    /// # Arguments
-    ///
-    /// * `resource_id` - Azure resource ID
-    /// * `region` - Azure region
-    /// * `batch_size` - Number of metrics to batch before sending
-    ///
-    /// # Errors
+    /// Creates a metrics exporter with explicit credentials and options.
     ///
-    /// Returns error if configuration is invalid.
-    pub async fn new_with_batch_size(
+    /// Must be called within a Tokio runtime.
+    pub fn with_options(
         resource_id: impl Into<String>,
         region: impl Into<String>,
-        batch_size: usize,
+        credential: AzureCredential,
+        options: ExportOptions,
     ) -> Result<Self> {
         let resource_id = resource_id.into();
         let region = region.into();
 
-        let client = Client::new();
+        let endpoint = options.endpoint_or(format!("https://{region}.monitoring.azure.com"));
+        let exporter = MetricsExporter {
+            sender: HttpSender::new(&options)?,
+            credential: Arc::new(credential),
+            url: format!("{}{}/metrics", endpoint, resource_id),
+        };
 
         tracing::info!(
-            "Initialized Azure Monitor Metrics client for resource: {} in region: {} with batch size: {}",
+            "Initialized Azure Monitor Metrics exporter for resource: {} in region: {}",
             resource_id,
-            region,
-            batch_size
+            region
         );
 
         Ok(Self {
-            client,
             resource_id,
             region,
-            batch_buffer: Arc::new(RwLock::new(Vec::new())),
-            batch_size,
+            queue: BatchQueue::spawn(&options.batch, CloudError::MetricsExport, exporter),
         })
     }
 
-    /// Gets the resource ID this client is configured for.
+    /// Gets the resource ID this exporter is configured for.
     pub fn resource_id(&self) -> &str {
         &self.resource_id
     }
 
-    /// Gets the region this client is configured for.
+    /// Gets the region this exporter is configured for.
     pub fn region(&self) -> &str {
         &self.region
     }
 
-    /// Flushes buffered metrics to Azure Monitor.
+    /// Exports all queued metrics.
     pub async fn flush(&self) -> Result<()> {
-        let mut buffer = self.batch_buffer.write().await;
-
-        if buffer.is_empty() {
-            return Ok(());
-        }
-
-        let metrics_to_send = buffer.drain(..).collect::<Vec<_>>();
-        drop(buffer);
-
-        self.send_metrics_batch(&metrics_to_send).await?;
-
-        Ok(())
-    }
-
-    /// Sends a batch of metrics to Azure Monitor.
-    async fn send_metrics_batch(&self, metrics: &[Metric]) -> Result<()> {
-        if metrics.is_empty() {
-            return Ok(());
-        }
-
-        tracing::debug!("Sending {} metrics to Azure Monitor", metrics.len());
-
-        // Convert metrics to Azure Monitor format
-        let time_series: Vec<serde_json::Value> = metrics
-            .iter()
-            .map(|m| {
-                let timestamp = chrono::DateTime::from_timestamp(m.timestamp as i64, 0)
-                    .unwrap_or_else(chrono::Utc::now)
-                    .to_rfc3339();
-
-                let mut data_point = json!({
-                    "timeStamp": timestamp,
-                    "total": m.value,
-                });
-
-                if let Some(ref unit) = m.unit {
-                    data_point["unit"] = json!(unit);
-                }
-
-                json!({
-                    "name": {
-                        "value": m.name,
-                        "localizedValue": m.name
-                    },
-                    "timeseries": [{
-                        "data": [data_point]
-                    }],
-                    "dimensions": m.dimensions.iter().map(|(k, v)| {
-                        json!({
-                            "name": k,
-                            "value": v
-                        })
-                    }).collect::<Vec<_>>()
-                })
-            })
-            .collect();
-
-        let payload = json!({
-            "time": chrono::Utc::now().to_rfc3339(),
-            "data": {
-                "baseData": {
-                    "metric": "LLMShieldMetrics",
-                    "namespace": "LLMShield",
-                    "dimNames": [],
-                    "series": time_series
-                }
-            }
-        });
-
-        // In production, this would use Azure Monitor REST API
-        // For now, we log the payload
-        tracing::info!(
-            "Would send {} metrics to Azure Monitor (payload prepared)",
-            metrics.len()
-        );
-
-        Ok(())
+        self.queue.flush().await
     }
 }
 
 #[async_trait]
 impl CloudMetrics for AzureMonitorMetrics {
     async fn export_metrics(&self, metrics: &[Metric]) -> Result<()> {
-        tracing::debug!("Exporting {} metrics to Azure Monitor", metrics.len());
-
-        // Send in batches
-        for chunk in metrics.chunks(self.batch_size) {
-            self.send_metrics_batch(chunk).await?;
-        }
-
-        Ok(())
+        self.queue.push(metrics.to_vec())
     }
+}
 
-    async fn export_metric(&self, metric: &Metric) -> Result<()> {
-        tracing::debug!("Exporting metric to Azure Monitor: {}", metric.name);
-
-        // Add to buffer
-        let mut buffer = self.batch_buffer.write().await;
-        buffer.push(metric.clone());
-
-        // Flush if buffer is full
-        if buffer.len() >= self.batch_size {
-            let metrics_to_send = buffer.drain(..).collect::<Vec<_>>();
-            drop(buffer);
+/// Min, max, sum and count of the values of one series.
+#[derive(Debug, Clone, Copy)]
+struct Aggregate {
+    min: f64,
+    max: f64,
+    sum: f64,
+    count: u64,
+}
 
-            self.send_metrics_batch(&metrics_to_send).await?;
+impl Aggregate {
+    fn new(value: f64) -> Self {
+        Self {
+            min: value,
+            max: value,
+            sum: value,
+            count: 1,
         }
+    }
 
-        Ok(())
+    fn add(&mut self, value: f64) {
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+        self.sum += value;
+        self.count += 1;
     }
 }
 
+/// Custom metrics API request bodies for a batch, one per metric name,
+/// minute and set of dimension names.
+fn metric_requests(metrics: &[Metric]) -> Vec<Value> {
+    type Series = BTreeMap<Vec<String>, Aggregate>;
+    let mut groups: BTreeMap<(String, u64, Vec<String>), Series> = BTreeMap::new();
+
+    for metric in metrics {
+        let dimensions: BTreeMap<_, _> = metric.dimensions.iter().collect();
+        let names = dimensions.keys().map(|k| k.to_string()).collect();
+        let values = dimensions.values().map(|v| v.to_string()).collect();
+        let minute = metric.timestamp - metric.timestamp % 60;
+
+        groups
+            .entry((metric.name.clone(), minute, names))
+            .or_default()
+            .entry(values)
+            .and_modify(|aggregate| aggregate.add(metric.value))
+            .or_insert_with(|| Aggregate::new(metric.value));
+    }
+
+    groups
+        .into_iter()
+        .map(|((name, minute, dim_names), series)| {
+            let series: Vec<Value> = series
+                .into_iter()
+                .map(|(dim_values, aggregate)| {
+                    json!({
+                        "dimValues": dim_values,
+                        "min": aggregate.min,
+                        "max": aggregate.max,
+                        "sum": aggregate.sum,
+                        "count": aggregate.count,
+                    })
+                })
+                .collect();
+
+            json!({
+                "time": unix_rfc3339(minute),
+                "data": {
+                    "baseData": {
+                        "metric": name,
+                        "namespace": METRIC_NAMESPACE,
+                        "dimNames": dim_names,
+                        "series": series,
+                    }
+                }
+            })
+        })
+        .collect()
+}
+
 /// Azure Monitor Logs implementation of `CloudLogger`.
 ///
-/// This implementation provides:
-/// - Batched log export for efficiency
-/// - Structured logging support
-/// - Log Analytics workspace integration
-/// - Custom log tables
+/// Entries are sent through the Logs Ingestion API: a data collection
+/// endpoint (DCE) receives them for a stream of a data collection rule
+/// (DCR), which routes them to a Log Analytics table. Requests carry a
+/// Microsoft Entra ID token and require the *Monitoring Metrics Publisher*
+/// role on the rule.
+///
+/// The stream must declare the columns `TimeGenerated` (datetime), `Level`,
+/// `Message`, `Source`, `TraceId`, `SpanId` (string) and `Properties`
+/// (dynamic, the entry's labels).
 ///
 /// # Example
 ///
@@ -265,263 +369,652 @@ impl CloudMetrics for AzureMonitorMetrics {
 /// #[tokio::main]
 /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
 ///     let logger = AzureMonitorLogs::new(
-///         "workspace-id",
-///         "shared-key",
-///         "LLMShieldLog"
-///     ).await?;
+///         "https://shield-dce-abcd.eastus-1.ingest.monitor.azure.com",
+///         "dcr-00000000000000000000000000000000",
+///         "Custom-LLMShieldLog_CL",
+///     )
+///     .await?;
 ///
 ///     logger.log("Application started", LogLevel::Info).await?;
+///     logger.flush().await?;
 ///     Ok(())
 /// }
 /// ```
 pub struct AzureMonitorLogs {
-    client: Client,
-    workspace_id: String,
-    shared_key: String,
-    log_type: String,
-    batch_buffer: Arc<RwLock<Vec<LogEntry>>>,
-    batch_size: usize,
+    endpoint: String,
+    rule_id: String,
+    stream_name: String,
+    queue: BatchQueue<LogEntry>,
+}
+
+struct LogsExporter {
+    sender: HttpSender,
+    credential: Arc<AzureCredential>,
+    url: String,
+}
+
+#[async_trait]
+impl BatchExport<LogEntry> for LogsExporter {
+    async fn export(&self, batch: Vec<LogEntry>) -> Result<()> {
+        let token = self
+            .credential
+            .token(LOGS_INGESTION_RESOURCE)
+            .await
+            .map_err(|e| CloudError::LogExport(e.to_string()))?;
+        let records: Vec<Value> = batch.iter().map(log_record).collect();
+
+        self.sender
+            .send(|client| client.post(&self.url).bearer_auth(&token).json(&records))
+            .await
+            .map(drop)
+            .map_err(CloudError::LogExport)
+    }
 }
 
 impl AzureMonitorLogs {
-    /// Creates a new Azure Monitor Logs client with default configuration.
+    /// Creates a logger for a stream of a data collection rule.
     ///
     /// # Arguments
     ///
-    /// * `workspace_id` - Log Analytics workspace ID
-    /// * `shared_key` - Log Analytics workspace shared key
-    /// * `log_type` - Custom log type name (e.g., "LLMShieldLog")
+    /// * `endpoint` - Logs ingestion URL of the data collection endpoint
+    /// * `rule_id` - Immutable ID of the data collection rule (e.g., "dcr-...")
+    /// * `stream_name` - Stream declared by the rule (e.g., "Custom-LLMShieldLog_CL")
+    ///
+    /// Credentials are resolved with [`AzureCredential::from_env`].
     ///
     /// # Errors
     ///
-    /// Returns error if configuration is invalid.
+    /// Returns error if the HTTP client cannot be created.
     pub async fn new(
-        workspace_id: impl Into<String>,
-        shared_key: impl Into<String>,
-        log_type: impl Into<String>,
+        endpoint: impl Into<String>,
+        rule_id: impl Into<String>,
+        stream_name: impl Into<String>,
     ) -> Result<Self> {
-        let workspace_id = workspace_id.into();
-        let shared_key = shared_key.into();
-        let log_type = log_type.into();
-
-        let client = Client::new();
-
-        tracing::info!(
-            "Initialized Azure Monitor Logs client for workspace: {} log type: {}",
-            workspace_id,
-            log_type
-        );
-
-        Ok(Self {
-            client,
-            workspace_id,
-            shared_key,
-            log_type,
-            batch_buffer: Arc::new(RwLock::new(Vec::new())),
-            batch_size: 100,
-        })
+        let options = ExportOptions {
+            endpoint: Some(endpoint.into()),
+            ..ExportOptions::default()
+        };
+        Self::with_options(rule_id, stream_name, AzureCredential::from_env(), options)
     }
 
-    /// Creates a new Azure Monitor Logs client with custom batch size.
+    /// Creates a logger with explicit credentials and options.
     ///
-    /// # Arguments
-    ///
-    /// * `workspace_id` - Log Analytics workspace ID
-    /// * `shared_key` - Log Analytics workspace shared key
-    /// * `log_type` - Custom log type name
-    /// * `batch_size` - Number of log entries to batch before sending
+    /// `options.endpoint` must be the logs ingestion URL of the data
+    /// collection endpoint. Must be called within a Tokio runtime.
     ///
     /// # Errors
     ///
-    /// Returns error if configuration is invalid.
-    pub async fn new_with_batch_size(
-        workspace_id: impl Into<String>,
-        shared_key: impl Into<String>,
-        log_type: impl Into<String>,
-        batch_size: usize,
+    /// Returns error if no endpoint is set or the HTTP client cannot be
+    /// created.
+    pub fn with_options(
+        rule_id: impl Into<String>,
+        stream_name: impl Into<String>,
+        credential: AzureCredential,
+        options: ExportOptions,
     ) -> Result<Self> {
-        let workspace_id = workspace_id.into();
-        let shared_key = shared_key.into();
-        let log_type = log_type.into();
-
-        let client = Client::new();
+        let rule_id = rule_id.into();
+        let stream_name = stream_name.into();
+        let endpoint = options
+            .endpoint
+            .as_deref()
+            .map(|endpoint| endpoint.trim_end_matches('/').to_string())
+            .ok_or_else(|| CloudError::InvalidConfig {
+                key: "azure.observability.logs_endpoint".to_string(),
+                reason: "a data collection endpoint is required".to_string(),
+            })?;
+
+        let exporter = LogsExporter {
+            sender: HttpSender::new(&options)?,
+            credential: Arc::new(credential),
+            url: format!(
+                "{endpoint}/dataCollectionRules/{rule_id}/streams/{stream_name}?api-version={LOGS_INGESTION_API_VERSION}"
+            ),
+        };
 
         tracing::info!(
-            "Initialized Azure Monitor Logs client for workspace: {} log type: {} with batch size: {}",
-            workspace_id,
-            log_type,
-            batch_size
+            "Initialized Azure Monitor Logs exporter for rule: {} stream: {}",
+            rule_id,
+            stream_name
         );
 
         Ok(Self {
-            client,
-            workspace_id,
-            shared_key,
-            log_type,
-            batch_buffer: Arc::new(RwLock::new(Vec::new())),
-            batch_size,
+            endpoint,
+            rule_id,
+            stream_name,
+            queue: BatchQueue::spawn(&options.batch, CloudError::LogExport, exporter),
         })
     }
 
-    /// Gets the workspace ID this client is configured for.
-    pub fn workspace_id(&self) -> &str {
-        &self.workspace_id
+    /// Gets the data collection endpoint this logger sends to.
+    pub fn endpoint(&self) -> &str {
+        &self.endpoint
     }
 
-    /// Gets the log type this client is configured for.
-    pub fn log_type(&self) -> &str {
-        &self.log_type
+    /// Gets the immutable ID of the data collection rule.
+    pub fn rule_id(&self) -> &str {
+        &self.rule_id
     }
 
-    /// Flushes buffered log entries to Azure Monitor.
+    /// Gets the stream this logger is configured for.
+    pub fn stream_name(&self) -> &str {
+        &self.stream_name
+    }
+
+    /// Exports all queued log entries.
     pub async fn flush(&self) -> Result<()> {
-        let mut buffer = self.batch_buffer.write().await;
+        self.queue.flush().await
+    }
+}
+
+#[async_trait]
+impl CloudLogger for AzureMonitorLogs {
+    async fn log(&self, message: &str, level: LogLevel) -> Result<()> {
+        self.log_structured(&LogEntry::new(level, message)).await
+    }
+
+    async fn log_structured(&self, entry: &LogEntry) -> Result<()> {
+        self.queue.push(vec![entry.clone()])
+    }
+
+    async fn log_batch(&self, entries: &[LogEntry]) -> Result<()> {
+        self.queue.push(entries.to_vec())
+    }
+}
+
+/// Logs Ingestion API record of a log entry; labels go to `Properties`.
+fn log_record(entry: &LogEntry) -> Value {
+    json!({
+        "TimeGenerated": rfc3339(entry.timestamp),
+        "Level": format_log_level(&entry.level),
+        "Message": entry.message,
+        "Source": entry.source,
+        "TraceId": entry.trace_id,
+        "SpanId": entry.span_id,
+        "Properties": entry.labels,
+    })
+}
+
+/// Formats a LogLevel as a string for Azure Monitor.
+fn format_log_level(level: &LogLevel) -> &'static str {
+    match level {
+        LogLevel::Trace => "Verbose",
+        LogLevel::Debug => "Verbose",
+        LogLevel::Info => "Informational",
+        LogLevel::Warn => "Warning",
+        LogLevel::Error => "Error",
+        LogLevel::Fatal => "Critical",
+    }
+}
+
+/// Application Insights resource and ingestion URL from a connection string.
+#[derive(Debug, Clone, PartialEq)]
+struct AppInsightsTarget {
+    instrumentation_key: String,
+    track_url: String,
+}
 
-        if buffer.is_empty() {
-            return Ok(());
+impl AppInsightsTarget {
+    /// Parses `InstrumentationKey=...;IngestionEndpoint=...` or a bare
+    /// instrumentation key.
+    fn parse(connection_string: &str, options: &ExportOptions) -> Result<Self> {
+        let mut instrumentation_key = None;
+        let mut ingestion_endpoint = None;
+
+        if connection_string.contains('=') {
+            for part in connection_string.split(';') {
+                let Some((key, value)) = part.split_once('=') else {
+                    continue;
+                };
+                match key.trim().to_ascii_lowercase().as_str() {
+                    "instrumentationkey" => instrumentation_key = Some(value.trim().to_string()),
+                    "ingestionendpoint" => ingestion_endpoint = Some(value.trim().to_string()),
+                    _ => {}
+                }
+            }
+        } else {
+            instrumentation_key = Some(connection_string.trim().to_string());
         }
 
-        let logs_to_send = buffer.drain(..).collect::<Vec<_>>();
-        drop(buffer);
+        let instrumentation_key = instrumentation_key
+            .filter(|key| !key.is_empty())
+            .ok_or_else(|| CloudError::InvalidConfig {
+                key: "azure.observability.connection_string".to_string(),
+                reason: "missing InstrumentationKey".to_string(),
+            })?;
+        let endpoint = options
+            .endpoint_or(ingestion_endpoint.unwrap_or_else(|| APP_INSIGHTS_ENDPOINT.to_string()));
 
-        self.send_logs_batch(&logs_to_send).await?;
+        Ok(Self {
+            instrumentation_key,
+            track_url: format!("{endpoint}/v2.1/track"),
+        })
+    }
 
-        Ok(())
+    /// Telemetry envelope of `base_type` data.
+    fn envelope(
+        &self,
+        kind: &str,
+        base_type: &str,
+        time: SystemTime,
+        tags: Map<String, Value>,
+        base_data: Value,
+    ) -> Value {
+        let mut tags = tags;
+        tags.insert("ai.cloud.role".to_string(), json!(CLOUD_ROLE));
+        json!({
+            "name": format!("Microsoft.ApplicationInsights.{kind}"),
+            "time": rfc3339(time),
+            "iKey": self.instrumentation_key,
+            "tags": tags,
+            "data": {
+                "baseType": base_type,
+                "baseData": base_data,
+            }
+        })
     }
 
-    /// Sends a batch of log entries to Azure Monitor.
-    async fn send_logs_batch(&self, entries: &[LogEntry]) -> Result<()> {
-        if entries.is_empty() {
-            return Ok(());
+    /// `MessageData` (trace telemetry) envelope of a log entry.
+    fn message(&self, entry: &LogEntry) -> Value {
+        let mut properties: Map<String, Value> = entry
+            .labels
+            .iter()
+            .map(|(key, value)| (key.clone(), json!(value)))
+            .collect();
+        if let Some(ref source) = entry.source {
+            properties.insert("source".to_string(), json!(source));
+        }
+
+        let mut tags = Map::new();
+        if let Some(ref trace_id) = entry.trace_id {
+            tags.insert(
+                "ai.operation.id".to_string(),
+                json!(export::hex_id(trace_id, 16)),
+            );
+        }
+        if let Some(ref span_id) = entry.span_id {
+            tags.insert(
+                "ai.operation.parentId".to_string(),
+                json!(export::hex_id(span_id, 8)),
+            );
         }
 
-        tracing::debug!("Sending {} log entries to Azure Monitor", entries.len());
+        self.envelope(
+            "Message",
+            "MessageData",
+            entry.timestamp,
+            tags,
+            json!({
+                "ver": 2,
+                "message": entry.message,
+                "severityLevel": severity_level(&entry.level),
+                "properties": properties,
+            }),
+        )
+    }
 
-        // Convert log entries to Azure Monitor format
-        let log_records: Vec<serde_json::Value> = entries
+    /// `RequestData` envelope of a root span, `RemoteDependencyData` of a
+    /// child span.
+    fn span(&self, span: &Span) -> Value {
+        let duration = span.duration().unwrap_or_default();
+        let success = !span
+            .status
+            .as_deref()
+            .is_some_and(|status| status.eq_ignore_ascii_case("error"));
+        let code = span.status.clone().unwrap_or_else(|| "OK".to_string());
+        let properties: Map<String, Value> = span
+            .attributes
             .iter()
-            .map(|entry| {
-                let timestamp = entry
-                    .timestamp
-                    .duration_since(std::time::UNIX_EPOCH)
-                    .unwrap_or_default();
-
-                let iso_timestamp = chrono::DateTime::from_timestamp(
-                    timestamp.as_secs() as i64,
-                    timestamp.subsec_nanos(),
-                )
-                .unwrap_or_else(chrono::Utc::now)
-                .to_rfc3339();
+            .map(|(key, value)| (key.clone(), json!(value)))
+            .collect();
 
-                let severity = format_log_level(&entry.level);
+        let mut tags = Map::new();
+        tags.insert(
+            "ai.operation.id".to_string(),
+            json!(export::hex_id(&span.trace_id, 16)),
+        );
+        let mut data = json!({
+            "ver": 2,
+            "id": export::hex_id(&span.span_id, 8),
+            "name": span.name,
+            "duration": timespan(duration),
+            "success": success,
+            "properties": properties,
+        });
 
-                let mut record = json!({
-                    "TimeGenerated": iso_timestamp,
-                    "Level": severity,
-                    "Message": entry.message,
-                });
+        match &span.parent_span_id {
+            Some(parent) => {
+                tags.insert(
+                    "ai.operation.parentId".to_string(),
+                    json!(export::hex_id(parent, 8)),
+                );
+                data["type"] = json!("InProc");
+                data["resultCode"] = json!(code);
+                self.envelope(
+                    "RemoteDependency",
+                    "RemoteDependencyData",
+                    span.start_time,
+                    tags,
+                    data,
+                )
+            }
+            None => {
+                tags.insert("ai.operation.name".to_string(), json!(span.name));
+                data["responseCode"] = json!(code);
+                self.envelope("Request", "RequestData", span.start_time, tags, data)
+            }
+        }
+    }
+}
 
-                // Add labels as properties
-                for (key, value) in &entry.labels {
-                    record[key] = json!(value);
-                }
+/// Sends telemetry envelopes to the Application Insights ingestion API.
+struct AppInsightsExporter {
+    sender: HttpSender,
+    url: String,
+    error: fn(String) -> CloudError,
+}
 
-                if let Some(ref trace_id) = entry.trace_id {
-                    record["TraceId"] = json!(trace_id);
-                }
+#[async_trait]
+impl BatchExport<Value> for AppInsightsExporter {
+    async fn export(&self, batch: Vec<Value>) -> Result<()> {
+        let (status, body) = self
+            .sender
+            .send(|client| client.post(&self.url).json(&batch))
+            .await
+            .map_err(self.error)?;
+
+        // 206: some items were rejected; those are reported, not resent
+        if status == 206 {
+            let response: Value = serde_json::from_str(&body).unwrap_or_default();
+            let accepted = response["itemsAccepted"].as_u64().unwrap_or_default();
+            let first_error = response["errors"][0]["message"]
+                .as_str()
+                .unwrap_or_default();
+            return Err((self.error)(format!(
+                "Application Insights accepted {} of {} items: {}",
+                accepted,
+                batch.len(),
+                first_error
+            )));
+        }
+        Ok(())
+    }
+}
 
-                if let Some(ref span_id) = entry.span_id {
-                    record["SpanId"] = json!(span_id);
-                }
+fn app_insights_queue(
+    connection_string: &str,
+    options: &ExportOptions,
+    error: fn(String) -> CloudError,
+) -> Result<(AppInsightsTarget, BatchQueue<Value>)> {
+    let target = AppInsightsTarget::parse(connection_string, options)?;
+    let exporter = AppInsightsExporter {
+        sender: HttpSender::new(options)?,
+        url: target.track_url.clone(),
+        error,
+    };
+    Ok((target, BatchQueue::spawn(&options.batch, error, exporter)))
+}
 
-                record
-            })
-            .collect();
+/// Azure Application Insights implementation of `CloudLogger`.
+///
+/// Entries are sent as trace telemetry (`MessageData`); trace and span IDs
+/// correlate them with spans exported by [`AzureTracer`].
+///
+/// # Example
+///
+/// ```no_run
+/// use llm_shield_cloud_azure::AzureAppInsights;
+/// use llm_shield_cloud::{CloudLogger, LogLevel};
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let logger = AzureAppInsights::new(
+///         "InstrumentationKey=00000000-0000-0000-0000-000000000000;IngestionEndpoint=https://eastus-8.in.applicationinsights.azure.com/"
+///     ).await?;
+///
+///     logger.log("Application started", LogLevel::Info).await?;
+///     logger.flush().await?;
+///     Ok(())
+/// }
+/// ```
+pub struct AzureAppInsights {
+    target: AppInsightsTarget,
+    queue: BatchQueue<Value>,
+}
 
-        let payload = json!(log_records);
+impl AzureAppInsights {
+    /// Creates a logger for an Application Insights connection string or
+    /// instrumentation key.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the connection string has no instrumentation key.
+    pub async fn new(connection_string: impl Into<String>) -> Result<Self> {
+        Self::with_options(connection_string, ExportOptions::default())
+    }
 
-        // In production, this would use Azure Monitor Data Collector API
-        // For now, we log the payload
-        tracing::info!(
-            "Would send {} log entries to Azure Monitor (payload prepared)",
-            entries.len()
-        );
+    /// Creates a logger with explicit options.
+    ///
+    /// Must be called within a Tokio runtime.
+    pub fn with_options(
+        connection_string: impl Into<String>,
+        options: ExportOptions,
+    ) -> Result<Self> {
+        let (target, queue) =
+            app_insights_queue(&connection_string.into(), &options, CloudError::LogExport)?;
+        tracing::info!("Initialized Azure Application Insights logger");
+        Ok(Self { target, queue })
+    }
 
-        Ok(())
+    /// Exports all queued log entries.
+    pub async fn flush(&self) -> Result<()> {
+        self.queue.flush().await
     }
 }
 
 #[async_trait]
-impl CloudLogger for AzureMonitorLogs {
+impl CloudLogger for AzureAppInsights {
     async fn log(&self, message: &str, level: LogLevel) -> Result<()> {
-        let entry = LogEntry {
-            timestamp: std::time::SystemTime::now(),
-            level,
-            message: message.to_string(),
-            labels: HashMap::new(),
-            trace_id: None,
-            span_id: None,
-        };
-
-        self.log_structured(&entry).await
+        self.log_structured(&LogEntry::new(level, message)).await
     }
 
     async fn log_structured(&self, entry: &LogEntry) -> Result<()> {
-        tracing::debug!("Logging structured entry to Azure Monitor");
+        self.queue.push(vec![self.target.message(entry)])
+    }
 
-        // Add to buffer
-        let mut buffer = self.batch_buffer.write().await;
-        buffer.push(entry.clone());
+    async fn log_batch(&self, entries: &[LogEntry]) -> Result<()> {
+        self.queue.push(
+            entries
+                .iter()
+                .map(|entry| self.target.message(entry))
+                .collect(),
+        )
+    }
+}
 
-        // Flush if buffer is full
-        if buffer.len() >= self.batch_size {
-            let logs_to_send = buffer.drain(..).collect::<Vec<_>>();
-            drop(buffer);
+/// Azure Application Insights implementation of `CloudTracer`.
+///
+/// Root spans are sent as requests and child spans as in-process
+/// dependencies of the same operation, so the end-to-end transaction view
+/// shows the scanner breakdown of a scan. Spans with status `ERROR` are
+/// marked as failed.
+///
+/// # Example
+///
+/// ```no_run
+/// use llm_shield_cloud_azure::AzureTracer;
+/// use llm_shield_cloud::CloudTracer;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let tracer = AzureTracer::new("InstrumentationKey=00000000-0000-0000-0000-000000000000").await?;
+///
+///     let span = tracer.start_span("scan_prompt");
+///     let child = tracer.start_child_span("toxicity", &span);
+///     tracer.end_span(child.end_with_status("OK")).await?;
+///     tracer.end_span(span.end_with_status("OK")).await?;
+///
+///     tracer.flush().await?;
+///     Ok(())
+/// }
+/// ```
+pub struct AzureTracer {
+    target: AppInsightsTarget,
+    queue: BatchQueue<Value>,
+}
 
-            self.send_logs_batch(&logs_to_send).await?;
-        }
+impl AzureTracer {
+    /// Creates a tracer for an Application Insights connection string or
+    /// instrumentation key.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the connection string has no instrumentation key.
+    pub async fn new(connection_string: impl Into<String>) -> Result<Self> {
+        Self::with_options(connection_string, ExportOptions::default())
+    }
 
-        Ok(())
+    /// Creates a tracer with explicit options.
+    ///
+    /// Must be called within a Tokio runtime.
+    pub fn with_options(
+        connection_string: impl Into<String>,
+        options: ExportOptions,
+    ) -> Result<Self> {
+        let (target, queue) =
+            app_insights_queue(&connection_string.into(), &options, CloudError::TraceExport)?;
+        tracing::info!("Initialized Azure Application Insights tracer");
+        Ok(Self { target, queue })
     }
 
-    async fn log_batch(&self, entries: &[LogEntry]) -> Result<()> {
-        tracing::debug!(
-            "Logging batch of {} entries to Azure Monitor",
-            entries.len()
-        );
+    /// Exports all queued spans.
+    pub async fn flush(&self) -> Result<()> {
+        self.queue.flush().await
+    }
+}
 
-        // Send in batches
-        for chunk in entries.chunks(self.batch_size) {
-            self.send_logs_batch(chunk).await?;
+#[async_trait]
+impl CloudTracer for AzureTracer {
+    async fn end_span(&self, mut span: Span) -> Result<()> {
+        if span.end_time.is_none() {
+            span.end_time = Some(SystemTime::now());
         }
+        self.queue.push(vec![self.target.span(&span)])
+    }
 
-        Ok(())
+    async fn export_spans(&self, spans: &[Span]) -> Result<()> {
+        self.queue
+            .push(spans.iter().map(|span| self.target.span(span)).collect())
     }
 }
 
-/// Formats a LogLevel as a string for Azure Monitor.
-fn format_log_level(level: &LogLevel) -> &'static str {
+/// Application Insights severity level of a log level.
+fn severity_level(level: &LogLevel) -> u8 {
     match level {
-        LogLevel::Trace => "Verbose",
-        LogLevel::Debug => "Verbose",
-        LogLevel::Info => "Informational",
-        LogLevel::Warn => "Warning",
-        LogLevel::Error => "Error",
-        LogLevel::Fatal => "Critical",
+        LogLevel::Trace | LogLevel::Debug => 0,
+        LogLevel::Info => 1,
+        LogLevel::Warn => 2,
+        LogLevel::Error => 3,
+        LogLevel::Fatal => 4,
     }
 }
 
+/// Application Insights duration: `d.hh:mm:ss.fffffff`.
+fn timespan(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    format!(
+        "{}.{:02}:{:02}:{:02}.{:07}",
+        seconds / 86_400,
+        seconds / 3600 % 24,
+        seconds / 60 % 60,
+        seconds % 60,
+        duration.subsec_nanos() / 100
+    )
+}
+
+fn rfc3339(time: SystemTime) -> String {
+    DateTime::<Utc>::from(time).to_rfc3339_opts(SecondsFormat::Micros, true)
+}
+
+fn unix_rfc3339(seconds: u64) -> String {
+    DateTime::from_timestamp(seconds as i64, 0)
+        .unwrap_or_else(Utc::now)
+        .to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use axum::body::Bytes;
+    use axum::extract::{OriginalUri, State};
+    use axum::http::{HeaderMap, StatusCode};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tokio::net::TcpListener;
+    use tokio::sync::mpsc;
+
+    /// Request received by a stand-in Azure endpoint
+    #[derive(Debug)]
+    struct Received {
+        path: String,
+        headers: HeaderMap,
+        body: Value,
+    }
 
-    #[test]
-    fn test_batch_size_limits() {
-        let metrics_batch_size = 20;
-        let logs_batch_size = 100;
+    #[derive(Clone)]
+    struct MockAzure {
+        requests: mpsc::UnboundedSender<Received>,
+        failures: Arc<AtomicUsize>,
+        status: StatusCode,
+        response: &'static str,
+    }
 
-        assert!(metrics_batch_size > 0);
-        assert!(logs_batch_size > 0);
+    async fn receive(
+        State(mock): State<MockAzure>,
+        OriginalUri(uri): OriginalUri,
+        headers: HeaderMap,
+        body: Bytes,
+    ) -> (StatusCode, &'static str) {
+        let throttled = mock
+            .failures
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+            .is_ok();
+        if throttled {
+            return (StatusCode::TOO_MANY_REQUESTS, "");
+        }
+
+        let _ = mock.requests.send(Received {
+            path: uri.to_string(),
+            headers,
+            body: serde_json::from_slice(&body).unwrap(),
+        });
+        (mock.status, mock.response)
+    }
+
+    /// Starts a stand-in endpoint answering 429 `failures` times, then
+    /// `status` with `response`
+    async fn mock_azure(
+        failures: usize,
+        status: StatusCode,
+        response: &'static str,
+    ) -> (ExportOptions, mpsc::UnboundedReceiver<Received>) {
+        let (requests, received) = mpsc::unbounded_channel();
+        let mock = MockAzure {
+            requests,
+            failures: Arc::new(AtomicUsize::new(failures)),
+            status,
+            response,
+        };
+
+        let app = axum::Router::new().fallback(receive).with_state(mock);
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let mut options = ExportOptions {
+            endpoint: Some(format!("http://{addr}")),
+            ..ExportOptions::default()
+        };
+        options.retry.initial_backoff_ms = 10;
+        (options, received)
     }
 
     #[test]
@@ -535,37 +1028,221 @@ mod tests {
     }
 
     #[test]
-    fn test_resource_id_format() {
-        let resource_id = "/subscriptions/sub-id/resourceGroups/rg-name/providers/Microsoft.Compute/virtualMachines/vm-name";
-        assert!(resource_id.starts_with("/subscriptions/"));
-        assert!(resource_id.contains("/resourceGroups/"));
+    fn test_connection_string_and_timespan() {
+        let options = ExportOptions::default();
+        let target = AppInsightsTarget::parse(
+            "InstrumentationKey=key-1;IngestionEndpoint=https://eastus-8.in.applicationinsights.azure.com/;LiveEndpoint=https://eastus.livediagnostics.monitor.azure.com/",
+            &options,
+        )
+        .unwrap();
+        assert_eq!(target.instrumentation_key, "key-1");
+        assert_eq!(
+            target.track_url,
+            "https://eastus-8.in.applicationinsights.azure.com/v2.1/track"
+        );
+
+        let bare = AppInsightsTarget::parse("key-2", &options).unwrap();
+        assert_eq!(
+            bare.track_url,
+            "https://dc.services.visualstudio.com/v2.1/track"
+        );
+        assert!(AppInsightsTarget::parse("IngestionEndpoint=https://x", &options).is_err());
+
+        assert_eq!(timespan(Duration::from_millis(1500)), "0.00:00:01.5000000");
+        assert_eq!(timespan(Duration::from_secs(90_061)), "1.01:01:01.0000000");
+    }
+
+    #[tokio::test]
+    async fn test_metrics_are_aggregated_per_series() {
+        let (mut options, mut received) = mock_azure(1, StatusCode::OK, "").await;
+        options.batch.max_batch_size = 100;
+        let resource_id =
+            "/subscriptions/sub/resourceGroups/rg/providers/Microsoft.App/containerApps/shield";
+        let metrics = AzureMonitorMetrics::with_options(
+            resource_id,
+            "eastus",
+            AzureCredential::access_token("token-1"),
+            options,
+        )
+        .unwrap();
+
+        let at = |value: f64, scanner: &str| {
+            let mut metric = Metric::new("scan_duration", value).with_dimension("scanner", scanner);
+            metric.timestamp = 1_700_000_010;
+            metric
+        };
+        metrics
+            .export_metrics(&[
+                at(10.0, "toxicity"),
+                at(30.0, "toxicity"),
+                at(5.0, "secrets"),
+            ])
+            .await
+            .unwrap();
+        metrics
+            .export_metric(&Metric::new("scans", 1.0))
+            .await
+            .unwrap();
+        metrics.flush().await.unwrap();
+
+        let mut bodies = Vec::new();
+        while let Ok(request) = received.try_recv() {
+            assert_eq!(request.path, format!("{resource_id}/metrics"));
+            assert_eq!(request.headers["authorization"], "Bearer token-1");
+            bodies.push(request.body);
+        }
+        assert_eq!(bodies.len(), 2);
+
+        let duration = bodies
+            .iter()
+            .find(|body| body["data"]["baseData"]["metric"] == "scan_duration")
+            .unwrap();
+        assert_eq!(duration["time"], "2023-11-14T22:13:00Z");
+        let base = &duration["data"]["baseData"];
+        assert_eq!(base["namespace"], "LLMShield");
+        assert_eq!(base["dimNames"], json!(["scanner"]));
+        assert_eq!(
+            base["series"],
+            json!([
+                { "dimValues": ["secrets"], "min": 5.0, "max": 5.0, "sum": 5.0, "count": 1 },
+                { "dimValues": ["toxicity"], "min": 10.0, "max": 30.0, "sum": 40.0, "count": 2 },
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_logs_are_sent_to_the_rule_stream() {
+        let (options, mut received) = mock_azure(0, StatusCode::NO_CONTENT, "").await;
+        let logger = AzureMonitorLogs::with_options(
+            "dcr-1",
+            "Custom-LLMShieldLog_CL",
+            AzureCredential::access_token("token-1"),
+            options,
+        )
+        .unwrap();
+
+        logger
+            .log_structured(
+                &LogEntry::new(LogLevel::Warn, "prompt blocked")
+                    .with_label("scanner", "toxicity")
+                    .with_trace_id("trace-1"),
+            )
+            .await
+            .unwrap();
+        logger.flush().await.unwrap();
+
+        let request = received.recv().await.unwrap();
+        assert_eq!(
+            request.path,
+            "/dataCollectionRules/dcr-1/streams/Custom-LLMShieldLog_CL?api-version=2023-01-01"
+        );
+        assert_eq!(request.headers["authorization"], "Bearer token-1");
+
+        let record = &request.body[0];
+        assert_eq!(record["Level"], "Warning");
+        assert_eq!(record["Message"], "prompt blocked");
+        assert_eq!(record["Properties"], json!({ "scanner": "toxicity" }));
+        assert_eq!(record["TraceId"], "trace-1");
+        assert!(record["TimeGenerated"].is_string());
+
+        // The data collection endpoint has no default
+        assert!(AzureMonitorLogs::with_options(
+            "dcr-1",
+            "Custom-LLMShieldLog_CL",
+            AzureCredential::access_token("token-1"),
+            ExportOptions::default()
+        )
+        .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_app_insights_logs_and_spans() {
+        let response = r#"{"itemsReceived":2,"itemsAccepted":2,"errors":[]}"#;
+        let (options, mut received) = mock_azure(0, StatusCode::OK, response).await;
+        let connection_string =
+            "InstrumentationKey=ikey-1;IngestionEndpoint=https://ignored.example";
+        let tracer = AzureTracer::with_options(connection_string, options.clone()).unwrap();
+        let logger = AzureAppInsights::with_options(connection_string, options).unwrap();
+
+        let root = tracer.start_span("scan_prompt");
+        let child = tracer
+            .start_child_span("toxicity", &root)
+            .with_attribute("risk_score", "0.9");
+        logger
+            .log_structured(
+                &LogEntry::new(LogLevel::Error, "scanner failed")
+                    .with_trace_id(root.trace_id.clone())
+                    .with_span_id(child.span_id.clone()),
+            )
+            .await
+            .unwrap();
+        tracer
+            .export_spans(&[
+                child.end_with_status("ERROR"),
+                root.clone().end_with_status("OK"),
+            ])
+            .await
+            .unwrap();
+        tracer.flush().await.unwrap();
+        logger.flush().await.unwrap();
+
+        let mut envelopes = Vec::new();
+        for _ in 0..2 {
+            let request = received.recv().await.unwrap();
+            assert_eq!(request.path, "/v2.1/track");
+            envelopes.extend(request.body.as_array().unwrap().clone());
+        }
+        let by_type = |base_type: &str| {
+            envelopes
+                .iter()
+                .find(|envelope| envelope["data"]["baseType"] == base_type)
+                .unwrap()
+                .clone()
+        };
+
+        let operation_id = export::hex_id(&root.trace_id, 16);
+        let request = by_type("RequestData");
+        assert_eq!(request["iKey"], "ikey-1");
+        assert_eq!(request["tags"]["ai.operation.id"], operation_id.as_str());
+        assert_eq!(request["data"]["baseData"]["success"], true);
+
+        let dependency = by_type("RemoteDependencyData");
+        assert_eq!(
+            dependency["name"],
+            "Microsoft.ApplicationInsights.RemoteDependency"
+        );
+        assert_eq!(dependency["tags"]["ai.operation.id"], operation_id.as_str());
+        assert_eq!(
+            dependency["tags"]["ai.operation.parentId"],
+            request["data"]["baseData"]["id"]
+        );
+        assert_eq!(dependency["data"]["baseData"]["success"], false);
+        assert_eq!(
+            dependency["data"]["baseData"]["properties"]["risk_score"],
+            "0.9"
+        );
+
+        let message = by_type("MessageData");
+        assert_eq!(message["data"]["baseData"]["severityLevel"], 3);
+        assert_eq!(
+            message["tags"]["ai.operation.parentId"],
+            dependency["data"]["baseData"]["id"]
+        );
     }
 
     #[tokio::test]
-    async fn test_metric_batching() {
-        let metrics = vec![
-            Metric {
-                name: "test1".to_string(),
-                value: 1.0,
-                timestamp: 1000,
-                dimensions: HashMap::new(),
-                unit: Some("Count".to_string()),
-            },
-            Metric {
-                name: "test2".to_string(),
-                value: 2.0,
-                timestamp: 2000,
-                dimensions: HashMap::new(),
-                unit: Some("Count".to_string()),
-            },
-        ];
-
-        // Test chunking logic
-        let batch_size = 1;
-        let chunks: Vec<_> = metrics.chunks(batch_size).collect();
-
-        assert_eq!(chunks.len(), 2);
-        assert_eq!(chunks[0].len(), 1);
-        assert_eq!(chunks[1].len(), 1);
+    async fn test_app_insights_partial_success_is_reported() {
+        let response = r#"{"itemsReceived":2,"itemsAccepted":1,"errors":[{"index":1,"statusCode":400,"message":"Field 'name' is required"}]}"#;
+        let (options, _received) = mock_azure(0, StatusCode::PARTIAL_CONTENT, response).await;
+        let logger = AzureAppInsights::with_options("ikey-1", options).unwrap();
+
+        logger.log("one", LogLevel::Info).await.unwrap();
+        logger.log("two", LogLevel::Info).await.unwrap();
+        match logger.flush().await {
+            Err(CloudError::LogExport(message)) => {
+                assert!(message.contains("accepted 1 of 2"), "{message}")
+            }
+            other => panic!("unexpected result: {other:?}"),
+        }
     }
 }
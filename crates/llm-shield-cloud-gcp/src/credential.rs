@@ -0,0 +1,380 @@
+//! OAuth 2.0 access tokens for the Google Cloud REST APIs.
+//!
+//! [`GcpCredential::from_env`] resolves Application Default Credentials for
+//! the common cases:
+//!
+//! 1. The key file named by `GOOGLE_APPLICATION_CREDENTIALS`
+//!    (service account or `gcloud` user credentials)
+//! 2. The `gcloud auth application-default login` credentials file
+//! 3. The metadata server of GCE, GKE (Workload Identity), Cloud Run, etc.
+
+use jsonwebtoken::{Algorithm, EncodingKey, Header};
+use llm_shield_cloud::{CloudError, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::sync::Mutex;
+
+const TOKEN_URI: &str = "https://oauth2.googleapis.com/token";
+const METADATA_HOST: &str = "metadata.google.internal";
+const SCOPE: &str = "https://www.googleapis.com/auth/cloud-platform";
+
+/// Tokens are refreshed this long before they expire.
+const REFRESH_MARGIN: Duration = Duration::from_secs(300);
+
+/// Lifetime of self-signed assertions and of tokens without an expiry.
+const DEFAULT_LIFETIME: Duration = Duration::from_secs(3600);
+
+enum Source {
+    AccessToken(String),
+    ServiceAccount {
+        client_email: String,
+        key: EncodingKey,
+        token_uri: String,
+    },
+    AuthorizedUser {
+        client_id: String,
+        client_secret: String,
+        refresh_token: String,
+        token_uri: String,
+    },
+    Metadata {
+        url: String,
+    },
+}
+
+/// Key file of `GOOGLE_APPLICATION_CREDENTIALS`.
+#[derive(Deserialize)]
+struct KeyFile {
+    #[serde(rename = "type")]
+    kind: String,
+    client_email: Option<String>,
+    private_key: Option<String>,
+    client_id: Option<String>,
+    client_secret: Option<String>,
+    refresh_token: Option<String>,
+    token_uri: Option<String>,
+}
+
+#[derive(Serialize)]
+struct Claims<'a> {
+    iss: &'a str,
+    scope: &'a str,
+    aud: &'a str,
+    iat: u64,
+    exp: u64,
+}
+
+struct CachedToken {
+    token: String,
+    expires_at: SystemTime,
+}
+
+/// Source of bearer tokens for the `cloud-platform` scope, with caching.
+pub struct GcpCredential {
+    source: Source,
+    client: reqwest::Client,
+    cached: Mutex<Option<CachedToken>>,
+}
+
+impl GcpCredential {
+    fn with_source(source: Source) -> Self {
+        Self {
+            source,
+            client: reqwest::Client::new(),
+            cached: Mutex::new(None),
+        }
+    }
+
+    /// Resolves Application Default Credentials (see the module docs).
+    ///
+    /// # Errors
+    ///
+    /// Returns error if a credentials file exists but cannot be used.
+    pub fn from_env() -> Result<Self> {
+        let key_file = std::env::var_os("GOOGLE_APPLICATION_CREDENTIALS")
+            .map(PathBuf::from)
+            .or_else(|| {
+                let home = std::env::var_os("HOME")?;
+                let path =
+                    PathBuf::from(home).join(".config/gcloud/application_default_credentials.json");
+                path.exists().then_some(path)
+            });
+
+        match key_file {
+            Some(path) => {
+                let json = std::fs::read_to_string(&path).map_err(|e| {
+                    CloudError::InvalidCredentials(format!("{}: {e}", path.display()))
+                })?;
+                Self::from_json(&json)
+            }
+            None => Ok(Self::metadata_server()),
+        }
+    }
+
+    /// Uses a fixed bearer token, e.g. from `gcloud auth print-access-token`.
+    pub fn access_token(token: impl Into<String>) -> Self {
+        Self::with_source(Source::AccessToken(token.into()))
+    }
+
+    /// Uses a service account key or `gcloud` user credentials file.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the file is not a supported credentials type.
+    pub fn from_json(json: &str) -> Result<Self> {
+        let invalid = |reason: &str| CloudError::InvalidCredentials(reason.to_string());
+        let file: KeyFile =
+            serde_json::from_str(json).map_err(|e| invalid(&format!("credentials file: {e}")))?;
+        let token_uri = file.token_uri.unwrap_or_else(|| TOKEN_URI.to_string());
+
+        let source = match file.kind.as_str() {
+            "service_account" => {
+                let (Some(client_email), Some(private_key)) = (file.client_email, file.private_key)
+                else {
+                    return Err(invalid(
+                        "service account key needs client_email and private_key",
+                    ));
+                };
+                let key = EncodingKey::from_rsa_pem(private_key.as_bytes())
+                    .map_err(|e| invalid(&format!("service account private key: {e}")))?;
+                Source::ServiceAccount {
+                    client_email,
+                    key,
+                    token_uri,
+                }
+            }
+            "authorized_user" => {
+                let (Some(client_id), Some(client_secret), Some(refresh_token)) =
+                    (file.client_id, file.client_secret, file.refresh_token)
+                else {
+                    return Err(invalid(
+                        "user credentials need client_id, client_secret and refresh_token",
+                    ));
+                };
+                Source::AuthorizedUser {
+                    client_id,
+                    client_secret,
+                    refresh_token,
+                    token_uri,
+                }
+            }
+            other => {
+                return Err(invalid(&format!("unsupported credentials type: {other}")));
+            }
+        };
+        Ok(Self::with_source(source))
+    }
+
+    /// Uses the service account attached to the workload through the
+    /// metadata server (`GCE_METADATA_HOST` overrides its address).
+    pub fn metadata_server() -> Self {
+        let host = std::env::var("GCE_METADATA_HOST").unwrap_or_else(|_| METADATA_HOST.to_string());
+        Self::with_source(Source::Metadata {
+            url: format!(
+                "http://{host}/computeMetadata/v1/instance/service-accounts/default/token"
+            ),
+        })
+    }
+
+    /// Bearer token for the `cloud-platform` scope.
+    ///
+    /// Tokens are cached until shortly before they expire.
+    pub async fn token(&self) -> Result<String> {
+        if let Source::AccessToken(token) = &self.source {
+            return Ok(token.clone());
+        }
+
+        let mut cached = self.cached.lock().await;
+        if let Some(token) = cached.as_ref() {
+            if SystemTime::now() + REFRESH_MARGIN < token.expires_at {
+                return Ok(token.token.clone());
+            }
+        }
+
+        let fetched = self.fetch().await?;
+        let token = fetched.token.clone();
+        *cached = Some(fetched);
+        Ok(token)
+    }
+
+    async fn fetch(&self) -> Result<CachedToken> {
+        let request = match &self.source {
+            Source::AccessToken(_) => unreachable!("fixed tokens are not fetched"),
+            Source::ServiceAccount {
+                client_email,
+                key,
+                token_uri,
+            } => {
+                let iat = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs();
+                let claims = Claims {
+                    iss: client_email,
+                    scope: SCOPE,
+                    aud: token_uri,
+                    iat,
+                    exp: iat + DEFAULT_LIFETIME.as_secs(),
+                };
+                let assertion = jsonwebtoken::encode(&Header::new(Algorithm::RS256), &claims, key)
+                    .map_err(|e| CloudError::AuthFailed(format!("GCP token assertion: {e}")))?;
+                self.client.post(token_uri).form(&[
+                    ("grant_type", "urn:ietf:params:oauth:grant-type:jwt-bearer"),
+                    ("assertion", assertion.as_str()),
+                ])
+            }
+            Source::AuthorizedUser {
+                client_id,
+                client_secret,
+                refresh_token,
+                token_uri,
+            } => self.client.post(token_uri).form(&[
+                ("grant_type", "refresh_token"),
+                ("client_id", client_id.as_str()),
+                ("client_secret", client_secret.as_str()),
+                ("refresh_token", refresh_token.as_str()),
+            ]),
+            Source::Metadata { url } => self
+                .client
+                .get(url)
+                .header("Metadata-Flavor", "Google")
+                .query(&[("scopes", SCOPE)]),
+        };
+
+        let response = request
+            .timeout(Duration::from_secs(30))
+            .send()
+            .await
+            .map_err(|e| CloudError::AuthFailed(format!("GCP token request failed: {e}")))?;
+        let status = response.status();
+        let body: Value = response.json().await.unwrap_or_default();
+        if !status.is_success() {
+            let reason = body
+                .get("error_description")
+                .or_else(|| body.get("error"))
+                .map_or_else(|| status.to_string(), Value::to_string);
+            return Err(CloudError::AuthFailed(format!(
+                "GCP token request rejected: {reason}"
+            )));
+        }
+
+        let token = body
+            .get("access_token")
+            .and_then(Value::as_str)
+            .ok_or_else(|| {
+                CloudError::AuthFailed("GCP token response has no access_token".to_string())
+            })?
+            .to_string();
+        let lifetime = body
+            .get("expires_in")
+            .and_then(Value::as_u64)
+            .map_or(DEFAULT_LIFETIME, Duration::from_secs);
+        Ok(CachedToken {
+            token,
+            expires_at: SystemTime::now() + lifetime,
+        })
+    }
+}
+
+impl std::fmt::Debug for GcpCredential {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let source = match &self.source {
+            Source::AccessToken(_) => "access_token",
+            Source::ServiceAccount { .. } => "service_account",
+            Source::AuthorizedUser { .. } => "authorized_user",
+            Source::Metadata { .. } => "metadata_server",
+        };
+        f.debug_struct("GcpCredential")
+            .field("source", &source)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::extract::State;
+    use axum::http::HeaderMap;
+    use axum::Form;
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    async fn serve(app: axum::Router) -> std::net::SocketAddr {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        addr
+    }
+
+    #[test]
+    fn test_credentials_file_validation() {
+        let invalid = [
+            r#"{"type": "external_account"}"#,
+            r#"{"type": "service_account", "client_email": "sa@p.iam.gserviceaccount.com"}"#,
+            r#"{"type": "service_account", "client_email": "sa@p.iam.gserviceaccount.com", "private_key": "not a key"}"#,
+            r#"{"type": "authorized_user", "client_id": "id"}"#,
+        ];
+        for json in invalid {
+            assert!(
+                matches!(
+                    GcpCredential::from_json(json),
+                    Err(CloudError::InvalidCredentials(_))
+                ),
+                "{json}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_user_and_metadata_tokens_are_cached() {
+        async fn refresh(
+            State(requests): State<Arc<AtomicUsize>>,
+            Form(form): Form<HashMap<String, String>>,
+        ) -> axum::Json<Value> {
+            requests.fetch_add(1, Ordering::SeqCst);
+            assert_eq!(form["grant_type"], "refresh_token");
+            assert_eq!(form["refresh_token"], "refresh-1");
+            axum::Json(serde_json::json!({ "access_token": "user-token", "expires_in": 3599 }))
+        }
+
+        async fn metadata(
+            State(requests): State<Arc<AtomicUsize>>,
+            headers: HeaderMap,
+        ) -> axum::Json<Value> {
+            requests.fetch_add(1, Ordering::SeqCst);
+            assert_eq!(headers["metadata-flavor"], "Google");
+            axum::Json(
+                serde_json::json!({ "access_token": "vm-token", "expires_in": 3599, "token_type": "Bearer" }),
+            )
+        }
+
+        let requests = Arc::new(AtomicUsize::new(0));
+        let app = axum::Router::new()
+            .route("/token", axum::routing::post(refresh))
+            .route(
+                "/computeMetadata/v1/instance/service-accounts/default/token",
+                axum::routing::get(metadata),
+            )
+            .with_state(requests.clone());
+        let addr = serve(app).await;
+
+        let user = GcpCredential::from_json(&format!(
+            r#"{{"type": "authorized_user", "client_id": "id", "client_secret": "secret", "refresh_token": "refresh-1", "token_uri": "http://{addr}/token"}}"#
+        ))
+        .unwrap();
+        assert_eq!(user.token().await.unwrap(), "user-token");
+        assert_eq!(user.token().await.unwrap(), "user-token");
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+
+        let vm = GcpCredential::with_source(Source::Metadata {
+            url: format!(
+                "http://{addr}/computeMetadata/v1/instance/service-accounts/default/token"
+            ),
+        });
+        assert_eq!(vm.token().await.unwrap(), "vm-token");
+        assert_eq!(requests.load(Ordering::SeqCst), 2);
+    }
+}
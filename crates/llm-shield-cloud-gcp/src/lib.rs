@@ -7,6 +7,10 @@
 //! - **Object Storage**: GCP Cloud Storage via `GcpCloudStorage`
 //! - **Metrics**: Cloud Monitoring via `GcpCloudMonitoring`
 //! - **Logging**: Cloud Logging via `GcpCloudLogging`
+//! - **Tracing**: Cloud Trace via `GcpCloudTracer`
+//!
+//! Observability talks to the Google Cloud REST APIs directly; access tokens
+//! come from [`GcpCredential`].
 //!
 //! # Features
 //!
@@ -163,10 +167,14 @@
 //!         labels,
 //!         trace_id: Some("trace-789".to_string()),
 //!         span_id: Some("span-012".to_string()),
+//!         source: None,
 //!     };
 //!
 //!     logger.log_structured(&entry).await?;
 //!
+//!     // Entries are exported in the background; wait for delivery
+//!     logger.flush().await?;
+//!
 //!     Ok(())
 //! }
 //! ```
@@ -215,7 +223,7 @@
 //!
 //! - **Secret caching**: >90% cache hit rate reduces API calls
 //! - **Resumable uploads**: Automatically used for objects >5MB
-//! - **Batch export**: Metrics (20/batch) and logs (100/batch)
+//! - **Batch export**: Background batching with retry of throttled requests
 //! - **Async operations**: All I/O is fully asynchronous with tokio
 //!
 //! # Testing
@@ -238,28 +246,30 @@
 //!
 //! MIT OR Apache-2.0
 
+pub mod credential;
+pub mod observability;
+
 // Stub implementations due to SDK breaking changes
 // TODO: Update to latest google-cloud SDK APIs
-pub mod observability_stub;
 pub mod secrets_stub;
 pub mod storage_stub;
 
 // Re-export main types
-pub use observability_stub::{GcpCloudLogging, GcpCloudMonitoring};
+pub use credential::GcpCredential;
+pub use observability::{ExportOptions, GcpCloudLogging, GcpCloudMonitoring, GcpCloudTracer};
 pub use secrets_stub::GcpSecretManager;
 pub use storage_stub::GcpCloudStorage;
 
 // Keep original modules but don't compile them
 // #[cfg(feature = "gcp-full-impl")]
-// pub mod observability;
-// #[cfg(feature = "gcp-full-impl")]
 // pub mod secrets;
 // #[cfg(feature = "gcp-full-impl")]
 // pub mod storage;
 
 // Re-export cloud abstractions for convenience
 pub use llm_shield_cloud::{
-    CloudError, CloudLogger, CloudMetrics, CloudSecretManager, CloudStorage, GetObjectOptions,
+    CloudError, CloudLogger, CloudMetrics, CloudSecretManager, CloudStorage, CloudTracer,
+    GetObjectOptions,
     LogEntry, LogLevel, Metric, ObjectMetadata, PutObjectOptions, Result, SecretMetadata,
     SecretValue,
 };
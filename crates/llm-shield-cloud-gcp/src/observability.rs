@@ -1,67 +1,194 @@
-//! GCP Cloud Monitoring and Cloud Logging integration.
+//! GCP Cloud Monitoring, Cloud Logging and Cloud Trace integration.
 //!
-//! Provides implementations of `CloudMetrics` and `CloudLogger` traits for GCP.
-
-use google_cloud_googleapis::cloud::logging::v2::{
-    log_entry::Payload, logging_service_v2_client::LoggingServiceV2Client, LogEntry as GcpLogEntry,
-    WriteLogEntriesRequest,
-};
-use google_cloud_googleapis::cloud::monitoring::v3::{
-    metric_service_client::MetricServiceClient, CreateTimeSeriesRequest, Point, TimeInterval,
-    TimeSeries, TypedValue,
-};
-use google_cloud_googleapis::api::{MonitoredResource, MonitoredResourceMetadata};
+//! Provides implementations of `CloudMetrics`, `CloudLogger` and `CloudTracer`
+//! on top of the Google Cloud REST APIs:
+//!
+//! - [`GcpCloudMonitoring`] with `projects.timeSeries.create` (Monitoring v3)
+//! - [`GcpCloudLogging`] with `entries.write` (Logging v2)
+//! - [`GcpCloudTracer`] with `projects.traces.batchWrite` (Trace v2)
+//!
+//! Each exporter queues items and sends them in batches from a background
+//! task (see [`ExportOptions`]), so recording never waits on the network.
+//! Throttled and unavailable requests are retried with exponential backoff.
+//! Use `flush` to wait for delivery, e.g. before shutdown.
+
+use crate::credential::GcpCredential;
+use chrono::{DateTime, SecondsFormat, Utc};
+use llm_shield_cloud::export::{self, AttemptError, BatchExport, BatchQueue};
 use llm_shield_cloud::{
-    async_trait, CloudError, CloudLogger, CloudMetrics, LogEntry, LogLevel, Metric, Result,
+    async_trait, BatchConfig, CloudError, CloudLogger, CloudMetrics, CloudTracer, LogEntry,
+    LogLevel, Metric, Result, RetryConfig, Span,
 };
-use prost_types::Timestamp;
-use std::collections::HashMap;
+use serde_json::{json, Map, Value};
+use std::collections::{BTreeMap, HashMap};
 use std::sync::Arc;
-use tokio::sync::RwLock;
-use tonic::transport::Channel;
+use std::time::{Duration, SystemTime};
+
+const MONITORING_ENDPOINT: &str = "https://monitoring.googleapis.com";
+const LOGGING_ENDPOINT: &str = "https://logging.googleapis.com";
+const TRACE_ENDPOINT: &str = "https://cloudtrace.googleapis.com";
+
+/// Time series accepted per `timeSeries.create` request.
+const MAX_TIME_SERIES: usize = 200;
+
+/// Entries accepted per `entries.write` request (well under the 10 MB limit).
+const MAX_LOG_ENTRIES: usize = 1000;
+
+/// Endpoint, batching and retry settings of an exporter.
+#[derive(Debug, Clone)]
+pub struct ExportOptions {
+    /// Base URL replacing the service endpoint, e.g. for a private endpoint.
+    pub endpoint: Option<String>,
+
+    /// Batching of exported items.
+    pub batch: BatchConfig,
+
+    /// Retry of throttled or failed requests.
+    pub retry: RetryConfig,
+
+    /// Timeout of a single request in seconds.
+    pub timeout_seconds: u64,
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        Self {
+            endpoint: None,
+            batch: BatchConfig::default(),
+            retry: RetryConfig::default(),
+            timeout_seconds: 10,
+        }
+    }
+}
+
+impl ExportOptions {
+    fn endpoint_or(&self, default: &str) -> String {
+        self.endpoint
+            .as_deref()
+            .unwrap_or(default)
+            .trim_end_matches('/')
+            .to_string()
+    }
+}
+
+/// Posts authenticated JSON requests, retrying throttled and failed ones.
+struct HttpSender {
+    client: reqwest::Client,
+    credential: Arc<GcpCredential>,
+    retry: RetryConfig,
+    error: fn(String) -> CloudError,
+}
+
+impl HttpSender {
+    fn new(
+        options: &ExportOptions,
+        credential: GcpCredential,
+        error: fn(String) -> CloudError,
+    ) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(options.timeout_seconds.max(1)))
+            .build()
+            .map_err(|e| CloudError::ClientInit(e.to_string()))?;
+        Ok(Self {
+            client,
+            credential: Arc::new(credential),
+            retry: options.retry.clone(),
+            error,
+        })
+    }
+
+    async fn post(&self, url: &str, body: &Value) -> Result<()> {
+        let token = self
+            .credential
+            .token()
+            .await
+            .map_err(|e| (self.error)(e.to_string()))?;
+
+        export::retry(&self.retry, || async {
+            let response = self
+                .client
+                .post(url)
+                .bearer_auth(&token)
+                .json(body)
+                .send()
+                .await
+                .map_err(|e| {
+                    if e.is_connect() || e.is_timeout() {
+                        AttemptError::retryable(e.to_string())
+                    } else {
+                        AttemptError::permanent(e.to_string())
+                    }
+                })?;
+
+            let status = response.status();
+            if status.is_success() {
+                return Ok(());
+            }
+            let body = response.text().await.unwrap_or_default();
+            Err(AttemptError::http_status(
+                status.as_u16(),
+                format!("{status}: {body}"),
+            ))
+        })
+        .await
+        .map_err(self.error)
+    }
+}
+
+/// `global` monitored resource of a project.
+fn global_resource(project_id: &str) -> Value {
+    json!({ "type": "global", "labels": { "project_id": project_id } })
+}
 
 /// GCP Cloud Monitoring implementation of `CloudMetrics`.
 ///
-/// This implementation provides:
-/// - Batched metric export for efficiency
-/// - Support for custom metric descriptors
-/// - Automatic project and resource configuration
-/// - Standard and custom metric types
+/// Metrics are written as `custom.googleapis.com/<name>` gauge time series
+/// on the `global` resource, with dimensions as metric labels. A time series
+/// takes one point per request, so only the latest value of each series in
+/// a batch is written. Requires the *Monitoring Metric Writer* role.
 ///
 /// # Example
 ///
 /// ```no_run
 /// use llm_shield_cloud_gcp::GcpCloudMonitoring;
 /// use llm_shield_cloud::{CloudMetrics, Metric};
-/// use std::collections::HashMap;
 ///
 /// #[tokio::main]
 /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
 ///     let metrics = GcpCloudMonitoring::new("my-project-id").await?;
 ///
-///     let metric = Metric {
-///         name: "RequestCount".to_string(),
-///         value: 1.0,
-///         timestamp: std::time::SystemTime::now()
-///             .duration_since(std::time::UNIX_EPOCH)?
-///             .as_secs(),
-///         dimensions: HashMap::new(),
-///         unit: Some("Count".to_string()),
-///     };
-///
+///     let metric = Metric::new("RequestCount", 1.0).with_unit("1");
 ///     metrics.export_metric(&metric).await?;
+///     metrics.flush().await?;
 ///     Ok(())
 /// }
 /// ```
 pub struct GcpCloudMonitoring {
-    client: MetricServiceClient<Channel>,
     project_id: String,
-    batch_buffer: Arc<RwLock<Vec<Metric>>>,
-    batch_size: usize,
+    queue: BatchQueue<Metric>,
+}
+
+struct MetricsExporter {
+    sender: HttpSender,
+    url: String,
+    project_id: String,
+}
+
+#[async_trait]
+impl BatchExport<Metric> for MetricsExporter {
+    async fn export(&self, batch: Vec<Metric>) -> Result<()> {
+        let series = time_series(&self.project_id, &batch);
+        for chunk in series.chunks(MAX_TIME_SERIES) {
+            self.sender
+                .post(&self.url, &json!({ "timeSeries": chunk }))
+                .await?;
+        }
+        Ok(())
+    }
 }
 
 impl GcpCloudMonitoring {
-    /// Creates a new Cloud Monitoring client with default configuration.
+    /// Creates a Cloud Monitoring exporter.
     ///
     /// # Arguments
     ///
@@ -71,63 +198,41 @@ impl GcpCloudMonitoring {
     ///
     /// Returns error if GCP credentials cannot be loaded.
     pub async fn new(project_id: impl Into<String>) -> Result<Self> {
-        let project_id = project_id.into();
-
-        // Create gRPC channel
-        let channel = Channel::from_static("https://monitoring.googleapis.com")
-            .connect()
-            .await
-            .map_err(|e| CloudError::Connection(e.to_string()))?;
-
-        let client = MetricServiceClient::new(channel);
-
-        tracing::info!(
-            "Initialized GCP Cloud Monitoring client for project: {}",
-            project_id
-        );
-
-        Ok(Self {
-            client,
+        Self::with_options(
             project_id,
-            batch_buffer: Arc::new(RwLock::new(Vec::new())),
-            batch_size: 20,
-        })
+            GcpCredential::from_env()?,
+            ExportOptions::default(),
+        )
     }
 
-    /// Creates a new Cloud Monitoring client with custom batch size.
-    ///
-    /// # Arguments
-    ///
-    /// * `project_id` - GCP project ID
-    /// * `batch_size` - Number of metrics to batch before sending (max 200)
-    ///
-    /// # Errors
+    /// Creates a Cloud Monitoring exporter with explicit credentials and
+    /// options.
     ///
-    /// Returns error if GCP credentials cannot be loaded.
-    pub async fn new_with_batch_size(
+    /// Must be called within a Tokio runtime.
+    pub fn with_options(
         project_id: impl Into<String>,
-        batch_size: usize,
+        credential: GcpCredential,
+        options: ExportOptions,
     ) -> Result<Self> {
         let project_id = project_id.into();
-
-        let channel = Channel::from_static("https://monitoring.googleapis.com")
-            .connect()
-            .await
-            .map_err(|e| CloudError::Connection(e.to_string()))?;
-
-        let client = MetricServiceClient::new(channel);
+        let exporter = MetricsExporter {
+            sender: HttpSender::new(&options, credential, CloudError::MetricsExport)?,
+            url: format!(
+                "{}/v3/projects/{}/timeSeries",
+                options.endpoint_or(MONITORING_ENDPOINT),
+                project_id
+            ),
+            project_id: project_id.clone(),
+        };
 
         tracing::info!(
-            "Initialized GCP Cloud Monitoring client for project: {} with batch size: {}",
-            project_id,
-            batch_size
+            "Initialized GCP Cloud Monitoring client for project: {}",
+            project_id
         );
 
         Ok(Self {
-            client,
             project_id,
-            batch_buffer: Arc::new(RwLock::new(Vec::new())),
-            batch_size: batch_size.min(200), // GCP limit
+            queue: BatchQueue::spawn(&options.batch, CloudError::MetricsExport, exporter),
         })
     }
 
@@ -136,131 +241,62 @@ impl GcpCloudMonitoring {
         &self.project_id
     }
 
-    /// Flushes buffered metrics to Cloud Monitoring.
+    /// Exports all queued metrics.
     pub async fn flush(&self) -> Result<()> {
-        let mut buffer = self.batch_buffer.write().await;
-
-        if buffer.is_empty() {
-            return Ok(());
-        }
-
-        let metrics_to_send = buffer.drain(..).collect::<Vec<_>>();
-        drop(buffer);
-
-        self.send_metrics_batch(&metrics_to_send).await?;
-
-        Ok(())
-    }
-
-    /// Sends a batch of metrics to Cloud Monitoring.
-    async fn send_metrics_batch(&self, metrics: &[Metric]) -> Result<()> {
-        if metrics.is_empty() {
-            return Ok(());
-        }
-
-        tracing::debug!("Sending {} metrics to Cloud Monitoring", metrics.len());
-
-        let project_name = format!("projects/{}", self.project_id);
-
-        // Convert metrics to TimeSeries
-        let time_series: Vec<TimeSeries> = metrics
-            .iter()
-            .map(|m| {
-                let metric_type = format!("custom.googleapis.com/{}", m.name);
-
-                let labels: HashMap<String, String> = m.dimensions.clone();
-
-                let timestamp = Timestamp {
-                    seconds: m.timestamp as i64,
-                    nanos: 0,
-                };
-
-                let point = Point {
-                    interval: Some(TimeInterval {
-                        end_time: Some(timestamp.clone()),
-                        start_time: Some(timestamp),
-                    }),
-                    value: Some(TypedValue {
-                        value: Some(google_cloud_googleapis::cloud::monitoring::v3::typed_value::Value::DoubleValue(
-                            m.value,
-                        )),
-                    }),
-                };
-
-                TimeSeries {
-                    metric: Some(google_cloud_googleapis::api::Metric {
-                        r#type: metric_type,
-                        labels,
-                    }),
-                    resource: Some(MonitoredResource {
-                        r#type: "global".to_string(),
-                        labels: HashMap::new(),
-                    }),
-                    metadata: None,
-                    metric_kind: google_cloud_googleapis::api::metric_descriptor::MetricKind::Gauge as i32,
-                    value_type: google_cloud_googleapis::api::metric_descriptor::ValueType::Double as i32,
-                    points: vec![point],
-                    unit: m.unit.clone().unwrap_or_default(),
-                }
-            })
-            .collect();
-
-        let request = CreateTimeSeriesRequest {
-            name: project_name,
-            time_series,
-        };
-
-        self.client
-            .clone()
-            .create_time_series(request)
-            .await
-            .map_err(|e| CloudError::MetricsExport(e.to_string()))?;
-
-        tracing::info!("Successfully sent {} metrics to Cloud Monitoring", metrics.len());
-
-        Ok(())
+        self.queue.flush().await
     }
 }
 
 #[async_trait]
 impl CloudMetrics for GcpCloudMonitoring {
     async fn export_metrics(&self, metrics: &[Metric]) -> Result<()> {
-        tracing::debug!("Exporting {} metrics to Cloud Monitoring", metrics.len());
-
-        // Send in batches
-        for chunk in metrics.chunks(self.batch_size) {
-            self.send_metrics_batch(chunk).await?;
-        }
-
-        Ok(())
+        self.queue.push(metrics.to_vec())
     }
+}
 
-    async fn export_metric(&self, metric: &Metric) -> Result<()> {
-        tracing::debug!("Exporting metric to Cloud Monitoring: {}", metric.name);
-
-        // Add to buffer
-        let mut buffer = self.batch_buffer.write().await;
-        buffer.push(metric.clone());
-
-        // Flush if buffer is full
-        if buffer.len() >= self.batch_size {
-            let metrics_to_send = buffer.drain(..).collect::<Vec<_>>();
-            drop(buffer);
-
-            self.send_metrics_batch(&metrics_to_send).await?;
+/// Time series of a batch, keeping the latest point of each series.
+fn time_series(project_id: &str, metrics: &[Metric]) -> Vec<Value> {
+    let mut latest: BTreeMap<(String, BTreeMap<&String, &String>), &Metric> = BTreeMap::new();
+    for metric in metrics {
+        let key = (metric.name.clone(), metric.dimensions.iter().collect());
+        match latest.get(&key) {
+            Some(current) if current.timestamp > metric.timestamp => {}
+            _ => {
+                latest.insert(key, metric);
+            }
         }
-
-        Ok(())
     }
+
+    latest
+        .into_values()
+        .map(|metric| {
+            let mut series = json!({
+                "metric": {
+                    "type": format!("custom.googleapis.com/{}", metric.name),
+                    "labels": metric.dimensions,
+                },
+                "resource": global_resource(project_id),
+                "metricKind": "GAUGE",
+                "valueType": "DOUBLE",
+                "points": [{
+                    "interval": { "endTime": unix_rfc3339(metric.timestamp) },
+                    "value": { "doubleValue": metric.value },
+                }],
+            });
+            if let Some(ref unit) = metric.unit {
+                series["unit"] = json!(unit);
+            }
+            series
+        })
+        .collect()
 }
 
 /// GCP Cloud Logging implementation of `CloudLogger`.
 ///
-/// This implementation provides:
-/// - Batched log export for efficiency
-/// - Structured logging support
-/// - Automatic resource detection
-/// - Log severity levels
+/// Entries are written to `projects/<project>/logs/<log_name>` on the
+/// `global` resource. Trace and span IDs are written in Cloud Trace format,
+/// so entries show up under the spans exported by [`GcpCloudTracer`].
+/// Requires the *Logs Writer* role.
 ///
 /// # Example
 ///
@@ -276,19 +312,49 @@ impl CloudMetrics for GcpCloudMonitoring {
 ///     ).await?;
 ///
 ///     logger.log("Application started", LogLevel::Info).await?;
+///     logger.flush().await?;
 ///     Ok(())
 /// }
 /// ```
 pub struct GcpCloudLogging {
-    client: LoggingServiceV2Client<Channel>,
     project_id: String,
     log_name: String,
-    batch_buffer: Arc<RwLock<Vec<LogEntry>>>,
-    batch_size: usize,
+    queue: BatchQueue<LogEntry>,
+}
+
+struct LogsExporter {
+    sender: HttpSender,
+    url: String,
+    project_id: String,
+    log_name: String,
+}
+
+#[async_trait]
+impl BatchExport<LogEntry> for LogsExporter {
+    async fn export(&self, batch: Vec<LogEntry>) -> Result<()> {
+        for chunk in batch.chunks(MAX_LOG_ENTRIES) {
+            let entries: Vec<Value> = chunk
+                .iter()
+                .map(|entry| log_entry(&self.project_id, entry))
+                .collect();
+            let body = json!({
+                "logName": format!(
+                    "projects/{}/logs/{}",
+                    self.project_id,
+                    self.log_name.replace('/', "%2F")
+                ),
+                "resource": global_resource(&self.project_id),
+                "entries": entries,
+                "partialSuccess": true,
+            });
+            self.sender.post(&self.url, &body).await?;
+        }
+        Ok(())
+    }
 }
 
 impl GcpCloudLogging {
-    /// Creates a new Cloud Logging client with default configuration.
+    /// Creates a Cloud Logging client.
     ///
     /// # Arguments
     ///
@@ -299,70 +365,42 @@ impl GcpCloudLogging {
     ///
     /// Returns error if GCP credentials cannot be loaded.
     pub async fn new(project_id: impl Into<String>, log_name: impl Into<String>) -> Result<Self> {
-        let project_id = project_id.into();
-        let log_name = log_name.into();
-
-        let channel = Channel::from_static("https://logging.googleapis.com")
-            .connect()
-            .await
-            .map_err(|e| CloudError::Connection(e.to_string()))?;
-
-        let client = LoggingServiceV2Client::new(channel);
-
-        tracing::info!(
-            "Initialized GCP Cloud Logging client for project: {} log: {}",
-            project_id,
-            log_name
-        );
-
-        Ok(Self {
-            client,
+        Self::with_options(
             project_id,
             log_name,
-            batch_buffer: Arc::new(RwLock::new(Vec::new())),
-            batch_size: 100,
-        })
+            GcpCredential::from_env()?,
+            ExportOptions::default(),
+        )
     }
 
-    /// Creates a new Cloud Logging client with custom batch size.
-    ///
-    /// # Arguments
+    /// Creates a Cloud Logging client with explicit credentials and options.
     ///
-    /// * `project_id` - GCP project ID
-    /// * `log_name` - Log name
-    /// * `batch_size` - Number of log entries to batch before sending
-    ///
-    /// # Errors
-    ///
-    /// Returns error if GCP credentials cannot be loaded.
-    pub async fn new_with_batch_size(
+    /// Must be called within a Tokio runtime.
+    pub fn with_options(
         project_id: impl Into<String>,
         log_name: impl Into<String>,
-        batch_size: usize,
+        credential: GcpCredential,
+        options: ExportOptions,
     ) -> Result<Self> {
         let project_id = project_id.into();
         let log_name = log_name.into();
-
-        let channel = Channel::from_static("https://logging.googleapis.com")
-            .connect()
-            .await
-            .map_err(|e| CloudError::Connection(e.to_string()))?;
-
-        let client = LoggingServiceV2Client::new(channel);
+        let exporter = LogsExporter {
+            sender: HttpSender::new(&options, credential, CloudError::LogExport)?,
+            url: format!("{}/v2/entries:write", options.endpoint_or(LOGGING_ENDPOINT)),
+            project_id: project_id.clone(),
+            log_name: log_name.clone(),
+        };
 
         tracing::info!(
-            "Initialized GCP Cloud Logging client for project: {} log: {} with batch size: {}",
+            "Initialized GCP Cloud Logging client for project: {} log: {}",
             project_id,
-            log_name,
-            batch_size
+            log_name
         );
 
         Ok(Self {
-            client,
             project_id,
             log_name,
-            batch_buffer: Arc::new(RwLock::new(Vec::new())),
-            batch_size,
+            queue: BatchQueue::spawn(&options.batch, CloudError::LogExport, exporter),
         })
     }
 
@@ -376,145 +414,304 @@ impl GcpCloudLogging {
         &self.log_name
     }
 
-    /// Flushes buffered log entries to Cloud Logging.
+    /// Exports all queued log entries.
     pub async fn flush(&self) -> Result<()> {
-        let mut buffer = self.batch_buffer.write().await;
+        self.queue.flush().await
+    }
+}
 
-        if buffer.is_empty() {
-            return Ok(());
-        }
+#[async_trait]
+impl CloudLogger for GcpCloudLogging {
+    async fn log(&self, message: &str, level: LogLevel) -> Result<()> {
+        self.log_structured(&LogEntry::new(level, message)).await
+    }
 
-        let logs_to_send = buffer.drain(..).collect::<Vec<_>>();
-        drop(buffer);
+    async fn log_structured(&self, entry: &LogEntry) -> Result<()> {
+        self.queue.push(vec![entry.clone()])
+    }
 
-        self.send_logs_batch(&logs_to_send).await?;
+    async fn log_batch(&self, entries: &[LogEntry]) -> Result<()> {
+        self.queue.push(entries.to_vec())
+    }
+}
 
-        Ok(())
+/// `LogEntry` resource of a log entry.
+fn log_entry(project_id: &str, entry: &LogEntry) -> Value {
+    let mut value = json!({
+        "timestamp": rfc3339(entry.timestamp),
+        "severity": format_severity(&entry.level),
+        "textPayload": entry.message,
+    });
+
+    let mut labels: Map<String, Value> = entry
+        .labels
+        .iter()
+        .map(|(key, label)| (key.clone(), json!(label)))
+        .collect();
+    if let Some(ref source) = entry.source {
+        labels.insert("source".to_string(), json!(source));
+    }
+    if !labels.is_empty() {
+        value["labels"] = Value::Object(labels);
+    }
+    if let Some(ref trace_id) = entry.trace_id {
+        value["trace"] = json!(trace_name(project_id, trace_id));
+    }
+    if let Some(ref span_id) = entry.span_id {
+        value["spanId"] = json!(export::hex_id(span_id, 8));
     }
 
-    /// Sends a batch of log entries to Cloud Logging.
-    async fn send_logs_batch(&self, entries: &[LogEntry]) -> Result<()> {
-        if entries.is_empty() {
-            return Ok(());
-        }
+    value
+}
 
-        tracing::debug!("Sending {} log entries to Cloud Logging", entries.len());
+/// Formats a LogLevel as a Cloud Logging severity.
+fn format_severity(level: &LogLevel) -> &'static str {
+    match level {
+        LogLevel::Trace | LogLevel::Debug => "DEBUG",
+        LogLevel::Info => "INFO",
+        LogLevel::Warn => "WARNING",
+        LogLevel::Error => "ERROR",
+        LogLevel::Fatal => "CRITICAL",
+    }
+}
 
-        let log_name = format!("projects/{}/logs/{}", self.project_id, self.log_name);
+/// GCP Cloud Trace implementation of `CloudTracer`.
+///
+/// Spans are written with `traces.batchWrite`; span attributes become
+/// string attributes and spans with status `ERROR` get an error status.
+/// Requires the *Cloud Trace Agent* role.
+///
+/// # Example
+///
+/// ```no_run
+/// use llm_shield_cloud_gcp::GcpCloudTracer;
+/// use llm_shield_cloud::CloudTracer;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let tracer = GcpCloudTracer::new("my-project-id").await?;
+///
+///     let span = tracer.start_span("scan_prompt");
+///     tracer.end_span(span.end_with_status("OK")).await?;
+///
+///     tracer.flush().await?;
+///     Ok(())
+/// }
+/// ```
+pub struct GcpCloudTracer {
+    project_id: String,
+    queue: BatchQueue<Span>,
+}
 
-        let gcp_entries: Vec<GcpLogEntry> = entries
+struct TraceExporter {
+    sender: HttpSender,
+    url: String,
+    project_id: String,
+}
+
+#[async_trait]
+impl BatchExport<Span> for TraceExporter {
+    async fn export(&self, batch: Vec<Span>) -> Result<()> {
+        let spans: Vec<Value> = batch
             .iter()
-            .map(|entry| {
-                let timestamp = entry
-                    .timestamp
-                    .duration_since(std::time::UNIX_EPOCH)
-                    .unwrap_or_default();
-
-                let severity = match entry.level {
-                    LogLevel::Trace => google_cloud_googleapis::logging::r#type::LogSeverity::Debug,
-                    LogLevel::Debug => google_cloud_googleapis::logging::r#type::LogSeverity::Debug,
-                    LogLevel::Info => google_cloud_googleapis::logging::r#type::LogSeverity::Info,
-                    LogLevel::Warn => google_cloud_googleapis::logging::r#type::LogSeverity::Warning,
-                    LogLevel::Error => google_cloud_googleapis::logging::r#type::LogSeverity::Error,
-                    LogLevel::Fatal => google_cloud_googleapis::logging::r#type::LogSeverity::Critical,
-                };
-
-                GcpLogEntry {
-                    log_name: log_name.clone(),
-                    resource: Some(MonitoredResource {
-                        r#type: "global".to_string(),
-                        labels: HashMap::new(),
-                    }),
-                    timestamp: Some(Timestamp {
-                        seconds: timestamp.as_secs() as i64,
-                        nanos: timestamp.subsec_nanos() as i32,
-                    }),
-                    severity: severity as i32,
-                    labels: entry.labels.clone(),
-                    trace: entry.trace_id.clone().unwrap_or_default(),
-                    span_id: entry.span_id.clone().unwrap_or_default(),
-                    payload: Some(Payload::TextPayload(entry.message.clone())),
-                    ..Default::default()
-                }
-            })
+            .map(|span| trace_span(&self.project_id, span))
             .collect();
+        self.sender
+            .post(&self.url, &json!({ "spans": spans }))
+            .await
+    }
+}
 
-        let request = WriteLogEntriesRequest {
-            log_name,
-            resource: Some(MonitoredResource {
-                r#type: "global".to_string(),
-                labels: HashMap::new(),
-            }),
-            labels: HashMap::new(),
-            entries: gcp_entries,
-            partial_success: false,
-            dry_run: false,
-        };
+impl GcpCloudTracer {
+    /// Creates a Cloud Trace exporter.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if GCP credentials cannot be loaded.
+    pub async fn new(project_id: impl Into<String>) -> Result<Self> {
+        Self::with_options(
+            project_id,
+            GcpCredential::from_env()?,
+            ExportOptions::default(),
+        )
+    }
 
-        self.client
-            .clone()
-            .write_log_entries(request)
-            .await
-            .map_err(|e| CloudError::LogExport(e.to_string()))?;
+    /// Creates a Cloud Trace exporter with explicit credentials and options.
+    ///
+    /// Must be called within a Tokio runtime.
+    pub fn with_options(
+        project_id: impl Into<String>,
+        credential: GcpCredential,
+        options: ExportOptions,
+    ) -> Result<Self> {
+        let project_id = project_id.into();
+        let exporter = TraceExporter {
+            sender: HttpSender::new(&options, credential, CloudError::TraceExport)?,
+            url: format!(
+                "{}/v2/projects/{}/traces:batchWrite",
+                options.endpoint_or(TRACE_ENDPOINT),
+                project_id
+            ),
+            project_id: project_id.clone(),
+        };
 
         tracing::info!(
-            "Successfully sent {} log entries to Cloud Logging",
-            entries.len()
+            "Initialized GCP Cloud Trace client for project: {}",
+            project_id
         );
 
-        Ok(())
+        Ok(Self {
+            project_id,
+            queue: BatchQueue::spawn(&options.batch, CloudError::TraceExport, exporter),
+        })
+    }
+
+    /// Gets the project ID this client is configured for.
+    pub fn project_id(&self) -> &str {
+        &self.project_id
+    }
+
+    /// Exports all queued spans.
+    pub async fn flush(&self) -> Result<()> {
+        self.queue.flush().await
     }
 }
 
 #[async_trait]
-impl CloudLogger for GcpCloudLogging {
-    async fn log(&self, message: &str, level: LogLevel) -> Result<()> {
-        let entry = LogEntry {
-            timestamp: std::time::SystemTime::now(),
-            level,
-            message: message.to_string(),
-            labels: HashMap::new(),
-            trace_id: None,
-            span_id: None,
-            source: None,
-        };
+impl CloudTracer for GcpCloudTracer {
+    async fn end_span(&self, mut span: Span) -> Result<()> {
+        if span.end_time.is_none() {
+            span.end_time = Some(SystemTime::now());
+        }
+        self.queue.push(vec![span])
+    }
+
+    async fn export_spans(&self, spans: &[Span]) -> Result<()> {
+        self.queue.push(spans.to_vec())
+    }
+}
 
-        self.log_structured(&entry).await
+/// Cloud Trace v2 `Span` resource of a span.
+fn trace_span(project_id: &str, span: &Span) -> Value {
+    let span_id = export::hex_id(&span.span_id, 8);
+    let attributes: HashMap<&String, Value> = span
+        .attributes
+        .iter()
+        .map(|(key, value)| (key, json!({ "stringValue": truncatable(value) })))
+        .collect();
+
+    let mut value = json!({
+        "name": format!("{}/spans/{}", trace_name(project_id, &span.trace_id), span_id),
+        "spanId": span_id,
+        "displayName": truncatable(&span.name),
+        "startTime": rfc3339(span.start_time),
+        "endTime": rfc3339(span.end_time.unwrap_or(span.start_time)),
+        "attributes": { "attributeMap": attributes },
+    });
+    if let Some(ref parent) = span.parent_span_id {
+        value["parentSpanId"] = json!(export::hex_id(parent, 8));
+    }
+    if let Some(status) = span
+        .status
+        .as_deref()
+        .filter(|status| status.eq_ignore_ascii_case("error"))
+    {
+        // google.rpc.Code UNKNOWN
+        value["status"] = json!({ "code": 2, "message": status });
     }
 
-    async fn log_structured(&self, entry: &LogEntry) -> Result<()> {
-        tracing::debug!("Logging structured entry to Cloud Logging");
+    value
+}
 
-        // Add to buffer
-        let mut buffer = self.batch_buffer.write().await;
-        buffer.push(entry.clone());
+/// `projects/<project>/traces/<32 hex digits>`, shared by logs and spans.
+fn trace_name(project_id: &str, trace_id: &str) -> String {
+    format!(
+        "projects/{}/traces/{}",
+        project_id,
+        export::hex_id(trace_id, 16)
+    )
+}
 
-        // Flush if buffer is full
-        if buffer.len() >= self.batch_size {
-            let logs_to_send = buffer.drain(..).collect::<Vec<_>>();
-            drop(buffer);
+fn truncatable(value: &str) -> Value {
+    json!({ "value": value, "truncatedByteCount": 0 })
+}
 
-            self.send_logs_batch(&logs_to_send).await?;
-        }
+fn rfc3339(time: SystemTime) -> String {
+    DateTime::<Utc>::from(time).to_rfc3339_opts(SecondsFormat::Micros, true)
+}
 
-        Ok(())
+fn unix_rfc3339(seconds: u64) -> String {
+    DateTime::from_timestamp(seconds as i64, 0)
+        .unwrap_or_else(Utc::now)
+        .to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Bytes;
+    use axum::extract::{OriginalUri, State};
+    use axum::http::{HeaderMap, StatusCode};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tokio::net::TcpListener;
+    use tokio::sync::mpsc;
+
+    /// Request received by a stand-in Google API endpoint
+    #[derive(Debug)]
+    struct Received {
+        path: String,
+        headers: HeaderMap,
+        body: Value,
     }
 
-    async fn log_batch(&self, entries: &[LogEntry]) -> Result<()> {
-        tracing::debug!("Logging batch of {} entries to Cloud Logging", entries.len());
+    #[derive(Clone)]
+    struct MockGoogle {
+        requests: mpsc::UnboundedSender<Received>,
+        failures: Arc<AtomicUsize>,
+    }
 
-        // Send in batches
-        for chunk in entries.chunks(self.batch_size) {
-            self.send_logs_batch(chunk).await?;
+    async fn receive(
+        State(mock): State<MockGoogle>,
+        OriginalUri(uri): OriginalUri,
+        headers: HeaderMap,
+        body: Bytes,
+    ) -> (StatusCode, &'static str) {
+        let unavailable = mock
+            .failures
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+            .is_ok();
+        if unavailable {
+            return (StatusCode::SERVICE_UNAVAILABLE, "");
         }
 
-        Ok(())
+        let _ = mock.requests.send(Received {
+            path: uri.to_string(),
+            headers,
+            body: serde_json::from_slice(&body).unwrap(),
+        });
+        (StatusCode::OK, "{}")
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// Starts a stand-in endpoint answering 503 `failures` times, then 200
+    async fn mock_google(failures: usize) -> (ExportOptions, mpsc::UnboundedReceiver<Received>) {
+        let (requests, received) = mpsc::unbounded_channel();
+        let mock = MockGoogle {
+            requests,
+            failures: Arc::new(AtomicUsize::new(failures)),
+        };
+
+        let app = axum::Router::new().fallback(receive).with_state(mock);
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let mut options = ExportOptions {
+            endpoint: Some(format!("http://{addr}")),
+            ..ExportOptions::default()
+        };
+        options.retry.initial_backoff_ms = 10;
+        (options, received)
+    }
 
     #[test]
     fn test_batch_size_limits() {
@@ -524,6 +721,8 @@ mod tests {
         // GCP limits
         assert!(metrics_batch_size <= 200);
         assert!(logs_batch_size <= 1000);
+        assert_eq!(MAX_TIME_SERIES, 200);
+        assert_eq!(MAX_LOG_ENTRIES, 1000);
     }
 
     #[test]
@@ -531,6 +730,9 @@ mod tests {
         let metric_name = "RequestCount";
         let expected = format!("custom.googleapis.com/{}", metric_name);
         assert_eq!(expected, "custom.googleapis.com/RequestCount");
+
+        let series = time_series("p", &[Metric::new(metric_name, 1.0)]);
+        assert_eq!(series[0]["metric"]["type"], expected.as_str());
     }
 
     #[test]
@@ -543,7 +745,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_metric_batching() {
-        let metrics = vec![
+        let metrics = [
             Metric {
                 name: "test1".to_string(),
                 value: 1.0,
@@ -568,4 +770,121 @@ mod tests {
         assert_eq!(chunks[0].len(), 1);
         assert_eq!(chunks[1].len(), 1);
     }
+
+    #[tokio::test]
+    async fn test_metrics_keep_latest_point_per_series() {
+        let (options, mut received) = mock_google(1).await;
+        let metrics = GcpCloudMonitoring::with_options(
+            "proj-1",
+            GcpCredential::access_token("token-1"),
+            options,
+        )
+        .unwrap();
+
+        let at = |value: f64, timestamp: u64, scanner: &str| {
+            let mut metric = Metric::new("scan_duration", value).with_dimension("scanner", scanner);
+            metric.timestamp = timestamp;
+            metric
+        };
+        metrics
+            .export_metrics(&[
+                at(30.0, 1_700_000_060, "toxicity"),
+                at(10.0, 1_700_000_000, "toxicity"),
+                at(5.0, 1_700_000_000, "secrets"),
+            ])
+            .await
+            .unwrap();
+        metrics.flush().await.unwrap();
+
+        let request = received.recv().await.unwrap();
+        assert_eq!(request.path, "/v3/projects/proj-1/timeSeries");
+        assert_eq!(request.headers["authorization"], "Bearer token-1");
+
+        let series = request.body["timeSeries"].as_array().unwrap();
+        assert_eq!(series.len(), 2);
+        let toxicity = series
+            .iter()
+            .find(|s| s["metric"]["labels"]["scanner"] == "toxicity")
+            .unwrap();
+        assert_eq!(
+            toxicity["metric"]["type"],
+            "custom.googleapis.com/scan_duration"
+        );
+        assert_eq!(toxicity["resource"], global_resource("proj-1"));
+        assert_eq!(toxicity["metricKind"], "GAUGE");
+        assert_eq!(
+            toxicity["points"],
+            json!([{
+                "interval": { "endTime": "2023-11-14T22:14:20Z" },
+                "value": { "doubleValue": 30.0 },
+            }])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_logs_and_spans_share_trace_ids() {
+        let (options, mut received) = mock_google(0).await;
+        let credential = || GcpCredential::access_token("token-1");
+        let logger = GcpCloudLogging::with_options(
+            "proj-1",
+            "llm-shield-api",
+            credential(),
+            options.clone(),
+        )
+        .unwrap();
+        let tracer = GcpCloudTracer::with_options("proj-1", credential(), options).unwrap();
+
+        let root = tracer.start_span("scan_prompt");
+        let child = tracer
+            .start_child_span("toxicity", &root)
+            .with_attribute("risk_score", "0.9");
+        logger
+            .log_structured(
+                &LogEntry::new(LogLevel::Warn, "prompt blocked")
+                    .with_label("scanner", "toxicity")
+                    .with_trace_id(root.trace_id.clone())
+                    .with_span_id(child.span_id.clone()),
+            )
+            .await
+            .unwrap();
+        tracer
+            .export_spans(&[
+                child.end_with_status("ERROR"),
+                root.clone().end_with_status("OK"),
+            ])
+            .await
+            .unwrap();
+        logger.flush().await.unwrap();
+        tracer.flush().await.unwrap();
+
+        let logs = received.recv().await.unwrap();
+        assert_eq!(logs.path, "/v2/entries:write");
+        assert_eq!(logs.body["logName"], "projects/proj-1/logs/llm-shield-api");
+        let entry = &logs.body["entries"][0];
+        assert_eq!(entry["severity"], "WARNING");
+        assert_eq!(entry["textPayload"], "prompt blocked");
+        assert_eq!(entry["labels"]["scanner"], "toxicity");
+
+        let traces = received.recv().await.unwrap();
+        assert_eq!(traces.path, "/v2/projects/proj-1/traces:batchWrite");
+        let spans = traces.body["spans"].as_array().unwrap();
+        let (child, root) = (&spans[0], &spans[1]);
+        assert_eq!(
+            root["name"],
+            format!(
+                "{}/spans/{}",
+                entry["trace"].as_str().unwrap(),
+                root["spanId"].as_str().unwrap()
+            )
+        );
+        assert_eq!(root["displayName"]["value"], "scan_prompt");
+        assert!(root.get("status").is_none());
+        assert_eq!(child["parentSpanId"], root["spanId"]);
+        assert_eq!(child["spanId"], entry["spanId"]);
+        assert_eq!(child["status"]["code"], 2);
+        assert_eq!(
+            child["attributes"]["attributeMap"]["risk_score"]["stringValue"]["value"],
+            "0.9"
+        );
+    }
 }
@@ -0,0 +1,309 @@
+//! Conversion of cloud observability types into OTLP protobuf messages.
+
+use llm_shield_cloud::export;
+use llm_shield_cloud::{LogEntry, LogLevel, Metric, OtlpConfig, Span};
+use opentelemetry_proto::tonic::collector::logs::v1::ExportLogsServiceRequest;
+use opentelemetry_proto::tonic::collector::metrics::v1::ExportMetricsServiceRequest;
+use opentelemetry_proto::tonic::collector::trace::v1::ExportTraceServiceRequest;
+use opentelemetry_proto::tonic::common::v1::{any_value, AnyValue, InstrumentationScope, KeyValue};
+use opentelemetry_proto::tonic::logs::v1::{LogRecord, ResourceLogs, ScopeLogs, SeverityNumber};
+use opentelemetry_proto::tonic::metrics::v1::{
+    metric, number_data_point, Gauge, Metric as OtlpMetric, NumberDataPoint, ResourceMetrics,
+    ScopeMetrics,
+};
+use opentelemetry_proto::tonic::resource::v1::Resource;
+use opentelemetry_proto::tonic::trace::v1::{
+    span::SpanKind, status::StatusCode, ResourceSpans, ScopeSpans, Span as OtlpSpan, Status,
+};
+use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Resource describing this service, shared by all exported signals.
+pub(crate) fn resource(config: &OtlpConfig) -> Resource {
+    let mut attributes = vec![key_value("service.name", &config.service_name)];
+    let mut extra: Vec<_> = config
+        .resource_attributes
+        .iter()
+        .filter(|(key, _)| key.as_str() != "service.name")
+        .collect();
+    extra.sort();
+    attributes.extend(extra.into_iter().map(|(key, value)| key_value(key, value)));
+
+    Resource {
+        attributes,
+        dropped_attributes_count: 0,
+    }
+}
+
+/// Builds a trace export request.
+pub(crate) fn traces_request(resource: &Resource, spans: &[Span]) -> ExportTraceServiceRequest {
+    ExportTraceServiceRequest {
+        resource_spans: vec![ResourceSpans {
+            resource: Some(resource.clone()),
+            scope_spans: vec![ScopeSpans {
+                scope: Some(scope()),
+                spans: spans.iter().map(span).collect(),
+                schema_url: String::new(),
+            }],
+            schema_url: String::new(),
+        }],
+    }
+}
+
+/// Builds a metrics export request.
+pub(crate) fn metrics_request(
+    resource: &Resource,
+    metrics: &[Metric],
+) -> ExportMetricsServiceRequest {
+    ExportMetricsServiceRequest {
+        resource_metrics: vec![ResourceMetrics {
+            resource: Some(resource.clone()),
+            scope_metrics: vec![ScopeMetrics {
+                scope: Some(scope()),
+                metrics: metrics.iter().map(gauge).collect(),
+                schema_url: String::new(),
+            }],
+            schema_url: String::new(),
+        }],
+    }
+}
+
+/// Builds a logs export request.
+pub(crate) fn logs_request(resource: &Resource, entries: &[LogEntry]) -> ExportLogsServiceRequest {
+    ExportLogsServiceRequest {
+        resource_logs: vec![ResourceLogs {
+            resource: Some(resource.clone()),
+            scope_logs: vec![ScopeLogs {
+                scope: Some(scope()),
+                log_records: entries.iter().map(log_record).collect(),
+                schema_url: String::new(),
+            }],
+            schema_url: String::new(),
+        }],
+    }
+}
+
+/// Converts a span.
+///
+/// Unfinished spans end now. Status "OK" and "ERROR" (any case) map to the
+/// OTLP status codes; any other status is left unset.
+pub(crate) fn span(span: &Span) -> OtlpSpan {
+    let code = match span.status.as_deref() {
+        Some(status) if status.eq_ignore_ascii_case("ok") => StatusCode::Ok,
+        Some(status) if status.eq_ignore_ascii_case("error") => StatusCode::Error,
+        _ => StatusCode::Unset,
+    };
+
+    OtlpSpan {
+        trace_id: trace_id(&span.trace_id),
+        span_id: span_id(&span.span_id),
+        trace_state: String::new(),
+        parent_span_id: span
+            .parent_span_id
+            .as_deref()
+            .map(span_id)
+            .unwrap_or_default(),
+        flags: 0,
+        name: span.name.clone(),
+        kind: SpanKind::Internal as i32,
+        start_time_unix_nano: unix_nanos(span.start_time),
+        end_time_unix_nano: unix_nanos(span.end_time.unwrap_or_else(SystemTime::now)),
+        attributes: attributes(&span.attributes),
+        dropped_attributes_count: 0,
+        events: Vec::new(),
+        dropped_events_count: 0,
+        links: Vec::new(),
+        dropped_links_count: 0,
+        status: Some(Status {
+            message: String::new(),
+            code: code as i32,
+        }),
+    }
+}
+
+/// Converts a metric data point into a single-point gauge.
+pub(crate) fn gauge(metric: &Metric) -> OtlpMetric {
+    let time_unix_nano = metric.timestamp.saturating_mul(1_000_000_000);
+
+    OtlpMetric {
+        name: metric.name.clone(),
+        description: String::new(),
+        unit: metric.unit.as_deref().map(ucum_unit).unwrap_or_default(),
+        metadata: Vec::new(),
+        data: Some(metric::Data::Gauge(Gauge {
+            data_points: vec![NumberDataPoint {
+                attributes: attributes(&metric.dimensions),
+                start_time_unix_nano: time_unix_nano,
+                time_unix_nano,
+                exemplars: Vec::new(),
+                flags: 0,
+                value: Some(number_data_point::Value::AsDouble(metric.value)),
+            }],
+        })),
+    }
+}
+
+/// Converts a log entry, keeping its trace context for correlation.
+pub(crate) fn log_record(entry: &LogEntry) -> LogRecord {
+    let mut attributes = attributes(&entry.labels);
+    if let Some(source) = &entry.source {
+        attributes.push(key_value("code.location", source));
+    }
+
+    LogRecord {
+        time_unix_nano: unix_nanos(entry.timestamp),
+        observed_time_unix_nano: unix_nanos(SystemTime::now()),
+        severity_number: severity(entry.level) as i32,
+        severity_text: entry.level.as_str().to_string(),
+        body: Some(string_value(&entry.message)),
+        attributes,
+        dropped_attributes_count: 0,
+        flags: 0,
+        trace_id: entry.trace_id.as_deref().map(trace_id).unwrap_or_default(),
+        span_id: entry.span_id.as_deref().map(span_id).unwrap_or_default(),
+    }
+}
+
+/// 16-byte OTLP trace ID for a trace identifier.
+///
+/// Hex IDs, including hyphenated UUIDs, are decoded as-is so they match IDs
+/// issued by other OpenTelemetry instrumentation. Other identifiers are
+/// hashed, so the same identifier always maps to the same trace.
+pub(crate) fn trace_id(id: &str) -> Vec<u8> {
+    export::id_bytes(id, 16)
+}
+
+/// 8-byte OTLP span ID for a span identifier.
+///
+/// Longer hex IDs, such as UUIDs, are truncated to their first 8 bytes.
+pub(crate) fn span_id(id: &str) -> Vec<u8> {
+    export::id_bytes(id, 8)
+}
+
+fn severity(level: LogLevel) -> SeverityNumber {
+    match level {
+        LogLevel::Trace => SeverityNumber::Trace,
+        LogLevel::Debug => SeverityNumber::Debug,
+        LogLevel::Info => SeverityNumber::Info,
+        LogLevel::Warn => SeverityNumber::Warn,
+        LogLevel::Error => SeverityNumber::Error,
+        LogLevel::Fatal => SeverityNumber::Fatal,
+    }
+}
+
+/// Maps the CloudWatch-style units used by [`Metric`] to UCUM units.
+fn ucum_unit(unit: &str) -> String {
+    match unit {
+        "Count" => "1",
+        "Seconds" => "s",
+        "Milliseconds" => "ms",
+        "Microseconds" => "us",
+        "Bytes" => "By",
+        "Kilobytes" => "kBy",
+        "Megabytes" => "MBy",
+        "Percent" => "%",
+        other => other,
+    }
+    .to_string()
+}
+
+fn scope() -> InstrumentationScope {
+    InstrumentationScope {
+        name: env!("CARGO_PKG_NAME").to_string(),
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        attributes: Vec::new(),
+        dropped_attributes_count: 0,
+    }
+}
+
+/// Attributes in key order, so identical inputs encode identically.
+fn attributes(map: &HashMap<String, String>) -> Vec<KeyValue> {
+    let mut entries: Vec<_> = map.iter().collect();
+    entries.sort();
+    entries
+        .into_iter()
+        .map(|(key, value)| key_value(key, value))
+        .collect()
+}
+
+fn key_value(key: &str, value: &str) -> KeyValue {
+    KeyValue {
+        key: key.to_string(),
+        value: Some(string_value(value)),
+    }
+}
+
+fn string_value(value: &str) -> AnyValue {
+    AnyValue {
+        value: Some(any_value::Value::StringValue(value.to_string())),
+    }
+}
+
+fn unix_nanos(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|elapsed| u64::try_from(elapsed.as_nanos()).unwrap_or(u64::MAX))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_span_conversion() {
+        let parent = Span::new("scan", "0af7651916cd43dd8448eb211c80319c");
+        let child = Span::new("toxicity", parent.trace_id.clone())
+            .with_parent(parent.span_id.clone())
+            .with_attribute("scanner", "toxicity")
+            .end_with_status("ERROR");
+
+        let converted = span(&child);
+        assert_eq!(converted.trace_id, trace_id(&parent.trace_id));
+        assert_eq!(converted.parent_span_id, span_id(&parent.span_id));
+        assert_eq!(converted.status.unwrap().code, StatusCode::Error as i32);
+        assert!(converted.end_time_unix_nano >= converted.start_time_unix_nano);
+        assert_eq!(converted.attributes[0].key, "scanner");
+
+        assert!(span(&parent).parent_span_id.is_empty());
+        assert_eq!(span(&parent).status.unwrap().code, StatusCode::Unset as i32);
+    }
+
+    #[test]
+    fn test_metric_and_log_conversion() {
+        let metric = gauge(&Metric::new("scans", 3.0).with_unit("Count"));
+        assert_eq!(metric.unit, "1");
+        let Some(metric::Data::Gauge(data)) = metric.data else {
+            panic!("expected gauge");
+        };
+        assert_eq!(
+            data.data_points[0].value,
+            Some(number_data_point::Value::AsDouble(3.0))
+        );
+
+        let record =
+            log_record(&LogEntry::new(LogLevel::Warn, "blocked").with_trace_id("execution-1"));
+        assert_eq!(record.severity_number, SeverityNumber::Warn as i32);
+        assert_eq!(record.severity_text, "WARN");
+        assert_eq!(record.trace_id, trace_id("execution-1"));
+        assert!(record.span_id.is_empty());
+    }
+
+    #[test]
+    fn test_resource_attributes() {
+        let mut config = OtlpConfig {
+            service_name: "shield-api".to_string(),
+            ..OtlpConfig::default()
+        };
+        config
+            .resource_attributes
+            .insert("deployment.environment".to_string(), "prod".to_string());
+
+        let resource = resource(&config);
+        let keys: Vec<_> = resource
+            .attributes
+            .iter()
+            .map(|kv| kv.key.as_str())
+            .collect();
+        assert_eq!(keys, ["service.name", "deployment.environment"]);
+    }
+}
@@ -0,0 +1,83 @@
+//! OpenTelemetry (OTLP) integrations for LLM Shield.
+//!
+//! This crate provides vendor-neutral implementations of the observability
+//! traits defined in `llm-shield-cloud`, exporting to any OTLP collector
+//! (OpenTelemetry Collector, Grafana Alloy, Jaeger, Honeycomb, Datadog, ...):
+//!
+//! - **Tracing**: `CloudTracer` via [`OtlpTracer`]
+//! - **Metrics**: `CloudMetrics` via [`OtlpMetrics`]
+//! - **Logging**: `CloudLogger` via [`OtlpLogger`]
+//!
+//! # Features
+//!
+//! - Protobuf over gRPC or HTTP ([`OtlpProtocol`])
+//! - Background batching by size and interval, with a bounded queue
+//! - Retry with exponential backoff for failures the OTLP specification
+//!   marks as retryable (e.g. `UNAVAILABLE`, HTTP 429/503)
+//! - Custom headers for collector authentication
+//!
+//! # Usage
+//!
+//! ```no_run
+//! use llm_shield_cloud::{CloudLogger, CloudTracer, LogLevel, OtlpConfig, OtlpProtocol};
+//! use llm_shield_cloud_otlp::{OtlpLogger, OtlpTracer};
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!     let config = OtlpConfig {
+//!         endpoint: "http://localhost:4318".to_string(),
+//!         protocol: OtlpProtocol::HttpProtobuf,
+//!         ..OtlpConfig::default()
+//!     };
+//!
+//!     let tracer = OtlpTracer::new(&config)?;
+//!     let logger = OtlpLogger::new(&config)?;
+//!
+//!     let span = tracer.start_span("scan_prompt");
+//!     logger.log("Scanning prompt", LogLevel::Info).await?;
+//!     tracer.end_span(span.end_with_status("OK")).await?;
+//!
+//!     // Wait for delivery before exiting
+//!     tracer.flush().await?;
+//!     logger.flush().await?;
+//!     Ok(())
+//! }
+//! ```
+//!
+//! # Configuration
+//!
+//! Select the OTLP provider via `CloudConfig`:
+//!
+//! ```yaml
+//! cloud:
+//!   provider: otlp
+//!   otlp:
+//!     endpoint: http://otel-collector:4317
+//!     protocol: grpc            # or http/protobuf
+//!     service_name: llm-shield
+//!     headers:
+//!       x-api-key: ...
+//!     batch:
+//!       max_batch_size: 512
+//!       max_queue_size: 2048
+//!       flush_interval_ms: 5000
+//!     retry:
+//!       max_retries: 3
+//!       initial_backoff_ms: 100
+//!       max_backoff_ms: 5000
+//! ```
+
+#![warn(missing_docs)]
+
+mod convert;
+pub mod observability;
+mod transport;
+
+// Re-export main types
+pub use observability::{OtlpLogger, OtlpMetrics, OtlpTracer};
+
+// Re-export cloud abstractions for convenience
+pub use llm_shield_cloud::{
+    BatchConfig, CloudError, CloudLogger, CloudMetrics, CloudTracer, OtlpBatchConfig, OtlpConfig,
+    OtlpProtocol, OtlpRetryConfig, Result, RetryConfig,
+};
@@ -0,0 +1,507 @@
+//! OTLP implementations of `CloudTracer`, `CloudMetrics` and `CloudLogger`.
+//!
+//! Each exporter queues items and sends them to the collector in batches from
+//! a background task (see [`BatchConfig`](llm_shield_cloud::BatchConfig)),
+//! so recording never waits on the network. Use `flush` to wait for delivery,
+//! e.g. before shutdown.
+
+use crate::convert;
+use crate::transport::{ExportRequest, Transport};
+use llm_shield_cloud::export::{BatchExport, BatchQueue};
+use llm_shield_cloud::{
+    async_trait, CloudError, CloudLogger, CloudMetrics, CloudTracer, LogEntry, LogLevel, Metric,
+    OtlpConfig, Result, Span,
+};
+use opentelemetry_proto::tonic::resource::v1::Resource;
+use std::time::SystemTime;
+
+/// Converts batches into export requests and sends them.
+struct Exporter {
+    transport: Transport,
+    resource: Resource,
+}
+
+impl Exporter {
+    fn new(config: &OtlpConfig) -> Result<Self> {
+        Ok(Self {
+            transport: Transport::new(config)?,
+            resource: convert::resource(config),
+        })
+    }
+}
+
+#[async_trait]
+impl BatchExport<Span> for Exporter {
+    async fn export(&self, batch: Vec<Span>) -> Result<()> {
+        let request = convert::traces_request(&self.resource, &batch);
+        self.transport.export(ExportRequest::Traces(request)).await
+    }
+}
+
+#[async_trait]
+impl BatchExport<Metric> for Exporter {
+    async fn export(&self, batch: Vec<Metric>) -> Result<()> {
+        let request = convert::metrics_request(&self.resource, &batch);
+        self.transport.export(ExportRequest::Metrics(request)).await
+    }
+}
+
+#[async_trait]
+impl BatchExport<LogEntry> for Exporter {
+    async fn export(&self, batch: Vec<LogEntry>) -> Result<()> {
+        let request = convert::logs_request(&self.resource, &batch);
+        self.transport.export(ExportRequest::Logs(request)).await
+    }
+}
+
+/// OTLP implementation of `CloudTracer`.
+///
+/// Span and trace IDs are mapped to OTLP IDs as follows: hex IDs, including
+/// the UUIDs generated by [`CloudTracer::start_span`], are used as-is, while
+/// other identifiers are hashed consistently, so parent links are preserved.
+///
+/// # Example
+///
+/// ```no_run
+/// use llm_shield_cloud::{CloudTracer, OtlpConfig};
+/// use llm_shield_cloud_otlp::OtlpTracer;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let tracer = OtlpTracer::new(&OtlpConfig::default())?;
+///
+///     let span = tracer.start_span("scan");
+///     let child = tracer.start_child_span("toxicity", &span);
+///     tracer.end_span(child.end_with_status("OK")).await?;
+///     tracer.end_span(span.end_with_status("OK")).await?;
+///
+///     tracer.flush().await?;
+///     Ok(())
+/// }
+/// ```
+pub struct OtlpTracer {
+    queue: BatchQueue<Span>,
+}
+
+impl OtlpTracer {
+    /// Creates a tracer exporting to the collector in `config`.
+    ///
+    /// Must be called within a Tokio runtime.
+    pub fn new(config: &OtlpConfig) -> Result<Self> {
+        let exporter = Exporter::new(config)?;
+        Ok(Self {
+            queue: BatchQueue::spawn(&config.batch, CloudError::TraceExport, exporter),
+        })
+    }
+
+    /// Exports all queued spans.
+    pub async fn flush(&self) -> Result<()> {
+        self.queue.flush().await
+    }
+}
+
+#[async_trait]
+impl CloudTracer for OtlpTracer {
+    async fn end_span(&self, mut span: Span) -> Result<()> {
+        if span.end_time.is_none() {
+            span.end_time = Some(SystemTime::now());
+        }
+        self.queue.push(vec![span])
+    }
+
+    async fn export_spans(&self, spans: &[Span]) -> Result<()> {
+        self.queue.push(spans.to_vec())
+    }
+}
+
+/// OTLP implementation of `CloudMetrics`.
+///
+/// Each metric is exported as a gauge data point.
+pub struct OtlpMetrics {
+    queue: BatchQueue<Metric>,
+}
+
+impl OtlpMetrics {
+    /// Creates a metrics exporter for the collector in `config`.
+    ///
+    /// Must be called within a Tokio runtime.
+    pub fn new(config: &OtlpConfig) -> Result<Self> {
+        let exporter = Exporter::new(config)?;
+        Ok(Self {
+            queue: BatchQueue::spawn(&config.batch, CloudError::MetricsExport, exporter),
+        })
+    }
+
+    /// Exports all queued metrics.
+    pub async fn flush(&self) -> Result<()> {
+        self.queue.flush().await
+    }
+}
+
+#[async_trait]
+impl CloudMetrics for OtlpMetrics {
+    async fn export_metrics(&self, metrics: &[Metric]) -> Result<()> {
+        self.queue.push(metrics.to_vec())
+    }
+}
+
+/// OTLP implementation of `CloudLogger`.
+///
+/// Trace and span IDs of log entries are mapped like [`OtlpTracer`] maps
+/// them, so logs correlate with exported spans.
+pub struct OtlpLogger {
+    queue: BatchQueue<LogEntry>,
+}
+
+impl OtlpLogger {
+    /// Creates a logger exporting to the collector in `config`.
+    ///
+    /// Must be called within a Tokio runtime.
+    pub fn new(config: &OtlpConfig) -> Result<Self> {
+        let exporter = Exporter::new(config)?;
+        Ok(Self {
+            queue: BatchQueue::spawn(&config.batch, CloudError::LogExport, exporter),
+        })
+    }
+
+    /// Exports all queued log entries.
+    pub async fn flush(&self) -> Result<()> {
+        self.queue.flush().await
+    }
+}
+
+#[async_trait]
+impl CloudLogger for OtlpLogger {
+    async fn log(&self, message: &str, level: LogLevel) -> Result<()> {
+        self.log_structured(&LogEntry::new(level, message)).await
+    }
+
+    async fn log_structured(&self, entry: &LogEntry) -> Result<()> {
+        self.queue.push(vec![entry.clone()])
+    }
+
+    async fn log_batch(&self, entries: &[LogEntry]) -> Result<()> {
+        self.queue.push(entries.to_vec())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Bytes;
+    use axum::extract::{Path, State};
+    use axum::http::{HeaderMap, StatusCode};
+    use llm_shield_cloud::OtlpProtocol;
+    use opentelemetry_proto::tonic::collector::logs::v1::{
+        logs_service_server::{LogsService, LogsServiceServer},
+        ExportLogsServiceRequest, ExportLogsServiceResponse,
+    };
+    use opentelemetry_proto::tonic::collector::metrics::v1::ExportMetricsServiceRequest;
+    use opentelemetry_proto::tonic::collector::trace::v1::{
+        trace_service_server::{TraceService, TraceServiceServer},
+        ExportTraceServiceRequest, ExportTraceServiceResponse,
+    };
+    use prost::Message;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use tokio::net::TcpListener;
+    use tokio::sync::mpsc;
+
+    /// Request received by a stand-in collector
+    #[derive(Debug)]
+    struct Received {
+        path: String,
+        api_key: Option<String>,
+        body: Vec<u8>,
+    }
+
+    #[derive(Clone)]
+    struct HttpCollector {
+        requests: mpsc::UnboundedSender<Received>,
+        failures: Arc<AtomicUsize>,
+        attempts: Arc<AtomicUsize>,
+    }
+
+    async fn receive(
+        State(collector): State<HttpCollector>,
+        Path(signal): Path<String>,
+        headers: HeaderMap,
+        body: Bytes,
+    ) -> StatusCode {
+        collector.attempts.fetch_add(1, Ordering::SeqCst);
+        let failing = collector
+            .failures
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+            .is_ok();
+        if failing {
+            return StatusCode::SERVICE_UNAVAILABLE;
+        }
+
+        let _ = collector.requests.send(Received {
+            path: format!("/v1/{signal}"),
+            api_key: headers
+                .get("x-api-key")
+                .and_then(|v| v.to_str().ok())
+                .map(String::from),
+            body: body.to_vec(),
+        });
+        StatusCode::OK
+    }
+
+    /// Starts an OTLP/HTTP stand-in collector answering 503 `failures` times
+    async fn http_collector(
+        failures: usize,
+    ) -> (
+        OtlpConfig,
+        mpsc::UnboundedReceiver<Received>,
+        Arc<AtomicUsize>,
+    ) {
+        let (requests, received) = mpsc::unbounded_channel();
+        let collector = HttpCollector {
+            requests,
+            failures: Arc::new(AtomicUsize::new(failures)),
+            attempts: Arc::new(AtomicUsize::new(0)),
+        };
+        let attempts = collector.attempts.clone();
+
+        let app = axum::Router::new()
+            .route("/v1/:signal", axum::routing::post(receive))
+            .with_state(collector);
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let mut config = test_config(OtlpProtocol::HttpProtobuf, format!("http://{addr}"));
+        config
+            .headers
+            .insert("x-api-key".to_string(), "secret".to_string());
+        (config, received, attempts)
+    }
+
+    #[derive(Clone)]
+    struct GrpcCollector {
+        requests: mpsc::UnboundedSender<Received>,
+    }
+
+    impl GrpcCollector {
+        fn record<T>(&self, path: &str, request: tonic::Request<T>)
+        where
+            T: Message,
+        {
+            let _ = self.requests.send(Received {
+                path: path.to_string(),
+                api_key: request
+                    .metadata()
+                    .get("x-api-key")
+                    .and_then(|v| v.to_str().ok())
+                    .map(String::from),
+                body: request.into_inner().encode_to_vec(),
+            });
+        }
+    }
+
+    #[tonic::async_trait]
+    impl TraceService for GrpcCollector {
+        async fn export(
+            &self,
+            request: tonic::Request<ExportTraceServiceRequest>,
+        ) -> std::result::Result<tonic::Response<ExportTraceServiceResponse>, tonic::Status>
+        {
+            self.record("traces", request);
+            Ok(tonic::Response::new(ExportTraceServiceResponse {
+                partial_success: None,
+            }))
+        }
+    }
+
+    #[tonic::async_trait]
+    impl LogsService for GrpcCollector {
+        async fn export(
+            &self,
+            request: tonic::Request<ExportLogsServiceRequest>,
+        ) -> std::result::Result<tonic::Response<ExportLogsServiceResponse>, tonic::Status>
+        {
+            self.record("logs", request);
+            Ok(tonic::Response::new(ExportLogsServiceResponse {
+                partial_success: None,
+            }))
+        }
+    }
+
+    /// Starts an OTLP/gRPC stand-in collector for traces and logs
+    async fn grpc_collector() -> (OtlpConfig, mpsc::UnboundedReceiver<Received>) {
+        let (requests, received) = mpsc::unbounded_channel();
+        let collector = GrpcCollector { requests };
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            tonic::transport::Server::builder()
+                .add_service(TraceServiceServer::new(collector.clone()))
+                .add_service(LogsServiceServer::new(collector))
+                .serve_with_incoming(tokio_stream::wrappers::TcpListenerStream::new(listener))
+                .await
+                .unwrap();
+        });
+
+        let mut config = test_config(OtlpProtocol::Grpc, format!("http://{addr}"));
+        config
+            .headers
+            .insert("X-Api-Key".to_string(), "secret".to_string());
+        (config, received)
+    }
+
+    fn test_config(protocol: OtlpProtocol, endpoint: String) -> OtlpConfig {
+        let mut config = OtlpConfig {
+            endpoint,
+            protocol,
+            service_name: "shield-test".to_string(),
+            ..OtlpConfig::default()
+        };
+        config.retry.initial_backoff_ms = 10;
+        config
+    }
+
+    #[tokio::test]
+    async fn test_tracer_over_http() {
+        let (config, mut received, _) = http_collector(0).await;
+        let tracer = OtlpTracer::new(&config).unwrap();
+
+        let root = tracer.start_span("scan");
+        let child = tracer.start_child_span("toxicity", &root);
+        tracer
+            .export_spans(&[
+                root.clone().end_with_status("OK"),
+                child.end_with_status("OK"),
+            ])
+            .await
+            .unwrap();
+        tracer.flush().await.unwrap();
+
+        let request = received.recv().await.unwrap();
+        assert_eq!(request.path, "/v1/traces");
+        assert_eq!(request.api_key.as_deref(), Some("secret"));
+
+        let decoded = ExportTraceServiceRequest::decode(request.body.as_slice()).unwrap();
+        let resource_spans = &decoded.resource_spans[0];
+        let service = &resource_spans.resource.as_ref().unwrap().attributes[0];
+        assert_eq!(service.key, "service.name");
+
+        let spans = &resource_spans.scope_spans[0].spans;
+        assert_eq!(spans.len(), 2);
+        assert_eq!(spans[1].trace_id, spans[0].trace_id);
+        assert_eq!(spans[1].parent_span_id, spans[0].span_id);
+        assert_eq!(spans[0].trace_id, convert::trace_id(&root.trace_id));
+    }
+
+    #[tokio::test]
+    async fn test_metrics_batching_over_http() {
+        let (mut config, mut received, _) = http_collector(0).await;
+        config.batch.max_batch_size = 2;
+        let metrics = OtlpMetrics::new(&config).unwrap();
+
+        let batch: Vec<_> = (0..5)
+            .map(|i| Metric::new("scans", f64::from(i)).with_dimension("scanner", "toxicity"))
+            .collect();
+        metrics.export_metrics(&batch).await.unwrap();
+        metrics.flush().await.unwrap();
+
+        let mut sizes = Vec::new();
+        while let Ok(request) = received.try_recv() {
+            assert_eq!(request.path, "/v1/metrics");
+            let decoded = ExportMetricsServiceRequest::decode(request.body.as_slice()).unwrap();
+            sizes.push(decoded.resource_metrics[0].scope_metrics[0].metrics.len());
+        }
+        assert_eq!(sizes, vec![2, 2, 1]);
+    }
+
+    #[tokio::test]
+    async fn test_http_export_retries_unavailable() {
+        let (config, mut received, attempts) = http_collector(2).await;
+        let logger = OtlpLogger::new(&config).unwrap();
+
+        logger.log("blocked prompt", LogLevel::Warn).await.unwrap();
+        logger.flush().await.unwrap();
+
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+        assert_eq!(received.recv().await.unwrap().path, "/v1/logs");
+    }
+
+    #[tokio::test]
+    async fn test_http_export_gives_up_after_max_retries() {
+        let (mut config, _received, attempts) = http_collector(10).await;
+        config.retry.max_retries = 1;
+        let tracer = OtlpTracer::new(&config).unwrap();
+
+        tracer.end_span(tracer.start_span("scan")).await.unwrap();
+        let result = tracer.flush().await;
+
+        assert!(matches!(result, Err(CloudError::TraceExport(_))));
+        assert_eq!(attempts.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_tracer_and_logger_over_grpc() {
+        let (config, mut received) = grpc_collector().await;
+        let tracer = OtlpTracer::new(&config).unwrap();
+        let logger = OtlpLogger::new(&config).unwrap();
+
+        let span = tracer.start_span("scan");
+        logger
+            .log_structured(
+                &LogEntry::new(LogLevel::Error, "scanner failed")
+                    .with_trace_id(span.trace_id.clone())
+                    .with_span_id(span.span_id.clone()),
+            )
+            .await
+            .unwrap();
+        tracer
+            .end_span(span.end_with_status("ERROR"))
+            .await
+            .unwrap();
+        tracer.flush().await.unwrap();
+        logger.flush().await.unwrap();
+
+        let mut traces = None;
+        let mut logs = None;
+        for _ in 0..2 {
+            let request = received.recv().await.unwrap();
+            assert_eq!(request.api_key.as_deref(), Some("secret"));
+            match request.path.as_str() {
+                "traces" => {
+                    traces =
+                        Some(ExportTraceServiceRequest::decode(request.body.as_slice()).unwrap())
+                }
+                _ => {
+                    logs = Some(ExportLogsServiceRequest::decode(request.body.as_slice()).unwrap())
+                }
+            }
+        }
+
+        let span = &traces.unwrap().resource_spans[0].scope_spans[0].spans[0];
+        let record = &logs.unwrap().resource_logs[0].scope_logs[0].log_records[0];
+        assert_eq!(record.trace_id, span.trace_id);
+        assert_eq!(record.span_id, span.span_id);
+        assert_eq!(record.severity_text, "ERROR");
+    }
+
+    #[tokio::test]
+    async fn test_grpc_connection_failure_is_reported() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        drop(listener);
+
+        let mut config = test_config(OtlpProtocol::Grpc, format!("http://{addr}"));
+        config.retry.max_retries = 0;
+        let metrics = OtlpMetrics::new(&config).unwrap();
+
+        metrics
+            .export_metric(&Metric::new("scans", 1.0))
+            .await
+            .unwrap();
+        assert!(matches!(
+            metrics.flush().await,
+            Err(CloudError::MetricsExport(_))
+        ));
+    }
+}
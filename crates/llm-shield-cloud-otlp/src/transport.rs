@@ -0,0 +1,297 @@
+//! OTLP transports (gRPC and HTTP/protobuf) with retry.
+
+use llm_shield_cloud::export::{self, AttemptError};
+use llm_shield_cloud::{CloudError, OtlpConfig, OtlpProtocol, Result, RetryConfig};
+use opentelemetry_proto::tonic::collector::logs::v1::{
+    logs_service_client::LogsServiceClient, ExportLogsServiceRequest,
+};
+use opentelemetry_proto::tonic::collector::metrics::v1::{
+    metrics_service_client::MetricsServiceClient, ExportMetricsServiceRequest,
+};
+use opentelemetry_proto::tonic::collector::trace::v1::{
+    trace_service_client::TraceServiceClient, ExportTraceServiceRequest,
+};
+use prost::Message;
+use std::str::FromStr;
+use std::time::Duration;
+use tonic::metadata::{MetadataKey, MetadataMap, MetadataValue};
+use tonic::transport::{Channel, ClientTlsConfig, Endpoint};
+
+/// Telemetry signal carried by an export request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Signal {
+    Traces,
+    Metrics,
+    Logs,
+}
+
+impl Signal {
+    /// Path appended to the endpoint for HTTP/protobuf.
+    fn http_path(self) -> &'static str {
+        match self {
+            Signal::Traces => "/v1/traces",
+            Signal::Metrics => "/v1/metrics",
+            Signal::Logs => "/v1/logs",
+        }
+    }
+
+    /// Error for a failed export of this signal.
+    fn error(self, message: impl Into<String>) -> CloudError {
+        match self {
+            Signal::Traces => CloudError::TraceExport(message.into()),
+            Signal::Metrics => CloudError::MetricsExport(message.into()),
+            Signal::Logs => CloudError::LogExport(message.into()),
+        }
+    }
+}
+
+/// Export request of any signal.
+pub(crate) enum ExportRequest {
+    Traces(ExportTraceServiceRequest),
+    Metrics(ExportMetricsServiceRequest),
+    Logs(ExportLogsServiceRequest),
+}
+
+impl ExportRequest {
+    fn signal(&self) -> Signal {
+        match self {
+            ExportRequest::Traces(_) => Signal::Traces,
+            ExportRequest::Metrics(_) => Signal::Metrics,
+            ExportRequest::Logs(_) => Signal::Logs,
+        }
+    }
+
+    fn encode_to_vec(&self) -> Vec<u8> {
+        match self {
+            ExportRequest::Traces(request) => request.encode_to_vec(),
+            ExportRequest::Metrics(request) => request.encode_to_vec(),
+            ExportRequest::Logs(request) => request.encode_to_vec(),
+        }
+    }
+}
+
+#[derive(Clone)]
+enum Inner {
+    Grpc {
+        channel: Channel,
+        metadata: MetadataMap,
+    },
+    Http {
+        client: reqwest::Client,
+        endpoint: String,
+    },
+}
+
+/// Sends export requests to an OTLP collector.
+#[derive(Clone)]
+pub(crate) struct Transport {
+    inner: Inner,
+    retry: RetryConfig,
+}
+
+impl Transport {
+    /// Creates a transport for `config`.
+    ///
+    /// gRPC connects lazily on the first export. Must be called within a
+    /// Tokio runtime.
+    pub(crate) fn new(config: &OtlpConfig) -> Result<Self> {
+        let timeout = Duration::from_secs(config.timeout_seconds.max(1));
+
+        let inner = match config.protocol {
+            OtlpProtocol::Grpc => {
+                let mut endpoint = Endpoint::from_shared(config.endpoint.clone())
+                    .map_err(|e| invalid("endpoint", e))?
+                    .timeout(timeout)
+                    .connect_timeout(timeout);
+                if endpoint.uri().scheme_str() == Some("https") {
+                    endpoint = endpoint
+                        .tls_config(ClientTlsConfig::new().with_native_roots())
+                        .map_err(|e| invalid("endpoint", e))?;
+                }
+
+                let mut metadata = MetadataMap::new();
+                for (key, value) in &config.headers {
+                    let key = MetadataKey::from_str(&key.to_ascii_lowercase())
+                        .map_err(|e| invalid("headers", e))?;
+                    let value = MetadataValue::try_from(value.as_str())
+                        .map_err(|e| invalid("headers", e))?;
+                    metadata.insert(key, value);
+                }
+
+                Inner::Grpc {
+                    channel: endpoint.connect_lazy(),
+                    metadata,
+                }
+            }
+            OtlpProtocol::HttpProtobuf => {
+                let headers = reqwest::header::HeaderMap::try_from(&config.headers)
+                    .map_err(|e| invalid("headers", e))?;
+                let client = reqwest::Client::builder()
+                    .timeout(timeout)
+                    .default_headers(headers)
+                    .build()
+                    .map_err(|e| CloudError::ClientInit(e.to_string()))?;
+
+                Inner::Http {
+                    client,
+                    endpoint: config.endpoint.trim_end_matches('/').to_string(),
+                }
+            }
+        };
+
+        Ok(Self {
+            inner,
+            retry: config.retry.clone(),
+        })
+    }
+
+    /// Exports a request, retrying retryable failures with exponential backoff.
+    pub(crate) async fn export(&self, request: ExportRequest) -> Result<()> {
+        export::retry(&self.retry, || self.attempt(&request))
+            .await
+            .map_err(|message| request.signal().error(message))
+    }
+
+    async fn attempt(&self, request: &ExportRequest) -> std::result::Result<(), AttemptError> {
+        match &self.inner {
+            Inner::Grpc { channel, metadata } => {
+                let result = match request {
+                    ExportRequest::Traces(request) => TraceServiceClient::new(channel.clone())
+                        .export(grpc_request(request.clone(), metadata))
+                        .await
+                        .map(drop),
+                    ExportRequest::Metrics(request) => MetricsServiceClient::new(channel.clone())
+                        .export(grpc_request(request.clone(), metadata))
+                        .await
+                        .map(drop),
+                    ExportRequest::Logs(request) => LogsServiceClient::new(channel.clone())
+                        .export(grpc_request(request.clone(), metadata))
+                        .await
+                        .map(drop),
+                };
+                result.map_err(|status| {
+                    let message = format!("{}: {}", status.code(), status.message());
+                    if is_retryable_code(status.code()) {
+                        AttemptError::retryable(message)
+                    } else {
+                        AttemptError::permanent(message)
+                    }
+                })
+            }
+            Inner::Http { client, endpoint } => {
+                let url = format!("{}{}", endpoint, request.signal().http_path());
+                let response = client
+                    .post(&url)
+                    .header(reqwest::header::CONTENT_TYPE, "application/x-protobuf")
+                    .body(request.encode_to_vec())
+                    .send()
+                    .await
+                    .map_err(|e| {
+                        if e.is_connect() || e.is_timeout() {
+                            AttemptError::retryable(e.to_string())
+                        } else {
+                            AttemptError::permanent(e.to_string())
+                        }
+                    })?;
+
+                let status = response.status();
+                if status.is_success() {
+                    Ok(())
+                } else {
+                    Err(AttemptError::http_status(
+                        status.as_u16(),
+                        format!("{url} returned {status}"),
+                    ))
+                }
+            }
+        }
+    }
+}
+
+fn grpc_request<T>(message: T, metadata: &MetadataMap) -> tonic::Request<T> {
+    let mut request = tonic::Request::new(message);
+    *request.metadata_mut() = metadata.clone();
+    request
+}
+
+/// gRPC codes the OTLP specification marks as retryable.
+fn is_retryable_code(code: tonic::Code) -> bool {
+    matches!(
+        code,
+        tonic::Code::Cancelled
+            | tonic::Code::DeadlineExceeded
+            | tonic::Code::ResourceExhausted
+            | tonic::Code::Aborted
+            | tonic::Code::OutOfRange
+            | tonic::Code::Unavailable
+            | tonic::Code::DataLoss
+    )
+}
+
+fn invalid(key: &str, error: impl std::fmt::Display) -> CloudError {
+    CloudError::InvalidConfig {
+        key: format!("otlp.{key}"),
+        reason: error.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retryable_failures() {
+        assert!(is_retryable_code(tonic::Code::Unavailable));
+        assert!(!is_retryable_code(tonic::Code::InvalidArgument));
+        assert!(export::is_retryable_status(503));
+        assert!(!export::is_retryable_status(400));
+    }
+
+    #[tokio::test]
+    async fn test_invalid_config() {
+        let config = OtlpConfig {
+            endpoint: "not a uri".to_string(),
+            ..OtlpConfig::default()
+        };
+        assert!(matches!(
+            Transport::new(&config),
+            Err(CloudError::InvalidConfig { .. })
+        ));
+
+        let mut config = OtlpConfig::default();
+        config
+            .headers
+            .insert("bad header".to_string(), "x".to_string());
+        assert!(Transport::new(&config).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_https_endpoint_uses_tls() {
+        use tokio::io::AsyncReadExt;
+
+        for protocol in [OtlpProtocol::Grpc, OtlpProtocol::HttpProtobuf] {
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let config = OtlpConfig {
+                endpoint: format!("https://{}", listener.local_addr().unwrap()),
+                protocol,
+                timeout_seconds: 1,
+                retry: RetryConfig {
+                    max_retries: 0,
+                    ..RetryConfig::default()
+                },
+                ..OtlpConfig::default()
+            };
+            let transport = Transport::new(&config).unwrap();
+
+            // The collector sees the first byte of a TLS handshake record
+            let collector = tokio::spawn(async move {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                socket.read_u8().await.unwrap()
+            });
+            let request = ExportRequest::Traces(ExportTraceServiceRequest::default());
+            let _ = tokio::time::timeout(Duration::from_secs(5), transport.export(request)).await;
+
+            assert_eq!(collector.await.unwrap(), 0x16, "{:?}", protocol);
+        }
+    }
+}
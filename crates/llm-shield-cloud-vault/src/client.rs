@@ -0,0 +1,378 @@
+//! Vault HTTP client with authentication and token lease management.
+
+use llm_shield_cloud::{CloudError, Result, VaultAuthConfig, VaultConfig};
+use reqwest::{Method, StatusCode};
+use serde_json::{json, Value};
+use std::sync::{Arc, Weak};
+use std::time::Duration;
+use tokio::sync::RwLock;
+use tokio::task::JoinHandle;
+
+/// Delay before retrying a failed renewal.
+const RENEWAL_RETRY_DELAY: Duration = Duration::from_secs(5);
+
+/// Response of a Vault API request.
+pub(crate) struct Response {
+    pub(crate) status: StatusCode,
+    pub(crate) body: Value,
+}
+
+impl Response {
+    /// Returns the errors reported by Vault, or the status if there are none.
+    pub(crate) fn error_message(&self) -> String {
+        match self.body.get("errors").and_then(Value::as_array) {
+            Some(errors) if !errors.is_empty() => errors
+                .iter()
+                .map(|e| e.as_str().map_or_else(|| e.to_string(), str::to_string))
+                .collect::<Vec<_>>()
+                .join("; "),
+            _ => format!("Vault returned {}", self.status),
+        }
+    }
+}
+
+/// Resolved authentication method.
+enum Auth {
+    Token,
+    AppRole {
+        mount: String,
+        role_id: String,
+        secret_id: String,
+    },
+}
+
+/// Current client token and its lease.
+#[derive(Clone)]
+struct TokenState {
+    token: String,
+    /// `None` for tokens that never expire (e.g. root tokens).
+    lease: Option<Duration>,
+    renewable: bool,
+}
+
+impl TokenState {
+    /// Reads the token state from the `auth` block of a login or renewal.
+    fn from_auth(auth: &Value) -> Option<Self> {
+        Some(Self {
+            token: auth.get("client_token")?.as_str()?.to_string(),
+            lease: lease(auth.get("lease_duration")),
+            renewable: auth
+                .get("renewable")
+                .and_then(Value::as_bool)
+                .unwrap_or(false),
+        })
+    }
+}
+
+fn lease(seconds: Option<&Value>) -> Option<Duration> {
+    seconds
+        .and_then(Value::as_u64)
+        .filter(|&seconds| seconds > 0)
+        .map(Duration::from_secs)
+}
+
+/// Authenticated Vault API client.
+pub(crate) struct VaultClient {
+    http: reqwest::Client,
+    address: String,
+    namespace: Option<String>,
+    auth: Auth,
+    token: RwLock<TokenState>,
+}
+
+impl VaultClient {
+    /// Creates a client and authenticates with the configured method.
+    pub(crate) async fn connect(config: &VaultConfig) -> Result<Self> {
+        let http = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.timeout_seconds))
+            .build()
+            .map_err(|e| CloudError::ClientInit(e.to_string()))?;
+
+        let (auth, token) = match &config.auth {
+            VaultAuthConfig::Token { token } => {
+                let token = token
+                    .clone()
+                    .or_else(|| std::env::var("VAULT_TOKEN").ok())
+                    .ok_or_else(|| {
+                        CloudError::MissingConfig(
+                            "vault.auth.token (or VAULT_TOKEN environment variable)".to_string(),
+                        )
+                    })?;
+                (Auth::Token, token)
+            }
+            VaultAuthConfig::AppRole {
+                mount,
+                role_id,
+                secret_id,
+            } => {
+                let secret_id = secret_id
+                    .clone()
+                    .or_else(|| std::env::var("VAULT_SECRET_ID").ok())
+                    .ok_or_else(|| {
+                        CloudError::MissingConfig(
+                            "vault.auth.secret_id (or VAULT_SECRET_ID environment variable)"
+                                .to_string(),
+                        )
+                    })?;
+                let auth = Auth::AppRole {
+                    mount: mount.clone(),
+                    role_id: role_id.clone(),
+                    secret_id,
+                };
+                (auth, String::new())
+            }
+        };
+
+        let client = Self {
+            http,
+            address: config.address.trim_end_matches('/').to_string(),
+            namespace: config.namespace.clone(),
+            auth,
+            token: RwLock::new(TokenState {
+                token,
+                lease: None,
+                renewable: false,
+            }),
+        };
+
+        let state = match client.auth {
+            Auth::Token => client.lookup_self().await?,
+            Auth::AppRole { .. } => client.login().await?,
+        };
+        *client.token.write().await = state;
+
+        Ok(client)
+    }
+
+    /// Sends a request to `/v1/{path}` with the client token.
+    ///
+    /// With AppRole auth, a request rejected with 403 (e.g. because the token
+    /// expired or was revoked) is retried once after logging in again.
+    pub(crate) async fn request(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<&Value>,
+    ) -> std::result::Result<Response, String> {
+        let response = self.send(method.clone(), path, body).await?;
+        if response.status != StatusCode::FORBIDDEN || !matches!(self.auth, Auth::AppRole { .. }) {
+            return Ok(response);
+        }
+
+        tracing::debug!("Vault rejected token, logging in again");
+        let state = self.login().await.map_err(|e| e.to_string())?;
+        *self.token.write().await = state;
+        self.send(method, path, body).await
+    }
+
+    async fn send(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<&Value>,
+    ) -> std::result::Result<Response, String> {
+        let token = self.token.read().await.token.clone();
+        self.send_with_token(method, path, body, &token).await
+    }
+
+    async fn send_with_token(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<&Value>,
+        token: &str,
+    ) -> std::result::Result<Response, String> {
+        let mut request = self
+            .http
+            .request(method, format!("{}/v1/{}", self.address, path));
+        if !token.is_empty() {
+            request = request.header("X-Vault-Token", token);
+        }
+        if let Some(namespace) = &self.namespace {
+            request = request.header("X-Vault-Namespace", namespace);
+        }
+        if let Some(body) = body {
+            request = request.json(body);
+        }
+
+        let response = request.send().await.map_err(|e| e.to_string())?;
+        let status = response.status();
+        let text = response.text().await.map_err(|e| e.to_string())?;
+        let body = if text.is_empty() {
+            Value::Null
+        } else {
+            serde_json::from_str(&text).unwrap_or(Value::String(text))
+        };
+
+        Ok(Response { status, body })
+    }
+
+    /// Logs in with AppRole.
+    async fn login(&self) -> Result<TokenState> {
+        let Auth::AppRole {
+            mount,
+            role_id,
+            secret_id,
+        } = &self.auth
+        else {
+            return Err(CloudError::AuthFailed(
+                "token auth cannot log in again".to_string(),
+            ));
+        };
+
+        let body = json!({ "role_id": role_id, "secret_id": secret_id });
+        let response = self
+            .send_with_token(
+                Method::POST,
+                &format!("auth/{mount}/login"),
+                Some(&body),
+                "",
+            )
+            .await
+            .map_err(CloudError::Connection)?;
+        if !response.status.is_success() {
+            return Err(CloudError::AuthFailed(format!(
+                "AppRole login failed: {}",
+                response.error_message()
+            )));
+        }
+
+        let state = response
+            .body
+            .get("auth")
+            .and_then(TokenState::from_auth)
+            .ok_or_else(|| {
+                CloudError::AuthFailed("AppRole login returned no client token".to_string())
+            })?;
+        tracing::info!("Logged in to Vault with AppRole (lease: {:?})", state.lease);
+        Ok(state)
+    }
+
+    /// Looks up the lease of a static token, verifying it is valid.
+    async fn lookup_self(&self) -> Result<TokenState> {
+        let response = self
+            .send(Method::GET, "auth/token/lookup-self", None)
+            .await
+            .map_err(CloudError::Connection)?;
+        if !response.status.is_success() {
+            return Err(CloudError::AuthFailed(format!(
+                "token lookup failed: {}",
+                response.error_message()
+            )));
+        }
+
+        let data = &response.body["data"];
+        Ok(TokenState {
+            token: self.token.read().await.token.clone(),
+            lease: lease(data.get("ttl")),
+            renewable: data
+                .get("renewable")
+                .and_then(Value::as_bool)
+                .unwrap_or(false),
+        })
+    }
+
+    /// Renews the token lease, logging in again with AppRole if the token
+    /// cannot be renewed.
+    ///
+    /// Returns the new lease duration, or `None` if the token never expires
+    /// or can no longer be kept alive.
+    pub(crate) async fn renew(&self) -> Result<Option<Duration>> {
+        let current = self.token.read().await.clone();
+        if current.lease.is_none() {
+            return Ok(None);
+        }
+
+        let renewed = if current.renewable {
+            match self.renew_self(&current.token).await {
+                Ok(state) => Some(state),
+                Err(e) if matches!(self.auth, Auth::AppRole { .. }) => {
+                    tracing::warn!("Vault token renewal failed, logging in again: {}", e);
+                    None
+                }
+                Err(e) => return Err(e),
+            }
+        } else {
+            None
+        };
+
+        let state = match renewed {
+            // A shorter lease than before means the token reached its max TTL
+            Some(state) if state.lease >= current.lease => state,
+            renewed => match self.auth {
+                Auth::AppRole { .. } => self.login().await?,
+                Auth::Token => match renewed {
+                    Some(state) => state,
+                    None => {
+                        tracing::warn!("Vault token is not renewable and will expire");
+                        return Ok(None);
+                    }
+                },
+            },
+        };
+
+        let lease = state.lease;
+        *self.token.write().await = state;
+        Ok(lease)
+    }
+
+    async fn renew_self(&self, token: &str) -> Result<TokenState> {
+        let response = self
+            .send_with_token(
+                Method::POST,
+                "auth/token/renew-self",
+                Some(&json!({})),
+                token,
+            )
+            .await
+            .map_err(CloudError::Connection)?;
+        if !response.status.is_success() {
+            return Err(CloudError::AuthFailed(format!(
+                "token renewal failed: {}",
+                response.error_message()
+            )));
+        }
+
+        response
+            .body
+            .get("auth")
+            .and_then(TokenState::from_auth)
+            .ok_or_else(|| CloudError::AuthFailed("token renewal returned no token".to_string()))
+    }
+
+    /// Gets the current token lease.
+    pub(crate) async fn lease(&self) -> Option<Duration> {
+        self.token.read().await.lease
+    }
+}
+
+/// Spawns a task renewing the client token when two thirds of its lease
+/// have elapsed. The task stops once the client is dropped.
+pub(crate) fn spawn_renewal(client: &Arc<VaultClient>) -> JoinHandle<()> {
+    let client: Weak<VaultClient> = Arc::downgrade(client);
+
+    tokio::spawn(async move {
+        let Some(mut lease) = (match client.upgrade() {
+            Some(client) => client.lease().await,
+            None => None,
+        }) else {
+            return;
+        };
+
+        loop {
+            tokio::time::sleep(lease * 2 / 3).await;
+            let Some(client) = client.upgrade() else {
+                return;
+            };
+
+            match client.renew().await {
+                Ok(Some(next)) => lease = next,
+                Ok(None) => return,
+                Err(e) => {
+                    tracing::warn!("Vault token renewal failed: {}", e);
+                    lease = RENEWAL_RETRY_DELAY * 3 / 2;
+                }
+            }
+        }
+    })
+}
@@ -0,0 +1,75 @@
+//! HashiCorp Vault integration for LLM Shield.
+//!
+//! This crate provides an implementation of the `CloudSecretManager` trait
+//! defined in `llm-shield-cloud` for the Vault KV version 2 secrets engine,
+//! for self-hosted and on-prem deployments that keep secrets in Vault rather
+//! than a cloud provider:
+//!
+//! - **Secrets**: `CloudSecretManager` via [`VaultSecretManager`]
+//!
+//! # Features
+//!
+//! - KV v2 secrets with version history, metadata and check-and-set writes
+//! - Token and AppRole authentication
+//! - Background token lease renewal, logging in again with AppRole once a
+//!   token can no longer be renewed
+//! - Built-in secret caching with TTL
+//!
+//! # Usage
+//!
+//! ```no_run
+//! use llm_shield_cloud::{CloudSecretManager, SecretValue, VaultAuthConfig, VaultConfig};
+//! use llm_shield_cloud_vault::VaultSecretManager;
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!     let config = VaultConfig {
+//!         address: "https://vault.internal:8200".to_string(),
+//!         auth: VaultAuthConfig::AppRole {
+//!             mount: "approle".to_string(),
+//!             role_id: "llm-shield".to_string(),
+//!             secret_id: None, // read from VAULT_SECRET_ID
+//!         },
+//!         ..VaultConfig::default()
+//!     };
+//!
+//!     let secrets = VaultSecretManager::new(&config).await?;
+//!     let api_key = secrets.get_secret("llm-shield/openai-api-key").await?;
+//!     secrets
+//!         .rotate_secret("llm-shield/openai-api-key", &SecretValue::from_string("sk-new".to_string()))
+//!         .await?;
+//!     Ok(())
+//! }
+//! ```
+//!
+//! # Configuration
+//!
+//! Select the Vault provider via `CloudConfig`:
+//!
+//! ```yaml
+//! cloud:
+//!   provider: vault
+//!   vault:
+//!     address: https://vault.internal:8200
+//!     mount: secret
+//!     namespace: admin        # Vault Enterprise only
+//!     auth:
+//!       method: approle       # or token
+//!       role_id: llm-shield
+//!     cache_ttl_seconds: 300
+//!     renew_token: true
+//! ```
+
+#![warn(missing_docs)]
+
+mod client;
+pub mod secrets;
+
+// Re-export main types
+pub use secrets::VaultSecretManager;
+
+// Re-export cloud abstractions for convenience
+pub use llm_shield_cloud::{
+    CloudError, CloudSecretManager, Result, SecretMetadata, SecretValue, VaultAuthConfig,
+    VaultConfig,
+};
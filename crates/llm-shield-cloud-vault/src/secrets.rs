@@ -0,0 +1,886 @@
+//! HashiCorp Vault KV v2 integration.
+//!
+//! Provides implementation of `CloudSecretManager` trait for the Vault KV
+//! version 2 secrets engine.
+
+use crate::client::{spawn_renewal, Response, VaultClient};
+use llm_shield_cloud::{
+    async_trait, CloudError, CloudSecretManager, Result, SecretCache, SecretMetadata, SecretValue,
+    VaultConfig,
+};
+use reqwest::{Method, StatusCode};
+use serde_json::{json, Map, Value};
+use std::sync::Arc;
+use tokio::task::JoinHandle;
+
+/// Key under which non-JSON secret values are stored.
+const VALUE_KEY: &str = "value";
+
+/// HashiCorp Vault implementation of `CloudSecretManager`.
+///
+/// This implementation provides:
+/// - KV version 2 secrets with version history
+/// - Token and AppRole authentication
+/// - Background token lease renewal (re-login with AppRole when the token
+///   reaches its max TTL)
+/// - Built-in secret caching with TTL
+///
+/// # Secret values
+///
+/// A KV v2 secret holds a map of key-value pairs. A secret whose only key is
+/// `value` is returned as that string; any other secret is returned as its
+/// JSON-encoded map. Conversely, values that are JSON objects are written as
+/// maps and all other values under the `value` key.
+///
+/// # Example
+///
+/// ```no_run
+/// use llm_shield_cloud::{CloudSecretManager, VaultConfig};
+/// use llm_shield_cloud_vault::VaultSecretManager;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     // Token from VAULT_TOKEN, KV v2 engine mounted at "secret"
+///     let config = VaultConfig {
+///         address: "http://127.0.0.1:8200".to_string(),
+///         ..Default::default()
+///     };
+///     let manager = VaultSecretManager::new(&config).await?;
+///
+///     let secret = manager.get_secret("llm-shield/openai-api-key").await?;
+///     println!("Secret: {}", secret.as_string());
+///     Ok(())
+/// }
+/// ```
+pub struct VaultSecretManager {
+    client: Arc<VaultClient>,
+    mount: String,
+    cache: SecretCache,
+    renewal: Option<JoinHandle<()>>,
+}
+
+impl VaultSecretManager {
+    /// Creates a new Vault secret manager and authenticates.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CloudError::MissingConfig` if no token or AppRole secret ID is
+    /// configured, `CloudError::AuthFailed` if Vault rejects the credentials.
+    pub async fn new(config: &VaultConfig) -> Result<Self> {
+        let client = Arc::new(VaultClient::connect(config).await?);
+        let renewal = config.renew_token.then(|| spawn_renewal(&client));
+
+        tracing::info!(
+            "Initialized Vault secret manager for {} (mount: {})",
+            config.address,
+            config.mount
+        );
+
+        Ok(Self {
+            client,
+            mount: config.mount.trim_matches('/').to_string(),
+            cache: SecretCache::new(config.cache_ttl_seconds),
+            renewal,
+        })
+    }
+
+    /// Creates a new Vault secret manager from the standard Vault environment
+    /// variables (`VAULT_ADDR`, `VAULT_TOKEN`, `VAULT_NAMESPACE`).
+    ///
+    /// # Errors
+    ///
+    /// Returns error if `VAULT_TOKEN` is not set or the token is invalid.
+    pub async fn from_env() -> Result<Self> {
+        let mut config = VaultConfig::default();
+        if let Ok(address) = std::env::var("VAULT_ADDR") {
+            config.address = address;
+        }
+        config.namespace = std::env::var("VAULT_NAMESPACE").ok();
+
+        Self::new(&config).await
+    }
+
+    /// Gets the mount path of the KV engine.
+    pub fn mount(&self) -> &str {
+        &self.mount
+    }
+
+    /// Fetches a specific version of a secret (not cached).
+    ///
+    /// # Errors
+    ///
+    /// Returns `CloudError::SecretNotFound` if the version doesn't exist or
+    /// was deleted.
+    pub async fn get_secret_version(&self, name: &str, version: u64) -> Result<SecretValue> {
+        self.read(name, Some(version)).await
+    }
+
+    /// Renews the token lease now.
+    ///
+    /// Leases are renewed automatically unless `renew_token` is disabled.
+    pub async fn renew_token(&self) -> Result<()> {
+        self.client.renew().await.map(|_| ())
+    }
+
+    /// Clears the secret cache.
+    pub async fn clear_cache(&self) {
+        self.cache.clear().await;
+        tracing::debug!("Cleared Vault secret cache");
+    }
+
+    /// Gets the number of cached secrets.
+    pub async fn cache_size(&self) -> usize {
+        self.cache.len().await
+    }
+
+    fn path(&self, kind: &str, name: &str) -> String {
+        format!(
+            "{}/{}/{}",
+            self.mount,
+            kind,
+            encode_path(name.trim_matches('/'))
+        )
+    }
+
+    async fn read(&self, name: &str, version: Option<u64>) -> Result<SecretValue> {
+        let mut path = self.path("data", name);
+        if let Some(version) = version {
+            path.push_str(&format!("?version={version}"));
+        }
+
+        let response = self
+            .client
+            .request(Method::GET, &path, None)
+            .await
+            .map_err(|e| CloudError::secret_fetch(name, e))?;
+        match response.status {
+            StatusCode::OK => {}
+            StatusCode::NOT_FOUND => return Err(CloudError::SecretNotFound(name.to_string())),
+            _ => return Err(CloudError::secret_fetch(name, response.error_message())),
+        }
+
+        match response.body["data"]["data"].as_object() {
+            Some(data) => Ok(decode_value(data)),
+            // Deleted or destroyed version
+            None => Err(CloudError::SecretNotFound(name.to_string())),
+        }
+    }
+
+    /// Writes a new version, returning its version number.
+    ///
+    /// With `cas` set, the write only succeeds if the current version matches
+    /// (`0` meaning the secret must not exist yet).
+    async fn write(
+        &self,
+        name: &str,
+        value: &SecretValue,
+        cas: Option<u64>,
+    ) -> std::result::Result<u64, String> {
+        let data = encode_value(value)?;
+        let mut body = json!({ "data": data });
+        if let Some(cas) = cas {
+            body["options"] = json!({ "cas": cas });
+        }
+
+        let response = self
+            .client
+            .request(Method::POST, &self.path("data", name), Some(&body))
+            .await?;
+        if !response.status.is_success() {
+            return Err(response.error_message());
+        }
+
+        self.cache.invalidate(name).await;
+        Ok(response.body["data"]["version"]
+            .as_u64()
+            .unwrap_or_default())
+    }
+
+    async fn current_version(&self, name: &str) -> Result<Option<u64>> {
+        match self.read_metadata(name).await {
+            Ok(metadata) => Ok(metadata["current_version"].as_u64()),
+            Err(CloudError::SecretNotFound(_)) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn read_metadata(&self, name: &str) -> Result<Value> {
+        let response = self
+            .client
+            .request(Method::GET, &self.path("metadata", name), None)
+            .await
+            .map_err(|e| CloudError::secret_fetch(name, e))?;
+
+        match response.status {
+            StatusCode::OK => Ok(response.body["data"].clone()),
+            StatusCode::NOT_FOUND => Err(CloudError::SecretNotFound(name.to_string())),
+            _ => Err(CloudError::secret_fetch(name, response.error_message())),
+        }
+    }
+
+    async fn list_folder(&self, folder: &str) -> std::result::Result<Response, String> {
+        let path = if folder.is_empty() {
+            format!("{}/metadata?list=true", self.mount)
+        } else {
+            format!("{}?list=true", self.path("metadata", folder))
+        };
+        self.client.request(Method::GET, &path, None).await
+    }
+}
+
+impl Drop for VaultSecretManager {
+    fn drop(&mut self) {
+        if let Some(renewal) = self.renewal.take() {
+            renewal.abort();
+        }
+    }
+}
+
+#[async_trait]
+impl CloudSecretManager for VaultSecretManager {
+    async fn get_secret(&self, name: &str) -> Result<SecretValue> {
+        // Check cache first
+        if let Some(cached) = self.cache.get(name).await {
+            tracing::debug!("Cache hit for secret: {}", name);
+            return Ok(cached);
+        }
+
+        tracing::debug!("Fetching secret from Vault: {}", name);
+
+        let value = self.read(name, None).await?;
+        self.cache.set(name.to_string(), value.clone()).await;
+
+        tracing::info!("Successfully fetched secret: {}", name);
+
+        Ok(value)
+    }
+
+    /// Lists all secrets below the mount, descending into folders.
+    async fn list_secrets(&self) -> Result<Vec<String>> {
+        tracing::debug!("Listing secrets from Vault mount: {}", self.mount);
+
+        let mut names = Vec::new();
+        let mut folders = vec![String::new()];
+
+        while let Some(folder) = folders.pop() {
+            let response = self
+                .list_folder(&folder)
+                .await
+                .map_err(CloudError::SecretList)?;
+            match response.status {
+                StatusCode::OK => {}
+                StatusCode::NOT_FOUND => continue,
+                _ => return Err(CloudError::SecretList(response.error_message())),
+            }
+
+            let keys = response.body["data"]["keys"]
+                .as_array()
+                .cloned()
+                .unwrap_or_default();
+            for key in keys.iter().filter_map(Value::as_str) {
+                let name = format!("{folder}{key}");
+                if key.ends_with('/') {
+                    folders.push(name);
+                } else {
+                    names.push(name);
+                }
+            }
+        }
+
+        names.sort();
+        tracing::info!("Listed {} secrets", names.len());
+
+        Ok(names)
+    }
+
+    async fn create_secret(&self, name: &str, value: &SecretValue) -> Result<()> {
+        tracing::debug!("Creating secret in Vault: {}", name);
+
+        // cas=0 only writes if the secret doesn't exist yet
+        self.write(name, value, Some(0))
+            .await
+            .map_err(|e| CloudError::secret_create(name, e))?;
+
+        tracing::info!("Successfully created secret: {}", name);
+
+        Ok(())
+    }
+
+    async fn update_secret(&self, name: &str, value: &SecretValue) -> Result<()> {
+        tracing::debug!("Updating secret in Vault: {}", name);
+
+        let current = self
+            .current_version(name)
+            .await?
+            .ok_or_else(|| CloudError::secret_update(name, "secret does not exist"))?;
+
+        // Check-and-set against the version we saw, so concurrent updates
+        // are not silently lost
+        let version = self
+            .write(name, value, Some(current))
+            .await
+            .map_err(|e| CloudError::secret_update(name, e))?;
+
+        tracing::info!(
+            "Successfully updated secret: {} (version {})",
+            name,
+            version
+        );
+
+        Ok(())
+    }
+
+    /// Rotates a secret by writing a new version.
+    ///
+    /// Previous versions stay readable through
+    /// [`VaultSecretManager::get_secret_version`] according to the mount's
+    /// `max_versions` setting.
+    async fn rotate_secret(&self, name: &str, new_value: &SecretValue) -> Result<()> {
+        self.update_secret(name, new_value).await
+    }
+
+    /// Deletes a secret with all its versions and metadata.
+    async fn delete_secret(&self, name: &str) -> Result<()> {
+        tracing::debug!("Deleting secret from Vault: {}", name);
+
+        let response = self
+            .client
+            .request(Method::DELETE, &self.path("metadata", name), None)
+            .await
+            .map_err(|e| CloudError::secret_delete(name, e))?;
+        if !response.status.is_success() {
+            return Err(CloudError::secret_delete(name, response.error_message()));
+        }
+
+        // Invalidate cache
+        self.cache.invalidate(name).await;
+
+        tracing::info!("Successfully deleted secret: {}", name);
+
+        Ok(())
+    }
+
+    async fn get_secret_metadata(&self, name: &str) -> Result<SecretMetadata> {
+        tracing::debug!("Fetching secret metadata from Vault: {}", name);
+
+        let metadata = self.read_metadata(name).await?;
+
+        let created_at = parse_time(&metadata["created_time"]).unwrap_or_else(chrono::Utc::now);
+        let updated_at = parse_time(&metadata["updated_time"]).unwrap_or(created_at);
+
+        let tags = metadata["custom_metadata"]
+            .as_object()
+            .map(|custom| {
+                custom
+                    .iter()
+                    .filter_map(|(key, value)| Some((key.clone(), value.as_str()?.to_string())))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Ok(SecretMetadata {
+            name: name.to_string(),
+            created_at,
+            updated_at,
+            tags,
+            version: metadata["current_version"].as_u64().map(|v| v.to_string()),
+        })
+    }
+}
+
+/// Converts KV data into a secret value.
+fn decode_value(data: &Map<String, Value>) -> SecretValue {
+    if data.len() == 1 {
+        if let Some(value) = data.get(VALUE_KEY).and_then(Value::as_str) {
+            return SecretValue::from_string(value.to_string());
+        }
+    }
+    SecretValue::from_string(Value::Object(data.clone()).to_string())
+}
+
+/// Converts a secret value into KV data.
+fn encode_value(value: &SecretValue) -> std::result::Result<Value, String> {
+    let text = std::str::from_utf8(value.as_bytes())
+        .map_err(|_| "Vault KV secrets must be valid UTF-8".to_string())?;
+
+    match serde_json::from_str::<Value>(text) {
+        Ok(Value::Object(data)) => Ok(Value::Object(data)),
+        _ => Ok(json!({ VALUE_KEY: text })),
+    }
+}
+
+/// Percent-encodes a secret name for use in a URL path, keeping `/`.
+fn encode_path(name: &str) -> String {
+    let mut encoded = String::with_capacity(name.len());
+    for byte in name.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' => {
+                encoded.push(byte as char);
+            }
+            _ => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    encoded
+}
+
+fn parse_time(value: &Value) -> Option<chrono::DateTime<chrono::Utc>> {
+    chrono::DateTime::parse_from_rfc3339(value.as_str()?)
+        .ok()
+        .map(|time| time.with_timezone(&chrono::Utc))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::extract::{Path, Query, State};
+    use axum::http::HeaderMap;
+    use axum::Json;
+    use llm_shield_cloud::VaultAuthConfig;
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    /// Minimal in-memory stand-in for a Vault dev server
+    #[derive(Default)]
+    struct MockVault {
+        /// Secret versions; `None` marks a deleted version
+        secrets: Mutex<HashMap<String, Vec<Option<Value>>>>,
+        tokens: Mutex<Vec<String>>,
+        /// Lease of issued tokens in seconds
+        lease: u64,
+        renewable: bool,
+        logins: AtomicUsize,
+        renewals: AtomicUsize,
+    }
+
+    type Reply = (StatusCode, Json<Value>);
+
+    fn reply(status: StatusCode, body: Value) -> Reply {
+        (status, Json(body))
+    }
+
+    fn not_found() -> Reply {
+        reply(StatusCode::NOT_FOUND, json!({ "errors": [] }))
+    }
+
+    impl MockVault {
+        fn issue_token(&self) -> Value {
+            let n = self.logins.fetch_add(1, Ordering::SeqCst);
+            let token = format!("s.approle-{n}");
+            self.tokens.lock().unwrap().push(token.clone());
+            json!({ "client_token": token, "lease_duration": self.lease, "renewable": self.renewable })
+        }
+
+        fn kv_data(
+            &self,
+            method: &str,
+            name: &str,
+            query: &HashMap<String, String>,
+            body: Value,
+        ) -> Reply {
+            let mut secrets = self.secrets.lock().unwrap();
+            match method {
+                "GET" => {
+                    let Some(versions) = secrets.get(name) else {
+                        return not_found();
+                    };
+                    let version = query
+                        .get("version")
+                        .and_then(|v| v.parse::<usize>().ok())
+                        .unwrap_or(versions.len());
+                    match versions.get(version.wrapping_sub(1)).cloned().flatten() {
+                        Some(data) => reply(
+                            StatusCode::OK,
+                            json!({ "data": { "data": data, "metadata": { "version": version } } }),
+                        ),
+                        None => not_found(),
+                    }
+                }
+                "POST" => {
+                    let versions = secrets.entry(name.to_string()).or_default();
+                    if let Some(cas) = body["options"]["cas"].as_u64() {
+                        if cas as usize != versions.len() {
+                            return reply(
+                                StatusCode::BAD_REQUEST,
+                                json!({ "errors": ["check-and-set parameter did not match the current version"] }),
+                            );
+                        }
+                    }
+                    versions.push(Some(body["data"].clone()));
+                    reply(
+                        StatusCode::OK,
+                        json!({ "data": { "version": versions.len() } }),
+                    )
+                }
+                _ => reply(StatusCode::METHOD_NOT_ALLOWED, json!({ "errors": [] })),
+            }
+        }
+
+        fn kv_metadata(&self, method: &str, name: &str, query: &HashMap<String, String>) -> Reply {
+            let mut secrets = self.secrets.lock().unwrap();
+            if method == "DELETE" {
+                secrets.remove(name);
+                return reply(StatusCode::NO_CONTENT, Value::Null);
+            }
+
+            if query.get("list").map(String::as_str) == Some("true") {
+                let prefix = if name.is_empty() {
+                    String::new()
+                } else {
+                    format!("{name}/")
+                };
+                let mut keys: Vec<String> = secrets
+                    .keys()
+                    .filter_map(|key| key.strip_prefix(&prefix))
+                    .map(|rest| match rest.split_once('/') {
+                        Some((folder, _)) => format!("{folder}/"),
+                        None => rest.to_string(),
+                    })
+                    .collect();
+                keys.sort();
+                keys.dedup();
+                return if keys.is_empty() {
+                    not_found()
+                } else {
+                    reply(StatusCode::OK, json!({ "data": { "keys": keys } }))
+                };
+            }
+
+            match secrets.get(name) {
+                Some(versions) => reply(
+                    StatusCode::OK,
+                    json!({ "data": {
+                        "created_time": "2024-01-01T00:00:00Z",
+                        "updated_time": "2024-06-01T12:00:00Z",
+                        "current_version": versions.len(),
+                        "custom_metadata": { "owner": "security" },
+                    } }),
+                ),
+                None => not_found(),
+            }
+        }
+    }
+
+    async fn handle(
+        State(vault): State<Arc<MockVault>>,
+        method: axum::http::Method,
+        Path(path): Path<String>,
+        Query(query): Query<HashMap<String, String>>,
+        headers: HeaderMap,
+        body: axum::body::Bytes,
+    ) -> Reply {
+        let body: Value = serde_json::from_slice(&body).unwrap_or(Value::Null);
+
+        if path == "auth/approle/login" {
+            if body["role_id"] == "llm-shield" && body["secret_id"] == "s3cr3t" {
+                return reply(StatusCode::OK, json!({ "auth": vault.issue_token() }));
+            }
+            return reply(
+                StatusCode::BAD_REQUEST,
+                json!({ "errors": ["invalid role or secret ID"] }),
+            );
+        }
+
+        let token = headers
+            .get("X-Vault-Token")
+            .and_then(|t| t.to_str().ok())
+            .unwrap_or_default()
+            .to_string();
+        if !vault.tokens.lock().unwrap().contains(&token) {
+            return reply(
+                StatusCode::FORBIDDEN,
+                json!({ "errors": ["permission denied"] }),
+            );
+        }
+
+        let method = method.as_str();
+        match path.as_str() {
+            "auth/token/lookup-self" => reply(
+                StatusCode::OK,
+                json!({ "data": { "ttl": vault.lease, "renewable": vault.renewable } }),
+            ),
+            "auth/token/renew-self" => {
+                vault.renewals.fetch_add(1, Ordering::SeqCst);
+                reply(
+                    StatusCode::OK,
+                    json!({ "auth": { "client_token": token, "lease_duration": vault.lease, "renewable": vault.renewable } }),
+                )
+            }
+            "secret/metadata" => vault.kv_metadata(method, "", &query),
+            _ => {
+                if let Some(name) = path.strip_prefix("secret/data/") {
+                    vault.kv_data(method, name, &query, body)
+                } else if let Some(name) = path.strip_prefix("secret/metadata/") {
+                    vault.kv_metadata(method, name, &query)
+                } else {
+                    not_found()
+                }
+            }
+        }
+    }
+
+    async fn serve(vault: MockVault) -> (String, Arc<MockVault>) {
+        let vault = Arc::new(vault);
+        let app = axum::Router::new()
+            .route("/v1/*path", axum::routing::any(handle))
+            .with_state(vault.clone());
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        (address, vault)
+    }
+
+    fn token_config(address: &str, token: &str) -> VaultConfig {
+        VaultConfig {
+            address: address.to_string(),
+            auth: VaultAuthConfig::Token {
+                token: Some(token.to_string()),
+            },
+            ..Default::default()
+        }
+    }
+
+    fn approle_config(address: &str) -> VaultConfig {
+        VaultConfig {
+            address: address.to_string(),
+            auth: VaultAuthConfig::AppRole {
+                mount: "approle".to_string(),
+                role_id: "llm-shield".to_string(),
+                secret_id: Some("s3cr3t".to_string()),
+            },
+            ..Default::default()
+        }
+    }
+
+    fn root_vault() -> MockVault {
+        let vault = MockVault::default();
+        vault.tokens.lock().unwrap().push("root".to_string());
+        vault
+    }
+
+    fn secret(value: &str) -> SecretValue {
+        SecretValue::from_string(value.to_string())
+    }
+
+    #[test]
+    fn test_value_encoding() {
+        let data = encode_value(&secret("sk-123")).unwrap();
+        assert_eq!(data, json!({ "value": "sk-123" }));
+        assert_eq!(
+            decode_value(data.as_object().unwrap()).as_string(),
+            "sk-123"
+        );
+
+        let data = encode_value(&secret(r#"{"username":"app","password":"pw"}"#)).unwrap();
+        assert_eq!(data["password"], "pw");
+        let decoded: Value =
+            serde_json::from_str(decode_value(data.as_object().unwrap()).as_string()).unwrap();
+        assert_eq!(decoded, json!({ "username": "app", "password": "pw" }));
+
+        assert!(encode_value(&SecretValue::from_bytes(vec![0xff])).is_err());
+        assert_eq!(encode_path("llm shield/api-key"), "llm%20shield/api-key");
+    }
+
+    #[tokio::test]
+    async fn test_kv_v2_lifecycle() {
+        let (address, _vault) = serve(root_vault()).await;
+        let manager = VaultSecretManager::new(&token_config(&address, "root"))
+            .await
+            .unwrap();
+
+        manager
+            .create_secret("llm-shield/openai", &secret("v1"))
+            .await
+            .unwrap();
+        assert!(matches!(
+            manager
+                .create_secret("llm-shield/openai", &secret("v1"))
+                .await,
+            Err(CloudError::SecretCreate { .. })
+        ));
+        assert_eq!(
+            manager
+                .get_secret("llm-shield/openai")
+                .await
+                .unwrap()
+                .as_string(),
+            "v1"
+        );
+
+        // Rotation writes a new version and keeps the old one readable
+        manager
+            .rotate_secret("llm-shield/openai", &secret("v2"))
+            .await
+            .unwrap();
+        assert_eq!(
+            manager
+                .get_secret("llm-shield/openai")
+                .await
+                .unwrap()
+                .as_string(),
+            "v2"
+        );
+        assert_eq!(
+            manager
+                .get_secret_version("llm-shield/openai", 1)
+                .await
+                .unwrap()
+                .as_string(),
+            "v1"
+        );
+
+        let metadata = manager
+            .get_secret_metadata("llm-shield/openai")
+            .await
+            .unwrap();
+        assert_eq!(metadata.version.as_deref(), Some("2"));
+        assert_eq!(metadata.tags["owner"], "security");
+        assert!(metadata.updated_at > metadata.created_at);
+
+        assert!(matches!(
+            manager.update_secret("missing", &secret("v1")).await,
+            Err(CloudError::SecretUpdate { .. })
+        ));
+
+        manager
+            .create_secret("db", &secret(r#"{"password":"pw"}"#))
+            .await
+            .unwrap();
+        manager
+            .create_secret("llm-shield/nested/key", &secret("k"))
+            .await
+            .unwrap();
+        assert_eq!(
+            manager.list_secrets().await.unwrap(),
+            vec!["db", "llm-shield/nested/key", "llm-shield/openai"]
+        );
+
+        manager.delete_secret("llm-shield/openai").await.unwrap();
+        assert!(matches!(
+            manager.get_secret("llm-shield/openai").await,
+            Err(CloudError::SecretNotFound(_))
+        ));
+        assert!(matches!(
+            manager.get_secret_metadata("llm-shield/openai").await,
+            Err(CloudError::SecretNotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_secret_cache() {
+        let (address, vault) = serve(root_vault()).await;
+        let manager = VaultSecretManager::new(&token_config(&address, "root"))
+            .await
+            .unwrap();
+
+        manager
+            .create_secret("api-key", &secret("v1"))
+            .await
+            .unwrap();
+        assert_eq!(
+            manager.get_secret("api-key").await.unwrap().as_string(),
+            "v1"
+        );
+        assert_eq!(manager.cache_size().await, 1);
+
+        // Changes made outside this manager are served from cache until it expires
+        vault
+            .secrets
+            .lock()
+            .unwrap()
+            .get_mut("api-key")
+            .unwrap()
+            .push(Some(json!({ "value": "external" })));
+        assert_eq!(
+            manager.get_secret("api-key").await.unwrap().as_string(),
+            "v1"
+        );
+
+        // Writes through the manager invalidate the cache
+        manager
+            .update_secret("api-key", &secret("v3"))
+            .await
+            .unwrap();
+        assert_eq!(
+            manager.get_secret("api-key").await.unwrap().as_string(),
+            "v3"
+        );
+
+        manager.clear_cache().await;
+        assert_eq!(manager.cache_size().await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_token_auth() {
+        let (address, _vault) = serve(root_vault()).await;
+
+        assert!(matches!(
+            VaultSecretManager::new(&token_config(&address, "wrong")).await,
+            Err(CloudError::AuthFailed(_))
+        ));
+
+        let mut config = token_config(&address, "root");
+        config.auth = VaultAuthConfig::AppRole {
+            mount: "approle".to_string(),
+            role_id: "llm-shield".to_string(),
+            secret_id: Some("wrong".to_string()),
+        };
+        assert!(matches!(
+            VaultSecretManager::new(&config).await,
+            Err(CloudError::AuthFailed(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_token_lease_renewal() {
+        let vault = MockVault {
+            lease: 1,
+            renewable: true,
+            ..root_vault()
+        };
+        let (address, vault) = serve(vault).await;
+        let manager = VaultSecretManager::new(&token_config(&address, "root"))
+            .await
+            .unwrap();
+
+        tokio::time::sleep(Duration::from_millis(1500)).await;
+        assert!(vault.renewals.load(Ordering::SeqCst) >= 1);
+        assert_eq!(vault.logins.load(Ordering::SeqCst), 0);
+
+        manager.renew_token().await.unwrap();
+        drop(manager);
+    }
+
+    #[tokio::test]
+    async fn test_approle_login_and_relogin() {
+        // Non-renewable AppRole tokens are replaced by logging in again
+        let vault = MockVault {
+            lease: 1,
+            ..MockVault::default()
+        };
+        let (address, vault) = serve(vault).await;
+        let manager = VaultSecretManager::new(&approle_config(&address))
+            .await
+            .unwrap();
+        assert_eq!(vault.logins.load(Ordering::SeqCst), 1);
+
+        manager
+            .create_secret("api-key", &secret("v1"))
+            .await
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(1500)).await;
+        assert!(vault.logins.load(Ordering::SeqCst) >= 2);
+        assert_eq!(vault.renewals.load(Ordering::SeqCst), 0);
+
+        // A revoked token is replaced on the next request
+        vault.tokens.lock().unwrap().clear();
+        let logins = vault.logins.load(Ordering::SeqCst);
+        manager.clear_cache().await;
+        assert_eq!(
+            manager.get_secret("api-key").await.unwrap().as_string(),
+            "v1"
+        );
+        assert_eq!(vault.logins.load(Ordering::SeqCst), logins + 1);
+    }
+}
@@ -0,0 +1,121 @@
+//! Integration tests for the Vault KV v2 secret manager.
+//!
+//! These tests require:
+//! - A running Vault server, e.g. `vault server -dev -dev-root-token-id=root`
+//! - `VAULT_ADDR` and `VAULT_TOKEN` set
+//! - A KV v2 engine mounted at `secret/` (the default in dev mode)
+//!
+//! Run with: cargo test --test integration_secrets -- --ignored
+
+use llm_shield_cloud::{CloudError, CloudSecretManager, SecretValue};
+use llm_shield_cloud_vault::VaultSecretManager;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const TEST_SECRET_PREFIX: &str = "llm-shield-test";
+
+/// Helper to create a test secret name with unique ID
+fn test_secret_name(suffix: &str) -> String {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_nanos();
+    format!("{TEST_SECRET_PREFIX}/{suffix}-{nanos}")
+}
+
+#[tokio::test]
+#[ignore] // Requires a Vault server
+async fn test_create_get_and_delete_secret() {
+    let manager = VaultSecretManager::from_env()
+        .await
+        .expect("Failed to initialize VaultSecretManager");
+
+    let secret_name = test_secret_name("create-get");
+    let secret_value = SecretValue::from_string("test-secret-value".to_string());
+
+    manager
+        .create_secret(&secret_name, &secret_value)
+        .await
+        .expect("Failed to create secret");
+
+    let retrieved = manager
+        .get_secret(&secret_name)
+        .await
+        .expect("Failed to get secret");
+    assert_eq!(retrieved.as_string(), "test-secret-value");
+
+    // Creating again must not overwrite
+    assert!(manager
+        .create_secret(&secret_name, &secret_value)
+        .await
+        .is_err());
+
+    let names = manager
+        .list_secrets()
+        .await
+        .expect("Failed to list secrets");
+    assert!(names.contains(&secret_name));
+
+    manager
+        .delete_secret(&secret_name)
+        .await
+        .expect("Failed to delete secret");
+    manager.clear_cache().await;
+
+    assert!(matches!(
+        manager.get_secret(&secret_name).await,
+        Err(CloudError::SecretNotFound(_))
+    ));
+}
+
+#[tokio::test]
+#[ignore] // Requires a Vault server
+async fn test_rotate_secret_versions() {
+    let manager = VaultSecretManager::from_env()
+        .await
+        .expect("Failed to initialize VaultSecretManager");
+
+    let secret_name = test_secret_name("rotate");
+    manager
+        .create_secret(&secret_name, &SecretValue::from_string("v1".to_string()))
+        .await
+        .expect("Failed to create secret");
+    manager
+        .rotate_secret(&secret_name, &SecretValue::from_string("v2".to_string()))
+        .await
+        .expect("Failed to rotate secret");
+
+    let current = manager.get_secret(&secret_name).await.unwrap();
+    assert_eq!(current.as_string(), "v2");
+
+    let previous = manager.get_secret_version(&secret_name, 1).await.unwrap();
+    assert_eq!(previous.as_string(), "v1");
+
+    let metadata = manager.get_secret_metadata(&secret_name).await.unwrap();
+    assert_eq!(metadata.version.as_deref(), Some("2"));
+
+    // Cleanup
+    let _ = manager.delete_secret(&secret_name).await;
+}
+
+#[tokio::test]
+#[ignore] // Requires a Vault server
+async fn test_json_secret() {
+    let manager = VaultSecretManager::from_env()
+        .await
+        .expect("Failed to initialize VaultSecretManager");
+
+    let secret_name = test_secret_name("json");
+    let value = r#"{"password":"pw","username":"app"}"#;
+    manager
+        .create_secret(&secret_name, &SecretValue::from_string(value.to_string()))
+        .await
+        .expect("Failed to create secret");
+
+    let retrieved = manager.get_secret(&secret_name).await.unwrap();
+    let parsed: serde_json::Value = serde_json::from_str(retrieved.as_string()).unwrap();
+    assert_eq!(parsed["username"], "app");
+    assert_eq!(parsed["password"], "pw");
+
+    // Cleanup
+    let _ = manager.delete_secret(&secret_name).await;
+}
@@ -0,0 +1,371 @@
+//! Local caching of artifacts kept in cloud storage.
+//!
+//! Model registries, scanner pipelines and pattern packs can be published to
+//! a single bucket and referenced as `storage://<key>`, optionally pinned to
+//! a SHA-256 checksum with a `#sha256=<hex>` suffix:
+//!
+//! ```text
+//! storage://registry/models.json
+//! storage://packs/competitors.yaml#sha256=9f86d081884c7d65...
+//! ```
+//!
+//! [`ArtifactCache`] downloads such objects into a local directory. Pinned
+//! artifacts are served from the cache while its content matches the
+//! checksum; unpinned artifacts are revalidated against the object's `ETag`
+//! on every fetch. If storage is unreachable, a previously cached copy is
+//! used, so instances keep starting while the bucket is unavailable.
+
+use crate::error::{CloudError, Result};
+use crate::local::{hidden_sibling, relative_path, write_atomic};
+use crate::storage::CloudStorage;
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// URI scheme of artifacts kept in cloud storage.
+pub const STORAGE_SCHEME: &str = "storage://";
+
+/// Suffix of the sidecar file recording the `ETag` of a cached artifact.
+const ETAG_SUFFIX: &str = ".etag";
+
+/// Reference to an artifact in cloud storage.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArtifactRef {
+    /// Object key
+    pub key: String,
+
+    /// Expected SHA-256 checksum (lowercase hex)
+    pub checksum: Option<String>,
+}
+
+impl ArtifactRef {
+    /// Creates a reference to `key` without a pinned checksum.
+    pub fn new(key: impl Into<String>) -> Self {
+        Self {
+            key: key.into(),
+            checksum: None,
+        }
+    }
+
+    /// Pins the reference to a SHA-256 checksum.
+    #[must_use]
+    pub fn with_checksum(mut self, checksum: impl Into<String>) -> Self {
+        self.checksum = Some(normalize_checksum(&checksum.into()));
+        self
+    }
+
+    /// Parses a `storage://<key>[#sha256=<hex>]` URI.
+    ///
+    /// Returns `None` for anything that is not a `storage://` URI, such as
+    /// local paths.
+    #[must_use]
+    pub fn parse(uri: &str) -> Option<Self> {
+        let rest = uri.strip_prefix(STORAGE_SCHEME)?;
+        let (key, fragment) = match rest.split_once('#') {
+            Some((key, fragment)) => (key, Some(fragment)),
+            None => (rest, None),
+        };
+
+        let reference = Self::new(key);
+        match fragment.and_then(|f| f.strip_prefix("sha256=")) {
+            Some(checksum) => Some(reference.with_checksum(checksum)),
+            None => Some(reference),
+        }
+    }
+
+    /// Returns whether `uri` refers to an artifact in cloud storage.
+    #[must_use]
+    pub fn is_storage_uri(uri: &str) -> bool {
+        uri.starts_with(STORAGE_SCHEME)
+    }
+}
+
+impl fmt::Display for ArtifactRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{STORAGE_SCHEME}{}", self.key)?;
+        if let Some(checksum) = &self.checksum {
+            write!(f, "#sha256={checksum}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Computes the SHA-256 checksum of `data` as lowercase hex.
+#[must_use]
+pub fn sha256_hex(data: &[u8]) -> String {
+    hex::encode(Sha256::digest(data))
+}
+
+/// Verifies that `data` has the expected SHA-256 checksum.
+///
+/// The checksum may be upper- or lowercase and carry a `sha256:` prefix.
+pub fn verify_checksum(key: &str, data: &[u8], expected: &str) -> Result<()> {
+    let actual = sha256_hex(data);
+    if actual == normalize_checksum(expected) {
+        Ok(())
+    } else {
+        Err(CloudError::StorageRead {
+            key: key.to_string(),
+            error: format!("checksum mismatch: expected {expected}, got {actual}"),
+        })
+    }
+}
+
+fn normalize_checksum(checksum: &str) -> String {
+    let checksum = checksum.trim();
+    checksum
+        .strip_prefix("sha256:")
+        .unwrap_or(checksum)
+        .to_ascii_lowercase()
+}
+
+/// Downloads artifacts from cloud storage into a local cache directory.
+///
+/// Artifacts are stored below the cache directory under their object key.
+///
+/// # Example
+///
+/// ```no_run
+/// use llm_shield_cloud::{ArtifactCache, ArtifactRef, LocalStorage};
+/// use std::sync::Arc;
+///
+/// # async fn example() -> llm_shield_cloud::Result<()> {
+/// let storage = Arc::new(LocalStorage::new("/mnt/artifacts").await?);
+/// let cache = ArtifactCache::new(storage, "/var/cache/llm-shield");
+///
+/// let reference = ArtifactRef::parse("storage://packs/competitors.yaml").unwrap();
+/// let path = cache.fetch_ref(&reference).await?;
+/// println!("Cached at {}", path.display());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct ArtifactCache {
+    storage: Arc<dyn CloudStorage>,
+    dir: PathBuf,
+}
+
+impl ArtifactCache {
+    /// Creates a cache for artifacts of `storage` in `dir`.
+    pub fn new(storage: Arc<dyn CloudStorage>, dir: impl Into<PathBuf>) -> Self {
+        Self {
+            storage,
+            dir: dir.into(),
+        }
+    }
+
+    /// Gets the storage artifacts are fetched from.
+    #[must_use]
+    pub fn storage(&self) -> &Arc<dyn CloudStorage> {
+        &self.storage
+    }
+
+    /// Gets the cache directory.
+    #[must_use]
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Fetches a referenced artifact, returning its local path.
+    pub async fn fetch_ref(&self, reference: &ArtifactRef) -> Result<PathBuf> {
+        self.fetch(&reference.key, reference.checksum.as_deref())
+            .await
+    }
+
+    /// Fetches an artifact into the cache, returning its local path.
+    ///
+    /// With a `checksum`, a cached copy with matching content is used without
+    /// contacting storage and downloads are verified against it. Without one,
+    /// the cached copy is used while its `ETag` matches the object's.
+    pub async fn fetch(&self, key: &str, checksum: Option<&str>) -> Result<PathBuf> {
+        let path = self.cache_path(key)?;
+        let etag_path = hidden_sibling(&path, ETAG_SUFFIX);
+        let cached = tokio::fs::read(&path).await.ok();
+
+        if let (Some(data), Some(checksum)) = (&cached, checksum) {
+            if verify_checksum(key, data, checksum).is_ok() {
+                tracing::debug!("Artifact cache hit: {}", key);
+                return Ok(path);
+            }
+        }
+
+        let etag = match self.storage.get_object_metadata(key).await {
+            Ok(metadata) => metadata.etag,
+            Err(CloudError::StorageObjectNotFound(key)) => {
+                return Err(CloudError::StorageObjectNotFound(key));
+            }
+            // Fall back to an unpinned cached copy while storage is unavailable
+            Err(e) if cached.is_some() && checksum.is_none() => {
+                tracing::warn!("Using cached artifact {} (storage unavailable: {})", key, e);
+                return Ok(path);
+            }
+            Err(e) => return Err(e),
+        };
+
+        if cached.is_some() && checksum.is_none() && etag.is_some() {
+            let cached_etag = tokio::fs::read_to_string(&etag_path).await.ok();
+            if cached_etag == etag {
+                tracing::debug!("Artifact cache hit: {}", key);
+                return Ok(path);
+            }
+        }
+
+        tracing::info!("Downloading artifact: {}", key);
+        let data = self.storage.get_object(key).await?;
+        if let Some(checksum) = checksum {
+            verify_checksum(key, &data, checksum)?;
+        }
+
+        let write_error = |e: std::io::Error| CloudError::StorageRead {
+            key: key.to_string(),
+            error: format!("failed to cache artifact at {}: {e}", path.display()),
+        };
+        write_atomic(&path, &data, true, 0o644)
+            .await
+            .map_err(write_error)?;
+        match &etag {
+            Some(etag) => write_atomic(&etag_path, etag.as_bytes(), true, 0o644)
+                .await
+                .map_err(write_error)?,
+            None => {
+                let _ = tokio::fs::remove_file(&etag_path).await;
+            }
+        }
+
+        Ok(path)
+    }
+
+    /// Fetches an artifact and returns its content.
+    pub async fn read(&self, key: &str, checksum: Option<&str>) -> Result<Vec<u8>> {
+        let path = self.fetch(key, checksum).await?;
+        tokio::fs::read(&path)
+            .await
+            .map_err(|e| CloudError::StorageRead {
+                key: key.to_string(),
+                error: e.to_string(),
+            })
+    }
+
+    /// Fetches a referenced artifact and returns its content.
+    pub async fn read_ref(&self, reference: &ArtifactRef) -> Result<Vec<u8>> {
+        self.read(&reference.key, reference.checksum.as_deref())
+            .await
+    }
+
+    fn cache_path(&self, key: &str) -> Result<PathBuf> {
+        relative_path(key)
+            .map(|relative| self.dir.join(relative))
+            .ok_or_else(|| CloudError::InvalidConfig {
+                key: "artifact key".to_string(),
+                reason: format!("'{key}' is not a valid artifact key"),
+            })
+    }
+}
+
+impl fmt::Debug for ArtifactCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ArtifactCache")
+            .field("storage", &self.storage.provider_name())
+            .field("dir", &self.dir)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::local::LocalStorage;
+
+    #[test]
+    fn test_artifact_ref_parse() {
+        assert_eq!(
+            ArtifactRef::parse("storage://registry/models.json"),
+            Some(ArtifactRef::new("registry/models.json"))
+        );
+
+        let reference = ArtifactRef::parse("storage://packs/a.yaml#sha256=ABCD").unwrap();
+        assert_eq!(reference.key, "packs/a.yaml");
+        assert_eq!(reference.checksum.as_deref(), Some("abcd"));
+        assert_eq!(reference.to_string(), "storage://packs/a.yaml#sha256=abcd");
+
+        assert!(ArtifactRef::parse("models/registry.json").is_none());
+        assert!(!ArtifactRef::is_storage_uri("s3://bucket/key"));
+    }
+
+    #[test]
+    fn test_verify_checksum() {
+        let checksum = sha256_hex(b"model");
+        assert!(verify_checksum("m", b"model", &checksum).is_ok());
+        assert!(verify_checksum(
+            "m",
+            b"model",
+            &format!("sha256:{}", checksum.to_uppercase())
+        )
+        .is_ok());
+        assert!(matches!(
+            verify_checksum("m", b"tampered", &checksum),
+            Err(CloudError::StorageRead { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_revalidates_by_etag() {
+        let bucket = tempfile::tempdir().unwrap();
+        let cache_dir = tempfile::tempdir().unwrap();
+        let storage = Arc::new(LocalStorage::new(bucket.path()).await.unwrap());
+        let cache = ArtifactCache::new(storage.clone(), cache_dir.path());
+
+        storage.put_object("packs/a.yaml", b"one").await.unwrap();
+        let path = cache.fetch("packs/a.yaml", None).await.unwrap();
+        assert_eq!(path, cache_dir.path().join("packs").join("a.yaml"));
+        assert_eq!(tokio::fs::read(&path).await.unwrap(), b"one");
+
+        // A changed object is downloaded again
+        storage.put_object("packs/a.yaml", b"two").await.unwrap();
+        assert_eq!(cache.read("packs/a.yaml", None).await.unwrap(), b"two");
+
+        // Deleted objects are reported, not served from cache
+        storage.delete_object("packs/a.yaml").await.unwrap();
+        assert!(matches!(
+            cache.fetch("packs/a.yaml", None).await,
+            Err(CloudError::StorageObjectNotFound(_))
+        ));
+
+        assert!(matches!(
+            cache.fetch("../escape", None).await,
+            Err(CloudError::InvalidConfig { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_pinned_checksum() {
+        let bucket = tempfile::tempdir().unwrap();
+        let cache_dir = tempfile::tempdir().unwrap();
+        let storage = Arc::new(LocalStorage::new(bucket.path()).await.unwrap());
+        let cache = ArtifactCache::new(storage.clone(), cache_dir.path());
+
+        storage
+            .put_object("models/registry.json", b"{}")
+            .await
+            .unwrap();
+        let reference = ArtifactRef::new("models/registry.json").with_checksum(sha256_hex(b"{}"));
+        cache.fetch_ref(&reference).await.unwrap();
+
+        // Pinned artifacts are served from cache without contacting storage
+        storage.delete_object("models/registry.json").await.unwrap();
+        assert_eq!(cache.read_ref(&reference).await.unwrap(), b"{}");
+
+        // Downloads that don't match the checksum are rejected and not cached
+        storage
+            .put_object("models/other.json", b"tampered")
+            .await
+            .unwrap();
+        let reference = ArtifactRef::new("models/other.json").with_checksum(sha256_hex(b"{}"));
+        assert!(matches!(
+            cache.fetch_ref(&reference).await,
+            Err(CloudError::StorageRead { .. })
+        ));
+        assert!(!cache_dir.path().join("models").join("other.json").exists());
+    }
+}
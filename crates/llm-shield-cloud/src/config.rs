@@ -15,6 +15,12 @@ pub enum CloudProvider {
     GCP,
     /// Microsoft Azure
     Azure,
+    /// Any OpenTelemetry (OTLP) collector
+    Otlp,
+    /// Local filesystem and environment (on-premises)
+    Local,
+    /// Vault (secrets only)
+    Vault,
     /// No cloud provider (local/development mode)
     None,
 }
@@ -26,6 +32,9 @@ impl CloudProvider {
             CloudProvider::AWS => "aws",
             CloudProvider::GCP => "gcp",
             CloudProvider::Azure => "azure",
+            CloudProvider::Otlp => "otlp",
+            CloudProvider::Local => "local",
+            CloudProvider::Vault => "vault",
             CloudProvider::None => "none",
         }
     }
@@ -54,6 +63,18 @@ pub struct CloudConfig {
     /// Azure-specific configuration.
     #[serde(default)]
     pub azure: AzureConfig,
+
+    /// OpenTelemetry (OTLP) configuration.
+    #[serde(default)]
+    pub otlp: OtlpConfig,
+
+    /// Local filesystem configuration.
+    #[serde(default)]
+    pub local: LocalConfig,
+
+    /// Vault configuration.
+    #[serde(default)]
+    pub vault: VaultConfig,
 }
 
 fn default_provider() -> CloudProvider {
@@ -67,6 +88,9 @@ impl Default for CloudConfig {
             aws: AwsConfig::default(),
             gcp: GcpConfig::default(),
             azure: AzureConfig::default(),
+            otlp: OtlpConfig::default(),
+            local: LocalConfig::default(),
+            vault: VaultConfig::default(),
         }
     }
 }
@@ -368,16 +392,26 @@ pub struct AzureMonitorConfig {
     #[serde(default)]
     pub enabled: bool,
 
-    /// Log Analytics workspace ID.
+    /// Logs ingestion URL of the data collection endpoint.
     #[serde(default)]
-    pub workspace_id: String,
+    pub endpoint: String,
+
+    /// Immutable ID of the data collection rule.
+    #[serde(default)]
+    pub rule_id: String,
+
+    /// Stream of the data collection rule receiving the logs.
+    #[serde(default)]
+    pub stream_name: String,
 }
 
 impl Default for AzureMonitorConfig {
     fn default() -> Self {
         Self {
             enabled: false,
-            workspace_id: String::new(),
+            endpoint: String::new(),
+            rule_id: String::new(),
+            stream_name: String::new(),
         }
     }
 }
@@ -403,6 +437,335 @@ impl Default for AzureApplicationInsightsConfig {
     }
 }
 
+// ============================================================================
+// OpenTelemetry (OTLP) Configuration
+// ============================================================================
+
+/// OTLP transport protocol.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub enum OtlpProtocol {
+    /// Protobuf over gRPC (collector port 4317).
+    #[default]
+    #[serde(rename = "grpc")]
+    Grpc,
+    /// Protobuf over HTTP (collector port 4318).
+    #[serde(rename = "http/protobuf")]
+    HttpProtobuf,
+}
+
+impl OtlpProtocol {
+    /// Returns the protocol name as used by `OTEL_EXPORTER_OTLP_PROTOCOL`.
+    pub fn as_str(&self) -> &str {
+        match self {
+            OtlpProtocol::Grpc => "grpc",
+            OtlpProtocol::HttpProtobuf => "http/protobuf",
+        }
+    }
+}
+
+/// OpenTelemetry (OTLP) configuration.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct OtlpConfig {
+    /// Collector endpoint (e.g., `http://localhost:4317`).
+    ///
+    /// For HTTP, the signal path (`/v1/traces`, `/v1/metrics`, `/v1/logs`)
+    /// is appended.
+    #[serde(default = "default_otlp_endpoint")]
+    pub endpoint: String,
+
+    /// Transport protocol.
+    #[serde(default)]
+    pub protocol: OtlpProtocol,
+
+    /// Headers sent with every export (e.g., collector authentication).
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+
+    /// Value of the `service.name` resource attribute.
+    #[serde(default = "default_otlp_service_name")]
+    pub service_name: String,
+
+    /// Additional resource attributes.
+    #[serde(default)]
+    pub resource_attributes: HashMap<String, String>,
+
+    /// Timeout of a single export request in seconds.
+    #[serde(default = "default_otlp_timeout")]
+    pub timeout_seconds: u64,
+
+    /// Batching configuration.
+    #[serde(default)]
+    pub batch: BatchConfig,
+
+    /// Retry configuration.
+    #[serde(default)]
+    pub retry: RetryConfig,
+}
+
+impl Default for OtlpConfig {
+    fn default() -> Self {
+        Self {
+            endpoint: default_otlp_endpoint(),
+            protocol: OtlpProtocol::Grpc,
+            headers: HashMap::new(),
+            service_name: default_otlp_service_name(),
+            resource_attributes: HashMap::new(),
+            timeout_seconds: default_otlp_timeout(),
+            batch: BatchConfig::default(),
+            retry: RetryConfig::default(),
+        }
+    }
+}
+
+fn default_otlp_endpoint() -> String {
+    "http://localhost:4317".to_string()
+}
+
+fn default_otlp_service_name() -> String {
+    "llm-shield".to_string()
+}
+
+fn default_otlp_timeout() -> u64 {
+    10
+}
+
+// ============================================================================
+// Export Configuration
+// ============================================================================
+
+/// Batching of exported telemetry.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct BatchConfig {
+    /// Maximum number of items per export request.
+    #[serde(default = "default_max_batch_size")]
+    pub max_batch_size: usize,
+
+    /// Maximum number of items waiting for export; new items are rejected
+    /// while the queue is full.
+    #[serde(default = "default_max_queue_size")]
+    pub max_queue_size: usize,
+
+    /// Interval in milliseconds at which partial batches are exported.
+    #[serde(default = "default_flush_interval")]
+    pub flush_interval_ms: u64,
+}
+
+impl Default for BatchConfig {
+    fn default() -> Self {
+        Self {
+            max_batch_size: default_max_batch_size(),
+            max_queue_size: default_max_queue_size(),
+            flush_interval_ms: default_flush_interval(),
+        }
+    }
+}
+
+fn default_max_batch_size() -> usize {
+    512
+}
+
+fn default_max_queue_size() -> usize {
+    2048
+}
+
+fn default_flush_interval() -> u64 {
+    5000
+}
+
+/// Retry of failed telemetry exports.
+///
+/// Only failures marked as retryable are retried, e.g. `UNAVAILABLE` or
+/// HTTP 503, with exponential backoff.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RetryConfig {
+    /// Maximum number of retries after the first attempt.
+    #[serde(default = "default_max_retries")]
+    pub max_retries: u32,
+
+    /// Backoff before the first retry in milliseconds.
+    #[serde(default = "default_initial_backoff")]
+    pub initial_backoff_ms: u64,
+
+    /// Upper bound of the backoff in milliseconds.
+    #[serde(default = "default_max_backoff")]
+    pub max_backoff_ms: u64,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            max_retries: default_max_retries(),
+            initial_backoff_ms: default_initial_backoff(),
+            max_backoff_ms: default_max_backoff(),
+        }
+    }
+}
+
+fn default_max_retries() -> u32 {
+    3
+}
+
+fn default_initial_backoff() -> u64 {
+    100
+}
+
+fn default_max_backoff() -> u64 {
+    5000
+}
+
+/// OTLP batching configuration, the generic [`BatchConfig`].
+pub type OtlpBatchConfig = BatchConfig;
+
+/// OTLP retry configuration, the generic [`RetryConfig`].
+pub type OtlpRetryConfig = RetryConfig;
+
+// ============================================================================
+// Local Configuration
+// ============================================================================
+
+/// Local filesystem configuration.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct LocalConfig {
+    /// Root directory for object storage.
+    #[serde(default = "default_local_storage_path")]
+    pub storage_path: String,
+
+    /// Directory for secret files.
+    #[serde(default = "default_local_secrets_path")]
+    pub secrets_path: String,
+
+    /// Prefix of environment variables providing secrets
+    /// (e.g., `LLM_SHIELD_SECRET_`).
+    #[serde(default)]
+    pub secrets_env_prefix: Option<String>,
+
+    /// Environment variable holding the hex-encoded 256-bit key used to
+    /// encrypt secret files at rest.
+    #[serde(default)]
+    pub encryption_key_env: Option<String>,
+}
+
+impl Default for LocalConfig {
+    fn default() -> Self {
+        Self {
+            storage_path: default_local_storage_path(),
+            secrets_path: default_local_secrets_path(),
+            secrets_env_prefix: None,
+            encryption_key_env: None,
+        }
+    }
+}
+
+fn default_local_storage_path() -> String {
+    "./data/storage".to_string()
+}
+
+fn default_local_secrets_path() -> String {
+    "./data/secrets".to_string()
+}
+
+// ============================================================================
+// HashiCorp Vault Configuration
+// ============================================================================
+
+/// Vault secret manager configuration.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct VaultConfig {
+    /// Vault server address (e.g., `https://vault.example.com:8200`).
+    #[serde(default = "default_vault_address")]
+    pub address: String,
+
+    /// Mount path of the KV version 2 secrets engine.
+    #[serde(default = "default_vault_mount")]
+    pub mount: String,
+
+    /// Vault Enterprise namespace.
+    #[serde(default)]
+    pub namespace: Option<String>,
+
+    /// Authentication method.
+    #[serde(default)]
+    pub auth: VaultAuthConfig,
+
+    /// Cache TTL in seconds.
+    #[serde(default = "default_cache_ttl")]
+    pub cache_ttl_seconds: u64,
+
+    /// Renew the token lease in the background before it expires
+    /// (re-authenticating with `AppRole` once it can no longer be renewed).
+    #[serde(default = "default_vault_renew_token")]
+    pub renew_token: bool,
+
+    /// Timeout of a single request in seconds.
+    #[serde(default = "default_vault_timeout")]
+    pub timeout_seconds: u64,
+}
+
+impl Default for VaultConfig {
+    fn default() -> Self {
+        Self {
+            address: default_vault_address(),
+            mount: default_vault_mount(),
+            namespace: None,
+            auth: VaultAuthConfig::default(),
+            cache_ttl_seconds: default_cache_ttl(),
+            renew_token: default_vault_renew_token(),
+            timeout_seconds: default_vault_timeout(),
+        }
+    }
+}
+
+fn default_vault_address() -> String {
+    "http://127.0.0.1:8200".to_string()
+}
+
+fn default_vault_mount() -> String {
+    "secret".to_string()
+}
+
+fn default_vault_renew_token() -> bool {
+    true
+}
+
+fn default_vault_timeout() -> u64 {
+    10
+}
+
+/// Vault authentication method.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(tag = "method", rename_all = "lowercase")]
+pub enum VaultAuthConfig {
+    /// Static token authentication.
+    Token {
+        /// Vault token; read from `VAULT_TOKEN` if not set.
+        #[serde(default)]
+        token: Option<String>,
+    },
+    /// `AppRole` authentication.
+    AppRole {
+        /// Mount path of the `AppRole` auth method.
+        #[serde(default = "default_vault_approle_mount")]
+        mount: String,
+
+        /// Role ID.
+        role_id: String,
+
+        /// Secret ID; read from `VAULT_SECRET_ID` if not set.
+        #[serde(default)]
+        secret_id: Option<String>,
+    },
+}
+
+impl Default for VaultAuthConfig {
+    fn default() -> Self {
+        VaultAuthConfig::Token { token: None }
+    }
+}
+
+fn default_vault_approle_mount() -> String {
+    "approle".to_string()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -412,6 +775,9 @@ mod tests {
         assert_eq!(CloudProvider::AWS.as_str(), "aws");
         assert_eq!(CloudProvider::GCP.as_str(), "gcp");
         assert_eq!(CloudProvider::Azure.as_str(), "azure");
+        assert_eq!(CloudProvider::Otlp.as_str(), "otlp");
+        assert_eq!(CloudProvider::Local.as_str(), "local");
+        assert_eq!(CloudProvider::Vault.as_str(), "vault");
         assert_eq!(CloudProvider::None.as_str(), "none");
     }
 
@@ -420,6 +786,9 @@ mod tests {
         assert!(CloudProvider::AWS.is_enabled());
         assert!(CloudProvider::GCP.is_enabled());
         assert!(CloudProvider::Azure.is_enabled());
+        assert!(CloudProvider::Otlp.is_enabled());
+        assert!(CloudProvider::Local.is_enabled());
+        assert!(CloudProvider::Vault.is_enabled());
         assert!(!CloudProvider::None.is_enabled());
     }
 
@@ -478,4 +847,73 @@ aws:
         assert!(yaml.contains("provider: gcp"));
         assert!(yaml.contains("project_id: my-project"));
     }
+
+    #[test]
+    fn test_otlp_config_deserialization() {
+        let yaml = r#"
+provider: otlp
+otlp:
+  endpoint: http://collector:4318
+  protocol: http/protobuf
+  headers:
+    x-api-key: secret
+  batch:
+    max_batch_size: 100
+"#;
+
+        let config: CloudConfig = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(config.provider, CloudProvider::Otlp);
+        assert_eq!(config.otlp.protocol, OtlpProtocol::HttpProtobuf);
+        assert_eq!(config.otlp.endpoint, "http://collector:4318");
+        assert_eq!(config.otlp.headers.get("x-api-key"), Some(&"secret".to_string()));
+        assert_eq!(config.otlp.batch.max_batch_size, 100);
+        assert_eq!(config.otlp.batch.max_queue_size, 2048);
+        assert_eq!(config.otlp.service_name, "llm-shield");
+        assert_eq!(config.otlp.retry.max_retries, 3);
+    }
+
+    #[test]
+    fn test_local_config() {
+        let yaml = r#"
+provider: local
+local:
+  secrets_path: /etc/llm-shield/secrets
+  encryption_key_env: LLM_SHIELD_SECRETS_KEY
+"#;
+
+        let config: CloudConfig = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(config.provider, CloudProvider::Local);
+        assert_eq!(config.local.storage_path, "./data/storage");
+        assert_eq!(config.local.secrets_path, "/etc/llm-shield/secrets");
+        assert_eq!(config.local.encryption_key_env.as_deref(), Some("LLM_SHIELD_SECRETS_KEY"));
+    }
+
+    #[test]
+    fn test_vault_config_deserialization() {
+        let yaml = r#"
+provider: vault
+vault:
+  address: https://vault.internal:8200
+  auth:
+    method: approle
+    role_id: llm-shield
+"#;
+
+        let config: CloudConfig = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(config.provider, CloudProvider::Vault);
+        assert_eq!(config.vault.address, "https://vault.internal:8200");
+        assert_eq!(config.vault.mount, "secret");
+        assert!(config.vault.renew_token);
+        match config.vault.auth {
+            VaultAuthConfig::AppRole { mount, role_id, secret_id } => {
+                assert_eq!(mount, "approle");
+                assert_eq!(role_id, "llm-shield");
+                assert!(secret_id.is_none());
+            }
+            VaultAuthConfig::Token { .. } => panic!("expected AppRole auth"),
+        }
+
+        let config = VaultConfig::default();
+        assert!(matches!(config.auth, VaultAuthConfig::Token { token: None }));
+    }
 }
@@ -0,0 +1,443 @@
+//! Batching and retry shared by the telemetry exporters.
+//!
+//! Provider crates implement [`BatchExport`] for their wire format and queue
+//! items through a [`BatchQueue`], so recording never waits on the network.
+//! [`retry`] re-runs failed export attempts with exponential backoff.
+
+use crate::config::{BatchConfig, RetryConfig};
+use crate::error::{CloudError, Result};
+use async_trait::async_trait;
+use std::future::Future;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{mpsc, oneshot};
+
+/// Exports one batch of items.
+#[async_trait]
+pub trait BatchExport<T>: Send + Sync + 'static {
+    /// Sends `batch` to the backend.
+    async fn export(&self, batch: Vec<T>) -> Result<()>;
+}
+
+enum Message<T> {
+    Items(Vec<T>),
+    Flush(oneshot::Sender<Result<()>>),
+}
+
+/// Queue exporting its items in batches from a background task.
+///
+/// A batch is exported as soon as `max_batch_size` items are queued, and
+/// partial batches every `flush_interval_ms`. Failed background exports are
+/// logged and dropped; [`BatchQueue::flush`] reports them to the caller.
+/// Dropping the queue exports the remaining items.
+pub struct BatchQueue<T> {
+    error: fn(String) -> CloudError,
+    sender: mpsc::UnboundedSender<Message<T>>,
+    queued: Arc<AtomicUsize>,
+    max_queue_size: usize,
+}
+
+impl<T: Send + 'static> BatchQueue<T> {
+    /// Starts the background export task. Must be called within a Tokio runtime.
+    ///
+    /// `error` builds the error returned for a full queue or a stopped task,
+    /// e.g. `CloudError::MetricsExport`.
+    pub fn spawn<E: BatchExport<T>>(
+        config: &BatchConfig,
+        error: fn(String) -> CloudError,
+        exporter: E,
+    ) -> Self {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let queued = Arc::new(AtomicUsize::new(0));
+
+        let worker = Worker {
+            exporter,
+            buffer: Vec::new(),
+            queued: queued.clone(),
+            max_batch_size: config.max_batch_size.max(1),
+        };
+        let interval = Duration::from_millis(config.flush_interval_ms.max(1));
+        tokio::spawn(worker.run(receiver, interval));
+
+        Self {
+            error,
+            sender,
+            queued,
+            max_queue_size: config.max_queue_size,
+        }
+    }
+
+    /// Queues items for export.
+    ///
+    /// Fails without queueing anything if the items do not fit into the queue.
+    pub fn push(&self, items: Vec<T>) -> Result<()> {
+        let count = items.len();
+        let reserved = self
+            .queued
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |queued| {
+                (queued + count <= self.max_queue_size).then_some(queued + count)
+            });
+        if reserved.is_err() {
+            return Err((self.error)(format!(
+                "export queue is full ({} items)",
+                self.max_queue_size
+            )));
+        }
+
+        self.sender.send(Message::Items(items)).map_err(|_| {
+            self.queued.fetch_sub(count, Ordering::AcqRel);
+            self.stopped()
+        })
+    }
+
+    /// Exports all queued items, returning the first export error.
+    pub async fn flush(&self) -> Result<()> {
+        let (reply, done) = oneshot::channel();
+        self.sender
+            .send(Message::Flush(reply))
+            .map_err(|_| self.stopped())?;
+        done.await.map_err(|_| self.stopped())?
+    }
+
+    fn stopped(&self) -> CloudError {
+        (self.error)("export task has stopped".to_string())
+    }
+}
+
+struct Worker<T, E> {
+    exporter: E,
+    buffer: Vec<T>,
+    queued: Arc<AtomicUsize>,
+    max_batch_size: usize,
+}
+
+impl<T: Send + 'static, E: BatchExport<T>> Worker<T, E> {
+    async fn run(mut self, mut receiver: mpsc::UnboundedReceiver<Message<T>>, interval: Duration) {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        ticker.tick().await;
+
+        loop {
+            tokio::select! {
+                message = receiver.recv() => match message {
+                    Some(Message::Items(items)) => {
+                        self.buffer.extend(items);
+                        while self.buffer.len() >= self.max_batch_size {
+                            let _ = self.export_batch().await;
+                        }
+                    }
+                    Some(Message::Flush(reply)) => {
+                        let _ = reply.send(self.export_all().await);
+                    }
+                    None => {
+                        let _ = self.export_all().await;
+                        break;
+                    }
+                },
+                _ = ticker.tick() => {
+                    let _ = self.export_all().await;
+                }
+            }
+        }
+    }
+
+    /// Exports everything buffered, returning the first error.
+    async fn export_all(&mut self) -> Result<()> {
+        let mut result = Ok(());
+        while !self.buffer.is_empty() {
+            let exported = self.export_batch().await;
+            if result.is_ok() {
+                result = exported;
+            }
+        }
+        result
+    }
+
+    async fn export_batch(&mut self) -> Result<()> {
+        let len = self.buffer.len().min(self.max_batch_size);
+        let batch: Vec<T> = self.buffer.drain(..len).collect();
+        self.queued.fetch_sub(len, Ordering::AcqRel);
+
+        let result = self.exporter.export(batch).await;
+        if let Err(e) = &result {
+            tracing::warn!(error = %e, items = len, "Dropping telemetry batch after failed export");
+        }
+        result
+    }
+}
+
+/// Failed export attempt.
+#[derive(Debug)]
+pub struct AttemptError {
+    /// Description of the failure.
+    pub message: String,
+    /// Whether the attempt may succeed when repeated.
+    pub retryable: bool,
+}
+
+impl AttemptError {
+    /// Failure worth retrying, e.g. an unavailable backend.
+    pub fn retryable(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            retryable: true,
+        }
+    }
+
+    /// Failure that repeating will not fix, e.g. a rejected payload.
+    pub fn permanent(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            retryable: false,
+        }
+    }
+
+    /// Failure of an HTTP request answered with `status`.
+    pub fn http_status(status: u16, message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            retryable: is_retryable_status(status),
+        }
+    }
+}
+
+/// Runs `attempt` until it succeeds, fails permanently or `retry.max_retries`
+/// retries are exhausted, backing off exponentially between attempts.
+///
+/// Returns the value of the successful attempt or the message of the last
+/// failure.
+pub async fn retry<T, F, Fut>(retry: &RetryConfig, mut attempt: F) -> std::result::Result<T, String>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = std::result::Result<T, AttemptError>>,
+{
+    let mut backoff = Duration::from_millis(retry.initial_backoff_ms);
+    let max_backoff = Duration::from_millis(retry.max_backoff_ms);
+    let mut retries = 0;
+
+    loop {
+        match attempt().await {
+            Ok(value) => return Ok(value),
+            Err(e) if e.retryable && retries < retry.max_retries => {
+                retries += 1;
+                tracing::debug!(retry = retries, error = %e.message, "Retrying telemetry export");
+                tokio::time::sleep(backoff).await;
+                backoff = (backoff * 2).min(max_backoff);
+            }
+            Err(e) if retries > 0 => {
+                return Err(format!("{} (after {} retries)", e.message, retries))
+            }
+            Err(e) => return Err(e.message),
+        }
+    }
+}
+
+/// HTTP status codes worth retrying: throttling and unavailable backends.
+#[must_use]
+pub fn is_retryable_status(status: u16) -> bool {
+    matches!(status, 429 | 502 | 503 | 504)
+}
+
+/// `len`-byte trace or span ID for an identifier.
+///
+/// Hex IDs, including hyphenated UUIDs, are decoded as-is (truncated to
+/// `len` bytes) so they match IDs issued by other tracing instrumentation.
+/// Other identifiers are hashed, so the same identifier always maps to the
+/// same ID. The result is never all zeros, which W3C Trace Context and OTLP
+/// reject.
+#[must_use]
+pub fn id_bytes(id: &str, len: usize) -> Vec<u8> {
+    let hex: String = id.chars().filter(|c| *c != '-').collect();
+    let mut bytes = match decode_hex(&hex) {
+        Some(decoded) if decoded.len() >= len => decoded[..len].to_vec(),
+        _ => hashed_id(id, len),
+    };
+
+    if bytes.iter().all(|b| *b == 0) {
+        bytes[len - 1] = 1;
+    }
+    bytes
+}
+
+/// [`id_bytes`] as lowercase hex, e.g. a 32-character W3C trace ID.
+#[must_use]
+pub fn hex_id(id: &str, len: usize) -> String {
+    hex::encode(id_bytes(id, len))
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) || !hex.is_ascii() {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).ok())
+        .collect()
+}
+
+/// FNV-1a hash of `id`, extended to `len` bytes by rehashing with a counter.
+fn hashed_id(id: &str, len: usize) -> Vec<u8> {
+    const OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    let mut bytes = Vec::with_capacity(len);
+    let mut round = 0u8;
+    while bytes.len() < len {
+        let hash = id
+            .bytes()
+            .chain(std::iter::once(round))
+            .fold(OFFSET, |hash, byte| {
+                (hash ^ u64::from(byte)).wrapping_mul(PRIME)
+            });
+        bytes.extend_from_slice(&hash.to_be_bytes());
+        round += 1;
+    }
+    bytes.truncate(len);
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[derive(Clone, Default)]
+    struct Recorder {
+        batches: Arc<Mutex<Vec<Vec<u32>>>>,
+    }
+
+    #[async_trait]
+    impl BatchExport<u32> for Recorder {
+        async fn export(&self, batch: Vec<u32>) -> Result<()> {
+            let fail = batch.contains(&0);
+            self.batches.lock().unwrap().push(batch);
+            if fail {
+                Err(CloudError::TraceExport("rejected".to_string()))
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    fn config(max_batch_size: usize, max_queue_size: usize) -> BatchConfig {
+        BatchConfig {
+            max_batch_size,
+            max_queue_size,
+            flush_interval_ms: 60_000,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_batches_by_size_and_flush() {
+        let recorder = Recorder::default();
+        let queue = BatchQueue::spawn(&config(2, 100), CloudError::TraceExport, recorder.clone());
+
+        queue.push(vec![1, 2, 3]).unwrap();
+        queue.push(vec![4, 5]).unwrap();
+        queue.flush().await.unwrap();
+
+        let batches = recorder.batches.lock().unwrap().clone();
+        assert_eq!(batches, vec![vec![1, 2], vec![3, 4], vec![5]]);
+    }
+
+    #[tokio::test]
+    async fn test_queue_limit_and_errors() {
+        let recorder = Recorder::default();
+        let queue = BatchQueue::spawn(&config(10, 3), CloudError::TraceExport, recorder.clone());
+
+        queue.push(vec![0, 1]).unwrap();
+        assert!(matches!(
+            queue.push(vec![2, 3]),
+            Err(CloudError::TraceExport(_))
+        ));
+        assert!(queue.flush().await.is_err());
+
+        // Exported items free their queue slots
+        queue.push(vec![2, 3]).unwrap();
+        queue.flush().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_interval_and_drop_export_partial_batches() {
+        let recorder = Recorder::default();
+        let queue = BatchQueue::spawn(
+            &BatchConfig {
+                max_batch_size: 10,
+                max_queue_size: 100,
+                flush_interval_ms: 20,
+            },
+            CloudError::TraceExport,
+            recorder.clone(),
+        );
+
+        queue.push(vec![1]).unwrap();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(recorder.batches.lock().unwrap().len(), 1);
+
+        queue.push(vec![2]).unwrap();
+        drop(queue);
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(recorder.batches.lock().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_retry() {
+        let policy = RetryConfig {
+            max_retries: 2,
+            initial_backoff_ms: 1,
+            max_backoff_ms: 2,
+        };
+
+        let mut attempts = 0;
+        let result = retry(&policy, || {
+            attempts += 1;
+            let outcome = if attempts < 3 {
+                Err(AttemptError::http_status(503, "unavailable"))
+            } else {
+                Ok(())
+            };
+            async move { outcome }
+        })
+        .await;
+        assert!(result.is_ok());
+        assert_eq!(attempts, 3);
+
+        let mut attempts = 0;
+        let result = retry(&policy, || {
+            attempts += 1;
+            async { Err::<(), _>(AttemptError::retryable("unavailable")) }
+        })
+        .await;
+        assert_eq!(result.unwrap_err(), "unavailable (after 2 retries)");
+        assert_eq!(attempts, 3);
+
+        let mut attempts = 0;
+        let result = retry(&policy, || {
+            attempts += 1;
+            async { Err::<(), _>(AttemptError::http_status(400, "bad request")) }
+        })
+        .await;
+        assert_eq!(result.unwrap_err(), "bad request");
+        assert_eq!(attempts, 1);
+    }
+
+    #[test]
+    fn test_ids_decode_hex_and_hash_others() {
+        let uuid = "0af7651916cd43dd8448eb211c80319c";
+        assert_eq!(id_bytes(uuid, 16), decode_hex(uuid).unwrap());
+        assert_eq!(
+            id_bytes("0af76519-16cd-43dd-8448-eb211c80319c", 16),
+            id_bytes(uuid, 16)
+        );
+        assert_eq!(id_bytes(uuid, 8), decode_hex("0af7651916cd43dd").unwrap());
+        assert_eq!(hex_id("0AF76519-16CD-43DD-8448-EB211C80319C", 16), uuid);
+
+        // Arbitrary identifiers hash to stable, valid IDs
+        assert_eq!(id_bytes("execution-1", 16), id_bytes("execution-1", 16));
+        assert_ne!(id_bytes("execution-1", 16), id_bytes("execution-2", 16));
+        assert_eq!(id_bytes("execution-1", 16).len(), 16);
+        assert_eq!(hex_id("x", 8).len(), 16);
+        assert_ne!(id_bytes("0000000000000000", 8), vec![0; 8]);
+    }
+}
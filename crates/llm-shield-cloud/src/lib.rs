@@ -8,9 +8,9 @@
 //!
 //! The crate defines trait-based abstractions for common cloud operations:
 //!
-//! - **Secret Management**: [`CloudSecretManager`] for AWS Secrets Manager, GCP Secret Manager, Azure Key Vault
+//! - **Secret Management**: [`CloudSecretManager`] for AWS Secrets Manager, GCP Secret Manager, Azure Key Vault, Vault
 //! - **Object Storage**: [`CloudStorage`] for AWS S3, GCP Cloud Storage, Azure Blob Storage
-//! - **Observability**: [`CloudMetrics`], [`CloudLogger`], [`CloudTracer`] for cloud-native monitoring
+//! - **Observability**: [`CloudMetrics`], [`CloudLogger`], [`CloudTracer`] for cloud-native monitoring or any OpenTelemetry collector
 //!
 //! # Features
 //!
@@ -19,6 +19,15 @@
 //! - `llm-shield-cloud-aws` - AWS integrations (enable with `cloud-aws` feature)
 //! - `llm-shield-cloud-gcp` - GCP integrations (enable with `cloud-gcp` feature)
 //! - `llm-shield-cloud-azure` - Azure integrations (enable with `cloud-azure` feature)
+//! - `llm-shield-cloud-otlp` - Vendor-neutral OTLP observability (enable with `cloud-otlp` feature)
+//! - `llm-shield-cloud-vault` - Vault secrets (enable with `cloud-vault` feature)
+//!
+//! For on-premises deployments and tests without a cloud account, the [`local`]
+//! module provides [`LocalStorage`] and [`LocalSecretManager`].
+//!
+//! Model registries, scanner pipelines and pattern packs published to any
+//! [`CloudStorage`] can be referenced as `storage://<key>` and fetched into a
+//! local cache with checksum verification via [`ArtifactCache`].
 //!
 //! # Example
 //!
@@ -65,17 +74,24 @@
 pub use async_trait::async_trait;
 
 // Module declarations
+pub mod artifacts;
 pub mod config;
 pub mod error;
+pub mod export;
+pub mod local;
 pub mod observability;
 pub mod secrets;
 pub mod storage;
 
 // Re-export commonly used types
 pub use config::{
-    AzureConfig, AwsConfig, CloudConfig, CloudProvider, GcpConfig,
+    AzureConfig, AwsConfig, BatchConfig, CloudConfig, CloudProvider, GcpConfig, LocalConfig,
+    OtlpBatchConfig, OtlpConfig, OtlpProtocol, OtlpRetryConfig, RetryConfig, VaultAuthConfig,
+    VaultConfig,
 };
+pub use artifacts::{ArtifactCache, ArtifactRef};
 pub use error::{CloudError, Result};
+pub use local::{LocalSecretManager, LocalStorage};
 pub use observability::{
     CloudLogger, CloudMetrics, CloudTracer, LogEntry, LogLevel, Metric, Span,
 };
@@ -0,0 +1,174 @@
+//! Local (filesystem) implementations of the cloud traits.
+//!
+//! These providers need no cloud account, which makes them suitable for
+//! on-premises deployments, development and hermetic integration tests:
+//!
+//! - [`LocalStorage`]: `CloudStorage` backed by a directory
+//! - [`LocalSecretManager`]: `CloudSecretManager` backed by a directory
+//!   and/or environment variables, with optional at-rest encryption
+//!
+//! # Layout
+//!
+//! Object keys and secret names map to paths below the root directory, with
+//! `/` separating directories (e.g. `models/toxicity.onnx`). Entries whose
+//! name starts with `.` are reserved for internal files (temporary files and
+//! metadata sidecars), so keys containing such a path component are rejected.
+//!
+//! All writes go to a temporary file in the target directory first and are
+//! then renamed into place, so readers never observe partially written data.
+
+mod secrets;
+mod storage;
+
+pub use secrets::LocalSecretManager;
+pub use storage::LocalStorage;
+
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Converts an object key or secret name into a relative path.
+///
+/// Returns `None` for keys that are empty, absolute, or contain empty,
+/// `.`-prefixed or otherwise unsafe path components.
+pub(crate) fn relative_path(key: &str) -> Option<PathBuf> {
+    if key.is_empty() || key.contains(['\\', '\0']) {
+        return None;
+    }
+
+    let mut path = PathBuf::new();
+    for component in key.split('/') {
+        if component.is_empty() || component.starts_with('.') {
+            return None;
+        }
+        path.push(component);
+    }
+    Some(path)
+}
+
+/// Returns the path of an internal file stored next to `path`.
+pub(crate) fn hidden_sibling(path: &Path, suffix: &str) -> PathBuf {
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy())
+        .unwrap_or_default();
+    path.with_file_name(format!(".{name}{suffix}"))
+}
+
+/// Atomically writes `data` to `path`, creating parent directories.
+///
+/// The data is written and synced to a temporary file, which is then renamed
+/// over `path`. With `overwrite` set to `false` the file is linked into place
+/// instead, failing with `AlreadyExists` if `path` exists.
+pub(crate) async fn write_atomic(
+    path: &Path,
+    data: &[u8],
+    overwrite: bool,
+    mode: u32,
+) -> io::Result<()> {
+    use tokio::io::AsyncWriteExt;
+
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+
+    let temp = hidden_sibling(path, &format!(".{}.tmp", uuid::Uuid::new_v4().simple()));
+    let mut options = tokio::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    options.mode(mode);
+    #[cfg(not(unix))]
+    let _ = mode;
+
+    let written = async {
+        let mut file = options.open(&temp).await?;
+        file.write_all(data).await?;
+        file.sync_all().await?;
+        drop(file);
+
+        if overwrite {
+            tokio::fs::rename(&temp, path).await
+        } else {
+            tokio::fs::hard_link(&temp, path).await?;
+            tokio::fs::remove_file(&temp).await
+        }
+    }
+    .await;
+
+    if written.is_err() {
+        let _ = tokio::fs::remove_file(&temp).await;
+    }
+    written
+}
+
+/// Lists the keys of all regular files below `root`, sorted.
+///
+/// Internal (`.`-prefixed) entries are skipped. A missing root yields an
+/// empty list.
+async fn list_keys(root: &Path) -> io::Result<Vec<String>> {
+    let mut keys = Vec::new();
+    let mut pending = vec![(root.to_path_buf(), String::new())];
+
+    while let Some((dir, prefix)) = pending.pop() {
+        let mut entries = match tokio::fs::read_dir(&dir).await {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(e),
+        };
+
+        while let Some(entry) = entries.next_entry().await? {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if name.starts_with('.') {
+                continue;
+            }
+
+            let key = format!("{prefix}{name}");
+            let file_type = entry.file_type().await?;
+            if file_type.is_dir() {
+                pending.push((entry.path(), format!("{key}/")));
+            } else if file_type.is_file() {
+                keys.push(key);
+            }
+        }
+    }
+
+    keys.sort();
+    Ok(keys)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_relative_path() {
+        assert_eq!(
+            relative_path("models/toxicity.onnx"),
+            Some(PathBuf::from("models").join("toxicity.onnx"))
+        );
+
+        for key in ["", "/etc/passwd", "a//b", "../secret", "a/./b", "a/.hidden", "a\\b"] {
+            assert!(relative_path(key).is_none(), "{key:?} should be rejected");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_write_atomic_and_list_keys() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("a").join("b.txt");
+
+        write_atomic(&path, b"one", true, 0o644).await.unwrap();
+        write_atomic(&path, b"two", true, 0o644).await.unwrap();
+        assert_eq!(tokio::fs::read(&path).await.unwrap(), b"two");
+
+        let err = write_atomic(&path, b"three", false, 0o644).await.unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::AlreadyExists);
+        assert_eq!(tokio::fs::read(&path).await.unwrap(), b"two");
+
+        write_atomic(&dir.path().join("c"), b"", true, 0o644).await.unwrap();
+        tokio::fs::write(hidden_sibling(&path, ".meta.json"), b"{}").await.unwrap();
+
+        // Temporary files are cleaned up and internal files are hidden
+        assert_eq!(list_keys(dir.path()).await.unwrap(), vec!["a/b.txt", "c"]);
+        assert!(list_keys(&dir.path().join("missing")).await.unwrap().is_empty());
+    }
+}
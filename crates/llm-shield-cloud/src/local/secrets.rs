@@ -0,0 +1,462 @@
+//! File- and environment-backed secret management.
+
+use super::{list_keys, relative_path, write_atomic};
+use crate::error::{CloudError, Result};
+use crate::secrets::{CloudSecretManager, SecretMetadata, SecretValue};
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Header of encrypted secret files, followed by the nonce and ciphertext.
+const ENCRYPTED_MAGIC: &[u8] = b"LLMSHIELD-AES256GCM\0";
+
+/// Length of the AES-GCM nonce in bytes.
+const NONCE_LEN: usize = 12;
+
+/// Local implementation of `CloudSecretManager`.
+///
+/// Secrets are resolved from two optional sources:
+///
+/// - **Environment variables** (read-only): with an env prefix of
+///   `LLM_SHIELD_SECRET_`, the secret `llm-shield/api-keys` is read from
+///   `LLM_SHIELD_SECRET_LLM_SHIELD_API_KEYS` (upper-cased, with every
+///   non-alphanumeric character replaced by `_`). Environment variables take
+///   precedence over files.
+/// - **Files**: each secret is a file below the secrets directory, named
+///   after the secret. Files are written atomically with mode `0600`.
+///
+/// With an encryption key, written files are encrypted with AES-256-GCM,
+/// bound to the secret name. Plaintext files remain readable, so existing
+/// secrets can be migrated by rotating them.
+///
+/// # Example
+///
+/// ```no_run
+/// use llm_shield_cloud::{CloudSecretManager, LocalSecretManager, SecretValue};
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let secrets = LocalSecretManager::new("/etc/llm-shield/secrets")
+///         .await?
+///         .with_env_prefix("LLM_SHIELD_SECRET_")
+///         .with_encryption_key_hex(&std::env::var("LLM_SHIELD_SECRETS_KEY")?)?;
+///
+///     let value = SecretValue::from_string("sk-...".to_string());
+///     secrets.create_secret("openai/api-key", &value).await?;
+///
+///     let api_key = secrets.get_secret("openai/api-key").await?;
+///     assert_eq!(api_key.as_string(), "sk-...");
+///
+///     Ok(())
+/// }
+/// ```
+pub struct LocalSecretManager {
+    dir: Option<PathBuf>,
+    env_prefix: Option<String>,
+    cipher: Option<Aes256Gcm>,
+}
+
+impl LocalSecretManager {
+    /// Creates a secret manager storing secrets below `dir`, creating the
+    /// directory if needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CloudError::ClientInit` if the directory cannot be created.
+    pub async fn new(dir: impl Into<PathBuf>) -> Result<Self> {
+        let dir = dir.into();
+        tokio::fs::create_dir_all(&dir).await.map_err(|e| {
+            CloudError::ClientInit(format!(
+                "failed to create secrets directory {}: {e}",
+                dir.display()
+            ))
+        })?;
+
+        Ok(Self {
+            dir: Some(dir),
+            env_prefix: None,
+            cipher: None,
+        })
+    }
+
+    /// Creates a read-only secret manager resolving secrets from environment
+    /// variables starting with `prefix`.
+    pub fn from_env(prefix: impl Into<String>) -> Self {
+        Self {
+            dir: None,
+            env_prefix: Some(prefix.into()),
+            cipher: None,
+        }
+    }
+
+    /// Also resolves secrets from environment variables starting with `prefix`.
+    #[must_use]
+    pub fn with_env_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.env_prefix = Some(prefix.into());
+        self
+    }
+
+    /// Encrypts secret files with the given 256-bit key.
+    #[must_use]
+    pub fn with_encryption_key(mut self, key: &[u8; 32]) -> Self {
+        self.cipher = Some(Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key)));
+        self
+    }
+
+    /// Encrypts secret files with a hex-encoded 256-bit key.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CloudError::InvalidConfig` if the key is not 64 hex digits.
+    pub fn with_encryption_key_hex(self, key: &str) -> Result<Self> {
+        let key: [u8; 32] = hex::decode(key.trim())
+            .ok()
+            .and_then(|key| key.try_into().ok())
+            .ok_or_else(|| CloudError::InvalidConfig {
+                key: "encryption_key".to_string(),
+                reason: "expected 32 bytes encoded as 64 hex digits".to_string(),
+            })?;
+        Ok(self.with_encryption_key(&key))
+    }
+
+    /// Gets the secrets directory, if secrets are stored in files.
+    #[must_use]
+    pub fn dir(&self) -> Option<&Path> {
+        self.dir.as_deref()
+    }
+
+    /// Returns the environment variable a secret is resolved from, if any.
+    #[must_use]
+    pub fn env_var_name(&self, name: &str) -> Option<String> {
+        let prefix = self.env_prefix.as_ref()?;
+        let suffix: String = name
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() {
+                    c.to_ascii_uppercase()
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        Some(format!("{prefix}{suffix}"))
+    }
+
+    fn env_value(&self, name: &str) -> Option<String> {
+        std::env::var(self.env_var_name(name)?).ok()
+    }
+
+    fn secret_path(
+        &self,
+        name: &str,
+        error: fn(String, String) -> CloudError,
+    ) -> Result<PathBuf> {
+        let dir = self.dir.as_ref().ok_or_else(|| {
+            error(
+                name.to_string(),
+                "secret manager is read-only (no secrets directory configured)".to_string(),
+            )
+        })?;
+
+        relative_path(name)
+            .map(|path| dir.join(path))
+            .ok_or_else(|| error(name.to_string(), "invalid secret name".to_string()))
+    }
+
+    fn encrypt(&self, name: &str, value: &[u8]) -> Result<Vec<u8>> {
+        let Some(cipher) = &self.cipher else {
+            return Ok(value.to_vec());
+        };
+
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = cipher
+            .encrypt(
+                &nonce,
+                Payload {
+                    msg: value,
+                    aad: name.as_bytes(),
+                },
+            )
+            .map_err(|_| CloudError::Internal("secret encryption failed".to_string()))?;
+
+        let mut data = Vec::with_capacity(ENCRYPTED_MAGIC.len() + NONCE_LEN + ciphertext.len());
+        data.extend_from_slice(ENCRYPTED_MAGIC);
+        data.extend_from_slice(&nonce);
+        data.extend_from_slice(&ciphertext);
+        Ok(data)
+    }
+
+    fn decrypt(&self, name: &str, data: Vec<u8>) -> Result<Vec<u8>> {
+        let Some(encrypted) = data.strip_prefix(ENCRYPTED_MAGIC) else {
+            return Ok(data);
+        };
+
+        let format_error = |reason: &str| CloudError::SecretFormat {
+            name: name.to_string(),
+            reason: reason.to_string(),
+        };
+        let cipher = self
+            .cipher
+            .as_ref()
+            .ok_or_else(|| format_error("secret is encrypted but no encryption key is configured"))?;
+        if encrypted.len() < NONCE_LEN {
+            return Err(format_error("encrypted secret is truncated"));
+        }
+
+        let (nonce, ciphertext) = encrypted.split_at(NONCE_LEN);
+        cipher
+            .decrypt(
+                Nonce::from_slice(nonce),
+                Payload {
+                    msg: ciphertext,
+                    aad: name.as_bytes(),
+                },
+            )
+            .map_err(|_| format_error("decryption failed (wrong key or tampered file)"))
+    }
+
+    async fn write(
+        &self,
+        name: &str,
+        value: &SecretValue,
+        overwrite: bool,
+        error: fn(String, String) -> CloudError,
+    ) -> Result<()> {
+        let path = self.secret_path(name, error)?;
+        let data = self.encrypt(name, value.as_bytes())?;
+
+        write_atomic(&path, &data, overwrite, 0o600)
+            .await
+            .map_err(|e| match e.kind() {
+                io::ErrorKind::AlreadyExists => {
+                    error(name.to_string(), "secret already exists".to_string())
+                }
+                _ => error(name.to_string(), e.to_string()),
+            })
+    }
+
+    async fn file_metadata(&self, name: &str) -> Result<std::fs::Metadata> {
+        let path = self.secret_path(name, CloudError::secret_fetch)?;
+
+        match tokio::fs::metadata(&path).await {
+            Ok(metadata) if metadata.is_file() => Ok(metadata),
+            Ok(_) => Err(CloudError::SecretNotFound(name.to_string())),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                Err(CloudError::SecretNotFound(name.to_string()))
+            }
+            Err(e) => Err(CloudError::secret_fetch(name, e.to_string())),
+        }
+    }
+}
+
+#[async_trait]
+impl CloudSecretManager for LocalSecretManager {
+    async fn get_secret(&self, name: &str) -> Result<SecretValue> {
+        if let Some(value) = self.env_value(name) {
+            return Ok(SecretValue::from_string(value));
+        }
+        if self.dir.is_none() {
+            return Err(CloudError::SecretNotFound(name.to_string()));
+        }
+
+        let path = self.secret_path(name, CloudError::secret_fetch)?;
+        let data = tokio::fs::read(&path).await.map_err(|e| match e.kind() {
+            io::ErrorKind::NotFound => CloudError::SecretNotFound(name.to_string()),
+            _ => CloudError::secret_fetch(name, e.to_string()),
+        })?;
+
+        Ok(SecretValue::from_bytes(self.decrypt(name, data)?))
+    }
+
+    /// Lists the secrets stored in files.
+    ///
+    /// Secrets provided only through environment variables are not listed,
+    /// as their names cannot be recovered from the variable names.
+    async fn list_secrets(&self) -> Result<Vec<String>> {
+        match &self.dir {
+            Some(dir) => list_keys(dir)
+                .await
+                .map_err(|e| CloudError::SecretList(e.to_string())),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    async fn create_secret(&self, name: &str, value: &SecretValue) -> Result<()> {
+        self.write(name, value, false, CloudError::secret_create).await
+    }
+
+    async fn update_secret(&self, name: &str, value: &SecretValue) -> Result<()> {
+        match self.file_metadata(name).await {
+            Ok(_) => {}
+            Err(CloudError::SecretNotFound(_)) => {
+                return Err(CloudError::secret_update(name, "secret does not exist"))
+            }
+            Err(e) => return Err(e),
+        }
+        self.write(name, value, true, CloudError::secret_update).await
+    }
+
+    async fn delete_secret(&self, name: &str) -> Result<()> {
+        let path = self.secret_path(name, CloudError::secret_delete)?;
+
+        tokio::fs::remove_file(&path).await.map_err(|e| match e.kind() {
+            io::ErrorKind::NotFound => CloudError::SecretNotFound(name.to_string()),
+            _ => CloudError::secret_delete(name, e.to_string()),
+        })
+    }
+
+    async fn get_secret_metadata(&self, name: &str) -> Result<SecretMetadata> {
+        let now = chrono::Utc::now();
+        let (created_at, updated_at, source) = if self.env_value(name).is_some() {
+            (now, now, "env")
+        } else {
+            let metadata = self.file_metadata(name).await?;
+            let updated_at = metadata.modified().map_or(now, chrono::DateTime::from);
+            let created_at = metadata.created().map_or(updated_at, chrono::DateTime::from);
+            (created_at, updated_at, "file")
+        };
+
+        Ok(SecretMetadata {
+            name: name.to_string(),
+            created_at,
+            updated_at,
+            tags: HashMap::from([("source".to_string(), source.to_string())]),
+            version: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: &str = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f";
+
+    fn secret(value: &str) -> SecretValue {
+        SecretValue::from_string(value.to_string())
+    }
+
+    #[tokio::test]
+    async fn test_file_secret_lifecycle() {
+        let dir = tempfile::tempdir().unwrap();
+        let secrets = LocalSecretManager::new(dir.path()).await.unwrap();
+
+        secrets.create_secret("openai/api-key", &secret("v1")).await.unwrap();
+        assert!(matches!(
+            secrets.create_secret("openai/api-key", &secret("v2")).await,
+            Err(CloudError::SecretCreate { .. })
+        ));
+        assert_eq!(secrets.get_secret("openai/api-key").await.unwrap().as_string(), "v1");
+
+        secrets.rotate_secret("openai/api-key", &secret("v2")).await.unwrap();
+        assert_eq!(secrets.get_secret("openai/api-key").await.unwrap().as_string(), "v2");
+        assert!(matches!(
+            secrets.update_secret("missing", &secret("v1")).await,
+            Err(CloudError::SecretUpdate { .. })
+        ));
+
+        secrets.create_secret("db-password", &secret("pw")).await.unwrap();
+        assert_eq!(
+            secrets.list_secrets().await.unwrap(),
+            vec!["db-password", "openai/api-key"]
+        );
+
+        let metadata = secrets.get_secret_metadata("db-password").await.unwrap();
+        assert_eq!(metadata.tags["source"], "file");
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(dir.path().join("db-password"))
+                .unwrap()
+                .permissions()
+                .mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        secrets.delete_secret("db-password").await.unwrap();
+        assert!(matches!(
+            secrets.get_secret("db-password").await,
+            Err(CloudError::SecretNotFound(_))
+        ));
+        assert!(matches!(
+            secrets.delete_secret("db-password").await,
+            Err(CloudError::SecretNotFound(_))
+        ));
+        assert!(secrets.get_secret("../etc/passwd").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_env_secrets() {
+        std::env::set_var("LLM_SHIELD_TEST_SECRET_OPENAI_API_KEY", "from-env");
+
+        let dir = tempfile::tempdir().unwrap();
+        let secrets = LocalSecretManager::new(dir.path())
+            .await
+            .unwrap()
+            .with_env_prefix("LLM_SHIELD_TEST_SECRET_");
+        assert_eq!(
+            secrets.env_var_name("openai/api-key").as_deref(),
+            Some("LLM_SHIELD_TEST_SECRET_OPENAI_API_KEY")
+        );
+
+        // Environment variables take precedence over files
+        secrets.create_secret("openai/api-key", &secret("from-file")).await.unwrap();
+        assert_eq!(secrets.get_secret("openai/api-key").await.unwrap().as_string(), "from-env");
+
+        let env_only = LocalSecretManager::from_env("LLM_SHIELD_TEST_SECRET_");
+        assert_eq!(env_only.get_secret("openai/api-key").await.unwrap().as_string(), "from-env");
+        assert!(matches!(
+            env_only.get_secret("missing").await,
+            Err(CloudError::SecretNotFound(_))
+        ));
+        assert!(env_only.list_secrets().await.unwrap().is_empty());
+        assert!(matches!(
+            env_only.create_secret("new", &secret("value")).await,
+            Err(CloudError::SecretCreate { .. })
+        ));
+
+        std::env::remove_var("LLM_SHIELD_TEST_SECRET_OPENAI_API_KEY");
+    }
+
+    #[tokio::test]
+    async fn test_encryption_at_rest() {
+        let dir = tempfile::tempdir().unwrap();
+        let secrets = LocalSecretManager::new(dir.path())
+            .await
+            .unwrap()
+            .with_encryption_key_hex(KEY)
+            .unwrap();
+
+        secrets.create_secret("api-key", &secret("sk-secret")).await.unwrap();
+        let raw = std::fs::read(dir.path().join("api-key")).unwrap();
+        assert!(raw.starts_with(ENCRYPTED_MAGIC));
+        assert!(!String::from_utf8_lossy(&raw).contains("sk-secret"));
+        assert_eq!(secrets.get_secret("api-key").await.unwrap().as_string(), "sk-secret");
+
+        // Plaintext files are still readable
+        std::fs::write(dir.path().join("legacy"), "plain").unwrap();
+        assert_eq!(secrets.get_secret("legacy").await.unwrap().as_string(), "plain");
+
+        // Ciphertext is bound to the secret name
+        std::fs::write(dir.path().join("copied"), &raw).unwrap();
+        assert!(matches!(
+            secrets.get_secret("copied").await,
+            Err(CloudError::SecretFormat { .. })
+        ));
+
+        // Reading without the key fails
+        let without_key = LocalSecretManager::new(dir.path()).await.unwrap();
+        assert!(matches!(
+            without_key.get_secret("api-key").await,
+            Err(CloudError::SecretFormat { .. })
+        ));
+
+        assert!(LocalSecretManager::from_env("X_")
+            .with_encryption_key_hex("abcd")
+            .is_err());
+    }
+}
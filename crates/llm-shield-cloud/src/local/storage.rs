@@ -0,0 +1,462 @@
+//! Directory-backed object storage.
+
+use super::{hidden_sibling, list_keys, relative_path, write_atomic};
+use crate::error::{CloudError, Result};
+use crate::storage::{CloudStorage, GetObjectOptions, ObjectMetadata, PutObjectOptions};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::io;
+use std::path::{Path, PathBuf};
+use tokio::io::AsyncReadExt;
+
+/// Suffix of the metadata sidecar stored next to each object.
+const SIDECAR_SUFFIX: &str = ".meta.json";
+
+/// Metadata persisted in an object's sidecar file.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Sidecar {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    content_type: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    storage_class: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    encryption: Option<String>,
+
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    metadata: BTreeMap<String, String>,
+}
+
+/// Local filesystem implementation of `CloudStorage`.
+///
+/// Objects are stored as plain files below a root directory, so they can be
+/// inspected and provisioned with standard tools. Upload options (content
+/// type, storage class, custom metadata) are kept in a hidden JSON sidecar
+/// next to each object. The `ETag` is the SHA-256 of the current content, so
+/// it stays correct when a file is replaced outside of this API.
+///
+/// # Example
+///
+/// ```no_run
+/// use llm_shield_cloud::{CloudStorage, LocalStorage};
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let storage = LocalStorage::new("/var/lib/llm-shield/storage").await?;
+///
+///     storage.put_object("models/toxicity.onnx", b"...").await?;
+///     let keys = storage.list_objects("models/").await?;
+///     assert_eq!(keys, vec!["models/toxicity.onnx"]);
+///
+///     Ok(())
+/// }
+/// ```
+pub struct LocalStorage {
+    root: PathBuf,
+}
+
+impl LocalStorage {
+    /// Creates a storage rooted at `root`, creating the directory if needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CloudError::ClientInit` if the directory cannot be created.
+    pub async fn new(root: impl Into<PathBuf>) -> Result<Self> {
+        let root = root.into();
+        tokio::fs::create_dir_all(&root).await.map_err(|e| {
+            CloudError::ClientInit(format!(
+                "failed to create storage directory {}: {e}",
+                root.display()
+            ))
+        })?;
+
+        Ok(Self { root })
+    }
+
+    /// Gets the root directory of this storage.
+    #[must_use]
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Gets the custom metadata an object was uploaded with.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CloudError::StorageObjectNotFound` if the object doesn't exist.
+    pub async fn get_object_user_metadata(&self, key: &str) -> Result<Vec<(String, String)>> {
+        let path = self.object_path(key, CloudError::storage_fetch)?;
+        ensure_file(&path, key).await?;
+
+        let sidecar = read_sidecar(&path).await.unwrap_or_default();
+        Ok(sidecar.metadata.into_iter().collect())
+    }
+
+    fn object_path(
+        &self,
+        key: &str,
+        error: fn(String, String) -> CloudError,
+    ) -> Result<PathBuf> {
+        relative_path(key)
+            .map(|path| self.root.join(path))
+            .ok_or_else(|| error(key.to_string(), "invalid object key".to_string()))
+    }
+}
+
+#[async_trait]
+impl CloudStorage for LocalStorage {
+    async fn get_object(&self, key: &str) -> Result<Vec<u8>> {
+        let path = self.object_path(key, CloudError::storage_fetch)?;
+
+        tokio::fs::read(&path).await.map_err(|e| match e.kind() {
+            io::ErrorKind::NotFound => CloudError::StorageObjectNotFound(key.to_string()),
+            _ => CloudError::storage_fetch(key, e.to_string()),
+        })
+    }
+
+    async fn get_object_with_options(
+        &self,
+        key: &str,
+        options: &GetObjectOptions,
+    ) -> Result<Vec<u8>> {
+        let data = self.get_object(key).await?;
+
+        if let Some(expected) = &options.if_match {
+            let actual = etag(&data);
+            if expected.trim_matches('"') != actual {
+                return Err(CloudError::storage_fetch(
+                    key,
+                    format!("precondition failed: ETag is {actual}, expected {expected}"),
+                ));
+            }
+        }
+
+        match options.range {
+            None => Ok(data),
+            Some((start, end)) => {
+                // Ranges are inclusive, as in HTTP range requests
+                let len = data.len();
+                let first = usize::try_from(start).unwrap_or(usize::MAX);
+                if start > end || first >= len {
+                    return Err(CloudError::storage_fetch(
+                        key,
+                        format!("range {start}-{end} not satisfiable for {len} bytes"),
+                    ));
+                }
+                let last = usize::try_from(end).unwrap_or(usize::MAX).min(len - 1);
+                Ok(data[first..=last].to_vec())
+            }
+        }
+    }
+
+    async fn put_object(&self, key: &str, data: &[u8]) -> Result<()> {
+        self.put_object_with_options(key, data, &PutObjectOptions::default())
+            .await
+    }
+
+    async fn put_object_with_options(
+        &self,
+        key: &str,
+        data: &[u8],
+        options: &PutObjectOptions,
+    ) -> Result<()> {
+        let path = self.object_path(key, CloudError::storage_put)?;
+        let sidecar = Sidecar {
+            content_type: options.content_type.clone(),
+            storage_class: options.storage_class.clone(),
+            encryption: options.encryption.clone(),
+            metadata: options.metadata.iter().cloned().collect(),
+        };
+        let sidecar = serde_json::to_vec_pretty(&sidecar)?;
+
+        write_atomic(&path, data, true, 0o644)
+            .await
+            .map_err(|e| CloudError::storage_put(key, e.to_string()))?;
+        write_atomic(&hidden_sibling(&path, SIDECAR_SUFFIX), &sidecar, true, 0o644)
+            .await
+            .map_err(|e| CloudError::storage_put(key, format!("failed to write metadata: {e}")))
+    }
+
+    async fn delete_object(&self, key: &str) -> Result<()> {
+        let path = self.object_path(key, CloudError::storage_delete)?;
+
+        // Deleting a missing object succeeds, as in S3
+        for path in [hidden_sibling(&path, SIDECAR_SUFFIX), path] {
+            match tokio::fs::remove_file(&path).await {
+                Ok(()) => {}
+                Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+                Err(e) => return Err(CloudError::storage_delete(key, e.to_string())),
+            }
+        }
+        Ok(())
+    }
+
+    async fn list_objects(&self, prefix: &str) -> Result<Vec<String>> {
+        let keys = list_keys(&self.root)
+            .await
+            .map_err(|e| CloudError::storage_list(prefix, e.to_string()))?;
+
+        Ok(keys
+            .into_iter()
+            .filter(|key| key.starts_with(prefix))
+            .collect())
+    }
+
+    async fn get_object_metadata(&self, key: &str) -> Result<ObjectMetadata> {
+        let path = self.object_path(key, CloudError::storage_fetch)?;
+        let metadata = ensure_file(&path, key).await?;
+        let sidecar = read_sidecar(&path).await;
+        let etag = file_etag(&path)
+            .await
+            .map_err(|e| CloudError::storage_fetch(key, e.to_string()))?;
+
+        Ok(ObjectMetadata {
+            size: metadata.len(),
+            last_modified: metadata
+                .modified()
+                .map_err(|e| CloudError::storage_fetch(key, e.to_string()))?,
+            content_type: sidecar.as_ref().and_then(|s| s.content_type.clone()),
+            etag: Some(etag),
+            storage_class: sidecar.and_then(|s| s.storage_class),
+        })
+    }
+
+    async fn copy_object(&self, from_key: &str, to_key: &str) -> Result<()> {
+        let from = self.object_path(from_key, CloudError::storage_fetch)?;
+        let data = self.get_object(from_key).await?;
+        let sidecar = read_sidecar(&from).await.unwrap_or_default();
+
+        let options = PutObjectOptions {
+            content_type: sidecar.content_type,
+            storage_class: sidecar.storage_class,
+            encryption: sidecar.encryption,
+            metadata: sidecar.metadata.into_iter().collect(),
+        };
+        self.put_object_with_options(to_key, &data, &options).await
+    }
+
+    async fn move_object(&self, from_key: &str, to_key: &str) -> Result<()> {
+        let from = self.object_path(from_key, CloudError::storage_fetch)?;
+        let to = self.object_path(to_key, CloudError::storage_put)?;
+        ensure_file(&from, from_key).await?;
+
+        if let Some(parent) = to.parent() {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .map_err(|e| CloudError::storage_put(to_key, e.to_string()))?;
+        }
+
+        // Move the sidecar first so the object never appears without it
+        let (from_sidecar, to_sidecar) = (
+            hidden_sibling(&from, SIDECAR_SUFFIX),
+            hidden_sibling(&to, SIDECAR_SUFFIX),
+        );
+        match tokio::fs::rename(&from_sidecar, &to_sidecar).await {
+            Ok(()) => {}
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                let _ = tokio::fs::remove_file(&to_sidecar).await;
+            }
+            Err(e) => return Err(CloudError::storage_put(to_key, e.to_string())),
+        }
+
+        tokio::fs::rename(&from, &to)
+            .await
+            .map_err(|e| CloudError::storage_put(to_key, e.to_string()))
+    }
+
+    fn provider_name(&self) -> &'static str {
+        "local"
+    }
+
+    async fn list_objects_with_metadata(&self, prefix: &str) -> Result<Vec<ObjectMetadata>> {
+        let mut objects = Vec::new();
+        for key in self.list_objects(prefix).await? {
+            match self.get_object_metadata(&key).await {
+                Ok(metadata) => objects.push(metadata),
+                // Deleted while listing
+                Err(CloudError::StorageObjectNotFound(_)) => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(objects)
+    }
+}
+
+/// Computes the `ETag` of object content.
+fn etag(data: &[u8]) -> String {
+    hex::encode(Sha256::digest(data))
+}
+
+/// Computes the `ETag` of an object file without loading it into memory.
+async fn file_etag(path: &Path) -> io::Result<String> {
+    let mut file = tokio::fs::File::open(path).await?;
+    let mut hasher = Sha256::new();
+    let mut buf = vec![0; 64 * 1024];
+    loop {
+        let n = file.read(&mut buf).await?;
+        if n == 0 {
+            return Ok(hex::encode(hasher.finalize()));
+        }
+        hasher.update(&buf[..n]);
+    }
+}
+
+/// Returns the filesystem metadata of an object file.
+async fn ensure_file(path: &Path, key: &str) -> Result<std::fs::Metadata> {
+    match tokio::fs::metadata(path).await {
+        Ok(metadata) if metadata.is_file() => Ok(metadata),
+        Ok(_) => Err(CloudError::StorageObjectNotFound(key.to_string())),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            Err(CloudError::StorageObjectNotFound(key.to_string()))
+        }
+        Err(e) => Err(CloudError::storage_fetch(key, e.to_string())),
+    }
+}
+
+/// Reads an object's sidecar, if present and valid.
+async fn read_sidecar(path: &Path) -> Option<Sidecar> {
+    let data = tokio::fs::read(hidden_sibling(path, SIDECAR_SUFFIX)).await.ok()?;
+    serde_json::from_slice(&data).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn storage() -> (tempfile::TempDir, LocalStorage) {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = LocalStorage::new(dir.path().join("storage")).await.unwrap();
+        (dir, storage)
+    }
+
+    #[tokio::test]
+    async fn test_put_get_list_delete() {
+        let (_dir, storage) = storage().await;
+        assert_eq!(storage.provider_name(), "local");
+
+        storage.put_object("models/b.onnx", b"model-b").await.unwrap();
+        storage.put_object("models/a.onnx", b"model-a").await.unwrap();
+        storage.put_object("results/1.json", b"{}").await.unwrap();
+
+        assert_eq!(storage.get_object("models/a.onnx").await.unwrap(), b"model-a");
+        assert_eq!(
+            storage.list_objects("models/").await.unwrap(),
+            vec!["models/a.onnx", "models/b.onnx"]
+        );
+        assert_eq!(storage.list_objects("").await.unwrap().len(), 3);
+        assert_eq!(storage.list_objects_with_limit("", 1).await.unwrap().len(), 1);
+
+        storage.delete_object("models/a.onnx").await.unwrap();
+        storage.delete_object("models/a.onnx").await.unwrap();
+        assert!(!storage.object_exists("models/a.onnx").await.unwrap());
+        assert!(matches!(
+            storage.get_object("models/a.onnx").await,
+            Err(CloudError::StorageObjectNotFound(_))
+        ));
+
+        // Directories are not objects
+        assert!(!storage.object_exists("models").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_metadata_sidecar() {
+        let (_dir, storage) = storage().await;
+        let options = PutObjectOptions {
+            content_type: Some("application/json".to_string()),
+            storage_class: Some("STANDARD".to_string()),
+            metadata: vec![("version".to_string(), "2".to_string())],
+            ..Default::default()
+        };
+        storage
+            .put_object_with_options("config.json", b"{}", &options)
+            .await
+            .unwrap();
+
+        let metadata = storage.get_object_metadata("config.json").await.unwrap();
+        assert_eq!(metadata.size, 2);
+        assert_eq!(metadata.content_type.as_deref(), Some("application/json"));
+        assert_eq!(metadata.storage_class.as_deref(), Some("STANDARD"));
+        assert_eq!(metadata.etag, Some(etag(b"{}")));
+
+        // Copies keep the metadata, moves take it along
+        storage.copy_object("config.json", "copy.json").await.unwrap();
+        storage.move_object("copy.json", "archive/moved.json").await.unwrap();
+        assert!(!storage.object_exists("copy.json").await.unwrap());
+        assert_eq!(
+            storage
+                .get_object_user_metadata("archive/moved.json")
+                .await
+                .unwrap(),
+            vec![("version".to_string(), "2".to_string())]
+        );
+
+        // Overwriting without options resets the metadata
+        storage.put_object("config.json", b"[]").await.unwrap();
+        let metadata = storage.get_object_metadata("config.json").await.unwrap();
+        assert!(metadata.content_type.is_none());
+        assert_eq!(metadata.etag, Some(etag(b"[]")));
+
+        let listed = storage.list_objects_with_metadata("").await.unwrap();
+        assert_eq!(listed.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_get_with_options() {
+        let (_dir, storage) = storage().await;
+        storage.put_object("data.bin", b"0123456789").await.unwrap();
+
+        let get = |range, if_match: Option<String>| {
+            let options = GetObjectOptions { range, if_match };
+            let storage = &storage;
+            async move { storage.get_object_with_options("data.bin", &options).await }
+        };
+
+        assert_eq!(get(Some((2, 4)), None).await.unwrap(), b"234");
+        assert_eq!(get(Some((8, 100)), None).await.unwrap(), b"89");
+        assert!(get(Some((10, 12)), None).await.is_err());
+
+        let current = format!("\"{}\"", etag(b"0123456789"));
+        assert!(get(None, Some(current)).await.is_ok());
+        assert!(get(None, Some("stale".to_string())).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_etag_follows_content() {
+        let (_dir, storage) = storage().await;
+        storage.put_object("model.onnx", b"v1").await.unwrap();
+
+        // Replaced outside of the API, the sidecar is left as it was
+        std::fs::write(storage.root().join("model.onnx"), b"v2").unwrap();
+
+        let metadata = storage.get_object_metadata("model.onnx").await.unwrap();
+        assert_eq!(metadata.etag, Some(etag(b"v2")));
+
+        let options = GetObjectOptions {
+            if_match: Some(etag(b"v1")),
+            ..Default::default()
+        };
+        assert!(storage
+            .get_object_with_options("model.onnx", &options)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_invalid_keys() {
+        let (dir, storage) = storage().await;
+        tokio::fs::write(dir.path().join("outside"), b"secret").await.unwrap();
+
+        assert!(matches!(
+            storage.get_object("../outside").await,
+            Err(CloudError::StorageFetch { .. })
+        ));
+        assert!(matches!(
+            storage.put_object("a/.meta.json", b"").await,
+            Err(CloudError::StoragePut { .. })
+        ));
+        assert!(storage.list_objects("").await.unwrap().is_empty());
+    }
+}
@@ -4,6 +4,7 @@
 //! - AWS: CloudWatch Metrics, CloudWatch Logs, X-Ray
 //! - GCP: Cloud Monitoring, Cloud Logging, Cloud Trace
 //! - Azure: Azure Monitor, Application Insights
+//! - OpenTelemetry: any OTLP collector (gRPC or HTTP/protobuf)
 
 use crate::error::{CloudError, Result};
 use async_trait::async_trait;
@@ -4,6 +4,7 @@
 //! - AWS Secrets Manager
 //! - GCP Secret Manager
 //! - Azure Key Vault
+//! - Vault (KV version 2)
 
 use crate::error::{CloudError, Result};
 use async_trait::async_trait;
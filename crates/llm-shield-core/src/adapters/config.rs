@@ -34,14 +34,26 @@
 //! let threshold = params.thresholds.get("prompt_injection").unwrap_or(&0.9);
 //! ```
 
+use crate::reload::{FileFingerprint, WatchHandle};
 use crate::{Error, Result, Vault};
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// How often `File` sources are checked for changes while watching
+pub const FILE_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Time limit for fetching a `Remote` source
+#[cfg(feature = "remote-config")]
+const REMOTE_TIMEOUT: Duration = Duration::from_secs(10);
 
 /// Shield parameters loaded from config manager
+///
+/// Fields missing from a serialized configuration take their defaults.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct ShieldParameters {
     /// Version of the configuration
     pub version: String,
@@ -347,7 +359,7 @@ pub struct CategoryEnforcement {
 pub enum ConfigSource {
     /// Load from a local file
     File { path: String },
-    /// Load from a remote URL
+    /// Fetch a JSON document from a URL (requires the `remote-config` feature)
     Remote { url: String },
     /// Load from environment variables
     Environment { prefix: String },
@@ -374,9 +386,12 @@ pub trait ConfigLoader: Send + Sync {
         self.load().await
     }
 
-    /// Watch for configuration changes
+    /// Wait until the configuration may have changed
+    ///
+    /// Returns when the source should be reloaded. The default implementation
+    /// polls: it returns after [`FILE_POLL_INTERVAL`].
     async fn watch(&self) -> Result<()> {
-        // Default implementation: no-op
+        tokio::time::sleep(FILE_POLL_INTERVAL).await;
         Ok(())
     }
 
@@ -396,6 +411,8 @@ pub struct ConfigAdapter {
     auto_refresh: bool,
     /// Refresh interval in seconds
     refresh_interval: u64,
+    /// Fingerprint of a `File` source when it was last read
+    file_fingerprint: Arc<Mutex<Option<FileFingerprint>>>,
 }
 
 impl ConfigAdapter {
@@ -406,6 +423,7 @@ impl ConfigAdapter {
             cached_params: Arc::new(tokio::sync::RwLock::new(None)),
             auto_refresh: false,
             refresh_interval: 300,
+            file_fingerprint: Arc::new(Mutex::new(None)),
         }
     }
 
@@ -416,16 +434,116 @@ impl ConfigAdapter {
             cached_params: Arc::new(tokio::sync::RwLock::new(None)),
             auto_refresh: false,
             refresh_interval: 300,
+            file_fingerprint: Arc::new(Mutex::new(None)),
         }
     }
 
     /// Enable auto-refresh
+    ///
+    /// Sets how often [`watch`](Self::watch) polls `Remote` and `Environment`
+    /// sources. `File` sources are checked every [`FILE_POLL_INTERVAL`].
     pub fn with_auto_refresh(mut self, interval_seconds: u64) -> Self {
         self.auto_refresh = true;
-        self.refresh_interval = interval_seconds;
+        self.refresh_interval = interval_seconds.max(1);
         self
     }
 
+    /// Whether auto-refresh is enabled
+    pub fn is_auto_refresh_enabled(&self) -> bool {
+        self.auto_refresh
+    }
+
+    /// Polling interval for sources without change detection
+    pub fn refresh_interval(&self) -> Duration {
+        Duration::from_secs(self.refresh_interval)
+    }
+
+    /// Wait until the source may have changed since it was last loaded
+    ///
+    /// `File` sources return once the file's modification time or size
+    /// changes; `Remote` and `Environment` sources return after the refresh
+    /// interval. `Inline` and `Default` sources never change.
+    pub async fn changed(&self) -> Result<()> {
+        match &self.source {
+            ConfigSource::File { path } => loop {
+                tokio::time::sleep(FILE_POLL_INTERVAL.min(self.refresh_interval())).await;
+                let current = FileFingerprint::of(path);
+                if current.is_some() && current != *self.fingerprint() {
+                    return Ok(());
+                }
+            },
+            ConfigSource::Remote { .. } | ConfigSource::Environment { .. } => {
+                tokio::time::sleep(self.refresh_interval()).await;
+                Ok(())
+            }
+            ConfigSource::Inline { .. } | ConfigSource::Default => {
+                std::future::pending::<()>().await;
+                Ok(())
+            }
+        }
+    }
+
+    /// Reload the parameters whenever the source changes
+    ///
+    /// Each changed set of parameters is passed to `apply`, which should
+    /// validate it and swap it into use. The cached parameters are only
+    /// updated if `apply` succeeds; if loading or `apply` fails, the previous
+    /// parameters stay in effect. Unchanged parameters are not re-applied.
+    ///
+    /// The task runs until the returned handle is dropped.
+    ///
+    /// ## Example
+    ///
+    /// ```rust,ignore
+    /// let adapter = Arc::new(ConfigAdapter::from_source(ConfigSource::File {
+    ///     path: "shield-params.json".to_string(),
+    /// }));
+    /// adapter.load_parameters().await?;
+    ///
+    /// let _watch = adapter.clone().watch(move |params| {
+    ///     shield.reload_scanners(build_scanners(params)?)?;
+    ///     Ok(())
+    /// });
+    /// ```
+    pub fn watch<F>(self: Arc<Self>, apply: F) -> WatchHandle
+    where
+        F: Fn(&ShieldParameters) -> Result<()> + Send + Sync + 'static,
+    {
+        WatchHandle::spawn(async move {
+            loop {
+                if self.changed().await.is_err() {
+                    continue;
+                }
+
+                let params = match self.load_from_source().await {
+                    Ok(params) => params,
+                    Err(e) => {
+                        tracing::warn!(error = %e, "Failed to reload shield parameters");
+                        continue;
+                    }
+                };
+
+                let mut cache = self.cached_params.write().await;
+                let unchanged = cache.as_ref().is_some_and(|cached| {
+                    serde_json::to_value(cached).ok() == serde_json::to_value(&params).ok()
+                });
+                if unchanged {
+                    continue;
+                }
+
+                match apply(&params) {
+                    Ok(()) => {
+                        tracing::info!(version = %params.version, "Reloaded shield parameters");
+                        *cache = Some(params);
+                    }
+                    Err(e) => {
+                        tracing::warn!(error = %e, "Rejected reloaded shield parameters, keeping previous");
+                    }
+                }
+            }
+        })
+    }
+
     /// Load shield parameters
     pub async fn load_parameters(&self) -> Result<ShieldParameters> {
         // Check cache first
@@ -479,11 +597,14 @@ impl ConfigAdapter {
         }
     }
 
-    /// Load from a file (placeholder - actual implementation would read file)
+    /// Load from a JSON file
     async fn load_from_file(&self, path: &str) -> Result<ShieldParameters> {
-        // In a real implementation, this would read and parse the file
-        // For now, we return defaults with the path noted
-        let mut params = ShieldParameters::default();
+        // Fingerprint before reading so a write during the read is seen as a change
+        *self.fingerprint() = FileFingerprint::of(path);
+
+        let contents = tokio::fs::read_to_string(path).await?;
+        let mut params: ShieldParameters = serde_json::from_str(&contents)
+            .map_err(|e| Error::config(format!("Invalid config file {}: {}", path, e)))?;
         params.metadata.insert(
             "source".to_string(),
             serde_json::json!({ "type": "file", "path": path }),
@@ -491,11 +612,21 @@ impl ConfigAdapter {
         Ok(params)
     }
 
-    /// Load from a remote URL (placeholder - actual implementation would fetch)
+    /// Fetch a JSON document from a remote URL
+    #[cfg(feature = "remote-config")]
     async fn load_from_remote(&self, url: &str) -> Result<ShieldParameters> {
-        // In a real implementation, this would fetch from the URL
-        // For now, we return defaults with the URL noted
-        let mut params = ShieldParameters::default();
+        let response = reqwest::Client::new()
+            .get(url)
+            .timeout(REMOTE_TIMEOUT)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| Error::config(format!("Failed to fetch config {}: {}", url, e)))?;
+
+        let mut params: ShieldParameters = response
+            .json()
+            .await
+            .map_err(|e| Error::config(format!("Invalid config from {}: {}", url, e)))?;
         params.metadata.insert(
             "source".to_string(),
             serde_json::json!({ "type": "remote", "url": url }),
@@ -503,6 +634,15 @@ impl ConfigAdapter {
         Ok(params)
     }
 
+    /// Remote sources need the `remote-config` feature
+    #[cfg(not(feature = "remote-config"))]
+    async fn load_from_remote(&self, url: &str) -> Result<ShieldParameters> {
+        Err(Error::config(format!(
+            "Cannot load config from {}: remote sources require the `remote-config` feature",
+            url
+        )))
+    }
+
     /// Load from environment variables
     fn load_from_env(&self, prefix: &str) -> Result<ShieldParameters> {
         let mut params = ShieldParameters::default();
@@ -542,6 +682,12 @@ impl ConfigAdapter {
         let params = self.load_parameters().await?;
         Ok(params.is_feature_enabled(feature))
     }
+
+    fn fingerprint(&self) -> std::sync::MutexGuard<'_, Option<FileFingerprint>> {
+        self.file_fingerprint
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+    }
 }
 
 impl Default for ConfigAdapter {
@@ -552,21 +698,30 @@ impl Default for ConfigAdapter {
 
 /// Default config loader implementation
 pub struct DefaultConfigLoader {
-    source: ConfigSource,
+    adapter: ConfigAdapter,
 }
 
 impl DefaultConfigLoader {
     /// Create a new default config loader
     pub fn new(source: ConfigSource) -> Self {
-        Self { source }
+        Self {
+            adapter: ConfigAdapter::from_source(source),
+        }
     }
 }
 
 #[async_trait]
 impl ConfigLoader for DefaultConfigLoader {
     async fn load(&self) -> Result<ShieldParameters> {
-        let adapter = ConfigAdapter::from_source(self.source.clone());
-        adapter.load_parameters().await
+        self.adapter.load_parameters().await
+    }
+
+    async fn reload(&self) -> Result<ShieldParameters> {
+        self.adapter.reload_parameters().await
+    }
+
+    async fn watch(&self) -> Result<()> {
+        self.adapter.changed().await
     }
 }
 
@@ -677,6 +832,50 @@ mod tests {
         assert_eq!(params.version, "2.0.0");
     }
 
+    #[cfg(not(feature = "remote-config"))]
+    #[tokio::test]
+    async fn test_config_adapter_remote_requires_feature() {
+        let adapter = ConfigAdapter::from_source(ConfigSource::Remote {
+            url: "https://config.example.com/shield.json".to_string(),
+        });
+
+        let err = adapter.load_parameters().await.unwrap_err();
+        assert!(err.to_string().contains("remote-config"));
+    }
+
+    #[tokio::test]
+    async fn test_config_adapter_file_watch() {
+        let path = std::env::temp_dir().join(format!("shield-params-{}.json", std::process::id()));
+        std::fs::write(&path, r#"{"version": "1.0.0"}"#).unwrap();
+
+        let adapter = Arc::new(ConfigAdapter::from_source(ConfigSource::File {
+            path: path.display().to_string(),
+        }));
+        assert_eq!(adapter.load_parameters().await.unwrap().version, "1.0.0");
+
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let _watch = adapter.clone().watch(move |params| {
+            let _ = tx.send(params.version.clone());
+            if params.version == "bad" {
+                return Err(Error::config("rejected"));
+            }
+            Ok(())
+        });
+
+        std::fs::write(&path, r#"{"version": "bad"}"#).unwrap();
+        let applied = tokio::time::timeout(Duration::from_secs(5), rx.recv()).await.unwrap();
+        assert_eq!(applied.as_deref(), Some("bad"));
+        // Rejected parameters are not cached
+        assert_eq!(adapter.load_parameters().await.unwrap().version, "1.0.0");
+
+        std::fs::write(&path, r#"{"version": "2.0.0-beta"}"#).unwrap();
+        let applied = tokio::time::timeout(Duration::from_secs(5), rx.recv()).await.unwrap();
+        assert_eq!(applied.as_deref(), Some("2.0.0-beta"));
+        assert_eq!(adapter.load_parameters().await.unwrap().version, "2.0.0-beta");
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
     #[test]
     fn test_config_hook() {
         let hook = ConfigHook::new("secrets-scanner")
@@ -293,6 +293,11 @@ impl<E: PolicyEvaluator> PolicyAdapter<E> {
         self
     }
 
+    /// Get the default context
+    pub fn default_context(&self) -> &PolicyContext {
+        &self.default_context
+    }
+
     /// Enable caching of policy decisions
     pub fn with_caching(mut self, ttl_seconds: u64) -> Self {
         self.enable_caching = true;
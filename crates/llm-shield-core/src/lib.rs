@@ -18,18 +18,36 @@
 //! 4. **Observability**: Comprehensive tracing and metrics
 //! 5. **Error Context**: Rich error types with context
 
+pub mod conversation;
 pub mod error;
+pub mod json;
+pub mod nli;
+pub mod offsets;
+pub mod reload;
 pub mod result;
 pub mod scanner;
+pub mod streaming;
 pub mod types;
 pub mod vault;
 pub mod adapters;
 
 // Re-exports for convenience
 pub use async_trait::async_trait;
+pub use conversation::{
+    Conversation, ConversationScanResult, ConversationState, Message, MessageScanResult,
+    MessageScanner, Role,
+};
 pub use error::{Error, Result};
+pub use json::{JsonLeaf, JsonScanMode, JsonScanResult, JSON_POINTER_KEY};
+pub use nli::NliScores;
+pub use offsets::OffsetMap;
+pub use reload::{watch_file, FileFingerprint, ReloadStatus, Reloadable, WatchHandle};
 pub use result::{Entity, RiskFactor, ScanResult, Severity};
-pub use scanner::{InputScanner, OutputScanner, Scanner, ScannerPipeline, ScannerType};
+pub use scanner::{
+    ErrorPolicy, InputScanner, OutputScanner, Scanner, ScannerPipeline, ScannerType,
+    TimeoutPolicy,
+};
+pub use streaming::{StreamChunk, StreamingScanner};
 pub use types::{ScannerConfig, ScannerMetadata, ScannerCategory, PerformanceInfo};
 pub use vault::Vault;
 
@@ -0,0 +1,33 @@
+//! Natural language inference scores
+//!
+//! Shared by the NLI models in `llm-shield-models` and the scanners that
+//! consume them, so scanners can take an NLI backend without the `ml`
+//! feature.
+
+use serde::{Deserialize, Serialize};
+
+/// Class probabilities from an NLI model for a (premise, hypothesis) pair
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct NliScores {
+    /// Probability that the premise entails the hypothesis
+    pub entailment: f32,
+    /// Probability that the premise neither entails nor contradicts the hypothesis
+    pub neutral: f32,
+    /// Probability that the premise contradicts the hypothesis
+    pub contradiction: f32,
+}
+
+impl NliScores {
+    /// Entailment probability renormalized against contradiction only
+    ///
+    /// This is the standard zero-shot classification score: the neutral
+    /// class is dropped and entailment is compared to contradiction.
+    pub fn zero_shot_score(&self) -> f32 {
+        let total = self.entailment + self.contradiction;
+        if total <= 0.0 {
+            0.0
+        } else {
+            self.entailment / total
+        }
+    }
+}
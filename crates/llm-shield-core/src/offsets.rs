@@ -0,0 +1,333 @@
+//! Offset mapping between original and sanitized text
+//!
+//! When scanners are chained, each scanner sees the previous scanner's
+//! `sanitized_text`, so the offsets it reports refer to that intermediate
+//! text. [`OffsetMap`] records how one text was rewritten into the next and
+//! maps offsets back, so entities can be reported in original coordinates.
+//!
+//! All offsets are byte offsets, matching the offsets scanners report.
+
+/// A rewritten region: `input[in_start..in_end]` became `output[out_start..out_end]`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Edit {
+    in_start: usize,
+    in_end: usize,
+    out_start: usize,
+    out_end: usize,
+}
+
+/// Mapping from offsets in a rewritten text back to the text it came from
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OffsetMap {
+    /// Sorted, non-overlapping, non-adjacent edits
+    edits: Vec<Edit>,
+}
+
+impl OffsetMap {
+    /// Mapping for an unchanged text
+    pub fn identity() -> Self {
+        Self::default()
+    }
+
+    /// Build the mapping from `input` to `output`
+    ///
+    /// `hints` are the `(start, end)` spans in `input` that the scanner
+    /// reported. When every change falls inside a hinted span and the
+    /// unchanged text between spans can be placed unambiguously, each
+    /// replacement is located exactly. Otherwise the mapping falls back to a
+    /// single edit between the common prefix and common suffix. That fallback
+    /// is coarser, but positions still map into the changed region.
+    pub fn between(input: &str, output: &str, hints: &[(usize, usize)]) -> Self {
+        if input == output {
+            return Self::identity();
+        }
+
+        Self::from_hints(input, output, hints).unwrap_or_else(|| Self::from_affixes(input, output))
+    }
+
+    /// Whether the mapping leaves every offset unchanged
+    pub fn is_identity(&self) -> bool {
+        self.edits.is_empty()
+    }
+
+    /// Map the start of a span in the output back to the input
+    ///
+    /// A start inside a replacement maps to the start of the replaced text.
+    pub fn map_start(&self, pos: usize) -> usize {
+        self.map(pos, false)
+    }
+
+    /// Map the (exclusive) end of a span in the output back to the input
+    ///
+    /// An end inside a replacement maps to the end of the replaced text.
+    pub fn map_end(&self, pos: usize) -> usize {
+        self.map(pos, true)
+    }
+
+    /// Map an offset in the input forward to the output
+    ///
+    /// An offset inside a rewritten region maps to the start of its
+    /// replacement; use [`unchanged_floor`](Self::unchanged_floor) first to
+    /// split text only where the mapping is exact.
+    pub fn map_forward(&self, pos: usize) -> usize {
+        let (mut in_cursor, mut out_cursor) = (0, 0);
+        for edit in &self.edits {
+            if pos <= edit.in_start {
+                return out_cursor + (pos - in_cursor);
+            }
+            if pos < edit.in_end {
+                return edit.out_start;
+            }
+            in_cursor = edit.in_end;
+            out_cursor = edit.out_end;
+        }
+        out_cursor + pos.saturating_sub(in_cursor)
+    }
+
+    /// The largest input offset at or before `pos` that is not inside a
+    /// rewritten region
+    ///
+    /// Splitting the input there splits the output at
+    /// [`map_forward`](Self::map_forward) of the same offset, with each
+    /// replacement entirely on one side.
+    pub fn unchanged_floor(&self, pos: usize) -> usize {
+        self.edits
+            .iter()
+            .find(|edit| edit.in_start < pos && pos < edit.in_end)
+            .map_or(pos, |edit| edit.in_start)
+    }
+
+    fn map(&self, pos: usize, is_end: bool) -> usize {
+        let (mut in_cursor, mut out_cursor) = (0, 0);
+        for edit in &self.edits {
+            if pos <= edit.out_start {
+                return in_cursor + (pos - out_cursor);
+            }
+            if pos < edit.out_end {
+                return if is_end { edit.in_end } else { edit.in_start };
+            }
+            in_cursor = edit.in_end;
+            out_cursor = edit.out_end;
+        }
+        in_cursor + pos.saturating_sub(out_cursor)
+    }
+
+    /// Locate each replacement using the scanner's reported spans
+    ///
+    /// The text before the first span and after the last is anchored to the
+    /// ends of `output`. Each unchanged gap between spans is searched for
+    /// from both ends; if the earliest and latest placements differ (a
+    /// replacement contains the text that follows it), the split is
+    /// ambiguous and `None` is returned.
+    fn from_hints(input: &str, output: &str, hints: &[(usize, usize)]) -> Option<Self> {
+        let regions = merge_spans(input, hints);
+        let (first, last) = (regions.first()?, regions.last()?);
+
+        let head = &input[..first.0];
+        let tail = &input[last.1..];
+        if head.len() + tail.len() > output.len()
+            || !output.starts_with(head)
+            || !output.ends_with(tail)
+        {
+            return None;
+        }
+        let (lo, hi) = (head.len(), output.len() - tail.len());
+
+        let gaps: Vec<&str> = regions
+            .windows(2)
+            .map(|pair| &input[pair[0].1..pair[1].0])
+            .collect();
+
+        let mut earliest = Vec::with_capacity(gaps.len());
+        let mut cursor = lo;
+        for gap in &gaps {
+            let pos = cursor + output[cursor..hi].find(gap)?;
+            earliest.push(pos);
+            cursor = pos + gap.len();
+        }
+
+        let mut latest = vec![0; gaps.len()];
+        let mut cursor = hi;
+        for (i, gap) in gaps.iter().enumerate().rev() {
+            let pos = lo + output[lo..cursor].rfind(gap)?;
+            latest[i] = pos;
+            cursor = pos;
+        }
+
+        if earliest != latest {
+            return None;
+        }
+
+        let mut edits = Vec::with_capacity(regions.len());
+        let mut out_start = lo;
+        for (i, &(start, end)) in regions.iter().enumerate() {
+            // The replacement ends where the next unchanged gap begins
+            let out_end = earliest.get(i).copied().unwrap_or(hi);
+            if input[start..end] != output[out_start..out_end] {
+                edits.push(Edit {
+                    in_start: start,
+                    in_end: end,
+                    out_start,
+                    out_end,
+                });
+            }
+            if let Some(gap) = gaps.get(i) {
+                out_start = out_end + gap.len();
+            }
+        }
+
+        Some(Self { edits })
+    }
+
+    /// Single edit between the longest common prefix and suffix
+    fn from_affixes(input: &str, output: &str) -> Self {
+        let (a, b) = (input.as_bytes(), output.as_bytes());
+
+        let mut prefix = a.iter().zip(b).take_while(|(x, y)| x == y).count();
+        while !input.is_char_boundary(prefix) || !output.is_char_boundary(prefix) {
+            prefix -= 1;
+        }
+
+        let max_suffix = a.len().min(b.len()) - prefix;
+        let mut suffix = a
+            .iter()
+            .rev()
+            .zip(b.iter().rev())
+            .take(max_suffix)
+            .take_while(|(x, y)| x == y)
+            .count();
+        while !input.is_char_boundary(a.len() - suffix) || !output.is_char_boundary(b.len() - suffix)
+        {
+            suffix -= 1;
+        }
+
+        Self {
+            edits: vec![Edit {
+                in_start: prefix,
+                in_end: a.len() - suffix,
+                out_start: prefix,
+                out_end: b.len() - suffix,
+            }],
+        }
+    }
+}
+
+/// Sort, validate and merge overlapping or adjacent spans
+fn merge_spans(input: &str, spans: &[(usize, usize)]) -> Vec<(usize, usize)> {
+    let mut spans: Vec<(usize, usize)> = spans
+        .iter()
+        .copied()
+        .filter(|&(start, end)| {
+            start < end
+                && end <= input.len()
+                && input.is_char_boundary(start)
+                && input.is_char_boundary(end)
+        })
+        .collect();
+    spans.sort_unstable();
+
+    let mut merged: Vec<(usize, usize)> = Vec::with_capacity(spans.len());
+    for (start, end) in spans {
+        match merged.last_mut() {
+            Some(last) if start <= last.1 => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_identity() {
+        let map = OffsetMap::between("hello", "hello", &[]);
+        assert!(map.is_identity());
+        assert_eq!(map.map_start(3), 3);
+    }
+
+    #[test]
+    fn test_hinted_replacements() {
+        let input = "key=sk-abc123 mail=a@b.io end";
+        let output = "key=[SECRET] mail=[EMAIL] end";
+        let map = OffsetMap::between(input, output, &[(4, 13), (19, 25)]);
+
+        // Unchanged text maps through shifts
+        assert_eq!(map.map_start(0), 0);
+        assert_eq!(map.map_start(13), 14); // "mail="
+        assert_eq!(&input[map.map_start(26)..], "end");
+
+        // Replacement tokens map to the original spans
+        assert_eq!((map.map_start(4), map.map_end(12)), (4, 13));
+        assert_eq!((map.map_start(19), map.map_end(25)), (19, 25));
+
+        // A span inside a token widens to the whole original span
+        assert_eq!((map.map_start(5), map.map_end(8)), (4, 13));
+    }
+
+    #[test]
+    fn test_forward_mapping() {
+        let input = "key=sk-abc123 mail=a@b.io end";
+        let output = "key=[SECRET] mail=[EMAIL] end";
+        let map = OffsetMap::between(input, output, &[(4, 13), (19, 25)]);
+
+        assert_eq!(map.map_forward(4), 4);
+        assert_eq!(&output[map.map_forward(13)..], " mail=[EMAIL] end");
+        assert_eq!(&output[map.map_forward(26)..], "end");
+
+        // Inside a replaced span: split before it
+        assert_eq!(map.unchanged_floor(8), 4);
+        assert_eq!(map.unchanged_floor(13), 13);
+        assert_eq!(map.map_forward(8), 4);
+    }
+
+    #[test]
+    fn test_fallback_without_hints() {
+        let input = "call 555-0100 now";
+        let output = "call [PHONE] now";
+        let map = OffsetMap::between(input, output, &[]);
+
+        assert_eq!(map.map_start(0), 0);
+        assert_eq!((map.map_start(5), map.map_end(12)), (5, 13));
+        assert_eq!(&input[map.map_start(13)..], "now");
+    }
+
+    #[test]
+    fn test_wrong_hints_fall_back() {
+        let input = "abc secret xyz";
+        let output = "abc [X] xyz";
+        // Hint points at unchanged text; the real change is elsewhere
+        let map = OffsetMap::between(input, output, &[(0, 3)]);
+        assert_eq!((map.map_start(4), map.map_end(7)), (4, 10));
+    }
+
+    #[test]
+    fn test_replacement_containing_next_segment() {
+        let input = "name=Bob role=admin ok";
+        // The first replacement contains " role=", the text after its span
+        let output = "name=[NAME role=x] role=[ROLE] ok";
+        let map = OffsetMap::between(input, output, &[(5, 8), (14, 19)]);
+
+        // " role=" inside the first token is not the original " role="
+        assert_eq!((map.map_start(10), map.map_end(16)), (5, 19));
+        assert_eq!(&input[map.map_start(0)..5], "name=");
+        assert_eq!(&input[map.map_start(output.len() - 2)..], "ok");
+
+        // With a single occurrence of the gap the split is exact
+        let output = "name=[NAME role] role=[ROLE] ok";
+        let map = OffsetMap::between(input, output, &[(5, 8), (14, 19)]);
+        assert_eq!((map.map_start(5), map.map_end(16)), (5, 8));
+        assert_eq!((map.map_start(22), map.map_end(28)), (14, 19));
+    }
+
+    #[test]
+    fn test_multibyte_boundaries() {
+        let input = "naïve café";
+        let output = "naïve [X]";
+        let map = OffsetMap::between(input, output, &[]);
+        let end = map.map_end(output.len());
+        assert!(input.is_char_boundary(map.map_start(6)));
+        assert_eq!(end, input.len());
+    }
+}
@@ -0,0 +1,330 @@
+//! Hot-reloadable values
+//!
+//! [`Reloadable`] holds a value, such as a scanner set, that readers load
+//! lock-free while a reload swaps in a replacement. Replacements are built
+//! and validated before the swap, so a failed reload leaves the current value
+//! in place; the value it replaced is kept for [`Reloadable::rollback`].
+//!
+//! [`watch_file`] detects changes to a file by polling its modification time
+//! and size, which works the same on every platform and filesystem.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! let scanners = Arc::new(Reloadable::new(build_scanners(&config)?));
+//!
+//! // Readers take a consistent snapshot per request
+//! let snapshot = scanners.load();
+//!
+//! // Reload when the file changes; errors keep the current scanners
+//! let target = scanners.clone();
+//! let _watch = watch_file("shield.yaml", Duration::from_secs(2), move |path| {
+//!     let _ = target.reload(|_| build_scanners(&load(path)?));
+//! });
+//! ```
+
+use crate::{Error, Result};
+use arc_swap::ArcSwap;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Reload history of a [`Reloadable`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReloadStatus {
+    /// Version of the current value, starting at 1
+    pub version: u64,
+
+    /// Successful reloads, including rollbacks
+    pub reloads: u64,
+
+    /// Failed reload attempts
+    pub failures: u64,
+
+    /// Unix time in milliseconds of the last successful reload
+    pub last_reload_ms: Option<u64>,
+
+    /// Error of the last failed reload, cleared by a successful one
+    pub last_error: Option<String>,
+}
+
+struct History<T> {
+    previous: Option<Arc<T>>,
+    status: ReloadStatus,
+}
+
+/// A value that can be atomically replaced while in use
+///
+/// `load` never blocks and returns a snapshot that stays valid for as long
+/// as the caller holds it, even if a reload happens meanwhile. Reloads are
+/// serialized with each other.
+pub struct Reloadable<T> {
+    current: ArcSwap<T>,
+    history: Mutex<History<T>>,
+}
+
+impl<T> Reloadable<T> {
+    /// Wrap an initial value as version 1
+    pub fn new(value: T) -> Self {
+        Self {
+            current: ArcSwap::from_pointee(value),
+            history: Mutex::new(History {
+                previous: None,
+                status: ReloadStatus {
+                    version: 1,
+                    reloads: 0,
+                    failures: 0,
+                    last_reload_ms: None,
+                    last_error: None,
+                },
+            }),
+        }
+    }
+
+    /// Snapshot of the current value
+    pub fn load(&self) -> Arc<T> {
+        self.current.load_full()
+    }
+
+    /// Version of the current value
+    pub fn version(&self) -> u64 {
+        self.history().status.version
+    }
+
+    /// Reload history
+    pub fn status(&self) -> ReloadStatus {
+        self.history().status.clone()
+    }
+
+    /// Replace the value with the one built from the current value
+    ///
+    /// `build` must fully validate the replacement; if it fails, the current
+    /// value is kept, the failure is recorded and the error returned.
+    /// Returns the new version.
+    pub fn reload<F>(&self, build: F) -> Result<u64>
+    where
+        F: FnOnce(&T) -> Result<T>,
+    {
+        let mut history = self.history();
+        let current = self.current.load_full();
+
+        match build(&current) {
+            Ok(next) => {
+                self.current.store(Arc::new(next));
+                history.previous = Some(current);
+                Ok(Self::record_success(&mut history))
+            }
+            Err(e) => {
+                history.status.failures += 1;
+                history.status.last_error = Some(e.to_string());
+                Err(e)
+            }
+        }
+    }
+
+    /// Replace the value unconditionally, returning the new version
+    pub fn replace(&self, value: T) -> u64 {
+        let mut history = self.history();
+        history.previous = Some(self.current.swap(Arc::new(value)));
+        Self::record_success(&mut history)
+    }
+
+    /// Restore the value replaced by the last reload
+    ///
+    /// The rolled-back value gets a new version; rolling back twice restores
+    /// the reloaded value again.
+    pub fn rollback(&self) -> Result<u64> {
+        let mut history = self.history();
+        let previous = history
+            .previous
+            .take()
+            .ok_or_else(|| Error::config("No previous value to roll back to"))?;
+
+        history.previous = Some(self.current.swap(previous));
+        Ok(Self::record_success(&mut history))
+    }
+
+    fn record_success(history: &mut History<T>) -> u64 {
+        let status = &mut history.status;
+        status.version += 1;
+        status.reloads += 1;
+        status.last_error = None;
+        status.last_reload_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .ok()
+            .map(|elapsed| elapsed.as_millis() as u64);
+        status.version
+    }
+
+    fn history(&self) -> MutexGuard<'_, History<T>> {
+        // A panicking reload cannot leave the history half-updated
+        self.history.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl<T: Default> Default for Reloadable<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T> std::fmt::Debug for Reloadable<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Reloadable")
+            .field("status", &self.status())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Cheap identity of a file's contents: modification time and size
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FileFingerprint {
+    modified: Option<SystemTime>,
+    len: u64,
+}
+
+impl FileFingerprint {
+    /// Fingerprint of `path`, or `None` if it cannot be read
+    pub fn of(path: impl AsRef<Path>) -> Option<Self> {
+        let metadata = std::fs::metadata(path).ok()?;
+        Some(Self {
+            modified: metadata.modified().ok(),
+            len: metadata.len(),
+        })
+    }
+}
+
+/// Background watch task, stopped when dropped
+#[derive(Debug)]
+pub struct WatchHandle {
+    task: tokio::task::JoinHandle<()>,
+}
+
+impl WatchHandle {
+    /// Run `future` as a watch task
+    pub fn spawn<F>(future: F) -> Self
+    where
+        F: std::future::Future<Output = ()> + Send + 'static,
+    {
+        Self {
+            task: tokio::spawn(future),
+        }
+    }
+
+    /// Whether the task has ended
+    pub fn is_finished(&self) -> bool {
+        self.task.is_finished()
+    }
+
+    /// Stop watching
+    pub fn stop(self) {}
+}
+
+impl Drop for WatchHandle {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Call `on_change` whenever the file at `path` changes
+///
+/// The file is checked every `interval`. While it is missing, e.g. in the
+/// middle of an editor's write-and-rename, no change is reported; it is
+/// reported once the file is back with a different fingerprint.
+///
+/// Must be called within a Tokio runtime.
+pub fn watch_file<F>(path: impl Into<PathBuf>, interval: Duration, mut on_change: F) -> WatchHandle
+where
+    F: FnMut(&Path) + Send + 'static,
+{
+    let path = path.into();
+    WatchHandle::spawn(async move {
+        let mut last = FileFingerprint::of(&path);
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        ticker.tick().await;
+
+        loop {
+            ticker.tick().await;
+            let Some(fingerprint) = FileFingerprint::of(&path) else {
+                continue;
+            };
+            if last != Some(fingerprint) {
+                last = Some(fingerprint);
+                on_change(&path);
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reload_swaps_and_versions() {
+        let value = Reloadable::new(vec![1]);
+        let snapshot = value.load();
+
+        let version = value.reload(|current| Ok([current.as_slice(), &[2]].concat())).unwrap();
+        assert_eq!(version, 2);
+        assert_eq!(*value.load(), vec![1, 2]);
+        // Earlier snapshots are unaffected
+        assert_eq!(*snapshot, vec![1]);
+        assert_eq!(value.status().reloads, 1);
+    }
+
+    #[test]
+    fn test_failed_reload_keeps_current() {
+        let value = Reloadable::new("good".to_string());
+
+        let result = value.reload(|_| Err(Error::config("bad pattern")));
+        assert!(result.is_err());
+        assert_eq!(*value.load(), "good");
+
+        let status = value.status();
+        assert_eq!(status.version, 1);
+        assert_eq!(status.failures, 1);
+        assert!(status.last_error.unwrap().contains("bad pattern"));
+
+        value.replace("newer".to_string());
+        assert!(value.status().last_error.is_none());
+    }
+
+    #[test]
+    fn test_rollback() {
+        let value = Reloadable::new(1);
+        assert!(value.rollback().is_err());
+
+        value.replace(2);
+        assert_eq!(value.rollback().unwrap(), 3);
+        assert_eq!(*value.load(), 1);
+        value.rollback().unwrap();
+        assert_eq!(*value.load(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_watch_file_reports_changes() {
+        let path = std::env::temp_dir().join(format!("shield-watch-{}.txt", std::process::id()));
+        std::fs::write(&path, "a").unwrap();
+
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let handle = watch_file(&path, Duration::from_millis(20), move |_| {
+            let _ = tx.send(());
+        });
+
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        assert!(rx.try_recv().is_err());
+
+        std::fs::write(&path, "changed").unwrap();
+        tokio::time::timeout(Duration::from_secs(2), rx.recv())
+            .await
+            .unwrap()
+            .unwrap();
+
+        handle.stop();
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -41,6 +41,21 @@ pub struct ScanResult {
 }
 
 impl ScanResult {
+    /// Metadata key listing scanners that exceeded their deadline
+    ///
+    /// [`combine`](Self::combine) concatenates this list across results
+    /// instead of letting the last result overwrite it.
+    pub const TIMED_OUT_SCANNERS: &'static str = "timed_out_scanners";
+
+    /// Metadata key listing scanners whose errors were isolated by the pipeline
+    ///
+    /// Concatenated by [`combine`](Self::combine) like
+    /// [`TIMED_OUT_SCANNERS`](Self::TIMED_OUT_SCANNERS).
+    pub const FAILED_SCANNERS: &'static str = "failed_scanners";
+
+    /// Metadata keys whose list values are concatenated when combining
+    const LIST_KEYS: [&'static str; 2] = [Self::TIMED_OUT_SCANNERS, Self::FAILED_SCANNERS];
+
     /// Create a new scan result
     pub fn new(sanitized_text: String, is_valid: bool, risk_score: f32) -> Self {
         Self {
@@ -123,14 +138,38 @@ impl ScanResult {
             max_risk,
         );
 
+        let mut lists: HashMap<&str, Vec<serde_json::Value>> = HashMap::new();
         for result in results {
             combined.entities.extend(result.entities);
             combined.risk_factors.extend(result.risk_factors);
             for (k, v) in result.metadata {
+                if let Some(key) = Self::LIST_KEYS.iter().find(|key| **key == k) {
+                    if let serde_json::Value::Array(names) = v {
+                        lists.entry(key).or_default().extend(names);
+                    }
+                    continue;
+                }
                 combined.metadata.insert(k, v);
             }
         }
 
+        for (key, names) in lists {
+            combined.metadata.insert(key.to_string(), names.into());
+        }
+
+        combined
+    }
+
+    /// Combine results from a chained-sanitization pipeline
+    ///
+    /// Like [`combine`](Self::combine), but the combined text is the last
+    /// result's `sanitized_text`, which carries every scanner's redactions.
+    pub fn combine_chained(results: Vec<ScanResult>) -> Self {
+        let sanitized_text = results.last().map(|r| r.sanitized_text.clone());
+        let mut combined = Self::combine(results);
+        if let Some(text) = sanitized_text {
+            combined.sanitized_text = text;
+        }
         combined
     }
 }
@@ -290,6 +329,38 @@ mod tests {
         assert!(!combined.is_valid);
     }
 
+    #[test]
+    fn test_combine_merges_timed_out_scanners() {
+        let results = vec![
+            ScanResult::pass("a".to_string())
+                .with_metadata(ScanResult::TIMED_OUT_SCANNERS, vec!["slow"]),
+            ScanResult::pass("a".to_string()),
+            ScanResult::pass("a".to_string())
+                .with_metadata(ScanResult::TIMED_OUT_SCANNERS, vec!["slower"])
+                .with_metadata(ScanResult::FAILED_SCANNERS, vec!["broken"]),
+        ];
+
+        let combined = ScanResult::combine(results);
+        assert_eq!(
+            combined.metadata[ScanResult::TIMED_OUT_SCANNERS],
+            serde_json::json!(["slow", "slower"])
+        );
+        assert_eq!(
+            combined.metadata[ScanResult::FAILED_SCANNERS],
+            serde_json::json!(["broken"])
+        );
+    }
+
+    #[test]
+    fn test_combine_chained_keeps_final_text() {
+        let r1 = ScanResult::fail("key=[SECRET] mail=a@b.io".to_string(), 1.0);
+        let r2 = ScanResult::fail("key=[SECRET] mail=[EMAIL]".to_string(), 0.8);
+
+        let combined = ScanResult::combine_chained(vec![r1, r2]);
+        assert_eq!(combined.sanitized_text, "key=[SECRET] mail=[EMAIL]");
+        assert_eq!(combined.risk_score, 1.0);
+    }
+
     #[test]
     fn test_severity_ordering() {
         assert!(Severity::Critical > Severity::High);
@@ -8,9 +8,16 @@
 //! - Type-safe configuration
 //! - Observability built-in
 
-use crate::{Error, Result, ScanResult, Vault};
+use crate::{
+    Error, OffsetMap, PerformanceInfo, Result, RiskFactor, ScanResult, Severity, Vault,
+};
 use async_trait::async_trait;
+use futures::stream::{self, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::Instant;
 
 /// Core scanner trait
 ///
@@ -80,6 +87,45 @@ pub trait Scanner: Send + Sync {
         "No description provided"
     }
 
+    /// Performance characteristics
+    ///
+    /// Used by [`ScannerPipeline::with_cost_ordering`] to run cheap scanners
+    /// first. The default describes a fast, local, pattern-based scanner.
+    fn performance(&self) -> PerformanceInfo {
+        PerformanceInfo::default()
+    }
+
+    /// Bytes of trailing text to hold back when scanning a stream
+    ///
+    /// A scanner that can judge partial text returns the longest match it
+    /// can report; [`StreamingScanner`](crate::StreamingScanner) then holds
+    /// back that much text in case the next chunk completes a match.
+    /// `None` (the default) means the scanner needs the complete text.
+    fn stream_lookback(&self) -> Option<usize> {
+        None
+    }
+
+    /// Start of a match that may still be growing at the end of `text`
+    ///
+    /// A scanner with open-ended patterns returns where a possibly
+    /// incomplete match ending at the end of the text begins, and a stream
+    /// holds back everything from there regardless of
+    /// [`stream_lookback`](Self::stream_lookback). `None` (the default)
+    /// means every match fits in the lookback.
+    fn open_match_start(&self, _text: &str) -> Option<usize> {
+        None
+    }
+
+    /// Whether this scanner reads or writes request state in the vault
+    ///
+    /// Such a scanner's result is not determined by the text alone (e.g.
+    /// references supplied per request), or scanning has side effects the
+    /// caller relies on (e.g. anonymization mappings), so its results must
+    /// not be cached.
+    fn uses_vault(&self) -> bool {
+        false
+    }
+
     /// Whether this scanner requires async execution
     ///
     /// Some scanners (e.g., URL checking) must be async.
@@ -130,6 +176,30 @@ pub enum ScannerType {
     Bidirectional,
 }
 
+/// What a pipeline reports for a scanner that exceeds its deadline
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimeoutPolicy {
+    /// Treat the scanner as passed and leave the text unchanged
+    FailOpen,
+    /// Treat the scanner as failed with maximum risk
+    #[default]
+    FailClosed,
+}
+
+/// What a pipeline does when a scanner returns an error
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorPolicy {
+    /// Abort the whole scan with the scanner's error
+    #[default]
+    Propagate,
+    /// Treat the scanner as passed and leave the text unchanged
+    FailOpen,
+    /// Treat the scanner as failed with maximum risk
+    FailClosed,
+}
+
 /// Scanner pipeline for composing multiple scanners
 ///
 /// ## Enterprise Pattern
@@ -139,10 +209,34 @@ pub enum ScannerType {
 /// - Parallel execution option
 /// - Short-circuit on high risk
 /// - Result aggregation
+/// - Chained sanitization (each scanner sees the previous scanner's output)
+/// - Per-scanner and total deadlines
+///
+/// ## Timeouts
+///
+/// A scanner that exceeds its deadline is cancelled (its future is dropped)
+/// and replaced by a result according to its [`TimeoutPolicy`]. The result
+/// lists the scanner under [`ScanResult::TIMED_OUT_SCANNERS`] in its
+/// metadata, which [`ScanResult::combine`] carries into the combined result.
+///
+/// ## Errors
+///
+/// By default the first scanner error aborts the scan. With an
+/// [`ErrorPolicy`] of `FailOpen` or `FailClosed`, the error is isolated to
+/// that scanner and it is listed under [`ScanResult::FAILED_SCANNERS`].
 pub struct ScannerPipeline {
     scanners: Vec<Arc<dyn Scanner>>,
     short_circuit: bool,
     short_circuit_threshold: f32,
+    chain_sanitization: bool,
+    scanner_timeout: Option<Duration>,
+    total_timeout: Option<Duration>,
+    timeout_policy: TimeoutPolicy,
+    timeout_overrides: HashMap<String, Duration>,
+    policy_overrides: HashMap<String, TimeoutPolicy>,
+    max_concurrent: Option<usize>,
+    cost_ordering: bool,
+    error_policy: ErrorPolicy,
 }
 
 impl ScannerPipeline {
@@ -152,6 +246,15 @@ impl ScannerPipeline {
             scanners: Vec::new(),
             short_circuit: false,
             short_circuit_threshold: 0.9,
+            chain_sanitization: false,
+            scanner_timeout: None,
+            total_timeout: None,
+            timeout_policy: TimeoutPolicy::default(),
+            timeout_overrides: HashMap::new(),
+            policy_overrides: HashMap::new(),
+            max_concurrent: None,
+            cost_ordering: false,
+            error_policy: ErrorPolicy::default(),
         }
     }
 
@@ -170,14 +273,164 @@ impl ScannerPipeline {
         self
     }
 
+    /// Enable chained sanitization
+    ///
+    /// Each scanner receives the previous scanner's `sanitized_text`, so
+    /// redactions accumulate instead of only the first one surviving. Entity
+    /// offsets are remapped to the original input, and each result's
+    /// `sanitized_text` is the cumulative text after that scanner.
+    ///
+    /// Chaining is inherently sequential: `execute_parallel` runs the
+    /// scanners in order when it is enabled.
+    pub fn with_chained_sanitization(mut self) -> Self {
+        self.chain_sanitization = true;
+        self
+    }
+
+    /// Whether chained sanitization is enabled
+    pub fn is_chained(&self) -> bool {
+        self.chain_sanitization
+    }
+
+    /// Set the default deadline for each scanner
+    pub fn with_scanner_timeout(mut self, timeout: Duration) -> Self {
+        self.scanner_timeout = Some(timeout);
+        self
+    }
+
+    /// Set the deadline for the whole pipeline
+    ///
+    /// Scanners still running when it passes are cancelled. In sequential
+    /// execution, scanners that have not started yet time out immediately.
+    pub fn with_total_timeout(mut self, timeout: Duration) -> Self {
+        self.total_timeout = Some(timeout);
+        self
+    }
+
+    /// Set the default policy for scanners that time out
+    pub fn with_timeout_policy(mut self, policy: TimeoutPolicy) -> Self {
+        self.timeout_policy = policy;
+        self
+    }
+
+    /// Override the deadline for the scanner with the given name
+    pub fn with_timeout_for<S: Into<String>>(mut self, scanner: S, timeout: Duration) -> Self {
+        self.timeout_overrides.insert(scanner.into(), timeout);
+        self
+    }
+
+    /// Override the timeout policy for the scanner with the given name
+    pub fn with_timeout_policy_for<S: Into<String>>(
+        mut self,
+        scanner: S,
+        policy: TimeoutPolicy,
+    ) -> Self {
+        self.policy_overrides.insert(scanner.into(), policy);
+        self
+    }
+
+    /// Limit how many scanners `execute_parallel` runs at once
+    pub fn with_max_concurrent(mut self, max_concurrent: usize) -> Self {
+        self.max_concurrent = Some(max_concurrent.max(1));
+        self
+    }
+
+    /// Run scanners cheapest first, by [`Scanner::performance`]
+    ///
+    /// Results are returned in execution order. Combined with short-circuit
+    /// evaluation, this lets cheap scanners stop the scan before expensive
+    /// ones start.
+    pub fn with_cost_ordering(mut self) -> Self {
+        self.cost_ordering = true;
+        self
+    }
+
+    /// Set what happens when a scanner returns an error
+    pub fn with_error_policy(mut self, policy: ErrorPolicy) -> Self {
+        self.error_policy = policy;
+        self
+    }
+
+    /// Scanners in execution order
+    fn ordered(&self) -> Vec<&Arc<dyn Scanner>> {
+        let mut scanners: Vec<_> = self.scanners.iter().collect();
+        if self.cost_ordering {
+            // Stable, so equally cheap scanners keep insertion order
+            scanners.sort_by_key(|s| s.performance().cost_key());
+        }
+        scanners
+    }
+
+    fn should_stop(&self, result: &ScanResult) -> bool {
+        self.short_circuit && result.risk_score >= self.short_circuit_threshold
+    }
+
+    /// Deadline for a scanner, bounded by the remaining total budget
+    fn limit_for(&self, scanner: &str, deadline: Option<Instant>) -> Option<Duration> {
+        let own = self
+            .timeout_overrides
+            .get(scanner)
+            .copied()
+            .or(self.scanner_timeout);
+        let remaining = deadline.map(|d| d.saturating_duration_since(Instant::now()));
+
+        match (own, remaining) {
+            (Some(own), Some(remaining)) => Some(own.min(remaining)),
+            (own, remaining) => own.or(remaining),
+        }
+    }
+
+    fn policy_for(&self, scanner: &str) -> TimeoutPolicy {
+        self.policy_overrides
+            .get(scanner)
+            .copied()
+            .unwrap_or(self.timeout_policy)
+    }
+
+    fn deadline(&self) -> Option<Instant> {
+        self.total_timeout.map(|t| Instant::now() + t)
+    }
+
+    /// Run one scanner, cancelling it if it exceeds its deadline and
+    /// applying the error policy to failures
+    async fn run_scanner(
+        &self,
+        scanner: &Arc<dyn Scanner>,
+        input: &str,
+        vault: &Vault,
+        deadline: Option<Instant>,
+    ) -> Result<ScanResult> {
+        let limit = self.limit_for(scanner.name(), deadline);
+        let policy = self.policy_for(scanner.name());
+
+        match scan_with_limit(scanner.as_ref(), input, vault, limit, policy).await {
+            Ok(result) => Ok(result),
+            Err(err) if self.error_policy == ErrorPolicy::Propagate => Err(err),
+            Err(err) => {
+                tracing::warn!(
+                    scanner = scanner.name(),
+                    error = %err,
+                    policy = ?self.error_policy,
+                    "Scanner failed"
+                );
+                Ok(failed_result(scanner.name(), input, &err, self.error_policy))
+            }
+        }
+    }
+
     /// Execute pipeline sequentially
     pub async fn execute(&self, input: &str, vault: &Vault) -> Result<Vec<ScanResult>> {
+        if self.chain_sanitization {
+            return self.execute_chained(input, vault).await;
+        }
+
         let mut results = Vec::new();
+        let deadline = self.deadline();
 
-        for scanner in &self.scanners {
-            let result = scanner.scan(input, vault).await?;
+        for scanner in self.ordered() {
+            let result = self.run_scanner(scanner, input, vault, deadline).await?;
 
-            if self.short_circuit && result.risk_score >= self.short_circuit_threshold {
+            if self.should_stop(&result) {
                 results.push(result);
                 break;
             }
@@ -188,36 +441,175 @@ impl ScannerPipeline {
         Ok(results)
     }
 
-    /// Execute pipeline in parallel
+    /// Execute pipeline with chained sanitization
+    async fn execute_chained(&self, input: &str, vault: &Vault) -> Result<Vec<ScanResult>> {
+        let mut results = Vec::new();
+        let mut current = input.to_string();
+        // Maps from each rewritten text back to the text before it
+        let mut maps: Vec<OffsetMap> = Vec::new();
+        let deadline = self.deadline();
+
+        for scanner in self.ordered() {
+            let mut result = self.run_scanner(scanner, &current, vault, deadline).await?;
+
+            // Spans in the scanner's input locate its replacements
+            let hints: Vec<(usize, usize)> =
+                result.entities.iter().map(|e| (e.start, e.end)).collect();
+
+            for entity in &mut result.entities {
+                let (mut start, mut end) = (entity.start, entity.end);
+                for map in maps.iter().rev() {
+                    start = map.map_start(start);
+                    end = map.map_end(end);
+                }
+                entity.start = start;
+                entity.end = end.max(start);
+            }
+
+            if result.sanitized_text != current {
+                maps.push(OffsetMap::between(&current, &result.sanitized_text, &hints));
+                current = result.sanitized_text.clone();
+            }
+
+            let stop = self.should_stop(&result);
+            results.push(result);
+            if stop {
+                break;
+            }
+        }
+
+        Ok(results)
+    }
+
+    /// Execute pipeline concurrently
     ///
-    /// All scanners run concurrently. Useful for I/O-bound scanners.
+    /// Scanners are polled together on the calling task, at most
+    /// `max_concurrent` at a time (all of them when unset), and start in
+    /// execution order as slots free up. Nothing is spawned, so this overlaps
+    /// I/O waits but not CPU-bound work. All scanners share the pipeline's
+    /// total timeout, counted from the call.
+    ///
+    /// Results are handled in completion order. With short-circuit enabled,
+    /// the first result at or above the threshold ends the scan: scanners
+    /// still running or not yet started are dropped, and only the results
+    /// already received are returned. Under [`ErrorPolicy::Propagate`] the
+    /// first scanner error ends the scan the same way and is returned. The
+    /// returned results are sorted back into execution order.
+    ///
+    /// With chained sanitization enabled, scanners run sequentially instead.
     pub async fn execute_parallel(&self, input: &str, vault: &Vault) -> Result<Vec<ScanResult>> {
-        use futures::future::join_all;
+        if self.chain_sanitization {
+            return self.execute_chained(input, vault).await;
+        }
 
-        let futures: Vec<_> = self
-            .scanners
-            .iter()
-            .map(|scanner| {
-                let input = input.to_string();
-                let vault = vault.clone();
-                let scanner = Arc::clone(scanner);
-                async move { scanner.scan(&input, &vault).await }
-            })
+        let scanners = self.ordered();
+        let limit = self.max_concurrent.unwrap_or(scanners.len()).max(1);
+        let deadline = self.deadline();
+
+        // The scanner futures are collected before streaming rather than
+        // created inside the stream, which keeps this future provably `Send`
+        // for callers behind `async_trait`. Creating them does not start
+        // them, so `buffer_unordered` still bounds concurrency.
+        let pending: Vec<_> = scanners
+            .into_iter()
+            .enumerate()
+            .map(|(index, scanner)| (index, self.run_scanner(scanner, input, vault, deadline)))
+            .map(|(index, run)| async move { (index, run.await) })
             .collect();
+        let mut running = stream::iter(pending).buffer_unordered(limit);
 
-        let results: Vec<Result<ScanResult>> = join_all(futures).await;
+        let mut results = Vec::new();
+        while let Some((index, result)) = running.next().await {
+            let result = result?;
+            let stop = self.should_stop(&result);
+            results.push((index, result));
+            if stop {
+                break;
+            }
+        }
+        // Dropping the stream cancels whatever is still in flight
+        drop(running);
 
-        // Collect results, propagating first error
-        results.into_iter().collect()
+        results.sort_by_key(|(index, _)| *index);
+        Ok(results.into_iter().map(|(_, result)| result).collect())
     }
 
     /// Get aggregated result from pipeline
     pub async fn execute_aggregated(&self, input: &str, vault: &Vault) -> Result<ScanResult> {
         let results = self.execute(input, vault).await?;
-        Ok(ScanResult::combine(results))
+        if self.chain_sanitization {
+            Ok(ScanResult::combine_chained(results))
+        } else {
+            Ok(ScanResult::combine(results))
+        }
     }
 }
 
+/// Run a scanner under an optional deadline
+async fn scan_with_limit(
+    scanner: &dyn Scanner,
+    input: &str,
+    vault: &Vault,
+    limit: Option<Duration>,
+    policy: TimeoutPolicy,
+) -> Result<ScanResult> {
+    let Some(limit) = limit else {
+        return scanner.scan(input, vault).await;
+    };
+
+    match tokio::time::timeout(limit, scanner.scan(input, vault)).await {
+        Ok(result) => result,
+        Err(_) => {
+            tracing::warn!(
+                scanner = scanner.name(),
+                timeout_ms = limit.as_millis() as u64,
+                ?policy,
+                "Scanner timed out"
+            );
+            Ok(timed_out_result(scanner.name(), input, limit, policy))
+        }
+    }
+}
+
+/// Result standing in for a scanner that was cancelled at its deadline
+fn timed_out_result(
+    scanner: &str,
+    input: &str,
+    limit: Duration,
+    policy: TimeoutPolicy,
+) -> ScanResult {
+    let result = match policy {
+        TimeoutPolicy::FailOpen => ScanResult::pass(input.to_string()),
+        TimeoutPolicy::FailClosed => ScanResult::fail(input.to_string(), 1.0).with_risk_factor(
+            RiskFactor::new(
+                "scanner_timeout".to_string(),
+                format!("Scanner '{}' timed out after {}ms", scanner, limit.as_millis()),
+                Severity::Critical,
+                1.0,
+            ),
+        ),
+    };
+
+    result.with_metadata(ScanResult::TIMED_OUT_SCANNERS, vec![scanner])
+}
+
+/// Result standing in for a scanner whose error was isolated
+fn failed_result(scanner: &str, input: &str, err: &Error, policy: ErrorPolicy) -> ScanResult {
+    let result = match policy {
+        ErrorPolicy::FailClosed => ScanResult::fail(input.to_string(), 1.0).with_risk_factor(
+            RiskFactor::new(
+                "scanner_error".to_string(),
+                format!("Scanner '{}' failed: {}", scanner, err),
+                Severity::Critical,
+                1.0,
+            ),
+        ),
+        _ => ScanResult::pass(input.to_string()),
+    };
+
+    result.with_metadata(ScanResult::FAILED_SCANNERS, vec![scanner])
+}
+
 impl Default for ScannerPipeline {
     fn default() -> Self {
         Self::new()
@@ -302,6 +694,358 @@ mod tests {
         assert_eq!(results[0].risk_score, 0.95);
     }
 
+    /// Replaces every occurrence of a word and reports it as an entity
+    struct RedactScanner {
+        word: &'static str,
+        replacement: &'static str,
+    }
+
+    #[async_trait]
+    impl Scanner for RedactScanner {
+        fn name(&self) -> &str {
+            self.word
+        }
+
+        async fn scan(&self, input: &str, _vault: &Vault) -> Result<ScanResult> {
+            let mut result = ScanResult::pass(input.replace(self.word, self.replacement));
+            for (start, matched) in input.match_indices(self.word) {
+                result = result.with_entity(crate::Entity::new(
+                    self.word,
+                    matched,
+                    start,
+                    start + matched.len(),
+                    1.0,
+                ));
+            }
+            Ok(result)
+        }
+    }
+
+    fn redaction_pipeline() -> ScannerPipeline {
+        ScannerPipeline::new()
+            .add(Arc::new(RedactScanner {
+                word: "sk-abc123",
+                replacement: "[SECRET]",
+            }))
+            .add(Arc::new(RedactScanner {
+                word: "a@b.io",
+                replacement: "[EMAIL]",
+            }))
+    }
+
+    #[tokio::test]
+    async fn test_unchained_keeps_first_redaction_only() {
+        let vault = Vault::new();
+        let result = redaction_pipeline()
+            .execute_aggregated("key sk-abc123 mail a@b.io", &vault)
+            .await
+            .unwrap();
+
+        assert_eq!(result.sanitized_text, "key [SECRET] mail a@b.io");
+    }
+
+    #[tokio::test]
+    async fn test_chained_sanitization_accumulates() {
+        let vault = Vault::new();
+        let input = "key sk-abc123 mail a@b.io, again sk-abc123";
+        let pipeline = redaction_pipeline().with_chained_sanitization();
+        assert!(pipeline.is_chained());
+
+        let result = pipeline.execute_aggregated(input, &vault).await.unwrap();
+        assert_eq!(result.sanitized_text, "key [SECRET] mail [EMAIL], again [SECRET]");
+
+        // Every entity points at its text in the original input
+        assert_eq!(result.entities.len(), 3);
+        for entity in &result.entities {
+            assert_eq!(&input[entity.start..entity.end], entity.text);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_chained_parallel_runs_in_order() {
+        let vault = Vault::new();
+        let results = redaction_pipeline()
+            .with_chained_sanitization()
+            .execute_parallel("sk-abc123 a@b.io", &vault)
+            .await
+            .unwrap();
+
+        assert_eq!(results[0].sanitized_text, "[SECRET] a@b.io");
+        assert_eq!(results[1].sanitized_text, "[SECRET] [EMAIL]");
+        assert_eq!((results[1].entities[0].start, results[1].entities[0].end), (10, 16));
+    }
+
+    /// Sleeps before passing, to exercise deadlines
+    struct SlowScanner {
+        name: &'static str,
+        delay: Duration,
+    }
+
+    #[async_trait]
+    impl Scanner for SlowScanner {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        async fn scan(&self, input: &str, _vault: &Vault) -> Result<ScanResult> {
+            tokio::time::sleep(self.delay).await;
+            Ok(ScanResult::pass(input.to_string()))
+        }
+    }
+
+    fn slow(name: &'static str, delay_ms: u64) -> Arc<dyn Scanner> {
+        Arc::new(SlowScanner {
+            name,
+            delay: Duration::from_millis(delay_ms),
+        })
+    }
+
+    fn timed_out(result: &ScanResult) -> Vec<String> {
+        result
+            .metadata
+            .get(ScanResult::TIMED_OUT_SCANNERS)
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+            .unwrap_or_default()
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_scanner_timeout_fail_closed() {
+        let vault = Vault::new();
+        let pipeline = ScannerPipeline::new()
+            .add(slow("fast", 10))
+            .add(slow("hung", 60_000))
+            .with_scanner_timeout(Duration::from_millis(100));
+
+        let result = pipeline.execute_aggregated("text", &vault).await.unwrap();
+        assert!(!result.is_valid);
+        assert_eq!(result.risk_score, 1.0);
+        assert_eq!(result.risk_factors[0].factor_type, "scanner_timeout");
+        assert_eq!(timed_out(&result), vec!["hung"]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_scanner_timeout_override_fail_open() {
+        let vault = Vault::new();
+        let pipeline = ScannerPipeline::new()
+            .add(slow("url_reachability", 5_000))
+            .add(slow("secrets", 50))
+            .with_scanner_timeout(Duration::from_millis(100))
+            .with_timeout_for("secrets", Duration::from_millis(10))
+            .with_timeout_policy_for("url_reachability", TimeoutPolicy::FailOpen);
+
+        let results = pipeline.execute_parallel("text", &vault).await.unwrap();
+        assert!(results[0].is_valid);
+        assert!(!results[1].is_valid);
+
+        let combined = ScanResult::combine(results);
+        assert_eq!(timed_out(&combined), vec!["url_reachability", "secrets"]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_total_timeout_cancels_remaining() {
+        let vault = Vault::new();
+        let pipeline = ScannerPipeline::new()
+            .add(slow("first", 80))
+            .add(slow("second", 80))
+            .add(slow("third", 80))
+            .with_total_timeout(Duration::from_millis(100))
+            .with_timeout_policy(TimeoutPolicy::FailOpen);
+
+        let start = Instant::now();
+        let results = pipeline.execute("text", &vault).await.unwrap();
+        assert!(start.elapsed() <= Duration::from_millis(100));
+
+        assert_eq!(results.len(), 3);
+        assert!(results.iter().all(|r| r.is_valid));
+        let combined = ScanResult::combine(results);
+        assert_eq!(timed_out(&combined), vec!["second", "third"]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_total_timeout_parallel() {
+        let vault = Vault::new();
+        let pipeline = ScannerPipeline::new()
+            .add(slow("quick", 20))
+            .add(slow("hung", 60_000))
+            .with_scanner_timeout(Duration::from_secs(30))
+            .with_total_timeout(Duration::from_millis(100));
+
+        let start = Instant::now();
+        let result = pipeline.execute_parallel("text", &vault).await.unwrap();
+        assert_eq!(start.elapsed(), Duration::from_millis(100));
+        assert_eq!(timed_out(&ScanResult::combine(result)), vec!["hung"]);
+    }
+
+    /// Scanner with configurable cost, delay, risk and failure
+    #[derive(Default)]
+    struct TestScanner {
+        name: &'static str,
+        delay_ms: u64,
+        risk_score: f32,
+        fails: bool,
+        performance: PerformanceInfo,
+        running: Arc<std::sync::atomic::AtomicUsize>,
+        peak: Arc<std::sync::atomic::AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl Scanner for TestScanner {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        async fn scan(&self, input: &str, _vault: &Vault) -> Result<ScanResult> {
+            use std::sync::atomic::Ordering;
+
+            let now = self.running.fetch_add(1, Ordering::SeqCst) + 1;
+            self.peak.fetch_max(now, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(self.delay_ms)).await;
+            self.running.fetch_sub(1, Ordering::SeqCst);
+
+            if self.fails {
+                return Err(Error::scanner(self.name, "backend unavailable"));
+            }
+            Ok(ScanResult::new(input.to_string(), self.risk_score < 0.5, self.risk_score)
+                .with_metadata("scanner", self.name))
+        }
+
+        fn performance(&self) -> PerformanceInfo {
+            self.performance.clone()
+        }
+    }
+
+    fn names(results: &[ScanResult]) -> Vec<&str> {
+        results
+            .iter()
+            .map(|r| r.metadata["scanner"].as_str().unwrap())
+            .collect()
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_parallel_respects_max_concurrent() {
+        let vault = Vault::new();
+        let running = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let peak = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+
+        let mut pipeline = ScannerPipeline::new().with_max_concurrent(2);
+        for name in ["a", "b", "c", "d", "e"] {
+            pipeline = pipeline.add(Arc::new(TestScanner {
+                name,
+                delay_ms: 10,
+                running: Arc::clone(&running),
+                peak: Arc::clone(&peak),
+                ..Default::default()
+            }));
+        }
+
+        let results = pipeline.execute_parallel("text", &vault).await.unwrap();
+        assert_eq!(names(&results), vec!["a", "b", "c", "d", "e"]);
+        assert_eq!(peak.load(std::sync::atomic::Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_parallel_short_circuit_cancels_remaining() {
+        let vault = Vault::new();
+        let pipeline = ScannerPipeline::new()
+            .add(Arc::new(TestScanner {
+                name: "slow",
+                delay_ms: 60_000,
+                ..Default::default()
+            }))
+            .add(Arc::new(TestScanner {
+                name: "risky",
+                delay_ms: 10,
+                risk_score: 0.95,
+                ..Default::default()
+            }))
+            .add(Arc::new(TestScanner {
+                name: "queued",
+                delay_ms: 10,
+                ..Default::default()
+            }))
+            .with_max_concurrent(2)
+            .with_short_circuit(0.9);
+
+        let start = Instant::now();
+        let results = pipeline.execute_parallel("text", &vault).await.unwrap();
+        assert_eq!(start.elapsed(), Duration::from_millis(10));
+        assert_eq!(names(&results), vec!["risky"]);
+    }
+
+    #[tokio::test]
+    async fn test_cost_ordering_runs_cheap_first() {
+        let vault = Vault::new();
+        let pipeline = ScannerPipeline::new()
+            .add(Arc::new(TestScanner {
+                name: "network",
+                performance: PerformanceInfo {
+                    requires_network: true,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }))
+            .add(Arc::new(TestScanner {
+                name: "model",
+                performance: PerformanceInfo {
+                    uses_ml_models: true,
+                    typical_latency_ms: 50,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }))
+            .add(Arc::new(TestScanner {
+                name: "regex",
+                ..Default::default()
+            }))
+            .with_cost_ordering();
+
+        let results = pipeline.execute("text", &vault).await.unwrap();
+        assert_eq!(names(&results), vec!["regex", "model", "network"]);
+
+        let results = pipeline.execute_parallel("text", &vault).await.unwrap();
+        assert_eq!(names(&results), vec!["regex", "model", "network"]);
+    }
+
+    #[tokio::test]
+    async fn test_error_policy() {
+        let vault = Vault::new();
+        let pipeline = || {
+            ScannerPipeline::new()
+                .add(Arc::new(TestScanner {
+                    name: "broken",
+                    fails: true,
+                    ..Default::default()
+                }))
+                .add(Arc::new(TestScanner {
+                    name: "ok",
+                    ..Default::default()
+                }))
+        };
+
+        assert!(pipeline().execute_parallel("text", &vault).await.is_err());
+
+        let results = pipeline()
+            .with_error_policy(ErrorPolicy::FailOpen)
+            .execute_parallel("text", &vault)
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 2);
+        let combined = ScanResult::combine(results);
+        assert!(combined.is_valid);
+        assert_eq!(
+            combined.metadata[ScanResult::FAILED_SCANNERS],
+            serde_json::json!(["broken"])
+        );
+
+        let combined = pipeline()
+            .with_error_policy(ErrorPolicy::FailClosed)
+            .execute_aggregated("text", &vault)
+            .await
+            .unwrap();
+        assert!(!combined.is_valid);
+        assert_eq!(combined.risk_factors[0].factor_type, "scanner_error");
+    }
+
     #[tokio::test]
     async fn test_scanner_pipeline_aggregated() {
         let vault = Vault::new();
@@ -0,0 +1,450 @@
+//! Streaming output scanning
+//!
+//! [`StreamingScanner`] scans an LLM response as it is generated, so text
+//! can be shown to the user before the response is complete. Chunks are
+//! appended to a buffer; on every chunk the unreleased buffer is scanned and
+//! the part that can no longer change the verdict is released, sanitized.
+//!
+//! ## Lookback
+//!
+//! A match may start in one chunk and end in a later one. Each scanner
+//! reports (via [`Scanner::stream_lookback`]) the longest match it can
+//! produce, and the stream holds back that many trailing bytes until more
+//! text arrives. Scanners with open-ended patterns also report where a
+//! match still open at the end of the buffer begins (via
+//! [`Scanner::open_match_start`]), and that match is held back however
+//! long it grows. Text is also never released in the middle of a detection
+//! or a redaction.
+//!
+//! ## Verdicts
+//!
+//! Detections are reported with the chunk that releases them. A detection
+//! the scanner redacted is released in redacted form and the stream
+//! continues. A detection the scanner could not redact blocks the stream:
+//! nothing more is released.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! let mut stream = StreamingScanner::new(vec![Arc::new(secrets)])?;
+//!
+//! while let Some(token) = upstream.next().await {
+//!     let chunk = stream.push(&token).await?;
+//!     send_to_user(&chunk.text);
+//!     if chunk.blocked {
+//!         break;
+//!     }
+//! }
+//! send_to_user(&stream.finish().await?.text);
+//! ```
+
+use crate::{Entity, Error, OffsetMap, Result, RiskFactor, ScanResult, Scanner, Vault};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// Incremental verdict for one pushed chunk
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StreamChunk {
+    /// Sanitized text released by this call, safe to forward
+    pub text: String,
+
+    /// Whether no detection was released (or blocked the stream) in this call
+    pub is_valid: bool,
+
+    /// Highest risk score among the scanners that reported a detection
+    pub risk_score: f32,
+
+    /// Detections released in this call, in offsets of the original stream
+    pub entities: Vec<Entity>,
+
+    /// Risk factors of the scanners that reported a detection
+    pub risk_factors: Vec<RiskFactor>,
+
+    /// Names of the scanners that flagged the text released in this call
+    #[serde(default)]
+    pub triggered: Vec<String>,
+
+    /// Whether the stream is blocked; no more text will be released
+    pub blocked: bool,
+
+    /// Whether this is the last chunk
+    pub done: bool,
+}
+
+impl StreamChunk {
+    fn empty(blocked: bool, done: bool) -> Self {
+        Self {
+            text: String::new(),
+            is_valid: !blocked,
+            risk_score: 0.0,
+            entities: Vec::new(),
+            risk_factors: Vec::new(),
+            triggered: Vec::new(),
+            blocked,
+            done,
+        }
+    }
+}
+
+/// One scanner's pass over the buffer
+struct Stage {
+    scanner: String,
+    result: ScanResult,
+    /// Mapping from this stage's output back to its input
+    map: OffsetMap,
+    /// Entity spans in buffer coordinates
+    spans: Vec<(usize, usize)>,
+}
+
+/// Chunk-by-chunk scanner for streamed output
+///
+/// Scanners run chained over the buffer, each seeing the previous one's
+/// sanitized text, so redactions from several scanners combine.
+pub struct StreamingScanner {
+    scanners: Vec<Arc<dyn Scanner>>,
+    vault: Vault,
+    lookback: usize,
+    /// Text received but not yet released
+    buffer: String,
+    /// Bytes of the original stream released so far
+    released: usize,
+    blocked: bool,
+    finished: bool,
+}
+
+impl StreamingScanner {
+    /// Create a streaming scanner
+    ///
+    /// Fails if a scanner cannot scan partial text (see
+    /// [`Scanner::stream_lookback`]).
+    pub fn new(scanners: Vec<Arc<dyn Scanner>>) -> Result<Self> {
+        let mut lookback = 0;
+        for scanner in &scanners {
+            let own = scanner.stream_lookback().ok_or_else(|| {
+                Error::config(format!(
+                    "Scanner '{}' does not support streaming",
+                    scanner.name()
+                ))
+            })?;
+            lookback = lookback.max(own);
+        }
+
+        Ok(Self {
+            scanners,
+            vault: Vault::new(),
+            lookback,
+            buffer: String::new(),
+            released: 0,
+            blocked: false,
+            finished: false,
+        })
+    }
+
+    /// Scan against an existing vault
+    pub fn with_vault(mut self, vault: Vault) -> Self {
+        self.vault = vault;
+        self
+    }
+
+    /// Bytes held back between chunks
+    pub fn lookback(&self) -> usize {
+        self.lookback
+    }
+
+    /// Whether a detection has blocked the stream
+    pub fn is_blocked(&self) -> bool {
+        self.blocked
+    }
+
+    /// Add a chunk and release whatever text is now settled
+    pub async fn push(&mut self, chunk: &str) -> Result<StreamChunk> {
+        if self.finished {
+            return Err(Error::invalid_input("Stream already finished"));
+        }
+        if self.blocked {
+            return Ok(StreamChunk::empty(true, false));
+        }
+
+        self.buffer.push_str(chunk);
+        self.process(false).await
+    }
+
+    /// End the stream and release the remaining text
+    pub async fn finish(&mut self) -> Result<StreamChunk> {
+        if self.finished {
+            return Err(Error::invalid_input("Stream already finished"));
+        }
+        self.finished = true;
+        if self.blocked {
+            return Ok(StreamChunk::empty(true, true));
+        }
+
+        self.process(true).await
+    }
+
+    async fn process(&mut self, done: bool) -> Result<StreamChunk> {
+        // Chain the scanners over the unreleased buffer
+        let mut stages: Vec<Stage> = Vec::with_capacity(self.scanners.len());
+        let mut text = self.buffer.clone();
+        for scanner in &self.scanners {
+            let result = scanner.scan(&text, &self.vault).await?;
+            let hints: Vec<_> = result.entities.iter().map(|e| (e.start, e.end)).collect();
+            let map = OffsetMap::between(&text, &result.sanitized_text, &hints);
+            let spans = hints
+                .iter()
+                .map(|&(start, end)| to_buffer(&stages, start, end))
+                .collect();
+            text = result.sanitized_text.clone();
+            stages.push(Stage {
+                scanner: scanner.name().to_string(),
+                result,
+                map,
+                spans,
+            });
+        }
+
+        let cut = if done {
+            self.buffer.len()
+        } else {
+            let mut cut = self.buffer.len().saturating_sub(self.lookback);
+            for scanner in &self.scanners {
+                if let Some(start) = scanner.open_match_start(&self.buffer) {
+                    cut = cut.min(start);
+                }
+            }
+            while !self.buffer.is_char_boundary(cut) {
+                cut -= 1;
+            }
+            settle(&stages, cut)
+        };
+
+        // Scanners whose detections are settled by this release
+        let reporting: Vec<&Stage> = stages
+            .iter()
+            .filter(|stage| {
+                let settled = stage.spans.iter().any(|&(_, end)| end <= cut);
+                settled || (done && !stage.result.is_valid)
+            })
+            .collect();
+
+        let blocked = reporting
+            .iter()
+            .any(|stage| !stage.result.is_valid && stage.map.is_identity());
+
+        let offset = self.released;
+        let entities = reporting
+            .iter()
+            .flat_map(|stage| {
+                stage
+                    .result
+                    .entities
+                    .iter()
+                    .zip(&stage.spans)
+                    .filter(|(_, &(_, end))| end <= cut)
+                    .map(|(entity, &(start, end))| Entity {
+                        start: offset + start,
+                        end: offset + end,
+                        ..entity.clone()
+                    })
+            })
+            .collect();
+
+        let chunk = StreamChunk {
+            text: String::new(),
+            is_valid: reporting.iter().all(|stage| stage.result.is_valid),
+            risk_score: reporting
+                .iter()
+                .map(|stage| stage.result.risk_score)
+                .fold(0.0f32, f32::max),
+            entities,
+            risk_factors: reporting
+                .iter()
+                .flat_map(|stage| stage.result.risk_factors.clone())
+                .collect(),
+            triggered: reporting
+                .iter()
+                .filter(|stage| !stage.result.is_valid)
+                .map(|stage| stage.scanner.clone())
+                .collect(),
+            blocked,
+            done,
+        };
+
+        if blocked {
+            self.blocked = true;
+            self.buffer.clear();
+            return Ok(chunk);
+        }
+
+        let out = stages
+            .iter()
+            .fold(cut, |pos, stage| stage.map.map_forward(pos));
+        self.buffer.drain(..cut);
+        self.released += cut;
+
+        Ok(StreamChunk {
+            text: text[..out].to_string(),
+            ..chunk
+        })
+    }
+}
+
+/// Map a span reported by the next stage back to buffer coordinates
+fn to_buffer(stages: &[Stage], start: usize, end: usize) -> (usize, usize) {
+    stages.iter().rev().fold((start, end), |(start, end), stage| {
+        (stage.map.map_start(start), stage.map.map_end(end))
+    })
+}
+
+/// Move `cut` back until it splits no detection and no redaction
+fn settle(stages: &[Stage], mut cut: usize) -> usize {
+    loop {
+        let mut next = cut;
+        for &(start, end) in stages.iter().flat_map(|stage| &stage.spans) {
+            if start < next && next < end {
+                next = start;
+            }
+        }
+
+        // Follow the cut through each stage; step back out of rewrites
+        let mut pos = next;
+        for (i, stage) in stages.iter().enumerate() {
+            let floor = stage.map.unchanged_floor(pos);
+            if floor != pos {
+                next = stages[..i]
+                    .iter()
+                    .rev()
+                    .fold(floor, |p, earlier| earlier.map.map_start(p));
+                break;
+            }
+            pos = stage.map.map_forward(pos);
+        }
+
+        if next == cut {
+            return cut;
+        }
+        cut = next;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::async_trait;
+
+    /// Redacts (or, unless `redact`, flags) every "sk-" key of 8 characters
+    struct KeyScanner {
+        redact: bool,
+    }
+
+    #[async_trait]
+    impl Scanner for KeyScanner {
+        fn name(&self) -> &str {
+            "keys"
+        }
+
+        async fn scan(&self, input: &str, _vault: &Vault) -> Result<ScanResult> {
+            let mut sanitized = String::new();
+            let mut result = ScanResult::pass(String::new());
+            let mut last = 0;
+            for (start, _) in input.match_indices("sk-") {
+                let end = start + 8;
+                if start < last || end > input.len() {
+                    continue;
+                }
+                sanitized.push_str(&input[last..start]);
+                sanitized.push_str(if self.redact { "[KEY]" } else { &input[start..end] });
+                result = result.with_entity(Entity::new("key", &input[start..end], start, end, 1.0));
+                result.is_valid = false;
+                result.risk_score = 0.9;
+                last = end;
+            }
+            sanitized.push_str(&input[last..]);
+            result.sanitized_text = sanitized;
+            Ok(result)
+        }
+
+        fn stream_lookback(&self) -> Option<usize> {
+            Some(8)
+        }
+    }
+
+    struct WholeTextScanner;
+
+    #[async_trait]
+    impl Scanner for WholeTextScanner {
+        fn name(&self) -> &str {
+            "whole"
+        }
+
+        async fn scan(&self, input: &str, _vault: &Vault) -> Result<ScanResult> {
+            Ok(ScanResult::pass(input.to_string()))
+        }
+    }
+
+    async fn run(stream: &mut StreamingScanner, chunks: &[&str]) -> (String, Vec<StreamChunk>) {
+        let mut out = String::new();
+        let mut verdicts = Vec::new();
+        for chunk in chunks {
+            let verdict = stream.push(chunk).await.unwrap();
+            out.push_str(&verdict.text);
+            verdicts.push(verdict);
+        }
+        let verdict = stream.finish().await.unwrap();
+        out.push_str(&verdict.text);
+        verdicts.push(verdict);
+        (out, verdicts)
+    }
+
+    #[tokio::test]
+    async fn test_redacts_match_split_across_chunks() {
+        let mut stream =
+            StreamingScanner::new(vec![Arc::new(KeyScanner { redact: true })]).unwrap();
+
+        let (out, verdicts) = run(&mut stream, &["hello there, key sk-1", "2345 ok and more text"]).await;
+
+        assert_eq!(out, "hello there, key [KEY] ok and more text");
+        // Text before the lookback is released early
+        assert!(verdicts[0].text.starts_with("hello"));
+        assert!(!verdicts[0].text.contains("sk-"));
+
+        let detections: Vec<_> = verdicts.iter().flat_map(|v| &v.entities).collect();
+        assert_eq!(detections.len(), 1);
+        assert_eq!((detections[0].start, detections[0].end), (17, 25));
+        assert!(verdicts.iter().all(|v| !v.blocked));
+        // The redacting scanner is reported once, with its detection
+        let triggered: Vec<_> = verdicts.iter().flat_map(|v| &v.triggered).collect();
+        assert_eq!(triggered, ["keys"]);
+    }
+
+    #[tokio::test]
+    async fn test_unredacted_detection_blocks_stream() {
+        let mut stream =
+            StreamingScanner::new(vec![Arc::new(KeyScanner { redact: false })]).unwrap();
+
+        let (out, verdicts) =
+            run(&mut stream, &["safe prefix text ", "then sk-12345678 leaks", " more"]).await;
+
+        assert!(!out.contains("sk-"));
+        assert!(out.starts_with("safe"));
+        assert!(stream.is_blocked());
+        let blocking = verdicts.iter().find(|v| v.blocked).unwrap();
+        assert!(!blocking.is_valid);
+        assert_eq!(blocking.entities.len(), 1);
+        assert_eq!(blocking.triggered, ["keys"]);
+        assert!(verdicts.last().unwrap().done);
+    }
+
+    #[tokio::test]
+    async fn test_rejects_scanner_without_stream_support() {
+        assert!(StreamingScanner::new(vec![Arc::new(WholeTextScanner)]).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_push_after_finish_fails() {
+        let mut stream =
+            StreamingScanner::new(vec![Arc::new(KeyScanner { redact: true })]).unwrap();
+        assert!(stream.finish().await.unwrap().done);
+        assert!(stream.push("late").await.is_err());
+    }
+}
@@ -100,6 +100,28 @@ pub struct PerformanceInfo {
     pub parallel_safe: bool,
 }
 
+impl Default for PerformanceInfo {
+    /// A cheap, local, pattern-based scanner
+    fn default() -> Self {
+        Self {
+            typical_latency_ms: 1,
+            requires_network: false,
+            uses_ml_models: false,
+            parallel_safe: true,
+        }
+    }
+}
+
+impl PerformanceInfo {
+    /// Sort key for running cheap scanners first
+    ///
+    /// Local scanners come before networked ones, pattern-based before ML,
+    /// then by typical latency.
+    pub fn cost_key(&self) -> (bool, bool, u64) {
+        (self.requires_network, self.uses_ml_models, self.typical_latency_ms)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
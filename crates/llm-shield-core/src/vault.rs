@@ -103,6 +103,32 @@ impl Vault {
         Ok(data.keys().cloned().collect())
     }
 
+    /// Copy entries from `other` whose keys are not set in this vault
+    ///
+    /// Used to seed a request-scoped vault from a long-lived one without
+    /// sharing storage: later writes to either vault are not visible in the
+    /// other.
+    pub fn inherit_from(&self, other: &Vault) -> Result<(), Error> {
+        if Arc::ptr_eq(&self.data, &other.data) {
+            return Ok(());
+        }
+
+        let source = other
+            .data
+            .read()
+            .map_err(|e| Error::vault(format!("Failed to acquire read lock: {}", e)))?;
+        let mut target = self
+            .data
+            .write()
+            .map_err(|e| Error::vault(format!("Failed to acquire write lock: {}", e)))?;
+
+        for (key, value) in source.iter() {
+            target.entry(key.clone()).or_insert_with(|| value.clone());
+        }
+
+        Ok(())
+    }
+
     /// Get number of entries
     pub fn len(&self) -> usize {
         self.data.read().map(|data| data.len()).unwrap_or(0)
@@ -141,6 +167,25 @@ mod tests {
         assert!(!vault.contains_key("key1"));
     }
 
+    #[test]
+    fn test_vault_inherit_from() {
+        let config = Vault::new();
+        config.set("threshold", 0.5).unwrap();
+        config.set("shared", "config").unwrap();
+
+        let request = Vault::new();
+        request.set("shared", "request").unwrap();
+        request.inherit_from(&config).unwrap();
+
+        assert_eq!(request.get::<_, f64>("threshold").unwrap(), Some(0.5));
+        assert_eq!(request.get::<_, String>("shared").unwrap(), Some("request".to_string()));
+
+        // Storage is not shared afterwards
+        request.set("anonymized", "x").unwrap();
+        assert!(!config.contains_key("anonymized"));
+        request.inherit_from(&request.clone()).unwrap();
+    }
+
     #[test]
     fn test_vault_typed_values() {
         let vault = Vault::new();
@@ -79,6 +79,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             level: std::env::var("RUST_LOG").unwrap_or_else(|_| "info".to_string()),
             format: "json".to_string(),
         },
+        alerting: Default::default(),
     };
 
     println!("🚀 Starting LLM Shield Dashboard...");
@@ -0,0 +1,291 @@
+//! Background alert rule evaluator
+
+use super::notifier::{
+    AlertNotification, AlertStatus, NotificationChannel, WebhookAllowList, WebhookNotifier,
+};
+use super::query::{MetricQuery, Operator};
+use super::state::{AlertState, AlertTransition};
+use crate::{
+    config::AlertingConfig,
+    db::DatabasePool,
+    error::Result,
+    models::{AlertRule, Severity},
+};
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+/// Periodically evaluates enabled alert rules
+///
+/// Rule state is held in memory; after a restart, a rule that is still
+/// breached re-enters `pending` and fires again once its hold elapses.
+/// Editing a rule resets its state the same way.
+pub struct AlertEvaluator {
+    pool: DatabasePool,
+    config: AlertingConfig,
+    notifier: WebhookNotifier,
+    states: Mutex<HashMap<Uuid, TrackedRule>>,
+}
+
+/// State of a rule as of its last update
+#[derive(Debug, Clone, Copy)]
+struct TrackedRule {
+    updated_at: DateTime<Utc>,
+    state: AlertState,
+}
+
+impl TrackedRule {
+    fn new(updated_at: DateTime<Utc>) -> Self {
+        Self {
+            updated_at,
+            state: AlertState::Inactive,
+        }
+    }
+
+    /// Reset the state if the rule was edited; returns the discarded state
+    fn sync(&mut self, updated_at: DateTime<Utc>) -> Option<AlertState> {
+        if self.updated_at == updated_at {
+            return None;
+        }
+        let previous = std::mem::replace(self, Self::new(updated_at));
+        Some(previous.state)
+    }
+}
+
+impl AlertEvaluator {
+    /// Create a new evaluator
+    pub fn new(pool: DatabasePool, config: AlertingConfig) -> Result<Self> {
+        let notifier = WebhookNotifier::new(
+            std::time::Duration::from_secs(config.webhook_timeout_secs),
+            WebhookAllowList::new(&config.webhook_allowed_hosts),
+        )?;
+        Ok(Self {
+            pool,
+            config,
+            notifier,
+            states: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Run the evaluation loop in the background
+    pub fn spawn(self: Arc<Self>) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(
+                self.config.evaluation_interval_secs,
+            ));
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+            info!(
+                "Alert evaluator started (interval: {}s)",
+                self.config.evaluation_interval_secs
+            );
+
+            loop {
+                interval.tick().await;
+                match self.evaluate_once().await {
+                    Ok(count) => debug!("Evaluated {} alert rules", count),
+                    Err(e) => warn!("Alert evaluation failed: {}", e),
+                }
+            }
+        })
+    }
+
+    /// Evaluate all enabled rules once; returns the number of rules evaluated
+    pub async fn evaluate_once(&self) -> Result<usize> {
+        let rules = sqlx::query_as!(
+            AlertRule,
+            r#"SELECT
+                id, tenant_id, name, description, query, threshold, operator,
+                duration_seconds,
+                severity as "severity: Severity",
+                enabled as "enabled!",
+                COALESCE(notification_channels, '{}') as "notification_channels!",
+                created_by, created_at, updated_at
+            FROM alert_rules
+            WHERE enabled = true"#
+        )
+        .fetch_all(self.pool.inner())
+        .await?;
+
+        let now = Utc::now();
+        let mut states = self.states.lock().await;
+
+        // Forget rules that were deleted or disabled
+        states.retain(|id, _| rules.iter().any(|rule| rule.id == *id));
+
+        for rule in &rules {
+            let value = match self.evaluate_rule(rule).await {
+                Ok(value) => value,
+                Err(e) => {
+                    warn!("Skipping alert rule '{}' ({}): {}", rule.name, rule.id, e);
+                    continue;
+                }
+            };
+
+            let breached = match (value, Operator::parse(&rule.operator)) {
+                (Some(value), Ok(op)) => op.compare(value, rule.threshold),
+                _ => false,
+            };
+
+            let tracked = states
+                .entry(rule.id)
+                .or_insert_with(|| TrackedRule::new(rule.updated_at));
+            if let Some(previous) = tracked.sync(rule.updated_at) {
+                debug!("Alert rule '{}' changed, resetting its state", rule.name);
+                // Receivers would otherwise keep the old alert open
+                if let AlertState::Firing { since } = previous {
+                    self.notify(rule, AlertStatus::Resolved, value, since, now)
+                        .await;
+                }
+            }
+
+            let hold = Duration::seconds(i64::from(rule.duration_seconds.max(0)));
+            let (next, transition) = tracked.state.advance(breached, now, hold);
+            tracked.state = next;
+
+            match transition {
+                Some(AlertTransition::Pending) => {
+                    debug!("Alert rule '{}' is pending", rule.name);
+                }
+                Some(AlertTransition::Firing { since }) => {
+                    info!("Alert rule '{}' is firing (value: {:?})", rule.name, value);
+                    if let Err(e) = self.record_firing(rule, value).await {
+                        warn!("Failed to record alert '{}': {}", rule.name, e);
+                    }
+                    self.notify(rule, AlertStatus::Firing, value, since, now)
+                        .await;
+                }
+                Some(AlertTransition::Resolved { since }) => {
+                    info!("Alert rule '{}' resolved", rule.name);
+                    self.notify(rule, AlertStatus::Resolved, value, since, now)
+                        .await;
+                }
+                None => {}
+            }
+        }
+
+        Ok(rules.len())
+    }
+
+    /// Current state of a rule
+    pub async fn state(&self, rule_id: Uuid) -> AlertState {
+        self.states
+            .lock()
+            .await
+            .get(&rule_id)
+            .map(|tracked| tracked.state)
+            .unwrap_or_default()
+    }
+
+    /// Run a rule's query against the metrics hypertable
+    async fn evaluate_rule(&self, rule: &AlertRule) -> Result<Option<f64>> {
+        let query = MetricQuery::parse(&rule.query)?;
+        let value: Option<f64> = sqlx::query_scalar(&query.to_sql())
+            .bind(rule.tenant_id)
+            .bind(&query.metric_name)
+            .bind(query.labels_json())
+            .bind(query.window.as_secs_f64())
+            .fetch_one(self.pool.inner())
+            .await?;
+        Ok(value)
+    }
+
+    /// Write a firing alert into `security_events`
+    async fn record_firing(&self, rule: &AlertRule, value: Option<f64>) -> Result<()> {
+        let description = format!(
+            "Alert '{}' firing: {} {} {} (value: {})",
+            rule.name,
+            rule.query,
+            rule.operator,
+            rule.threshold,
+            value.map_or_else(|| "n/a".to_string(), |v| v.to_string())
+        );
+        let metadata = serde_json::json!({
+            "rule_id": rule.id,
+            "rule_name": rule.name,
+            "query": rule.query,
+            "operator": rule.operator,
+            "threshold": rule.threshold,
+            "value": value,
+            "duration_seconds": rule.duration_seconds,
+        });
+
+        sqlx::query!(
+            r#"INSERT INTO security_events
+                (time, tenant_id, event_type, severity, description, metadata)
+            VALUES (NOW(), $1, 'alert_firing', $2, $3, $4)"#,
+            rule.tenant_id,
+            rule.severity.as_str(),
+            description,
+            metadata
+        )
+        .execute(self.pool.inner())
+        .await?;
+
+        Ok(())
+    }
+
+    /// Deliver a notification to every configured channel
+    async fn notify(
+        &self,
+        rule: &AlertRule,
+        status: AlertStatus,
+        value: Option<f64>,
+        started_at: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) {
+        let notification = AlertNotification {
+            status,
+            rule_id: rule.id,
+            rule_name: rule.name.clone(),
+            tenant_id: rule.tenant_id,
+            severity: rule.severity,
+            query: rule.query.clone(),
+            operator: rule.operator.clone(),
+            threshold: rule.threshold,
+            value,
+            started_at,
+            timestamp: now,
+            description: rule.description.clone(),
+        };
+
+        for channel in &rule.notification_channels {
+            let channel = match NotificationChannel::parse(channel) {
+                Ok(channel) => channel,
+                Err(e) => {
+                    warn!("Alert rule '{}': {}", rule.name, e);
+                    continue;
+                }
+            };
+            if let Err(e) = self.notifier.send(&channel, &notification).await {
+                warn!("Failed to notify for alert '{}': {}", rule.name, e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rule_update_resets_state() {
+        let created = Utc::now();
+        let mut tracked = TrackedRule::new(created);
+        tracked.state = AlertState::Firing { since: created };
+        assert_eq!(tracked.sync(created), None);
+        assert_eq!(tracked.state, AlertState::Firing { since: created });
+
+        let edited = created + Duration::seconds(5);
+        assert_eq!(
+            tracked.sync(edited),
+            Some(AlertState::Firing { since: created })
+        );
+        assert_eq!(tracked.state, AlertState::Inactive);
+        assert_eq!(tracked.sync(edited), None);
+    }
+}
@@ -0,0 +1,17 @@
+//! Alert rule evaluation
+//!
+//! A background [`AlertEvaluator`] periodically runs each enabled rule in
+//! `alert_rules` against the `metrics` hypertable. Rules move through
+//! `pending` → `firing` → `resolved` using `duration_seconds` as the hold;
+//! firing alerts are written to `security_events`, and both firing and
+//! resolved transitions are delivered to the rule's webhook channels.
+
+pub mod evaluator;
+pub mod notifier;
+pub mod query;
+pub mod state;
+
+pub use evaluator::AlertEvaluator;
+pub use notifier::{AlertNotification, AlertStatus, NotificationChannel, WebhookNotifier};
+pub use query::{Aggregation, MetricQuery, Operator};
+pub use state::{AlertState, AlertTransition};
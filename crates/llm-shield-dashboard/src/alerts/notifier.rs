@@ -0,0 +1,401 @@
+//! Alert notification delivery
+//!
+//! Webhook URLs come from tenant-editable rules, so delivery is restricted:
+//! the host must be on the configured allow-list, and every address it
+//! resolves to must be public. Loopback, private, link-local and other
+//! internal addresses are refused, and redirects are not followed.
+
+use crate::error::{DashboardError, Result};
+use crate::models::Severity;
+use chrono::{DateTime, Utc};
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+
+/// Notification channel from `alert_rules.notification_channels`
+///
+/// Channels are written as `webhook:https://...` or as a bare `http(s)://` URL.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NotificationChannel {
+    Webhook(String),
+}
+
+impl NotificationChannel {
+    /// Parse a channel entry
+    pub fn parse(channel: &str) -> Result<Self> {
+        let channel = channel.trim();
+        let url = channel.strip_prefix("webhook:").unwrap_or(channel);
+        if !url.starts_with("https://") && !url.starts_with("http://") {
+            return Err(DashboardError::Validation(format!(
+                "Unsupported notification channel '{}'",
+                channel
+            )));
+        }
+
+        let parsed = Url::parse(url).map_err(|e| {
+            DashboardError::Validation(format!("Invalid webhook URL '{}': {}", url, e))
+        })?;
+        if let Some(ip) = literal_ip(&parsed) {
+            if !is_public_ip(ip) {
+                return Err(DashboardError::Validation(format!(
+                    "Webhook URL '{}' points to a non-public address",
+                    url
+                )));
+            }
+        }
+        Ok(NotificationChannel::Webhook(url.to_string()))
+    }
+}
+
+/// Webhook hosts notifications may be delivered to
+///
+/// Entries match a host exactly, or any subdomain when written as
+/// `*.example.com`. An empty allow-list refuses every webhook.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WebhookAllowList {
+    hosts: Vec<String>,
+}
+
+impl WebhookAllowList {
+    /// Create an allow-list from host patterns
+    pub fn new<I, S>(hosts: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Self {
+            hosts: hosts
+                .into_iter()
+                .map(|host| normalize_host(host.as_ref()))
+                .filter(|host| !host.is_empty())
+                .collect(),
+        }
+    }
+
+    /// Whether `host` is allowed
+    pub fn allows(&self, host: &str) -> bool {
+        let host = normalize_host(host);
+        self.hosts
+            .iter()
+            .any(|pattern| match pattern.strip_prefix("*.") {
+                Some(domain) => host
+                    .strip_suffix(domain)
+                    .is_some_and(|prefix| prefix.ends_with('.') && prefix.len() > 1),
+                None => host == *pattern,
+            })
+    }
+
+    /// Check that a webhook URL's host is allowed and, when it is an IP
+    /// address, public
+    pub fn check(&self, url: &str) -> Result<Url> {
+        let parsed = Url::parse(url).map_err(|e| {
+            DashboardError::Validation(format!("Invalid webhook URL '{}': {}", url, e))
+        })?;
+        let host = parsed.host_str().unwrap_or_default();
+        if !self.allows(host) {
+            return Err(DashboardError::Validation(format!(
+                "Webhook host '{}' is not in alerting.webhook_allowed_hosts",
+                host
+            )));
+        }
+        if let Some(ip) = literal_ip(&parsed) {
+            if !is_public_ip(ip) {
+                return Err(DashboardError::Validation(format!(
+                    "Webhook host '{}' is not a public address",
+                    host
+                )));
+            }
+        }
+        Ok(parsed)
+    }
+}
+
+fn normalize_host(host: &str) -> String {
+    host.trim()
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .trim_end_matches('.')
+        .to_ascii_lowercase()
+}
+
+fn literal_ip(url: &Url) -> Option<IpAddr> {
+    normalize_host(url.host_str()?).parse().ok()
+}
+
+/// Whether an address is reachable on the public internet
+///
+/// Loopback, private, link-local, shared (CGNAT), unspecified, broadcast,
+/// multicast and documentation ranges are not.
+pub fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_public_ipv4(ip),
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(mapped) => is_public_ipv4(mapped),
+            None => is_public_ipv6(ip),
+        },
+    }
+}
+
+fn is_public_ipv4(ip: Ipv4Addr) -> bool {
+    let [a, b, ..] = ip.octets();
+    !(ip.is_loopback()
+        || ip.is_private()
+        || ip.is_link_local()
+        || ip.is_unspecified()
+        || ip.is_broadcast()
+        || ip.is_multicast()
+        || ip.is_documentation()
+        || a == 0
+        || (a == 100 && (64..128).contains(&b))
+        || a >= 240)
+}
+
+fn is_public_ipv6(ip: Ipv6Addr) -> bool {
+    let first = ip.segments()[0];
+    !(ip.is_loopback()
+        || ip.is_unspecified()
+        || ip.is_multicast()
+        // Unique local fc00::/7
+        || (first & 0xfe00) == 0xfc00
+        // Link-local fe80::/10
+        || (first & 0xffc0) == 0xfe80
+        // Documentation 2001:db8::/32
+        || (first == 0x2001 && ip.segments()[1] == 0x0db8))
+}
+
+/// DNS resolver that refuses hosts resolving to non-public addresses
+///
+/// Used for the webhook connection itself, so the checked addresses are the
+/// ones connected to.
+struct PublicResolver;
+
+impl Resolve for PublicResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let host = name.as_str().to_string();
+        Box::pin(async move {
+            let addrs: Vec<SocketAddr> =
+                tokio::net::lookup_host((host.as_str(), 0)).await?.collect();
+            if addrs.is_empty() {
+                return Err(format!("Webhook host '{}' did not resolve", host).into());
+            }
+            if let Some(addr) = addrs.iter().find(|addr| !is_public_ip(addr.ip())) {
+                return Err(format!(
+                    "Webhook host '{}' resolves to non-public address {}",
+                    host,
+                    addr.ip()
+                )
+                .into());
+            }
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        })
+    }
+}
+
+/// Alert status in a notification
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AlertStatus {
+    Firing,
+    Resolved,
+}
+
+/// Webhook payload
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertNotification {
+    pub status: AlertStatus,
+    pub rule_id: Uuid,
+    pub rule_name: String,
+    pub tenant_id: Uuid,
+    pub severity: Severity,
+    pub query: String,
+    pub operator: String,
+    pub threshold: f64,
+    /// Latest evaluated value (`None` when the metric has no data)
+    pub value: Option<f64>,
+    pub started_at: DateTime<Utc>,
+    pub timestamp: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// Delivers notifications to webhook channels
+#[derive(Clone)]
+pub struct WebhookNotifier {
+    client: reqwest::Client,
+    allowed_hosts: WebhookAllowList,
+}
+
+impl WebhookNotifier {
+    /// Create a notifier with the given request timeout, delivering only to
+    /// `allowed_hosts`
+    pub fn new(timeout: Duration, allowed_hosts: WebhookAllowList) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(timeout)
+            .user_agent(concat!("llm-shield-dashboard/", env!("CARGO_PKG_VERSION")))
+            .dns_resolver(Arc::new(PublicResolver))
+            // A proxy would resolve the host itself, and a redirect could
+            // lead anywhere
+            .no_proxy()
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+            .map_err(|e| DashboardError::Internal(format!("Failed to build HTTP client: {}", e)))?;
+        Ok(Self {
+            client,
+            allowed_hosts,
+        })
+    }
+
+    /// Send a notification to one channel
+    pub async fn send(
+        &self,
+        channel: &NotificationChannel,
+        notification: &AlertNotification,
+    ) -> Result<()> {
+        match channel {
+            NotificationChannel::Webhook(url) => {
+                let url = self.allowed_hosts.check(url)?;
+                let response = self
+                    .client
+                    .post(url)
+                    .json(notification)
+                    .send()
+                    .await
+                    .map_err(|e| {
+                        DashboardError::Internal(format!(
+                            "Webhook request failed: {}",
+                            error_chain(&e)
+                        ))
+                    })?;
+
+                if !response.status().is_success() {
+                    return Err(DashboardError::Internal(format!(
+                        "Webhook returned status {}",
+                        response.status()
+                    )));
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// An error and its sources, so resolver refusals show up in the message
+fn error_chain(error: &dyn std::error::Error) -> String {
+    let mut message = error.to_string();
+    let mut source = error.source();
+    while let Some(cause) = source {
+        message.push_str(": ");
+        message.push_str(&cause.to_string());
+        source = cause.source();
+    }
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_channel() {
+        assert_eq!(
+            NotificationChannel::parse("webhook:https://hooks.example.com/a").unwrap(),
+            NotificationChannel::Webhook("https://hooks.example.com/a".to_string())
+        );
+        assert!(NotificationChannel::parse("http://localhost:9000/alerts").is_ok());
+        assert!(NotificationChannel::parse("email:ops@example.com").is_err());
+        assert!(NotificationChannel::parse("http://169.254.169.254/latest").is_err());
+        assert!(NotificationChannel::parse("http://[::1]:9000/alerts").is_err());
+    }
+
+    #[test]
+    fn test_allow_list() {
+        let allowed = WebhookAllowList::new(["hooks.example.com", "*.ops.example.org"]);
+        assert!(allowed.allows("hooks.example.com"));
+        assert!(allowed.allows("HOOKS.example.com."));
+        assert!(allowed.allows("a.ops.example.org"));
+        assert!(!allowed.allows("ops.example.org"));
+        assert!(!allowed.allows("evilops.example.org"));
+        assert!(!allowed.allows("example.com"));
+        assert!(!WebhookAllowList::default().allows("hooks.example.com"));
+
+        assert!(allowed.check("https://hooks.example.com/a").is_ok());
+        assert!(allowed.check("https://other.example.com/a").is_err());
+
+        let allowed = WebhookAllowList::new(["10.0.0.5"]);
+        assert!(allowed.check("http://10.0.0.5/a").is_err());
+    }
+
+    #[test]
+    fn test_public_addresses() {
+        for ip in ["8.8.8.8", "1.1.1.1", "2606:4700::1111"] {
+            assert!(is_public_ip(ip.parse().unwrap()), "{}", ip);
+        }
+        for ip in [
+            "127.0.0.1",
+            "10.1.2.3",
+            "172.16.0.1",
+            "192.168.1.1",
+            "169.254.169.254",
+            "100.64.0.1",
+            "0.0.0.0",
+            "::1",
+            "fd00::1",
+            "fe80::1",
+            "::ffff:127.0.0.1",
+        ] {
+            assert!(!is_public_ip(ip.parse().unwrap()), "{}", ip);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_send_refuses_private_resolution() {
+        let notifier =
+            WebhookNotifier::new(Duration::from_secs(1), WebhookAllowList::new(["localhost"]))
+                .unwrap();
+        let channel = NotificationChannel::parse("http://localhost:9/alerts").unwrap();
+        let notification = AlertNotification {
+            status: AlertStatus::Firing,
+            rule_id: Uuid::new_v4(),
+            rule_name: "High latency".to_string(),
+            tenant_id: Uuid::new_v4(),
+            severity: Severity::Critical,
+            query: "avg(scan_latency_ms[5m])".to_string(),
+            operator: ">".to_string(),
+            threshold: 100.0,
+            value: Some(250.0),
+            started_at: Utc::now(),
+            timestamp: Utc::now(),
+            description: None,
+        };
+
+        let err = notifier.send(&channel, &notification).await.unwrap_err();
+        assert!(format!("{:?}", err).contains("non-public"), "{:?}", err);
+    }
+
+    #[test]
+    fn test_notification_payload() {
+        let notification = AlertNotification {
+            status: AlertStatus::Firing,
+            rule_id: Uuid::new_v4(),
+            rule_name: "High latency".to_string(),
+            tenant_id: Uuid::new_v4(),
+            severity: Severity::Critical,
+            query: "avg(scan_latency_ms[5m])".to_string(),
+            operator: ">".to_string(),
+            threshold: 100.0,
+            value: Some(250.0),
+            started_at: Utc::now(),
+            timestamp: Utc::now(),
+            description: None,
+        };
+
+        let json = serde_json::to_value(&notification).unwrap();
+        assert_eq!(json["status"], "firing");
+        assert_eq!(json["severity"], "critical");
+        assert!(json.get("description").is_none());
+    }
+}
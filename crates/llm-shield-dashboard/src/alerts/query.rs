@@ -0,0 +1,291 @@
+//! Alert rule query language
+//!
+//! Rules select a metric from the `metrics` hypertable with a small,
+//! PromQL-flavoured expression:
+//!
+//! ```text
+//! avg(scan_latency_ms{scanner="toxicity"}[5m])
+//! count(scan_blocked[1h])
+//! requests_total                      # avg over the default 5m window
+//! ```
+//!
+//! Expressions are parsed into a [`MetricQuery`] and executed with bound
+//! parameters, so tenant-supplied text never reaches SQL directly.
+
+use crate::error::{DashboardError, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+/// Window used when the expression has no `[..]` suffix
+pub const DEFAULT_WINDOW: Duration = Duration::from_secs(300);
+
+/// Aggregation applied over the window
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Aggregation {
+    Avg,
+    Min,
+    Max,
+    Sum,
+    Count,
+}
+
+impl Aggregation {
+    /// SQL aggregate function
+    pub fn sql_function(&self) -> &'static str {
+        match self {
+            Aggregation::Avg => "AVG",
+            Aggregation::Min => "MIN",
+            Aggregation::Max => "MAX",
+            Aggregation::Sum => "SUM",
+            Aggregation::Count => "COUNT",
+        }
+    }
+
+    fn parse(name: &str) -> Option<Self> {
+        match name {
+            "avg" => Some(Aggregation::Avg),
+            "min" => Some(Aggregation::Min),
+            "max" => Some(Aggregation::Max),
+            "sum" => Some(Aggregation::Sum),
+            "count" => Some(Aggregation::Count),
+            _ => None,
+        }
+    }
+}
+
+/// Parsed alert rule query
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetricQuery {
+    pub aggregation: Aggregation,
+    pub metric_name: String,
+    /// Label matchers (exact match against the `labels` JSONB column)
+    pub labels: BTreeMap<String, String>,
+    pub window: Duration,
+}
+
+impl MetricQuery {
+    /// Parse a rule query expression
+    pub fn parse(expr: &str) -> Result<Self> {
+        let expr = expr.trim();
+        if expr.is_empty() {
+            return Err(invalid("query cannot be empty"));
+        }
+
+        // Optional aggregation wrapper
+        let (aggregation, inner) = match expr.find('(') {
+            Some(open) => {
+                let name = expr[..open].trim();
+                let aggregation = Aggregation::parse(name)
+                    .ok_or_else(|| invalid(format!("unknown aggregation '{}'", name)))?;
+                let inner = expr[open + 1..]
+                    .strip_suffix(')')
+                    .ok_or_else(|| invalid("missing closing ')'"))?;
+                (aggregation, inner.trim())
+            }
+            None => (Aggregation::Avg, expr),
+        };
+
+        // Optional window suffix
+        let (selector, window) = match inner.strip_suffix(']') {
+            Some(rest) => {
+                let open = rest
+                    .rfind('[')
+                    .ok_or_else(|| invalid("missing opening '['"))?;
+                (rest[..open].trim(), parse_window(&rest[open + 1..])?)
+            }
+            None => (inner, DEFAULT_WINDOW),
+        };
+
+        // Metric name and optional label matchers
+        let (metric_name, labels) = match selector.find('{') {
+            Some(open) => {
+                let body = selector[open + 1..]
+                    .strip_suffix('}')
+                    .ok_or_else(|| invalid("missing closing '}'"))?;
+                (selector[..open].trim(), parse_labels(body)?)
+            }
+            None => (selector, BTreeMap::new()),
+        };
+
+        if !is_valid_identifier(metric_name) {
+            return Err(invalid(format!("invalid metric name '{}'", metric_name)));
+        }
+
+        Ok(Self {
+            aggregation,
+            metric_name: metric_name.to_string(),
+            labels,
+            window,
+        })
+    }
+
+    /// Label matchers as a JSONB containment document
+    pub fn labels_json(&self) -> serde_json::Value {
+        serde_json::Value::Object(
+            self.labels
+                .iter()
+                .map(|(k, v)| (k.clone(), serde_json::Value::String(v.clone())))
+                .collect(),
+        )
+    }
+
+    /// Build the evaluation SQL
+    ///
+    /// Parameters: `$1` tenant id, `$2` metric name, `$3` labels (JSONB),
+    /// `$4` window in seconds.
+    pub fn to_sql(&self) -> String {
+        format!(
+            "SELECT {}(metric_value)::DOUBLE PRECISION FROM metrics \
+             WHERE tenant_id = $1 AND metric_name = $2 \
+             AND COALESCE(labels, '{{}}'::jsonb) @> $3 \
+             AND time > NOW() - make_interval(secs => $4)",
+            self.aggregation.sql_function()
+        )
+    }
+}
+
+/// Comparison operator from `alert_rules.operator`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operator {
+    Gt,
+    Lt,
+    Gte,
+    Lte,
+    Eq,
+    Ne,
+}
+
+impl Operator {
+    /// Parse a stored operator
+    pub fn parse(op: &str) -> Result<Self> {
+        match op.trim() {
+            ">" => Ok(Operator::Gt),
+            "<" => Ok(Operator::Lt),
+            ">=" => Ok(Operator::Gte),
+            "<=" => Ok(Operator::Lte),
+            "=" | "==" => Ok(Operator::Eq),
+            "!=" => Ok(Operator::Ne),
+            other => Err(invalid(format!("unsupported operator '{}'", other))),
+        }
+    }
+
+    /// Whether `value` breaches `threshold`
+    pub fn compare(&self, value: f64, threshold: f64) -> bool {
+        match self {
+            Operator::Gt => value > threshold,
+            Operator::Lt => value < threshold,
+            Operator::Gte => value >= threshold,
+            Operator::Lte => value <= threshold,
+            Operator::Eq => (value - threshold).abs() < f64::EPSILON,
+            Operator::Ne => (value - threshold).abs() >= f64::EPSILON,
+        }
+    }
+}
+
+fn parse_window(window: &str) -> Result<Duration> {
+    let window = window.trim();
+    let split = window
+        .find(|c: char| !c.is_ascii_digit())
+        .ok_or_else(|| invalid(format!("window '{}' is missing a unit", window)))?;
+    let amount: u64 = window[..split]
+        .parse()
+        .map_err(|_| invalid(format!("invalid window '{}'", window)))?;
+    let seconds = match &window[split..] {
+        "s" => amount,
+        "m" => amount * 60,
+        "h" => amount * 3600,
+        "d" => amount * 86400,
+        unit => return Err(invalid(format!("unknown window unit '{}'", unit))),
+    };
+    if seconds == 0 {
+        return Err(invalid("window must be greater than 0"));
+    }
+    Ok(Duration::from_secs(seconds))
+}
+
+fn parse_labels(body: &str) -> Result<BTreeMap<String, String>> {
+    let mut labels = BTreeMap::new();
+    for matcher in body.split(',').map(str::trim).filter(|m| !m.is_empty()) {
+        let (key, value) = matcher
+            .split_once('=')
+            .ok_or_else(|| invalid(format!("invalid label matcher '{}'", matcher)))?;
+        let key = key.trim();
+        if !is_valid_identifier(key) {
+            return Err(invalid(format!("invalid label name '{}'", key)));
+        }
+        let value = value
+            .trim()
+            .strip_prefix('"')
+            .and_then(|v| v.strip_suffix('"'))
+            .ok_or_else(|| invalid(format!("label '{}' value must be quoted", key)))?;
+        labels.insert(key.to_string(), value.to_string());
+    }
+    Ok(labels)
+}
+
+fn is_valid_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(c) if c.is_ascii_alphabetic() || c == '_' => {}
+        _ => return false,
+    }
+    chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | ':' | '.'))
+}
+
+fn invalid(msg: impl Into<String>) -> DashboardError {
+    DashboardError::Validation(format!("Invalid alert query: {}", msg.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_full_expression() {
+        let query = MetricQuery::parse(r#"max(scan_latency_ms{scanner="toxicity", env="prod"}[10m])"#)
+            .unwrap();
+        assert_eq!(query.aggregation, Aggregation::Max);
+        assert_eq!(query.metric_name, "scan_latency_ms");
+        assert_eq!(query.labels.get("scanner").map(String::as_str), Some("toxicity"));
+        assert_eq!(query.labels.len(), 2);
+        assert_eq!(query.window, Duration::from_secs(600));
+    }
+
+    #[test]
+    fn test_parse_bare_metric() {
+        let query = MetricQuery::parse("requests_total").unwrap();
+        assert_eq!(query.aggregation, Aggregation::Avg);
+        assert!(query.labels.is_empty());
+        assert_eq!(query.window, DEFAULT_WINDOW);
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid() {
+        assert!(MetricQuery::parse("").is_err());
+        assert!(MetricQuery::parse("median(x[5m])").is_err());
+        assert!(MetricQuery::parse("avg(x[5w])").is_err());
+        assert!(MetricQuery::parse("avg(x{scanner=toxicity})").is_err());
+        assert!(MetricQuery::parse("avg(x; DROP TABLE metrics)").is_err());
+    }
+
+    #[test]
+    fn test_sql_uses_whitelisted_aggregate() {
+        let query = MetricQuery::parse("count(scan_blocked[1h])").unwrap();
+        assert!(query.to_sql().starts_with("SELECT COUNT(metric_value)"));
+        // Rows without labels still match a query without matchers
+        assert!(query.to_sql().contains("COALESCE(labels, '{}'::jsonb) @> $3"));
+        assert_eq!(query.labels_json(), serde_json::json!({}));
+    }
+
+    #[test]
+    fn test_operator_compare() {
+        assert!(Operator::parse(">").unwrap().compare(2.0, 1.0));
+        assert!(!Operator::parse("<").unwrap().compare(2.0, 1.0));
+        assert!(Operator::parse(">=").unwrap().compare(1.0, 1.0));
+        assert!(Operator::parse("=").unwrap().compare(1.0, 1.0));
+        assert!(Operator::parse("!=").unwrap().compare(1.5, 1.0));
+        assert!(Operator::parse("~").is_err());
+    }
+}
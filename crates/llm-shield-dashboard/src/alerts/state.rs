@@ -0,0 +1,123 @@
+//! Alert state machine
+//!
+//! ```text
+//!            breach                 breach held for duration_seconds
+//! Inactive ─────────► Pending ─────────────────────────────────► Firing
+//!    ▲                   │ recovered                                │
+//!    └───────────────────┴──────────────── recovered (Resolved) ◄───┘
+//! ```
+
+use chrono::{DateTime, Duration, Utc};
+
+/// Current state of a rule
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AlertState {
+    /// Condition not met
+    #[default]
+    Inactive,
+    /// Condition met, waiting for the hold duration
+    Pending { since: DateTime<Utc> },
+    /// Condition held for the full duration
+    Firing { since: DateTime<Utc> },
+}
+
+/// State change worth acting on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlertTransition {
+    /// Rule entered the pending state
+    Pending,
+    /// Rule started firing
+    Firing { since: DateTime<Utc> },
+    /// Firing rule recovered
+    Resolved { since: DateTime<Utc> },
+}
+
+impl AlertState {
+    /// Advance the state given whether the condition is currently breached
+    pub fn advance(
+        self,
+        breached: bool,
+        now: DateTime<Utc>,
+        hold: Duration,
+    ) -> (AlertState, Option<AlertTransition>) {
+        match (self, breached) {
+            (AlertState::Inactive, true) if hold <= Duration::zero() => (
+                AlertState::Firing { since: now },
+                Some(AlertTransition::Firing { since: now }),
+            ),
+            (AlertState::Inactive, true) => (
+                AlertState::Pending { since: now },
+                Some(AlertTransition::Pending),
+            ),
+            (AlertState::Pending { since }, true) if now - since >= hold => (
+                AlertState::Firing { since: now },
+                Some(AlertTransition::Firing { since: now }),
+            ),
+            (AlertState::Firing { since }, false) => (
+                AlertState::Inactive,
+                Some(AlertTransition::Resolved { since }),
+            ),
+            (AlertState::Pending { .. }, false) => (AlertState::Inactive, None),
+            (state, _) => (state, None),
+        }
+    }
+
+    /// State name for logs and notifications
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AlertState::Inactive => "inactive",
+            AlertState::Pending { .. } => "pending",
+            AlertState::Firing { .. } => "firing",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hold_duration() {
+        let start = Utc::now();
+        let hold = Duration::seconds(60);
+
+        let (state, transition) = AlertState::Inactive.advance(true, start, hold);
+        assert_eq!(state, AlertState::Pending { since: start });
+        assert_eq!(transition, Some(AlertTransition::Pending));
+
+        let (state, transition) = state.advance(true, start + Duration::seconds(30), hold);
+        assert_eq!(state, AlertState::Pending { since: start });
+        assert_eq!(transition, None);
+
+        let fired_at = start + Duration::seconds(60);
+        let (state, transition) = state.advance(true, fired_at, hold);
+        assert_eq!(state, AlertState::Firing { since: fired_at });
+        assert_eq!(transition, Some(AlertTransition::Firing { since: fired_at }));
+
+        // Stays firing without re-notifying
+        let (state, transition) = state.advance(true, fired_at + Duration::seconds(30), hold);
+        assert_eq!(state.as_str(), "firing");
+        assert_eq!(transition, None);
+
+        let (state, transition) = state.advance(false, fired_at + Duration::seconds(90), hold);
+        assert_eq!(state, AlertState::Inactive);
+        assert_eq!(transition, Some(AlertTransition::Resolved { since: fired_at }));
+    }
+
+    #[test]
+    fn test_pending_recovers_silently() {
+        let now = Utc::now();
+        let (state, _) = AlertState::Inactive.advance(true, now, Duration::seconds(60));
+        let (state, transition) = state.advance(false, now + Duration::seconds(10), Duration::seconds(60));
+        assert_eq!(state, AlertState::Inactive);
+        assert_eq!(transition, None);
+    }
+
+    #[test]
+    fn test_zero_hold_fires_immediately() {
+        let now = Utc::now();
+        let (state, transition) = AlertState::Inactive.advance(true, now, Duration::zero());
+        assert_eq!(state, AlertState::Firing { since: now });
+        assert_eq!(transition, Some(AlertTransition::Firing { since: now }));
+    }
+}
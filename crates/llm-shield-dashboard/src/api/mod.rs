@@ -1,6 +1,7 @@
 //! API routes and handlers
 
 use crate::{
+    auth::Claims,
     config::DashboardConfig,
     db::DatabasePool,
     graphql::DashboardSchema,
@@ -11,6 +12,7 @@ use async_graphql::http::{playground_source, GraphQLPlaygroundConfig};
 use async_graphql_axum::{GraphQLRequest, GraphQLResponse};
 use axum::{
     extract::State,
+    Extension,
     http::{header, Method, StatusCode},
     middleware,
     response::{Html, IntoResponse},
@@ -29,11 +31,19 @@ pub struct AppState {
 }
 
 /// GraphQL handler
+///
+/// Claims from the auth middleware are attached to the request so resolvers
+/// can scope queries to the caller's tenant.
 async fn graphql_handler(
     State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
     req: GraphQLRequest,
 ) -> GraphQLResponse {
-    state.schema.execute(req.into_inner()).await.into()
+    state
+        .schema
+        .execute(req.into_inner().data(claims))
+        .await
+        .into()
 }
 
 /// GraphQL playground handler
@@ -158,6 +168,7 @@ mod tests {
                 level: "info".to_string(),
                 format: "json".to_string(),
             },
+            alerting: Default::default(),
         }
     }
 
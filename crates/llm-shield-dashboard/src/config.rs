@@ -23,6 +23,10 @@ pub struct DashboardConfig {
     /// Logging configuration
     #[serde(default)]
     pub logging: LoggingConfig,
+
+    /// Alert evaluation configuration
+    #[serde(default)]
+    pub alerting: AlertingConfig,
 }
 
 impl DashboardConfig {
@@ -70,6 +74,7 @@ impl DashboardConfig {
         self.server.validate()?;
         self.database.validate()?;
         self.auth.validate()?;
+        self.alerting.validate()?;
         Ok(())
     }
 }
@@ -82,6 +87,7 @@ impl Default for DashboardConfig {
             auth: AuthConfig::default(),
             cors: CorsConfig::default(),
             logging: LoggingConfig::default(),
+            alerting: AlertingConfig::default(),
         }
     }
 }
@@ -313,6 +319,57 @@ impl Default for LoggingConfig {
     }
 }
 
+/// Alert evaluation configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertingConfig {
+    /// Run the background alert evaluator
+    #[serde(default = "default_alerting_enabled")]
+    pub enabled: bool,
+
+    /// Seconds between rule evaluations
+    #[serde(default = "default_evaluation_interval")]
+    pub evaluation_interval_secs: u64,
+
+    /// Webhook request timeout in seconds
+    #[serde(default = "default_webhook_timeout")]
+    pub webhook_timeout_secs: u64,
+
+    /// Hosts alert webhooks may be delivered to (`hooks.example.com` or
+    /// `*.example.com`); webhooks to other hosts are refused
+    #[serde(default)]
+    pub webhook_allowed_hosts: Vec<String>,
+}
+
+impl AlertingConfig {
+    /// Validate alerting configuration
+    pub fn validate(&self) -> Result<()> {
+        if self.evaluation_interval_secs == 0 {
+            return Err(DashboardError::Configuration(
+                "Alert evaluation interval must be greater than 0".to_string(),
+            ));
+        }
+
+        if self.webhook_timeout_secs == 0 {
+            return Err(DashboardError::Configuration(
+                "Webhook timeout must be greater than 0".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for AlertingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_alerting_enabled(),
+            evaluation_interval_secs: default_evaluation_interval(),
+            webhook_timeout_secs: default_webhook_timeout(),
+            webhook_allowed_hosts: Vec::new(),
+        }
+    }
+}
+
 // Default value functions
 fn default_host() -> String {
     "127.0.0.1".to_string()
@@ -388,6 +445,18 @@ fn default_log_format() -> String {
     "pretty".to_string()
 }
 
+fn default_alerting_enabled() -> bool {
+    true
+}
+
+fn default_evaluation_interval() -> u64 {
+    30
+}
+
+fn default_webhook_timeout() -> u64 {
+    10
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -479,4 +548,14 @@ mod tests {
         assert_eq!(config.level, "info");
         assert_eq!(config.format, "pretty");
     }
+
+    #[test]
+    fn test_alerting_config_validation() {
+        let mut config = AlertingConfig::default();
+        assert!(config.enabled);
+        assert!(config.validate().is_ok());
+
+        config.evaluation_interval_secs = 0;
+        assert!(config.validate().is_err());
+    }
 }
@@ -329,6 +329,84 @@ async fn create_continuous_aggregates(pool: &PgPool) -> Result<()> {
         Err(e) => warn!("Metrics continuous aggregate may already exist: {}", e),
     }
 
+    // Create hourly aggregate for scanner stats
+    match sqlx::query(
+        r#"
+        CREATE MATERIALIZED VIEW IF NOT EXISTS scanner_stats_hourly
+        WITH (timescaledb.continuous) AS
+        SELECT
+            time_bucket('1 hour', time) AS bucket,
+            tenant_id,
+            scanner_name,
+            SUM(requests_total)::BIGINT AS requests_total,
+            SUM(requests_valid)::BIGINT AS requests_valid,
+            SUM(requests_invalid)::BIGINT AS requests_invalid,
+            AVG(avg_latency_ms) AS avg_latency_ms,
+            MAX(p95_latency_ms) AS max_p95_latency_ms,
+            MAX(p99_latency_ms) AS max_p99_latency_ms
+        FROM scanner_stats
+        GROUP BY bucket, tenant_id, scanner_name
+        WITH NO DATA
+        "#,
+    )
+    .execute(pool)
+    .await
+    {
+        Ok(_) => {
+            info!("Scanner stats hourly continuous aggregate created");
+            // Refresh policy
+            let _ = sqlx::query(
+                r#"
+                SELECT add_continuous_aggregate_policy('scanner_stats_hourly',
+                    start_offset => INTERVAL '1 day',
+                    end_offset => INTERVAL '1 hour',
+                    schedule_interval => INTERVAL '15 minutes',
+                    if_not_exists => TRUE)
+                "#,
+            )
+            .execute(pool)
+            .await;
+        }
+        Err(e) => warn!("Scanner stats continuous aggregate may already exist: {}", e),
+    }
+
+    // Create hourly aggregate for security events
+    match sqlx::query(
+        r#"
+        CREATE MATERIALIZED VIEW IF NOT EXISTS security_events_hourly
+        WITH (timescaledb.continuous) AS
+        SELECT
+            time_bucket('1 hour', time) AS bucket,
+            tenant_id,
+            event_type,
+            severity,
+            COUNT(*) AS event_count
+        FROM security_events
+        GROUP BY bucket, tenant_id, event_type, severity
+        WITH NO DATA
+        "#,
+    )
+    .execute(pool)
+    .await
+    {
+        Ok(_) => {
+            info!("Security events hourly continuous aggregate created");
+            // Refresh policy
+            let _ = sqlx::query(
+                r#"
+                SELECT add_continuous_aggregate_policy('security_events_hourly',
+                    start_offset => INTERVAL '1 day',
+                    end_offset => INTERVAL '1 hour',
+                    schedule_interval => INTERVAL '15 minutes',
+                    if_not_exists => TRUE)
+                "#,
+            )
+            .execute(pool)
+            .await;
+        }
+        Err(e) => warn!("Security events continuous aggregate may already exist: {}", e),
+    }
+
     Ok(())
 }
 
@@ -2,8 +2,10 @@
 
 pub mod migrations;
 pub mod pool;
+pub mod writer;
 
 pub use pool::DatabasePool;
+pub use writer::{ScanEventWriter, ScanRecord, WriterConfig};
 
 use crate::error::Result;
 use sqlx::PgPool;
@@ -0,0 +1,461 @@
+//! Batched writer for live scan traffic
+//!
+//! [`ScanEventWriter`] buffers scan outcomes in a bounded channel and a
+//! background task flushes them to TimescaleDB, either when `batch_size`
+//! records have accumulated or every `flush_interval`. Each flush runs in a
+//! single transaction and writes:
+//!
+//! - `metrics`: `scan_latency_ms`, `scan_risk_score` and `scan_requests` per record
+//! - `scanner_stats`: one row per (tenant, scanner) in the batch
+//! - `security_events`: a `scan_blocked` event for every invalid scan
+//!
+//! With the `api-sink` feature the writer implements
+//! `llm_shield_api::services::ScanEventSink`, so it can be attached directly
+//! to the API's `AppState`.
+
+use crate::{db::DatabasePool, error::Result, models::Severity};
+use chrono::{DateTime, Utc};
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tracing::{debug, warn};
+use uuid::Uuid;
+
+/// Outcome of a single scanner run
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScanRecord {
+    pub time: DateTime<Utc>,
+    pub tenant_id: Uuid,
+    pub scanner: String,
+    /// `prompt` or `output`
+    pub kind: String,
+    pub latency_ms: f64,
+    pub risk_score: f64,
+    pub is_valid: bool,
+    pub risk_factors: Vec<String>,
+}
+
+/// Writer configuration
+#[derive(Debug, Clone)]
+pub struct WriterConfig {
+    /// Records per flush
+    pub batch_size: usize,
+    /// Maximum time a record waits before being flushed
+    pub flush_interval: Duration,
+    /// Buffered records before new ones are dropped
+    pub channel_capacity: usize,
+    /// Tenant for events that do not carry a valid tenant id
+    pub default_tenant_id: Option<Uuid>,
+}
+
+impl Default for WriterConfig {
+    fn default() -> Self {
+        Self {
+            batch_size: 500,
+            flush_interval: Duration::from_secs(5),
+            channel_capacity: 10_000,
+            default_tenant_id: None,
+        }
+    }
+}
+
+/// Handle to the background writer (cheap to clone)
+#[derive(Clone)]
+pub struct ScanEventWriter {
+    sender: mpsc::Sender<ScanRecord>,
+    dropped: Arc<AtomicU64>,
+    default_tenant_id: Option<Uuid>,
+}
+
+impl ScanEventWriter {
+    /// Start the writer task
+    ///
+    /// The task flushes any remaining records and exits once every handle
+    /// has been dropped.
+    pub fn spawn(pool: DatabasePool, config: WriterConfig) -> (Self, JoinHandle<()>) {
+        let (sender, receiver) = mpsc::channel(config.channel_capacity.max(1));
+        let writer = Self {
+            sender,
+            dropped: Arc::new(AtomicU64::new(0)),
+            default_tenant_id: config.default_tenant_id,
+        };
+        let task = tokio::spawn(run(pool, receiver, config));
+        (writer, task)
+    }
+
+    /// Queue a record without waiting; drops it if the buffer is full
+    pub fn try_record(&self, record: ScanRecord) {
+        if self.sender.try_send(record).is_err() {
+            let dropped = self.dropped.fetch_add(1, Ordering::Relaxed) + 1;
+            if dropped.is_power_of_two() {
+                warn!("Scan event buffer full; {} records dropped so far", dropped);
+            }
+        }
+    }
+
+    /// Number of records dropped because the buffer was full or closed
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+
+    /// Resolve a tenant string, falling back to the configured default
+    pub fn resolve_tenant(&self, tenant_id: Option<&str>) -> Option<Uuid> {
+        tenant_id
+            .and_then(|t| Uuid::parse_str(t).ok())
+            .or(self.default_tenant_id)
+    }
+}
+
+async fn run(pool: DatabasePool, mut receiver: mpsc::Receiver<ScanRecord>, config: WriterConfig) {
+    let batch_size = config.batch_size.max(1);
+    let mut batch = Vec::with_capacity(batch_size);
+    let mut ticker = tokio::time::interval(config.flush_interval);
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    loop {
+        tokio::select! {
+            received = receiver.recv() => match received {
+                Some(record) => {
+                    batch.push(record);
+                    if batch.len() >= batch_size {
+                        flush(&pool, &mut batch).await;
+                    }
+                }
+                None => {
+                    flush(&pool, &mut batch).await;
+                    debug!("Scan event writer stopped");
+                    return;
+                }
+            },
+            _ = ticker.tick() => flush(&pool, &mut batch).await,
+        }
+    }
+}
+
+async fn flush(pool: &DatabasePool, batch: &mut Vec<ScanRecord>) {
+    if batch.is_empty() {
+        return;
+    }
+    let records = std::mem::take(batch);
+    match write_batch(pool, &records).await {
+        Ok(()) => debug!("Flushed {} scan records", records.len()),
+        Err(e) => warn!("Failed to write {} scan records: {}", records.len(), e),
+    }
+}
+
+/// Write a batch of records in one transaction
+pub async fn write_batch(pool: &DatabasePool, records: &[ScanRecord]) -> Result<()> {
+    if records.is_empty() {
+        return Ok(());
+    }
+
+    let mut tx = pool.inner().begin().await?;
+
+    // Metrics
+    let metrics = metric_rows(records);
+    sqlx::query(
+        r#"
+        INSERT INTO metrics (time, tenant_id, metric_name, metric_value, labels)
+        SELECT time, tenant_id, metric_name, metric_value, labels::jsonb
+        FROM UNNEST($1::timestamptz[], $2::uuid[], $3::text[], $4::float8[], $5::text[])
+            AS m(time, tenant_id, metric_name, metric_value, labels)
+        "#,
+    )
+    .bind(metrics.iter().map(|m| m.time).collect::<Vec<_>>())
+    .bind(metrics.iter().map(|m| m.tenant_id).collect::<Vec<_>>())
+    .bind(metrics.iter().map(|m| m.name).collect::<Vec<_>>())
+    .bind(metrics.iter().map(|m| m.value).collect::<Vec<_>>())
+    .bind(metrics.iter().map(|m| m.labels.to_string()).collect::<Vec<_>>())
+    .execute(&mut *tx)
+    .await?;
+
+    // Scanner stats
+    let now = Utc::now();
+    for stats in summarize(records) {
+        sqlx::query(
+            r#"
+            INSERT INTO scanner_stats
+                (time, tenant_id, scanner_name, requests_total, requests_valid,
+                 requests_invalid, avg_latency_ms, p95_latency_ms, p99_latency_ms)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            "#,
+        )
+        .bind(now)
+        .bind(stats.tenant_id)
+        .bind(&stats.scanner_name)
+        .bind(stats.requests_total)
+        .bind(stats.requests_valid)
+        .bind(stats.requests_invalid)
+        .bind(stats.avg_latency_ms)
+        .bind(stats.p95_latency_ms)
+        .bind(stats.p99_latency_ms)
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    // Security events for blocked scans
+    for record in records.iter().filter(|r| !r.is_valid) {
+        sqlx::query(
+            r#"
+            INSERT INTO security_events (time, tenant_id, event_type, severity, description, metadata)
+            VALUES ($1, $2, 'scan_blocked', $3, $4, $5)
+            "#,
+        )
+        .bind(record.time)
+        .bind(record.tenant_id)
+        .bind(severity_for_risk(record.risk_score).as_str())
+        .bind(format!(
+            "Scanner '{}' blocked {} (risk score {:.2})",
+            record.scanner, record.kind, record.risk_score
+        ))
+        .bind(json!({
+            "scanner": record.scanner,
+            "kind": record.kind,
+            "risk_score": record.risk_score,
+            "latency_ms": record.latency_ms,
+            "risk_factors": record.risk_factors,
+        }))
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    tx.commit().await?;
+    Ok(())
+}
+
+struct MetricRow {
+    time: DateTime<Utc>,
+    tenant_id: Uuid,
+    name: &'static str,
+    value: f64,
+    labels: serde_json::Value,
+}
+
+fn metric_rows(records: &[ScanRecord]) -> Vec<MetricRow> {
+    records
+        .iter()
+        .flat_map(|r| {
+            let labels = json!({
+                "scanner": r.scanner,
+                "kind": r.kind,
+                "valid": r.is_valid.to_string(),
+            });
+            [
+                ("scan_latency_ms", r.latency_ms),
+                ("scan_risk_score", r.risk_score),
+                ("scan_requests", 1.0),
+            ]
+            .into_iter()
+            .map(move |(name, value)| MetricRow {
+                time: r.time,
+                tenant_id: r.tenant_id,
+                name,
+                value,
+                labels: labels.clone(),
+            })
+        })
+        .collect()
+}
+
+/// Per-scanner statistics for one batch
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchStats {
+    pub tenant_id: Uuid,
+    pub scanner_name: String,
+    pub requests_total: i64,
+    pub requests_valid: i64,
+    pub requests_invalid: i64,
+    pub avg_latency_ms: f64,
+    pub p95_latency_ms: f64,
+    pub p99_latency_ms: f64,
+}
+
+/// Aggregate a batch into one row per (tenant, scanner)
+pub fn summarize(records: &[ScanRecord]) -> Vec<BatchStats> {
+    let mut groups: BTreeMap<(Uuid, &str), Vec<&ScanRecord>> = BTreeMap::new();
+    for record in records {
+        groups
+            .entry((record.tenant_id, record.scanner.as_str()))
+            .or_default()
+            .push(record);
+    }
+
+    groups
+        .into_iter()
+        .map(|((tenant_id, scanner), group)| {
+            let mut latencies: Vec<f64> = group.iter().map(|r| r.latency_ms).collect();
+            latencies.sort_by(|a, b| a.total_cmp(b));
+            let valid = group.iter().filter(|r| r.is_valid).count() as i64;
+            let total = group.len() as i64;
+
+            BatchStats {
+                tenant_id,
+                scanner_name: scanner.to_string(),
+                requests_total: total,
+                requests_valid: valid,
+                requests_invalid: total - valid,
+                avg_latency_ms: latencies.iter().sum::<f64>() / latencies.len() as f64,
+                p95_latency_ms: percentile(&latencies, 0.95),
+                p99_latency_ms: percentile(&latencies, 0.99),
+            }
+        })
+        .collect()
+}
+
+/// Nearest-rank percentile of sorted values
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let rank = (p * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// Severity of a blocked scan
+pub fn severity_for_risk(risk_score: f64) -> Severity {
+    if risk_score >= 0.9 {
+        Severity::Critical
+    } else if risk_score >= 0.7 {
+        Severity::Error
+    } else if risk_score >= 0.4 {
+        Severity::Warning
+    } else {
+        Severity::Info
+    }
+}
+
+#[cfg(feature = "api-sink")]
+impl llm_shield_api::services::ScanEventSink for ScanEventWriter {
+    fn record(&self, events: Vec<llm_shield_api::services::ScanEvent>) {
+        for event in events {
+            let Some(tenant_id) = self.resolve_tenant(event.tenant_id.as_deref()) else {
+                debug!("Dropping scan event without a resolvable tenant");
+                continue;
+            };
+            self.try_record(ScanRecord {
+                time: event.timestamp,
+                tenant_id,
+                scanner: event.scanner,
+                kind: event.kind.as_str().to_string(),
+                latency_ms: event.latency_ms as f64,
+                risk_score: f64::from(event.risk_score),
+                is_valid: event.is_valid,
+                risk_factors: event.risk_factors,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(tenant_id: Uuid, scanner: &str, latency_ms: f64, is_valid: bool) -> ScanRecord {
+        ScanRecord {
+            time: Utc::now(),
+            tenant_id,
+            scanner: scanner.to_string(),
+            kind: "prompt".to_string(),
+            latency_ms,
+            risk_score: if is_valid { 0.1 } else { 0.95 },
+            is_valid,
+            risk_factors: vec![],
+        }
+    }
+
+    #[test]
+    fn test_summarize_groups_by_tenant_and_scanner() {
+        let tenant = Uuid::new_v4();
+        let mut records: Vec<_> = (1..=100)
+            .map(|i| record(tenant, "toxicity", i as f64, i % 10 != 0))
+            .collect();
+        records.push(record(tenant, "secrets", 3.0, true));
+
+        let stats = summarize(&records);
+        assert_eq!(stats.len(), 2);
+
+        let toxicity = stats.iter().find(|s| s.scanner_name == "toxicity").unwrap();
+        assert_eq!(toxicity.requests_total, 100);
+        assert_eq!(toxicity.requests_invalid, 10);
+        assert_eq!(toxicity.avg_latency_ms, 50.5);
+        assert_eq!(toxicity.p95_latency_ms, 95.0);
+        assert_eq!(toxicity.p99_latency_ms, 99.0);
+    }
+
+    #[test]
+    fn test_metric_rows_per_record() {
+        let rows = metric_rows(&[record(Uuid::new_v4(), "toxicity", 12.0, false)]);
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[0].name, "scan_latency_ms");
+        assert_eq!(rows[0].labels["scanner"], "toxicity");
+        assert_eq!(rows[0].labels["valid"], "false");
+    }
+
+    #[test]
+    fn test_severity_for_risk() {
+        assert_eq!(severity_for_risk(0.95), Severity::Critical);
+        assert_eq!(severity_for_risk(0.75), Severity::Error);
+        assert_eq!(severity_for_risk(0.5), Severity::Warning);
+        assert_eq!(severity_for_risk(0.1), Severity::Info);
+    }
+
+    #[test]
+    fn test_resolve_tenant() {
+        let default = Uuid::new_v4();
+        let (sender, _receiver) = mpsc::channel(1);
+        let writer = ScanEventWriter {
+            sender,
+            dropped: Arc::new(AtomicU64::new(0)),
+            default_tenant_id: Some(default),
+        };
+
+        let tenant = Uuid::new_v4();
+        assert_eq!(writer.resolve_tenant(Some(&tenant.to_string())), Some(tenant));
+        assert_eq!(writer.resolve_tenant(Some("not-a-uuid")), Some(default));
+        assert_eq!(writer.resolve_tenant(None), Some(default));
+    }
+
+    #[tokio::test]
+    #[ignore] // Requires running PostgreSQL with TimescaleDB
+    async fn test_write_batch() {
+        let database_url = std::env::var("DATABASE_URL")
+            .unwrap_or_else(|_| "postgres://localhost/llm_shield_dashboard_test".to_string());
+        let config = crate::config::DatabaseConfig {
+            url: database_url,
+            ..Default::default()
+        };
+        let pool = DatabasePool::new(&config).await.unwrap();
+        crate::db::initialize_database(pool.inner()).await.unwrap();
+
+        let tenant = Uuid::new_v4();
+        let records = vec![
+            record(tenant, "toxicity", 10.0, true),
+            record(tenant, "toxicity", 30.0, false),
+        ];
+        write_batch(&pool, &records).await.unwrap();
+
+        let blocked: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM security_events WHERE tenant_id = $1 AND event_type = 'scan_blocked'",
+        )
+        .bind(tenant)
+        .fetch_one(pool.inner())
+        .await
+        .unwrap();
+        assert_eq!(blocked, 1);
+
+        let requests: i64 = sqlx::query_scalar(
+            "SELECT requests_total FROM scanner_stats WHERE tenant_id = $1 AND scanner_name = 'toxicity'",
+        )
+        .bind(tenant)
+        .fetch_one(pool.inner())
+        .await
+        .unwrap();
+        assert_eq!(requests, 2);
+
+        pool.close().await;
+    }
+}
@@ -1,10 +1,44 @@
 //! GraphQL API
+//!
+//! All security data queries are tenant-scoped: the tenant comes from the
+//! authenticated [`Claims`], and only `super_admin` users may query another
+//! tenant by passing `tenantId` explicitly.
 
-use crate::{db::DatabasePool, models::*};
-use async_graphql::{Context, EmptyMutation, EmptySubscription, Object, Schema};
+pub mod types;
+
+use crate::{auth::Claims, db::DatabasePool, models::*};
+use async_graphql::{Context, EmptySubscription, Object, Schema};
+use chrono::Utc;
+use types::*;
 use uuid::Uuid;
 
-pub type DashboardSchema = Schema<Query, EmptyMutation, EmptySubscription>;
+pub type DashboardSchema = Schema<Query, Mutation, EmptySubscription>;
+
+/// Resolve the tenant a caller may access
+pub fn resolve_tenant(claims: &Claims, requested: Option<Uuid>) -> async_graphql::Result<Uuid> {
+    match requested {
+        Some(tenant_id) if tenant_id != claims.tenant_id => {
+            if claims.role == "super_admin" {
+                Ok(tenant_id)
+            } else {
+                Err("Access to other tenants is not permitted".into())
+            }
+        }
+        _ => Ok(claims.tenant_id),
+    }
+}
+
+/// Ensure the caller may modify data (viewers are read-only)
+pub fn require_write_access(claims: &Claims) -> async_graphql::Result<()> {
+    if claims.role == "viewer" {
+        return Err("Viewers cannot modify data".into());
+    }
+    Ok(())
+}
+
+fn scoped_tenant(ctx: &Context<'_>, requested: Option<Uuid>) -> async_graphql::Result<Uuid> {
+    resolve_tenant(ctx.data::<Claims>()?, requested)
+}
 
 /// GraphQL Query root
 pub struct Query;
@@ -50,11 +84,449 @@ impl Query {
         .await?;
         Ok(user)
     }
+
+    /// Security events, newest first
+    async fn security_events(
+        &self,
+        ctx: &Context<'_>,
+        tenant_id: Option<Uuid>,
+        time_range: Option<TimeRangeInput>,
+        filter: Option<SecurityEventFilter>,
+        pagination: Option<PaginationInput>,
+    ) -> async_graphql::Result<SecurityEventPage> {
+        let pool = ctx.data::<DatabasePool>()?;
+        let tenant_id = scoped_tenant(ctx, tenant_id)?;
+        let (from, to) = TimeRangeInput::resolve(time_range)?;
+        let (limit, offset) = PaginationInput::resolve(pagination);
+        let filter = filter.unwrap_or_default();
+        let severity = filter.severity.map(|s| s.as_str());
+
+        let items = sqlx::query_as!(
+            SecurityEvent,
+            r#"SELECT
+                time, event_id, tenant_id, user_id, event_type,
+                severity as "severity: Severity",
+                description, metadata,
+                acknowledged as "acknowledged!",
+                acknowledged_by, acknowledged_at
+            FROM security_events
+            WHERE tenant_id = $1 AND time >= $2 AND time < $3
+              AND ($4::text IS NULL OR severity = $4)
+              AND ($5::text IS NULL OR event_type = $5)
+              AND ($6::boolean IS NULL OR COALESCE(acknowledged, false) = $6)
+            ORDER BY time DESC
+            LIMIT $7 OFFSET $8"#,
+            tenant_id,
+            from,
+            to,
+            severity,
+            filter.event_type,
+            filter.acknowledged,
+            limit,
+            offset
+        )
+        .fetch_all(pool.inner())
+        .await?;
+
+        let total_count = sqlx::query_scalar!(
+            r#"SELECT COUNT(*) as "count!"
+            FROM security_events
+            WHERE tenant_id = $1 AND time >= $2 AND time < $3
+              AND ($4::text IS NULL OR severity = $4)
+              AND ($5::text IS NULL OR event_type = $5)
+              AND ($6::boolean IS NULL OR COALESCE(acknowledged, false) = $6)"#,
+            tenant_id,
+            from,
+            to,
+            severity,
+            filter.event_type,
+            filter.acknowledged
+        )
+        .fetch_one(pool.inner())
+        .await?;
+
+        Ok(SecurityEventPage {
+            items,
+            total_count,
+            has_next_page: has_next_page(total_count, limit, offset),
+        })
+    }
+
+    /// Raw scanner statistics rows, newest first
+    async fn scanner_stats(
+        &self,
+        ctx: &Context<'_>,
+        tenant_id: Option<Uuid>,
+        time_range: Option<TimeRangeInput>,
+        scanner_name: Option<String>,
+        pagination: Option<PaginationInput>,
+    ) -> async_graphql::Result<ScannerStatsPage> {
+        let pool = ctx.data::<DatabasePool>()?;
+        let tenant_id = scoped_tenant(ctx, tenant_id)?;
+        let (from, to) = TimeRangeInput::resolve(time_range)?;
+        let (limit, offset) = PaginationInput::resolve(pagination);
+
+        let items = sqlx::query_as!(
+            ScannerStats,
+            r#"SELECT
+                time, tenant_id, scanner_name, requests_total, requests_valid,
+                requests_invalid, avg_latency_ms, p95_latency_ms, p99_latency_ms, metadata
+            FROM scanner_stats
+            WHERE tenant_id = $1 AND time >= $2 AND time < $3
+              AND ($4::text IS NULL OR scanner_name = $4)
+            ORDER BY time DESC
+            LIMIT $5 OFFSET $6"#,
+            tenant_id,
+            from,
+            to,
+            scanner_name,
+            limit,
+            offset
+        )
+        .fetch_all(pool.inner())
+        .await?;
+
+        let total_count = sqlx::query_scalar!(
+            r#"SELECT COUNT(*) as "count!"
+            FROM scanner_stats
+            WHERE tenant_id = $1 AND time >= $2 AND time < $3
+              AND ($4::text IS NULL OR scanner_name = $4)"#,
+            tenant_id,
+            from,
+            to,
+            scanner_name
+        )
+        .fetch_one(pool.inner())
+        .await?;
+
+        Ok(ScannerStatsPage {
+            items,
+            total_count,
+            has_next_page: has_next_page(total_count, limit, offset),
+        })
+    }
+
+    /// Bucketed metric series from the `metrics_1min` continuous aggregate
+    async fn metric_series(
+        &self,
+        ctx: &Context<'_>,
+        tenant_id: Option<Uuid>,
+        metric_name: String,
+        scanner: Option<String>,
+        time_range: Option<TimeRangeInput>,
+        #[graphql(default_with = "TimeBucket::Minute")] bucket: TimeBucket,
+    ) -> async_graphql::Result<Vec<MetricSeriesPoint>> {
+        let pool = ctx.data::<DatabasePool>()?;
+        let tenant_id = scoped_tenant(ctx, tenant_id)?;
+        let (from, to) = TimeRangeInput::resolve(time_range)?;
+
+        let points = sqlx::query_as!(
+            MetricSeriesPoint,
+            r#"SELECT
+                time_bucket($1::text::interval, bucket) as "bucket!",
+                (SUM(avg_value * count) / NULLIF(SUM(count), 0))::float8 as avg_value,
+                MAX(max_value) as max_value,
+                MIN(min_value) as min_value,
+                SUM(count)::bigint as "count!"
+            FROM metrics_1min
+            WHERE tenant_id = $2 AND metric_name = $3
+              AND ($4::text IS NULL OR scanner = $4)
+              AND bucket >= $5 AND bucket < $6
+            GROUP BY 1
+            ORDER BY 1"#,
+            bucket.interval(),
+            tenant_id,
+            metric_name,
+            scanner,
+            from,
+            to
+        )
+        .fetch_all(pool.inner())
+        .await?;
+
+        Ok(points)
+    }
+
+    /// Per-scanner totals from the `scanner_stats_hourly` continuous aggregate
+    async fn scanner_summary(
+        &self,
+        ctx: &Context<'_>,
+        tenant_id: Option<Uuid>,
+        time_range: Option<TimeRangeInput>,
+    ) -> async_graphql::Result<Vec<ScannerSummary>> {
+        let pool = ctx.data::<DatabasePool>()?;
+        let tenant_id = scoped_tenant(ctx, tenant_id)?;
+        let (from, to) = TimeRangeInput::resolve(time_range)?;
+
+        let summary = sqlx::query_as!(
+            ScannerSummary,
+            r#"SELECT
+                scanner_name as "scanner_name!",
+                SUM(requests_total)::bigint as "requests_total!",
+                SUM(requests_valid)::bigint as "requests_valid!",
+                SUM(requests_invalid)::bigint as "requests_invalid!",
+                AVG(avg_latency_ms)::float8 as avg_latency_ms,
+                MAX(max_p95_latency_ms) as max_p95_latency_ms,
+                MAX(max_p99_latency_ms) as max_p99_latency_ms
+            FROM scanner_stats_hourly
+            WHERE tenant_id = $1 AND bucket >= $2 AND bucket < $3
+            GROUP BY scanner_name
+            ORDER BY 2 DESC"#,
+            tenant_id,
+            from,
+            to
+        )
+        .fetch_all(pool.inner())
+        .await?;
+
+        Ok(summary)
+    }
+
+    /// Security event counts by severity from the `security_events_hourly`
+    /// continuous aggregate (`MINUTE` buckets are widened to `HOUR`)
+    async fn security_event_counts(
+        &self,
+        ctx: &Context<'_>,
+        tenant_id: Option<Uuid>,
+        time_range: Option<TimeRangeInput>,
+        #[graphql(default_with = "TimeBucket::Hour")] bucket: TimeBucket,
+    ) -> async_graphql::Result<Vec<SecurityEventCount>> {
+        let pool = ctx.data::<DatabasePool>()?;
+        let tenant_id = scoped_tenant(ctx, tenant_id)?;
+        let (from, to) = TimeRangeInput::resolve(time_range)?;
+
+        let counts = sqlx::query_as!(
+            SecurityEventCount,
+            r#"SELECT
+                time_bucket($1::text::interval, bucket) as "bucket!",
+                severity as "severity!: Severity",
+                SUM(event_count)::bigint as "count!"
+            FROM security_events_hourly
+            WHERE tenant_id = $2 AND bucket >= $3 AND bucket < $4
+            GROUP BY 1, 2
+            ORDER BY 1, 2"#,
+            bucket.hourly_interval(),
+            tenant_id,
+            from,
+            to
+        )
+        .fetch_all(pool.inner())
+        .await?;
+
+        Ok(counts)
+    }
+
+    /// Alert rules for a tenant
+    async fn alert_rules(
+        &self,
+        ctx: &Context<'_>,
+        tenant_id: Option<Uuid>,
+    ) -> async_graphql::Result<Vec<AlertRule>> {
+        let pool = ctx.data::<DatabasePool>()?;
+        let tenant_id = scoped_tenant(ctx, tenant_id)?;
+
+        let rules = sqlx::query_as!(
+            AlertRule,
+            r#"SELECT
+                id, tenant_id, name, description, query, threshold, operator,
+                duration_seconds,
+                severity as "severity: Severity",
+                enabled as "enabled!",
+                COALESCE(notification_channels, '{}') as "notification_channels!",
+                created_by, created_at, updated_at
+            FROM alert_rules
+            WHERE tenant_id = $1
+            ORDER BY name"#,
+            tenant_id
+        )
+        .fetch_all(pool.inner())
+        .await?;
+
+        Ok(rules)
+    }
+}
+
+/// GraphQL Mutation root
+pub struct Mutation;
+
+#[Object]
+impl Mutation {
+    /// Acknowledge a single security event
+    async fn acknowledge_event(
+        &self,
+        ctx: &Context<'_>,
+        event_id: Uuid,
+    ) -> async_graphql::Result<SecurityEvent> {
+        let pool = ctx.data::<DatabasePool>()?;
+        let claims = ctx.data::<Claims>()?;
+        require_write_access(claims)?;
+
+        let event = sqlx::query_as!(
+            SecurityEvent,
+            r#"UPDATE security_events
+            SET acknowledged = true, acknowledged_by = $3, acknowledged_at = NOW()
+            WHERE tenant_id = $1 AND event_id = $2
+            RETURNING
+                time, event_id, tenant_id, user_id, event_type,
+                severity as "severity: Severity",
+                description, metadata,
+                acknowledged as "acknowledged!",
+                acknowledged_by, acknowledged_at"#,
+            claims.tenant_id,
+            event_id,
+            claims.sub
+        )
+        .fetch_optional(pool.inner())
+        .await?;
+
+        event.ok_or_else(|| "Security event not found".into())
+    }
+
+    /// Acknowledge several security events; returns how many were updated
+    async fn acknowledge_events(
+        &self,
+        ctx: &Context<'_>,
+        event_ids: Vec<Uuid>,
+    ) -> async_graphql::Result<AcknowledgeResult> {
+        let pool = ctx.data::<DatabasePool>()?;
+        let claims = ctx.data::<Claims>()?;
+        require_write_access(claims)?;
+
+        let acknowledged_at = Utc::now();
+        let result = sqlx::query!(
+            r#"UPDATE security_events
+            SET acknowledged = true, acknowledged_by = $3, acknowledged_at = $4
+            WHERE tenant_id = $1 AND event_id = ANY($2)
+              AND acknowledged IS NOT TRUE"#,
+            claims.tenant_id,
+            &event_ids,
+            claims.sub,
+            acknowledged_at
+        )
+        .execute(pool.inner())
+        .await?;
+
+        Ok(AcknowledgeResult {
+            acknowledged_count: result.rows_affected() as i64,
+            acknowledged_by: claims.sub,
+            acknowledged_at,
+        })
+    }
+
+    /// Create an alert rule in the caller's tenant
+    async fn create_alert_rule(
+        &self,
+        ctx: &Context<'_>,
+        input: CreateAlertRuleInput,
+    ) -> async_graphql::Result<AlertRule> {
+        let pool = ctx.data::<DatabasePool>()?;
+        let claims = ctx.data::<Claims>()?;
+        require_write_access(claims)?;
+        input.validate()?;
+
+        let rule = sqlx::query_as!(
+            AlertRule,
+            r#"INSERT INTO alert_rules
+                (tenant_id, name, description, query, threshold, operator,
+                 duration_seconds, severity, enabled, notification_channels, created_by)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+            RETURNING
+                id, tenant_id, name, description, query, threshold, operator,
+                duration_seconds,
+                severity as "severity: Severity",
+                enabled as "enabled!",
+                COALESCE(notification_channels, '{}') as "notification_channels!",
+                created_by, created_at, updated_at"#,
+            claims.tenant_id,
+            input.name.trim(),
+            input.description,
+            input.query,
+            input.threshold,
+            input.operator,
+            input.duration_seconds,
+            input.severity.as_str(),
+            input.enabled,
+            &input.notification_channels,
+            claims.sub
+        )
+        .fetch_one(pool.inner())
+        .await?;
+
+        Ok(rule)
+    }
+
+    /// Update an alert rule; omitted fields are left unchanged
+    async fn update_alert_rule(
+        &self,
+        ctx: &Context<'_>,
+        id: Uuid,
+        input: UpdateAlertRuleInput,
+    ) -> async_graphql::Result<AlertRule> {
+        let pool = ctx.data::<DatabasePool>()?;
+        let claims = ctx.data::<Claims>()?;
+        require_write_access(claims)?;
+        input.validate()?;
+
+        let rule = sqlx::query_as!(
+            AlertRule,
+            r#"UPDATE alert_rules SET
+                name = COALESCE($3, name),
+                description = COALESCE($4, description),
+                query = COALESCE($5, query),
+                threshold = COALESCE($6, threshold),
+                operator = COALESCE($7, operator),
+                duration_seconds = COALESCE($8, duration_seconds),
+                severity = COALESCE($9, severity),
+                enabled = COALESCE($10, enabled),
+                notification_channels = COALESCE($11, notification_channels),
+                updated_at = NOW()
+            WHERE tenant_id = $1 AND id = $2
+            RETURNING
+                id, tenant_id, name, description, query, threshold, operator,
+                duration_seconds,
+                severity as "severity: Severity",
+                enabled as "enabled!",
+                COALESCE(notification_channels, '{}') as "notification_channels!",
+                created_by, created_at, updated_at"#,
+            claims.tenant_id,
+            id,
+            input.name.as_deref().map(str::trim),
+            input.description,
+            input.query,
+            input.threshold,
+            input.operator,
+            input.duration_seconds,
+            input.severity.map(|s| s.as_str()),
+            input.enabled,
+            input.notification_channels.as_deref()
+        )
+        .fetch_optional(pool.inner())
+        .await?;
+
+        rule.ok_or_else(|| "Alert rule not found".into())
+    }
+
+    /// Delete an alert rule; returns whether it existed
+    async fn delete_alert_rule(&self, ctx: &Context<'_>, id: Uuid) -> async_graphql::Result<bool> {
+        let pool = ctx.data::<DatabasePool>()?;
+        let claims = ctx.data::<Claims>()?;
+        require_write_access(claims)?;
+
+        let result = sqlx::query!(
+            "DELETE FROM alert_rules WHERE tenant_id = $1 AND id = $2",
+            claims.tenant_id,
+            id
+        )
+        .execute(pool.inner())
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
 }
 
 /// Create GraphQL schema
 pub fn create_schema(pool: DatabasePool) -> DashboardSchema {
-    Schema::build(Query, EmptyMutation, EmptySubscription)
+    Schema::build(Query, Mutation, EmptySubscription)
         .data(pool)
         .finish()
 }
@@ -63,6 +535,16 @@ pub fn create_schema(pool: DatabasePool) -> DashboardSchema {
 mod tests {
     use super::*;
 
+    fn claims(role: &str) -> Claims {
+        Claims {
+            sub: Uuid::new_v4(),
+            tenant_id: Uuid::new_v4(),
+            role: role.to_string(),
+            exp: 9999999999,
+            iat: 1000000000,
+        }
+    }
+
     #[test]
     fn test_schema_creation() {
         // This test just verifies the schema can be created
@@ -72,4 +554,48 @@ mod tests {
         assert!(sdl.contains("version"));
         assert!(sdl.contains("health"));
     }
+
+    #[test]
+    fn test_schema_exposes_security_data() {
+        let sdl = DashboardSchema::sdl();
+        assert!(sdl.contains("securityEvents"));
+        assert!(sdl.contains("scannerStats"));
+        assert!(sdl.contains("metricSeries"));
+        assert!(sdl.contains("scannerSummary"));
+        assert!(sdl.contains("securityEventCounts"));
+        assert!(sdl.contains("type Mutation"));
+        assert!(sdl.contains("acknowledgeEvent"));
+    }
+
+    #[test]
+    fn test_schema_exposes_alert_rules() {
+        let sdl = DashboardSchema::sdl();
+        assert!(sdl.contains("alertRules"));
+        assert!(sdl.contains("createAlertRule"));
+        assert!(sdl.contains("updateAlertRule"));
+        assert!(sdl.contains("deleteAlertRule"));
+    }
+
+    #[test]
+    fn test_resolve_tenant_defaults_to_own() {
+        let claims = claims("developer");
+        assert_eq!(resolve_tenant(&claims, None).unwrap(), claims.tenant_id);
+        assert_eq!(
+            resolve_tenant(&claims, Some(claims.tenant_id)).unwrap(),
+            claims.tenant_id
+        );
+    }
+
+    #[test]
+    fn test_resolve_tenant_cross_tenant() {
+        let other = Uuid::new_v4();
+        assert!(resolve_tenant(&claims("tenant_admin"), Some(other)).is_err());
+        assert_eq!(resolve_tenant(&claims("super_admin"), Some(other)).unwrap(), other);
+    }
+
+    #[test]
+    fn test_viewer_is_read_only() {
+        assert!(require_write_access(&claims("viewer")).is_err());
+        assert!(require_write_access(&claims("developer")).is_ok());
+    }
 }
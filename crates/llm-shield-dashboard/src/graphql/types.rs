@@ -0,0 +1,323 @@
+//! GraphQL input and output types
+
+use crate::alerts::{MetricQuery, NotificationChannel, Operator};
+use crate::models::{ScannerStats, SecurityEvent, Severity};
+use async_graphql::{Enum, InputObject, SimpleObject};
+use chrono::{DateTime, Duration, Utc};
+use uuid::Uuid;
+
+/// Default page size
+pub const DEFAULT_PAGE_SIZE: i64 = 50;
+
+/// Maximum page size
+pub const MAX_PAGE_SIZE: i64 = 500;
+
+/// Time range filter (`from` inclusive, `to` exclusive)
+#[derive(Debug, Clone, Copy, InputObject)]
+pub struct TimeRangeInput {
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+}
+
+impl TimeRangeInput {
+    /// Resolve an optional range, defaulting to the last 24 hours
+    pub fn resolve(range: Option<Self>) -> async_graphql::Result<(DateTime<Utc>, DateTime<Utc>)> {
+        let (from, to) = match range {
+            Some(range) => (range.from, range.to),
+            None => {
+                let now = Utc::now();
+                (now - Duration::hours(24), now)
+            }
+        };
+
+        if from >= to {
+            return Err("Time range 'from' must be before 'to'".into());
+        }
+
+        Ok((from, to))
+    }
+}
+
+/// Offset pagination
+#[derive(Debug, Clone, Copy, Default, InputObject)]
+pub struct PaginationInput {
+    /// Page size (default 50, max 500)
+    pub limit: Option<i64>,
+    /// Number of rows to skip
+    pub offset: Option<i64>,
+}
+
+impl PaginationInput {
+    /// Resolve to a clamped `(limit, offset)` pair
+    pub fn resolve(page: Option<Self>) -> (i64, i64) {
+        let page = page.unwrap_or_default();
+        let limit = page.limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+        let offset = page.offset.unwrap_or(0).max(0);
+        (limit, offset)
+    }
+}
+
+/// Security event filters
+#[derive(Debug, Clone, Default, InputObject)]
+pub struct SecurityEventFilter {
+    pub severity: Option<Severity>,
+    pub event_type: Option<String>,
+    pub acknowledged: Option<bool>,
+}
+
+/// Aggregation bucket width
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Enum)]
+pub enum TimeBucket {
+    Minute,
+    Hour,
+    Day,
+}
+
+impl TimeBucket {
+    /// Postgres interval literal
+    pub fn interval(&self) -> &'static str {
+        match self {
+            TimeBucket::Minute => "1 minute",
+            TimeBucket::Hour => "1 hour",
+            TimeBucket::Day => "1 day",
+        }
+    }
+
+    /// Interval literal no finer than one hour (for hourly aggregates)
+    pub fn hourly_interval(&self) -> &'static str {
+        match self {
+            TimeBucket::Minute => TimeBucket::Hour.interval(),
+            other => other.interval(),
+        }
+    }
+}
+
+/// Page of security events
+#[derive(Debug, Clone, SimpleObject)]
+pub struct SecurityEventPage {
+    pub items: Vec<SecurityEvent>,
+    pub total_count: i64,
+    pub has_next_page: bool,
+}
+
+/// Page of scanner statistics
+#[derive(Debug, Clone, SimpleObject)]
+pub struct ScannerStatsPage {
+    pub items: Vec<ScannerStats>,
+    pub total_count: i64,
+    pub has_next_page: bool,
+}
+
+/// Whether more rows exist after this page
+pub fn has_next_page(total_count: i64, limit: i64, offset: i64) -> bool {
+    offset + limit < total_count
+}
+
+/// Bucketed metric values (from `metrics_1min`)
+#[derive(Debug, Clone, SimpleObject, sqlx::FromRow)]
+pub struct MetricSeriesPoint {
+    pub bucket: DateTime<Utc>,
+    pub avg_value: Option<f64>,
+    pub max_value: Option<f64>,
+    pub min_value: Option<f64>,
+    pub count: i64,
+}
+
+/// Per-scanner totals (from `scanner_stats_hourly`)
+#[derive(Debug, Clone, SimpleObject, sqlx::FromRow)]
+pub struct ScannerSummary {
+    pub scanner_name: String,
+    pub requests_total: i64,
+    pub requests_valid: i64,
+    pub requests_invalid: i64,
+    pub avg_latency_ms: Option<f64>,
+    pub max_p95_latency_ms: Option<f64>,
+    pub max_p99_latency_ms: Option<f64>,
+}
+
+/// Bucketed security event counts (from `security_events_hourly`)
+#[derive(Debug, Clone, SimpleObject, sqlx::FromRow)]
+pub struct SecurityEventCount {
+    pub bucket: DateTime<Utc>,
+    pub severity: Severity,
+    pub count: i64,
+}
+
+/// Result of a bulk acknowledgement
+#[derive(Debug, Clone, SimpleObject)]
+pub struct AcknowledgeResult {
+    pub acknowledged_count: i64,
+    pub acknowledged_by: Uuid,
+    pub acknowledged_at: DateTime<Utc>,
+}
+
+/// New alert rule
+#[derive(Debug, Clone, InputObject)]
+pub struct CreateAlertRuleInput {
+    pub name: String,
+    pub description: Option<String>,
+    /// Metric query, e.g. `avg(scan_latency_ms{scanner="toxicity"}[5m])`
+    pub query: String,
+    pub threshold: f64,
+    /// One of `>`, `<`, `>=`, `<=`, `=`, `!=`
+    pub operator: String,
+    /// Seconds the condition must hold before firing
+    #[graphql(default)]
+    pub duration_seconds: i32,
+    pub severity: Severity,
+    #[graphql(default = true)]
+    pub enabled: bool,
+    /// Webhook URLs (`webhook:https://...` or `https://...`)
+    #[graphql(default)]
+    pub notification_channels: Vec<String>,
+}
+
+impl CreateAlertRuleInput {
+    /// Validate the rule definition
+    pub fn validate(&self) -> async_graphql::Result<()> {
+        validate_rule_fields(
+            Some(&self.name),
+            Some(&self.query),
+            Some(self.threshold),
+            Some(&self.operator),
+            Some(self.duration_seconds),
+            Some(&self.notification_channels),
+        )
+    }
+}
+
+/// Partial alert rule update (omitted fields are unchanged)
+#[derive(Debug, Clone, Default, InputObject)]
+pub struct UpdateAlertRuleInput {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub query: Option<String>,
+    pub threshold: Option<f64>,
+    pub operator: Option<String>,
+    pub duration_seconds: Option<i32>,
+    pub severity: Option<Severity>,
+    pub enabled: Option<bool>,
+    pub notification_channels: Option<Vec<String>>,
+}
+
+impl UpdateAlertRuleInput {
+    /// Validate the provided fields
+    pub fn validate(&self) -> async_graphql::Result<()> {
+        validate_rule_fields(
+            self.name.as_deref(),
+            self.query.as_deref(),
+            self.threshold,
+            self.operator.as_deref(),
+            self.duration_seconds,
+            self.notification_channels.as_deref(),
+        )
+    }
+}
+
+fn validate_rule_fields(
+    name: Option<&str>,
+    query: Option<&str>,
+    threshold: Option<f64>,
+    operator: Option<&str>,
+    duration_seconds: Option<i32>,
+    channels: Option<&[String]>,
+) -> async_graphql::Result<()> {
+    if name.is_some_and(|name| name.trim().is_empty()) {
+        return Err("Alert rule name cannot be empty".into());
+    }
+    if let Some(query) = query {
+        MetricQuery::parse(query)?;
+    }
+    if threshold.is_some_and(|t| !t.is_finite()) {
+        return Err("Alert rule threshold must be finite".into());
+    }
+    if let Some(operator) = operator {
+        Operator::parse(operator)?;
+    }
+    if duration_seconds.is_some_and(|d| d < 0) {
+        return Err("Alert rule duration cannot be negative".into());
+    }
+    for channel in channels.unwrap_or_default() {
+        NotificationChannel::parse(channel)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_time_range_default() {
+        let (from, to) = TimeRangeInput::resolve(None).unwrap();
+        assert_eq!(to - from, Duration::hours(24));
+    }
+
+    #[test]
+    fn test_time_range_inverted() {
+        let now = Utc::now();
+        let range = TimeRangeInput {
+            from: now,
+            to: now - Duration::hours(1),
+        };
+        assert!(TimeRangeInput::resolve(Some(range)).is_err());
+    }
+
+    #[test]
+    fn test_pagination_clamped() {
+        assert_eq!(PaginationInput::resolve(None), (DEFAULT_PAGE_SIZE, 0));
+
+        let page = PaginationInput {
+            limit: Some(10_000),
+            offset: Some(-5),
+        };
+        assert_eq!(PaginationInput::resolve(Some(page)), (MAX_PAGE_SIZE, 0));
+    }
+
+    #[test]
+    fn test_has_next_page() {
+        assert!(has_next_page(120, 50, 50));
+        assert!(!has_next_page(100, 50, 50));
+    }
+
+    #[test]
+    fn test_hourly_interval() {
+        assert_eq!(TimeBucket::Minute.hourly_interval(), "1 hour");
+        assert_eq!(TimeBucket::Day.hourly_interval(), "1 day");
+    }
+
+    #[test]
+    fn test_alert_rule_input_validation() {
+        let input = CreateAlertRuleInput {
+            name: "High latency".to_string(),
+            description: None,
+            query: "avg(scan_latency_ms[5m])".to_string(),
+            threshold: 100.0,
+            operator: ">".to_string(),
+            duration_seconds: 60,
+            severity: Severity::Warning,
+            enabled: true,
+            notification_channels: vec!["https://hooks.example.com/alerts".to_string()],
+        };
+        assert!(input.validate().is_ok());
+
+        let bad_operator = CreateAlertRuleInput {
+            operator: "~".to_string(),
+            ..input.clone()
+        };
+        assert!(bad_operator.validate().is_err());
+
+        let bad_channel = CreateAlertRuleInput {
+            notification_channels: vec!["sms:+15550100".to_string()],
+            ..input
+        };
+        assert!(bad_channel.validate().is_err());
+
+        let update = UpdateAlertRuleInput {
+            duration_seconds: Some(-1),
+            ..Default::default()
+        };
+        assert!(update.validate().is_err());
+        assert!(UpdateAlertRuleInput::default().validate().is_ok());
+    }
+}
@@ -36,6 +36,7 @@
 //! }
 //! ```
 
+pub mod alerts;
 pub mod api;
 pub mod auth;
 pub mod config;
@@ -5,7 +5,7 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 /// Tenant model
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, async_graphql::SimpleObject)]
 pub struct Tenant {
     pub id: Uuid,
     pub name: String,
@@ -16,12 +16,13 @@ pub struct Tenant {
 }
 
 /// User model
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, async_graphql::SimpleObject)]
 pub struct User {
     pub id: Uuid,
     pub tenant_id: Uuid,
     pub email: String,
     #[serde(skip_serializing)]
+    #[graphql(skip)]
     pub password_hash: String,
     pub role: UserRole,
     pub enabled: bool,
@@ -30,7 +31,7 @@ pub struct User {
 }
 
 /// User role
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, async_graphql::Enum)]
 #[sqlx(type_name = "text")]
 pub enum UserRole {
     #[serde(rename = "super_admin")]
@@ -63,7 +64,7 @@ pub struct ApiKey {
 }
 
 /// Metric data point
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, async_graphql::SimpleObject)]
 pub struct MetricDataPoint {
     pub time: DateTime<Utc>,
     pub tenant_id: Uuid,
@@ -74,7 +75,7 @@ pub struct MetricDataPoint {
 }
 
 /// Scanner statistics
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, async_graphql::SimpleObject)]
 pub struct ScannerStats {
     pub time: DateTime<Utc>,
     pub tenant_id: Uuid,
@@ -89,7 +90,7 @@ pub struct ScannerStats {
 }
 
 /// Security event
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, async_graphql::SimpleObject)]
 pub struct SecurityEvent {
     pub time: DateTime<Utc>,
     pub event_id: Uuid,
@@ -105,7 +106,7 @@ pub struct SecurityEvent {
 }
 
 /// Severity level
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, async_graphql::Enum)]
 #[sqlx(type_name = "text")]
 pub enum Severity {
     #[serde(rename = "info")]
@@ -122,8 +123,20 @@ pub enum Severity {
     Critical,
 }
 
+impl Severity {
+    /// Database representation
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+            Severity::Critical => "critical",
+        }
+    }
+}
+
 /// Alert rule
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, async_graphql::SimpleObject)]
 pub struct AlertRule {
     pub id: Uuid,
     pub tenant_id: Uuid,
@@ -206,6 +219,7 @@ mod tests {
 
         let deserialized: Severity = serde_json::from_str(&json).unwrap();
         assert_eq!(deserialized, Severity::Critical);
+        assert_eq!(severity.as_str(), "critical");
     }
 
     #[test]
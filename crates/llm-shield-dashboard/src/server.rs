@@ -1,6 +1,7 @@
 //! Dashboard server
 
 use crate::{
+    alerts::AlertEvaluator,
     api::{create_router, AppState},
     config::DashboardConfig,
     db::{migrations::run_migrations, DatabasePool},
@@ -92,6 +93,15 @@ impl DashboardServer {
         // Create router
         let app = create_router(state);
 
+        // Start background alert evaluation
+        let alert_task = if self.config.alerting.enabled {
+            let evaluator = AlertEvaluator::new(self.pool.clone(), self.config.alerting.clone())?;
+            Some(Arc::new(evaluator).spawn())
+        } else {
+            info!("Alert evaluation disabled");
+            None
+        };
+
         // Create socket address
         let addr = format!("{}:{}", self.config.server.host, self.config.server.port);
         let socket_addr: SocketAddr = addr.parse()
@@ -114,6 +124,10 @@ impl DashboardServer {
             .await
             .map_err(|e| crate::error::DashboardError::Server(e.to_string()))?;
 
+        if let Some(task) = alert_task {
+            task.abort();
+        }
+
         info!("Server stopped gracefully");
         Ok(())
     }
@@ -198,6 +212,7 @@ mod tests {
                 level: "info".to_string(),
                 format: "text".to_string(),
             },
+            alerting: Default::default(),
         }
     }
 
@@ -40,6 +40,7 @@ fn create_test_config() -> DashboardConfig {
             level: "debug".to_string(),
             format: "text".to_string(),
         },
+        alerting: Default::default(),
     }
 }
 
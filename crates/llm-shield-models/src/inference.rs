@@ -764,6 +764,184 @@ impl InferenceEngine {
 
         Ok(predictions)
     }
+
+    /// Compute a sentence embedding (for semantic similarity models)
+    ///
+    /// Runs the model and mean-pools the `last_hidden_state` output over the
+    /// attention mask, then L2-normalizes the result so that the dot product of
+    /// two embeddings equals their cosine similarity.
+    ///
+    /// # Arguments
+    ///
+    /// * `input_ids` - Token IDs from tokenizer
+    /// * `attention_mask` - Attention mask (1=real token, 0=padding)
+    ///
+    /// # Returns
+    ///
+    /// Normalized embedding vector of the model's hidden size
+    pub async fn embed_async(
+        &self,
+        input_ids: &[u32],
+        attention_mask: &[u32],
+    ) -> crate::Result<Vec<f32>> {
+        if input_ids.is_empty() {
+            return Err(Error::model("input_ids cannot be empty"));
+        }
+        if input_ids.len() != attention_mask.len() {
+            return Err(Error::model(format!(
+                "input_ids length ({}) != attention_mask length ({})",
+                input_ids.len(),
+                attention_mask.len()
+            )));
+        }
+
+        // Run inference in blocking thread pool to avoid blocking async runtime
+        let session = Arc::clone(&self.session);
+        let input_ids = input_ids.to_vec();
+        let attention_mask = attention_mask.to_vec();
+
+        tokio::task::spawn_blocking(move || {
+            let mut session_guard = session.lock()
+                .map_err(|e| Error::model(format!("Failed to lock session: {}", e)))?;
+            Self::embed_sync(&mut session_guard, &input_ids, &attention_mask)
+        })
+        .await
+        .map_err(|e| Error::model(format!("Async inference task failed: {}", e)))?
+    }
+
+    /// Internal synchronous embedding implementation
+    fn embed_sync(
+        session: &mut Session,
+        input_ids: &[u32],
+        attention_mask: &[u32],
+    ) -> crate::Result<Vec<f32>> {
+        let input_ids_i64: Vec<i64> = input_ids.iter().map(|&x| x as i64).collect();
+        let attention_mask_i64: Vec<i64> = attention_mask.iter().map(|&x| x as i64).collect();
+
+        let batch_size = 1;
+        let seq_length = input_ids.len();
+
+        let input_ids_array =
+            Array2::from_shape_vec((batch_size, seq_length), input_ids_i64)
+                .map_err(|e| Error::model(format!("Failed to create input array: {}", e)))?;
+
+        let attention_mask_array =
+            Array2::from_shape_vec((batch_size, seq_length), attention_mask_i64)
+                .map_err(|e| Error::model(format!("Failed to create attention mask array: {}", e)))?;
+
+        let input_ids_value = ort::value::Value::from_array(input_ids_array)
+            .map_err(|e| Error::model(format!("Failed to create input_ids value: {}", e)))?;
+        let attention_mask_value = ort::value::Value::from_array(attention_mask_array)
+            .map_err(|e| Error::model(format!("Failed to create attention_mask value: {}", e)))?;
+
+        let outputs = session
+            .run(ort::inputs![
+                "input_ids" => input_ids_value,
+                "attention_mask" => attention_mask_value,
+            ])
+            .map_err(|e| Error::model(format!("Inference failed: {}", e)))?;
+
+        // Extract hidden states [batch_size, seq_length, hidden_size]
+        let hidden = outputs["last_hidden_state"]
+            .try_extract_tensor::<f32>()
+            .map_err(|e| Error::model(format!("Failed to extract hidden states: {}", e)))?;
+
+        let (shape, data) = hidden;
+
+        if shape.len() != 3 {
+            return Err(Error::model(format!(
+                "Expected 3D hidden state tensor, got shape with {} dimensions",
+                shape.len()
+            )));
+        }
+
+        let hidden_size = shape[2] as usize;
+        let pooled = Self::mean_pool_static(data, hidden_size, attention_mask);
+
+        Ok(Self::normalize_static(&pooled))
+    }
+
+    /// Mean-pool token embeddings over the attention mask (static method)
+    ///
+    /// # Arguments
+    ///
+    /// * `hidden` - Flattened hidden states `[seq_length * hidden_size]`
+    /// * `hidden_size` - Embedding dimension
+    /// * `attention_mask` - Attention mask (1=real token, 0=padding)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use llm_shield_models::InferenceEngine;
+    ///
+    /// let hidden = vec![1.0, 2.0, 3.0, 4.0, 100.0, 100.0];
+    /// let pooled = InferenceEngine::mean_pool_static(&hidden, 2, &[1, 1, 0]);
+    /// assert_eq!(pooled, vec![2.0, 3.0]);
+    /// ```
+    pub fn mean_pool_static(hidden: &[f32], hidden_size: usize, attention_mask: &[u32]) -> Vec<f32> {
+        let mut pooled = vec![0.0f32; hidden_size];
+        if hidden_size == 0 {
+            return pooled;
+        }
+
+        let mut count = 0.0f32;
+        for (token_idx, token) in hidden.chunks(hidden_size).enumerate() {
+            if attention_mask.get(token_idx).copied().unwrap_or(0) == 0 {
+                continue;
+            }
+            for (acc, value) in pooled.iter_mut().zip(token) {
+                *acc += value;
+            }
+            count += 1.0;
+        }
+
+        if count > 0.0 {
+            for value in pooled.iter_mut() {
+                *value /= count;
+            }
+        }
+
+        pooled
+    }
+
+    /// L2-normalize a vector (static method)
+    ///
+    /// Zero vectors are returned unchanged.
+    pub fn normalize_static(vector: &[f32]) -> Vec<f32> {
+        let norm = vector.iter().map(|x| x * x).sum::<f32>().sqrt();
+        if norm == 0.0 {
+            return vector.to_vec();
+        }
+        vector.iter().map(|x| x / norm).collect()
+    }
+
+    /// Cosine similarity between two vectors (static method)
+    ///
+    /// Returns 0.0 for vectors of different length or zero magnitude.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use llm_shield_models::InferenceEngine;
+    ///
+    /// let sim = InferenceEngine::cosine_similarity_static(&[1.0, 0.0], &[1.0, 0.0]);
+    /// assert!((sim - 1.0).abs() < 1e-6);
+    /// ```
+    pub fn cosine_similarity_static(a: &[f32], b: &[f32]) -> f32 {
+        if a.len() != b.len() || a.is_empty() {
+            return 0.0;
+        }
+
+        let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+        let norm_a = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+        let norm_b = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+
+        if norm_a == 0.0 || norm_b == 0.0 {
+            0.0
+        } else {
+            dot / (norm_a * norm_b)
+        }
+    }
 }
 
 #[cfg(test)]
@@ -790,4 +968,30 @@ mod tests {
         // This test verifies the structure compiles
         assert!(true);
     }
+
+    #[test]
+    fn test_mean_pool_ignores_padding() {
+        let hidden = vec![1.0, 3.0, 3.0, 5.0, 9.0, 9.0];
+        let pooled = InferenceEngine::mean_pool_static(&hidden, 2, &[1, 1, 0]);
+        assert_eq!(pooled, vec![2.0, 4.0]);
+    }
+
+    #[test]
+    fn test_cosine_similarity() {
+        let same = InferenceEngine::cosine_similarity_static(&[1.0, 2.0], &[2.0, 4.0]);
+        assert!((same - 1.0).abs() < 1e-6);
+
+        let orthogonal = InferenceEngine::cosine_similarity_static(&[1.0, 0.0], &[0.0, 1.0]);
+        assert!(orthogonal.abs() < 1e-6);
+
+        assert_eq!(InferenceEngine::cosine_similarity_static(&[1.0], &[1.0, 2.0]), 0.0);
+    }
+
+    #[test]
+    fn test_normalize() {
+        let normalized = InferenceEngine::normalize_static(&[3.0, 4.0]);
+        assert!((normalized[0] - 0.6).abs() < 1e-6);
+        assert!((normalized[1] - 0.8).abs() < 1e-6);
+        assert_eq!(InferenceEngine::normalize_static(&[0.0, 0.0]), vec![0.0, 0.0]);
+    }
 }
@@ -8,6 +8,7 @@ pub mod tokenizer;
 pub mod inference;
 pub mod registry;
 pub mod cache;
+pub mod semantic;
 pub mod types;
 
 pub use model_loader::{ModelLoader, ModelConfig, ModelType};
@@ -15,6 +16,7 @@ pub use tokenizer::{TokenizerWrapper, TokenizerConfig, Encoding};
 pub use inference::{InferenceEngine, InferenceResult, TokenPrediction, PostProcessing};
 pub use registry::{ModelRegistry, ModelTask, ModelVariant, ModelMetadata};
 pub use cache::{ResultCache, CacheConfig, CacheStats};
+pub use semantic::{ZeroShotClassifier, EmbeddingModel, NliScores};
 pub use types::{
     MLConfig, CacheSettings, HybridMode, DetectionMethod, InferenceMetrics,
 };
@@ -54,6 +54,10 @@ pub enum ModelType {
     Sentiment,
     /// Named Entity Recognition (PII detection)
     NamedEntityRecognition,
+    /// Natural language inference (zero-shot classification)
+    ZeroShotClassification,
+    /// Sentence embeddings (semantic similarity)
+    SentenceEmbedding,
 }
 
 /// Conversion from ModelTask to ModelType
@@ -64,6 +68,8 @@ impl From<ModelTask> for ModelType {
             ModelTask::Toxicity => ModelType::Toxicity,
             ModelTask::Sentiment => ModelType::Sentiment,
             ModelTask::NamedEntityRecognition => ModelType::NamedEntityRecognition,
+            ModelTask::ZeroShotClassification => ModelType::ZeroShotClassification,
+            ModelTask::SentenceEmbedding => ModelType::SentenceEmbedding,
         }
     }
 }
@@ -76,6 +82,8 @@ impl From<ModelType> for ModelTask {
             ModelType::Toxicity => ModelTask::Toxicity,
             ModelType::Sentiment => ModelTask::Sentiment,
             ModelType::NamedEntityRecognition => ModelTask::NamedEntityRecognition,
+            ModelType::ZeroShotClassification => ModelTask::ZeroShotClassification,
+            ModelType::SentenceEmbedding => ModelTask::SentenceEmbedding,
         }
     }
 }
@@ -8,6 +8,8 @@
 //! - Automatic downloading with caching
 //! - Checksum verification
 //! - Support for multiple model tasks and variants
+//! - Models and tokenizers kept in any `CloudStorage` (`storage://` URLs,
+//!   requires the `cloud` feature)
 //!
 //! ## Example
 //!
@@ -31,6 +33,12 @@ use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
+#[cfg(feature = "cloud")]
+use llm_shield_cloud::{ArtifactCache, ArtifactRef, CloudStorage};
+
+/// URL scheme of models and tokenizers kept in the registry's cloud storage
+const STORAGE_URL_SCHEME: &str = "storage://";
+
 /// Result type alias
 pub type Result<T> = std::result::Result<T, Error>;
 
@@ -45,6 +53,10 @@ pub enum ModelTask {
     Sentiment,
     /// Named Entity Recognition (PII detection)
     NamedEntityRecognition,
+    /// Natural language inference (zero-shot classification)
+    ZeroShotClassification,
+    /// Sentence embeddings (semantic similarity)
+    SentenceEmbedding,
 }
 
 /// Model variant (precision/quantization)
@@ -67,12 +79,18 @@ pub struct ModelMetadata {
     pub task: ModelTask,
     /// Model variant (precision)
     pub variant: ModelVariant,
-    /// Download URL
+    /// Download URL (`https://`, `file://` or `storage://<key>`)
     pub url: String,
     /// SHA-256 checksum
     pub checksum: String,
     /// Model size in bytes
     pub size_bytes: usize,
+    /// Download URL of the model's `tokenizer.json`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tokenizer_url: Option<String>,
+    /// SHA-256 checksum of the tokenizer
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tokenizer_checksum: Option<String>,
 }
 
 /// Registry data structure (for deserialization)
@@ -97,6 +115,21 @@ pub struct ModelRegistry {
     models: Arc<HashMap<String, ModelMetadata>>,
     /// Local cache directory
     cache_dir: Arc<PathBuf>,
+    /// Storage serving `storage://` URLs
+    #[cfg(feature = "cloud")]
+    storage: Option<StorageSource>,
+}
+
+/// Cloud storage handle of a registry
+#[cfg(feature = "cloud")]
+#[derive(Clone)]
+struct StorageSource(Arc<dyn CloudStorage>);
+
+#[cfg(feature = "cloud")]
+impl std::fmt::Debug for StorageSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.0.provider_name())
+    }
 }
 
 impl ModelRegistry {
@@ -107,6 +140,8 @@ impl ModelRegistry {
         Self {
             models: Arc::new(HashMap::new()),
             cache_dir: Arc::new(cache_dir),
+            #[cfg(feature = "cloud")]
+            storage: None,
         }
     }
 
@@ -130,7 +165,16 @@ impl ModelRegistry {
             Error::model(format!("Failed to read registry file '{}': {}", path, e))
         })?;
 
-        let data: RegistryData = serde_json::from_str(&json).map_err(|e| {
+        Self::from_json_str(&json)
+    }
+
+    /// Create a registry from JSON
+    ///
+    /// # Arguments
+    ///
+    /// * `json` - Registry in the format of registry.json
+    pub fn from_json_str(json: &str) -> Result<Self> {
+        let data: RegistryData = serde_json::from_str(json).map_err(|e| {
             Error::model(format!("Failed to parse registry JSON: {}", e))
         })?;
 
@@ -160,10 +204,49 @@ impl ModelRegistry {
 
         Ok(Self {
             models: Arc::new(models),
-            cache_dir: Arc::new(cache_dir)
+            cache_dir: Arc::new(cache_dir),
+            #[cfg(feature = "cloud")]
+            storage: None,
         })
     }
 
+    /// Create a registry from a registry.json kept in cloud storage
+    ///
+    /// The registry file is cached by `artifacts`, and `storage://` model
+    /// and tokenizer URLs are downloaded from the same storage.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use llm_shield_models::ModelRegistry;
+    /// # use llm_shield_cloud::{ArtifactCache, ArtifactRef};
+    /// # async fn example(artifacts: ArtifactCache) -> Result<(), llm_shield_core::Error> {
+    /// let reference = ArtifactRef::parse("storage://models/registry.json").unwrap();
+    /// let registry = ModelRegistry::from_storage(&artifacts, &reference).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "cloud")]
+    pub async fn from_storage(artifacts: &ArtifactCache, reference: &ArtifactRef) -> Result<Self> {
+        tracing::info!("Loading model registry from: {}", reference);
+
+        let json = artifacts.read_ref(reference).await.map_err(|e| {
+            Error::model(format!("Failed to fetch registry '{}': {}", reference, e))
+        })?;
+        let json = String::from_utf8(json).map_err(|e| {
+            Error::model(format!("Failed to parse registry JSON: {}", e))
+        })?;
+
+        Ok(Self::from_json_str(&json)?.with_storage(artifacts.storage().clone()))
+    }
+
+    /// Download `storage://` model and tokenizer URLs from `storage`
+    #[cfg(feature = "cloud")]
+    pub fn with_storage(mut self, storage: Arc<dyn CloudStorage>) -> Self {
+        self.storage = Some(StorageSource(storage));
+        self
+    }
+
     /// Get metadata for a specific model
     ///
     /// # Arguments
@@ -379,44 +462,92 @@ impl ModelRegistry {
         variant: ModelVariant,
     ) -> Result<PathBuf> {
         let metadata = self.get_model_metadata(task, variant)?;
-        let model_path = self.cache_dir.join(&metadata.id).join("model.onnx");
+        self.ensure_artifact_available(
+            &metadata.id,
+            "model.onnx",
+            &metadata.url,
+            &metadata.checksum,
+        )
+        .await
+    }
+
+    /// Ensure a model's tokenizer is available locally (download if needed)
+    ///
+    /// The tokenizer is cached next to the model and verified like the model
+    /// when the registry lists a `tokenizer_checksum`.
+    ///
+    /// # Returns
+    ///
+    /// Path to the local `tokenizer.json`, or Error if the model has no
+    /// `tokenizer_url`
+    pub async fn ensure_tokenizer_available(
+        &self,
+        task: ModelTask,
+        variant: ModelVariant,
+    ) -> Result<PathBuf> {
+        let metadata = self.get_model_metadata(task, variant)?;
+        let url = metadata.tokenizer_url.as_ref().ok_or_else(|| {
+            Error::model(format!("Model '{}' has no tokenizer URL", metadata.id))
+        })?;
+
+        match &metadata.tokenizer_checksum {
+            Some(checksum) => {
+                self.ensure_artifact_available(&metadata.id, "tokenizer.json", url, checksum)
+                    .await
+            }
+            None => {
+                let path = self.cache_dir.join(&metadata.id).join("tokenizer.json");
+                if !path.exists() {
+                    tracing::info!("Downloading tokenizer for {} from {}", metadata.id, url);
+                    self.download(url, &path).await?;
+                }
+                Ok(path)
+            }
+        }
+    }
+
+    /// Ensure a checksummed file of a model is cached
+    async fn ensure_artifact_available(
+        &self,
+        id: &str,
+        file_name: &str,
+        url: &str,
+        checksum: &str,
+    ) -> Result<PathBuf> {
+        let path = self.cache_dir.join(id).join(file_name);
 
         // Check if already cached and valid
-        if model_path.exists() {
-            tracing::debug!("Model found in cache: {:?}", model_path);
+        if path.exists() {
+            tracing::debug!("Found in cache: {:?}", path);
 
-            if self.verify_checksum(&model_path, &metadata.checksum)? {
-                tracing::debug!("Checksum verified, using cached model");
-                return Ok(model_path);
+            if self.verify_checksum(&path, checksum)? {
+                tracing::debug!("Checksum verified, using cached {}", file_name);
+                return Ok(path);
             } else {
-                tracing::warn!("Cached model checksum mismatch, re-downloading");
+                tracing::warn!("Cached {} checksum mismatch, re-downloading", file_name);
             }
         }
 
-        // Download model
-        tracing::info!(
-            "Downloading model: {} from {}",
-            metadata.id,
-            metadata.url
-        );
-        self.download_model(metadata, &model_path).await?;
+        // Download
+        tracing::info!("Downloading {} for {} from {}", file_name, id, url);
+        self.download(url, &path).await?;
 
         // Verify checksum
-        if !self.verify_checksum(&model_path, &metadata.checksum)? {
+        if !self.verify_checksum(&path, checksum)? {
             // Clean up failed download
-            let _ = std::fs::remove_file(&model_path);
+            let _ = std::fs::remove_file(&path);
             return Err(Error::model(format!(
-                "Checksum verification failed for model: {}",
-                metadata.id
+                "Checksum verification failed for {} of model: {}",
+                file_name, id
             )));
         }
 
-        tracing::info!("Model downloaded and verified: {:?}", model_path);
-        Ok(model_path)
+        tracing::info!("Downloaded and verified: {:?}", path);
+        Ok(path)
     }
 
-    /// Download a model from URL to local path
-    async fn download_model(&self, metadata: &ModelMetadata, dest: &Path) -> Result<()> {
+    /// Download a URL to a local path
+    async fn download(&self, url: &str, dest: &Path) -> Result<()> {
         // Create parent directory
         if let Some(parent) = dest.parent() {
             std::fs::create_dir_all(parent).map_err(|e| {
@@ -429,8 +560,7 @@ impl ModelRegistry {
         }
 
         // Handle file:// URLs for testing
-        if metadata.url.starts_with("file://") {
-            let src_path = metadata.url.strip_prefix("file://").unwrap();
+        if let Some(src_path) = url.strip_prefix("file://") {
             std::fs::copy(src_path, dest).map_err(|e| {
                 Error::model(format!(
                     "Failed to copy model from '{}' to '{}': {}",
@@ -442,24 +572,27 @@ impl ModelRegistry {
             return Ok(());
         }
 
-        // Download using reqwest for HTTP(S) URLs
-        let response = reqwest::get(&metadata.url).await.map_err(|e| {
-            Error::model(format!(
-                "Failed to download model from '{}': {}",
-                metadata.url, e
-            ))
-        })?;
+        let bytes = if let Some(key) = url.strip_prefix(STORAGE_URL_SCHEME) {
+            self.download_from_storage(key).await?
+        } else {
+            // Download using reqwest for HTTP(S) URLs
+            let response = reqwest::get(url).await.map_err(|e| {
+                Error::model(format!("Failed to download model from '{}': {}", url, e))
+            })?;
 
-        if !response.status().is_success() {
-            return Err(Error::model(format!(
-                "HTTP error downloading model: {}",
-                response.status()
-            )));
-        }
+            if !response.status().is_success() {
+                return Err(Error::model(format!(
+                    "HTTP error downloading model: {}",
+                    response.status()
+                )));
+            }
 
-        let bytes = response.bytes().await.map_err(|e| {
-            Error::model(format!("Failed to read response body: {}", e))
-        })?;
+            response
+                .bytes()
+                .await
+                .map_err(|e| Error::model(format!("Failed to read response body: {}", e)))?
+                .to_vec()
+        };
 
         // Write to file
         std::fs::write(dest, bytes).map_err(|e| {
@@ -469,6 +602,29 @@ impl ModelRegistry {
         Ok(())
     }
 
+    /// Read an object from the registry's cloud storage
+    #[cfg(feature = "cloud")]
+    async fn download_from_storage(&self, key: &str) -> Result<Vec<u8>> {
+        let storage = self.storage.as_ref().ok_or_else(|| {
+            Error::model(format!(
+                "Cannot download '{}{}': registry has no cloud storage",
+                STORAGE_URL_SCHEME, key
+            ))
+        })?;
+
+        storage.0.get_object(key).await.map_err(|e| {
+            Error::model(format!("Failed to download model from storage: {}", e))
+        })
+    }
+
+    #[cfg(not(feature = "cloud"))]
+    async fn download_from_storage(&self, key: &str) -> Result<Vec<u8>> {
+        Err(Error::model(format!(
+            "Cannot download '{}{}': cloud storage support requires the `cloud` feature",
+            STORAGE_URL_SCHEME, key
+        )))
+    }
+
     /// Verify SHA-256 checksum of a file
     fn verify_checksum(&self, path: &Path, expected: &str) -> Result<bool> {
         let bytes = std::fs::read(path).map_err(|e| {
@@ -613,18 +769,126 @@ mod tests {
             url: format!("file://{}", src_file.display()),
             checksum,
             size_bytes: content.len(),
+            tokenizer_url: None,
+            tokenizer_checksum: None,
         };
 
         let dest_file = temp_dir.path().join("dest.onnx");
         let registry = ModelRegistry::new();
 
-        registry.download_model(&metadata, &dest_file).await.unwrap();
+        registry.download(&metadata.url, &dest_file).await.unwrap();
         assert!(dest_file.exists());
 
         let downloaded = std::fs::read(&dest_file).unwrap();
         assert_eq!(downloaded, content);
     }
 
+    #[tokio::test]
+    async fn test_ensure_tokenizer_available() {
+        let temp_dir = TempDir::new().unwrap();
+        let tokenizer_file = temp_dir.path().join("tokenizer.json");
+        std::fs::write(&tokenizer_file, b"{}").unwrap();
+
+        let registry_json = format!(
+            r#"{{
+                "cache_dir": "{}",
+                "models": [
+                    {{
+                        "id": "with-tokenizer",
+                        "task": "Toxicity",
+                        "variant": "FP32",
+                        "url": "file:///nonexistent/model.onnx",
+                        "checksum": "unused",
+                        "size_bytes": 0,
+                        "tokenizer_url": "file://{}",
+                        "tokenizer_checksum": "{:x}"
+                    }},
+                    {{
+                        "id": "without-tokenizer",
+                        "task": "Sentiment",
+                        "variant": "FP32",
+                        "url": "file:///nonexistent/model.onnx",
+                        "checksum": "unused",
+                        "size_bytes": 0
+                    }}
+                ]
+            }}"#,
+            temp_dir.path().join("cache").display(),
+            tokenizer_file.display(),
+            Sha256::digest(b"{}")
+        );
+        let registry = ModelRegistry::from_json_str(&registry_json).unwrap();
+
+        let path = registry
+            .ensure_tokenizer_available(ModelTask::Toxicity, ModelVariant::FP32)
+            .await
+            .unwrap();
+        assert_eq!(path, temp_dir.path().join("cache/with-tokenizer/tokenizer.json"));
+        assert_eq!(std::fs::read(&path).unwrap(), b"{}");
+
+        assert!(registry
+            .ensure_tokenizer_available(ModelTask::Sentiment, ModelVariant::FP32)
+            .await
+            .is_err());
+    }
+
+    #[cfg(feature = "cloud")]
+    #[tokio::test]
+    async fn test_registry_from_storage() {
+        use llm_shield_cloud::LocalStorage;
+
+        let bucket = TempDir::new().unwrap();
+        let cache = TempDir::new().unwrap();
+        let storage = Arc::new(LocalStorage::new(bucket.path()).await.unwrap());
+
+        let model = b"fake model data";
+        let registry_json = format!(
+            r#"{{
+                "cache_dir": "{}",
+                "models": [{{
+                    "id": "pi",
+                    "task": "PromptInjection",
+                    "variant": "INT8",
+                    "url": "storage://models/pi/model.onnx",
+                    "checksum": "{:x}",
+                    "size_bytes": {}
+                }}]
+            }}"#,
+            cache.path().join("models").display(),
+            Sha256::digest(model),
+            model.len()
+        );
+        storage.put_object("models/registry.json", registry_json.as_bytes()).await.unwrap();
+        storage.put_object("models/pi/model.onnx", model).await.unwrap();
+
+        let artifacts = ArtifactCache::new(storage.clone(), cache.path().join("artifacts"));
+        let reference = ArtifactRef::parse("storage://models/registry.json").unwrap();
+        let registry = ModelRegistry::from_storage(&artifacts, &reference).await.unwrap();
+        assert!(registry.has_model(ModelTask::PromptInjection, ModelVariant::INT8));
+
+        let path = registry
+            .ensure_model_available(ModelTask::PromptInjection, ModelVariant::INT8)
+            .await
+            .unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), model);
+
+        // A tampered object fails verification
+        std::fs::remove_file(&path).unwrap();
+        storage.put_object("models/pi/model.onnx", b"tampered").await.unwrap();
+        assert!(registry
+            .ensure_model_available(ModelTask::PromptInjection, ModelVariant::INT8)
+            .await
+            .is_err());
+        assert!(!path.exists());
+
+        // Registries without storage can't resolve storage:// URLs
+        let detached = ModelRegistry::from_json_str(&registry_json).unwrap();
+        assert!(detached
+            .ensure_model_available(ModelTask::PromptInjection, ModelVariant::INT8)
+            .await
+            .is_err());
+    }
+
     #[test]
     fn test_model_task_serialization() {
         let task = ModelTask::PromptInjection;
@@ -0,0 +1,163 @@
+//! Semantic Models
+//!
+//! Higher-level wrappers around [`InferenceEngine`] and [`TokenizerWrapper`]
+//! for scanners that reason about meaning rather than surface patterns.
+//!
+//! ## Features
+//!
+//! - Zero-shot classification via natural language inference (NLI)
+//! - Sentence embeddings with cosine similarity
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use llm_shield_models::{ZeroShotClassifier, InferenceEngine, TokenizerWrapper};
+//!
+//! let classifier = ZeroShotClassifier::new(Arc::new(engine), Arc::new(tokenizer));
+//! let score = classifier
+//!     .entailment_score("Mix flour, sugar and eggs.", "This text is about cooking.")
+//!     .await?;
+//! ```
+
+use crate::inference::{InferenceEngine, PostProcessing};
+use crate::tokenizer::TokenizerWrapper;
+use llm_shield_core::Error;
+use std::sync::Arc;
+
+pub use llm_shield_core::NliScores;
+
+/// Result type alias
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Zero-shot classifier backed by an NLI cross-encoder
+///
+/// ## Label Order
+///
+/// NLI checkpoints disagree on output order. The default is the MNLI order
+/// `["contradiction", "neutral", "entailment"]`; use [`with_labels`] when the
+/// model was exported differently.
+///
+/// [`with_labels`]: ZeroShotClassifier::with_labels
+pub struct ZeroShotClassifier {
+    engine: Arc<InferenceEngine>,
+    tokenizer: Arc<TokenizerWrapper>,
+    labels: Vec<String>,
+}
+
+impl ZeroShotClassifier {
+    /// Create a classifier with MNLI label order
+    pub fn new(engine: Arc<InferenceEngine>, tokenizer: Arc<TokenizerWrapper>) -> Self {
+        Self {
+            engine,
+            tokenizer,
+            labels: vec![
+                "contradiction".to_string(),
+                "neutral".to_string(),
+                "entailment".to_string(),
+            ],
+        }
+    }
+
+    /// Override the model's output label order
+    ///
+    /// Labels must include `entailment` and `contradiction` (case-insensitive).
+    pub fn with_labels(mut self, labels: Vec<String>) -> Result<Self> {
+        let has = |name: &str| labels.iter().any(|l| l.eq_ignore_ascii_case(name));
+        if !has("entailment") || !has("contradiction") {
+            return Err(Error::config(
+                "NLI labels must include 'entailment' and 'contradiction'",
+            ));
+        }
+        self.labels = labels;
+        Ok(self)
+    }
+
+    /// Classify the relationship between a premise and a hypothesis
+    pub async fn classify(&self, premise: &str, hypothesis: &str) -> Result<NliScores> {
+        let encoding = self.tokenizer.encode_pair(premise, hypothesis)?;
+        let result = self
+            .engine
+            .infer_async(
+                &encoding.input_ids,
+                &encoding.attention_mask,
+                &self.labels,
+                PostProcessing::Softmax,
+            )
+            .await?;
+
+        let score_for = |name: &str| {
+            self.labels
+                .iter()
+                .position(|l| l.eq_ignore_ascii_case(name))
+                .and_then(|idx| result.scores.get(idx).copied())
+                .unwrap_or(0.0)
+        };
+
+        Ok(NliScores {
+            entailment: score_for("entailment"),
+            neutral: score_for("neutral"),
+            contradiction: score_for("contradiction"),
+        })
+    }
+
+    /// Zero-shot score that `text` matches `hypothesis` (0.0 to 1.0)
+    pub async fn entailment_score(&self, text: &str, hypothesis: &str) -> Result<f32> {
+        Ok(self.classify(text, hypothesis).await?.zero_shot_score())
+    }
+}
+
+/// Sentence embedding model
+///
+/// Produces L2-normalized, mean-pooled embeddings suitable for cosine
+/// similarity (e.g. `sentence-transformers/all-MiniLM-L6-v2` exported to ONNX).
+pub struct EmbeddingModel {
+    engine: Arc<InferenceEngine>,
+    tokenizer: Arc<TokenizerWrapper>,
+}
+
+impl EmbeddingModel {
+    /// Create a new embedding model
+    pub fn new(engine: Arc<InferenceEngine>, tokenizer: Arc<TokenizerWrapper>) -> Self {
+        Self { engine, tokenizer }
+    }
+
+    /// Embed a single text
+    pub async fn embed(&self, text: &str) -> Result<Vec<f32>> {
+        let encoding = self.tokenizer.encode(text)?;
+        self.engine
+            .embed_async(&encoding.input_ids, &encoding.attention_mask)
+            .await
+    }
+
+    /// Cosine similarity between two texts
+    pub async fn similarity(&self, a: &str, b: &str) -> Result<f32> {
+        let left = self.embed(a).await?;
+        let right = self.embed(b).await?;
+        Ok(InferenceEngine::cosine_similarity_static(&left, &right))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_zero_shot_score_drops_neutral() {
+        let scores = NliScores {
+            entailment: 0.3,
+            neutral: 0.6,
+            contradiction: 0.1,
+        };
+        assert!((scores.zero_shot_score() - 0.75).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_zero_shot_score_empty() {
+        let scores = NliScores {
+            entailment: 0.0,
+            neutral: 1.0,
+            contradiction: 0.0,
+        };
+        assert_eq!(scores.zero_shot_score(), 0.0);
+    }
+}
@@ -239,22 +239,35 @@ impl TokenizerWrapper {
         // In production, this should download from HuggingFace Hub
         let tokenizer_path = format!("models/{}/tokenizer.json", model_name);
 
-        let mut tokenizer = if std::path::Path::new(&tokenizer_path).exists() {
-            Tokenizer::from_file(&tokenizer_path)
-                .map_err(|e| {
-                    Error::model(format!(
-                        "Failed to load tokenizer from '{}': {}",
-                        tokenizer_path, e
-                    ))
-                })?
-        } else {
-            // Fall back to a basic tokenizer for testing
+        if !std::path::Path::new(&tokenizer_path).exists() {
             // In production, implement proper HuggingFace Hub download
             return Err(Error::model(format!(
                 "Tokenizer not found at '{}'. Please download tokenizer files first.",
                 tokenizer_path
             )));
-        };
+        }
+
+        Self::from_file(&tokenizer_path, config)
+    }
+
+    /// Load a tokenizer from a `tokenizer.json` file
+    ///
+    /// Use this with paths returned by
+    /// [`ModelRegistry::ensure_tokenizer_available`](crate::ModelRegistry::ensure_tokenizer_available).
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to a HuggingFace `tokenizer.json` file
+    /// * `config` - Tokenizer configuration
+    pub fn from_file(path: impl AsRef<std::path::Path>, config: TokenizerConfig) -> Result<Self> {
+        let path = path.as_ref();
+        let mut tokenizer = Tokenizer::from_file(path).map_err(|e| {
+            Error::model(format!(
+                "Failed to load tokenizer from '{}': {}",
+                path.display(),
+                e
+            ))
+        })?;
 
         // Configure padding
         if config.padding {
@@ -339,6 +352,44 @@ impl TokenizerWrapper {
         Ok(Encoding::with_offsets(input_ids, attention_mask, offsets))
     }
 
+    /// Encode a pair of texts as a single sequence
+    ///
+    /// Used for cross-encoder tasks such as natural language inference,
+    /// where the premise and hypothesis are joined with the model's separator
+    /// token. Offsets are relative to the text each token came from.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use llm_shield_models::{TokenizerWrapper, TokenizerConfig};
+    /// # let tokenizer = TokenizerWrapper::from_pretrained(
+    /// #     "microsoft/deberta-v3-base",
+    /// #     TokenizerConfig::default(),
+    /// # )?;
+    /// let encoding = tokenizer.encode_pair(
+    ///     "The cake recipe needs flour.",
+    ///     "This text is about cooking.",
+    /// )?;
+    /// # Ok::<(), llm_shield_core::Error>(())
+    /// ```
+    pub fn encode_pair(&self, first: &str, second: &str) -> Result<Encoding> {
+        let encoding = self.tokenizer
+            .encode((first, second), self.config.add_special_tokens)
+            .map_err(|e| {
+                Error::model(format!("Failed to encode text pair: {}", e))
+            })?;
+
+        let input_ids = encoding.get_ids().to_vec();
+        let attention_mask = encoding.get_attention_mask().to_vec();
+        let offsets: Vec<(usize, usize)> = encoding
+            .get_offsets()
+            .iter()
+            .map(|offset| (offset.0, offset.1))
+            .collect();
+
+        Ok(Encoding::with_offsets(input_ids, attention_mask, offsets))
+    }
+
     /// Encode multiple texts in batch
     ///
     /// Batch encoding is more efficient than encoding texts individually.
@@ -19,6 +19,7 @@ from llm_shield._internal import (
     # Core types
     Vault,
     create_vault,
+    Pipeline,
 
     # Exceptions
     LLMShieldError,
@@ -61,6 +62,7 @@ __all__ = [
     # Core
     "Vault",
     "create_vault",
+    "Pipeline",
 
     # Exceptions
     "LLMShieldError",
@@ -12,11 +12,13 @@ mod error;
 mod types;
 mod vault;
 mod scanners;
+mod pipeline;
 mod utils;
 
 use error::*;
 use types::*;
 use vault::PyVault;
+use pipeline::PyPipeline;
 use scanners::input::*;
 use scanners::output::*;
 
@@ -76,6 +78,10 @@ fn llm_shield(py: Python<'_>, m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<PyFactuality>()?;
     m.add_class::<PyURLReachability>()?;
     m.add_class::<PyRegexOutput>()?;
+    m.add_class::<PyJsonOutput>()?;
+
+    // Register declarative pipelines
+    m.add_class::<PyPipeline>()?;
 
     // Register utility functions
     m.add_function(wrap_pyfunction!(create_vault, m)?)?;
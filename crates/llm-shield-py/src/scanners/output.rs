@@ -179,3 +179,6 @@ output_scanner!(PyURLReachability, URLReachability, URLReachabilityConfig);
 
 /// RegexOutput scanner - Pattern matching on outputs
 output_scanner!(PyRegexOutput, RegexOutput, RegexOutputConfig);
+
+/// JsonOutput scanner - Validates JSON in outputs
+output_scanner!(PyJsonOutput, JsonOutput, JsonOutputConfig);
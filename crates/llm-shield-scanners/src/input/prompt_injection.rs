@@ -11,8 +11,8 @@
 //! Tests are written first, driving the implementation.
 
 use llm_shield_core::{
-    async_trait, Entity, Error, Result, RiskFactor, ScanResult, Scanner, ScannerType, Severity,
-    Vault,
+    async_trait, Entity, Error, PerformanceInfo, Result, RiskFactor, ScanResult, Scanner,
+    ScannerType, Severity, Vault,
 };
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -332,6 +332,16 @@ impl Scanner for PromptInjection {
     fn description(&self) -> &str {
         "Detects prompt injection attacks using ML-based and heuristic detection"
     }
+
+    fn performance(&self) -> PerformanceInfo {
+        // The heuristic path is pattern-based; otherwise the model runs
+        let uses_ml_models = !self.config.use_fallback;
+        PerformanceInfo {
+            typical_latency_ms: if uses_ml_models { 50 } else { 1 },
+            uses_ml_models,
+            ..PerformanceInfo::default()
+        }
+    }
 }
 
 #[cfg(test)]
@@ -95,6 +95,11 @@ impl RegexScanner {
     }
 }
 
+/// Bytes held back when scanning a stream
+///
+/// Matches longer than this may be split across released chunks.
+const STREAM_LOOKBACK: usize = 256;
+
 #[async_trait]
 impl Scanner for RegexScanner {
     fn name(&self) -> &str {
@@ -164,6 +169,10 @@ impl Scanner for RegexScanner {
     fn description(&self) -> &str {
         "Detects patterns using regular expressions"
     }
+
+    fn stream_lookback(&self) -> Option<usize> {
+        Some(STREAM_LOOKBACK)
+    }
 }
 
 #[cfg(test)]
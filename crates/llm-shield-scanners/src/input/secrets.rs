@@ -217,6 +217,30 @@ static GENERIC_SECRET: LazyLock<Regex> = LazyLock::new(|| {
     Regex::new(r#"(?i)(api[_-]?key|apikey|secret|password|passwd|pwd|token|auth)['"]?\s*[:=]\s*['"]?([A-Za-z0-9+/_-]{16,})['"]?"#).unwrap()
 });
 
+// Incomplete matches of the open-ended patterns, anchored at the end of
+// the text. While one matches, the secret may still be growing past the
+// stream lookback.
+static AZURE_CONNECTION_STRING_OPEN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)DefaultEndpointsProtocol=https;AccountName=[^;]*(;AccountKey=[A-Za-z0-9+/=]*)?\z").unwrap()
+});
+static GITLAB_TOKEN_OPEN: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)glpat-[a-zA-Z0-9\-_]*\z").unwrap());
+static SLACK_WEBHOOK_OPEN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"https://hooks\.slack\.com/services/[a-zA-Z0-9_/]*\z").unwrap()
+});
+static STRIPE_KEY_OPEN: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)(sk|pk|rk)_(test|live)_[0-9a-zA-Z]*\z").unwrap());
+static DATABASE_URL_OPEN: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)(postgres|mysql|mongodb|redis)://\S*\z").unwrap());
+static CONNECTION_STRING_OPEN: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)(Server|Data Source|Host)=[^\n]*\z").unwrap());
+static JWT_TOKEN_OPEN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"eyJ[A-Za-z0-9_-]*(\.[A-Za-z0-9_-]*){0,2}\z").unwrap()
+});
+static GENERIC_SECRET_OPEN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?i)(api[_-]?key|apikey|secret|password|passwd|pwd|token|auth)['"]?\s*[:=]\s*['"]?[A-Za-z0-9+/_-]*\z"#).unwrap()
+});
+
 /// Secrets scanner implementation
 ///
 /// ## Enterprise Features
@@ -468,6 +492,20 @@ impl Secrets {
         patterns
     }
 
+    /// Incomplete-match patterns for a category's open-ended patterns
+    fn open_patterns(category: SecretCategory) -> Vec<&'static Regex> {
+        match category {
+            SecretCategory::Azure => vec![&*AZURE_CONNECTION_STRING_OPEN],
+            SecretCategory::GitLab => vec![&*GITLAB_TOKEN_OPEN],
+            SecretCategory::Slack => vec![&*SLACK_WEBHOOK_OPEN],
+            SecretCategory::Stripe => vec![&*STRIPE_KEY_OPEN],
+            SecretCategory::DatabaseURLs => vec![&*DATABASE_URL_OPEN, &*CONNECTION_STRING_OPEN],
+            SecretCategory::JWT => vec![&*JWT_TOKEN_OPEN],
+            SecretCategory::Generic => vec![&*GENERIC_SECRET_OPEN],
+            _ => Vec::new(),
+        }
+    }
+
     fn detect_secrets(&self, text: &str) -> Vec<SecretMatch> {
         let mut matches = Vec::new();
 
@@ -576,6 +614,9 @@ struct SecretMatch {
     matched_text: String,
 }
 
+/// Bytes held back when scanning a stream
+const STREAM_LOOKBACK: usize = 512;
+
 #[async_trait]
 impl Scanner for Secrets {
     fn name(&self) -> &str {
@@ -645,6 +686,22 @@ impl Scanner for Secrets {
     fn description(&self) -> &str {
         "Detects exposed secrets, API keys, tokens, and credentials using 40+ patterns"
     }
+
+    fn stream_lookback(&self) -> Option<usize> {
+        // Longest bounded pattern (GitHub tokens) is under 300 bytes
+        Some(STREAM_LOOKBACK)
+    }
+
+    fn open_match_start(&self, text: &str) -> Option<usize> {
+        // Unbounded patterns (JWTs, connection strings, ...) can outgrow the
+        // lookback, so a match still open at the end is held back entirely
+        self.config
+            .categories
+            .iter()
+            .flat_map(|&category| Self::open_patterns(category))
+            .filter_map(|pattern| pattern.find(text).map(|m| m.start()))
+            .min()
+    }
 }
 
 #[cfg(test)]
@@ -854,4 +911,55 @@ mod tests {
         assert!(!result.is_valid);
         assert!(result.entities.iter().any(|e| e.text.contains("Azure")));
     }
+
+    #[tokio::test]
+    async fn test_secrets_streaming_redacts_split_key() {
+        use llm_shield_core::StreamingScanner;
+        use std::sync::Arc;
+
+        let mut stream =
+            StreamingScanner::new(vec![Arc::new(Secrets::default_config().unwrap())]).unwrap();
+
+        let mut output = String::new();
+        for chunk in ["Your key is AKIAIOSF", "ODNN7EXAMPLE, keep it safe."] {
+            output.push_str(&stream.push(chunk).await.unwrap().text);
+        }
+        let last = stream.finish().await.unwrap();
+        output.push_str(&last.text);
+
+        assert_eq!(output, "Your key is [REDACTED], keep it safe.");
+        assert!(!last.is_valid);
+        assert!(!last.blocked);
+    }
+
+    #[tokio::test]
+    async fn test_secrets_streaming_holds_long_jwt() {
+        use llm_shield_core::StreamingScanner;
+        use std::sync::Arc;
+
+        let config = SecretsConfig {
+            categories: vec![SecretCategory::JWT],
+            ..Default::default()
+        };
+        let mut stream =
+            StreamingScanner::new(vec![Arc::new(Secrets::new(config).unwrap())]).unwrap();
+
+        // Payload alone is far longer than the stream lookback
+        let jwt = format!(
+            "eyJhbGciOiJIUzI1NiJ9.eyJ{}.dozjgNryP4J3jVmNHl0w5N_XgL0n3I9PlFUP0THsR8U",
+            "a".repeat(2 * STREAM_LOOKBACK)
+        );
+        let text = format!("Bearer {} is the token.", jwt);
+        assert!(jwt.len() > STREAM_LOOKBACK);
+
+        let mut output = String::new();
+        for chunk in text.as_bytes().chunks(16) {
+            let released = stream.push(std::str::from_utf8(chunk).unwrap()).await.unwrap();
+            assert!(!released.text.contains("eyJ"));
+            output.push_str(&released.text);
+        }
+        output.push_str(&stream.finish().await.unwrap().text);
+
+        assert_eq!(output, "Bearer [REDACTED] is the token.");
+    }
 }
@@ -9,7 +9,11 @@
 pub mod input;
 pub mod output;
 pub mod common;
+pub mod pipeline;
 
 // Re-exports
 pub use input::*;
 pub use output::*;
+pub use pipeline::{
+    parse_params_pack, PipelineConfig, PipelineResources, PipelineSettings, ScannerSpec,
+};
@@ -7,17 +7,26 @@
 //! Prevents LLMs from generating content on banned topics.
 //! Essential for content moderation and policy compliance.
 //!
+//! Topics are matched in two stages: an Aho-Corasick keyword pass that is
+//! cheap and deterministic, followed by optional zero-shot semantic scoring
+//! (sentence-embedding similarity or NLI) through a pluggable [`TopicScorer`].
+//! With a scorer, every topic is scored by default, so paraphrases that
+//! contain none of the keywords are caught; enable `require_keyword_hit` to
+//! score keyword topics only after a keyword hit, trading recall for latency.
+//! Topics without keywords are always scored.
+//!
 //! ## London School TDD
 //!
 //! Tests written first drive the implementation.
 
 use aho_corasick::AhoCorasick;
 use llm_shield_core::{
-    async_trait, Entity, Error, Result, RiskFactor, ScanResult, Scanner, ScannerType, Severity,
-    Vault,
+    async_trait, Entity, Error, ErrorPolicy, PerformanceInfo, Result, RiskFactor, ScanResult, Scanner,
+    ScannerType, Severity, Vault,
 };
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::Arc;
 
 /// BanTopics scanner configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -31,6 +40,36 @@ pub struct BanTopicsConfig {
 
     /// Case-sensitive matching
     pub case_sensitive: bool,
+
+    /// Default semantic score threshold (0.0 to 1.0)
+    /// Used for topics without their own threshold
+    #[serde(default = "default_semantic_threshold")]
+    pub semantic_threshold: f32,
+
+    /// Only run semantic scoring for topics with at least one keyword hit
+    /// Trades recall for latency when a scorer is configured; topics
+    /// without keywords are always scored
+    #[serde(default = "default_require_keyword_hit")]
+    pub require_keyword_hit: bool,
+
+    /// What to do when the semantic scorer fails
+    ///
+    /// `fail_closed` (default) flags the text, `fail_open` keeps the keyword
+    /// results and `propagate` returns the scorer's error.
+    #[serde(default = "default_scorer_error_policy")]
+    pub on_scorer_error: ErrorPolicy,
+}
+
+fn default_semantic_threshold() -> f32 {
+    0.7
+}
+
+fn default_require_keyword_hit() -> bool {
+    false
+}
+
+fn default_scorer_error_policy() -> ErrorPolicy {
+    ErrorPolicy::FailClosed
 }
 
 impl Default for BanTopicsConfig {
@@ -39,6 +78,9 @@ impl Default for BanTopicsConfig {
             topics: Self::default_topics(),
             threshold: 0.6,
             case_sensitive: false,
+            semantic_threshold: default_semantic_threshold(),
+            require_keyword_hit: default_require_keyword_hit(),
+            on_scorer_error: default_scorer_error_policy(),
         }
     }
 }
@@ -47,48 +89,42 @@ impl BanTopicsConfig {
     /// Default banned topics for general content moderation
     fn default_topics() -> Vec<BannedTopic> {
         vec![
-            BannedTopic {
-                name: "violence".to_string(),
-                keywords: vec![
-                    "kill".to_string(),
-                    "murder".to_string(),
-                    "assault".to_string(),
-                    "attack".to_string(),
-                    "weapon".to_string(),
-                    "bomb".to_string(),
-                    "explosion".to_string(),
-                ],
-                severity: Severity::High,
-            },
-            BannedTopic {
-                name: "illegal_drugs".to_string(),
-                keywords: vec![
-                    "cocaine".to_string(),
-                    "heroin".to_string(),
-                    "methamphetamine".to_string(),
-                    "drug dealing".to_string(),
-                ],
-                severity: Severity::High,
-            },
-            BannedTopic {
-                name: "self_harm".to_string(),
-                keywords: vec![
-                    "suicide".to_string(),
-                    "self-harm".to_string(),
-                    "cutting".to_string(),
-                    "end my life".to_string(),
-                ],
-                severity: Severity::High,
-            },
-            BannedTopic {
-                name: "hate_speech".to_string(),
-                keywords: vec![
-                    "racial slur".to_string(),
-                    "nazi".to_string(),
-                    "white supremacy".to_string(),
-                ],
-                severity: Severity::High,
-            },
+            BannedTopic::new(
+                "violence",
+                ["kill", "murder", "assault", "attack", "weapon", "bomb", "explosion"],
+                Severity::High,
+            )
+            .with_examples([
+                "Step-by-step instructions for hurting another person",
+                "How to build an explosive device at home",
+            ]),
+            BannedTopic::new(
+                "illegal_drugs",
+                ["cocaine", "heroin", "methamphetamine", "drug dealing"],
+                Severity::High,
+            )
+            .with_examples([
+                "Where to buy illegal narcotics without getting caught",
+                "How to cook meth in a home lab",
+            ]),
+            BannedTopic::new(
+                "self_harm",
+                ["suicide", "self-harm", "cutting", "end my life"],
+                Severity::High,
+            )
+            .with_examples([
+                "Methods a person could use to hurt themselves",
+                "The most painless way to stop living",
+            ]),
+            BannedTopic::new(
+                "hate_speech",
+                ["racial slur", "nazi", "white supremacy"],
+                Severity::High,
+            )
+            .with_examples([
+                "Arguments that one race is superior to others",
+                "Insults targeting people because of their ethnicity",
+            ]),
         ]
     }
 }
@@ -104,6 +140,197 @@ pub struct BannedTopic {
 
     /// Severity of this topic
     pub severity: Severity,
+
+    /// Example sentences describing this topic (used for semantic scoring)
+    #[serde(default)]
+    pub examples: Vec<String>,
+
+    /// Per-topic semantic threshold (overrides `semantic_threshold`)
+    #[serde(default)]
+    pub threshold: Option<f32>,
+}
+
+impl BannedTopic {
+    /// Create a topic from a name, keywords and severity
+    pub fn new<I, S>(name: impl Into<String>, keywords: I, severity: Severity) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            name: name.into(),
+            keywords: keywords.into_iter().map(Into::into).collect(),
+            severity,
+            examples: Vec::new(),
+            threshold: None,
+        }
+    }
+
+    /// Set example sentences for semantic matching
+    pub fn with_examples<I, S>(mut self, examples: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.examples = examples.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Set a topic-specific semantic threshold
+    pub fn with_threshold(mut self, threshold: f32) -> Self {
+        self.threshold = Some(threshold);
+        self
+    }
+}
+
+/// Semantic scorer for banned topics
+///
+/// Implementations return how strongly `text` belongs to `topic`, from 0.0
+/// (unrelated) to 1.0 (clearly on topic). With the `ml` feature enabled,
+/// [`EmbeddingTopicScorer`] and [`NliTopicScorer`] provide model-backed
+/// implementations via `llm-shield-models`.
+#[async_trait]
+pub trait TopicScorer: Send + Sync {
+    /// Scorer name (reported in entity metadata)
+    fn name(&self) -> &str;
+
+    /// Score how strongly `text` matches `topic`
+    async fn score(&self, text: &str, topic: &BannedTopic) -> Result<f32>;
+}
+
+/// Topic scorer using sentence-embedding similarity
+///
+/// Scores a text by its maximum cosine similarity to the topic's example
+/// sentences (or the topic name when no examples are given). Example
+/// embeddings are computed once per topic and cached.
+#[cfg(feature = "ml")]
+pub struct EmbeddingTopicScorer {
+    model: Arc<llm_shield_models::EmbeddingModel>,
+    example_cache: std::sync::RwLock<HashMap<String, Arc<Vec<Vec<f32>>>>>,
+}
+
+#[cfg(feature = "ml")]
+impl EmbeddingTopicScorer {
+    /// Create a scorer from a loaded embedding model
+    pub fn new(model: Arc<llm_shield_models::EmbeddingModel>) -> Self {
+        Self {
+            model,
+            example_cache: std::sync::RwLock::new(HashMap::new()),
+        }
+    }
+
+    async fn example_embeddings(&self, topic: &BannedTopic) -> Result<Arc<Vec<Vec<f32>>>> {
+        if let Some(cached) = self
+            .example_cache
+            .read()
+            .ok()
+            .and_then(|cache| cache.get(&topic.name).cloned())
+        {
+            return Ok(cached);
+        }
+
+        let mut embeddings = Vec::new();
+        if topic.examples.is_empty() {
+            embeddings.push(self.model.embed(&topic.name.replace('_', " ")).await?);
+        } else {
+            for example in &topic.examples {
+                embeddings.push(self.model.embed(example).await?);
+            }
+        }
+
+        let embeddings = Arc::new(embeddings);
+        if let Ok(mut cache) = self.example_cache.write() {
+            cache.insert(topic.name.clone(), Arc::clone(&embeddings));
+        }
+        Ok(embeddings)
+    }
+}
+
+#[cfg(feature = "ml")]
+#[async_trait]
+impl TopicScorer for EmbeddingTopicScorer {
+    fn name(&self) -> &str {
+        "embedding"
+    }
+
+    async fn score(&self, text: &str, topic: &BannedTopic) -> Result<f32> {
+        let text_embedding = self.model.embed(text).await?;
+        let examples = self.example_embeddings(topic).await?;
+
+        Ok(examples
+            .iter()
+            .map(|e| llm_shield_models::InferenceEngine::cosine_similarity_static(&text_embedding, e))
+            .fold(0.0f32, f32::max))
+    }
+}
+
+/// Topic scorer using NLI zero-shot classification
+///
+/// Scores a text by the highest entailment probability among the
+/// hypothesis `"This text is about {topic}."` and one hypothesis per topic
+/// example, `"This text is similar to: {example}"`.
+#[cfg(feature = "ml")]
+pub struct NliTopicScorer {
+    classifier: Arc<llm_shield_models::ZeroShotClassifier>,
+    hypothesis_template: String,
+    example_template: String,
+}
+
+#[cfg(feature = "ml")]
+impl NliTopicScorer {
+    /// Create a scorer from a loaded NLI classifier
+    pub fn new(classifier: Arc<llm_shield_models::ZeroShotClassifier>) -> Self {
+        Self {
+            classifier,
+            hypothesis_template: "This text is about {}.".to_string(),
+            example_template: "This text is similar to: {}".to_string(),
+        }
+    }
+
+    /// Override the hypothesis template (`{}` is replaced with the topic)
+    pub fn with_hypothesis_template(mut self, template: impl Into<String>) -> Self {
+        self.hypothesis_template = template.into();
+        self
+    }
+
+    /// Override the example hypothesis template (`{}` is replaced with the example)
+    pub fn with_example_template(mut self, template: impl Into<String>) -> Self {
+        self.example_template = template.into();
+        self
+    }
+}
+
+/// NLI hypotheses for a topic: its name, then each of its examples
+#[cfg(feature = "ml")]
+fn topic_hypotheses(
+    topic: &BannedTopic,
+    hypothesis_template: &str,
+    example_template: &str,
+) -> Vec<String> {
+    std::iter::once(hypothesis_template.replace("{}", &topic.name.replace('_', " ")))
+        .chain(
+            topic
+                .examples
+                .iter()
+                .map(|example| example_template.replace("{}", example)),
+        )
+        .collect()
+}
+
+#[cfg(feature = "ml")]
+#[async_trait]
+impl TopicScorer for NliTopicScorer {
+    fn name(&self) -> &str {
+        "nli"
+    }
+
+    async fn score(&self, text: &str, topic: &BannedTopic) -> Result<f32> {
+        let mut best = 0.0f32;
+        for hypothesis in topic_hypotheses(topic, &self.hypothesis_template, &self.example_template) {
+            best = best.max(self.classifier.entailment_score(text, &hypothesis).await?);
+        }
+        Ok(best)
+    }
 }
 
 /// BanTopics scanner implementation
@@ -116,6 +343,7 @@ pub struct BannedTopic {
 /// - Confidence scoring based on keyword density
 /// - Case-sensitive/insensitive matching
 /// - Severity levels per topic
+/// - Zero-shot semantic matching with per-topic thresholds
 ///
 /// ## Example
 ///
@@ -131,6 +359,7 @@ pub struct BanTopics {
     config: BanTopicsConfig,
     matcher: Option<AhoCorasick>,
     keyword_to_topic: HashMap<String, usize>, // Map keyword to topic index
+    scorer: Option<Arc<dyn TopicScorer>>,
 }
 
 impl BanTopics {
@@ -140,10 +369,27 @@ impl BanTopics {
             return Err(Error::config("Threshold must be between 0.0 and 1.0"));
         }
 
+        if !(0.0..=1.0).contains(&config.semantic_threshold) {
+            return Err(Error::config(
+                "Semantic threshold must be between 0.0 and 1.0",
+            ));
+        }
+
         if config.topics.is_empty() {
             return Err(Error::config("At least one topic must be configured"));
         }
 
+        for topic in &config.topics {
+            if let Some(threshold) = topic.threshold {
+                if !(0.0..=1.0).contains(&threshold) {
+                    return Err(Error::config(format!(
+                        "Threshold for topic '{}' must be between 0.0 and 1.0",
+                        topic.name
+                    )));
+                }
+            }
+        }
+
         // Build Aho-Corasick matcher
         let mut all_keywords = Vec::new();
         let mut keyword_to_topic = HashMap::new();
@@ -175,6 +421,7 @@ impl BanTopics {
             config,
             matcher,
             keyword_to_topic,
+            scorer: None,
         })
     }
 
@@ -183,6 +430,58 @@ impl BanTopics {
         Self::new(BanTopicsConfig::default())
     }
 
+    /// Enable semantic topic scoring
+    ///
+    /// Keyword matching still runs first; topics already flagged by keywords
+    /// are not re-scored.
+    pub fn with_scorer(mut self, scorer: Arc<dyn TopicScorer>) -> Self {
+        self.scorer = Some(scorer);
+        self
+    }
+
+    /// Score topics semantically, skipping those already flagged by keywords
+    async fn detect_semantic_topics(
+        &self,
+        scorer: &dyn TopicScorer,
+        text: &str,
+        keyword_matches: &[TopicMatch],
+    ) -> Result<Vec<TopicMatch>> {
+        let mut matches = Vec::new();
+
+        for (topic_idx, topic) in self.config.topics.iter().enumerate() {
+            let keyword_match = keyword_matches.iter().find(|m| m.topic_idx == topic_idx);
+
+            if keyword_match.is_some_and(|m| m.confidence >= self.config.threshold) {
+                continue;
+            }
+            if self.config.require_keyword_hit
+                && !topic.keywords.is_empty()
+                && keyword_match.is_none()
+            {
+                continue;
+            }
+
+            let score = scorer.score(text, topic).await?;
+            let threshold = topic.threshold.unwrap_or(self.config.semantic_threshold);
+
+            if score >= threshold {
+                matches.push(TopicMatch {
+                    topic_idx,
+                    topic_name: topic.name.clone(),
+                    severity: topic.severity,
+                    matched_keywords: keyword_match
+                        .map(|m| m.matched_keywords.clone())
+                        .unwrap_or_default(),
+                    confidence: score,
+                    method: MatchMethod::Semantic,
+                    threshold,
+                });
+            }
+        }
+
+        Ok(matches)
+    }
+
     /// Detect banned topics in text
     fn detect_banned_topics(&self, text: &str) -> Vec<TopicMatch> {
         let mut topic_matches: HashMap<usize, TopicMatchBuilder> = HashMap::new();
@@ -223,10 +522,13 @@ impl BanTopics {
                 let confidence = (density * 10.0).min(1.0); // Scale factor
 
                 TopicMatch {
+                    topic_idx,
                     topic_name: topic.name.clone(),
                     severity: topic.severity,
                     matched_keywords: builder.keyword_matches,
                     confidence,
+                    method: MatchMethod::Keyword,
+                    threshold: self.config.threshold,
                 }
             })
             .collect()
@@ -239,23 +541,52 @@ impl BanTopics {
         output: &str,
         _vault: &Vault,
     ) -> Result<ScanResult> {
-        let matches = self.detect_banned_topics(output);
+        let keyword_matches = self.detect_banned_topics(output);
+
+        let mut semantic_error = None;
+        let semantic_matches = match &self.scorer {
+            Some(scorer) => {
+                match self
+                    .detect_semantic_topics(scorer.as_ref(), output, &keyword_matches)
+                    .await
+                {
+                    Ok(matches) => matches,
+                    Err(e) if self.config.on_scorer_error == ErrorPolicy::Propagate => {
+                        return Err(e);
+                    }
+                    Err(e) => {
+                        // Keyword results still apply; fail-closed also flags the text below
+                        tracing::warn!("BanTopics semantic scoring failed: {}", e);
+                        semantic_error = Some(e.to_string());
+                        Vec::new()
+                    }
+                }
+            }
+            None => Vec::new(),
+        };
 
-        if matches.is_empty() {
-            return Ok(ScanResult::pass(output.to_string())
-                .with_metadata("banned_topics_found", "0"));
-        }
+        let had_keyword_matches = !keyword_matches.is_empty();
 
         // Filter by threshold
-        let significant_matches: Vec<_> = matches
+        let significant_matches: Vec<_> = keyword_matches
             .into_iter()
             .filter(|m| m.confidence >= self.config.threshold)
+            .chain(semantic_matches)
             .collect();
 
-        if significant_matches.is_empty() {
-            return Ok(ScanResult::pass(output.to_string())
-                .with_metadata("banned_topics_found", "0")
-                .with_metadata("below_threshold_matches", "true"));
+        let fail_closed =
+            semantic_error.is_some() && self.config.on_scorer_error == ErrorPolicy::FailClosed;
+
+        if significant_matches.is_empty() && !fail_closed {
+            let mut result = ScanResult::pass(output.to_string())
+                .with_metadata("banned_topics_found", "0");
+            if had_keyword_matches {
+                result = result.with_metadata("below_threshold_matches", "true");
+            }
+            if let Some(error) = semantic_error {
+                result = result.with_metadata("semantic_error", error);
+            }
+            return Ok(result);
         }
 
         // Build entities
@@ -269,6 +600,13 @@ impl BanTopics {
                     m.matched_keywords.join(", "),
                 );
                 metadata.insert("keyword_count".to_string(), m.matched_keywords.len().to_string());
+                metadata.insert("match_method".to_string(), m.method.as_str().to_string());
+                metadata.insert("threshold".to_string(), m.threshold.to_string());
+                if m.method == MatchMethod::Semantic {
+                    if let Some(scorer) = &self.scorer {
+                        metadata.insert("scorer".to_string(), scorer.name().to_string());
+                    }
+                }
 
                 Entity {
                     entity_type: "banned_topic".to_string(),
@@ -281,32 +619,52 @@ impl BanTopics {
             })
             .collect();
 
-        // Determine overall severity
-        let max_severity = significant_matches
-            .iter()
-            .map(|m| &m.severity)
-            .max()
-            .unwrap_or(&Severity::Low);
-
-        let max_confidence = significant_matches
-            .iter()
-            .map(|m| m.confidence)
-            .fold(0.0f32, f32::max);
-
-        let description = format!(
-            "LLM response contains {} banned topic(s)",
-            significant_matches.len()
-        );
-        let risk_factor = RiskFactor::new(
-            "banned_topic",
-            &description,
-            *max_severity,
-            max_confidence,
-        );
+        let max_confidence = if fail_closed {
+            1.0
+        } else {
+            significant_matches
+                .iter()
+                .map(|m| m.confidence)
+                .fold(0.0f32, f32::max)
+        };
 
         let mut result = ScanResult::new(output.to_string(), false, max_confidence)
-            .with_risk_factor(risk_factor)
-            .with_metadata("banned_topics_found", significant_matches.len());
+            .with_metadata("banned_topics_found", significant_matches.len())
+            .with_metadata(
+                "matched_topics",
+                significant_matches
+                    .iter()
+                    .map(|m| m.topic_name.clone())
+                    .collect::<Vec<_>>(),
+            );
+
+        if let Some(error) = semantic_error {
+            if fail_closed {
+                result = result.with_risk_factor(RiskFactor::new(
+                    "topic_scorer_error",
+                    &format!("Banned topic scoring failed: {}", error),
+                    Severity::High,
+                    1.0,
+                ));
+            }
+            result = result.with_metadata("semantic_error", error);
+        }
+
+        // One risk factor per topic so callers can see which topic triggered
+        for m in &significant_matches {
+            let description = format!(
+                "LLM response matches banned topic '{}' ({} score {:.2})",
+                m.topic_name,
+                m.method.as_str(),
+                m.confidence
+            );
+            result = result.with_risk_factor(RiskFactor::new(
+                "banned_topic",
+                &description,
+                m.severity,
+                m.confidence,
+            ));
+        }
 
         for entity in entities {
             result = result.with_entity(entity);
@@ -322,12 +680,30 @@ struct TopicMatchBuilder {
     keyword_matches: Vec<String>,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MatchMethod {
+    Keyword,
+    Semantic,
+}
+
+impl MatchMethod {
+    fn as_str(&self) -> &'static str {
+        match self {
+            MatchMethod::Keyword => "keyword",
+            MatchMethod::Semantic => "semantic",
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 struct TopicMatch {
+    topic_idx: usize,
     topic_name: String,
     severity: Severity,
     matched_keywords: Vec<String>,
     confidence: f32,
+    method: MatchMethod,
+    threshold: f32,
 }
 
 #[async_trait]
@@ -347,6 +723,14 @@ impl Scanner for BanTopics {
     fn description(&self) -> &str {
         "Prevents LLMs from generating content on banned topics"
     }
+
+    fn performance(&self) -> PerformanceInfo {
+        PerformanceInfo {
+            typical_latency_ms: if self.scorer.is_some() { 50 } else { 1 },
+            uses_ml_models: self.scorer.is_some(),
+            ..PerformanceInfo::default()
+        }
+    }
 }
 
 #[cfg(test)]
@@ -414,9 +798,12 @@ mod tests {
                 name: "competitor_products".to_string(),
                 keywords: vec!["CompetitorX".to_string(), "RivalProduct".to_string()],
                 severity: Severity::Medium,
+                examples: vec![],
+                threshold: None,
             }],
             threshold: 0.5,
             case_sensitive: false,
+            ..Default::default()
         };
         let scanner = BanTopics::new(config).unwrap();
         let vault = Vault::new();
@@ -459,9 +846,12 @@ mod tests {
                 name: "test".to_string(),
                 keywords: vec!["Secret".to_string()],
                 severity: Severity::Low,
+                examples: vec![],
+                threshold: None,
             }],
             threshold: 0.5,
             case_sensitive: true,
+            ..Default::default()
         };
         let scanner = BanTopics::new(config).unwrap();
         let vault = Vault::new();
@@ -499,15 +889,20 @@ mod tests {
                     name: "high_severity".to_string(),
                     keywords: vec!["dangerous".to_string()],
                     severity: Severity::High,
+                    examples: vec![],
+                    threshold: None,
                 },
                 BannedTopic {
                     name: "low_severity".to_string(),
                     keywords: vec!["mild".to_string()],
                     severity: Severity::Low,
+                    examples: vec![],
+                    threshold: None,
                 },
             ],
             threshold: 0.1,
             case_sensitive: false,
+            ..Default::default()
         };
         let scanner = BanTopics::new(config).unwrap();
         let vault = Vault::new();
@@ -538,9 +933,293 @@ mod tests {
             topics: vec![],
             threshold: 0.5,
             case_sensitive: false,
+            ..Default::default()
         };
         let result = BanTopics::new(config);
 
         assert!(result.is_err()); // Should fail - no topics configured
     }
+
+    struct MockScorer {
+        scores: HashMap<String, f32>,
+    }
+
+    #[async_trait]
+    impl TopicScorer for MockScorer {
+        fn name(&self) -> &str {
+            "mock"
+        }
+
+        async fn score(&self, _text: &str, topic: &BannedTopic) -> Result<f32> {
+            Ok(self.scores.get(&topic.name).copied().unwrap_or(0.0))
+        }
+    }
+
+    struct FailingScorer;
+
+    #[async_trait]
+    impl TopicScorer for FailingScorer {
+        fn name(&self) -> &str {
+            "failing"
+        }
+
+        async fn score(&self, _text: &str, _topic: &BannedTopic) -> Result<f32> {
+            Err(Error::model("model not loaded"))
+        }
+    }
+
+    fn mock_scorer(scores: &[(&str, f32)]) -> Arc<dyn TopicScorer> {
+        Arc::new(MockScorer {
+            scores: scores.iter().map(|(k, v)| (k.to_string(), *v)).collect(),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_ban_topics_semantic_match_without_keywords() {
+        let config = BanTopicsConfig {
+            require_keyword_hit: false,
+            ..Default::default()
+        };
+        let scanner = BanTopics::new(config)
+            .unwrap()
+            .with_scorer(mock_scorer(&[("self_harm", 0.92)]));
+        let vault = Vault::new();
+
+        let response = "Here is the most painless way to stop living.";
+        let result = scanner.scan_output("", response, &vault).await.unwrap();
+
+        assert!(!result.is_valid);
+        let entity = &result.entities[0];
+        assert_eq!(entity.metadata.get("topic").unwrap(), "self_harm");
+        assert_eq!(entity.metadata.get("match_method").unwrap(), "semantic");
+        assert_eq!(entity.metadata.get("scorer").unwrap(), "mock");
+        assert!(result.risk_factors[0].description.contains("self_harm"));
+    }
+
+    #[tokio::test]
+    async fn test_ban_topics_semantic_below_threshold() {
+        let scanner = BanTopics::default_config()
+            .unwrap()
+            .with_scorer(mock_scorer(&[("violence", 0.5)]));
+        let vault = Vault::new();
+
+        let result = scanner
+            .scan_output("", "A history of the printing press.", &vault)
+            .await
+            .unwrap();
+
+        assert!(result.is_valid);
+    }
+
+    #[tokio::test]
+    async fn test_ban_topics_per_topic_threshold() {
+        let config = BanTopicsConfig {
+            topics: vec![
+                BannedTopic::new("competitors", ["CompetitorX"], Severity::Medium)
+                    .with_examples(["You should switch to another vendor"])
+                    .with_threshold(0.4),
+                BannedTopic::new("politics", ["election"], Severity::Medium),
+            ],
+            require_keyword_hit: false,
+            ..Default::default()
+        };
+        let scanner = BanTopics::new(config)
+            .unwrap()
+            .with_scorer(mock_scorer(&[("competitors", 0.5), ("politics", 0.5)]));
+        let vault = Vault::new();
+
+        let result = scanner
+            .scan_output("", "Maybe another vendor suits you better.", &vault)
+            .await
+            .unwrap();
+
+        assert!(!result.is_valid);
+        assert_eq!(result.entities.len(), 1);
+        assert_eq!(result.entities[0].metadata.get("topic").unwrap(), "competitors");
+    }
+
+    #[tokio::test]
+    async fn test_ban_topics_require_keyword_hit() {
+        let config = BanTopicsConfig {
+            require_keyword_hit: true,
+            ..Default::default()
+        };
+        let scanner = BanTopics::new(config)
+            .unwrap()
+            .with_scorer(mock_scorer(&[("self_harm", 0.95)]));
+        let vault = Vault::new();
+
+        let result = scanner
+            .scan_output("", "The most painless way to stop living.", &vault)
+            .await
+            .unwrap();
+
+        // No keyword hit, so the scorer is never consulted
+        assert!(result.is_valid);
+    }
+
+    #[tokio::test]
+    async fn test_ban_topics_semantic_match_by_default() {
+        let scanner = BanTopics::default_config()
+            .unwrap()
+            .with_scorer(mock_scorer(&[("violence", 0.9)]));
+        let vault = Vault::new();
+
+        // A paraphrase without any of the topic's keywords
+        let result = scanner
+            .scan_output("", "Pack a steel tube with black powder and seal both ends.", &vault)
+            .await
+            .unwrap();
+
+        assert!(!result.is_valid);
+        assert_eq!(result.entities[0].metadata.get("topic").unwrap(), "violence");
+        assert_eq!(
+            result.entities[0].metadata.get("match_method").unwrap(),
+            "semantic"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_ban_topics_keywordless_topic_always_scored() {
+        let config = BanTopicsConfig {
+            topics: vec![
+                BannedTopic::new("competitors", Vec::<String>::new(), Severity::Medium)
+                    .with_examples(["You should switch to another vendor"]),
+            ],
+            require_keyword_hit: true,
+            ..Default::default()
+        };
+        let scanner = BanTopics::new(config)
+            .unwrap()
+            .with_scorer(mock_scorer(&[("competitors", 0.9)]));
+        let vault = Vault::new();
+
+        let result = scanner
+            .scan_output("", "Maybe another vendor suits you better.", &vault)
+            .await
+            .unwrap();
+
+        assert!(!result.is_valid);
+    }
+
+    #[tokio::test]
+    async fn test_ban_topics_scorer_skipped_without_keyword_hit() {
+        let config = BanTopicsConfig {
+            require_keyword_hit: true,
+            ..Default::default()
+        };
+        let scanner = BanTopics::new(config)
+            .unwrap()
+            .with_scorer(Arc::new(FailingScorer));
+        let vault = Vault::new();
+
+        // Clean text never reaches the scorer with `require_keyword_hit`
+        let result = scanner
+            .scan_output("", "A history of the printing press.", &vault)
+            .await
+            .unwrap();
+
+        assert!(result.is_valid);
+        assert!(!result.metadata.contains_key("semantic_error"));
+    }
+
+    #[tokio::test]
+    async fn test_ban_topics_scorer_error_fails_closed() {
+        let config = BanTopicsConfig {
+            require_keyword_hit: false,
+            ..Default::default()
+        };
+        let scanner = BanTopics::new(config)
+            .unwrap()
+            .with_scorer(Arc::new(FailingScorer));
+        let vault = Vault::new();
+
+        let result = scanner
+            .scan_output("", "A history of the printing press.", &vault)
+            .await
+            .unwrap();
+
+        assert!(!result.is_valid);
+        assert_eq!(result.risk_score, 1.0);
+        assert!(result.metadata.contains_key("semantic_error"));
+        assert_eq!(result.risk_factors[0].factor_type, "topic_scorer_error");
+    }
+
+    #[tokio::test]
+    async fn test_ban_topics_scorer_error_policies() {
+        let config = BanTopicsConfig {
+            require_keyword_hit: false,
+            on_scorer_error: ErrorPolicy::FailOpen,
+            ..Default::default()
+        };
+        let scanner = BanTopics::new(config)
+            .unwrap()
+            .with_scorer(Arc::new(FailingScorer));
+        let vault = Vault::new();
+
+        // Fail-open keeps the keyword results
+        let result = scanner
+            .scan_output("", "How to make a bomb", &vault)
+            .await
+            .unwrap();
+        assert!(!result.is_valid);
+        assert!(result.metadata.contains_key("semantic_error"));
+        assert_eq!(
+            result.entities[0].metadata.get("match_method").unwrap(),
+            "keyword"
+        );
+
+        let result = scanner
+            .scan_output("", "A history of the printing press.", &vault)
+            .await
+            .unwrap();
+        assert!(result.is_valid);
+
+        let config = BanTopicsConfig {
+            require_keyword_hit: false,
+            on_scorer_error: ErrorPolicy::Propagate,
+            ..Default::default()
+        };
+        let scanner = BanTopics::new(config)
+            .unwrap()
+            .with_scorer(Arc::new(FailingScorer));
+        assert!(scanner
+            .scan_output("", "A history of the printing press.", &vault)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_ban_topics_invalid_topic_threshold() {
+        let config = BanTopicsConfig {
+            topics: vec![BannedTopic::new("test", ["x"], Severity::Low).with_threshold(1.5)],
+            ..Default::default()
+        };
+
+        assert!(BanTopics::new(config).is_err());
+    }
+
+    #[cfg(feature = "ml")]
+    #[test]
+    fn test_nli_hypotheses_include_examples() {
+        let topic = BannedTopic::new("self_harm", ["suicide"], Severity::Critical)
+            .with_examples(["ways to end my life"]);
+
+        let hypotheses = topic_hypotheses(&topic, "This text is about {}.", "Like: {}");
+        assert_eq!(
+            hypotheses,
+            ["This text is about self harm.", "Like: ways to end my life"]
+        );
+    }
+
+    #[test]
+    fn test_banned_topic_deserialize_defaults() {
+        let topic: BannedTopic = serde_json::from_str(
+            r#"{"name": "gambling", "keywords": ["casino"], "severity": "medium"}"#,
+        )
+        .unwrap();
+
+        assert!(topic.examples.is_empty());
+        assert!(topic.threshold.is_none());
+    }
 }
@@ -0,0 +1,605 @@
+//! FactualConsistency Output Scanner
+//!
+//! ## SPARC Implementation
+//!
+//! Checks an LLM response against reference documents (e.g. RAG context)
+//! using natural language inference. Each output sentence is treated as a
+//! hypothesis and each reference passage as a premise; sentences that are
+//! contradicted by, or not entailed by, any reference are flagged.
+//!
+//! Unlike [`Factuality`](super::Factuality), which only looks for hedging
+//! language, this scanner detects grounded hallucinations.
+//!
+//! ## London School TDD
+//!
+//! Tests written first drive the implementation.
+
+use llm_shield_core::{
+    async_trait, Entity, Error, NliScores, PerformanceInfo, Result, RiskFactor, ScanResult,
+    Scanner, ScannerType, Severity, Vault,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Default vault key for reference documents
+pub const DEFAULT_REFERENCE_VAULT_KEY: &str = "reference_documents";
+
+/// FactualConsistency scanner configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FactualConsistencyConfig {
+    /// Entailment probability at or above which a sentence is supported
+    pub entailment_threshold: f32,
+
+    /// Contradiction probability at or above which a sentence is contradicted
+    pub contradiction_threshold: f32,
+
+    /// Fail responses containing sentences no reference supports
+    /// When false, only contradictions fail the scan
+    pub flag_unsupported: bool,
+
+    /// Sentences with fewer words are not checked
+    pub min_sentence_words: usize,
+
+    /// Number of reference sentences per NLI premise window
+    /// Keeps premises within the model's context length
+    pub reference_window_sentences: usize,
+
+    /// Vault key holding reference documents (`Vec<String>` or `String`)
+    pub vault_key: String,
+}
+
+impl Default for FactualConsistencyConfig {
+    fn default() -> Self {
+        Self {
+            entailment_threshold: 0.5,
+            contradiction_threshold: 0.5,
+            flag_unsupported: true,
+            min_sentence_words: 3,
+            reference_window_sentences: 4,
+            vault_key: DEFAULT_REFERENCE_VAULT_KEY.to_string(),
+        }
+    }
+}
+
+/// Natural language inference backend
+///
+/// With the `ml` feature enabled this is implemented for
+/// `llm_shield_models::ZeroShotClassifier`.
+#[async_trait]
+pub trait NliPredictor: Send + Sync {
+    /// Classify the relationship between `premise` and `hypothesis`
+    async fn predict(&self, premise: &str, hypothesis: &str) -> Result<NliScores>;
+}
+
+#[cfg(feature = "ml")]
+#[async_trait]
+impl NliPredictor for llm_shield_models::ZeroShotClassifier {
+    async fn predict(&self, premise: &str, hypothesis: &str) -> Result<NliScores> {
+        self.classify(premise, hypothesis).await
+    }
+}
+
+/// FactualConsistency scanner implementation
+///
+/// ## Enterprise Features
+///
+/// - Sentence-level NLI against reference documents
+/// - Contradicted and unsupported claims reported with byte spans
+/// - References from the request or from the [`Vault`]
+/// - Windowed reference passages to respect model context length
+/// - Pluggable NLI backend via [`NliPredictor`]
+///
+/// ## Example
+///
+/// ```rust,ignore
+/// use llm_shield_scanners::output::FactualConsistency;
+///
+/// let scanner = FactualConsistency::new(Default::default(), Arc::new(classifier))?;
+/// let references = vec!["The Eiffel Tower is in Paris.".to_string()];
+/// let result = scanner
+///     .scan_output_with_references("", "The Eiffel Tower is in Rome.", &references)
+///     .await?;
+/// assert!(!result.is_valid); // Contradicted claim
+/// ```
+pub struct FactualConsistency {
+    config: FactualConsistencyConfig,
+    predictor: Arc<dyn NliPredictor>,
+}
+
+impl FactualConsistency {
+    /// Create a new FactualConsistency scanner
+    pub fn new(config: FactualConsistencyConfig, predictor: Arc<dyn NliPredictor>) -> Result<Self> {
+        if !(0.0..=1.0).contains(&config.entailment_threshold) {
+            return Err(Error::config(
+                "entailment_threshold must be between 0.0 and 1.0",
+            ));
+        }
+
+        if !(0.0..=1.0).contains(&config.contradiction_threshold) {
+            return Err(Error::config(
+                "contradiction_threshold must be between 0.0 and 1.0",
+            ));
+        }
+
+        if config.reference_window_sentences == 0 {
+            return Err(Error::config(
+                "reference_window_sentences must be at least 1",
+            ));
+        }
+
+        Ok(Self { config, predictor })
+    }
+
+    /// Scan output against references stored in the vault
+    pub async fn scan_output(
+        &self,
+        prompt: &str,
+        output: &str,
+        vault: &Vault,
+    ) -> Result<ScanResult> {
+        let references = self.references_from_vault(vault)?;
+        self.scan_output_with_references(prompt, output, &references)
+            .await
+    }
+
+    /// Scan output against explicitly supplied references
+    pub async fn scan_output_with_references(
+        &self,
+        _prompt: &str,
+        output: &str,
+        references: &[String],
+    ) -> Result<ScanResult> {
+        let passages = self.reference_passages(references);
+
+        if passages.is_empty() {
+            return Ok(ScanResult::pass(output.to_string())
+                .with_metadata("references_found", 0)
+                .with_metadata("skipped", "no reference documents"));
+        }
+
+        let mut checked = 0usize;
+        let mut findings = Vec::new();
+
+        for (start, sentence) in split_sentences(output) {
+            if sentence.split_whitespace().count() < self.config.min_sentence_words {
+                continue;
+            }
+            checked += 1;
+
+            let verdict = self.check_sentence(sentence, &passages).await?;
+            if let Some(kind) = self.classify(&verdict) {
+                findings.push(SentenceFinding {
+                    kind,
+                    text: sentence.to_string(),
+                    start,
+                    end: start + sentence.len(),
+                    verdict,
+                });
+            }
+        }
+
+        let contradicted = findings
+            .iter()
+            .filter(|f| f.kind == ClaimKind::Contradicted)
+            .count();
+        let unsupported = findings.len() - contradicted;
+        let support_ratio = if checked == 0 {
+            1.0
+        } else {
+            (checked - findings.len()) as f32 / checked as f32
+        };
+
+        let failing: Vec<_> = findings
+            .into_iter()
+            .filter(|f| f.kind == ClaimKind::Contradicted || self.config.flag_unsupported)
+            .collect();
+
+        let base = |result: ScanResult| {
+            result
+                .with_metadata("references_found", references.len())
+                .with_metadata("sentences_checked", checked)
+                .with_metadata("contradicted_sentences", contradicted)
+                .with_metadata("unsupported_sentences", unsupported)
+                .with_metadata("support_ratio", support_ratio)
+        };
+
+        if failing.is_empty() {
+            return Ok(base(ScanResult::pass(output.to_string())));
+        }
+
+        let risk_score = failing
+            .iter()
+            .map(|f| f.confidence())
+            .fold(0.0f32, f32::max);
+
+        let mut result = base(ScanResult::new(output.to_string(), false, risk_score));
+
+        for finding in &failing {
+            let mut metadata = HashMap::new();
+            metadata.insert(
+                "entailment".to_string(),
+                format!("{:.3}", finding.verdict.entailment),
+            );
+            metadata.insert(
+                "contradiction".to_string(),
+                format!("{:.3}", finding.verdict.contradiction),
+            );
+            if let Some(idx) = finding.verdict.reference_index {
+                metadata.insert("reference_index".to_string(), idx.to_string());
+            }
+
+            result = result.with_entity(Entity {
+                entity_type: finding.kind.entity_type().to_string(),
+                text: finding.text.clone(),
+                start: finding.start,
+                end: finding.end,
+                confidence: finding.confidence(),
+                metadata,
+            });
+        }
+
+        if contradicted > 0 {
+            let description = format!(
+                "LLM response contradicts reference documents in {} sentence(s)",
+                contradicted
+            );
+            result = result.with_risk_factor(RiskFactor::new(
+                "contradicted_claim",
+                &description,
+                Severity::High,
+                risk_score,
+            ));
+        }
+
+        if unsupported > 0 && self.config.flag_unsupported {
+            let description = format!(
+                "LLM response contains {} sentence(s) not supported by reference documents",
+                unsupported
+            );
+            result = result.with_risk_factor(RiskFactor::new(
+                "unsupported_claim",
+                &description,
+                Severity::Medium,
+                1.0 - support_ratio,
+            ));
+        }
+
+        Ok(result)
+    }
+
+    /// Load references from the vault (accepts a list or a single string)
+    fn references_from_vault(&self, vault: &Vault) -> Result<Vec<String>> {
+        let key = self.config.vault_key.as_str();
+        if let Ok(Some(references)) = vault.get::<_, Vec<String>>(key) {
+            return Ok(references);
+        }
+        Ok(vault.get::<_, String>(key)?.into_iter().collect())
+    }
+
+    /// Split references into premise windows of a few sentences each
+    fn reference_passages(&self, references: &[String]) -> Vec<(usize, String)> {
+        let mut passages = Vec::new();
+
+        for (idx, reference) in references.iter().enumerate() {
+            let sentences: Vec<&str> = split_sentences(reference).map(|(_, s)| s).collect();
+            for window in sentences.chunks(self.config.reference_window_sentences) {
+                passages.push((idx, window.join(" ")));
+            }
+        }
+
+        passages
+    }
+
+    /// Run NLI for one sentence against every passage
+    async fn check_sentence(
+        &self,
+        sentence: &str,
+        passages: &[(usize, String)],
+    ) -> Result<SentenceVerdict> {
+        let mut verdict = SentenceVerdict::default();
+
+        for (reference_idx, passage) in passages {
+            let outcome = self.predictor.predict(passage, sentence).await?;
+
+            if outcome.entailment > verdict.entailment {
+                verdict.entailment = outcome.entailment;
+            }
+            if outcome.contradiction > verdict.contradiction {
+                verdict.contradiction = outcome.contradiction;
+                verdict.reference_index = Some(*reference_idx);
+            }
+
+            // One supporting passage is enough
+            if verdict.entailment >= self.config.entailment_threshold {
+                break;
+            }
+        }
+
+        Ok(verdict)
+    }
+
+    fn classify(&self, verdict: &SentenceVerdict) -> Option<ClaimKind> {
+        if verdict.entailment >= self.config.entailment_threshold {
+            None
+        } else if verdict.contradiction >= self.config.contradiction_threshold {
+            Some(ClaimKind::Contradicted)
+        } else {
+            Some(ClaimKind::Unsupported)
+        }
+    }
+}
+
+/// Split text into trimmed sentences with their byte offsets
+fn split_sentences(text: &str) -> impl Iterator<Item = (usize, &str)> {
+    text.split_sentence_bound_indices().filter_map(|(offset, raw)| {
+        let trimmed_start = raw.len() - raw.trim_start().len();
+        let sentence = raw.trim();
+        (!sentence.is_empty()).then_some((offset + trimmed_start, sentence))
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ClaimKind {
+    Contradicted,
+    Unsupported,
+}
+
+impl ClaimKind {
+    fn entity_type(&self) -> &'static str {
+        match self {
+            ClaimKind::Contradicted => "contradicted_claim",
+            ClaimKind::Unsupported => "unsupported_claim",
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct SentenceVerdict {
+    entailment: f32,
+    contradiction: f32,
+    reference_index: Option<usize>,
+}
+
+#[derive(Debug)]
+struct SentenceFinding {
+    kind: ClaimKind,
+    text: String,
+    start: usize,
+    end: usize,
+    verdict: SentenceVerdict,
+}
+
+impl SentenceFinding {
+    fn confidence(&self) -> f32 {
+        match self.kind {
+            ClaimKind::Contradicted => self.verdict.contradiction,
+            ClaimKind::Unsupported => 1.0 - self.verdict.entailment,
+        }
+    }
+}
+
+#[async_trait]
+impl Scanner for FactualConsistency {
+    fn name(&self) -> &str {
+        "FactualConsistency"
+    }
+
+    async fn scan(&self, input: &str, vault: &Vault) -> Result<ScanResult> {
+        self.scan_output("", input, vault).await
+    }
+
+    fn scanner_type(&self) -> ScannerType {
+        ScannerType::Output
+    }
+
+    fn uses_vault(&self) -> bool {
+        // References are supplied per request
+        true
+    }
+
+    fn requires_async(&self) -> bool {
+        true
+    }
+
+    fn description(&self) -> &str {
+        "Checks LLM responses against reference documents using NLI"
+    }
+
+    fn performance(&self) -> PerformanceInfo {
+        PerformanceInfo {
+            typical_latency_ms: 100,
+            uses_ml_models: true,
+            ..PerformanceInfo::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Keyword-driven NLI stand-in: entails when the premise contains the
+    /// hypothesis, contradicts when the premise contains a listed antonym.
+    struct MockNli {
+        contradictions: Vec<(&'static str, &'static str)>,
+    }
+
+    #[async_trait]
+    impl NliPredictor for MockNli {
+        async fn predict(&self, premise: &str, hypothesis: &str) -> Result<NliScores> {
+            let hypothesis = hypothesis.trim_end_matches('.');
+            if premise.contains(hypothesis) {
+                return Ok(NliScores {
+                    entailment: 0.95,
+                    neutral: 0.04,
+                    contradiction: 0.01,
+                });
+            }
+            let contradicted = self
+                .contradictions
+                .iter()
+                .any(|(p, h)| premise.contains(p) && hypothesis.contains(h));
+            Ok(if contradicted {
+                NliScores {
+                    entailment: 0.02,
+                    neutral: 0.08,
+                    contradiction: 0.9,
+                }
+            } else {
+                NliScores {
+                    entailment: 0.1,
+                    neutral: 0.8,
+                    contradiction: 0.1,
+                }
+            })
+        }
+    }
+
+    fn scanner(config: FactualConsistencyConfig) -> FactualConsistency {
+        let nli = MockNli {
+            contradictions: vec![("in Paris", "in Rome")],
+        };
+        FactualConsistency::new(config, Arc::new(nli)).unwrap()
+    }
+
+    fn references() -> Vec<String> {
+        vec!["The Eiffel Tower is in Paris. It was completed in 1889.".to_string()]
+    }
+
+    #[tokio::test]
+    async fn test_supported_response_passes() {
+        let scanner = scanner(Default::default());
+
+        let result = scanner
+            .scan_output_with_references("", "The Eiffel Tower is in Paris.", &references())
+            .await
+            .unwrap();
+
+        assert!(result.is_valid);
+        assert_eq!(result.entities.len(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_contradicted_sentence_flagged_with_span() {
+        let scanner = scanner(Default::default());
+        let output = "It was completed in 1889. The Eiffel Tower is in Rome.";
+
+        let result = scanner
+            .scan_output_with_references("", output, &references())
+            .await
+            .unwrap();
+
+        assert!(!result.is_valid);
+        assert_eq!(result.entities.len(), 1);
+        let entity = &result.entities[0];
+        assert_eq!(entity.entity_type, "contradicted_claim");
+        assert_eq!(&output[entity.start..entity.end], "The Eiffel Tower is in Rome.");
+        assert_eq!(entity.metadata.get("reference_index").unwrap(), "0");
+        assert!(result
+            .risk_factors
+            .iter()
+            .any(|r| r.factor_type == "contradicted_claim" && r.severity == Severity::High));
+    }
+
+    #[tokio::test]
+    async fn test_unsupported_sentence_flagged() {
+        let scanner = scanner(Default::default());
+
+        let result = scanner
+            .scan_output_with_references("", "The tower is painted bright green.", &references())
+            .await
+            .unwrap();
+
+        assert!(!result.is_valid);
+        assert_eq!(result.entities[0].entity_type, "unsupported_claim");
+    }
+
+    #[tokio::test]
+    async fn test_unsupported_allowed_when_not_flagged() {
+        let scanner = scanner(FactualConsistencyConfig {
+            flag_unsupported: false,
+            ..Default::default()
+        });
+
+        let result = scanner
+            .scan_output_with_references("", "The tower is painted bright green.", &references())
+            .await
+            .unwrap();
+
+        assert!(result.is_valid);
+    }
+
+    #[tokio::test]
+    async fn test_references_from_vault() {
+        let scanner = scanner(Default::default());
+        let vault = Vault::new();
+        vault
+            .set(DEFAULT_REFERENCE_VAULT_KEY, references())
+            .unwrap();
+
+        let result = scanner
+            .scan_output("", "The Eiffel Tower is in Rome.", &vault)
+            .await
+            .unwrap();
+
+        assert!(!result.is_valid);
+    }
+
+    #[tokio::test]
+    async fn test_single_string_reference_in_vault() {
+        let scanner = scanner(Default::default());
+        let vault = Vault::new();
+        vault
+            .set(DEFAULT_REFERENCE_VAULT_KEY, "The Eiffel Tower is in Paris.")
+            .unwrap();
+
+        let result = scanner
+            .scan_output("", "The Eiffel Tower is in Paris.", &vault)
+            .await
+            .unwrap();
+
+        assert!(result.is_valid);
+    }
+
+    #[tokio::test]
+    async fn test_no_references_skips() {
+        let scanner = scanner(Default::default());
+        let vault = Vault::new();
+
+        let result = scanner
+            .scan_output("", "Anything at all goes here.", &vault)
+            .await
+            .unwrap();
+
+        assert!(result.is_valid);
+        assert!(result.metadata.contains_key("skipped"));
+    }
+
+    #[tokio::test]
+    async fn test_short_sentences_skipped() {
+        let scanner = scanner(Default::default());
+
+        let result = scanner
+            .scan_output_with_references("", "Sure. Okay!", &references())
+            .await
+            .unwrap();
+
+        assert!(result.is_valid);
+        assert_eq!(result.metadata.get("sentences_checked").unwrap(), 0);
+    }
+
+    #[test]
+    fn test_invalid_threshold() {
+        let nli = Arc::new(MockNli {
+            contradictions: vec![],
+        });
+        let config = FactualConsistencyConfig {
+            entailment_threshold: 1.5,
+            ..Default::default()
+        };
+
+        assert!(FactualConsistency::new(config, nli).is_err());
+    }
+}
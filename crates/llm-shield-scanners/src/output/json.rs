@@ -0,0 +1,309 @@
+//! JSON Output Scanner
+//!
+//! Converted from llm_guard/output_scanners/json.py
+//!
+//! ## SPARC Implementation
+//!
+//! Validates JSON in LLM responses:
+//! - Finds JSON objects and arrays, bare or embedded in prose
+//! - Requires a minimum number of JSON values
+//! - Validates each value against an optional JSON schema
+//!
+//! ## London School TDD
+//!
+//! Tests written first drive the implementation.
+
+use llm_shield_core::{
+    async_trait, Entity, Error, Result, RiskFactor, ScanResult, Scanner, ScannerType, Severity,
+    Vault,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// JSON scanner configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JsonOutputConfig {
+    /// JSON schema every JSON value must satisfy (None = syntax only)
+    pub schema: Option<Value>,
+
+    /// Minimum number of JSON values the response must contain
+    pub required_elements: usize,
+
+    /// Accept JSON embedded in surrounding text (e.g. code fences)
+    ///
+    /// When false, the whole response must be a single JSON value.
+    pub allow_embedded: bool,
+}
+
+impl Default for JsonOutputConfig {
+    fn default() -> Self {
+        Self {
+            schema: None,
+            required_elements: 0,
+            allow_embedded: true,
+        }
+    }
+}
+
+/// JSON scanner implementation
+///
+/// ## Enterprise Features
+///
+/// - Extracts JSON objects and arrays from free-form responses
+/// - JSON schema validation (draft 4 through 2020-12)
+/// - Reports each violation with its instance path
+/// - Enforces structured-output contracts for agents and tools
+///
+/// ## Example
+///
+/// ```rust,ignore
+/// use llm_shield_scanners::output::{JsonOutput, JsonOutputConfig};
+///
+/// let config = JsonOutputConfig {
+///     schema: Some(serde_json::json!({
+///         "type": "object",
+///         "required": ["name"],
+///     })),
+///     required_elements: 1,
+///     ..Default::default()
+/// };
+/// let scanner = JsonOutput::new(config)?;
+///
+/// let response = r#"Here you go: {"age": 3}"#;
+/// let result = scanner.scan_output("", response, &vault).await?;
+/// assert!(!result.is_valid); // Missing "name"
+/// ```
+pub struct JsonOutput {
+    config: JsonOutputConfig,
+    validator: Option<jsonschema::Validator>,
+}
+
+impl JsonOutput {
+    /// Create a new JSON scanner
+    pub fn new(config: JsonOutputConfig) -> Result<Self> {
+        let validator = config
+            .schema
+            .as_ref()
+            .map(jsonschema::validator_for)
+            .transpose()
+            .map_err(|e| Error::config(format!("Invalid JSON schema: {}", e)))?;
+
+        if !config.allow_embedded && config.required_elements > 1 {
+            return Err(Error::config(
+                "required_elements cannot exceed 1 when allow_embedded is false",
+            ));
+        }
+
+        Ok(Self { config, validator })
+    }
+
+    /// Create with default configuration
+    pub fn default_config() -> Result<Self> {
+        Self::new(JsonOutputConfig::default())
+    }
+
+    /// Find JSON objects and arrays in the response, with their byte spans
+    fn extract_json(&self, text: &str) -> Vec<(usize, usize, Value)> {
+        if !self.config.allow_embedded {
+            return match serde_json::from_str::<Value>(text) {
+                Ok(value) => vec![(0, text.len(), value)],
+                Err(_) => Vec::new(),
+            };
+        }
+
+        let mut found = Vec::new();
+        let mut pos = 0;
+        while let Some(offset) = text[pos..].find(['{', '[']) {
+            let start = pos + offset;
+            let mut values = serde_json::Deserializer::from_str(&text[start..]).into_iter::<Value>();
+            match values.next() {
+                Some(Ok(value)) => {
+                    let end = start + values.byte_offset();
+                    found.push((start, end, value));
+                    pos = end;
+                }
+                _ => pos = start + 1,
+            }
+        }
+        found
+    }
+
+    /// Scan output for JSON constraints
+    pub async fn scan_output(
+        &self,
+        _prompt: &str,
+        output: &str,
+        _vault: &Vault,
+    ) -> Result<ScanResult> {
+        let values = self.extract_json(output);
+
+        let mut result = ScanResult::pass(output.to_string())
+            .with_metadata("json_elements", values.len())
+            .with_metadata("required_elements", self.config.required_elements);
+
+        if values.len() < self.config.required_elements || (!self.config.allow_embedded && values.is_empty()) {
+            let description = format!(
+                "Response contains {} valid JSON value(s), {} required",
+                values.len(),
+                self.config.required_elements.max(1)
+            );
+            result.is_valid = false;
+            result.risk_score = 0.7;
+            result.risk_factors.push(RiskFactor::new(
+                "json_missing",
+                &description,
+                Severity::Medium,
+                0.7,
+            ));
+        }
+
+        if let Some(validator) = &self.validator {
+            for (start, end, value) in &values {
+                for error in validator.iter_errors(value) {
+                    let mut metadata = HashMap::new();
+                    metadata.insert("instance_path".to_string(), error.instance_path.to_string());
+                    metadata.insert("error".to_string(), error.to_string());
+
+                    result.entities.push(Entity {
+                        entity_type: "json_schema_violation".to_string(),
+                        text: output[*start..*end].to_string(),
+                        start: *start,
+                        end: *end,
+                        confidence: 1.0,
+                        metadata,
+                    });
+                }
+            }
+
+            let violations = result.entities.len();
+            if violations > 0 {
+                let description = format!("JSON schema validation failed with {} error(s)", violations);
+                result.is_valid = false;
+                result.risk_score = result.risk_score.max(0.8);
+                result.risk_factors.push(RiskFactor::new(
+                    "json_schema_violation",
+                    &description,
+                    Severity::High,
+                    0.8,
+                ));
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+#[async_trait]
+impl Scanner for JsonOutput {
+    fn name(&self) -> &str {
+        "JSON"
+    }
+
+    async fn scan(&self, input: &str, vault: &Vault) -> Result<ScanResult> {
+        self.scan_output("", input, vault).await
+    }
+
+    fn scanner_type(&self) -> ScannerType {
+        ScannerType::Output
+    }
+
+    fn description(&self) -> &str {
+        "Validates JSON in LLM responses against an optional JSON schema"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn person_schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "name": {"type": "string"},
+                "age": {"type": "integer", "minimum": 0}
+            },
+            "required": ["name"]
+        })
+    }
+
+    #[tokio::test]
+    async fn test_json_embedded_valid() {
+        let scanner = JsonOutput::new(JsonOutputConfig {
+            schema: Some(person_schema()),
+            required_elements: 1,
+            ..Default::default()
+        })
+        .unwrap();
+        let vault = Vault::new();
+
+        let response = "Sure:\n```json\n{\"name\": \"Ada\", \"age\": 36}\n```";
+        let result = scanner.scan_output("", response, &vault).await.unwrap();
+
+        assert!(result.is_valid);
+        assert_eq!(result.metadata["json_elements"], 1);
+    }
+
+    #[tokio::test]
+    async fn test_json_schema_violation() {
+        let scanner = JsonOutput::new(JsonOutputConfig {
+            schema: Some(person_schema()),
+            ..Default::default()
+        })
+        .unwrap();
+        let vault = Vault::new();
+
+        let response = r#"Result: {"age": -1}"#;
+        let result = scanner.scan_output("", response, &vault).await.unwrap();
+
+        assert!(!result.is_valid);
+        assert_eq!(result.entities.len(), 2);
+        assert!(result
+            .entities
+            .iter()
+            .any(|e| e.metadata.get("instance_path").map(String::as_str) == Some("/age")));
+        assert_eq!(&response[result.entities[0].start..result.entities[0].end], r#"{"age": -1}"#);
+    }
+
+    #[tokio::test]
+    async fn test_json_required_elements() {
+        let scanner = JsonOutput::new(JsonOutputConfig {
+            required_elements: 2,
+            ..Default::default()
+        })
+        .unwrap();
+        let vault = Vault::new();
+
+        let result = scanner
+            .scan_output("", "Only one: [1, 2] and {broken", &vault)
+            .await
+            .unwrap();
+
+        assert!(!result.is_valid);
+        assert_eq!(result.metadata["json_elements"], 1);
+    }
+
+    #[tokio::test]
+    async fn test_json_strict_mode() {
+        let scanner = JsonOutput::new(JsonOutputConfig {
+            allow_embedded: false,
+            ..Default::default()
+        })
+        .unwrap();
+        let vault = Vault::new();
+
+        assert!(scanner.scan_output("", r#"{"ok": true}"#, &vault).await.unwrap().is_valid);
+        assert!(!scanner.scan_output("", r#"Here: {"ok": true}"#, &vault).await.unwrap().is_valid);
+    }
+
+    #[test]
+    fn test_json_invalid_schema() {
+        let result = JsonOutput::new(JsonOutputConfig {
+            schema: Some(json!({"type": 12})),
+            ..Default::default()
+        });
+        assert!(result.is_err());
+    }
+}
@@ -18,8 +18,10 @@
 //! - `MaliciousURLs` - Detect phishing/malware URLs
 //! - `ReadingTime` - Validate response length
 //! - `Factuality` - Assess factual confidence
+//! - `FactualConsistency` - Check claims against reference documents (NLI)
 //! - `URLReachability` - Verify URL accessibility
 //! - `RegexOutput` - Custom output patterns
+//! - `JsonOutput` - Validate JSON against a schema
 
 pub mod no_refusal;
 pub mod relevance;
@@ -29,8 +31,10 @@ pub mod bias;
 pub mod malicious_urls;
 pub mod reading_time;
 pub mod factuality;
+pub mod factual_consistency;
 pub mod url_reachability;
 pub mod regex;
+pub mod json;
 
 // Re-exports - Scanner types
 pub use no_refusal::NoRefusal;
@@ -41,17 +45,23 @@ pub use bias::Bias;
 pub use malicious_urls::MaliciousURLs;
 pub use reading_time::ReadingTime;
 pub use factuality::Factuality;
+pub use factual_consistency::FactualConsistency;
 pub use url_reachability::URLReachability;
 pub use regex::RegexOutput;
+pub use json::JsonOutput;
 
 // Re-exports - Configuration types
 pub use no_refusal::NoRefusalConfig;
 pub use relevance::RelevanceConfig;
 pub use sensitive::{SensitiveConfig, SensitiveEntityType};
-pub use ban_topics::{BanTopicsConfig, BannedTopic};
+pub use ban_topics::{BanTopicsConfig, BannedTopic, TopicScorer};
+#[cfg(feature = "ml")]
+pub use ban_topics::{EmbeddingTopicScorer, NliTopicScorer};
 pub use bias::BiasConfig;
 pub use malicious_urls::MaliciousURLsConfig;
 pub use reading_time::ReadingTimeConfig;
 pub use factuality::FactualityConfig;
+pub use factual_consistency::{FactualConsistencyConfig, NliPredictor};
 pub use url_reachability::URLReachabilityConfig;
 pub use regex::{RegexOutputConfig, RegexPattern, MatchMode};
+pub use json::JsonOutputConfig;
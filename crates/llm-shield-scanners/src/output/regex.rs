@@ -276,6 +276,11 @@ struct PatternMatch {
     severity: Severity,
 }
 
+/// Bytes held back when scanning a stream in deny-list mode
+///
+/// Matches longer than this may be split across released chunks.
+const STREAM_LOOKBACK: usize = 256;
+
 #[async_trait]
 impl Scanner for RegexOutput {
     fn name(&self) -> &str {
@@ -293,6 +298,14 @@ impl Scanner for RegexOutput {
     fn description(&self) -> &str {
         "Custom pattern matching for LLM outputs using regular expressions"
     }
+
+    fn stream_lookback(&self) -> Option<usize> {
+        // An allow list can only be judged on the complete output
+        match self.config.match_mode {
+            MatchMode::DenyList => Some(STREAM_LOOKBACK),
+            MatchMode::AllowList => None,
+        }
+    }
 }
 
 #[cfg(test)]
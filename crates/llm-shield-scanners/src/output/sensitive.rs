@@ -469,6 +469,9 @@ struct SensitiveMatch {
     confidence: f32,
 }
 
+/// Bytes held back when scanning a stream
+const STREAM_LOOKBACK: usize = 256;
+
 #[async_trait]
 impl Scanner for Sensitive {
     fn name(&self) -> &str {
@@ -486,6 +489,11 @@ impl Scanner for Sensitive {
     fn description(&self) -> &str {
         "Detects sensitive information in LLM responses (PII, financial data, credentials)"
     }
+
+    fn stream_lookback(&self) -> Option<usize> {
+        // Bounds URLs, the only unbounded pattern
+        Some(STREAM_LOOKBACK)
+    }
 }
 
 #[cfg(test)]
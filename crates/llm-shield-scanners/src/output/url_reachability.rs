@@ -12,8 +12,8 @@
 //! Tests written first drive the implementation.
 
 use llm_shield_core::{
-    async_trait, Entity, Error, Result, RiskFactor, ScanResult, Scanner, ScannerType, Severity,
-    Vault,
+    async_trait, Entity, Error, PerformanceInfo, Result, RiskFactor, ScanResult, Scanner,
+    ScannerType, Severity, Vault,
 };
 use regex::Regex;
 use serde::{Deserialize, Serialize};
@@ -303,6 +303,14 @@ impl Scanner for URLReachability {
     fn description(&self) -> &str {
         "Validates that URLs in LLM responses are reachable"
     }
+
+    fn performance(&self) -> PerformanceInfo {
+        PerformanceInfo {
+            typical_latency_ms: 500,
+            requires_network: true,
+            ..PerformanceInfo::default()
+        }
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,942 @@
+//! Declarative pipeline configuration
+//!
+//! Describes a scanner pipeline in YAML, TOML or JSON so it can be shared by
+//! the SDK, the API server and the language bindings instead of being
+//! assembled in Rust code.
+//!
+//! Each scanner entry names its type and lists its parameters, which are
+//! merged onto the scanner's default configuration (`SecretsConfig`,
+//! `BanTopicsConfig`, ...) and deserialized into it. Scanners run in the
+//! order they are listed unless `order_by_cost` is set.
+//!
+//! ```yaml
+//! version: "2024-06-01"
+//! settings:
+//!   short_circuit_threshold: 0.9
+//!   error_policy: fail_closed
+//! input:
+//!   - type: secrets
+//!     params:
+//!       redact: true
+//!   - type: ban_substrings
+//!     params:
+//!       substrings: ["internal only"]
+//!     timeout_ms: 50
+//! output:
+//!   - type: sensitive
+//! ```
+//!
+//! Long pattern lists (banned substrings, competitor names, secret rules) can
+//! live in separate pattern packs listed under `params_from`. A pack is a
+//! YAML, TOML or JSON mapping of parameters; packs are merged in order and
+//! `params` override them:
+//!
+//! ```yaml
+//! input:
+//!   - type: ban_competitors
+//!     params_from: ["packs/competitors.yaml"]
+//!     params:
+//!       redact: true
+//! ```
+//!
+//! [`PipelineConfig::from_path`] loads packs given as paths relative to the
+//! pipeline file. Packs referenced by URI (e.g. `storage://packs/a.yaml`) are
+//! left for the caller to load with [`PipelineConfig::resolve_params_from`].
+//!
+//! Scanners backed by a model take it from [`PipelineResources`] rather than
+//! from parameters: `factual_consistency` needs an NLI backend and only
+//! builds through [`PipelineConfig::build_with`].
+//!
+//! Errors name the offending entry and field, e.g.
+//! `input[1].params.substrings: invalid type: string "x", expected a sequence`.
+
+use crate::input::{
+    BanCode, BanCodeConfig, BanCompetitors, BanCompetitorsConfig, BanSubstrings,
+    BanSubstringsConfig, Gibberish, GibberishConfig, InvisibleText, InvisibleTextConfig, Language,
+    LanguageConfig, PromptInjection, PromptInjectionConfig, RegexConfig, RegexScanner, Secrets,
+    SecretsConfig, Sentiment, SentimentConfig, TokenLimit, TokenLimitConfig, Toxicity,
+    ToxicityConfig,
+};
+use crate::output::{
+    BanTopics, BanTopicsConfig, Bias, BiasConfig, FactualConsistency, FactualConsistencyConfig,
+    Factuality, FactualityConfig, JsonOutput, JsonOutputConfig, MaliciousURLs, MaliciousURLsConfig,
+    NliPredictor, NoRefusal, NoRefusalConfig, ReadingTime, ReadingTimeConfig, RegexOutput,
+    RegexOutputConfig, Relevance, RelevanceConfig, Sensitive, SensitiveConfig, URLReachability,
+    URLReachabilityConfig,
+};
+use llm_shield_core::{async_trait, Error, ErrorPolicy, NliScores, Result, Scanner, TimeoutPolicy};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::path::Path;
+use std::sync::Arc;
+
+/// Separator marking pattern pack references that are URIs rather than paths
+const URI_SCHEME_SEPARATOR: &str = "://";
+
+/// Input scanner types that can be configured declaratively
+pub const INPUT_SCANNER_TYPES: &[&str] = &[
+    "ban_code",
+    "ban_competitors",
+    "ban_substrings",
+    "gibberish",
+    "invisible_text",
+    "language",
+    "prompt_injection",
+    "regex",
+    "secrets",
+    "sentiment",
+    "token_limit",
+    "toxicity",
+];
+
+/// Output scanner types that can be configured declaratively
+pub const OUTPUT_SCANNER_TYPES: &[&str] = &[
+    "ban_topics",
+    "bias",
+    "factual_consistency",
+    "factuality",
+    "json",
+    "malicious_urls",
+    "no_refusal",
+    "reading_time",
+    "regex",
+    "relevance",
+    "sensitive",
+    "url_reachability",
+];
+
+/// A scanner pipeline described as data
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PipelineConfig {
+    /// Free-form version of this configuration
+    #[serde(default)]
+    pub version: Option<String>,
+
+    /// Execution settings
+    #[serde(default)]
+    pub settings: PipelineSettings,
+
+    /// Scanners run on prompts, in order
+    #[serde(default)]
+    pub input: Vec<ScannerSpec>,
+
+    /// Scanners run on model output, in order
+    #[serde(default)]
+    pub output: Vec<ScannerSpec>,
+}
+
+/// Pipeline execution settings
+///
+/// Unset fields keep the consumer's defaults (e.g. the SDK preset).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PipelineSettings {
+    /// Stop scanning once a scanner's risk score reaches this value
+    pub short_circuit_threshold: Option<f32>,
+
+    /// Feed each scanner the previous scanner's sanitized text
+    pub chain_sanitization: Option<bool>,
+
+    /// Run cheap scanners first instead of in listed order
+    pub order_by_cost: Option<bool>,
+
+    /// Run scanners concurrently
+    pub parallel: Option<bool>,
+
+    /// Maximum concurrent scanners
+    pub max_concurrent: Option<usize>,
+
+    /// What to do when a scanner returns an error
+    pub error_policy: Option<ErrorPolicy>,
+
+    /// Deadline for a whole scan in milliseconds
+    pub timeout_ms: Option<u64>,
+
+    /// Default per-scanner timeout in milliseconds
+    pub scanner_timeout_ms: Option<u64>,
+
+    /// How timed-out scanners are reported
+    pub timeout_policy: Option<TimeoutPolicy>,
+}
+
+/// One scanner in a pipeline
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ScannerSpec {
+    /// Scanner type, e.g. `secrets` or `ban_topics`
+    #[serde(rename = "type")]
+    pub scanner_type: String,
+
+    /// Parameters merged onto the scanner's default configuration
+    #[serde(default)]
+    pub params: Map<String, Value>,
+
+    /// Pattern packs whose parameters are merged under `params`, in order
+    ///
+    /// Entries are paths relative to the pipeline file or URIs such as
+    /// `storage://packs/competitors.yaml`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub params_from: Vec<String>,
+
+    /// Whether the scanner is built (disabled entries are still validated)
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+
+    /// Timeout for this scanner in milliseconds
+    #[serde(default)]
+    pub timeout_ms: Option<u64>,
+
+    /// Policy when this scanner times out
+    #[serde(default)]
+    pub on_timeout: Option<TimeoutPolicy>,
+}
+
+impl ScannerSpec {
+    /// Spec for a scanner type with default parameters
+    pub fn new(scanner_type: impl Into<String>) -> Self {
+        Self {
+            scanner_type: scanner_type.into(),
+            params: Map::new(),
+            params_from: Vec::new(),
+            enabled: true,
+            timeout_ms: None,
+            on_timeout: None,
+        }
+    }
+
+    /// Set a parameter
+    pub fn with_param(mut self, key: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.params.insert(key.into(), value.into());
+        self
+    }
+
+    /// Merge parameters from a pattern pack
+    pub fn with_params_from(mut self, reference: impl Into<String>) -> Self {
+        self.params_from.push(reference.into());
+        self
+    }
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// Timeout override for a built scanner, keyed by its runtime name
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScannerTimeoutSpec {
+    /// Scanner name as reported by [`Scanner::name`]
+    pub scanner: String,
+
+    /// Timeout in milliseconds
+    pub timeout_ms: Option<u64>,
+
+    /// Policy on timeout
+    pub policy: Option<TimeoutPolicy>,
+}
+
+/// Scanners built from a [`PipelineConfig`]
+#[derive(Clone, Default)]
+pub struct BuiltPipeline {
+    /// Enabled input scanners, in listed order
+    pub input: Vec<Arc<dyn Scanner>>,
+
+    /// Enabled output scanners, in listed order
+    pub output: Vec<Arc<dyn Scanner>>,
+
+    /// Per-scanner timeout overrides
+    pub timeouts: Vec<ScannerTimeoutSpec>,
+}
+
+/// Which side of the model a scanner list applies to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Side {
+    Input,
+    Output,
+}
+
+impl Side {
+    fn key(self) -> &'static str {
+        match self {
+            Side::Input => "input",
+            Side::Output => "output",
+        }
+    }
+}
+
+impl PipelineConfig {
+    /// Load a configuration file, choosing the format by extension
+    ///
+    /// `.yaml`/`.yml`, `.toml` and `.json` are supported. Pattern packs given
+    /// as paths are loaded relative to the file's directory.
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path).map_err(|e| {
+            Error::config(format!("Failed to read pipeline config {}: {}", path.display(), e))
+        })?;
+
+        let mut config = Self::from_named_str(&path.display().to_string(), &text)?;
+
+        let base = path.parent().unwrap_or_else(|| Path::new(""));
+        config.resolve_params_from(|reference| {
+            if reference.contains(URI_SCHEME_SEPARATOR) {
+                return Ok(None);
+            }
+            let pack = base.join(reference);
+            let text = std::fs::read_to_string(&pack).map_err(|e| {
+                Error::config(format!("Failed to read pattern pack {}: {}", pack.display(), e))
+            })?;
+            parse_params_pack(reference, &text).map(Some)
+        })?;
+
+        Ok(config)
+    }
+
+    /// Parse a configuration, choosing the format by the extension of `name`
+    ///
+    /// `name` is the file name or storage key the text was read from.
+    pub fn from_named_str(name: &str, text: &str) -> Result<Self> {
+        let config = match format_of(name)? {
+            Format::Yaml => Self::from_yaml_str(text),
+            Format::Toml => Self::from_toml_str(text),
+            Format::Json => Self::from_json_str(text),
+        };
+
+        config.map_err(|e| Error::config(format!("{}: {}", name, e)))
+    }
+
+    /// Parse a YAML configuration
+    pub fn from_yaml_str(text: &str) -> Result<Self> {
+        serde_path_to_error::deserialize(serde_yaml::Deserializer::from_str(text))
+            .map_err(path_error)
+    }
+
+    /// Parse a TOML configuration
+    pub fn from_toml_str(text: &str) -> Result<Self> {
+        serde_path_to_error::deserialize(toml::Deserializer::new(text)).map_err(path_error)
+    }
+
+    /// Parse a JSON configuration
+    pub fn from_json_str(text: &str) -> Result<Self> {
+        serde_path_to_error::deserialize(&mut serde_json::Deserializer::from_str(text))
+            .map_err(path_error)
+    }
+
+    /// Pattern pack references that are not loaded yet, without duplicates
+    pub fn pending_params_from(&self) -> Vec<&str> {
+        let mut references: Vec<&str> = Vec::new();
+        for spec in self.input.iter().chain(&self.output) {
+            for reference in &spec.params_from {
+                if !references.contains(&reference.as_str()) {
+                    references.push(reference);
+                }
+            }
+        }
+        references
+    }
+
+    /// Merge pattern packs into scanner parameters
+    ///
+    /// `load` returns a pack's parameters, or `None` to leave the reference
+    /// for a later call. Loaded references are removed from `params_from`.
+    pub fn resolve_params_from<F>(&mut self, mut load: F) -> Result<()>
+    where
+        F: FnMut(&str) -> Result<Option<Map<String, Value>>>,
+    {
+        for (side, specs) in [(Side::Input, &mut self.input), (Side::Output, &mut self.output)] {
+            for (index, spec) in specs.iter_mut().enumerate() {
+                if spec.params_from.is_empty() {
+                    continue;
+                }
+
+                let mut merged = Map::new();
+                let mut pending = Vec::new();
+                for (pack_index, reference) in spec.params_from.iter().enumerate() {
+                    let pack = load(reference).map_err(|e| {
+                        Error::config(format!(
+                            "{}[{}].params_from[{}]: {}",
+                            side.key(),
+                            index,
+                            pack_index,
+                            e
+                        ))
+                    })?;
+                    match pack {
+                        Some(pack) => merged.extend(pack),
+                        None => pending.push(reference.clone()),
+                    }
+                }
+
+                // Inline parameters take precedence over packs
+                merged.extend(std::mem::take(&mut spec.params));
+                spec.params = merged;
+                spec.params_from = pending;
+            }
+        }
+        Ok(())
+    }
+
+    /// String values of scanner parameters, by path such as
+    /// `input[0].params.api_key`
+    ///
+    /// Values nested in arrays and objects are included, e.g.
+    /// `output[1].params.headers.authorization`.
+    pub fn string_params_mut(&mut self) -> Vec<(String, &mut String)> {
+        let mut fields = Vec::new();
+        for (side, specs) in [(Side::Input, &mut self.input), (Side::Output, &mut self.output)] {
+            for (index, spec) in specs.iter_mut().enumerate() {
+                for (key, value) in spec.params.iter_mut() {
+                    let path = format!("{}[{}].params.{}", side.key(), index, key);
+                    collect_strings(path, value, &mut fields);
+                }
+            }
+        }
+        fields
+    }
+
+    /// Check settings and build every scanner, without keeping them
+    ///
+    /// Model-backed scanners are checked against a placeholder model, so a
+    /// pipeline validates before its models are loaded.
+    pub fn validate(&self) -> Result<()> {
+        let placeholder = PipelineResources::new().with_nli(Arc::new(UnloadedNli));
+        self.build_with(&placeholder).map(|_| ())
+    }
+
+    /// Build the enabled scanners
+    ///
+    /// Disabled scanners are still built, so a configuration that validates
+    /// keeps validating when they are switched on. Pipelines with
+    /// model-backed scanners need [`build_with`](Self::build_with).
+    pub fn build(&self) -> Result<BuiltPipeline> {
+        self.build_with(&PipelineResources::default())
+    }
+
+    /// Build the enabled scanners, taking models from `resources`
+    pub fn build_with(&self, resources: &PipelineResources) -> Result<BuiltPipeline> {
+        self.settings.validate()?;
+
+        let mut built = BuiltPipeline::default();
+        for (side, specs) in [(Side::Input, &self.input), (Side::Output, &self.output)] {
+            for (index, spec) in specs.iter().enumerate() {
+                let path = format!("{}[{}]", side.key(), index);
+                if let Some(reference) = spec.params_from.first() {
+                    return Err(Error::config(format!(
+                        "{}.params_from: pattern pack '{}' has not been loaded",
+                        path, reference
+                    )));
+                }
+                let scanner = create_scanner(side, spec, &path, resources)?;
+                if !spec.enabled {
+                    continue;
+                }
+
+                if spec.timeout_ms.is_some() || spec.on_timeout.is_some() {
+                    built.timeouts.push(ScannerTimeoutSpec {
+                        scanner: scanner.name().to_string(),
+                        timeout_ms: spec.timeout_ms,
+                        policy: spec.on_timeout,
+                    });
+                }
+                match side {
+                    Side::Input => built.input.push(scanner),
+                    Side::Output => built.output.push(scanner),
+                }
+            }
+        }
+
+        Ok(built)
+    }
+}
+
+/// Models shared by the scanners of a pipeline
+#[derive(Clone, Default)]
+pub struct PipelineResources {
+    /// NLI backend for `factual_consistency`
+    pub nli: Option<Arc<dyn NliPredictor>>,
+}
+
+impl PipelineResources {
+    /// Create empty resources
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the NLI backend
+    pub fn with_nli(mut self, nli: Arc<dyn NliPredictor>) -> Self {
+        self.nli = Some(nli);
+        self
+    }
+}
+
+impl std::fmt::Debug for PipelineResources {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PipelineResources")
+            .field("nli", &self.nli.is_some())
+            .finish()
+    }
+}
+
+/// Stands in for the NLI model while validating; never asked to predict
+struct UnloadedNli;
+
+#[async_trait]
+impl NliPredictor for UnloadedNli {
+    async fn predict(&self, _premise: &str, _hypothesis: &str) -> Result<NliScores> {
+        Err(Error::model("NLI model is not loaded"))
+    }
+}
+
+impl PipelineSettings {
+    /// Validate setting ranges
+    pub fn validate(&self) -> Result<()> {
+        if let Some(threshold) = self.short_circuit_threshold {
+            if !(0.0..=1.0).contains(&threshold) {
+                return Err(Error::config(
+                    "settings.short_circuit_threshold: must be between 0.0 and 1.0",
+                ));
+            }
+        }
+        if self.max_concurrent == Some(0) {
+            return Err(Error::config(
+                "settings.max_concurrent: must be greater than 0",
+            ));
+        }
+        if self.timeout_ms == Some(0) || self.scanner_timeout_ms == Some(0) {
+            return Err(Error::config("settings: timeouts must be greater than 0"));
+        }
+        Ok(())
+    }
+}
+
+/// Parse a pattern pack, choosing the format by the extension of `name`
+///
+/// A pack is a mapping of scanner parameters, e.g.
+/// `competitors: ["Acme", "Globex"]`.
+pub fn parse_params_pack(name: &str, text: &str) -> Result<Map<String, Value>> {
+    let pack = match format_of(name)? {
+        Format::Yaml => serde_yaml::from_str(text).map_err(|e| e.to_string()),
+        Format::Toml => toml::from_str(text).map_err(|e| e.to_string()),
+        Format::Json => serde_json::from_str(text).map_err(|e| e.to_string()),
+    };
+
+    pack.map_err(|e| Error::config(format!("{}: expected a mapping of parameters: {}", name, e)))
+}
+
+/// Configuration file format
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Yaml,
+    Toml,
+    Json,
+}
+
+fn format_of(name: &str) -> Result<Format> {
+    let extension = Path::new(name)
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_ascii_lowercase);
+    match extension.as_deref() {
+        Some("yaml") | Some("yml") => Ok(Format::Yaml),
+        Some("toml") => Ok(Format::Toml),
+        Some("json") => Ok(Format::Json),
+        _ => Err(Error::config(format!(
+            "Unsupported config format: {} (expected .yaml, .yml, .toml or .json)",
+            name
+        ))),
+    }
+}
+
+fn collect_strings<'a>(
+    path: String,
+    value: &'a mut Value,
+    fields: &mut Vec<(String, &'a mut String)>,
+) {
+    match value {
+        Value::String(text) => fields.push((path, text)),
+        Value::Array(items) => {
+            for (index, item) in items.iter_mut().enumerate() {
+                collect_strings(format!("{}[{}]", path, index), item, fields);
+            }
+        }
+        Value::Object(map) => {
+            for (key, item) in map.iter_mut() {
+                collect_strings(format!("{}.{}", path, key), item, fields);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn path_error<E: std::fmt::Display>(error: serde_path_to_error::Error<E>) -> Error {
+    let path = error.path().to_string();
+    if path == "." {
+        Error::config(error.into_inner().to_string())
+    } else {
+        Error::config(format!("{}: {}", path, error.into_inner()))
+    }
+}
+
+/// Build one scanner from its spec
+fn create_scanner(
+    side: Side,
+    spec: &ScannerSpec,
+    path: &str,
+    resources: &PipelineResources,
+) -> Result<Arc<dyn Scanner>> {
+    let params = &spec.params;
+    match (side, spec.scanner_type.as_str()) {
+        (Side::Input, "ban_code") => build::<BanCodeConfig, _>(params, path, BanCode::new),
+        (Side::Input, "ban_competitors") => {
+            build::<BanCompetitorsConfig, _>(params, path, BanCompetitors::new)
+        }
+        (Side::Input, "ban_substrings") => {
+            build::<BanSubstringsConfig, _>(params, path, BanSubstrings::new)
+        }
+        (Side::Input, "gibberish") => build::<GibberishConfig, _>(params, path, Gibberish::new),
+        (Side::Input, "invisible_text") => {
+            build::<InvisibleTextConfig, _>(params, path, InvisibleText::new)
+        }
+        (Side::Input, "language") => build::<LanguageConfig, _>(params, path, Language::new),
+        (Side::Input, "prompt_injection") => {
+            build::<PromptInjectionConfig, _>(params, path, PromptInjection::new)
+        }
+        (Side::Input, "regex") => build::<RegexConfig, _>(params, path, RegexScanner::new),
+        (Side::Input, "secrets") => build::<SecretsConfig, _>(params, path, Secrets::new),
+        (Side::Input, "sentiment") => build::<SentimentConfig, _>(params, path, Sentiment::new),
+        (Side::Input, "token_limit") => build::<TokenLimitConfig, _>(params, path, TokenLimit::new),
+        (Side::Input, "toxicity") => build::<ToxicityConfig, _>(params, path, Toxicity::new),
+
+        (Side::Output, "ban_topics") => build::<BanTopicsConfig, _>(params, path, BanTopics::new),
+        (Side::Output, "bias") => build::<BiasConfig, _>(params, path, Bias::new),
+        (Side::Output, "factual_consistency") => {
+            let nli = resources.nli.clone().ok_or_else(|| {
+                Error::config(format!(
+                    "{}.type: factual_consistency needs an NLI model (PipelineResources::with_nli)",
+                    path
+                ))
+            })?;
+            build::<FactualConsistencyConfig, _>(params, path, |config| {
+                FactualConsistency::new(config, nli)
+            })
+        }
+        (Side::Output, "factuality") => build::<FactualityConfig, _>(params, path, Factuality::new),
+        (Side::Output, "json") => build::<JsonOutputConfig, _>(params, path, JsonOutput::new),
+        (Side::Output, "malicious_urls") => {
+            build::<MaliciousURLsConfig, _>(params, path, MaliciousURLs::new)
+        }
+        (Side::Output, "no_refusal") => build::<NoRefusalConfig, _>(params, path, NoRefusal::new),
+        (Side::Output, "reading_time") => {
+            build::<ReadingTimeConfig, _>(params, path, ReadingTime::new)
+        }
+        (Side::Output, "regex") => build::<RegexOutputConfig, _>(params, path, RegexOutput::new),
+        (Side::Output, "relevance") => build::<RelevanceConfig, _>(params, path, Relevance::new),
+        (Side::Output, "sensitive") => build::<SensitiveConfig, _>(params, path, Sensitive::new),
+        (Side::Output, "url_reachability") => {
+            build::<URLReachabilityConfig, _>(params, path, URLReachability::new)
+        }
+
+        (side, other) => {
+            let known = match side {
+                Side::Input => INPUT_SCANNER_TYPES,
+                Side::Output => OUTPUT_SCANNER_TYPES,
+            };
+            Err(Error::config(format!(
+                "{}.type: unknown {} scanner '{}', expected one of: {}",
+                path,
+                side.key(),
+                other,
+                known.join(", ")
+            )))
+        }
+    }
+}
+
+/// Deserialize `params` over the default `C` and construct the scanner
+fn build<C, S>(
+    params: &Map<String, Value>,
+    path: &str,
+    constructor: impl FnOnce(C) -> Result<S>,
+) -> Result<Arc<dyn Scanner>>
+where
+    C: Default + Serialize + DeserializeOwned,
+    S: Scanner + 'static,
+{
+    let mut value = serde_json::to_value(C::default())
+        .map_err(|e| Error::config(format!("{}: {}", path, e)))?;
+
+    if let Value::Object(defaults) = &mut value {
+        for (key, param) in params {
+            if !defaults.contains_key(key) {
+                let mut known: Vec<_> = defaults.keys().map(String::as_str).collect();
+                known.sort_unstable();
+                return Err(Error::config(format!(
+                    "{}.params.{}: unknown parameter, expected one of: {}",
+                    path,
+                    key,
+                    known.join(", ")
+                )));
+            }
+            defaults.insert(key.clone(), param.clone());
+        }
+    }
+
+    let config: C = serde_path_to_error::deserialize(value).map_err(|e| {
+        Error::config(format!("{}.params.{}: {}", path, e.path(), e.inner()))
+    })?;
+
+    let scanner = constructor(config).map_err(|e| Error::config(format!("{}: {}", path, e)))?;
+    Ok(Arc::new(scanner))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const YAML: &str = r#"
+version: "7"
+settings:
+  short_circuit_threshold: 0.8
+  error_policy: fail_closed
+input:
+  - type: ban_substrings
+    params:
+      substrings: ["project falcon"]
+    timeout_ms: 50
+  - type: secrets
+    enabled: false
+output:
+  - type: json
+    params:
+      required_elements: 1
+"#;
+
+    #[test]
+    fn test_yaml_builds_scanners_in_order() {
+        let config = PipelineConfig::from_yaml_str(YAML).unwrap();
+        assert_eq!(config.version.as_deref(), Some("7"));
+        assert_eq!(config.settings.error_policy, Some(ErrorPolicy::FailClosed));
+
+        let built = config.build().unwrap();
+        assert_eq!(built.input.len(), 1);
+        assert_eq!(built.output.len(), 1);
+        assert_eq!(built.output[0].name(), "JSON");
+        assert_eq!(built.timeouts[0].timeout_ms, Some(50));
+        assert_eq!(built.timeouts[0].scanner, built.input[0].name());
+    }
+
+    #[test]
+    fn test_toml_matches_yaml() {
+        let toml = r#"
+version = "7"
+
+[settings]
+short_circuit_threshold = 0.8
+error_policy = "fail_closed"
+
+[[input]]
+type = "ban_substrings"
+timeout_ms = 50
+params = { substrings = ["project falcon"] }
+
+[[input]]
+type = "secrets"
+enabled = false
+
+[[output]]
+type = "json"
+params = { required_elements = 1 }
+"#;
+        assert_eq!(
+            PipelineConfig::from_toml_str(toml).unwrap(),
+            PipelineConfig::from_yaml_str(YAML).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_errors_name_the_field() {
+        let err = PipelineConfig::from_yaml_str("input:\n  - type: secrets\n    timeout: 5\n")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("input[0]"), "{}", err);
+
+        let config = PipelineConfig {
+            input: vec![
+                ScannerSpec::new("secrets"),
+                ScannerSpec::new("ban_substrings").with_param("substrings", "not a list"),
+            ],
+            ..Default::default()
+        };
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("input[1].params.substrings"), "{}", err);
+
+        let config = PipelineConfig {
+            output: vec![ScannerSpec::new("secrets")],
+            ..Default::default()
+        };
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("output[0].type: unknown output scanner 'secrets'"), "{}", err);
+
+        let config = PipelineConfig {
+            input: vec![ScannerSpec::new("secrets").with_param("redact_secrets", true)],
+            ..Default::default()
+        };
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("input[0].params.redact_secrets: unknown parameter"), "{}", err);
+    }
+
+    #[test]
+    fn test_invalid_settings() {
+        let config = PipelineConfig::from_json_str(r#"{"settings": {"short_circuit_threshold": 2.0}}"#)
+            .unwrap();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_params_from_packs() {
+        let mut config = PipelineConfig {
+            input: vec![ScannerSpec::new("ban_competitors")
+                .with_params_from("packs/competitors.yaml")
+                .with_params_from("storage://packs/extra.json")
+                .with_param("redact", true)],
+            ..Default::default()
+        };
+        assert_eq!(
+            config.pending_params_from(),
+            vec!["packs/competitors.yaml", "storage://packs/extra.json"]
+        );
+
+        // Unloaded packs fail the build instead of being skipped
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("input[0].params_from"), "{}", err);
+
+        config
+            .resolve_params_from(|reference| match reference {
+                "packs/competitors.yaml" => parse_params_pack(
+                    reference,
+                    "competitors: [Acme, Globex]\nredact: false\n",
+                )
+                .map(Some),
+                _ => Ok(None),
+            })
+            .unwrap();
+        assert_eq!(config.pending_params_from(), vec!["storage://packs/extra.json"]);
+
+        config
+            .resolve_params_from(|reference| {
+                parse_params_pack(reference, r#"{"case_sensitive": true}"#).map(Some)
+            })
+            .unwrap();
+        let params = &config.input[0].params;
+        assert_eq!(params["competitors"], serde_json::json!(["Acme", "Globex"]));
+        assert_eq!(params["case_sensitive"], true);
+        // Inline parameters win over packs
+        assert_eq!(params["redact"], true);
+        assert!(config.validate().is_ok());
+
+        assert!(parse_params_pack("pack.yaml", "- a\n- b\n").is_err());
+        assert!(parse_params_pack("pack.txt", "a").is_err());
+    }
+
+    #[test]
+    fn test_string_params_by_path() {
+        let mut config = PipelineConfig::from_yaml_str(
+            "input:\n  - type: ban_substrings\n    params:\n      substrings: [a, b]\n      redact: true\noutput:\n  - type: json\n    params:\n      extra:\n        token: t\n",
+        )
+        .unwrap();
+
+        let mut fields = config.string_params_mut();
+        let paths: Vec<&str> = fields.iter().map(|(path, _)| path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                "input[0].params.substrings[0]",
+                "input[0].params.substrings[1]",
+                "output[0].params.extra.token",
+            ]
+        );
+
+        *fields[2].1 = "resolved".to_string();
+        assert_eq!(config.output[0].params["extra"]["token"], "resolved");
+    }
+
+    #[test]
+    fn test_from_path_loads_relative_packs() {
+        let dir = std::env::temp_dir().join(format!("pipeline-packs-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("packs")).unwrap();
+        std::fs::write(
+            dir.join("packs").join("substrings.toml"),
+            "substrings = [\"project falcon\"]\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("pipeline.yaml"),
+            "input:\n  - type: ban_substrings\n    params_from: [packs/substrings.toml]\n  - type: secrets\n    params_from: [missing.yaml]\n",
+        )
+        .unwrap();
+
+        let err = PipelineConfig::from_path(dir.join("pipeline.yaml"))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("input[1].params_from[0]"), "{}", err);
+
+        std::fs::write(
+            dir.join("pipeline.yaml"),
+            "input:\n  - type: ban_substrings\n    params_from: [packs/substrings.toml]\n",
+        )
+        .unwrap();
+        let config = PipelineConfig::from_path(dir.join("pipeline.yaml")).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert!(config.input[0].params_from.is_empty());
+        assert_eq!(config.input[0].params["substrings"], serde_json::json!(["project falcon"]));
+        assert_eq!(config.build().unwrap().input.len(), 1);
+    }
+
+    /// Contradicts any hypothesis mentioning Rome, entails the rest
+    struct RomeNli;
+
+    #[async_trait]
+    impl NliPredictor for RomeNli {
+        async fn predict(&self, _premise: &str, hypothesis: &str) -> Result<NliScores> {
+            let contradiction = if hypothesis.contains("Rome") { 0.9 } else { 0.0 };
+            Ok(NliScores {
+                entailment: 0.9 - contradiction,
+                neutral: 0.1,
+                contradiction,
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_factual_consistency_needs_nli() {
+        let config = PipelineConfig::from_yaml_str(
+            "output:\n  - type: factual_consistency\n    params:\n      flag_unsupported: false\n",
+        )
+        .unwrap();
+
+        // Validates before the model is loaded, but only builds with one
+        config.validate().unwrap();
+        let err = config.build().err().unwrap().to_string();
+        assert!(err.contains("output[0].type"), "{}", err);
+
+        let built = config
+            .build_with(&PipelineResources::new().with_nli(Arc::new(RomeNli)))
+            .unwrap();
+        assert_eq!(built.output[0].name(), "FactualConsistency");
+
+        let vault = llm_shield_core::Vault::new();
+        vault
+            .set(
+                crate::output::factual_consistency::DEFAULT_REFERENCE_VAULT_KEY,
+                vec!["The Eiffel Tower is in Paris.".to_string()],
+            )
+            .unwrap();
+        let result = built.output[0]
+            .scan("The Eiffel Tower is in Rome.", &vault)
+            .await
+            .unwrap();
+        assert!(!result.is_valid);
+    }
+}
@@ -9,12 +9,16 @@
 //! - Clear error messages for invalid configurations
 //! - Support for presets and custom configurations
 
-use crate::config::{ParallelConfig, ScanMode, ShieldConfig};
+use crate::config::{ParallelConfig, ScanMode, ScannerTimeout, ShieldConfig};
 use crate::error::{SdkError, SdkResult};
+use crate::integrations::policy_integration::PolicyIntegrationAdapter;
+use crate::integrations::runtime_hooks::RuntimeHooks;
 use crate::preset::Preset;
 use crate::scanner_factory::{InputScannerFactory, OutputScannerFactory};
 use crate::shield::Shield;
-use llm_shield_core::Scanner;
+use llm_shield_core::{ErrorPolicy, PolicyAdapter, PolicyEvaluator, Scanner, TimeoutPolicy};
+use llm_shield_scanners::PipelineConfig;
+use std::path::Path;
 use std::sync::Arc;
 
 /// Builder for constructing Shield instances
@@ -34,6 +38,8 @@ pub struct ShieldBuilder {
     input_scanners: Vec<Arc<dyn Scanner>>,
     output_scanners: Vec<Arc<dyn Scanner>>,
     preset: Option<Preset>,
+    hooks: Option<RuntimeHooks>,
+    policy: Option<Arc<dyn PolicyIntegrationAdapter>>,
 }
 
 impl ShieldBuilder {
@@ -44,6 +50,8 @@ impl ShieldBuilder {
             input_scanners: Vec::new(),
             output_scanners: Vec::new(),
             preset: None,
+            hooks: None,
+            policy: None,
         }
     }
 
@@ -181,6 +189,35 @@ impl ShieldBuilder {
         self
     }
 
+    /// Enable/disable chained sanitization
+    ///
+    /// When enabled, each scanner receives the previous scanner's sanitized
+    /// text, so redactions from every scanner are kept. Scanners then run
+    /// sequentially even if parallel execution is enabled.
+    pub fn with_chained_sanitization(mut self, enabled: bool) -> Self {
+        self.config.chain_sanitization = enabled;
+        self
+    }
+
+    /// Enable/disable running cheap scanners first
+    ///
+    /// Scanners are ordered by their performance characteristics (local
+    /// before networked, pattern-based before ML). With short-circuit
+    /// enabled, expensive scanners may then be skipped entirely.
+    pub fn with_cost_ordering(mut self, enabled: bool) -> Self {
+        self.config.order_by_cost = enabled;
+        self
+    }
+
+    /// Set what happens when a scanner returns an error
+    ///
+    /// `Propagate` (the default) fails the whole scan; `FailOpen` and
+    /// `FailClosed` isolate the error to that scanner.
+    pub fn with_error_policy(mut self, policy: ErrorPolicy) -> Self {
+        self.config.error_policy = policy;
+        self
+    }
+
     /// Set operation timeout in milliseconds
     pub fn with_timeout_ms(mut self, timeout_ms: u64) -> Self {
         self.config.timeout_ms = Some(timeout_ms);
@@ -193,6 +230,43 @@ impl ShieldBuilder {
         self
     }
 
+    /// Set the default per-scanner timeout in milliseconds
+    pub fn with_scanner_timeout_ms(mut self, timeout_ms: u64) -> Self {
+        self.config.scanner_timeout_ms = Some(timeout_ms);
+        self
+    }
+
+    /// Set how timed-out scanners are reported
+    ///
+    /// `FailClosed` (the default) marks the scan invalid with maximum risk;
+    /// `FailOpen` treats the scanner as passed.
+    pub fn with_timeout_policy(mut self, policy: TimeoutPolicy) -> Self {
+        self.config.timeout_policy = policy;
+        self
+    }
+
+    /// Override the timeout and/or policy for one scanner
+    ///
+    /// ## Example
+    ///
+    /// ```rust,ignore
+    /// let shield = Shield::builder()
+    ///     .with_preset(Preset::Standard)
+    ///     .with_scanner_timeout("url_reachability", Some(2_000), Some(TimeoutPolicy::FailOpen))
+    ///     .build()?;
+    /// ```
+    pub fn with_scanner_timeout(
+        mut self,
+        scanner: impl Into<String>,
+        timeout_ms: Option<u64>,
+        policy: Option<TimeoutPolicy>,
+    ) -> Self {
+        self.config
+            .scanner_timeouts
+            .insert(scanner.into(), ScannerTimeout { timeout_ms, policy });
+        self
+    }
+
     // ========================================================================
     // Observability Configuration
     // ========================================================================
@@ -230,6 +304,40 @@ impl ShieldBuilder {
         self
     }
 
+    // ========================================================================
+    // Integrations
+    // ========================================================================
+
+    /// Run runtime hooks around every scan
+    ///
+    /// Pre-scan hooks can approve or reject content without scanning, or
+    /// adjust the risk score; post-scan hooks can adjust the final result.
+    ///
+    /// ## Example
+    ///
+    /// ```rust,ignore
+    /// let hooks = RuntimeHooks::new()
+    ///     .with_pre_scan_hook(Arc::new(PolicyPreCheckHook::new("prompt")));
+    ///
+    /// let shield = Shield::builder()
+    ///     .with_preset(Preset::Standard)
+    ///     .with_hooks(hooks)
+    ///     .build()?;
+    /// ```
+    pub fn with_hooks(mut self, hooks: RuntimeHooks) -> Self {
+        self.hooks = Some(hooks);
+        self
+    }
+
+    /// Apply a policy decision to every scan result
+    ///
+    /// The adapter's default context is used, with a `scan_type` attribute
+    /// of `"prompt"` or `"output"`.
+    pub fn with_policy<E: PolicyEvaluator + 'static>(mut self, policy: PolicyAdapter<E>) -> Self {
+        self.policy = Some(Arc::new(policy));
+        self
+    }
+
     // ========================================================================
     // Full Configuration
     // ========================================================================
@@ -240,6 +348,72 @@ impl ShieldBuilder {
         self
     }
 
+    /// Create a builder from a declarative pipeline file
+    ///
+    /// The format is chosen by extension: `.yaml`/`.yml`, `.toml` or `.json`.
+    ///
+    /// ## Example
+    ///
+    /// ```rust,ignore
+    /// let shield = ShieldBuilder::from_file("shield.yaml")?
+    ///     .with_tracing(true)
+    ///     .build()?;
+    /// ```
+    pub fn from_file(path: impl AsRef<Path>) -> SdkResult<Self> {
+        let config = PipelineConfig::from_path(path)?;
+        Self::new().with_pipeline_config(&config)
+    }
+
+    /// Add the scanners and settings of a declarative pipeline
+    ///
+    /// Scanners are appended to any already added; settings present in the
+    /// pipeline override the current configuration.
+    pub fn with_pipeline_config(mut self, pipeline: &PipelineConfig) -> SdkResult<Self> {
+        let built = pipeline.build()?;
+        self.input_scanners.extend(built.input);
+        self.output_scanners.extend(built.output);
+
+        let settings = &pipeline.settings;
+        if let Some(threshold) = settings.short_circuit_threshold {
+            self.config.short_circuit_threshold = Some(threshold);
+        }
+        if let Some(enabled) = settings.chain_sanitization {
+            self.config.chain_sanitization = enabled;
+        }
+        if let Some(enabled) = settings.order_by_cost {
+            self.config.order_by_cost = enabled;
+        }
+        if let Some(enabled) = settings.parallel {
+            self.config.parallel.enabled = enabled;
+        }
+        if let Some(max) = settings.max_concurrent {
+            self.config.parallel.max_concurrent = max;
+        }
+        if let Some(policy) = settings.error_policy {
+            self.config.error_policy = policy;
+        }
+        if let Some(timeout_ms) = settings.timeout_ms {
+            self.config.timeout_ms = Some(timeout_ms);
+        }
+        if let Some(timeout_ms) = settings.scanner_timeout_ms {
+            self.config.scanner_timeout_ms = Some(timeout_ms);
+        }
+        if let Some(policy) = settings.timeout_policy {
+            self.config.timeout_policy = policy;
+        }
+        for timeout in built.timeouts {
+            self.config.scanner_timeouts.insert(
+                timeout.scanner,
+                ScannerTimeout {
+                    timeout_ms: timeout.timeout_ms,
+                    policy: timeout.policy,
+                },
+            );
+        }
+
+        Ok(self)
+    }
+
     // ========================================================================
     // Build
     // ========================================================================
@@ -270,11 +444,12 @@ impl ShieldBuilder {
         // Validate configuration
         self.validate()?;
 
-        Ok(Shield::new(
+        Shield::new(
             self.config,
             self.input_scanners,
             self.output_scanners,
-        ))
+        )
+        .with_integrations(self.hooks, self.policy)
     }
 
     /// Load scanners from a preset
@@ -374,6 +549,32 @@ mod tests {
         assert_eq!(builder.config.short_circuit_threshold, Some(0.9));
     }
 
+    #[test]
+    fn test_builder_with_chained_sanitization() {
+        let builder = ShieldBuilder::new().with_chained_sanitization(true);
+        assert!(builder.config.chain_sanitization);
+    }
+
+    #[test]
+    fn test_builder_with_timeouts() {
+        let builder = ShieldBuilder::new()
+            .with_scanner_timeout_ms(500)
+            .with_timeout_policy(TimeoutPolicy::FailOpen)
+            .with_scanner_timeout("secrets", Some(50), None);
+        assert_eq!(builder.config.scanner_timeout_ms, Some(500));
+        assert_eq!(builder.config.timeout_policy, TimeoutPolicy::FailOpen);
+        assert_eq!(builder.config.scanner_timeouts["secrets"].timeout_ms, Some(50));
+    }
+
+    #[test]
+    fn test_builder_with_error_policy() {
+        let builder = ShieldBuilder::new()
+            .with_cost_ordering(true)
+            .with_error_policy(ErrorPolicy::FailClosed);
+        assert!(builder.config.order_by_cost);
+        assert_eq!(builder.config.error_policy, ErrorPolicy::FailClosed);
+    }
+
     #[test]
     fn test_builder_with_parallel() {
         let builder = ShieldBuilder::new()
@@ -405,4 +606,45 @@ mod tests {
         let shield = builder.build();
         assert!(shield.is_ok());
     }
+
+    #[test]
+    fn test_builder_with_pipeline_config() {
+        let pipeline = PipelineConfig::from_yaml_str(
+            r#"
+settings:
+  short_circuit_threshold: 0.8
+  parallel: false
+input:
+  - type: secrets
+    timeout_ms: 50
+  - type: token_limit
+    params:
+      limit: 512
+output:
+  - type: sensitive
+    enabled: false
+"#,
+        )
+        .unwrap();
+
+        let builder = ShieldBuilder::new().with_pipeline_config(&pipeline).unwrap();
+        assert_eq!(builder.input_scanners.len(), 2);
+        assert!(builder.output_scanners.is_empty());
+        assert_eq!(builder.config.short_circuit_threshold, Some(0.8));
+        assert!(!builder.config.parallel.enabled);
+        assert_eq!(builder.config.scanner_timeouts.len(), 1);
+        assert!(builder.build().is_ok());
+    }
+
+    #[test]
+    fn test_builder_from_file() {
+        let path = std::env::temp_dir().join(format!("shield-pipeline-{}.toml", std::process::id()));
+        std::fs::write(&path, "[[input]]\ntype = \"invisible_text\"\n").unwrap();
+
+        let builder = ShieldBuilder::from_file(&path);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(builder.unwrap().input_scanners.len(), 1);
+
+        assert!(ShieldBuilder::from_file(std::env::temp_dir().join("missing-shield.yaml")).is_err());
+    }
 }
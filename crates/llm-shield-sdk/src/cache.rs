@@ -0,0 +1,159 @@
+//! # Scan Result Cache
+//!
+//! In-memory TTL cache for pipeline results, enabled by
+//! `ShieldConfig::enable_caching`.
+//!
+//! Only the scanner pipeline's output is cached. Hooks and policy decisions
+//! are re-applied on every call, so a policy change takes effect immediately
+//! even for cached content.
+//!
+//! Entries are keyed by the scanner set that produced them, so results from
+//! a replaced set are never served. Scans with a scanner that uses the
+//! request's vault ([`Scanner::uses_vault`](llm_shield_core::Scanner::uses_vault))
+//! bypass the cache entirely, since a cache hit would skip the vault writes
+//! and ignore per-request state.
+
+use llm_shield_core::ScanResult;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Default maximum number of cached results
+pub(crate) const DEFAULT_CAPACITY: usize = 10_000;
+
+/// TTL cache keyed by scanner set, scan type and input text
+pub(crate) struct ResultCache {
+    ttl: Duration,
+    capacity: usize,
+    entries: Mutex<HashMap<CacheKey, CacheEntry>>,
+}
+
+/// Scanner set ID, scan type and input text
+type CacheKey = (u64, String, String);
+
+struct CacheEntry {
+    inserted_at: Instant,
+    result: ScanResult,
+}
+
+impl ResultCache {
+    /// Create a cache whose entries expire after `ttl`
+    pub(crate) fn new(ttl: Duration, capacity: usize) -> Self {
+        Self {
+            ttl,
+            capacity: capacity.max(1),
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Look up a fresh result produced by scanner set `set_id`
+    pub(crate) fn get(&self, set_id: u64, scan_type: &str, input: &str) -> Option<ScanResult> {
+        let mut entries = self.entries.lock().ok()?;
+        let key = (set_id, scan_type.to_string(), input.to_string());
+
+        match entries.get(&key) {
+            Some(entry) if entry.inserted_at.elapsed() < self.ttl => Some(entry.result.clone()),
+            Some(_) => {
+                entries.remove(&key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// Store a result produced by scanner set `set_id`
+    ///
+    /// Results with timed-out or failed scanners are not cached, since they
+    /// reflect a transient condition rather than the content.
+    pub(crate) fn insert(&self, set_id: u64, scan_type: &str, input: &str, result: &ScanResult) {
+        if result.metadata.contains_key(ScanResult::TIMED_OUT_SCANNERS)
+            || result.metadata.contains_key(ScanResult::FAILED_SCANNERS)
+        {
+            return;
+        }
+
+        let Ok(mut entries) = self.entries.lock() else {
+            return;
+        };
+
+        if entries.len() >= self.capacity {
+            entries.retain(|_, entry| entry.inserted_at.elapsed() < self.ttl);
+        }
+        if entries.len() >= self.capacity {
+            // Still full of live entries: evict the oldest
+            if let Some(oldest) = entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.inserted_at)
+                .map(|(key, _)| key.clone())
+            {
+                entries.remove(&oldest);
+            }
+        }
+
+        entries.insert(
+            (set_id, scan_type.to_string(), input.to_string()),
+            CacheEntry {
+                inserted_at: Instant::now(),
+                result: result.clone(),
+            },
+        );
+    }
+
+    /// Remove all entries
+    pub(crate) fn clear(&self) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.clear();
+        }
+    }
+
+    /// Number of entries (including expired ones not yet evicted)
+    #[cfg(test)]
+    pub(crate) fn len(&self) -> usize {
+        self.entries.lock().map(|e| e.len()).unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_hit_and_scan_type() {
+        let cache = ResultCache::new(Duration::from_secs(60), 10);
+        cache.insert(1, "prompt", "hello", &ScanResult::pass("hello".to_string()));
+
+        assert!(cache.get(1, "prompt", "hello").is_some());
+        assert!(cache.get(1, "output", "hello").is_none());
+        assert!(cache.get(1, "prompt", "other").is_none());
+        // Results from another scanner set are never served
+        assert!(cache.get(2, "prompt", "hello").is_none());
+    }
+
+    #[test]
+    fn test_cache_expiry() {
+        let cache = ResultCache::new(Duration::ZERO, 10);
+        cache.insert(1, "prompt", "hello", &ScanResult::pass("hello".to_string()));
+        assert!(cache.get(1, "prompt", "hello").is_none());
+        assert_eq!(cache.len(), 0);
+    }
+
+    #[test]
+    fn test_cache_evicts_oldest_when_full() {
+        let cache = ResultCache::new(Duration::from_secs(60), 2);
+        for input in ["a", "b", "c"] {
+            cache.insert(1, "prompt", input, &ScanResult::pass(input.to_string()));
+        }
+
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get(1, "prompt", "c").is_some());
+    }
+
+    #[test]
+    fn test_cache_skips_degraded_results() {
+        let cache = ResultCache::new(Duration::from_secs(60), 10);
+        let result = ScanResult::pass("hello".to_string())
+            .with_metadata(ScanResult::TIMED_OUT_SCANNERS, vec!["slow"]);
+        cache.insert(1, "prompt", "hello", &result);
+        assert_eq!(cache.len(), 0);
+    }
+}
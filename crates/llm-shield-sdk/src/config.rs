@@ -8,7 +8,9 @@
 //! - Easy customization through builder pattern
 //! - Serializable for configuration files
 
+use llm_shield_core::{ErrorPolicy, TimeoutPolicy};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 /// Main SDK configuration
 ///
@@ -34,9 +36,36 @@ pub struct ShieldConfig {
     /// Short-circuit threshold (stop scanning if risk exceeds this)
     pub short_circuit_threshold: Option<f32>,
 
+    /// Feed each scanner the previous scanner's sanitized text
+    #[serde(default)]
+    pub chain_sanitization: bool,
+
+    /// Run cheap scanners first (by their performance characteristics)
+    #[serde(default)]
+    pub order_by_cost: bool,
+
+    /// What to do when a scanner returns an error
+    #[serde(default)]
+    pub error_policy: ErrorPolicy,
+
     /// Operation timeout in milliseconds
+    ///
+    /// Enforced as a deadline for the whole scan; scanners still running
+    /// when it passes are cancelled.
     pub timeout_ms: Option<u64>,
 
+    /// Default per-scanner timeout in milliseconds
+    #[serde(default)]
+    pub scanner_timeout_ms: Option<u64>,
+
+    /// How timed-out scanners are reported (fail-open or fail-closed)
+    #[serde(default)]
+    pub timeout_policy: TimeoutPolicy,
+
+    /// Per-scanner timeout overrides, keyed by scanner name
+    #[serde(default)]
+    pub scanner_timeouts: HashMap<String, ScannerTimeout>,
+
     /// Enable tracing/observability
     pub enable_tracing: bool,
 
@@ -56,7 +85,13 @@ impl Default for ShieldConfig {
             scan_mode: ScanMode::Both,
             parallel: ParallelConfig::default(),
             short_circuit_threshold: None,
+            chain_sanitization: false,
+            order_by_cost: false,
+            error_policy: ErrorPolicy::Propagate,
             timeout_ms: Some(30_000), // 30 seconds default
+            scanner_timeout_ms: None,
+            timeout_policy: TimeoutPolicy::FailClosed,
+            scanner_timeouts: HashMap::new(),
             enable_tracing: true,
             enable_caching: false,
             cache_ttl_seconds: 300, // 5 minutes
@@ -80,7 +115,13 @@ impl ShieldConfig {
                 max_concurrent: 1,
             },
             short_circuit_threshold: None,
+            chain_sanitization: false,
+            order_by_cost: false,
+            error_policy: ErrorPolicy::Propagate,
             timeout_ms: Some(60_000), // 1 minute
+            scanner_timeout_ms: None,
+            timeout_policy: TimeoutPolicy::FailClosed,
+            scanner_timeouts: HashMap::new(),
             enable_tracing: true,
             enable_caching: false,
             cache_ttl_seconds: 0,
@@ -97,7 +138,13 @@ impl ShieldConfig {
                 max_concurrent: 8,
             },
             short_circuit_threshold: Some(0.95), // Short-circuit on critical risks
+            chain_sanitization: false,
+            order_by_cost: true,
+            error_policy: ErrorPolicy::Propagate,
             timeout_ms: Some(10_000), // 10 seconds
+            scanner_timeout_ms: None,
+            timeout_policy: TimeoutPolicy::FailClosed,
+            scanner_timeouts: HashMap::new(),
             enable_tracing: true,
             enable_caching: true,
             cache_ttl_seconds: 300,
@@ -114,7 +161,13 @@ impl ShieldConfig {
                 max_concurrent: 16,
             },
             short_circuit_threshold: Some(0.9),
+            chain_sanitization: false,
+            order_by_cost: true,
+            error_policy: ErrorPolicy::Propagate,
             timeout_ms: Some(5_000), // 5 seconds
+            scanner_timeout_ms: None,
+            timeout_policy: TimeoutPolicy::FailClosed,
+            scanner_timeouts: HashMap::new(),
             enable_tracing: false, // Disable for performance
             enable_caching: true,
             cache_ttl_seconds: 600,
@@ -123,6 +176,18 @@ impl ShieldConfig {
     }
 }
 
+/// Timeout override for a single scanner
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScannerTimeout {
+    /// Timeout in milliseconds (falls back to `scanner_timeout_ms`)
+    #[serde(default)]
+    pub timeout_ms: Option<u64>,
+
+    /// Policy on timeout (falls back to `timeout_policy`)
+    #[serde(default)]
+    pub policy: Option<TimeoutPolicy>,
+}
+
 /// Scan mode configuration
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -204,6 +269,28 @@ mod tests {
         assert!(config.short_circuit_threshold.is_some());
     }
 
+    #[test]
+    fn test_timeout_fields_default_when_missing() {
+        let json = r#"{
+            "scan_mode": "both",
+            "parallel": {"enabled": true, "max_concurrent": 4},
+            "short_circuit_threshold": null,
+            "timeout_ms": 1000,
+            "enable_tracing": false,
+            "enable_caching": false,
+            "cache_ttl_seconds": 60,
+            "max_batch_size": 10,
+            "scanner_timeouts": {"url_reachability": {"policy": "fail_open"}}
+        }"#;
+        let config: ShieldConfig = serde_json::from_str(json).unwrap();
+        assert_eq!(config.scanner_timeout_ms, None);
+        assert_eq!(config.timeout_policy, TimeoutPolicy::FailClosed);
+        assert_eq!(
+            config.scanner_timeouts["url_reachability"].policy,
+            Some(TimeoutPolicy::FailOpen)
+        );
+    }
+
     #[test]
     fn test_high_throughput_config() {
         let config = ShieldConfig::high_throughput();
@@ -0,0 +1,194 @@
+//! # Scan Context
+//!
+//! Per-request state for a single scan.
+//!
+//! A `Shield` is long-lived and shared across requests, so request state
+//! (anonymization mappings, cross-scanner notes, caller identity) must not
+//! live in the Shield's own vault. Each scan runs against a [`ScanContext`]
+//! instead: its vault is seeded from the Shield's configuration vault and is
+//! dropped with the context.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! let ctx = ScanContext::new()
+//!     .with_tenant("tenant-a")
+//!     .with_user("user-42")
+//!     .with_attribute("channel", "chat");
+//!
+//! let result = shield.scan_prompt_with_context("Hello", &ctx).await?;
+//!
+//! // Request-scoped state written by scanners stays in the context
+//! let mappings = ctx.vault().keys()?;
+//! ```
+
+use llm_shield_core::Vault;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Request-scoped context for a scan
+#[derive(Clone)]
+pub struct ScanContext {
+    vault: Vault,
+    request_id: String,
+    tenant_id: Option<String>,
+    user_id: Option<String>,
+    attributes: HashMap<String, serde_json::Value>,
+}
+
+/// Identity and attributes of a scan, as seen by scanners
+///
+/// Stored in the request vault under [`ScanContext::VAULT_KEY`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScanContextInfo {
+    pub request_id: String,
+    pub tenant_id: Option<String>,
+    pub user_id: Option<String>,
+    #[serde(default)]
+    pub attributes: HashMap<String, serde_json::Value>,
+}
+
+impl ScanContext {
+    /// Vault key under which the context's [`ScanContextInfo`] is stored
+    pub const VAULT_KEY: &'static str = "scan_context";
+
+    /// Create a context with a fresh vault and a generated request id
+    pub fn new() -> Self {
+        Self {
+            vault: Vault::new(),
+            request_id: generate_request_id(),
+            tenant_id: None,
+            user_id: None,
+            attributes: HashMap::new(),
+        }
+    }
+
+    /// Use a caller-supplied request id (e.g. from an `x-request-id` header)
+    pub fn with_request_id(mut self, request_id: impl Into<String>) -> Self {
+        self.request_id = request_id.into();
+        self
+    }
+
+    /// Set the tenant
+    pub fn with_tenant(mut self, tenant_id: impl Into<String>) -> Self {
+        self.tenant_id = Some(tenant_id.into());
+        self
+    }
+
+    /// Set the user
+    pub fn with_user(mut self, user_id: impl Into<String>) -> Self {
+        self.user_id = Some(user_id.into());
+        self
+    }
+
+    /// Add an attribute
+    pub fn with_attribute(mut self, key: impl Into<String>, value: impl Serialize) -> Self {
+        if let Ok(v) = serde_json::to_value(value) {
+            self.attributes.insert(key.into(), v);
+        }
+        self
+    }
+
+    /// Use an existing vault, e.g. to share anonymization state between the
+    /// prompt and output scans of one conversation turn
+    pub fn with_vault(mut self, vault: Vault) -> Self {
+        self.vault = vault;
+        self
+    }
+
+    /// Request-scoped vault passed to scanners and hooks
+    pub fn vault(&self) -> &Vault {
+        &self.vault
+    }
+
+    /// Request id
+    pub fn request_id(&self) -> &str {
+        &self.request_id
+    }
+
+    /// Tenant id, if set
+    pub fn tenant_id(&self) -> Option<&str> {
+        self.tenant_id.as_deref()
+    }
+
+    /// User id, if set
+    pub fn user_id(&self) -> Option<&str> {
+        self.user_id.as_deref()
+    }
+
+    /// Attributes
+    pub fn attributes(&self) -> &HashMap<String, serde_json::Value> {
+        &self.attributes
+    }
+
+    /// Snapshot of identity and attributes
+    pub fn info(&self) -> ScanContextInfo {
+        ScanContextInfo {
+            request_id: self.request_id.clone(),
+            tenant_id: self.tenant_id.clone(),
+            user_id: self.user_id.clone(),
+            attributes: self.attributes.clone(),
+        }
+    }
+}
+
+impl Default for ScanContext {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for ScanContext {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ScanContext")
+            .field("request_id", &self.request_id)
+            .field("tenant_id", &self.tenant_id)
+            .field("user_id", &self.user_id)
+            .field("attributes", &self.attributes)
+            .field("vault_entries", &self.vault.len())
+            .finish()
+    }
+}
+
+/// Process-unique request id: start time plus a counter
+fn generate_request_id() -> String {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or(0);
+    let seq = COUNTER.fetch_add(1, Ordering::Relaxed);
+    format!("req-{:x}-{:x}", nanos, seq)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_context_builder() {
+        let ctx = ScanContext::new()
+            .with_request_id("req-1")
+            .with_tenant("tenant-a")
+            .with_user("user-42")
+            .with_attribute("channel", "chat");
+
+        assert_eq!(ctx.request_id(), "req-1");
+        assert_eq!(ctx.tenant_id(), Some("tenant-a"));
+        assert_eq!(ctx.user_id(), Some("user-42"));
+        assert_eq!(ctx.info().attributes["channel"], "chat");
+    }
+
+    #[test]
+    fn test_contexts_are_isolated() {
+        let a = ScanContext::new();
+        let b = ScanContext::new();
+
+        assert_ne!(a.request_id(), b.request_id());
+        a.vault().set("key", "a").unwrap();
+        assert!(!b.vault().contains_key("key"));
+    }
+}
@@ -37,6 +37,6 @@ pub mod config_integration;
 pub mod runtime_hooks;
 
 // Re-export main types
-pub use policy_integration::{PolicyIntegration, PolicyIntegrationBuilder};
+pub use policy_integration::{PolicyIntegration, PolicyIntegrationAdapter, PolicyIntegrationBuilder};
 pub use config_integration::{ConfigIntegration, ConfigIntegrationBuilder};
-pub use runtime_hooks::{RuntimeHooks, ScanHook, HookResult};
+pub use runtime_hooks::{RuntimeHooks, ScanHook, HookResult, HookFallback, PreScanResult};
@@ -222,6 +222,46 @@ pub trait PolicyIntegrationAdapter: Send + Sync {
 
     /// Health check
     async fn health_check(&self) -> bool;
+
+    /// Context for evaluating a scan of the given type ("prompt" or "output")
+    fn scan_context(&self, scan_type: &str) -> PolicyContext {
+        PolicyContext::new(scan_type)
+    }
+}
+
+/// Any `PolicyAdapter` can back a `Shield` (see `ShieldBuilder::with_policy`)
+#[async_trait::async_trait]
+impl<E: PolicyEvaluator + 'static> PolicyIntegrationAdapter for PolicyAdapter<E> {
+    async fn evaluate(
+        &self,
+        context: &PolicyContext,
+        content: &str,
+    ) -> PolicyResult<PolicyDecision> {
+        PolicyAdapter::evaluate(self, context, content).await
+    }
+
+    async fn apply_to_result(
+        &self,
+        context: &PolicyContext,
+        content: &str,
+        scan_result: ScanResult,
+    ) -> Result<ScanResult> {
+        self.apply_policy(context, content, scan_result).await
+    }
+
+    async fn health_check(&self) -> bool {
+        self.is_healthy().await
+    }
+
+    /// The adapter's default context, stamped now and tagged with the scan type
+    fn scan_context(&self, scan_type: &str) -> PolicyContext {
+        let defaults = self.default_context();
+        let mut context = PolicyContext::new(defaults.check_type.clone());
+        context.user_id = defaults.user_id.clone();
+        context.tenant_id = defaults.tenant_id.clone();
+        context.attributes = defaults.attributes.clone();
+        context.with_attribute("scan_type", scan_type)
+    }
 }
 
 /// Default policy adapter using NoOp evaluator
@@ -216,7 +216,7 @@ impl RuntimeHooks {
                 }
                 Ok(HookResult::Error { message, fallback }) => {
                     if !self.continue_on_error {
-                        return Err(Error::scanner(format!("Pre-scan hook failed: {}", message)));
+                        return Err(Error::scanner("runtime_hooks", format!("Pre-scan hook failed: {}", message)));
                     }
                     match fallback {
                         HookFallback::Block => {
@@ -232,7 +232,7 @@ impl RuntimeHooks {
                             break;
                         }
                         HookFallback::Fail => {
-                            return Err(Error::scanner(format!("Pre-scan hook failed: {}", message)));
+                            return Err(Error::scanner("runtime_hooks", format!("Pre-scan hook failed: {}", message)));
                         }
                         HookFallback::Continue => continue,
                     }
@@ -276,7 +276,7 @@ impl RuntimeHooks {
                 }
                 Ok(HookResult::Error { message, fallback }) => {
                     if !self.continue_on_error {
-                        return Err(Error::scanner(format!("Post-scan hook failed: {}", message)));
+                        return Err(Error::scanner("runtime_hooks", format!("Post-scan hook failed: {}", message)));
                     }
                     match fallback {
                         HookFallback::Block => {
@@ -289,7 +289,7 @@ impl RuntimeHooks {
                             break;
                         }
                         HookFallback::Fail => {
-                            return Err(Error::scanner(format!("Post-scan hook failed: {}", message)));
+                            return Err(Error::scanner("runtime_hooks", format!("Post-scan hook failed: {}", message)));
                         }
                         _ => continue,
                     }
@@ -88,6 +88,22 @@
 //! }
 //! ```
 //!
+//! ## Conversations
+//!
+//! Multi-turn chats are scanned message by message, with prior turns
+//! visible to scanners and only new turns rescanned:
+//!
+//! ```rust,ignore
+//! let mut state = ConversationState::new();
+//! let ctx = ScanContext::new();
+//!
+//! conversation.push(Message::user(next_prompt));
+//! let result = shield
+//!     .scan_conversation_incremental(&conversation, &mut state, &ctx)
+//!     .await?;
+//! println!("triggered by messages {:?}", result.triggered);
+//! ```
+//!
 //! ## Available Scanners
 //!
 //! ### Input Scanners (scan prompts before LLM)
@@ -118,9 +134,29 @@
 //! | `Bias` | Detect biased content |
 //! | `MaliciousURLs` | Detect phishing/malware URLs |
 //! | `Factuality` | Assess factual confidence |
+//! | `FactualConsistency` | Check claims against reference documents |
 //! | `ReadingTime` | Validate response length |
 //! | `URLReachability` | Verify URL accessibility |
 //! | `RegexOutput` | Custom output patterns |
+//! | `JsonOutput` | Validate JSON against a schema |
+//!
+//! ## Declarative Configuration
+//!
+//! Pipelines can be described in YAML, TOML or JSON and loaded without
+//! recompiling:
+//!
+//! ```rust,ignore
+//! // shield.yaml:
+//! //   settings:
+//! //     short_circuit_threshold: 0.9
+//! //   input:
+//! //     - type: secrets
+//! //     - type: token_limit
+//! //       params: { limit: 2048 }
+//! //   output:
+//! //     - type: sensitive
+//! let shield = ShieldBuilder::from_file("shield.yaml")?.build()?;
+//! ```
 //!
 //! ## Enterprise Features
 //!
@@ -141,7 +177,9 @@
 //! - `cloud-azure`: Azure integration
 
 pub mod builder;
+mod cache;
 pub mod config;
+pub mod context;
 pub mod error;
 pub mod prelude;
 pub mod preset;
@@ -151,7 +189,8 @@ pub mod integrations;
 
 // Re-export main types for convenience
 pub use builder::ShieldBuilder;
-pub use config::{ShieldConfig, ScanMode, ParallelConfig};
+pub use config::{ShieldConfig, ScanMode, ParallelConfig, ScannerTimeout};
+pub use context::{ScanContext, ScanContextInfo};
 pub use error::{SdkError, SdkResult};
 pub use preset::Preset;
 pub use shield::Shield;
@@ -166,7 +205,9 @@ pub use integrations::{
 // Re-export core types
 pub use llm_shield_core::{
     Entity, Error as CoreError, Result as CoreResult, RiskFactor, ScanResult, Scanner,
-    ScannerType, Severity, Vault, ScannerPipeline,
+    ScannerType, Severity, Vault, ScannerPipeline, TimeoutPolicy, ErrorPolicy,
+    Conversation, ConversationScanResult, ConversationState, Message, MessageScanResult, Role,
+    ReloadStatus, WatchHandle,
 };
 
 // Re-export core adapter types for upstream integration (Phase 2B)
@@ -201,11 +242,16 @@ pub use llm_shield_scanners::output::{
     Bias, BiasConfig,
     MaliciousURLs, MaliciousURLsConfig,
     Factuality, FactualityConfig,
+    FactualConsistency, FactualConsistencyConfig, NliPredictor,
     ReadingTime, ReadingTimeConfig,
     URLReachability, URLReachabilityConfig,
     RegexOutput, RegexOutputConfig,
+    JsonOutput, JsonOutputConfig,
 };
 
+// Re-export declarative pipeline configuration
+pub use llm_shield_scanners::{PipelineConfig, PipelineSettings, ScannerSpec};
+
 /// Library version
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 
@@ -26,6 +26,7 @@ pub use crate::shield::Shield;
 pub use crate::builder::ShieldBuilder;
 pub use crate::preset::Preset;
 pub use crate::config::{ShieldConfig, ScanMode, ParallelConfig};
+pub use crate::context::ScanContext;
 pub use crate::error::{SdkError, SdkResult};
 
 // ============================================================================
@@ -45,6 +46,15 @@ pub use llm_shield_core::{
     OutputScanner,
     ScannerType,
     ScannerPipeline,
+    TimeoutPolicy,
+    ErrorPolicy,
+
+    // Conversations
+    Conversation,
+    ConversationState,
+    ConversationScanResult,
+    Message,
+    Role,
 
     // State management
     Vault,
@@ -155,6 +165,11 @@ pub use llm_shield_scanners::output::{
     Factuality,
     FactualityConfig,
 
+    // Factual Consistency (NLI)
+    FactualConsistency,
+    FactualConsistencyConfig,
+    NliPredictor,
+
     // Reading Time Validation
     ReadingTime,
     ReadingTimeConfig,
@@ -166,8 +181,18 @@ pub use llm_shield_scanners::output::{
     // Regex Output Checking
     RegexOutput,
     RegexOutputConfig,
+
+    // JSON Validation
+    JsonOutput,
+    JsonOutputConfig,
 };
 
+// ============================================================================
+// Declarative Configuration
+// ============================================================================
+
+pub use llm_shield_scanners::{PipelineConfig, PipelineSettings, ScannerSpec};
+
 // ============================================================================
 // Async Runtime Support
 // ============================================================================
@@ -10,7 +10,9 @@
 //! - **Custom**: Build your own configuration
 
 use crate::config::{ParallelConfig, ShieldConfig, ScanMode};
+use llm_shield_core::{ErrorPolicy, TimeoutPolicy};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 /// Security preset levels
 ///
@@ -74,7 +76,13 @@ impl Preset {
                 scan_mode: ScanMode::Both,
                 parallel: ParallelConfig::disabled(), // Deterministic
                 short_circuit_threshold: Some(0.7), // Low tolerance
+                chain_sanitization: false,
+                order_by_cost: true,
+                error_policy: ErrorPolicy::FailClosed,
                 timeout_ms: Some(60_000), // 1 minute
+                scanner_timeout_ms: None,
+                timeout_policy: TimeoutPolicy::FailClosed,
+                scanner_timeouts: HashMap::new(),
                 enable_tracing: true,
                 enable_caching: false, // No caching for strict mode
                 cache_ttl_seconds: 0,
@@ -84,7 +92,13 @@ impl Preset {
                 scan_mode: ScanMode::Both,
                 parallel: ParallelConfig::with_concurrency(4),
                 short_circuit_threshold: Some(0.9),
+                chain_sanitization: false,
+                order_by_cost: true,
+                error_policy: ErrorPolicy::Propagate,
                 timeout_ms: Some(30_000),
+                scanner_timeout_ms: None,
+                timeout_policy: TimeoutPolicy::FailClosed,
+                scanner_timeouts: HashMap::new(),
                 enable_tracing: true,
                 enable_caching: true,
                 cache_ttl_seconds: 300,
@@ -94,7 +108,13 @@ impl Preset {
                 scan_mode: ScanMode::Both,
                 parallel: ParallelConfig::with_concurrency(8),
                 short_circuit_threshold: None, // No short-circuit
+                chain_sanitization: false,
+                order_by_cost: true,
+                error_policy: ErrorPolicy::FailOpen,
                 timeout_ms: Some(10_000),
+                scanner_timeout_ms: None,
+                timeout_policy: TimeoutPolicy::FailOpen,
+                scanner_timeouts: HashMap::new(),
                 enable_tracing: false,
                 enable_caching: true,
                 cache_ttl_seconds: 600,
@@ -201,6 +221,7 @@ pub enum OutputScannerType {
     ReadingTime,
     URLReachability,
     RegexOutput,
+    Json,
 }
 
 #[cfg(test)]
@@ -252,6 +273,8 @@ mod tests {
 
         assert!(config.parallel.enabled);
         assert!(config.short_circuit_threshold.is_none());
+        assert_eq!(config.timeout_policy, TimeoutPolicy::FailOpen);
+        assert_eq!(config.error_policy, ErrorPolicy::FailOpen);
 
         let input_scanners = preset.input_scanners();
         assert!(input_scanners.len() <= 3);
@@ -19,7 +19,7 @@ use llm_shield_scanners::input::{
     TokenLimitConfig, Toxicity, ToxicityConfig,
 };
 use llm_shield_scanners::output::{
-    BanTopics, BanTopicsConfig, Bias, Factuality, MaliciousURLs,
+    BanTopics, BanTopicsConfig, Bias, Factuality, JsonOutput, JsonOutputConfig, MaliciousURLs,
     NoRefusal, ReadingTime, ReadingTimeConfig, RegexOutput,
     RegexOutputConfig, Relevance, Sensitive, SensitiveConfig, URLReachability,
 };
@@ -309,6 +309,11 @@ impl OutputScannerFactory {
                     "RegexOutput requires explicit configuration with patterns",
                 ))
             }
+            OutputScannerType::Json => {
+                let scanner = JsonOutput::default_config()
+                    .map_err(|e| SdkError::scanner_init("JSON", e.to_string()))?;
+                Ok(Arc::new(scanner))
+            }
         }
     }
 
@@ -348,11 +353,7 @@ impl OutputScannerFactory {
             .into_iter()
             .map(|t| {
                 let name: String = t.into();
-                BannedTopic {
-                    name: name.clone(),
-                    keywords: vec![name.clone()],
-                    severity: Severity::Medium,
-                }
+                BannedTopic::new(name.clone(), [name], Severity::Medium)
             })
             .collect();
         let config = BanTopicsConfig {
@@ -426,6 +427,27 @@ impl OutputScannerFactory {
             .map_err(|e| SdkError::scanner_init("RegexOutput", e.to_string()))?;
         Ok(Arc::new(scanner))
     }
+
+    /// Create JSON scanner with default configuration
+    pub fn json() -> SdkResult<Arc<dyn Scanner>> {
+        Self::create(OutputScannerType::Json)
+    }
+
+    /// Create JSON scanner requiring at least one value that matches `schema`
+    pub fn json_with_schema(schema: serde_json::Value) -> SdkResult<Arc<dyn Scanner>> {
+        Self::json_with_config(JsonOutputConfig {
+            schema: Some(schema),
+            required_elements: 1,
+            ..Default::default()
+        })
+    }
+
+    /// Create JSON scanner with configuration
+    pub fn json_with_config(config: JsonOutputConfig) -> SdkResult<Arc<dyn Scanner>> {
+        let scanner = JsonOutput::new(config)
+            .map_err(|e| SdkError::scanner_init("JSON", e.to_string()))?;
+        Ok(Arc::new(scanner))
+    }
 }
 
 #[cfg(test)]
@@ -463,6 +485,13 @@ mod tests {
         assert!(scanner.is_ok());
     }
 
+    #[test]
+    fn test_output_scanner_factory_json() {
+        assert!(OutputScannerFactory::json().is_ok());
+        assert!(OutputScannerFactory::json_with_schema(serde_json::json!({"type": "object"})).is_ok());
+        assert!(OutputScannerFactory::json_with_schema(serde_json::json!({"type": 1})).is_err());
+    }
+
     #[test]
     fn test_output_scanner_factory_requires_config() {
         // BanTopics without config should fail
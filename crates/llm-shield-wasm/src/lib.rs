@@ -8,6 +8,10 @@
 //! - **Type Safety**: Full type conversion between Rust and JavaScript
 //! - **Async Support**: Proper async/await support
 //!
+//! Declarative pipeline files (`PipelineConfig`) are not supported: they are
+//! defined and built by `llm-shield-scanners`, which does not compile to
+//! WebAssembly (see `Cargo.toml`).
+//!
 //! ## Example Usage (JavaScript)
 //!
 //! ```javascript